@@ -0,0 +1,91 @@
+#
+# OpenFlight Windows Installer
+# Installs Python dependencies, builds the UI and registers the OpenFlight
+# Windows service. Run from an administrator PowerShell in the repository:
+#
+#   powershell -ExecutionPolicy Bypass -File scripts\install_windows.ps1 -Port COM3
+#   powershell -ExecutionPolicy Bypass -File scripts\install_windows.ps1 -Mock
+#
+# Extra openflight-server arguments go after -ServerArgs, e.g.
+#   ... -Port COM3 -ServerArgs "--webhook-url","http://localhost:8000/shot"
+#
+
+param(
+    [string]$Port,
+    [switch]$Mock,
+    [string[]]$ServerArgs = @(),
+    [switch]$NoStart
+)
+
+$ErrorActionPreference = "Stop"
+$ProjectDir = Split-Path -Parent $PSScriptRoot
+Set-Location $ProjectDir
+
+function Log($Message) { Write-Host "[OpenFlight] $Message" -ForegroundColor Green }
+function Warn($Message) { Write-Host "[OpenFlight] $Message" -ForegroundColor Yellow }
+function Fail($Message) { Write-Host "[OpenFlight] $Message" -ForegroundColor Red; exit 1 }
+
+$Principal = New-Object Security.Principal.WindowsPrincipal(
+    [Security.Principal.WindowsIdentity]::GetCurrent())
+if (-not $Principal.IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)) {
+    Fail "Run this script from an administrator PowerShell (services need it)"
+}
+
+if (-not $Port -and -not $Mock) {
+    Fail "Give the radar's COM port (-Port COM3, see Device Manager) or -Mock"
+}
+
+# Install uv if not present
+if (-not (Get-Command uv -ErrorAction SilentlyContinue)) {
+    Log "Installing uv (fast Python package manager)..."
+    Invoke-RestMethod https://astral.sh/uv/install.ps1 | Invoke-Expression
+    $env:Path = "$env:USERPROFILE\.local\bin;$env:Path"
+}
+
+# The service runs as LocalSystem, so the venv needs its own Python rather
+# than one under the current user's profile
+Log "Creating Python virtual environment..."
+uv python install 3.12
+uv venv .venv --python 3.12 --allow-existing
+$Python = Join-Path $ProjectDir ".venv\Scripts\python.exe"
+
+Log "Installing Python dependencies..."
+uv pip install --python $Python -e ".[ui,windows]"
+# pywin32 copies its DLLs next to the interpreter so pythonservice.exe can load them
+& $Python -m pywin32_postinstall -install -silent
+Log "Python dependencies installed"
+
+if (Get-Command npm -ErrorAction SilentlyContinue) {
+    Log "Building UI..."
+    Push-Location ui
+    npm install
+    npm run build
+    Pop-Location
+    Log "UI built"
+} else {
+    Warn "npm not found - skipping the UI build (install Node.js 18+ and rerun for the web UI)"
+}
+
+$Launch = @()
+if ($Mock) { $Launch += "--mock" } else { $Launch += @("--port", $Port) }
+$Launch += $ServerArgs
+
+$Service = Join-Path $ProjectDir ".venv\Scripts\openflight-service.exe"
+if (Get-Service OpenFlight -ErrorAction SilentlyContinue) {
+    Log "Updating the OpenFlight service..."
+    & $Service stop 2>$null
+    & $Service --startup auto update '--' @Launch
+} else {
+    Log "Installing the OpenFlight service..."
+    & $Service --startup auto install '--' @Launch
+}
+if ($LASTEXITCODE -ne 0) { Fail "Could not register the service" }
+
+if (-not $NoStart) {
+    & $Service start
+    Log "Service started - the UI is at http://localhost:8080"
+}
+
+Write-Host ""
+Log "Control it with openflight-service start|stop|pause|continue or services.msc"
+Log "Server output is in Event Viewer > Windows Logs > Application (source OpenFlight)"
@@ -146,9 +146,9 @@ log "Python dependencies installed ✓"
 # Install dev dependencies
 log "Installing development dependencies..."
 if command -v uv &> /dev/null; then
-    uv pip install pytest ruff pylint
+    uv pip install pytest hypothesis ruff pylint
 else
-    pip install pytest ruff pylint
+    pip install pytest hypothesis ruff pylint
 fi
 log "Dev dependencies installed ✓"
 
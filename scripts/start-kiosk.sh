@@ -17,6 +17,8 @@ NO_CAMERA=false  # Camera auto-enabled by default (uses Hough + ByteTrack)
 MODE="rolling-buffer"  # Default: rolling buffer mode (requires one-time radar setup)
 TRIGGER="sound"  # Default: hardware sound trigger (SEN-14262 → HOST_INT)
 SOUND_PRE_TRIGGER=""
+SUPERVISE=true  # Restart the server if it crashes, hangs or the radar wedges
+CONTROL_SOCKET="${XDG_RUNTIME_DIR:-/tmp}/openflight.sock"  # openflight-ctl talks to the server here
 
 # Parse arguments
 while [[ $# -gt 0 ]]; do
@@ -37,6 +39,10 @@ while [[ $# -gt 0 ]]; do
             NO_CAMERA=true
             shift
             ;;
+        --no-supervise)
+            SUPERVISE=false
+            shift
+            ;;
         --mode)
             MODE="$2"
             shift 2
@@ -84,6 +90,12 @@ error() {
 cleanup() {
     log "Shutting down..."
     if [ -n "$SERVER_PID" ]; then
+        # Ask the server to stop cleanly (closes the session log); kill it if it doesn't
+        openflight-ctl --socket "$CONTROL_SOCKET" shutdown > /dev/null 2>&1 || true
+        for i in {1..10}; do
+            kill -0 $SERVER_PID 2>/dev/null || break
+            sleep 0.5
+        done
         kill $SERVER_PID 2>/dev/null || true
     fi
     if [ -n "$BROWSER_PID" ]; then
@@ -115,7 +127,7 @@ if [ ! -d "ui/dist" ]; then
 fi
 
 # Build server command
-SERVER_CMD="openflight-server --web-port $PORT"
+SERVER_CMD="openflight-server --web-port $PORT --control-socket $CONTROL_SOCKET"
 
 if [ "$MOCK_MODE" = true ]; then
     SERVER_CMD="$SERVER_CMD --mock"
@@ -133,6 +145,10 @@ if [ "$NO_CAMERA" = true ]; then
     SERVER_CMD="$SERVER_CMD --no-camera"
 fi
 
+if [ "$SUPERVISE" = true ]; then
+    SERVER_CMD="$SERVER_CMD --supervise"
+fi
+
 if [ -n "$MODE" ]; then
     SERVER_CMD="$SERVER_CMD --mode $MODE"
 fi
@@ -0,0 +1,130 @@
+"""
+Access control for the OpenFlight server's network interfaces.
+
+A monitor on a shared network (home LAN, garage guest Wi-Fi) should not
+let every device on it change thresholds or inject shots. Two optional,
+independent checks are provided:
+
+- Address allowlist: only clients whose IP falls in one of the configured
+  networks may connect at all (HTTP and WebSocket). Loopback is always
+  allowed so the kiosk browser on the Pi keeps working.
+- API token: requests to /api must carry the shared token as
+  "Authorization: Bearer <token>" or "X-OpenFlight-Token: <token>", and
+  Socket.IO clients must send it when connecting ({"token": ...} in the
+  auth payload, or the same headers).
+"""
+
+import hmac
+import ipaddress
+import logging
+from typing import Iterable, List, Optional, Union
+
+logger = logging.getLogger(__name__)
+
+TOKEN_HEADER = "X-OpenFlight-Token"
+
+IPNetwork = Union[ipaddress.IPv4Network, ipaddress.IPv6Network]
+
+
+def parse_networks(specs: Iterable[str]) -> List[IPNetwork]:
+    """
+    Parse allowlist entries into networks.
+
+    Args:
+        specs: IP addresses or CIDR ranges, e.g. "192.168.1.20", "10.0.0.0/24"
+
+    Returns:
+        List of networks (single addresses become /32 or /128)
+
+    Raises:
+        ValueError: If an entry is not a valid address or network
+    """
+    return [ipaddress.ip_network(spec.strip(), strict=False) for spec in specs]
+
+
+class AccessPolicy:
+    """
+    Decides whether a client may use the server.
+
+    With no token and no allowlist, everything is permitted (the default,
+    matching previous behaviour).
+    """
+
+    def __init__(self, token: Optional[str] = None, allowed: Optional[Iterable[str]] = None):
+        """
+        Initialize access policy.
+
+        Args:
+            token: Shared API token. None disables token checks.
+            allowed: Allowed client addresses/networks. None or empty allows all.
+        """
+        self.token = token or None
+        self.networks = parse_networks(allowed or [])
+
+    @property
+    def enabled(self) -> bool:
+        """True if any restriction is configured."""
+        return bool(self.token or self.networks)
+
+    @staticmethod
+    def is_loopback(address: Optional[str]) -> bool:
+        """True if the address is localhost (IPv4, IPv6 or IPv4-mapped)."""
+        try:
+            ip = ipaddress.ip_address(address or "")
+        except ValueError:
+            return False
+        if isinstance(ip, ipaddress.IPv6Address) and ip.ipv4_mapped:
+            ip = ip.ipv4_mapped
+        return ip.is_loopback
+
+    def is_address_allowed(self, address: Optional[str]) -> bool:
+        """
+        Check a client address against the allowlist.
+
+        Args:
+            address: Client IP address (e.g. request.remote_addr)
+
+        Returns:
+            True if the address may connect
+        """
+        if not self.networks:
+            return True
+        if not address:
+            return False
+
+        try:
+            ip = ipaddress.ip_address(address)
+        except ValueError:
+            return False
+
+        # IPv4 clients on a dual-stack socket show up as ::ffff:a.b.c.d
+        if isinstance(ip, ipaddress.IPv6Address) and ip.ipv4_mapped:
+            ip = ip.ipv4_mapped
+
+        if self.is_loopback(str(ip)):
+            return True
+        return any(ip in network for network in self.networks)
+
+    def is_token_valid(
+        self, authorization: Optional[str] = None, token_header: Optional[str] = None
+    ) -> bool:
+        """
+        Check the API token from request headers.
+
+        Args:
+            authorization: Value of the Authorization header
+            token_header: Value of the X-OpenFlight-Token header
+
+        Returns:
+            True if no token is configured or a matching token was supplied
+        """
+        if not self.token:
+            return True
+
+        supplied = token_header
+        if not supplied and authorization and authorization.lower().startswith("bearer "):
+            supplied = authorization[len("bearer ") :].strip()
+        if not supplied:
+            return False
+
+        return hmac.compare_digest(supplied.encode("utf-8"), self.token.encode("utf-8"))
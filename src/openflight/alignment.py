@@ -0,0 +1,309 @@
+"""
+Radar alignment assist.
+
+A radar aimed a few degrees off the target line reads weaker signal on
+every shot and, on the IWR6843, puts a bias into every horizontal angle;
+it usually shows up only after a session of shots all "pushed" the same
+way. Alignment mode checks the aim before hitting: roll a ball slowly
+along the target line, away from the radar, and watch the indicator:
+
+    openflight-align --port /dev/ttyACM0
+    openflight-align --radar iwr6843 --port /dev/ttyUSB0 --data-port /dev/ttyUSB1
+
+    L [-------------|---*---------] R   +3.2°   signal [#########.....]  24 dB
+
+While the ball rolls, the live line shows where it is relative to
+boresight and the signal strength. When it stops, the roll is summed up:
+on the IWR6843 the ball's path is fitted in the sensor's own coordinates,
+and the path's angle to boresight is how far the radar is turned away
+from the target line (independent of where beside the line it sits):
+
+    Roll 3: path +3.2° right of boresight - turn the radar 3.2° right
+
+The OPS243 measures no angle, so it gets the signal strength only: pan
+the radar between rolls and keep the heading with the strongest peak.
+"""
+
+import argparse
+import math
+import statistics
+import sys
+import time
+from dataclasses import dataclass
+from typing import List, Optional, Tuple
+
+from .frames import Direction, PointCloud, SpeedReading
+from .units import MetersPerSecond, to_mph
+
+MIN_ROLL_MPH = 0.5  # Slower than this is the ball settling, not rolling
+MAX_ROLL_MPH = 15.0  # Faster is a swing or a person, not a rolled ball
+ROLL_GAP_SEC = 1.0  # A roll ends after this long without the ball
+MIN_FIT_POINTS = 5
+MIN_FIT_LENGTH_M = 0.5  # Path needed to fit its direction
+ALIGNED_DEG = 1.0  # Within this, the radar counts as aligned
+
+
+@dataclass
+class AlignmentSample:
+    """Where the rolling ball was seen (position only on radars that measure it)."""
+
+    timestamp: float
+    magnitude: Optional[float]
+    azimuth_deg: Optional[float] = None
+    x: Optional[float] = None  # Meters right of boresight
+    y: Optional[float] = None  # Meters along boresight
+
+
+@dataclass
+class RollResult:
+    """
+    Summary of one roll.
+
+    Attributes:
+        samples: Number of sightings
+        peak_magnitude: Strongest signal
+        mean_azimuth_deg: Average angle of the ball from boresight (None without angles)
+        path_deg: Direction of the ball's path from boresight, + right
+            (None without enough positions to fit it)
+    """
+
+    samples: int
+    peak_magnitude: Optional[float]
+    mean_azimuth_deg: Optional[float] = None
+    path_deg: Optional[float] = None
+
+    @property
+    def misalignment_deg(self) -> Optional[float]:
+        """How far the radar is turned from the target line (fitted path, else mean angle)."""
+        return self.path_deg if self.path_deg is not None else self.mean_azimuth_deg
+
+
+def fit_path(points: List[Tuple[float, float]]) -> Optional[float]:
+    """
+    Direction of a straight path through (x, y) positions.
+
+    Args:
+        points: (x right of boresight, y along boresight) in meters
+
+    Returns:
+        Angle of the path from boresight in degrees, + right, or None if
+        there are too few points or they span too little distance
+    """
+    if len(points) < MIN_FIT_POINTS:
+        return None
+    ys = [p[1] for p in points]
+    if max(ys) - min(ys) < MIN_FIT_LENGTH_M:
+        return None
+    mean_x = statistics.mean(p[0] for p in points)
+    mean_y = statistics.mean(ys)
+    # Least squares of x on y: the ball moves mostly along y
+    slope = sum((x - mean_x) * (y - mean_y) for x, y in points) / sum(
+        (y - mean_y) ** 2 for y in ys
+    )
+    return math.degrees(math.atan(slope))
+
+
+def advice(result: RollResult) -> str:
+    """What to do about a roll's misalignment."""
+    angle = result.misalignment_deg
+    if angle is None:
+        return "no angle measured - keep the heading with the strongest signal"
+    if abs(angle) <= ALIGNED_DEG:
+        return f"aligned (within {ALIGNED_DEG:g}°)"
+    return f"turn the radar {abs(angle):.1f}° {'right' if angle > 0 else 'left'}"
+
+
+class AlignmentTracker:
+    """
+    Collects sightings of a rolled ball into rolls.
+
+    Feed it every reading (OPS243) or point cloud (IWR6843) and call
+    poll() regularly; a roll is finished once the ball hasn't been seen
+    for ROLL_GAP_SEC.
+
+    Example:
+        tracker = AlignmentTracker()
+        radar.start_streaming(callback=tracker.add_reading)
+        while True:
+            result = tracker.poll()
+            if result:
+                print(advice(result))
+    """
+
+    def __init__(self):
+        """Initialize with no roll in progress."""
+        self.samples: List[AlignmentSample] = []
+        self.last: Optional[AlignmentSample] = None  # Latest sighting, for the live line
+
+    def add_reading(self, reading: SpeedReading):
+        """Add an OPS243 reading if it is a ball rolling away."""
+        if reading.direction != Direction.OUTBOUND:
+            return
+        if not MIN_ROLL_MPH <= float(to_mph(reading.speed, reading.unit)) <= MAX_ROLL_MPH:
+            return
+        timestamp = reading.timestamp if reading.timestamp is not None else time.monotonic()
+        self._add(AlignmentSample(timestamp=timestamp, magnitude=reading.magnitude))
+
+    def add_point_cloud(self, cloud: PointCloud):
+        """Add the strongest point of an IWR6843 frame that is a ball rolling away."""
+        moving = [
+            p
+            for p in cloud.points
+            if p.velocity > 0
+            and MIN_ROLL_MPH <= float(MetersPerSecond(p.velocity).to_mph()) <= MAX_ROLL_MPH
+        ]
+        if not moving:
+            return
+        point = max(moving, key=lambda p: p.snr_db if p.snr_db is not None else -math.inf)
+        timestamp = cloud.timestamp if cloud.timestamp is not None else time.monotonic()
+        self._add(
+            AlignmentSample(
+                timestamp=timestamp,
+                magnitude=point.snr_db,
+                azimuth_deg=point.azimuth_deg,
+                x=point.x,
+                y=point.y,
+            )
+        )
+
+    def _add(self, sample: AlignmentSample):
+        self.samples.append(sample)
+        self.last = sample
+
+    def poll(self, now: Optional[float] = None) -> Optional[RollResult]:
+        """
+        Finish the roll in progress if the ball is gone.
+
+        Args:
+            now: Current time (default: time.monotonic())
+
+        Returns:
+            The finished roll, or None while rolling (or with nothing seen)
+        """
+        now = time.monotonic() if now is None else now
+        if not self.samples or now - self.samples[-1].timestamp < ROLL_GAP_SEC:
+            return None
+        samples, self.samples = self.samples, []
+        return summarize(samples)
+
+
+def summarize(samples: List[AlignmentSample]) -> RollResult:
+    """Sum up a roll's sightings."""
+    magnitudes = [s.magnitude for s in samples if s.magnitude is not None]
+    azimuths = [s.azimuth_deg for s in samples if s.azimuth_deg is not None]
+    positions = [(s.x, s.y) for s in samples if s.x is not None and s.y is not None]
+    return RollResult(
+        samples=len(samples),
+        peak_magnitude=max(magnitudes) if magnitudes else None,
+        mean_azimuth_deg=statistics.mean(azimuths) if azimuths else None,
+        path_deg=fit_path(positions),
+    )
+
+
+def render_indicator(
+    azimuth_deg: Optional[float],
+    magnitude: Optional[float],
+    full_scale: float,
+    span_deg: float = 15.0,
+    width: int = 29,
+) -> str:
+    """
+    One-line alignment indicator.
+
+    Args:
+        azimuth_deg: Ball angle from boresight, + right (None: no angle bar)
+        magnitude: Signal strength
+        full_scale: Magnitude that fills the signal bar
+        span_deg: Angle at either end of the angle bar
+        width: Characters in the angle bar (odd, so boresight is centred)
+
+    Returns:
+        e.g. "L [-------------|---*---------] R   +3.2°   signal [#########.....]"
+    """
+    parts = []
+    if azimuth_deg is not None:
+        bar = ["-"] * width
+        centre = width // 2
+        bar[centre] = "|"
+        clamped = max(-span_deg, min(span_deg, azimuth_deg))
+        bar[centre + round(clamped / span_deg * centre)] = "*"
+        parts.append(f"L [{''.join(bar)}] R  {azimuth_deg:+5.1f}°")
+    if magnitude is not None:
+        filled = max(0, min(14, round(14 * magnitude / full_scale))) if full_scale > 0 else 0
+        parts.append(f"signal [{'#' * filled}{'.' * (14 - filled)}] {magnitude:5.0f}")
+    return "   ".join(parts) if parts else "waiting for a rolling ball"
+
+
+def main():
+    """CLI entry point: live alignment indicator."""
+    parser = argparse.ArgumentParser(
+        description="Aim the radar: roll a ball along the target line, away from the radar"
+    )
+    parser.add_argument(
+        "--radar",
+        choices=["ops243", "iwr6843"],
+        default="ops243",
+        help="Radar backend (only the iwr6843 measures angles)",
+    )
+    parser.add_argument("--port", "-p", help="Serial port (IWR6843: its CLI port)")
+    parser.add_argument("--data-port", help="IWR6843 data port (e.g. /dev/ttyUSB1)")
+    args = parser.parse_args()
+
+    tracker = AlignmentTracker()
+    if args.radar == "iwr6843":
+        # pylint: disable=import-outside-toplevel
+        from .iwr6843 import IWR6843Radar
+
+        radar = IWR6843Radar(cli_port=args.port, data_port=args.data_port)
+        full_scale = 30.0  # dB
+    else:
+        from .ops243 import OPS243Radar  # pylint: disable=import-outside-toplevel
+
+        radar = OPS243Radar(port=args.port)
+        full_scale = 2000.0
+    try:
+        radar.connect()
+        radar.configure_for_golf()
+    except ConnectionError as e:
+        print(f"Error: {e}")
+        return 1
+    if args.radar == "iwr6843":
+        radar.start_streaming(
+            callback=lambda _reading: None, frame_callback=tracker.add_point_cloud
+        )
+    else:
+        radar.set_min_speed_filter(MIN_ROLL_MPH)  # Golf filters drop rolling speeds
+        radar.start_streaming(callback=tracker.add_reading)
+
+    print("Roll a ball slowly along the target line, away from the radar (Ctrl+C to stop)")
+    rolls = 0
+    best: Optional[Tuple[int, float]] = None  # (roll, peak magnitude)
+    try:
+        while True:
+            time.sleep(0.1)
+            last = tracker.last
+            if last and tracker.samples:
+                line = render_indicator(last.azimuth_deg, last.magnitude, full_scale)
+                print(f"\r{line}\033[K", end="", flush=True)
+            result = tracker.poll()
+            if not result:
+                continue
+            rolls += 1
+            peak_magnitude = result.peak_magnitude
+            if peak_magnitude is not None and (best is None or peak_magnitude > best[1]):
+                best = (rolls, peak_magnitude)
+            angle = result.misalignment_deg
+            where = f"path {angle:+.1f}° from boresight - " if angle is not None else ""
+            peak = f", peak signal {result.peak_magnitude:.0f}" if result.peak_magnitude else ""
+            strongest = f" (strongest: roll {best[0]})" if best else ""
+            print(f"\rRoll {rolls}: {where}{advice(result)}{peak}{strongest}\033[K")
+    except KeyboardInterrupt:
+        print()
+    finally:
+        radar.stop_streaming()
+        radar.disconnect()
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,106 @@
+"""
+Ball-present auto-arming.
+
+Some simulators expect "ready" once per ball: the monitor should only say
+it is ready when a ball is sitting on the mat. This state machine turns a
+noisy ball-present signal (camera ball detection) into clean ready /
+not-ready transitions:
+
+    IDLE --ball seen for settle_sec--> ARMED --shot--> IDLE
+                                         |
+                                         +--ball removed--> IDLE
+
+The settle time keeps a hand placing the ball, or a single-frame false
+detection, from arming the monitor.
+"""
+
+import time
+from enum import Enum
+from typing import Optional
+
+
+class ArmState(Enum):
+    """Arming state."""
+
+    IDLE = "idle"  # No ball on the mat (or waiting for it to settle)
+    ARMED = "armed"  # Ball present and stable - ready for a shot
+
+
+class ArmingStateMachine:
+    """
+    Tracks whether a ball is ready to be hit.
+
+    Example:
+        arming = ArmingStateMachine(settle_sec=0.5)
+        event = arming.update(ball_present=True)
+        if event == "ready":
+            notify_sim_ready()
+    """
+
+    DEFAULT_SETTLE_SEC = 0.5
+
+    def __init__(self, settle_sec: float = DEFAULT_SETTLE_SEC):
+        """
+        Initialize arming state machine.
+
+        Args:
+            settle_sec: How long the ball must be continuously present to arm
+        """
+        self.settle_sec = settle_sec
+        self.state = ArmState.IDLE
+        self._present_since: Optional[float] = None
+
+    @property
+    def armed(self) -> bool:
+        """True if a ball is on the mat and ready."""
+        return self.state == ArmState.ARMED
+
+    def update(self, ball_present: bool, now: Optional[float] = None) -> Optional[str]:
+        """
+        Feed the latest ball-present observation.
+
+        Call this for every observation (e.g. every camera frame), not only
+        on changes, so the settle timer can expire.
+
+        Args:
+            ball_present: Whether a ball is currently detected
+            now: Observation time (default: time.monotonic())
+
+        Returns:
+            "ready" when the monitor becomes armed, "not_ready" when an armed
+            ball is removed without a shot, otherwise None
+        """
+        now = time.monotonic() if now is None else now
+
+        if not ball_present:
+            self._present_since = None
+            if self.state == ArmState.ARMED:
+                self.state = ArmState.IDLE
+                return "not_ready"
+            return None
+
+        if self.state == ArmState.ARMED:
+            return None
+
+        if self._present_since is None:
+            self._present_since = now
+        if now - self._present_since >= self.settle_sec:
+            self.state = ArmState.ARMED
+            return "ready"
+        return None
+
+    def arm(self) -> str:
+        """
+        Arm by hand (e.g. from the phone remote) without waiting for a ball.
+
+        Returns:
+            "ready", to be published like an automatic arm
+        """
+        self.state = ArmState.ARMED
+        self._present_since = None
+        return "ready"
+
+    def on_shot(self):
+        """Disarm after a shot; the next ball has to settle again."""
+        self.state = ArmState.IDLE
+        self._present_since = None
@@ -0,0 +1,126 @@
+"""
+Ball types: what the shot detection and carry expect of the ball.
+
+The thresholds and carry tables assume a premium golf ball. Other balls
+come off the face slower and reflect less of the radar's signal, so shots
+with them are rejected (too weak, smash factor out of range) or get carries
+that mean nothing. A ball type adjusts for that:
+
+    openflight-server --ball-type foam
+
+- premium: tour and retail balls; the defaults
+- range: range and limited-flight balls are harder and lose some speed,
+  so their own smash window replaces the club's loft window, and they
+  carry about 10% less than a premium ball at the same speed
+- foam: foam and plastic practice balls into a curtain; they're a fraction
+  of a golf ball's radar cross-section and come off the face much slower,
+  so speed bands, smash window and magnitude thresholds are lowered (and
+  the club's loft no longer sets the smash window), and
+  carry is scaled up to roughly what a golf ball would have done, which
+  keeps the numbers sensible relative to each other and to a real bag
+
+--ball-type is also a session tag, so other values ("pro-v1") are only
+recorded. The thresholds apply over a calibration profile's and a
+--sensitivity preset's (magnitudes are scaled rather than set, so the two
+combine); the carry scaling is kept with each shot, and shots logged with
+a ball type are reprocessed with it.
+"""
+
+from dataclasses import dataclass, field
+from typing import Any, Dict, Optional
+
+
+@dataclass(frozen=True)
+class BallType:
+    """
+    How one kind of ball changes detection and carry.
+
+    Attributes:
+        thresholds: MonitorConfig fields set outright (speed bands, smash window)
+        magnitude_scale: min_magnitude and min_shot_magnitude are multiplied by this
+        carry_scale: Carry estimates are multiplied by this
+    """
+
+    thresholds: Dict[str, float] = field(default_factory=dict)
+    magnitude_scale: float = 1.0
+    carry_scale: float = 1.0
+
+
+BALL_TYPES: Dict[str, BallType] = {
+    "premium": BallType(),
+    "range": BallType(
+        thresholds={
+            "max_ball_speed_mph": 200,
+            "smash_factor_max": 1.6,
+            "club_smash_margin": 0,  # Loft windows assume a premium ball
+        },
+        carry_scale=0.9,
+    ),
+    "foam": BallType(
+        thresholds={
+            "min_ball_speed_mph": 15,
+            "max_ball_speed_mph": 150,
+            "min_club_speed_mph": 15,
+            "smash_factor_min": 1.0,
+            "smash_factor_max": 1.4,
+            "club_speed_min_ratio": 0.7,
+            "club_speed_max_ratio": 1.0,
+            "club_smash_margin": 0,  # Loft windows assume a golf ball
+        },
+        magnitude_scale=0.25,
+        carry_scale=1.8,
+    ),
+}
+
+BALL_TYPE_NAMES = tuple(BALL_TYPES)
+
+
+def ball_type_for(name: Optional[str]) -> Optional[str]:
+    """The ball type a --ball-type value names ("Foam" -> "foam"), or None for other balls."""
+    if not name:
+        return None
+    name = name.strip().lower()
+    return name if name in BALL_TYPES else None
+
+
+def apply_ball_type(config: Any, name: str) -> Any:
+    """
+    The config with a ball type's thresholds applied.
+
+    Args:
+        config: MonitorConfig to start from
+        name: Ball type (one of BALL_TYPE_NAMES)
+
+    Returns:
+        New MonitorConfig (config is unchanged)
+
+    Raises:
+        ValueError: If the ball type is unknown
+    """
+    if name not in BALL_TYPES:
+        raise ValueError(
+            f"Unknown ball type {name!r} (choose from: {', '.join(BALL_TYPE_NAMES)})"
+        )
+    ball = BALL_TYPES[name]
+    updates: Dict[str, Any] = dict(ball.thresholds)
+    if ball.magnitude_scale != 1.0:
+        updates["min_magnitude"] = config.min_magnitude * ball.magnitude_scale
+        updates["min_shot_magnitude"] = config.min_shot_magnitude * ball.magnitude_scale
+    return config.with_updates(updates)
+
+
+def carry_scale(name: Optional[str]) -> float:
+    """How much a ball type's carries are scaled (1.0 for None or other balls)."""
+    ball = BALL_TYPES.get(name) if name else None
+    return ball.carry_scale if ball else 1.0
+
+
+def describe(name: str) -> str:
+    """A ball type's changes as text, e.g. "min_ball_speed_mph=15, magnitudes x0.25"."""
+    ball = BALL_TYPES[name]
+    parts = [f"{key}={value:g}" for key, value in ball.thresholds.items()]
+    if ball.magnitude_scale != 1.0:
+        parts.append(f"magnitudes x{ball.magnitude_scale:g}")
+    if ball.carry_scale != 1.0:
+        parts.append(f"carry x{ball.carry_scale:g}")
+    return ", ".join(parts) or "defaults"
@@ -0,0 +1,230 @@
+"""
+Multi-bay mode: several hitting bays driven from one process.
+
+For clubs and garages running two or more mats off one mini-PC. Each bay
+gets its own radar, detection state and sim endpoint (webhook URLs), and
+its output is prefixed with the bay name. Bays are described in a JSON
+file:
+
+    {
+      "bays": [
+        {"name": "bay1", "port": "/dev/ttyACM0", "webhook_urls": ["http://sim1:8000/shot"]},
+        {"name": "bay2", "port": "/dev/ttyACM1", "webhook_urls": ["http://sim2:8000/shot"],
+         "mount_position": "front"}
+      ]
+    }
+
+Usage:
+    openflight-bays bays.json
+"""
+
+import argparse
+import json
+import sys
+import time
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, List, Optional
+
+from .launch_monitor import LaunchMonitor, Shot
+from .ops243 import MountPosition
+from .webhooks import WebhookSink
+
+
+@dataclass
+class BayConfig:
+    """
+    Configuration for one hitting bay.
+
+    Attributes:
+        name: Bay name, used as the log prefix and in shot payloads
+        port: Serial port of this bay's radar
+        webhook_urls: Sim/consumer endpoints that receive this bay's shots
+        webhook_secret: Optional HMAC secret for this bay's webhooks
+        mount_position: Where this bay's radar sits
+    """
+
+    name: str
+    port: str
+    webhook_urls: List[str] = field(default_factory=list)
+    webhook_secret: Optional[str] = None
+    mount_position: MountPosition = MountPosition.BEHIND
+
+
+def load_bays(path: Path) -> List[BayConfig]:
+    """
+    Load and validate a bay configuration file.
+
+    Args:
+        path: JSON file with a "bays" list
+
+    Returns:
+        Bay configurations in file order
+
+    Raises:
+        ValueError: If the file is malformed, or names/ports are missing or repeated
+    """
+    try:
+        data = json.loads(Path(path).read_text())
+    except json.JSONDecodeError as e:
+        raise ValueError(f"{path} is not valid JSON: {e}") from None
+
+    entries = data.get("bays") if isinstance(data, dict) else None
+    if not entries:
+        raise ValueError(f"{path} must contain a non-empty \"bays\" list")
+
+    bays = []
+    for i, entry in enumerate(entries, start=1):
+        name = entry.get("name")
+        port = entry.get("port")
+        if not name or not port:
+            raise ValueError(f"Bay #{i} needs both a name and a port")
+        try:
+            position = MountPosition(entry.get("mount_position", MountPosition.BEHIND.value))
+        except ValueError:
+            raise ValueError(f"Bay '{name}': unknown mount_position") from None
+        bays.append(
+            BayConfig(
+                name=name,
+                port=port,
+                webhook_urls=list(entry.get("webhook_urls", [])),
+                webhook_secret=entry.get("webhook_secret"),
+                mount_position=position,
+            )
+        )
+
+    for attr in ("name", "port"):
+        values = [getattr(b, attr) for b in bays]
+        duplicates = sorted({v for v in values if values.count(v) > 1})
+        if duplicates:
+            raise ValueError(f"Duplicate bay {attr}(s): {', '.join(duplicates)}")
+
+    return bays
+
+
+class Bay:
+    """
+    One hitting bay: a radar, its shot detection state and its sim endpoint.
+
+    Each bay owns its own LaunchMonitor, so detection state (buffered
+    readings, shot history, current club) never leaks between bays.
+    """
+
+    def __init__(
+        self,
+        config: BayConfig,
+        monitor_factory: Optional[Callable[[BayConfig], LaunchMonitor]] = None,
+    ):
+        """
+        Initialize bay.
+
+        Args:
+            config: Bay configuration
+            monitor_factory: Builds the monitor for this bay (for testing).
+                             Defaults to a LaunchMonitor on config.port.
+        """
+        self.config = config
+        factory = monitor_factory or (
+            lambda c: LaunchMonitor(port=c.port, mount_position=c.mount_position)
+        )
+        self.monitor = factory(config)
+        self.sink: Optional[WebhookSink] = None
+        if config.webhook_urls:
+            self.sink = WebhookSink(config.webhook_urls, secret=config.webhook_secret)
+
+    @property
+    def name(self) -> str:
+        """Bay name."""
+        return self.config.name
+
+    def log(self, message: str):
+        """Print a message prefixed with the bay name."""
+        print(f"[{self.name}] {message}")
+
+    def start(self):
+        """Connect the radar and start detecting shots."""
+        self.monitor.connect()
+        if self.sink:
+            self.sink.start()
+        self.monitor.start(shot_callback=self.on_shot)
+        self.log(f"Ready on {self.config.port}")
+
+    def stop(self):
+        """Stop detection and flush pending webhook deliveries."""
+        self.monitor.disconnect()
+        if self.sink:
+            self.sink.stop()
+
+    def on_shot(self, shot: Shot):
+        """Report a shot from this bay and forward it to the bay's sim."""
+        club = f", club {shot.club_speed_mph:.1f} mph" if shot.club_speed_mph else ""
+        self.log(
+            f"Shot: ball {shot.ball_speed_mph:.1f} mph{club}, "
+            f"carry ~{shot.estimated_carry_yards:.0f} yds"
+        )
+        if self.sink:
+            self.sink.send({"event": "shot", "bay": self.name, "shot": shot_payload(shot)})
+
+
+def shot_payload(shot: Shot) -> dict:
+    """Serialize the fields a sim needs from a shot."""
+    return {
+        "ball_speed_mph": round(shot.ball_speed_mph, 1),
+        "club_speed_mph": round(shot.club_speed_mph, 1) if shot.club_speed_mph else None,
+        "smash_factor": round(shot.smash_factor, 2) if shot.smash_factor else None,
+        "estimated_carry_yards": round(shot.estimated_carry_yards),
+        "club": shot.club.value,
+        "timestamp": shot.timestamp.isoformat(),
+        "launch_angle_vertical": shot.launch_angle_vertical,
+        "launch_angle_horizontal": shot.launch_angle_horizontal,
+        "spin_rpm": round(shot.spin_rpm) if shot.spin_rpm else None,
+    }
+
+
+def main():
+    """CLI entry point."""
+    parser = argparse.ArgumentParser(description="Run several hitting bays from one process")
+    parser.add_argument("config", help="Bay configuration JSON file")
+    args = parser.parse_args()
+
+    try:
+        bays = [Bay(config) for config in load_bays(Path(args.config))]
+    except (OSError, ValueError) as e:
+        print(f"Error: {e}")
+        return 1
+
+    print("=" * 50)
+    print(f"  OpenFlight - {len(bays)} bays")
+    print("=" * 50)
+
+    started = []
+    try:
+        for bay in bays:
+            try:
+                bay.start()
+                started.append(bay)
+            except Exception as e:  # pylint: disable=broad-except
+                # One missing radar shouldn't take the other bays down
+                bay.log(f"Failed to start: {e}")
+
+        if not started:
+            print("No bays started")
+            return 1
+
+        print(f"{datetime.now():%H:%M:%S} Running {len(started)}/{len(bays)} bays - Ctrl+C to stop")
+        while True:
+            time.sleep(0.5)
+    except KeyboardInterrupt:
+        print()
+    finally:
+        for bay in started:
+            bay.stop()
+            stats = bay.monitor.get_session_stats()
+            bay.log(f"{stats['shot_count']} shots")
+
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
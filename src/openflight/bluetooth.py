@@ -0,0 +1,262 @@
+"""
+Shot output over Bluetooth serial (RFCOMM).
+
+Some tablets used at hitting nets only take shots from a launch monitor
+connected as a Bluetooth serial port. With --bluetooth-serial, the server
+listens on an RFCOMM channel (what the Serial Port Profile runs over) and
+writes one line per shot to every connected device:
+
+    openflight-server --bluetooth-serial
+    openflight-server --bluetooth-serial --bluetooth-channel 3 --bluetooth-format csv
+    openflight-server --bluetooth-serial --bluetooth-format "BS={ball_speed:.1f},CY={carry:.0f}"
+
+Formats:
+
+    json   shot_to_dict output, one JSON object per line (the default)
+    csv    ball speed, club speed, smash, carry, vertical and horizontal
+           launch angle, spin and club; empty where unknown
+    a shot card (see shot_card): "compact", "full" or a template
+
+Lines end in CRLF; a multi-line card is sent as several lines. Pair the
+tablet with the Pi first (bluetoothctl), and if its app looks serial ports
+up by SDP, advertise the channel with `sdptool add --channel=1 SP`.
+
+Linux only: Python's RFCOMM sockets need BlueZ.
+"""
+
+import json
+import logging
+import socket
+import threading
+from typing import Any, List, Optional, Tuple
+
+from .shot_card import ShotCard
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_CHANNEL = 1
+LINE_FORMATS = ("json", "csv")  # Besides shot cards
+CSV_FIELDS = (
+    "ball_speed_mph",
+    "club_speed_mph",
+    "smash_factor",
+    "estimated_carry_yards",
+    "launch_angle_vertical",
+    "launch_angle_horizontal",
+    "spin_rpm",
+    "club",
+)
+
+
+def bluetooth_available() -> bool:
+    """Whether this Python can open RFCOMM sockets (Linux with BlueZ)."""
+    return hasattr(socket, "AF_BLUETOOTH") and hasattr(socket, "BTPROTO_RFCOMM")
+
+
+class LineFormat:
+    """
+    How a shot is written as a line.
+
+    Example:
+        line_format = LineFormat("csv")
+        line_format.render(shot, shot_to_dict(shot))  # "152.3,104.1,1.46,241,..."
+    """
+
+    def __init__(self, spec: str):
+        """
+        Parse a format.
+
+        Args:
+            spec: "json", "csv", a built-in shot card name or a card template
+
+        Raises:
+            ValueError: If spec is none of those
+        """
+        self.spec = spec
+        self.card: Optional[ShotCard] = None
+        if spec not in LINE_FORMATS:
+            try:
+                self.card = ShotCard.load(spec)
+            except ValueError as e:
+                raise ValueError(f"{e} (or use {', '.join(LINE_FORMATS)})") from None
+
+    def render(self, shot: Any, shot_dict: dict) -> str:
+        """
+        The text for a shot, without a line ending.
+
+        Args:
+            shot: Shot (for shot cards)
+            shot_dict: The shot as shot_to_dict gives it (for json and csv)
+        """
+        if self.spec == "json":
+            return json.dumps(shot_dict, separators=(",", ":"))
+        if self.spec == "csv":
+            values = (shot_dict.get(name) for name in CSV_FIELDS)
+            return ",".join("" if value is None else str(value) for value in values)
+        return self.card.render(shot)
+
+
+class BluetoothSerialServer:
+    """
+    RFCOMM server writing shot lines to every connected device.
+
+    Devices are accepted on a background thread; one that stops reading
+    (or disconnects) is dropped on the next write.
+
+    Example:
+        server = BluetoothSerialServer(channel=1, line_format="csv")
+        server.start()
+        server.send_shot(shot, shot_to_dict(shot))
+        ...
+        server.stop()
+    """
+
+    ACCEPT_TIMEOUT_SEC = 1.0  # How often the accept loop checks for stop()
+    WRITE_TIMEOUT_SEC = 2.0  # A device not taking a line within this is dropped
+
+    def __init__(self, channel: int = DEFAULT_CHANNEL, line_format: str = "json"):
+        """
+        Initialize Bluetooth serial server.
+
+        Args:
+            channel: RFCOMM channel to listen on (1-30)
+            line_format: Shot line format (see LineFormat)
+
+        Raises:
+            ValueError: If the channel or format is invalid
+        """
+        if not 1 <= channel <= 30:
+            raise ValueError(f"RFCOMM channel must be 1-30, got {channel}")
+        self.channel = channel
+        self.line_format = LineFormat(line_format)
+        self._clients: List[Tuple[socket.socket, str]] = []
+        self._lock = threading.Lock()
+        self._sock: Optional[socket.socket] = None
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    @property
+    def clients(self) -> List[str]:
+        """Addresses of the connected devices."""
+        with self._lock:
+            return [address for _, address in self._clients]
+
+    def start(self):
+        """
+        Start listening and accepting devices on a background thread.
+
+        Raises:
+            RuntimeError: If Bluetooth sockets aren't available
+            OSError: If the channel can't be bound (no adapter, channel in use)
+        """
+        if self._thread and self._thread.is_alive():
+            return
+        self._sock = self._listen()
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._accept_loop, daemon=True)
+        self._thread.start()
+
+    def _listen(self) -> socket.socket:
+        if not bluetooth_available():
+            raise RuntimeError("Bluetooth serial needs Linux with BlueZ (no AF_BLUETOOTH)")
+        # pylint: disable=no-member
+        sock = socket.socket(socket.AF_BLUETOOTH, socket.SOCK_STREAM, socket.BTPROTO_RFCOMM)
+        try:
+            sock.bind((socket.BDADDR_ANY, self.channel))
+            sock.listen(2)
+        except OSError:
+            sock.close()
+            raise
+        return sock
+
+    def stop(self, timeout: float = 5.0):
+        """Disconnect every device and stop listening."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=timeout)
+            self._thread = None
+        if self._sock:
+            self._sock.close()
+            self._sock = None
+        with self._lock:
+            clients, self._clients = self._clients, []
+        for conn, _ in clients:
+            conn.close()
+
+    def _accept_loop(self):
+        self._sock.settimeout(self.ACCEPT_TIMEOUT_SEC)
+        while not self._stop_event.is_set():
+            try:
+                conn, address = self._sock.accept()
+            except socket.timeout:
+                continue
+            except OSError as e:
+                if not self._stop_event.is_set():
+                    logger.warning("Bluetooth serial accept failed: %s", e)
+                    self._stop_event.wait(self.ACCEPT_TIMEOUT_SEC)
+                continue
+            conn.settimeout(self.WRITE_TIMEOUT_SEC)
+            name = address[0] if isinstance(address, tuple) else str(address)
+            with self._lock:
+                self._clients.append((conn, name))
+            logger.info("Bluetooth serial device connected: %s", name)
+
+    def send_line(self, text: str):
+        """Write text, with CRLF line endings, to every connected device."""
+        data = ("\r\n".join(text.split("\n")) + "\r\n").encode("utf-8")
+        with self._lock:
+            clients = list(self._clients)
+        for conn, name in clients:
+            try:
+                conn.sendall(data)
+            except OSError as e:
+                logger.info("Bluetooth serial device %s dropped: %s", name, e)
+                with self._lock:
+                    if (conn, name) in self._clients:
+                        self._clients.remove((conn, name))
+                conn.close()
+
+    def send_shot(self, shot: Any, shot_dict: dict):
+        """Write a shot, in the configured format, to every connected device."""
+        text = self.line_format.render(shot, shot_dict)
+        if text:
+            self.send_line(text)
+
+
+# Global Bluetooth serial server instance
+_bluetooth_serial: Optional[BluetoothSerialServer] = None
+
+
+def get_bluetooth_serial() -> Optional[BluetoothSerialServer]:
+    """Get the global Bluetooth serial server (None if not enabled)."""
+    return _bluetooth_serial
+
+
+def init_bluetooth_serial(
+    channel: int = DEFAULT_CHANNEL, line_format: str = "json"
+) -> BluetoothSerialServer:
+    """
+    Initialize and start the global Bluetooth serial server.
+
+    Args:
+        channel: RFCOMM channel to listen on
+        line_format: Shot line format (see LineFormat)
+
+    Returns:
+        BluetoothSerialServer instance
+
+    Raises:
+        ValueError: If the channel or format is invalid
+        RuntimeError: If Bluetooth sockets aren't available
+        OSError: If the channel can't be bound
+    """
+    global _bluetooth_serial  # pylint: disable=global-statement
+
+    if _bluetooth_serial:
+        _bluetooth_serial.stop()
+        _bluetooth_serial = None
+
+    server = BluetoothSerialServer(channel=channel, line_format=line_format)
+    server.start()
+    _bluetooth_serial = server
+    return server
@@ -0,0 +1,450 @@
+"""
+Time-synchronized multi-sensor capture.
+
+Fusing the radar with the sound trigger and the camera needs all three
+recorded against one clock. The session log has shots, the serial dump
+has radar bytes, the camera has its own frames; none of them line up.
+With --capture FILE, the server writes one container per session with:
+
+- radar: every reading, as the frame it came in (SpeedTarget; the other
+  frame types can be stored too)
+- audio: sound trigger impact gates (rolling buffer --trigger sound or
+  sound-gpio), with whether the capture was accepted and the trigger latency
+- camera: launch angle events from the camera tracker
+- shot: each detected shot, as sent to the UI
+
+all stamped with time.monotonic(), the clock radar frames already carry;
+the header holds the session clock's mapping to wall time (see clock).
+Analysis then works on one timeline:
+
+    openflight-capture session.ofcap
+    openflight-capture session.ofcap --events   # every record
+    openflight-capture session.ofcap --align    # sensor offsets around each shot
+
+File format: one JSON header line, e.g.
+
+    {"format": "openflight-capture", "version": 1, "radar": "ops243",
+     "started": "...", "clock": {"monotonic_epoch": ..., "wall_epoch": ...}}
+
+then one record per event: a little-endian float64 monotonic time, a
+uint8 stream id (1 radar, 2 audio, 3 camera, 4 shot), a uint32 length
+and that many bytes of UTF-8 JSON.
+"""
+
+import argparse
+import json
+import struct
+import sys
+import threading
+import time
+from collections import Counter
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from typing import Any, BinaryIO, Dict, Iterator, List, Optional, Tuple
+
+from .clock import SessionClock, get_clock
+from .frames import (
+    Direction,
+    FftFrame,
+    Frame,
+    Point,
+    PointCloud,
+    RawAdc,
+    SpeedReading,
+    SpeedTarget,
+    Status,
+    Target,
+)
+
+FORMAT_NAME = "openflight-capture"
+FORMAT_VERSION = 1
+
+RADAR = "radar"
+AUDIO = "audio"
+CAMERA = "camera"
+SHOT = "shot"
+STREAMS = (RADAR, AUDIO, CAMERA, SHOT)
+_STREAM_IDS = {name: number for number, name in enumerate(STREAMS, start=1)}
+
+_RECORD_HEADER = struct.Struct("<dBI")
+_FRAME_TYPES = {cls.__name__: cls for cls in (SpeedTarget, FftFrame, RawAdc, Status, PointCloud)}
+
+DEFAULT_ALIGN_WINDOW_SEC = 0.5
+
+
+def frame_to_dict(frame: Frame) -> dict:
+    """A radar frame as JSON-serializable data, with its type."""
+    data = asdict(frame)
+    data.pop("timestamp")  # The record's time
+    if isinstance(frame, SpeedTarget):
+        for target in data["targets"]:
+            target["direction"] = target["direction"].value
+    return {"type": type(frame).__name__, **data}
+
+
+def frame_from_dict(data: dict, timestamp: float) -> Frame:
+    """
+    Rebuild a radar frame stored by frame_to_dict.
+
+    Raises:
+        ValueError: If the frame type is unknown or its fields don't match
+    """
+    fields = dict(data)
+    cls = _FRAME_TYPES.get(fields.pop("type", None))
+    if cls is None:
+        raise ValueError(f"Unknown frame type {data.get('type')!r}")
+    try:
+        if cls is SpeedTarget:
+            fields["targets"] = [
+                Target(**{**t, "direction": Direction(t["direction"])})
+                for t in fields.get("targets", [])
+            ]
+        elif cls is PointCloud:
+            fields["points"] = [Point(**p) for p in fields.get("points", [])]
+        return cls(timestamp=timestamp, **fields)
+    except (KeyError, TypeError, ValueError) as e:
+        raise ValueError(f"Bad {cls.__name__} frame: {e}") from None
+
+
+def reading_to_frame(reading: SpeedReading) -> SpeedTarget:
+    """The frame a reading came from: it and its frame_objects, strongest first."""
+    readings = [reading] + list(reading.frame_objects or [])
+    return SpeedTarget(
+        timestamp=reading.timestamp if reading.timestamp is not None else time.monotonic(),
+        targets=[
+            Target(
+                float(r.speed), r.direction, r.magnitude, r.range_m, r.azimuth_deg, r.elevation_deg
+            )
+            for r in readings
+        ],
+        unit=reading.unit,
+    )
+
+
+@dataclass
+class CaptureRecord:
+    """One event in a capture."""
+
+    timestamp: float  # time.monotonic() when it happened
+    stream: str  # One of STREAMS
+    data: dict
+
+    def frame(self) -> Optional[Frame]:
+        """The radar frame (None for other streams)."""
+        if self.stream != RADAR:
+            return None
+        return frame_from_dict(self.data, self.timestamp)
+
+
+class CaptureWriter:
+    """
+    Capture file that every sensor's events are recorded into.
+
+    Thread safe: the radar, trigger and camera threads all record into it.
+
+    Example:
+        capture = CaptureWriter("session.ofcap", radar="ops243")
+        capture.record_reading(reading)
+        capture.record_camera(angle.vertical, angle.horizontal, angle.confidence)
+        ...
+        capture.close()
+    """
+
+    def __init__(
+        self, path: str, radar: str = "ops243", clock: Optional[SessionClock] = None
+    ):
+        """
+        Create the capture file (overwriting an existing one).
+
+        Args:
+            path: Capture file
+            radar: Radar model the capture is from
+            clock: Monotonic to wall time mapping (default: the session clock)
+        """
+        self.path = path
+        self.counts: Counter = Counter()  # Records written per stream
+        self._lock = threading.Lock()
+        self._file: Optional[BinaryIO] = open(path, "wb")  # pylint: disable=consider-using-with
+        header = {
+            "format": FORMAT_NAME,
+            "version": FORMAT_VERSION,
+            "radar": radar,
+            "started": datetime.now().isoformat(),
+            "clock": (clock or get_clock()).to_dict(),
+        }
+        self._file.write(json.dumps(header).encode("utf-8") + b"\n")
+        self._file.flush()
+
+    def record(self, stream: str, data: dict, timestamp: Optional[float] = None):
+        """
+        Append a record (ignored once closed).
+
+        Args:
+            stream: One of STREAMS
+            data: JSON-serializable event data
+            timestamp: time.monotonic() of the event (default: now)
+        """
+        payload = json.dumps(data, separators=(",", ":")).encode("utf-8")
+        timestamp = time.monotonic() if timestamp is None else timestamp
+        with self._lock:
+            if self._file is None:
+                return
+            self._file.write(
+                _RECORD_HEADER.pack(timestamp, _STREAM_IDS[stream], len(payload)) + payload
+            )
+            # Flushed every record so a crash or power cut keeps what led up to it
+            self._file.flush()
+            self.counts[stream] += 1
+
+    def record_frame(self, frame: Frame):
+        """Record a radar frame at its own timestamp."""
+        self.record(RADAR, frame_to_dict(frame), frame.timestamp)
+
+    def record_reading(self, reading: SpeedReading):
+        """Record a radar reading, as the frame it came in."""
+        self.record_frame(reading_to_frame(reading))
+
+    def record_audio_gate(self, timestamp: Optional[float] = None, **info: Any):
+        """Record a sound trigger's impact gate, e.g. record_audio_gate(t, trigger="sound-gpio")."""
+        self.record(AUDIO, info, timestamp)
+
+    def record_camera(
+        self,
+        vertical: float,
+        horizontal: float,
+        confidence: float,
+        timestamp: Optional[float] = None,
+    ):
+        """Record a camera launch angle event."""
+        self.record(
+            CAMERA,
+            {"vertical": vertical, "horizontal": horizontal, "confidence": confidence},
+            timestamp,
+        )
+
+    def record_shot(self, shot: dict, timestamp: Optional[float] = None):
+        """Record a detected shot (as shot_to_dict gives it)."""
+        self.record(SHOT, shot, timestamp)
+
+    def close(self):
+        """Close the capture file."""
+        with self._lock:
+            if self._file is not None:
+                self._file.close()
+                self._file = None
+
+
+def read_capture(path: str) -> Tuple[dict, Iterator[CaptureRecord]]:
+    """
+    Open a capture file.
+
+    Returns:
+        (header, records in file order); records stop at a truncated final
+        record, and records of unknown streams are skipped
+
+    Raises:
+        ValueError: If the file isn't a capture
+    """
+    f = open(path, "rb")  # pylint: disable=consider-using-with
+    try:
+        header = json.loads(f.readline())
+    except ValueError:
+        header = None
+    if not isinstance(header, dict) or header.get("format") != FORMAT_NAME:
+        f.close()
+        raise ValueError(f"{path} is not an OpenFlight capture")
+    if header.get("version") != FORMAT_VERSION:
+        f.close()
+        raise ValueError(f"{path}: unsupported capture version {header.get('version')}")
+    streams = {number: name for name, number in _STREAM_IDS.items()}
+
+    def records() -> Iterator[CaptureRecord]:
+        with f:
+            while True:
+                head = f.read(_RECORD_HEADER.size)
+                if len(head) < _RECORD_HEADER.size:
+                    return
+                timestamp, stream_id, length = _RECORD_HEADER.unpack(head)
+                payload = f.read(length)
+                if len(payload) < length:
+                    return
+                if stream_id in streams:
+                    yield CaptureRecord(timestamp, streams[stream_id], json.loads(payload))
+
+    return header, records()
+
+
+def capture_clock(header: dict) -> Optional[SessionClock]:
+    """The capture's monotonic to wall time mapping (None if the header has none)."""
+    clock = header.get("clock")
+    if not isinstance(clock, dict):
+        return None
+    try:
+        return SessionClock(**clock)
+    except TypeError:
+        return None
+
+
+def readings(records: Iterator[CaptureRecord]) -> Iterator[SpeedReading]:
+    """
+    The capture's radar readings, for replaying them through shot detection.
+
+    Yields:
+        One SpeedReading per SpeedTarget frame, as the radar gave it
+    """
+    for record in records:
+        if record.stream != RADAR or record.data.get("type") != SpeedTarget.__name__:
+            continue
+        reading = record.frame().to_reading()
+        if reading is not None:
+            yield reading
+
+
+@dataclass
+class AlignedEvent:
+    """One event with the nearest record of each other stream."""
+
+    anchor: CaptureRecord
+    nearest: Dict[str, Optional[CaptureRecord]] = field(default_factory=dict)
+
+    def offset_sec(self, stream: str) -> Optional[float]:
+        """Time from the anchor to the stream's nearest record (None if none was close)."""
+        record = self.nearest.get(stream)
+        return None if record is None else record.timestamp - self.anchor.timestamp
+
+
+def align(
+    records: List[CaptureRecord],
+    anchor_stream: str = SHOT,
+    window_sec: float = DEFAULT_ALIGN_WINDOW_SEC,
+) -> List[AlignedEvent]:
+    """
+    Line up the other streams around each event of one stream.
+
+    For radar, the nearest record is the strongest frame in the window
+    (the ball or club), not merely the closest in time.
+
+    Args:
+        records: Capture records
+        anchor_stream: Stream whose events are lined up against (e.g. SHOT or AUDIO)
+        window_sec: How far either side of an anchor to look
+
+    Returns:
+        One AlignedEvent per anchor record, in time order
+    """
+    by_stream: Dict[str, List[CaptureRecord]] = {name: [] for name in STREAMS}
+    for record in sorted(records, key=lambda r: r.timestamp):
+        by_stream[record.stream].append(record)
+
+    events = []
+    for anchor in by_stream[anchor_stream]:
+        event = AlignedEvent(anchor=anchor)
+        for stream in STREAMS:
+            if stream == anchor_stream:
+                continue
+            near = [
+                r
+                for r in by_stream[stream]
+                if abs(r.timestamp - anchor.timestamp) <= window_sec
+            ]
+            if not near:
+                event.nearest[stream] = None
+            elif stream == RADAR:
+                event.nearest[stream] = max(near, key=_peak_magnitude)
+            else:
+                event.nearest[stream] = min(
+                    near, key=lambda r, t=anchor.timestamp: abs(r.timestamp - t)
+                )
+        events.append(event)
+    return events
+
+
+def _peak_magnitude(record: CaptureRecord) -> float:
+    magnitudes = [
+        t.get("magnitude") or 0.0
+        for t in record.data.get("targets", [])
+    ]
+    return max(magnitudes, default=0.0)
+
+
+def describe_record(record: CaptureRecord) -> str:
+    """One-line summary of a record."""
+    if record.stream == RADAR:
+        # pylint: disable=import-outside-toplevel,cyclic-import
+        from .serial_dump import describe_frame
+
+        frame = record.frame()
+        return f"{type(frame).__name__:<12} {describe_frame(frame)}"
+    if record.stream == SHOT:
+        carry = record.data.get("estimated_carry_yards")
+        return (
+            f"{record.data.get('ball_speed_mph')} mph"
+            + (f", {carry} yd" if carry is not None else "")
+            + f" ({record.data.get('club', '?')})"
+        )
+    return json.dumps(record.data)
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """CLI entry point: summarize a capture."""
+    parser = argparse.ArgumentParser(description="Summarize a multi-sensor --capture file")
+    parser.add_argument("capture", help="Capture file written with --capture")
+    parser.add_argument("--events", action="store_true", help="Print every record")
+    parser.add_argument(
+        "--align",
+        choices=[SHOT, AUDIO],
+        nargs="?",
+        const=SHOT,
+        help="Print each shot's (or audio gate's) offset to the other sensors",
+    )
+    parser.add_argument(
+        "--window",
+        type=float,
+        default=DEFAULT_ALIGN_WINDOW_SEC,
+        metavar="SEC",
+        help=f"How far around an event --align looks (default: {DEFAULT_ALIGN_WINDOW_SEC})",
+    )
+    args = parser.parse_args(argv)
+
+    try:
+        header, records = read_capture(args.capture)
+        records = list(records)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+    start = records[0].timestamp if records else 0.0
+
+    def offset(timestamp: float) -> str:
+        return f"{timestamp - start:9.3f}s"
+
+    if args.events:
+        for record in records:
+            print(f"{offset(record.timestamp)}  {record.stream:<6} {describe_record(record)}")
+        print()
+
+    duration = records[-1].timestamp - start if records else 0.0
+    clock = capture_clock(header)
+    started = (
+        clock.to_datetime(start).isoformat(timespec="seconds")
+        if clock and records
+        else header.get("started", "unknown")
+    )
+    print(f"{args.capture}: {header.get('radar', '?')}, {duration:.1f}s, started {started}")
+    counts = Counter(r.stream for r in records)
+    for stream in STREAMS:
+        print(f"  {stream:<8} {counts[stream]}")
+
+    if args.align:
+        print(f"\nAligned on {args.align} (offsets in ms, within {args.window:g}s):")
+        others = [s for s in STREAMS if s != args.align]
+        print(f"{'Time':>10}  " + "  ".join(f"{s:>8}" for s in others))
+        for event in align(records, args.align, args.window):
+            cells = []
+            for stream in others:
+                value = event.offset_sec(stream)
+                cells.append(f"{value * 1000:+8.0f}" if value is not None else f"{'-':>8}")
+            print(f"{offset(event.anchor.timestamp)}  " + "  ".join(cells))
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
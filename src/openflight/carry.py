@@ -0,0 +1,390 @@
+"""
+Pluggable carry distance models.
+
+Range validation shows no single carry estimate wins for every club, so the
+model is chosen per club. Three models are available:
+
+- "table": TrackMan-derived ball speed lookup with a launch angle penalty
+  (the original estimate, and the default)
+- "physics": point-mass flight simulation with drag and Magnus lift, using
+  the measured launch angle and spin when available
+- "polynomial": user-fitted polynomial in ball speed, e.g. from rangefinder
+  or GPS measured carries
+
+Selection lives in the calibration profile under "carry_models", keyed by
+club (or "default"):
+
+    "carry_models": {
+      "default": {"model": "table"},
+      "driver": {"model": "physics", "correction": [4.0, 0.97]},
+      "7-iron": {"model": "polynomial", "coefficients": [-40.0, 2.1]}
+    }
+
+An optional "correction" (fit by openflight-calibrate-carry from measured
+outdoor carries) maps the model's estimate e to c0 + c1*e.
+"""
+
+import math
+from abc import ABC, abstractmethod
+from typing import Dict, List, Optional, Tuple
+
+from .launch_monitor import (
+    _OPTIMAL_LAUNCH,
+    ClubType,
+    Shot,
+    adjust_carry_for_launch_angle,
+    estimate_carry_distance,
+)
+from .units import Degrees, Mph, Rpm
+
+DEFAULT_KEY = "default"
+
+# Spin used when the shot has none measured (TrackMan PGA Tour averages, rpm)
+_TYPICAL_SPIN_RPM = {
+    ClubType.DRIVER: 2686,
+    ClubType.WOOD_3: 3655,
+    ClubType.WOOD_5: 4350,
+    ClubType.WOOD_7: 4600,
+    ClubType.HYBRID_3: 4437,
+    ClubType.HYBRID_5: 4700,
+    ClubType.HYBRID_7: 5000,
+    ClubType.HYBRID_9: 5400,
+    ClubType.IRON_2: 4300,
+    ClubType.IRON_3: 4630,
+    ClubType.IRON_4: 4836,
+    ClubType.IRON_5: 5361,
+    ClubType.IRON_6: 6231,
+    ClubType.IRON_7: 7097,
+    ClubType.IRON_8: 7998,
+    ClubType.IRON_9: 8647,
+    ClubType.PW: 9304,
+    ClubType.GW: 9600,
+    ClubType.SW: 9900,
+    ClubType.LW: 10200,
+    ClubType.UNKNOWN: 5000,
+}
+
+
+class CarryModel(ABC):
+    """Estimates carry distance for a shot."""
+
+    name = ""
+
+    @abstractmethod
+    def estimate(self, shot: Shot) -> float:
+        """
+        Estimate carry distance.
+
+        Args:
+            shot: Shot with at least ball speed and club set
+
+        Returns:
+            Carry distance in yards
+        """
+
+    def to_dict(self) -> dict:
+        """Serialize to a profile "carry_models" entry."""
+        return {"model": self.name}
+
+
+class TableCarryModel(CarryModel):
+    """TrackMan-derived lookup table, penalized for off-optimal launch angle."""
+
+    name = "table"
+
+    def estimate(self, shot: Shot) -> float:
+        base = estimate_carry_distance(shot.ball_speed_mph, shot.club)
+        if shot.launch_angle_vertical is not None:
+            return adjust_carry_for_launch_angle(
+                base,
+                shot.launch_angle_vertical,
+                shot.club,
+                shot.launch_angle_confidence or 0.2,
+            )
+        return base
+
+
+class PhysicsCarryModel(CarryModel):
+    """
+    Point-mass ball flight with drag and Magnus lift.
+
+    Drag and lift coefficients are linear in the spin factor S = r*omega/v,
+    fit to TrackMan PGA Tour averages for driver, 7-iron and PW (within
+    ~8 yards). Missing launch angle or spin fall back to club typicals.
+    """
+
+    name = "physics"
+
+    BALL_MASS_KG = 0.04593
+    BALL_RADIUS_M = 0.02135
+    AIR_DENSITY = 1.225  # kg/m^3, sea level at 15°C
+    GRAVITY = 9.81
+    DRAG_BASE, DRAG_SPIN = 0.17, 0.4
+    LIFT_BASE, LIFT_SPIN = 0.1, 0.6
+    SPIN_DECAY_PER_SEC = 0.04
+    TIME_STEP_SEC = 0.005
+    MAX_FLIGHT_SEC = 15.0
+
+    def estimate(self, shot: Shot) -> float:
+        launch_deg = shot.launch_angle_vertical
+        if launch_deg is None:
+            launch_deg = _OPTIMAL_LAUNCH.get(shot.club, 18.0)
+        spin_rpm = shot.spin_rpm or _TYPICAL_SPIN_RPM.get(shot.club, 5000)
+        return self.simulate(shot.ball_speed_mph, launch_deg, spin_rpm)
+
+    def simulate(self, ball_speed_mph: float, launch_deg: float, spin_rpm: float) -> float:
+        """
+        Fly the ball until it returns to launch height.
+
+        Args:
+            ball_speed_mph: Ball speed at launch
+            launch_deg: Vertical launch angle
+            spin_rpm: Backspin
+
+        Returns:
+            Carry distance in yards
+        """
+        points = self.trajectory(ball_speed_mph, launch_deg, spin_rpm)
+        return points[-1][1] * 1.09361 if points else 0.0
+
+    def trajectory(
+        self, ball_speed_mph: float, launch_deg: float, spin_rpm: float
+    ) -> List[Tuple[float, float, float, float, float]]:
+        """
+        Ball flight in the vertical plane, one point per time step.
+
+        Args:
+            ball_speed_mph: Ball speed at launch
+            launch_deg: Vertical launch angle
+            spin_rpm: Backspin
+
+        Returns:
+            (t_sec, x_m, y_m, vx_m/s, vy_m/s) from launch until the ball
+            returns to launch height (empty for a zero-speed ball)
+        """
+        if ball_speed_mph <= 0:
+            return []
+
+        area = math.pi * self.BALL_RADIUS_M**2
+        speed = Mph(ball_speed_mph).to_mps()
+        launch_rad = Degrees(launch_deg).to_radians()
+        vx = float(speed) * math.cos(launch_rad)
+        vy = float(speed) * math.sin(launch_rad)
+        omega = Rpm(spin_rpm).to_rad_per_sec()
+        x = y = t = 0.0
+        dt = self.TIME_STEP_SEC
+        points = [(t, x, y, vx, vy)]
+
+        while t < self.MAX_FLIGHT_SEC:
+            v = math.hypot(vx, vy)
+            spin_factor = self.BALL_RADIUS_M * omega / v
+            drag = self.DRAG_BASE + self.DRAG_SPIN * spin_factor
+            lift = self.LIFT_BASE + self.LIFT_SPIN * spin_factor
+            k = 0.5 * self.AIR_DENSITY * area * v / self.BALL_MASS_KG
+
+            ax = -k * (drag * vx + lift * vy)
+            ay = k * (lift * vx - drag * vy) - self.GRAVITY
+            vx += ax * dt
+            vy += ay * dt
+            x += vx * dt
+            y += vy * dt
+            t += dt
+            omega *= 1 - self.SPIN_DECAY_PER_SEC * dt
+            points.append((t, x, y, vx, vy))
+            if y < 0:
+                break
+
+        return points
+
+
+class PolynomialCarryModel(CarryModel):
+    """
+    User-fitted polynomial in ball speed.
+
+    carry = c0 + c1*v + c2*v^2 + ... with v in mph, coefficients lowest
+    order first.
+    """
+
+    name = "polynomial"
+
+    def __init__(self, coefficients: List[float]):
+        """
+        Initialize polynomial model.
+
+        Args:
+            coefficients: Polynomial coefficients, lowest order first
+
+        Raises:
+            ValueError: If no coefficients are given
+        """
+        if not coefficients:
+            raise ValueError("polynomial carry model needs at least one coefficient")
+        self.coefficients = [float(c) for c in coefficients]
+
+    def estimate(self, shot: Shot) -> float:
+        v = shot.ball_speed_mph
+        return max(0.0, sum(c * v**i for i, c in enumerate(self.coefficients)))
+
+    def to_dict(self) -> dict:
+        return {"model": self.name, "coefficients": list(self.coefficients)}
+
+
+class CorrectedCarryModel(CarryModel):
+    """
+    Another model's estimate passed through a linear correction curve.
+
+    carry = c0 + c1 * base_estimate, fit per club from measured carries.
+    """
+
+    def __init__(self, base: CarryModel, correction: List[float]):
+        """
+        Initialize corrected model.
+
+        Args:
+            base: Model whose estimate is corrected
+            correction: [offset_yards, scale]
+
+        Raises:
+            ValueError: If the correction is not two numbers
+        """
+        if len(correction) != 2:
+            raise ValueError("carry correction must be [offset_yards, scale]")
+        self.base = base
+        self.correction = [float(c) for c in correction]
+        self.name = base.name
+
+    def estimate(self, shot: Shot) -> float:
+        offset, scale = self.correction
+        return max(0.0, offset + scale * self.base.estimate(shot))
+
+    def to_dict(self) -> dict:
+        return {**self.base.to_dict(), "correction": list(self.correction)}
+
+
+def fit_correction(estimated: List[float], actual: List[float]) -> List[float]:
+    """
+    Fit a correction curve mapping model estimates to measured carries.
+
+    With fewer than three shots, or estimates spanning under 10 yards, an
+    offset can't be told apart from a scale, so only a scale is fit.
+
+    Args:
+        estimated: Model carry estimates (yards)
+        actual: Measured carries for the same shots (yards)
+
+    Returns:
+        [offset_yards, scale] for CorrectedCarryModel
+
+    Raises:
+        ValueError: If the lists are empty or differ in length
+    """
+    if not estimated or len(estimated) != len(actual):
+        raise ValueError("need matching, non-empty estimated and actual carries")
+
+    n = len(estimated)
+    if n < 3 or max(estimated) - min(estimated) < 10.0:
+        sum_sq = sum(e * e for e in estimated)
+        if sum_sq == 0:
+            raise ValueError("estimated carries are all zero")
+        return [0.0, sum(e * a for e, a in zip(estimated, actual)) / sum_sq]
+
+    mean_e = sum(estimated) / n
+    mean_a = sum(actual) / n
+    cov = sum((e - mean_e) * (a - mean_a) for e, a in zip(estimated, actual))
+    var = sum((e - mean_e) ** 2 for e in estimated)
+    scale = cov / var
+    return [mean_a - scale * mean_e, scale]
+
+
+def carry_model_from_dict(spec: dict) -> CarryModel:
+    """
+    Build a carry model from a profile entry.
+
+    Args:
+        spec: e.g. {"model": "physics"} or {"model": "polynomial", "coefficients": [...]}
+
+    Raises:
+        ValueError: If the model name or its parameters are invalid
+    """
+    if not isinstance(spec, dict):
+        raise ValueError(f"Carry model entry must be an object, got {spec!r}")
+
+    name = spec.get("model")
+    model: CarryModel
+    if name == TableCarryModel.name:
+        model = TableCarryModel()
+    elif name == PhysicsCarryModel.name:
+        model = PhysicsCarryModel()
+    elif name == PolynomialCarryModel.name:
+        coefficients = spec.get("coefficients")
+        if not isinstance(coefficients, list):
+            raise ValueError("polynomial carry model needs a 'coefficients' list")
+        model = PolynomialCarryModel(coefficients)
+    else:
+        raise ValueError(
+            f"Unknown carry model {name!r} (use 'table', 'physics' or 'polynomial')"
+        )
+
+    correction = spec.get("correction")
+    if correction is not None:
+        if not isinstance(correction, list):
+            raise ValueError("carry correction must be [offset_yards, scale]")
+        model = CorrectedCarryModel(model, correction)
+    return model
+
+
+def parse_carry_models(specs: Dict[str, dict]) -> Dict[Optional[ClubType], CarryModel]:
+    """
+    Validate a profile's "carry_models" section.
+
+    Args:
+        specs: Club value (e.g. "driver", "7-iron") or "default" -> model entry
+
+    Returns:
+        Club (None for the default) -> model
+
+    Raises:
+        ValueError: If a club name or model entry is invalid
+    """
+    models: Dict[Optional[ClubType], CarryModel] = {}
+    for key, spec in specs.items():
+        if key == DEFAULT_KEY:
+            club = None
+        else:
+            try:
+                club = ClubType(key)
+            except ValueError:
+                raise ValueError(f"Unknown club {key!r} in carry_models") from None
+        models[club] = carry_model_from_dict(spec)
+    return models
+
+
+# Active per-club selection (clubs not listed use the default)
+_models: Dict[Optional[ClubType], CarryModel] = {}
+_default_model: CarryModel = TableCarryModel()
+
+
+def get_carry_model(club: ClubType) -> CarryModel:
+    """Get the carry model selected for a club."""
+    return _models.get(club) or _models.get(None) or _default_model
+
+
+def get_carry_model_specs() -> Dict[str, dict]:
+    """Current selection as a profile "carry_models" section."""
+    return {
+        (club.value if club else DEFAULT_KEY): model.to_dict() for club, model in _models.items()
+    }
+
+
+def set_carry_models(specs: Dict[str, dict]):
+    """
+    Select carry models per club.
+
+    Args:
+        specs: A profile's "carry_models" section (empty = table model for all)
+
+    Raises:
+        ValueError: If the section is invalid (the current selection is kept)
+    """
+    global _models  # pylint: disable=global-statement
+    _models = parse_carry_models(specs)
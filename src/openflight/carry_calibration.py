@@ -0,0 +1,281 @@
+"""
+Fit per-club carry corrections from measured outdoor carries.
+
+Hit shots outdoors with the session logger on, measure where each one
+landed (rangefinder or GPS), then enter those carries against the logged
+shots. For each club a correction curve is fit between the profile's carry
+model and the measured carries, and stored in the profile's "carry_models"
+section so it is applied automatically from then on.
+
+Carries can be typed in per shot, or given as a CSV with columns
+shot_number, carry_yards and (when several sessions are given) session:
+
+    shot_number,carry_yards,session
+    3,231,session_20250601_100000_range
+    4,224,session_20250601_100000_range
+
+Usage:
+    openflight-calibrate-carry ~/openflight_sessions/session_20250601_*.jsonl --profile range
+    openflight-calibrate-carry logs/*.jsonl --profile range --carries carries.csv --dry-run
+"""
+
+import argparse
+import csv
+import json
+import math
+import sys
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Dict, List, Optional, Tuple
+
+from .carry import DEFAULT_KEY, TableCarryModel, carry_model_from_dict, fit_correction
+from .launch_monitor import ClubType
+from .profiles import CalibrationProfile, load_profile, save_profile
+from .reprocess import shot_from_entry
+
+# Shots that did not come from the radar can't be calibrated against
+_SKIPPED_MODES = ("mock", "injected")
+
+
+@dataclass
+class CarrySample:
+    """One logged shot paired with its measured carry."""
+
+    club: ClubType
+    estimated_yards: float
+    actual_yards: float
+
+
+@dataclass
+class ClubFit:
+    """Correction fit for one club."""
+
+    club: ClubType
+    samples: int
+    correction: List[float]
+    rms_before: float
+    rms_after: float
+
+
+def load_shots(paths: List[str]) -> List[Tuple[str, dict]]:
+    """
+    Read radar shots from session logs.
+
+    Args:
+        paths: Session log files
+
+    Returns:
+        (session name, shot_detected entry) pairs in file order
+    """
+    shots = []
+    for path in paths:
+        session = Path(path).stem
+        with open(path, encoding="utf-8") as f:
+            for line in f:
+                line = line.strip()
+                if not line:
+                    continue
+                try:
+                    entry = json.loads(line)
+                except ValueError:
+                    continue
+                if entry.get("type") != "shot_detected":
+                    continue
+                if entry.get("mode") in _SKIPPED_MODES:
+                    continue
+                shots.append((session, entry))
+    return shots
+
+
+def read_carries_csv(path: str) -> Dict[Tuple[Optional[str], int], float]:
+    """
+    Read measured carries from a CSV file.
+
+    Args:
+        path: CSV with shot_number, carry_yards and optional session columns
+
+    Returns:
+        (session or None, shot number) -> carry yards
+
+    Raises:
+        ValueError: If a row is missing a column or has a non-numeric value
+    """
+    carries = {}
+    with open(path, newline="", encoding="utf-8") as f:
+        for row_number, row in enumerate(csv.DictReader(f), start=2):
+            try:
+                key = (row.get("session") or None, int(row["shot_number"]))
+                carries[key] = float(row["carry_yards"])
+            except (KeyError, TypeError, ValueError):
+                raise ValueError(
+                    f"{path}:{row_number}: need shot_number and carry_yards"
+                ) from None
+    return carries
+
+
+def base_spec(profile: CalibrationProfile, club: ClubType) -> dict:
+    """
+    The profile's carry model entry for a club, without any correction.
+
+    Recalibrating fits against the uncorrected model so corrections don't
+    compound.
+    """
+    spec = profile.carry_models.get(club.value) or profile.carry_models.get(DEFAULT_KEY)
+    if not spec:
+        return TableCarryModel().to_dict()
+    return {k: v for k, v in spec.items() if k != "correction"}
+
+
+def build_samples(
+    profile: CalibrationProfile,
+    shots: List[Tuple[str, dict]],
+    carries: Dict[Tuple[Optional[str], int], float],
+) -> List[CarrySample]:
+    """
+    Pair logged shots with their measured carries.
+
+    Args:
+        profile: Profile whose carry models produce the estimates
+        shots: Output of load_shots
+        carries: (session or None, shot number) -> measured carry
+
+    Returns:
+        One sample per shot that has a measured carry
+    """
+    samples = []
+    for session, entry in shots:
+        number = entry.get("shot_number")
+        actual = carries.get((session, number), carries.get((None, number)))
+        if actual is None:
+            continue
+        shot = shot_from_entry(entry)
+        model = carry_model_from_dict(base_spec(profile, shot.club))
+        samples.append(CarrySample(shot.club, model.estimate(shot), actual))
+    return samples
+
+
+def _rms(errors: List[float]) -> float:
+    return math.sqrt(sum(e * e for e in errors) / len(errors))
+
+
+def fit_clubs(samples: List[CarrySample]) -> Dict[ClubType, ClubFit]:
+    """
+    Fit a correction curve per club.
+
+    Args:
+        samples: Shots paired with measured carries
+
+    Returns:
+        Club -> fit, for every club with at least one sample
+    """
+    by_club: Dict[ClubType, List[CarrySample]] = {}
+    for sample in samples:
+        by_club.setdefault(sample.club, []).append(sample)
+
+    fits = {}
+    for club, club_samples in by_club.items():
+        estimated = [s.estimated_yards for s in club_samples]
+        actual = [s.actual_yards for s in club_samples]
+        correction = fit_correction(estimated, actual)
+        corrected = [correction[0] + correction[1] * e for e in estimated]
+        fits[club] = ClubFit(
+            club=club,
+            samples=len(club_samples),
+            correction=[round(c, 4) for c in correction],
+            rms_before=_rms([e - a for e, a in zip(estimated, actual)]),
+            rms_after=_rms([c - a for c, a in zip(corrected, actual)]),
+        )
+    return fits
+
+
+def apply_fits(profile: CalibrationProfile, fits: Dict[ClubType, ClubFit]):
+    """Store each club's correction in the profile's carry_models section."""
+    for club, club_fit in fits.items():
+        profile.carry_models[club.value] = {
+            **base_spec(profile, club),
+            "correction": club_fit.correction,
+        }
+
+
+def _prompt_carries(shots: List[Tuple[str, dict]]) -> Dict[Tuple[Optional[str], int], float]:
+    print("Enter the measured carry in yards for each shot (blank to skip, q to finish)")
+    carries = {}
+    for session, entry in shots:
+        label = (
+            f"{session} #{entry.get('shot_number')} {entry.get('club', 'driver')} "
+            f"{entry['ball_speed_mph']:.1f} mph, est {entry.get('estimated_carry_yards', 0):.0f} yd"
+        )
+        while True:
+            answer = input(f"  {label}: ").strip()
+            if answer.lower() == "q":
+                return carries
+            if not answer:
+                break
+            try:
+                carries[(session, entry.get("shot_number"))] = float(answer)
+                break
+            except ValueError:
+                print("  Enter a number of yards")
+    return carries
+
+
+def main():
+    """CLI entry point for carry calibration."""
+    parser = argparse.ArgumentParser(
+        description="Fit per-club carry corrections from measured outdoor carries"
+    )
+    parser.add_argument("sessions", nargs="+", help="Session log files with the recorded shots")
+    parser.add_argument("--profile", required=True, help="Calibration profile to update")
+    parser.add_argument("--profile-dir", help="Directory for calibration profiles")
+    parser.add_argument(
+        "--carries", help="CSV of measured carries (shot_number, carry_yards[, session])"
+    )
+    parser.add_argument(
+        "--dry-run", action="store_true", help="Show the fit without saving the profile"
+    )
+    args = parser.parse_args()
+
+    profile_dir = Path(args.profile_dir).expanduser() if args.profile_dir else None
+    try:
+        profile = load_profile(args.profile, profile_dir)
+    except (FileNotFoundError, ValueError) as e:
+        parser.error(str(e))
+
+    shots = load_shots(args.sessions)
+    if not shots:
+        print("No radar shots found in the given sessions")
+        sys.exit(1)
+
+    if args.carries:
+        try:
+            carries = read_carries_csv(args.carries)
+        except (OSError, ValueError) as e:
+            parser.error(str(e))
+    else:
+        carries = _prompt_carries(shots)
+
+    samples = build_samples(profile, shots, carries)
+    if not samples:
+        print("No shots had a measured carry")
+        sys.exit(1)
+
+    fits = fit_clubs(samples)
+    print(f"\n{'Club':<10} {'Shots':>5} {'RMS before':>11} {'RMS after':>10}  Correction")
+    for club_fit in fits.values():
+        offset, scale = club_fit.correction
+        print(
+            f"{club_fit.club.value:<10} {club_fit.samples:>5} {club_fit.rms_before:>9.1f}yd "
+            f"{club_fit.rms_after:>8.1f}yd  {offset:+.1f} yd + {scale:.3f}x"
+        )
+
+    if args.dry_run:
+        print("\nDry run, profile not changed")
+        return
+
+    apply_fits(profile, fits)
+    path = save_profile(profile, profile_dir)
+    print(f"\nSaved corrections to {path}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,140 @@
+"""
+Minimal CBOR (RFC 8949) encoding and decoding.
+
+Enough of CBOR for the ESP32 bridge protocol (see esp_bridge), so the
+server needs no extra package: integers, byte and text strings, arrays,
+maps, booleans, null and floats. Tags are skipped (their content is
+returned); indefinite-length items are not supported, as embedded
+encoders such as TinyCBOR and QCBOR only write them when asked to.
+
+Example:
+    data = dumps({"s": 1, "d": b"{...}\\n"})
+    assert loads(data) == {"s": 1, "d": b"{...}\\n"}
+"""
+
+import struct
+from typing import Any, Tuple
+
+# Major types
+_UINT, _NEGINT, _BYTES, _TEXT, _ARRAY, _MAP, _TAG, _SIMPLE = range(8)
+
+_MAX_DEPTH = 16
+
+
+class CBORDecodeError(ValueError):
+    """Data that isn't (supported) CBOR."""
+
+
+def _head(major: int, value: int) -> bytes:
+    if value < 24:
+        return bytes([major << 5 | value])
+    for info, fmt in ((24, ">B"), (25, ">H"), (26, ">I"), (27, ">Q")):
+        if value < 1 << (8 * struct.calcsize(fmt)):
+            return bytes([major << 5 | info]) + struct.pack(fmt, value)
+    raise ValueError(f"integer {value} too large for CBOR")
+
+
+def dumps(value: Any) -> bytes:
+    """
+    Encode a value as CBOR.
+
+    Raises:
+        ValueError: For integers outside 64 bits
+        TypeError: For types CBOR (here) has no encoding for
+    """
+    if value is None:
+        return b"\xf6"
+    if value is True:
+        return b"\xf5"
+    if value is False:
+        return b"\xf4"
+    if isinstance(value, int):
+        return _head(_UINT, value) if value >= 0 else _head(_NEGINT, -1 - value)
+    if isinstance(value, float):
+        return b"\xfb" + struct.pack(">d", value)
+    if isinstance(value, (bytes, bytearray)):
+        return _head(_BYTES, len(value)) + bytes(value)
+    if isinstance(value, str):
+        encoded = value.encode("utf-8")
+        return _head(_TEXT, len(encoded)) + encoded
+    if isinstance(value, (list, tuple)):
+        return _head(_ARRAY, len(value)) + b"".join(dumps(v) for v in value)
+    if isinstance(value, dict):
+        return _head(_MAP, len(value)) + b"".join(
+            dumps(k) + dumps(v) for k, v in value.items()
+        )
+    raise TypeError(f"can't encode {type(value).__name__} as CBOR")
+
+
+def _take(data: bytes, pos: int, size: int) -> Tuple[bytes, int]:
+    if pos + size > len(data):
+        raise CBORDecodeError("truncated CBOR item")
+    return data[pos:pos + size], pos + size
+
+
+def _decode(data: bytes, pos: int, depth: int) -> Tuple[Any, int]:
+    if depth > _MAX_DEPTH:
+        raise CBORDecodeError("CBOR nested too deeply")
+    initial, pos = _take(data, pos, 1)
+    major, info = initial[0] >> 5, initial[0] & 0x1F
+
+    if major == _SIMPLE:
+        simple = {20: False, 21: True, 22: None, 23: None}
+        if info in simple:
+            return simple[info], pos
+        formats = {25: ">e", 26: ">f", 27: ">d"}
+        if info in formats:
+            raw, pos = _take(data, pos, struct.calcsize(formats[info]))
+            return struct.unpack(formats[info], raw)[0], pos
+        raise CBORDecodeError(f"unsupported CBOR simple value {info}")
+
+    if info < 24:
+        value = info
+    elif info <= 27:
+        raw, pos = _take(data, pos, 1 << (info - 24))
+        value = int.from_bytes(raw, "big")
+    else:
+        raise CBORDecodeError("indefinite-length CBOR items are not supported")
+
+    if major == _UINT:
+        return value, pos
+    if major == _NEGINT:
+        return -1 - value, pos
+    if major == _BYTES:
+        return _take(data, pos, value)
+    if major == _TEXT:
+        raw, pos = _take(data, pos, value)
+        try:
+            return raw.decode("utf-8"), pos
+        except UnicodeDecodeError as e:
+            raise CBORDecodeError(f"invalid UTF-8 in CBOR text: {e}") from None
+    if major == _ARRAY:
+        items = []
+        for _ in range(value):
+            item, pos = _decode(data, pos, depth + 1)
+            items.append(item)
+        return items, pos
+    if major == _MAP:
+        result = {}
+        for _ in range(value):
+            key, pos = _decode(data, pos, depth + 1)
+            if isinstance(key, (list, dict)):
+                raise CBORDecodeError("unhashable CBOR map key")
+            result[key], pos = _decode(data, pos, depth + 1)
+        return result, pos
+    # _TAG: the tagged item is returned as it is
+    return _decode(data, pos, depth + 1)
+
+
+def loads(data: bytes) -> Any:
+    """
+    Decode one CBOR item that makes up all of data.
+
+    Raises:
+        CBORDecodeError: If data is truncated, has trailing bytes or uses
+            an unsupported feature
+    """
+    value, pos = _decode(bytes(data), 0, 0)
+    if pos != len(data):
+        raise CBORDecodeError(f"{len(data) - pos} bytes after the CBOR item")
+    return value
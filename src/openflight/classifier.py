@@ -0,0 +1,158 @@
+"""
+Learned shot/not-shot classifier.
+
+The rule-based filters in LaunchMonitor (shot duration, peak magnitude,
+minimum ball speed) are hand-tuned for one setup, and people walking past
+or practice swings near the thresholds still get through. With a model
+file (--shot-classifier), each candidate cluster of readings is instead
+scored by a logistic regression over features the monitor already looks
+at, and kept if the predicted probability of a real shot reaches the
+model's threshold.
+
+The model is a small JSON file, so inference needs no extra packages:
+
+    {
+      "version": 1,
+      "features": ["reading_count", "duration_sec", ...],
+      "mean": [...], "scale": [...],     # Standardization
+      "weights": [...], "bias": -1.2,
+      "threshold": 0.5
+    }
+
+Models are trained offline from labeled sessions.
+"""
+
+import json
+import math
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Sequence
+
+from .frames import SpeedReading
+
+MODEL_VERSION = 1
+
+# Features of a candidate cluster, in model order
+FEATURES = [
+    "reading_count",
+    "duration_sec",
+    "peak_speed_mph",
+    "speed_spread_mph",
+    "log_peak_magnitude",
+    "max_frame_objects",
+    "peak_position",
+]
+
+
+def extract_features(readings: Sequence[SpeedReading]) -> Dict[str, float]:
+    """
+    Features of a candidate cluster of readings.
+
+    Args:
+        readings: Readings of one shot window, in time order
+
+    Returns:
+        Feature name to value, for every name in FEATURES
+    """
+    if not readings:
+        return {name: 0.0 for name in FEATURES}
+
+    speeds = [r.speed for r in readings]
+    times = [r.timestamp or 0.0 for r in readings]
+    magnitudes = [r.magnitude for r in readings if r.magnitude]
+    peak = speeds.index(max(speeds))
+    return {
+        "reading_count": float(len(readings)),
+        "duration_sec": times[-1] - times[0],
+        "peak_speed_mph": float(max(speeds)),
+        "speed_spread_mph": float(max(speeds) - min(speeds)),
+        "log_peak_magnitude": math.log1p(max(magnitudes)) if magnitudes else 0.0,
+        "max_frame_objects": float(max(len(r.frame_objects or []) for r in readings)),
+        # Where the fastest reading falls: the ball comes after the club
+        "peak_position": peak / (len(readings) - 1) if len(readings) > 1 else 1.0,
+    }
+
+
+@dataclass
+class ShotClassifier:
+    """Logistic regression over standardized cluster features."""
+
+    weights: List[float]
+    bias: float
+    mean: List[float]
+    scale: List[float]
+    features: List[str] = field(default_factory=lambda: list(FEATURES))
+    threshold: float = 0.5
+    metrics: Optional[Dict[str, Any]] = None  # Evaluation results from training
+
+    def __post_init__(self):
+        n = len(self.features)
+        if not len(self.weights) == len(self.mean) == len(self.scale) == n:
+            raise ValueError("weights, mean and scale must have one value per feature")
+        unknown = sorted(set(self.features) - set(FEATURES))
+        if unknown:
+            raise ValueError(f"Unknown feature(s): {', '.join(unknown)}")
+
+    def probability(self, features: Dict[str, float]) -> float:
+        """Predicted probability that a cluster with these features is a shot."""
+        z = self.bias
+        for name, weight, mean, scale in zip(self.features, self.weights, self.mean, self.scale):
+            z += weight * (features[name] - mean) / (scale or 1.0)
+        # Numerically stable sigmoid
+        if z >= 0:
+            return 1.0 / (1.0 + math.exp(-z))
+        e = math.exp(z)
+        return e / (1.0 + e)
+
+    def classify(self, readings: Sequence[SpeedReading]) -> float:
+        """Probability that these readings (one shot window, in time order) are a shot."""
+        return self.probability(extract_features(readings))
+
+    def to_dict(self) -> dict:
+        """Serialize for a model file."""
+        return {"version": MODEL_VERSION, **asdict(self)}
+
+    @classmethod
+    def from_dict(cls, data: dict) -> "ShotClassifier":
+        """
+        Load from a model file's contents.
+
+        Raises:
+            ValueError: If the model is malformed or from a newer version
+        """
+        if not isinstance(data, dict):
+            raise ValueError("model must be a JSON object")
+        if data.get("version", MODEL_VERSION) > MODEL_VERSION:
+            raise ValueError(f"model version {data['version']} is newer than supported")
+        try:
+            return cls(
+                weights=[float(w) for w in data["weights"]],
+                bias=float(data["bias"]),
+                mean=[float(m) for m in data["mean"]],
+                scale=[float(s) for s in data["scale"]],
+                features=list(data.get("features", FEATURES)),
+                threshold=float(data.get("threshold", 0.5)),
+                metrics=data.get("metrics"),
+            )
+        except (KeyError, TypeError) as e:
+            raise ValueError(f"malformed model: {e}") from None
+
+
+def load_classifier(path: str) -> ShotClassifier:
+    """
+    Load a model file.
+
+    Raises:
+        ValueError: If the file isn't a valid model
+        OSError: If it can't be read
+    """
+    try:
+        data = json.loads(Path(path).read_text(encoding="utf-8"))
+    except json.JSONDecodeError as e:
+        raise ValueError(f"{path}: not JSON ({e})") from None
+    return ShotClassifier.from_dict(data)
+
+
+def save_classifier(classifier: ShotClassifier, path: str):
+    """Write a model file."""
+    Path(path).write_text(json.dumps(classifier.to_dict(), indent=2) + "\n", encoding="utf-8")
@@ -0,0 +1,301 @@
+"""
+Train a shot classifier from labeled sessions.
+
+Record sessions with the session logger on, then label the detected shots
+as real (true) or not (false: a practice swing, someone walking past).
+Labels are a JSON file keyed by session file name (without .jsonl) and
+shot number:
+
+    {
+      "session_20250601_100000_range": {"1": true, "2": false, "3": true},
+      "session_20250602_180000_garage": {"7": false}
+    }
+
+Features are extracted from each labeled shot's logged readings, a
+logistic regression is fit on part of them and evaluated on the rest, and
+the model file is written for --shot-classifier. Unlabeled shots, shots
+logged without readings and mock/injected shots are left out.
+
+Usage:
+    openflight-train --labels labels.json --sessions ~/openflight_sessions/
+    openflight-train --labels labels.json --sessions logs/ --output garage.json --threshold 0.7
+"""
+
+import argparse
+import json
+import math
+import random
+import sys
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Dict, List, Tuple
+
+from .classifier import FEATURES, ShotClassifier, extract_features, save_classifier
+from .reprocess import OUTPUT_SUFFIX, readings_from_entry
+
+# Shots that did not come from the radar have no real readings to learn from
+_SKIPPED_MODES = ("mock", "injected")
+
+
+@dataclass
+class Example:
+    """One labeled shot."""
+
+    session: str
+    shot_number: int
+    features: Dict[str, float]
+    is_shot: bool
+
+
+def load_labels(path: str) -> Dict[Tuple[str, int], bool]:
+    """
+    Read a labels file.
+
+    Args:
+        path: JSON file of session name -> shot number -> true/false
+
+    Returns:
+        (session, shot number) -> whether it was a real shot
+
+    Raises:
+        ValueError: If the file isn't in the expected shape
+    """
+    try:
+        data = json.loads(Path(path).read_text(encoding="utf-8"))
+    except json.JSONDecodeError as e:
+        raise ValueError(f"{path}: not JSON ({e})") from None
+    if not isinstance(data, dict):
+        raise ValueError(f"{path}: expected an object of session names")
+
+    labels = {}
+    for session, shots in data.items():
+        if not isinstance(shots, dict):
+            raise ValueError(f"{path}: {session}: expected an object of shot numbers")
+        for number, is_shot in shots.items():
+            if not isinstance(is_shot, bool):
+                raise ValueError(f"{path}: {session} #{number}: label must be true or false")
+            try:
+                labels[(session, int(number))] = is_shot
+            except ValueError:
+                raise ValueError(f"{path}: {session}: {number!r} is not a shot number") from None
+    return labels
+
+
+def load_examples(session_dir: str, labels: Dict[Tuple[str, int], bool]) -> List[Example]:
+    """
+    Extract features of the labeled shots in a directory of session logs.
+
+    Args:
+        session_dir: Directory of session JSONL files
+        labels: Output of load_labels
+
+    Returns:
+        One example per labeled shot with readings, in file order
+    """
+    examples = []
+    paths = sorted(Path(session_dir).expanduser().glob("*.jsonl"))
+    for path in paths:
+        if path.name.endswith(OUTPUT_SUFFIX):
+            continue
+        session = path.stem
+        with open(path, encoding="utf-8") as f:
+            for line in f:
+                try:
+                    entry = json.loads(line)
+                except ValueError:
+                    continue
+                if entry.get("type") != "shot_detected":
+                    continue
+                if entry.get("mode") in _SKIPPED_MODES:
+                    continue
+                is_shot = labels.get((session, entry.get("shot_number")))
+                readings = readings_from_entry(entry)
+                if is_shot is None or not readings:
+                    continue
+                readings.sort(key=lambda r: r.timestamp or 0.0)
+                examples.append(
+                    Example(session, entry["shot_number"], extract_features(readings), is_shot)
+                )
+    return examples
+
+
+def split_examples(
+    examples: List[Example], test_fraction: float, seed: int = 0
+) -> Tuple[List[Example], List[Example]]:
+    """
+    Shuffle examples into training and test sets.
+
+    Args:
+        examples: Labeled examples
+        test_fraction: Share held out for evaluation (0 = none)
+        seed: Shuffle seed, so runs are repeatable
+
+    Returns:
+        (training, test) examples
+    """
+    shuffled = list(examples)
+    random.Random(seed).shuffle(shuffled)
+    test_count = int(round(len(shuffled) * test_fraction))
+    return shuffled[test_count:], shuffled[:test_count]
+
+
+def train(
+    examples: List[Example],
+    threshold: float = 0.5,
+    l2: float = 0.01,
+    epochs: int = 2000,
+    learning_rate: float = 0.5,
+) -> ShotClassifier:
+    """
+    Fit a logistic regression by batch gradient descent.
+
+    Args:
+        examples: Training examples, with both real and non-shots
+        threshold: Probability at which the model keeps a shot
+        l2: Weight penalty, so a few labels don't give extreme weights
+        epochs: Gradient descent steps
+        learning_rate: Step size (features are standardized first)
+
+    Returns:
+        Trained classifier
+
+    Raises:
+        ValueError: If the examples don't include both classes
+    """
+    if len({e.is_shot for e in examples}) < 2:
+        raise ValueError("need labeled examples of both real shots and non-shots")
+
+    n = len(examples)
+    columns = [[e.features[name] for e in examples] for name in FEATURES]
+    mean = [sum(c) / n for c in columns]
+    scale = [
+        math.sqrt(sum((v - m) ** 2 for v in c) / n) or 1.0 for c, m in zip(columns, mean)
+    ]
+    rows = [
+        [(e.features[name] - m) / s for name, m, s in zip(FEATURES, mean, scale)]
+        for e in examples
+    ]
+    targets = [1.0 if e.is_shot else 0.0 for e in examples]
+
+    # Fit on standardized rows, then attach the standardization to the result
+    n_features = len(FEATURES)
+    fit = ShotClassifier(
+        weights=[0.0] * n_features, bias=0.0, mean=[0.0] * n_features, scale=[1.0] * n_features
+    )
+    for _ in range(epochs):
+        grad_w = [l2 * w for w in fit.weights]
+        grad_b = 0.0
+        for row, target in zip(rows, targets):
+            error = fit.probability(dict(zip(FEATURES, row))) - target
+            for i, value in enumerate(row):
+                grad_w[i] += error * value / n
+            grad_b += error / n
+        fit.weights = [w - learning_rate * g for w, g in zip(fit.weights, grad_w)]
+        fit.bias -= learning_rate * grad_b
+
+    return ShotClassifier(
+        weights=fit.weights, bias=fit.bias, mean=mean, scale=scale, threshold=threshold
+    )
+
+
+def evaluate(classifier: ShotClassifier, examples: List[Example]) -> Dict[str, float]:
+    """
+    Score a classifier on labeled examples.
+
+    Returns:
+        examples, accuracy, precision, recall and log_loss (precision and
+        recall are 0 when undefined)
+    """
+    if not examples:
+        return {"examples": 0}
+
+    true_pos = false_pos = false_neg = correct = 0
+    log_loss = 0.0
+    for example in examples:
+        p = classifier.probability(example.features)
+        predicted = p >= classifier.threshold
+        correct += predicted == example.is_shot
+        true_pos += predicted and example.is_shot
+        false_pos += predicted and not example.is_shot
+        false_neg += not predicted and example.is_shot
+        p = min(max(p, 1e-12), 1 - 1e-12)
+        log_loss -= math.log(p if example.is_shot else 1 - p)
+
+    return {
+        "examples": len(examples),
+        "accuracy": round(correct / len(examples), 4),
+        "precision": round(true_pos / (true_pos + false_pos), 4) if true_pos + false_pos else 0.0,
+        "recall": round(true_pos / (true_pos + false_neg), 4) if true_pos + false_neg else 0.0,
+        "log_loss": round(log_loss / len(examples), 4),
+    }
+
+
+def _print_metrics(name: str, metrics: Dict[str, float]):
+    if not metrics.get("examples"):
+        print(f"{name:<9} (no examples)")
+        return
+    print(
+        f"{name:<9} {metrics['examples']:>5} {metrics['accuracy']:>9.1%} "
+        f"{metrics['precision']:>10.1%} {metrics['recall']:>7.1%} {metrics['log_loss']:>9.3f}"
+    )
+
+
+def main():
+    """CLI entry point for classifier training."""
+    parser = argparse.ArgumentParser(
+        description="Train a shot/not-shot classifier from labeled sessions"
+    )
+    parser.add_argument("--labels", required=True, help="JSON file of shot labels")
+    parser.add_argument("--sessions", required=True, help="Directory of session log files")
+    parser.add_argument("--output", default="shot_classifier.json", help="Model file to write")
+    parser.add_argument(
+        "--test-fraction",
+        type=float,
+        default=0.2,
+        help="Share of labeled shots held out for evaluation",
+    )
+    parser.add_argument(
+        "--threshold", type=float, default=0.5, help="Probability at which a shot is kept"
+    )
+    parser.add_argument("--seed", type=int, default=0, help="Seed for the train/test split")
+    args = parser.parse_args()
+
+    if not 0 <= args.test_fraction < 1:
+        parser.error("--test-fraction must be at least 0 and below 1")
+    if not 0 < args.threshold < 1:
+        parser.error("--threshold must be between 0 and 1")
+
+    try:
+        labels = load_labels(args.labels)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+
+    examples = load_examples(args.sessions, labels)
+    if not examples:
+        print("No labeled shots with readings found in the given sessions")
+        sys.exit(1)
+    positives = sum(e.is_shot for e in examples)
+    print(f"{len(examples)} labeled shots ({positives} real, {len(examples) - positives} not)")
+
+    training, test = split_examples(examples, args.test_fraction, args.seed)
+    try:
+        classifier = train(training, threshold=args.threshold)
+    except ValueError as e:
+        print(f"Can't train: {e}")
+        sys.exit(1)
+
+    metrics = {"train": evaluate(classifier, training), "test": evaluate(classifier, test)}
+    classifier.metrics = metrics
+    print(
+        f"\n{'Set':<9} {'Shots':>5} {'Accuracy':>9} {'Precision':>10} {'Recall':>7} "
+        f"{'Log loss':>9}"
+    )
+    _print_metrics("train", metrics["train"])
+    _print_metrics("test", metrics["test"])
+
+    save_classifier(classifier, args.output)
+    print(f"\nSaved model to {args.output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,63 @@
+"""
+Monotonic timestamps with a wall-clock mapping.
+
+Radar readings and I/Q blocks are stamped with time.monotonic(), so shot
+windows, gaps and durations stay right when NTP steps the system clock
+mid-session (common on a Pi without a real-time clock that syncs once it
+reaches the network).
+
+Wall-clock times for logs and exports come from one mapping per session,
+taken when the session starts:
+
+    wall = wall_epoch + (monotonic - monotonic_epoch)
+
+so times within a session stay in order and evenly spaced even if the
+system clock jumps; the mapping is logged in session_start.
+"""
+
+import time
+from dataclasses import asdict, dataclass
+from datetime import datetime
+from typing import Optional
+
+
+@dataclass(frozen=True)
+class SessionClock:
+    """Mapping from time.monotonic() to Unix time."""
+
+    monotonic_epoch: float
+    wall_epoch: float
+
+    @classmethod
+    def start(cls) -> "SessionClock":
+        """A mapping taken now."""
+        return cls(monotonic_epoch=time.monotonic(), wall_epoch=time.time())
+
+    def to_wall(self, monotonic_time: Optional[float]) -> Optional[float]:
+        """Unix time for a monotonic timestamp (None stays None)."""
+        if monotonic_time is None:
+            return None
+        return self.wall_epoch + (monotonic_time - self.monotonic_epoch)
+
+    def to_datetime(self, monotonic_time: float) -> datetime:
+        """Local datetime for a monotonic timestamp."""
+        return datetime.fromtimestamp(self.to_wall(monotonic_time))
+
+    def to_dict(self) -> dict:
+        """For the session log."""
+        return asdict(self)
+
+
+_clock = SessionClock.start()
+
+
+def get_clock() -> SessionClock:
+    """The current session's clock mapping."""
+    return _clock
+
+
+def reset_clock() -> SessionClock:
+    """Take a new mapping, e.g. at session start."""
+    global _clock  # pylint: disable=global-statement
+    _clock = SessionClock.start()
+    return _clock
@@ -0,0 +1,536 @@
+"""
+Upload session logs to S3 or WebDAV after each session.
+
+A Pi in the garage is easy to forget about until its SD card dies. With a
+sync target configured, each session log is queued for upload when the
+session ends, and uploads run on a background thread with retries and
+backoff. The queue is kept in a state file next to the logs, so uploads
+that fail (network down, Pi switched off) are picked up again on the next
+start, and files already uploaded aren't sent twice unless they change.
+
+Targets:
+    s3://bucket/prefix            AWS S3, or any S3-compatible store with
+                                  --sync-endpoint (MinIO, Backblaze B2, R2).
+                                  Credentials from AWS_ACCESS_KEY_ID,
+                                  AWS_SECRET_ACCESS_KEY (and AWS_SESSION_TOKEN),
+                                  region from AWS_REGION (default us-east-1)
+    https://host/dav/path/        WebDAV (Nextcloud, a NAS...). Credentials
+                                  from OPENFLIGHT_DAV_USER and
+                                  OPENFLIGHT_DAV_PASSWORD
+
+A session's voice memos (see voice_memo) are uploaded with its log. Files
+are uploaded whole; an upload cut off part way is retried from the start
+of that file.
+
+Usage:
+    openflight-server --sync-to s3://my-bucket/openflight
+    openflight-sync s3://my-bucket/openflight            # upload anything not yet synced
+    openflight-sync https://nas.local/dav/golf/ --log-dir /mnt/sessions
+"""
+
+import argparse
+import base64
+import hashlib
+import hmac
+import json
+import logging
+import os
+import queue
+import sys
+import threading
+import urllib.error
+import urllib.parse
+import urllib.request
+from dataclasses import dataclass
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Dict, List, Optional
+
+from .session_logger import SessionLogger
+from .voice_memo import session_memos
+
+logger = logging.getLogger(__name__)
+
+STATE_FILE = ".openflight_sync.json"
+
+
+class UploadError(Exception):
+    """An upload failed; retryable unless the server rejected it outright."""
+
+    def __init__(self, message: str, retryable: bool = True):
+        super().__init__(message)
+        self.retryable = retryable
+
+
+def _check_response(url: str, error: urllib.error.HTTPError):
+    """Raise UploadError for an HTTP error, retryable for 408/429/5xx."""
+    retryable = error.code in (408, 429) or error.code >= 500
+    raise UploadError(f"{url}: HTTP {error.code}", retryable=retryable) from None
+
+
+class S3Uploader:
+    """PUTs objects to an S3 bucket with AWS Signature Version 4."""
+
+    def __init__(
+        self,
+        bucket: str,
+        prefix: str,
+        access_key: str,
+        secret_key: str,
+        region: str = "us-east-1",
+        endpoint: Optional[str] = None,
+        session_token: Optional[str] = None,
+        timeout_sec: float = 60.0,
+    ):
+        """
+        Initialize S3 uploader.
+
+        Args:
+            bucket: Bucket name
+            prefix: Key prefix ("" = bucket root)
+            access_key: Access key ID
+            secret_key: Secret access key
+            region: Bucket region
+            endpoint: S3-compatible endpoint (default: AWS for the region)
+            session_token: Temporary credentials token
+            timeout_sec: Per-request timeout
+        """
+        self.bucket = bucket
+        self.prefix = prefix.strip("/")
+        self.access_key = access_key
+        self.secret_key = secret_key
+        self.region = region
+        self.endpoint = (endpoint or f"https://s3.{region}.amazonaws.com").rstrip("/")
+        self.session_token = session_token
+        self.timeout_sec = timeout_sec
+
+    def describe(self) -> str:
+        """Target as a URL, for messages."""
+        return f"s3://{self.bucket}/{self.prefix}"
+
+    def object_url(self, name: str) -> str:
+        """Path-style URL of the object a file is uploaded to."""
+        key = f"{self.prefix}/{name}" if self.prefix else name
+        return f"{self.endpoint}/{self.bucket}/{urllib.parse.quote(key, safe='/-_.~')}"
+
+    def signed_headers(self, url: str, body: bytes, now: datetime) -> Dict[str, str]:
+        """Headers for a signed PUT of body to url."""
+        parsed = urllib.parse.urlsplit(url)
+        amz_date = now.strftime("%Y%m%dT%H%M%SZ")
+        datestamp = now.strftime("%Y%m%d")
+        payload_hash = hashlib.sha256(body).hexdigest()
+
+        headers = {
+            "host": parsed.netloc,
+            "x-amz-content-sha256": payload_hash,
+            "x-amz-date": amz_date,
+        }
+        if self.session_token:
+            headers["x-amz-security-token"] = self.session_token
+        names = sorted(headers)
+        canonical_request = "\n".join(
+            [
+                "PUT",
+                parsed.path,
+                "",
+                "".join(f"{name}:{headers[name]}\n" for name in names),
+                ";".join(names),
+                payload_hash,
+            ]
+        )
+        scope = f"{datestamp}/{self.region}/s3/aws4_request"
+        string_to_sign = "\n".join(
+            [
+                "AWS4-HMAC-SHA256",
+                amz_date,
+                scope,
+                hashlib.sha256(canonical_request.encode("utf-8")).hexdigest(),
+            ]
+        )
+
+        key = f"AWS4{self.secret_key}".encode("utf-8")
+        for part in (datestamp, self.region, "s3", "aws4_request"):
+            key = hmac.new(key, part.encode("utf-8"), hashlib.sha256).digest()
+        signature = hmac.new(key, string_to_sign.encode("utf-8"), hashlib.sha256).hexdigest()
+
+        headers["Authorization"] = (
+            f"AWS4-HMAC-SHA256 Credential={self.access_key}/{scope}, "
+            f"SignedHeaders={';'.join(names)}, Signature={signature}"
+        )
+        del headers["host"]  # urllib sets it
+        return headers
+
+    def upload(self, name: str, body: bytes):
+        """
+        Upload one file.
+
+        Raises:
+            UploadError: If the upload failed
+        """
+        url = self.object_url(name)
+        headers = self.signed_headers(url, body, datetime.now(timezone.utc))
+        request = urllib.request.Request(url, data=body, headers=headers, method="PUT")
+        try:
+            with urllib.request.urlopen(request, timeout=self.timeout_sec):
+                pass
+        except urllib.error.HTTPError as e:
+            _check_response(url, e)
+        except (urllib.error.URLError, OSError) as e:
+            raise UploadError(f"{url}: {getattr(e, 'reason', e)}") from None
+
+
+class WebDavUploader:
+    """PUTs files to a WebDAV collection."""
+
+    def __init__(
+        self,
+        base_url: str,
+        username: Optional[str] = None,
+        password: Optional[str] = None,
+        timeout_sec: float = 60.0,
+    ):
+        """
+        Initialize WebDAV uploader.
+
+        Args:
+            base_url: Collection URL files are uploaded into (created if missing)
+            username: Basic auth user
+            password: Basic auth password
+            timeout_sec: Per-request timeout
+        """
+        self.base_url = base_url.rstrip("/") + "/"
+        self.timeout_sec = timeout_sec
+        self._headers = {}
+        if username:
+            token = base64.b64encode(f"{username}:{password or ''}".encode("utf-8")).decode()
+            self._headers["Authorization"] = f"Basic {token}"
+        self._collection_ready = False
+
+    def describe(self) -> str:
+        """Target as a URL, for messages."""
+        return self.base_url
+
+    def _request(self, method: str, url: str, body: Optional[bytes] = None):
+        request = urllib.request.Request(url, data=body, headers=self._headers, method=method)
+        with urllib.request.urlopen(request, timeout=self.timeout_sec):
+            pass
+
+    def upload(self, name: str, body: bytes):
+        """
+        Upload one file.
+
+        Raises:
+            UploadError: If the upload failed
+        """
+        url = self.base_url + urllib.parse.quote(name)
+        try:
+            if not self._collection_ready:
+                try:
+                    self._request("MKCOL", self.base_url)
+                except urllib.error.HTTPError as e:
+                    if e.code != 405:  # 405: the collection already exists
+                        raise
+                self._collection_ready = True
+            self._request("PUT", url, body)
+        except urllib.error.HTTPError as e:
+            _check_response(url, e)
+        except (urllib.error.URLError, OSError) as e:
+            raise UploadError(f"{url}: {getattr(e, 'reason', e)}") from None
+
+
+def make_uploader(target: str, endpoint: Optional[str] = None, env=None):
+    """
+    Build the uploader for a sync target, with credentials from the environment.
+
+    Args:
+        target: s3://bucket/prefix or an http(s) WebDAV URL
+        endpoint: S3-compatible endpoint URL
+        env: Environment to read credentials from (default: os.environ)
+
+    Raises:
+        ValueError: If the target is not understood or S3 credentials are missing
+    """
+    env = os.environ if env is None else env
+    parsed = urllib.parse.urlsplit(target)
+    if parsed.scheme == "s3":
+        if not parsed.netloc:
+            raise ValueError("S3 target needs a bucket, e.g. s3://my-bucket/openflight")
+        access_key = env.get("AWS_ACCESS_KEY_ID")
+        secret_key = env.get("AWS_SECRET_ACCESS_KEY")
+        if not access_key or not secret_key:
+            raise ValueError("Set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY to sync to S3")
+        return S3Uploader(
+            bucket=parsed.netloc,
+            prefix=parsed.path,
+            access_key=access_key,
+            secret_key=secret_key,
+            region=env.get("AWS_REGION") or env.get("AWS_DEFAULT_REGION") or "us-east-1",
+            endpoint=endpoint,
+            session_token=env.get("AWS_SESSION_TOKEN"),
+        )
+    if parsed.scheme in ("http", "https"):
+        return WebDavUploader(
+            target, env.get("OPENFLIGHT_DAV_USER"), env.get("OPENFLIGHT_DAV_PASSWORD")
+        )
+    raise ValueError(f"Sync target must be s3://bucket/prefix or a WebDAV URL, got {target!r}")
+
+
+@dataclass
+class SyncResult:
+    """Outcome of one pass over the upload queue."""
+
+    uploaded: int = 0
+    failed: int = 0
+    pending: int = 0
+
+
+class CloudSync:
+    """
+    Queue of session files to upload, persisted across restarts.
+
+    Example:
+        sync = CloudSync(make_uploader("s3://bucket/openflight"), log_dir)
+        sync.start()  # retries anything left over from last time
+        sync.enqueue(session_path)
+        ...
+        sync.stop()
+    """
+
+    DEFAULT_MAX_ATTEMPTS = 5  # Attempts per file per pass
+    DEFAULT_BACKOFF_SEC = 2.0  # Delay before first retry, doubled each attempt
+    MAX_BACKOFF_SEC = 60.0  # Cap on delay between retries
+
+    def __init__(
+        self,
+        uploader,
+        state_dir: Path,
+        max_attempts: int = DEFAULT_MAX_ATTEMPTS,
+        backoff_sec: float = DEFAULT_BACKOFF_SEC,
+    ):
+        """
+        Initialize cloud sync.
+
+        Args:
+            uploader: S3Uploader or WebDavUploader
+            state_dir: Directory for the queue state file (the session log dir)
+            max_attempts: Upload attempts per file before leaving it for next time
+            backoff_sec: Initial retry delay in seconds (doubles per attempt)
+        """
+        self.uploader = uploader
+        self.state_path = Path(state_dir) / STATE_FILE
+        self.max_attempts = max(1, max_attempts)
+        self.backoff_sec = backoff_sec
+
+        self._lock = threading.Lock()
+        self._state = self._load_state()
+        self._wake: "queue.Queue[Optional[bool]]" = queue.Queue()
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    def _load_state(self) -> dict:
+        try:
+            state = json.loads(self.state_path.read_text(encoding="utf-8"))
+        except (OSError, ValueError):
+            state = {}
+        target = self.uploader.describe()
+        if state.get("target") != target:
+            # Different destination: nothing has been uploaded there yet
+            state = {"target": target, "pending": state.get("pending", []), "uploaded": {}}
+        return state
+
+    def _save_state(self):
+        temp = self.state_path.with_name(self.state_path.name + ".tmp")
+        try:
+            self.state_path.parent.mkdir(parents=True, exist_ok=True)
+            temp.write_text(json.dumps(self._state, indent=2), encoding="utf-8")
+            os.replace(temp, self.state_path)
+        except OSError as e:
+            logger.warning("Could not save sync state: %s", e)
+
+    @property
+    def pending(self) -> List[str]:
+        """Files waiting to be uploaded."""
+        with self._lock:
+            return list(self._state["pending"])
+
+    def _fingerprint(self, path: Path) -> dict:
+        stat = path.stat()
+        return {"size": stat.st_size, "mtime": stat.st_mtime}
+
+    def needs_upload(self, path: str) -> bool:
+        """True if the file was never uploaded, or changed since."""
+        source = Path(path)
+        if not source.is_file():
+            return False
+        with self._lock:
+            done = self._state["uploaded"].get(str(source.resolve()))
+        if done is None:
+            return True
+        return {k: done.get(k) for k in ("size", "mtime")} != self._fingerprint(source)
+
+    def enqueue(self, *paths: str):
+        """Queue files for upload; ones already uploaded and unchanged are dropped unsent."""
+        with self._lock:
+            for path in paths:
+                resolved = str(Path(path).resolve())
+                if resolved not in self._state["pending"]:
+                    self._state["pending"].append(resolved)
+            self._save_state()
+        self._wake.put(True)
+
+    def sync_pending(self) -> SyncResult:
+        """
+        Try to upload every queued file once (with retries).
+
+        Files that still fail stay queued for the next pass.
+        """
+        result = SyncResult()
+        for path in self.pending:
+            if self._stop_event.is_set():
+                break
+            if not self.needs_upload(path):
+                self._finish(path, uploaded=False)
+                continue
+            if self._upload_with_retries(Path(path)):
+                self._finish(path, uploaded=True)
+                result.uploaded += 1
+            else:
+                result.failed += 1
+        result.pending = len(self.pending)
+        return result
+
+    def _finish(self, path: str, uploaded: bool):
+        with self._lock:
+            if path in self._state["pending"]:
+                self._state["pending"].remove(path)
+            if uploaded and Path(path).is_file():
+                self._state["uploaded"][path] = {
+                    **self._fingerprint(Path(path)),
+                    "uploaded_at": datetime.now().isoformat(),
+                }
+            self._save_state()
+
+    def _upload_with_retries(self, path: Path) -> bool:
+        delay = self.backoff_sec
+        for attempt in range(1, self.max_attempts + 1):
+            try:
+                self.uploader.upload(path.name, path.read_bytes())
+                print(f"[SYNC] Uploaded {path.name} to {self.uploader.describe()}")
+                return True
+            except OSError as e:
+                logger.warning("Sync: can't read %s: %s", path, e)
+                return False
+            except UploadError as e:
+                logger.warning("Sync: %s (attempt %d/%d)", e, attempt, self.max_attempts)
+                if not e.retryable or attempt == self.max_attempts:
+                    break
+            if self._stop_event.wait(delay):
+                break
+            delay = min(delay * 2, self.MAX_BACKOFF_SEC)
+        print(f"[SYNC] {path.name} not uploaded; will retry next time")
+        return False
+
+    def start(self):
+        """Start the background upload thread (uploads anything left from last time)."""
+        if self._thread and self._thread.is_alive():
+            return
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._worker, daemon=True)
+        self._thread.start()
+        self._wake.put(True)
+
+    def stop(self, timeout: float = 30.0):
+        """
+        Stop after the current pass, waiting up to timeout for queued uploads.
+
+        Anything not uploaded stays in the state file for next time.
+        """
+        if not self._thread:
+            return
+        self._wake.put(None)
+        self._thread.join(timeout=timeout)
+        self._stop_event.set()
+        self._thread = None
+
+    def _worker(self):
+        while True:
+            item = self._wake.get()
+            if item is None:
+                break
+            if self.pending:
+                self.sync_pending()
+
+
+def session_files(session_path: str) -> List[str]:
+    """A session log and its voice memos, which are uploaded together."""
+    return [str(session_path), *(str(p) for p in session_memos(Path(session_path)))]
+
+
+# Global cloud sync instance
+_cloud_sync: Optional[CloudSync] = None
+
+
+def get_cloud_sync() -> Optional[CloudSync]:
+    """Get the global cloud sync instance (None if no target configured)."""
+    return _cloud_sync
+
+
+def init_cloud_sync(
+    target: Optional[str], state_dir: Path, endpoint: Optional[str] = None
+) -> Optional[CloudSync]:
+    """
+    Initialize and start the global cloud sync.
+
+    Args:
+        target: s3://bucket/prefix or WebDAV URL (None disables sync)
+        state_dir: Directory for the queue state file
+        endpoint: S3-compatible endpoint URL
+
+    Returns:
+        CloudSync instance, or None if no target was given
+
+    Raises:
+        ValueError: If the target or credentials are invalid
+    """
+    global _cloud_sync  # pylint: disable=global-statement
+
+    if _cloud_sync:
+        _cloud_sync.stop()
+        _cloud_sync = None
+    if not target:
+        return None
+
+    _cloud_sync = CloudSync(make_uploader(target, endpoint), state_dir)
+    _cloud_sync.start()
+    return _cloud_sync
+
+
+def main():
+    """CLI entry point: upload session logs (and their memos) that haven't been synced yet."""
+    parser = argparse.ArgumentParser(description="Upload session logs to S3 or WebDAV")
+    parser.add_argument("target", help="s3://bucket/prefix or WebDAV URL")
+    parser.add_argument("files", nargs="*", help="Files to upload (default: all session logs)")
+    parser.add_argument(
+        "--log-dir", help="Directory for session logs (default: ~/openflight_sessions)"
+    )
+    parser.add_argument("--endpoint", help="S3-compatible endpoint URL (MinIO, B2, R2...)")
+    args = parser.parse_args()
+
+    log_dir = Path(args.log_dir).expanduser() if args.log_dir else SessionLogger.DEFAULT_LOG_DIR
+    try:
+        sync = CloudSync(make_uploader(args.target, args.endpoint), log_dir)
+    except ValueError as e:
+        parser.error(str(e))
+
+    files = args.files or [
+        f for p in sorted(log_dir.glob("session_*.jsonl")) for f in session_files(str(p))
+    ]
+    sync.enqueue(*[f for f in files if sync.needs_upload(f)])
+    result = sync.sync_pending()
+    print(f"{result.uploaded} uploaded, {result.failed} failed, {result.pending} still pending")
+    if result.failed:
+        sys.exit(1)
+
+
+if __name__ == "__main__":
+    main()
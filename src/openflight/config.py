@@ -0,0 +1,418 @@
+"""
+Layered configuration: defaults < profile < environment < command line.
+
+Containers and kiosk setups configure the server without editing its
+command line, so every openflight-server option can also be set with an
+environment variable named after it:
+
+    OPENFLIGHT_PORT=/dev/ttyACM0               --port /dev/ttyACM0
+    OPENFLIGHT_WEB_PORT=8081                   --web-port 8081
+    OPENFLIGHT_MOCK=1                          --mock (1/true/yes/on or 0/false/no/off)
+    OPENFLIGHT_WEBHOOK_URL=http://a,http://b   --webhook-url for each (comma-separated)
+
+An option given on the command line wins over its variable. --supervise
+and --service choose how the process runs and can't be set this way.
+
+Shot detection thresholds (MonitorConfig fields) are layered the same way,
+each layer overriding the ones before it:
+
+    defaults
+    the calibration profile's "monitor" section (--profile)
+    OPENFLIGHT_MONITOR_<FIELD> variables, e.g. OPENFLIGHT_MONITOR_MIN_SHOT_MAGNITUDE=80
+    the --sensitivity and --ball-type presets
+    --set FIELD=VALUE, e.g. --set min_shot_magnitude=80
+
+Values are JSON if they parse and strings otherwise (filters may also be
+given comma-separated). Errors name the layer the bad value came from.
+
+openflight-config shows the result and where each value came from, for
+the options the server would be started with:
+
+    openflight-config show                     # What's changed from the defaults
+    openflight-config show --effective         # Everything
+    openflight-config show --effective -- --profile garage-net --sensitivity high
+"""
+
+import argparse
+import json
+import os
+import sys
+from dataclasses import fields
+from pathlib import Path
+from typing import Any, Dict, List, Mapping, Optional, Tuple
+
+from .ball_types import apply_ball_type, ball_type_for
+from .launch_monitor import ConfigError, MonitorConfig
+from .profiles import load_profile
+from .sensitivity import apply_sensitivity
+
+ENV_PREFIX = "OPENFLIGHT_"
+MONITOR_ENV_PREFIX = "OPENFLIGHT_MONITOR_"
+
+DEFAULT_SOURCE = "default"
+COMMAND_LINE_SOURCE = "command line"
+
+# Process modes, and --set (which has OPENFLIGHT_MONITOR_* instead)
+_NOT_FROM_ENV = ("help", "supervise", "service", "set")
+_TRUE_WORDS = ("true", "yes", "on")
+_FALSE_WORDS = ("false", "no", "off")
+
+# Options whose values aren't shown
+_SECRET_WORDS = ("token", "secret", "password")
+
+
+def env_name(dest: str) -> str:
+    """The environment variable for an option ("web_port" -> "OPENFLIGHT_WEB_PORT")."""
+    return ENV_PREFIX + dest.upper()
+
+
+def parse_flag(name: str, text: str) -> bool:
+    """
+    An on/off environment variable's value.
+
+    Raises:
+        ValueError: If it's not 1/true/yes/on or 0/false/no/off (or empty)
+    """
+    word = text.strip().lower()
+    if word == "1" or word in _TRUE_WORDS:
+        return True
+    if word in ("0", "") or word in _FALSE_WORDS:
+        return False
+    raise ValueError(f"{name}: expected 1/true/yes/on or 0/false/no/off, got {text!r}")
+
+
+def _convert(action: argparse.Action, name: str, text: str) -> Any:
+    try:
+        value = action.type(text) if action.type else text
+    except (TypeError, ValueError, argparse.ArgumentTypeError) as e:
+        raise ValueError(f"{name}: invalid value {text!r}") from e
+    if action.choices is not None and value not in action.choices:
+        choices = ", ".join(str(c) for c in action.choices)
+        raise ValueError(f"{name}: {text!r} is not one of {choices}")
+    return value
+
+
+def _env_value(action: argparse.Action, name: str, text: str) -> Any:
+    """An option's value from its environment variable, as argparse would have parsed it."""
+    if action.nargs == 0:  # store_true, store_false, store_const
+        return action.const if parse_flag(name, text) else action.default
+    # pylint: disable-next=protected-access
+    if isinstance(action, argparse._AppendAction) or action.nargs in ("+", "*"):
+        return [_convert(action, name, item.strip()) for item in text.split(",") if item.strip()]
+    if action.nargs == "?" and text.strip().lower() in _TRUE_WORDS:
+        return action.const
+    return _convert(action, name, text)
+
+
+def _options(parser: argparse.ArgumentParser) -> List[argparse.Action]:
+    # pylint: disable-next=protected-access
+    return [a for a in parser._actions if a.option_strings and a.dest != "help"]
+
+
+def apply_env_defaults(
+    parser: argparse.ArgumentParser, environ: Mapping[str, str]
+) -> Dict[str, str]:
+    """
+    Make OPENFLIGHT_<OPTION> environment variables the parser's defaults.
+
+    Options given on the command line still win, since they replace the
+    defaults when the parser runs.
+
+    Args:
+        parser: openflight-server's parser
+        environ: Environment (os.environ)
+
+    Returns:
+        Variable used for each option set from the environment, by dest
+
+    Raises:
+        ValueError: If a variable's value isn't valid for its option
+    """
+    used = {}
+    for action in _options(parser):
+        name = env_name(action.dest)
+        if action.dest in _NOT_FROM_ENV or name not in environ:
+            continue
+        action.default = _env_value(action, name, environ[name])
+        used[action.dest] = name
+    return used
+
+
+def option_sources(
+    parser: argparse.ArgumentParser, argv: List[str], env_options: Mapping[str, str]
+) -> Dict[str, str]:
+    """
+    Where each option's value comes from.
+
+    Args:
+        parser: Parser the options were parsed with
+        argv: Command line arguments
+        env_options: What apply_env_defaults() returned
+
+    Returns:
+        COMMAND_LINE_SOURCE, "env VARIABLE" or DEFAULT_SOURCE, by dest
+    """
+    actions = _options(parser)
+    saved = [action.default for action in actions]
+    for action in actions:
+        action.default = argparse.SUPPRESS  # Leaves only what argv gave
+    try:
+        given = vars(parser.parse_known_args(argv)[0])
+    finally:
+        for action, default in zip(actions, saved):
+            action.default = default
+
+    sources = {}
+    for action in actions:
+        if action.dest in given:
+            sources[action.dest] = COMMAND_LINE_SOURCE
+        elif action.dest in env_options:
+            sources[action.dest] = f"env {env_options[action.dest]}"
+        else:
+            sources[action.dest] = DEFAULT_SOURCE
+    return sources
+
+
+def parse_monitor_value(key: str, text: str) -> Any:
+    """A threshold value given as text: JSON if it parses, with comma-separated filters."""
+    try:
+        value = json.loads(text)
+    except ValueError:
+        value = text
+    if key == "filters" and isinstance(value, str):
+        value = [item.strip() for item in value.split(",") if item.strip()]
+    return value
+
+
+def monitor_env_overrides(environ: Mapping[str, str]) -> Dict[str, Tuple[str, Any]]:
+    """
+    Thresholds set by OPENFLIGHT_MONITOR_<FIELD> variables.
+
+    Returns:
+        (variable, value) by field name
+    """
+    overrides = {}
+    for name in sorted(environ):
+        if name.startswith(MONITOR_ENV_PREFIX):
+            key = name[len(MONITOR_ENV_PREFIX):].lower()
+            overrides[key] = (name, parse_monitor_value(key, environ[name]))
+    return overrides
+
+
+def parse_assignments(items: List[str]) -> Dict[str, Any]:
+    """
+    --set FIELD=VALUE arguments as thresholds (the last wins if a field repeats).
+
+    Raises:
+        ValueError: If one isn't FIELD=VALUE
+    """
+    updates = {}
+    for item in items:
+        key, sep, text = item.partition("=")
+        if not sep or not key:
+            raise ValueError(f"--set: expected FIELD=VALUE, got {item!r}")
+        updates[key] = parse_monitor_value(key, text)
+    return updates
+
+
+class ConfigLayers:
+    """
+    A MonitorConfig built up layer by layer, remembering where each value came from.
+
+    Example:
+        layers = ConfigLayers()
+        layers.apply("profile garage", profile.monitor)
+        layers.set("--set", {"min_shot_magnitude": 80})
+        layers.config.min_shot_magnitude  # 80
+        layers.sources["min_shot_magnitude"]  # "--set"
+    """
+
+    def __init__(self):
+        """Start from the defaults."""
+        self.config = MonitorConfig()
+        self.sources: Dict[str, str] = {f.name: DEFAULT_SOURCE for f in fields(self.config)}
+
+    @property
+    def changed(self) -> bool:
+        """Whether any layer changed a threshold."""
+        return any(source != DEFAULT_SOURCE for source in self.sources.values())
+
+    def update(self, updates: Dict[str, Any], sources: Dict[str, str]):
+        """
+        Apply a layer whose thresholds each have their own source.
+
+        The thresholds are validated together, so a layer can change
+        settings that are only consistent with each other (e.g. a min and
+        a max) in any order.
+
+        Raises:
+            ValueError: If the result is invalid, naming the offending source
+        """
+        try:
+            self.config = self.config.with_updates(updates)
+        except ConfigError as e:
+            source = sources.get(e.key) or ", ".join(sorted(set(sources.values())))
+            raise ValueError(f"{source}: {e}") from e
+        self.sources.update({key: sources[key] for key in updates})
+
+    def set(self, source: str, updates: Dict[str, Any]):
+        """
+        Apply a layer's thresholds.
+
+        Raises:
+            ValueError: If the result is invalid
+        """
+        self.update(updates, {key: source for key in updates})
+
+    def apply(self, source: str, config: MonitorConfig):
+        """Apply a whole config, crediting the layer with the values it changed."""
+        current = self.config.to_dict()
+        self.set(source, {k: v for k, v in config.to_dict().items() if v != current[k]})
+
+    def rows(self, effective: bool = True) -> List[Tuple[str, Any, str]]:
+        """(field, value, source) rows; only changed values unless effective."""
+        return [
+            (key, value, self.sources[key])
+            for key, value in self.config.to_dict().items()
+            if effective or self.sources[key] != DEFAULT_SOURCE
+        ]
+
+
+def build_monitor_layers(
+    profile: Optional[Any],
+    environ: Mapping[str, str],
+    sensitivity: Optional[str] = None,
+    ball_type: Optional[str] = None,
+    assignments: Optional[Dict[str, Any]] = None,
+    mode: Optional[str] = None,
+) -> ConfigLayers:
+    """
+    Layer the server's shot detection thresholds.
+
+    Args:
+        profile: CalibrationProfile loaded with --profile (None for none)
+        environ: Environment, for OPENFLIGHT_MONITOR_* variables
+        sensitivity: --sensitivity preset
+        ball_type: --ball-type ball type (as ball_type_for() gives it)
+        assignments: --set thresholds (parse_assignments())
+        mode: Radar mode ("streaming", "swing-speed" or "mock"), for the
+            profile's shot timing for it
+
+    Raises:
+        ValueError: If a layer's values are invalid, naming the layer
+    """
+    layers = ConfigLayers()
+    if profile is not None:
+        layers.apply(f"profile {profile.name}", profile.monitor)
+        timing = profile.shot_timing.for_mode(mode) if mode else {}
+        if timing:
+            layers.update(timing, {key: f"profile {profile.name} ({mode})" for key in timing})
+    overrides = monitor_env_overrides(environ)
+    if overrides:
+        layers.update(
+            {key: value for key, (_, value) in overrides.items()},
+            {key: f"env {name}" for key, (name, _) in overrides.items()},
+        )
+    if sensitivity:
+        layers.apply(f"--sensitivity {sensitivity}", apply_sensitivity(layers.config, sensitivity))
+    if ball_type:
+        layers.apply(f"--ball-type {ball_type}", apply_ball_type(layers.config, ball_type))
+    if assignments:
+        layers.update(assignments, {key: f"--set {key}" for key in assignments})
+    return layers
+
+
+def _is_secret(dest: str) -> bool:
+    return any(word in dest for word in _SECRET_WORDS)
+
+
+def _display(value: Any) -> str:
+    if isinstance(value, (list, tuple)):
+        return ", ".join(str(v) for v in value) or "[]"
+    return "-" if value is None else str(value)
+
+
+def _print_rows(title: str, rows: List[Tuple[str, str, str]]):
+    print(f"{title}:")
+    if not rows:
+        print("  (all defaults)")
+        return
+    width = max(len(name) for name, _, _ in rows)
+    for name, value, source in rows:
+        print(f"  {name:<{width}}  {value}  ({source})")
+
+
+def main(argv: Optional[List[str]] = None):
+    """CLI entry point: show the layered configuration."""
+    parser = argparse.ArgumentParser(
+        description="Show OpenFlight's configuration and where each value comes from"
+    )
+    commands = parser.add_subparsers(dest="command", required=True)
+    show = commands.add_parser("show", help="Show the server's options and detection thresholds")
+    show.add_argument(
+        "--effective",
+        action="store_true",
+        help="Show every value, not just those changed from the defaults",
+    )
+    show.add_argument("--json", action="store_true", help="Print JSON")
+    show.add_argument(
+        "server_args",
+        nargs=argparse.REMAINDER,
+        help="openflight-server options to include, after --",
+    )
+    args = parser.parse_args(argv)
+
+    from .server import build_parser  # pylint: disable=import-outside-toplevel,cyclic-import
+
+    server_argv = args.server_args[1:] if args.server_args[:1] == ["--"] else args.server_args
+    server_parser = build_parser()
+    try:
+        env_options = apply_env_defaults(server_parser, os.environ)
+    except ValueError as e:
+        parser.error(str(e))
+    server_args = server_parser.parse_args(server_argv)
+    sources = option_sources(server_parser, server_argv, env_options)
+
+    try:
+        profile = None
+        if server_args.profile:
+            directory = server_args.profile_dir and Path(server_args.profile_dir).expanduser()
+            profile = load_profile(server_args.profile, directory or None)
+        layers = build_monitor_layers(
+            profile,
+            os.environ,
+            sensitivity=server_args.sensitivity,
+            ball_type=ball_type_for(server_args.ball_type),
+            assignments=parse_assignments(server_args.set),
+            mode="mock" if server_args.mock else server_args.mode,
+        )
+    except (FileNotFoundError, ValueError) as e:
+        parser.error(str(e))
+
+    options = {}
+    for action in _options(server_parser):
+        source = sources[action.dest]
+        if action.dest == "set" or not (args.effective or source != DEFAULT_SOURCE):
+            continue
+        value = getattr(server_args, action.dest)
+        if _is_secret(action.dest) and value:
+            value = "********"
+        options[max(action.option_strings, key=len)] = (value, source)
+    monitor = {key: (value, source) for key, value, source in layers.rows(args.effective)}
+
+    if args.json:
+        data = {
+            section: {name: {"value": v, "source": s} for name, (v, s) in values.items()}
+            for section, values in (("options", options), ("monitor", monitor))
+        }
+        json.dump(data, sys.stdout, indent=2, default=str)
+        print()
+        return 0
+
+    _print_rows("Options", [(n, _display(v), s) for n, (v, s) in options.items()])
+    _print_rows("Detection thresholds", [(n, _display(v), s) for n, (v, s) in monitor.items()])
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
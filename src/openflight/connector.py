@@ -0,0 +1,136 @@
+"""
+Polling shot feed for tablet golf apps.
+
+Several tablet apps (Awesome Golf and others built on the same connector
+layout) take third-party launch monitors by polling a small HTTP feed
+on the local network rather than holding a socket open. With --connector
+the server serves that layout, so those apps can use OpenFlight as is:
+
+    openflight-server --connector
+    # In the app: third-party launch monitor, http://raspberrypi.local:8080
+
+The app polls three endpoints, a few times a second:
+
+    GET /connector/status          Device, Connected, Ready, LastShotNumber
+    GET /connector/shot            The last shot (204 until there is one)
+    GET /connector/shots?since=N   Shots after number N, for catching up
+
+A changed ShotNumber is a new shot. Shots carry BallData (Speed in mph,
+VLA/HLA launch angles in degrees, TotalSpin in rpm, CarryDistance in
+yards) and ClubData (Speed, SmashFactor); what the monitor didn't
+measure, e.g. launch angles without a camera, is left out rather than
+sent as 0. The feed runs off the server's event bus, so it follows
+every shot however it was detected; a mulligan takes the shot back out
+of the catch-up list (numbers aren't reused). Like the UI, the feed
+needs no API token; --allow still applies.
+"""
+
+import threading
+from collections import deque
+from typing import Any, Deque, Dict, List, Optional
+
+DEVICE_NAME = "OpenFlight"
+HISTORY_SIZE = 100  # Shots kept for /connector/shots
+
+# Feed key -> shot_to_dict key
+_BALL_FIELDS = {
+    "Speed": "ball_speed_mph",
+    "VLA": "launch_angle_vertical",
+    "HLA": "launch_angle_horizontal",
+    "TotalSpin": "spin_rpm",
+    "CarryDistance": "estimated_carry_yards",
+}
+_CLUB_FIELDS = {
+    "Speed": "club_speed_mph",
+    "SmashFactor": "smash_factor",
+}
+
+
+def _measured(shot: Dict[str, Any], field_map: Dict[str, str]) -> Dict[str, Any]:
+    return {key: shot[name] for key, name in field_map.items() if shot.get(name) is not None}
+
+
+def connector_shot(shot_number: int, shot: Dict[str, Any]) -> dict:
+    """
+    A shot in the connector layout.
+
+    Args:
+        shot_number: The feed's number for the shot
+        shot: The shot as shot_to_dict() gives it
+    """
+    return {
+        "ShotNumber": shot_number,
+        "Timestamp": shot.get("timestamp"),
+        "Club": shot.get("club"),
+        "Units": {"Speed": "mph", "Distance": "yards", "Angle": "degrees", "Spin": "rpm"},
+        "BallData": _measured(shot, _BALL_FIELDS),
+        "ClubData": _measured(shot, _CLUB_FIELDS),
+    }
+
+
+class ConnectorFeed:
+    """
+    Event bus consumer keeping the shots and ready state tablet apps poll.
+
+    Example:
+        feed = ConnectorFeed(ready=arming is None)
+        add_event_sink(feed.on_event)
+        feed.latest()  # {"ShotNumber": 3, "BallData": {...}, ...}
+    """
+
+    def __init__(self, ready: bool = True, history_size: int = HISTORY_SIZE):
+        """
+        Initialize the feed.
+
+        Args:
+            ready: Whether the monitor starts ready for a shot (False with auto-arming)
+            history_size: Shots kept for catching up
+        """
+        self.ready = ready
+        self._shots: Deque[dict] = deque(maxlen=history_size)
+        self._last_number = 0
+        self._lock = threading.Lock()
+
+    @property
+    def last_shot_number(self) -> int:
+        """Number of the last shot published (0 before the first)."""
+        with self._lock:
+            return self._last_number
+
+    def on_event(self, event: str, data: dict):
+        """Event sink: follow shots, mulligans and the ready state."""
+        with self._lock:
+            if event == "shot" and data.get("shot"):
+                self._last_number += 1
+                self._shots.append(connector_shot(self._last_number, data["shot"]))
+            elif event == "mulligan" and self._shots:
+                self._shots.pop()
+            elif event in ("ready", "not_ready"):
+                self.ready = event == "ready"
+            elif event == "disarmed":
+                self.ready = False
+
+    def status(self, connected: bool) -> dict:
+        """
+        The /connector/status body.
+
+        Args:
+            connected: Whether the radar is running
+        """
+        with self._lock:
+            return {
+                "Device": DEVICE_NAME,
+                "Connected": connected,
+                "Ready": connected and self.ready,
+                "LastShotNumber": self._last_number,
+            }
+
+    def latest(self) -> Optional[dict]:
+        """The last shot, or None if there isn't one (or it was taken back)."""
+        with self._lock:
+            return self._shots[-1] if self._shots else None
+
+    def since(self, shot_number: int) -> List[dict]:
+        """Shots numbered after shot_number, oldest first."""
+        with self._lock:
+            return [shot for shot in self._shots if shot["ShotNumber"] > shot_number]
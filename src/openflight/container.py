@@ -0,0 +1,80 @@
+"""
+Running in a container (Docker, Podman).
+
+Several people run the server on a NAS next to their sim PC. Inside a
+container the radar's serial device only exists if it was passed
+through, and without it auto-detection just reports that no radar was
+found. So when the server sees it is in a container with no serial
+devices, it says how to pass the radar through instead:
+
+    docker run --device /dev/ttyACM0 -p 8080:8080 openflight --port /dev/ttyACM0
+
+Device names can change when the radar is replugged; --port takes a glob
+(see resolve_port in radar_detect), so share /dev, allow USB serial
+devices (major 166 is ttyACM) and match the radar by name:
+
+    docker run -v /dev:/dev --device-cgroup-rule 'c 166:* rmw' ... \\
+        --port '/dev/serial/by-id/usb-OmniPreSense*'
+
+Network listeners bind to --host (web UI and --time-sync) and
+--status-host (--status-port); inside a container, publish their ports
+with -p and keep --host 0.0.0.0.
+"""
+
+import glob
+import os
+from typing import List, Optional
+
+# Serial devices a radar can show up as (USB CDC-ACM, USB-serial bridges)
+SERIAL_DEVICE_PATTERNS = ("ttyACM*", "ttyUSB*", "serial/by-id/*")
+
+PASS_THROUGH_HELP = (
+    "Running in a container without serial devices: pass the radar through, "
+    "e.g. docker run --device /dev/ttyACM0 ... (or devices: ['/dev/ttyACM0'] in compose), "
+    "or run with --mock"
+)
+
+_CGROUP_MARKERS = ("docker", "containerd", "kubepods", "libpod", "lxc")
+
+
+def in_container(root: str = "/") -> bool:
+    """
+    Whether this process runs in a container.
+
+    Docker leaves /.dockerenv and Podman /run/.containerenv; other
+    runtimes show up in PID 1's cgroup.
+
+    Args:
+        root: Filesystem root (for tests)
+    """
+    if os.path.exists(os.path.join(root, ".dockerenv")):
+        return True
+    if os.path.exists(os.path.join(root, "run", ".containerenv")):
+        return True
+    try:
+        with open(os.path.join(root, "proc", "1", "cgroup"), encoding="utf-8") as f:
+            cgroup = f.read()
+    except OSError:
+        return False
+    return any(marker in cgroup for marker in _CGROUP_MARKERS)
+
+
+def serial_devices(dev_dir: str = "/dev") -> List[str]:
+    """Serial devices present, by path."""
+    found = set()
+    for pattern in SERIAL_DEVICE_PATTERNS:
+        found.update(glob.glob(os.path.join(dev_dir, pattern)))
+    return sorted(found)
+
+
+def device_hint(dev_dir: str = "/dev", root: str = "/") -> Optional[str]:
+    """
+    PASS_THROUGH_HELP if this is a container with no serial devices, else None.
+
+    Args:
+        dev_dir: Device directory (for tests)
+        root: Filesystem root (for tests)
+    """
+    if in_container(root) and not serial_devices(dev_dir):
+        return PASS_THROUGH_HELP
+    return None
@@ -0,0 +1,325 @@
+"""
+Local control socket for the kiosk shell.
+
+With --control-socket PATH, the server listens on a Unix domain socket for
+JSON-RPC 2.0 requests, one JSON object per line, answered with one line
+each. Only local processes that can open the socket file (mode 0600, so
+the same user) can connect; nothing is exposed on the network. Methods:
+
+    status                           -> {"mode": ..., "mock": ..., "shots": ..., ...}
+    state                            -> the monitor's internal state (as GET /api/state)
+    set_mode {"mode": "streaming"}   -> restarts the monitor in that mode
+    shutdown                         -> stops the server cleanly (session log closed)
+
+For example:
+
+    $ echo '{"jsonrpc": "2.0", "id": 1, "method": "status"}' | nc -U /run/user/1000/openflight.sock
+    {"jsonrpc": "2.0", "id": 1, "result": {"mode": "streaming", ...}}
+
+or with the bundled client:
+
+    openflight-ctl --socket /run/user/1000/openflight.sock status
+    openflight-ctl state
+    openflight-ctl set_mode mode=rolling-buffer
+    openflight-ctl shutdown
+"""
+
+import argparse
+import json
+import logging
+import os
+import socket
+import stat
+import sys
+import threading
+from typing import Any, Callable, Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+# JSON-RPC 2.0 error codes
+PARSE_ERROR = -32700
+INVALID_REQUEST = -32600
+METHOD_NOT_FOUND = -32601
+INVALID_PARAMS = -32602
+INTERNAL_ERROR = -32603
+
+
+def default_socket_path() -> str:
+    """The per-user runtime directory's openflight.sock (/tmp if there is none)."""
+    return os.path.join(os.environ.get("XDG_RUNTIME_DIR") or "/tmp", "openflight.sock")
+
+
+def remove_stale_socket(path: str):
+    """
+    Remove a socket file left by a crashed server.
+
+    Raises:
+        OSError: If the path isn't a socket, or a server is listening on it
+    """
+    try:
+        mode = os.stat(path).st_mode
+    except FileNotFoundError:
+        return
+    if not stat.S_ISSOCK(mode):
+        raise OSError(f"{path} exists and is not a socket")
+    probe = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    try:
+        probe.connect(path)
+    except OSError:
+        os.unlink(path)
+    else:
+        raise OSError(f"Another server is listening on {path}")
+    finally:
+        probe.close()
+
+
+class ControlError(Exception):
+    """Error returned by the control socket (or raised by a handler to return one)."""
+
+    def __init__(self, message: str, code: int = INVALID_PARAMS):
+        super().__init__(message)
+        self.code = code
+
+
+class ControlServer:
+    """
+    JSON-RPC server on a Unix domain socket.
+
+    Handlers take the request's params (a dict, empty if none were given)
+    and return a JSON-serializable result. A ValueError or ControlError
+    from a handler is answered as an error; anything else as an internal
+    error, without stopping the server.
+
+    Example:
+        control = ControlServer("/tmp/openflight.sock", {"status": lambda params: {...}})
+        control.start()
+        ...
+        control.stop()
+    """
+
+    def __init__(self, path: str, handlers: Dict[str, Callable[[dict], Any]]):
+        """
+        Initialize control server.
+
+        Args:
+            path: Socket file to listen on
+            handlers: Method name -> handler
+        """
+        self.path = path
+        self.handlers = dict(handlers)
+        self._sock: Optional[socket.socket] = None
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    def start(self):
+        """
+        Bind the socket and serve requests on a background thread.
+
+        Raises:
+            OSError: If another server is listening on the path, or it can't be bound
+        """
+        remove_stale_socket(self.path)
+        sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            sock.bind(self.path)
+            os.chmod(self.path, 0o600)
+            sock.listen(4)
+        except OSError:
+            sock.close()
+            raise
+        sock.settimeout(0.5)
+        self._sock = sock
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._accept_loop, daemon=True)
+        self._thread.start()
+
+    def stop(self, timeout: float = 2.0):
+        """Stop serving and remove the socket file."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=timeout)
+            self._thread = None
+        if self._sock:
+            self._sock.close()
+            self._sock = None
+            try:
+                os.unlink(self.path)
+            except OSError:
+                pass
+
+    def _accept_loop(self):
+        while not self._stop_event.is_set():
+            try:
+                conn, _ = self._sock.accept()
+            except socket.timeout:
+                continue
+            except OSError:
+                return
+            threading.Thread(target=self._serve, args=(conn,), daemon=True).start()
+
+    def _serve(self, conn: socket.socket):
+        """Answer requests on one connection until the client closes it."""
+        with conn, conn.makefile("rwb") as stream:
+            for line in stream:
+                if not line.strip():
+                    continue
+                response = self.handle(line)
+                if response is not None:
+                    stream.write(json.dumps(response).encode("utf-8") + b"\n")
+                    stream.flush()
+
+    def handle(self, line: bytes) -> Optional[dict]:
+        """
+        Run one request line.
+
+        Returns:
+            The JSON-RPC response, or None for a notification (no id)
+        """
+        try:
+            request = json.loads(line)
+        except ValueError:
+            return _error(None, PARSE_ERROR, "Parse error")
+        if (
+            not isinstance(request, dict)
+            or request.get("jsonrpc") != "2.0"
+            or not isinstance(request.get("method"), str)
+        ):
+            request_id = request.get("id") if isinstance(request, dict) else None
+            return _error(request_id, INVALID_REQUEST, "Invalid request")
+        request_id = request.get("id")
+        params = request.get("params", {})
+        if not isinstance(params, dict):
+            return _error(request_id, INVALID_PARAMS, "params must be an object")
+
+        handler = self.handlers.get(request["method"])
+        if handler is None:
+            response = _error(request_id, METHOD_NOT_FOUND, f"Unknown method: {request['method']}")
+        else:
+            try:
+                response = {"jsonrpc": "2.0", "id": request_id, "result": handler(params)}
+            except ControlError as e:
+                response = _error(request_id, e.code, str(e))
+            except ValueError as e:
+                response = _error(request_id, INVALID_PARAMS, str(e))
+            except Exception as e:  # pylint: disable=broad-except
+                logger.exception("Control method %s failed", request["method"])
+                response = _error(request_id, INTERNAL_ERROR, str(e))
+        return response if "id" in request else None
+
+
+def _error(request_id, code: int, message: str) -> dict:
+    return {"jsonrpc": "2.0", "id": request_id, "error": {"code": code, "message": message}}
+
+
+def call(path: str, method: str, params: Optional[dict] = None, timeout: float = 10.0) -> Any:
+    """
+    Call a method on a running server's control socket.
+
+    Args:
+        path: Socket file
+        method: Method name
+        params: Method params
+        timeout: Seconds to wait for the answer
+
+    Returns:
+        The method's result
+
+    Raises:
+        OSError: If no server is listening on the path
+        ControlError: If the server answered with an error
+    """
+    request = {"jsonrpc": "2.0", "id": 1, "method": method, "params": params or {}}
+    with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+        sock.settimeout(timeout)
+        sock.connect(path)
+        with sock.makefile("rwb") as stream:
+            stream.write(json.dumps(request).encode("utf-8") + b"\n")
+            stream.flush()
+            line = stream.readline()
+    if not line:
+        raise ConnectionError("Server closed the control connection without answering")
+    response = json.loads(line)
+    if "error" in response:
+        raise ControlError(response["error"]["message"], response["error"]["code"])
+    return response.get("result")
+
+
+def _parse_param(text: str):
+    """Parse a key=value argument; values are JSON if they parse, strings otherwise."""
+    key, sep, value = text.partition("=")
+    if not sep or not key:
+        raise ValueError(f"Expected key=value, got {text!r}")
+    try:
+        return key, json.loads(value)
+    except ValueError:
+        return key, value
+
+
+# Global control server instance
+_control: Optional[ControlServer] = None
+
+
+def get_control_server() -> Optional[ControlServer]:
+    """Get the global control server (None if no control socket is configured)."""
+    return _control
+
+
+def init_control_server(
+    path: Optional[str], handlers: Dict[str, Callable[[dict], Any]]
+) -> Optional[ControlServer]:
+    """
+    Initialize and start the global control server.
+
+    Args:
+        path: Socket file; None disables the control socket
+        handlers: Method name -> handler
+
+    Returns:
+        ControlServer instance, or None if no path was given
+
+    Raises:
+        OSError: If the socket can't be bound
+    """
+    global _control  # pylint: disable=global-statement
+
+    if _control:
+        _control.stop()
+        _control = None
+
+    if not path:
+        return None
+
+    _control = ControlServer(path, handlers)
+    _control.start()
+    return _control
+
+
+def main():
+    """CLI entry point: call a method on a running server's control socket."""
+    parser = argparse.ArgumentParser(description="Control a running openflight-server")
+    parser.add_argument(
+        "--socket",
+        default=default_socket_path(),
+        help="Control socket of the server (default: %(default)s)",
+    )
+    parser.add_argument("method", help="Method to call, e.g. status, state, set_mode, shutdown")
+    parser.add_argument("params", nargs="*", help="Method params as key=value, e.g. mode=streaming")
+    args = parser.parse_args()
+
+    try:
+        params = dict(_parse_param(p) for p in args.params)
+    except ValueError as e:
+        parser.error(str(e))
+    try:
+        result = call(args.socket, args.method, params)
+    except ControlError as e:
+        print(f"Error: {e}", file=sys.stderr)
+        sys.exit(1)
+    except OSError as e:
+        print(f"Error: can't reach server on {args.socket}: {e}", file=sys.stderr)
+        sys.exit(2)
+    print(json.dumps(result, indent=2))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,233 @@
+"""
+Crash reports with a snapshot of what the monitor was doing.
+
+When an exception escapes (in the main thread or any background thread:
+radar streaming, camera, webhooks...), a JSON report is written to the
+crash directory (~/openflight_sessions/crashes by default):
+
+    {
+      "time": "2025-06-01T10:15:30.123456",
+      "version": "0.2.0",
+      "thread": "Thread-3 (_iq_stream_loop)",
+      "exception": {"type": "IndexError", "message": "...", "traceback": [...]},
+      "readings": [{"time": ..., "speed": 42.1, "direction": "inbound", "magnitude": 850}, ...],
+      "state": {"mode": "streaming", "config": {...}, "monitor": {...}, ...},
+      "platform": {...}
+    }
+
+"readings" are the last readings the radar reported (before filtering) and
+"state" comes from the snapshot function the server registers: active
+thresholds, where shot detection was (buffered readings, shot window,
+takeaway window), arming state and the session. With --crash-report-url
+the report is also POSTed there, so a user who isn't a developer can
+file a bug by pointing at the report rather than describing it.
+"""
+
+import json
+import logging
+import os
+import platform
+import sys
+import threading
+import time
+import traceback
+import urllib.error
+import urllib.request
+from collections import deque
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Deque, Dict, Optional
+
+from . import __version__
+from .frames import SpeedReading
+
+logger = logging.getLogger(__name__)
+
+
+class CrashReporter:
+    """
+    Keeps recent readings and writes a report when an exception escapes.
+
+    Example:
+        reporter = CrashReporter(Path("~/openflight_sessions/crashes"), snapshot=get_state)
+        reporter.install()
+        reporter.record_reading(reading)   # for every radar reading
+    """
+
+    MAX_READINGS = 200  # Readings kept for the report
+    POST_TIMEOUT_SEC = 5.0
+
+    def __init__(
+        self,
+        directory: Path,
+        snapshot: Optional[Callable[[], Dict[str, Any]]] = None,
+        endpoint: Optional[str] = None,
+        max_readings: int = MAX_READINGS,
+    ):
+        """
+        Initialize crash reporter.
+
+        Args:
+            directory: Where reports are written (created on the first crash)
+            snapshot: Returns the monitor state to include in a report
+            endpoint: URL each report is also POSTed to
+            max_readings: Number of recent readings kept
+        """
+        self.directory = Path(directory).expanduser()
+        self.snapshot = snapshot
+        self.endpoint = endpoint
+        self._readings: Deque[Dict[str, Any]] = deque(maxlen=max_readings)
+        self._lock = threading.Lock()
+        self._previous_excepthook = None
+        self._previous_threading_excepthook = None
+
+    def record_reading(self, reading: SpeedReading):
+        """Remember a reading for the next report."""
+        entry = {
+            "time": time.time(),
+            "speed": reading.speed,
+            "direction": reading.direction.value,
+            "magnitude": reading.magnitude,
+        }
+        with self._lock:
+            self._readings.append(entry)
+
+    def build_report(self, exc: BaseException, thread_name: Optional[str] = None) -> dict:
+        """
+        Build a report for an exception (never raises).
+
+        Args:
+            exc: The exception that escaped
+            thread_name: Thread it escaped from (default: the current thread)
+        """
+        with self._lock:
+            readings = list(self._readings)
+        state: Dict[str, Any] = {}
+        if self.snapshot:
+            try:
+                state = self.snapshot()
+            except Exception as e:  # pylint: disable=broad-except
+                # The crash may have left the state the snapshot reads broken
+                state = {"snapshot_error": f"{type(e).__name__}: {e}"}
+        return {
+            "time": datetime.now().isoformat(),
+            "version": __version__,
+            "thread": thread_name or threading.current_thread().name,
+            "exception": {
+                "type": type(exc).__name__,
+                "message": str(exc),
+                "traceback": traceback.format_exception(type(exc), exc, exc.__traceback__),
+            },
+            "readings": readings,
+            "state": state,
+            "platform": {
+                "python": platform.python_version(),
+                "system": platform.platform(),
+                "machine": platform.machine(),
+                "argv": sys.argv,
+            },
+        }
+
+    def report(self, exc: BaseException, thread_name: Optional[str] = None) -> Optional[Path]:
+        """
+        Write (and POST, if configured) a report for an exception.
+
+        Returns:
+            The report file, or None if it couldn't be written
+        """
+        report = self.build_report(exc, thread_name)
+        body = json.dumps(report, indent=2, default=str)
+        path = None
+        try:
+            self.directory.mkdir(parents=True, exist_ok=True)
+            stamp = datetime.now().strftime("%Y%m%d_%H%M%S")
+            path = self.directory / f"crash_{stamp}_{os.getpid()}.json"
+            path.write_text(body)
+            print(f"[CRASH] Report written to {path}")
+        except OSError as e:
+            print(f"[CRASH] Couldn't write crash report: {e}")
+            path = None
+        if self.endpoint:
+            self.post(body.encode("utf-8"))
+        return path
+
+    def post(self, body: bytes) -> bool:
+        """POST a report to the endpoint (one attempt; the process may be going down)."""
+        request = urllib.request.Request(
+            self.endpoint,
+            data=body,
+            headers={"Content-Type": "application/json", "User-Agent": "OpenFlight-Crash"},
+            method="POST",
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=self.POST_TIMEOUT_SEC):
+                print(f"[CRASH] Report sent to {self.endpoint}")
+                return True
+        except (urllib.error.URLError, OSError) as e:
+            print(f"[CRASH] Couldn't send crash report to {self.endpoint}: {e}")
+            return False
+
+    def install(self):
+        """Report exceptions escaping any thread, then let the previous hooks run."""
+        self._previous_excepthook = sys.excepthook
+        self._previous_threading_excepthook = threading.excepthook
+
+        def excepthook(exc_type, exc, tb):
+            if not issubclass(exc_type, KeyboardInterrupt):
+                self.report(exc)
+            self._previous_excepthook(exc_type, exc, tb)
+
+        def threading_excepthook(args):
+            if args.exc_value is not None and not issubclass(args.exc_type, SystemExit):
+                self.report(args.exc_value, args.thread.name if args.thread else None)
+            self._previous_threading_excepthook(args)
+
+        sys.excepthook = excepthook
+        threading.excepthook = threading_excepthook
+
+    def uninstall(self):
+        """Put back the hooks from before install()."""
+        if self._previous_excepthook is not None:
+            sys.excepthook = self._previous_excepthook
+            threading.excepthook = self._previous_threading_excepthook
+            self._previous_excepthook = None
+            self._previous_threading_excepthook = None
+
+
+# Global crash reporter instance
+_crash_reporter: Optional[CrashReporter] = None
+
+
+def get_crash_reporter() -> Optional[CrashReporter]:
+    """Get the global crash reporter (None if crash reports are disabled)."""
+    return _crash_reporter
+
+
+def init_crash_reporter(
+    directory: Optional[Path],
+    snapshot: Optional[Callable[[], Dict[str, Any]]] = None,
+    endpoint: Optional[str] = None,
+) -> Optional[CrashReporter]:
+    """
+    Initialize the global crash reporter and install its exception hooks.
+
+    Args:
+        directory: Where reports are written; None disables crash reports
+        snapshot: Returns the monitor state to include in a report
+        endpoint: URL each report is also POSTed to
+
+    Returns:
+        CrashReporter instance, or None if disabled
+    """
+    global _crash_reporter  # pylint: disable=global-statement
+
+    if _crash_reporter:
+        _crash_reporter.uninstall()
+        _crash_reporter = None
+
+    if directory is None:
+        return None
+
+    _crash_reporter = CrashReporter(directory, snapshot=snapshot, endpoint=endpoint)
+    _crash_reporter.install()
+    return _crash_reporter
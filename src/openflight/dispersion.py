@@ -0,0 +1,189 @@
+"""
+Landing dispersion per club.
+
+When a shot has a horizontal launch angle (from the camera, a sim or an
+injected shot), it gets a landing point: carry along the target line and
+offline distance, + right of it, - left:
+
+    downrange = carry * cos(angle)
+    offline   = carry * sin(angle)
+
+Each club's landing points are summarized by a dispersion ellipse, the
+region that holds about 95% of that club's shots if they scatter normally:
+centred on the mean landing point, with axes along the principal
+directions of the scatter. A long, thin ellipse is a distance control
+problem; a wide one a direction problem; a tilted one, shots that go long
+when they go right (or left).
+
+The target basket is a circle around the club's target, its average
+carry on the target line, with a radius of basket_pct (10% by default) of
+that carry; "in_basket_pct" is the share of shots landing in it.
+
+Shots without a horizontal angle (radar only) have no landing point and
+are left out.
+"""
+
+import math
+import statistics
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Tuple
+
+DEFAULT_CONFIDENCE = 0.95  # Share of a normal scatter inside the ellipse
+DEFAULT_BASKET_PCT = 10.0  # Basket radius as a percentage of the target carry
+MIN_ELLIPSE_SHOTS = 3  # Fewer points don't define a scatter
+
+
+@dataclass
+class Ellipse:
+    """
+    Dispersion ellipse in yards.
+
+    Attributes:
+        downrange: Centre, along the target line
+        offline: Centre, + right of the target line
+        length: Full length of the long axis
+        width: Full length of the short axis
+        angle_deg: Angle of the long axis from the target line, + toward right
+    """
+
+    downrange: float
+    offline: float
+    length: float
+    width: float
+    angle_deg: float
+
+
+@dataclass
+class Dispersion:
+    """Landing scatter of one club."""
+
+    club: str
+    points: List[Tuple[float, float]] = field(default_factory=list)  # (downrange, offline)
+    ellipse: Optional[Ellipse] = None  # None with fewer than MIN_ELLIPSE_SHOTS
+    basket_radius: float = 0.0
+    in_basket_pct: float = 0.0
+
+    @property
+    def shots(self) -> int:
+        """Number of shots with a landing point."""
+        return len(self.points)
+
+    def to_dict(self) -> dict:
+        """Serialize for the stats API (yards, rounded)."""
+        downrange = [p[0] for p in self.points]
+        offline = [p[1] for p in self.points]
+        spread = len(self.points) > 1
+        return {
+            "club": self.club,
+            "shots": self.shots,
+            "avg_downrange": round(statistics.mean(downrange), 1),
+            "avg_offline": round(statistics.mean(offline), 1),
+            "downrange_std_dev": round(statistics.stdev(downrange), 1) if spread else 0.0,
+            "offline_std_dev": round(statistics.stdev(offline), 1) if spread else 0.0,
+            "ellipse": (
+                {k: round(v, 1) for k, v in vars(self.ellipse).items()} if self.ellipse else None
+            ),
+            "basket_radius": round(self.basket_radius, 1),
+            "in_basket_pct": round(self.in_basket_pct, 1),
+            "points": [[round(d, 1), round(o, 1)] for d, o in self.points],
+        }
+
+
+def landing_point(shot: dict) -> Optional[Tuple[float, float]]:
+    """
+    Where a shot landed, in yards.
+
+    Args:
+        shot: shot_detected log entry or shot_to_dict output
+
+    Returns:
+        (downrange, offline), or None without a carry or horizontal angle
+    """
+    angle = shot.get("launch_angle_horizontal")
+    carry = shot.get("carry_spin_adjusted") or shot.get("estimated_carry_yards")
+    if not isinstance(angle, (int, float)) or not isinstance(carry, (int, float)):
+        return None
+    return landing(carry, angle)
+
+
+def landing(carry: float, angle_deg: float) -> Tuple[float, float]:
+    """(downrange, offline) in yards for a carry and horizontal launch angle."""
+    radians = math.radians(angle_deg)
+    return carry * math.cos(radians), carry * math.sin(radians)
+
+
+def fit_ellipse(
+    points: List[Tuple[float, float]], confidence: float = DEFAULT_CONFIDENCE
+) -> Optional[Ellipse]:
+    """
+    Fit a dispersion ellipse to landing points.
+
+    Args:
+        points: (downrange, offline) points
+        confidence: Share of a normal scatter the ellipse should hold
+
+    Returns:
+        The ellipse, or None with fewer than MIN_ELLIPSE_SHOTS points
+    """
+    if len(points) < MIN_ELLIPSE_SHOTS:
+        return None
+    downrange = [p[0] for p in points]
+    offline = [p[1] for p in points]
+    mean_d, mean_o = statistics.mean(downrange), statistics.mean(offline)
+    n = len(points) - 1
+    var_d = sum((d - mean_d) ** 2 for d in downrange) / n
+    var_o = sum((o - mean_o) ** 2 for o in offline) / n
+    cov = sum((d - mean_d) * (o - mean_o) for d, o in points) / n
+
+    # Eigenvalues of the covariance matrix: variances along the principal axes
+    half_sum = (var_d + var_o) / 2
+    root = math.hypot((var_d - var_o) / 2, cov)
+    major, minor = half_sum + root, max(half_sum - root, 0.0)
+    # Chi-squared quantile with 2 degrees of freedom
+    scale = math.sqrt(-2 * math.log(1 - confidence))
+    return Ellipse(
+        downrange=mean_d,
+        offline=mean_o,
+        length=2 * scale * math.sqrt(major),
+        width=2 * scale * math.sqrt(minor),
+        angle_deg=math.degrees(0.5 * math.atan2(2 * cov, var_d - var_o)),
+    )
+
+
+def club_dispersion(
+    shots: List[dict],
+    confidence: float = DEFAULT_CONFIDENCE,
+    basket_pct: float = DEFAULT_BASKET_PCT,
+) -> List[Dispersion]:
+    """
+    Landing scatter of each club, in the order the clubs were first hit.
+
+    Args:
+        shots: shot_detected log entries or shot_to_dict output
+        confidence: Share of a normal scatter each ellipse should hold
+        basket_pct: Basket radius as a percentage of the club's target carry
+
+    Returns:
+        One Dispersion per club with at least one landing point
+    """
+    by_club: Dict[str, List[Tuple[float, float]]] = {}
+    for shot in shots:
+        point = landing_point(shot)
+        if point is not None:
+            by_club.setdefault(shot.get("club", "driver"), []).append(point)
+
+    results = []
+    for club, points in by_club.items():
+        target = statistics.mean(p[0] for p in points)
+        radius = target * basket_pct / 100
+        inside = sum(math.hypot(d - target, o) <= radius for d, o in points)
+        results.append(
+            Dispersion(
+                club=club,
+                points=points,
+                ellipse=fit_ellipse(points, confidence),
+                basket_radius=radius,
+                in_basket_pct=100 * inside / len(points),
+            )
+        )
+    return results
@@ -0,0 +1,111 @@
+"""
+Kinetic energy of the club and ball, and how much of it reaches the ball.
+
+Speed training tracks energy as well as speed: a faster swing with a
+heavier club delivers more, and smash factor alone hides the head mass.
+For each shot with both speeds:
+
+- ball_energy_j: the ball's kinetic energy after impact (0.5 m v², joules)
+- club_energy_j: the club head's kinetic energy at impact
+- energy_transfer_pct: ball energy as a percentage of club energy
+
+With a typical head mass, a centered driver (smash 1.48, 200 g) transfers
+about 50%; a 7-iron (smash 1.33, 267 g) about 30%, since the heavier head
+keeps more of its energy. The head mass stands in for the club's
+effective mass at impact (the shaft and hands add a little), so the
+percentage is for comparing swings rather than an absolute efficiency.
+
+Shots with no club speed, or with the club "unknown", have only a ball
+energy.
+"""
+
+import statistics
+from typing import Any, Dict, Iterable, Optional
+
+from .units import MPH_TO_MPS
+
+BALL_MASS_KG = 0.04593  # Rules of Golf maximum (1.620 oz)
+
+# Typical club head mass by club: about 7 g more per iron down the set
+CLUB_HEAD_MASS_KG: Dict[str, float] = {
+    "driver": 0.200,
+    "3-wood": 0.212,
+    "5-wood": 0.217,
+    "7-wood": 0.222,
+    "3-hybrid": 0.225,
+    "5-hybrid": 0.235,
+    "7-hybrid": 0.245,
+    "9-hybrid": 0.255,
+    "2-iron": 0.232,
+    "3-iron": 0.239,
+    "4-iron": 0.246,
+    "5-iron": 0.253,
+    "6-iron": 0.260,
+    "7-iron": 0.267,
+    "8-iron": 0.274,
+    "9-iron": 0.281,
+    "pw": 0.288,
+    "gw": 0.295,
+    "sw": 0.300,
+    "lw": 0.305,
+}
+
+# Shot fields this module adds
+ENERGY_FIELDS = ("ball_energy_j", "club_energy_j", "energy_transfer_pct")
+
+
+def kinetic_energy_j(mass_kg: float, speed_mph: float) -> float:
+    """Kinetic energy in joules of a mass moving at a speed."""
+    speed_mps = float(speed_mph) * MPH_TO_MPS
+    return 0.5 * mass_kg * speed_mps**2
+
+
+def shot_energy(
+    ball_speed_mph: Optional[float], club_speed_mph: Optional[float], club: str
+) -> Dict[str, Optional[float]]:
+    """
+    A shot's ENERGY_FIELDS (None where they can't be worked out).
+
+    Args:
+        ball_speed_mph: Ball speed
+        club_speed_mph: Club head speed (None if not measured)
+        club: Club name (ClubType value)
+    """
+    ball = kinetic_energy_j(BALL_MASS_KG, ball_speed_mph) if ball_speed_mph else None
+    head_mass = CLUB_HEAD_MASS_KG.get(club)
+    club_energy = None
+    if club_speed_mph and head_mass:
+        club_energy = kinetic_energy_j(head_mass, club_speed_mph)
+    transfer = 100 * ball / club_energy if ball and club_energy else None
+    return {"ball_energy_j": ball, "club_energy_j": club_energy, "energy_transfer_pct": transfer}
+
+
+def energy_from_dict(shot: dict) -> Dict[str, Optional[float]]:
+    """shot_energy() for a shot_detected log entry or shot_to_dict output."""
+    return shot_energy(
+        shot.get("ball_speed_mph"), shot.get("club_speed_mph"), shot.get("club", "driver")
+    )
+
+
+def energy_stats(shots: Iterable[Any]) -> Dict[str, Optional[float]]:
+    """
+    Session energy statistics for get_session_stats().
+
+    Args:
+        shots: Shot objects
+
+    Returns:
+        avg_ball_energy_j, max_ball_energy_j, avg_club_energy_j and
+        avg_energy_transfer_pct (None when no shot had the value)
+    """
+    energies = [shot_energy(s.ball_speed_mph, s.club_speed_mph, s.club.value) for s in shots]
+
+    ball, club, transfer = (
+        [e[name] for e in energies if e[name] is not None] for name in ENERGY_FIELDS
+    )
+    return {
+        "avg_ball_energy_j": statistics.mean(ball) if ball else None,
+        "max_ball_energy_j": max(ball) if ball else None,
+        "avg_club_energy_j": statistics.mean(club) if club else None,
+        "avg_energy_transfer_pct": statistics.mean(transfer) if transfer else None,
+    }
@@ -0,0 +1,536 @@
+"""
+ESP32 companion bridge: an OPS243 on the mat, its data sent over Wi-Fi.
+
+For fully wireless mat hardware, an ESP32 wired to the radar's UART
+forwards everything the radar sends to the server over UDP, and writes
+the server's commands to the radar. With --radar esp-bridge the server
+listens for it (--port [HOST:]PORT, default 0.0.0.0:4210) and drives the
+radar through it as if it were on a local serial port; the ESP32's
+address is learned from its datagrams.
+
+Protocol (version 1, see docs/esp32-bridge.md for the firmware side):
+
+- A datagram carries one or more frames: a 2-byte big-endian length,
+  then that many bytes of CBOR (see cbor)
+- ESP32 -> server, each frame a map:
+    "v": protocol version (1)
+    "b": boot id, a random 32-bit number picked at each power-up
+    "s": sequence number, +1 per frame, wrapping at 2**32
+    "t": the ESP32's microsecond clock when the data was read
+    "d": bytes read from the radar (absent in the 1-second heartbeat)
+- server -> ESP32, each frame a map:
+    "v": protocol version (1)
+    "c": bytes to write to the radar
+
+The firmware flushes "d" at the end of each radar line, so a lost
+datagram loses whole lines rather than splicing two together.
+
+UDP can reorder, duplicate and drop datagrams. Frames are released in
+sequence order (ReorderBuffer): one that arrives early waits up to
+reorder_window_sec for the frames before it, after which they are
+counted as lost and skipped. Readings are timed by the ESP32's clock
+rather than their arrival, mapped onto time.monotonic() by the smallest
+delay seen recently (BridgeClock), so Wi-Fi jitter doesn't smear the
+timing within a shot. A new boot id starts both over.
+
+I/Q streaming isn't carried over the bridge (the local FFT needs every
+block and more bandwidth than the radar's UART gives), so the radar's
+internal speed processing is used.
+"""
+
+import logging
+import socket
+import struct
+import time
+from collections import deque
+from dataclasses import replace
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+import serial
+
+from .cbor import dumps, loads
+from .frames import Frame
+from .ops243 import OPS243Radar, RadarCapabilities
+
+logger = logging.getLogger(__name__)
+
+PROTOCOL_VERSION = 1
+DEFAULT_UDP_PORT = 4210
+DEFAULT_REORDER_WINDOW_SEC = 0.05
+HEARTBEAT_TIMEOUT_SEC = 3.0  # The ESP32 sends a heartbeat every second
+
+SEQ_MASK = 0xFFFFFFFF
+_SEQ_HALF = 1 << 31
+_LENGTH = struct.Struct(">H")
+
+
+def encode_frame(message: Dict[str, Any]) -> bytes:
+    """A message as a length-prefixed CBOR frame."""
+    payload = dumps(message)
+    if len(payload) > 0xFFFF:
+        raise ValueError(f"frame of {len(payload)} bytes is too large")
+    return _LENGTH.pack(len(payload)) + payload
+
+
+def decode_datagram(data: bytes) -> List[Dict[str, Any]]:
+    """
+    The messages in a datagram.
+
+    Raises:
+        ValueError: If a frame is truncated, isn't CBOR or isn't a map
+    """
+    messages = []
+    pos = 0
+    while pos < len(data):
+        if pos + _LENGTH.size > len(data):
+            raise ValueError("truncated frame length")
+        (length,) = _LENGTH.unpack_from(data, pos)
+        pos += _LENGTH.size
+        if pos + length > len(data):
+            raise ValueError(f"frame of {length} bytes truncated to {len(data) - pos}")
+        message = loads(data[pos:pos + length])
+        if not isinstance(message, dict):
+            raise ValueError("frame is not a CBOR map")
+        messages.append(message)
+        pos += length
+    return messages
+
+
+def parse_address(address: str) -> Tuple[str, int]:
+    """
+    A "[HOST:]PORT" listen address as (host, port).
+
+    Raises:
+        ValueError: If the port isn't a number up to 65535 (0 picks a free one)
+    """
+    host, sep, port = address.rpartition(":")
+    if not sep:
+        host = "0.0.0.0"
+    try:
+        number = int(port)
+    except ValueError:
+        raise ValueError(f"expected [HOST:]PORT, got {address!r}") from None
+    if not 0 <= number < 65536:
+        raise ValueError(f"port {number} out of range")
+    return host.strip("[]") or "0.0.0.0", number
+
+
+class ReorderBuffer:
+    """
+    Puts sequenced items back in order, skipping ones that never arrive.
+
+    Example:
+        buffer = ReorderBuffer(window_sec=0.05)
+        ready = buffer.push(seq, item, now)  # items now in order
+        ready += buffer.flush(now)  # now and then, to give up on gaps
+    """
+
+    def __init__(self, window_sec: float = DEFAULT_REORDER_WINDOW_SEC, max_held: int = 256):
+        """
+        Initialize reorder buffer.
+
+        Args:
+            window_sec: How long an early item waits for the ones before it
+            max_held: Items held at most; beyond this the gap is skipped
+        """
+        self.window_sec = window_sec
+        self.max_held = max_held
+        self.received = 0
+        self.reordered = 0  # Arrived after a later item, and put back in place
+        self.late = 0  # Arrived after being given up on, or twice
+        self.lost = 0  # Never arrived within the window
+        self._expected: Optional[int] = None
+        self._held: Dict[int, Tuple[Any, float]] = {}
+
+    def reset(self):
+        """Forget the sequence (the sender restarted)."""
+        self._expected = None
+        self._held.clear()
+
+    def push(self, seq: int, item: Any, now: Optional[float] = None) -> List[Any]:
+        """
+        Add an item.
+
+        Args:
+            seq: The item's sequence number
+            item: Anything
+            now: Arrival time (default: time.monotonic())
+
+        Returns:
+            Items now ready, in sequence order
+        """
+        now = time.monotonic() if now is None else now
+        self.received += 1
+        seq &= SEQ_MASK
+        if self._expected is None:
+            self._expected = seq
+        ahead = (seq - self._expected) & SEQ_MASK
+        if ahead >= _SEQ_HALF or seq in self._held:
+            self.late += 1
+            return []
+        if ahead == 0 and self._held:
+            self.reordered += 1
+        self._held[seq] = (item, now)
+        ready = self._release()
+        while len(self._held) > self.max_held:
+            ready += self._skip_gap()
+        return ready + self.flush(now)
+
+    def flush(self, now: Optional[float] = None) -> List[Any]:
+        """
+        Give up on gaps that have been waited on for window_sec.
+
+        Returns:
+            Items released by skipping the gaps, in sequence order
+        """
+        now = time.monotonic() if now is None else now
+        ready: List[Any] = []
+        while self._held and min(t for _, t in self._held.values()) <= now - self.window_sec:
+            ready += self._skip_gap()
+        return ready
+
+    def _skip_gap(self) -> List[Any]:
+        nearest = min(self._held, key=lambda s: (s - self._expected) & SEQ_MASK)
+        self.lost += (nearest - self._expected) & SEQ_MASK
+        self._expected = nearest
+        return self._release()
+
+    def _release(self) -> List[Any]:
+        ready = []
+        while self._expected in self._held:
+            ready.append(self._held.pop(self._expected)[0])
+            self._expected = (self._expected + 1) & SEQ_MASK
+        return ready
+
+
+class BridgeClock:
+    """
+    Maps the ESP32's clock onto time.monotonic().
+
+    The offset is the smallest (arrival - device time) over the last
+    window_sec: the frame that got through quickest. It follows the
+    clocks' drift as old samples age out.
+    """
+
+    def __init__(self, window_sec: float = 10.0):
+        """
+        Initialize bridge clock.
+
+        Args:
+            window_sec: How long a sample counts toward the offset
+        """
+        self.window_sec = window_sec
+        self._samples: Deque[Tuple[float, float]] = deque()
+
+    def reset(self):
+        """Forget the offset (the ESP32 restarted)."""
+        self._samples.clear()
+
+    def to_local(self, device_us: int, arrival: float) -> float:
+        """
+        The local time of a device timestamp.
+
+        Args:
+            device_us: ESP32 microsecond clock
+            arrival: When the frame arrived (time.monotonic())
+        """
+        device = device_us / 1e6
+        self._samples.append((arrival, arrival - device))
+        while self._samples[0][0] < arrival - self.window_sec:
+            self._samples.popleft()
+        return device + min(offset for _, offset in self._samples)
+
+
+class EspBridgePort:
+    """
+    A serial-port-like link to a radar through the ESP32 bridge.
+
+    Has what OPS243Radar uses of a pyserial port (read, readline, write,
+    in_waiting, reset_input_buffer, timeout, is_open, close). read()
+    returns what has arrived when the first data is in, rather than
+    waiting for all of size.
+    """
+
+    LOSS_LOG_INTERVAL_SEC = 10.0
+
+    def __init__(
+        self,
+        address: str = f"0.0.0.0:{DEFAULT_UDP_PORT}",
+        timeout: Optional[float] = 1.0,
+        reorder_window_sec: float = DEFAULT_REORDER_WINDOW_SEC,
+        sock: Optional[socket.socket] = None,
+    ):
+        """
+        Listen for the ESP32.
+
+        Args:
+            address: UDP address to listen on, "[HOST:]PORT"
+            timeout: Read timeout in seconds (None waits for data)
+            reorder_window_sec: See ReorderBuffer
+            sock: Bound UDP socket to use instead (for tests)
+
+        Raises:
+            ValueError: If address isn't [HOST:]PORT
+            OSError: If the address can't be bound
+        """
+        if sock is None:
+            sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+            try:
+                sock.bind(parse_address(address))
+            except OSError:
+                sock.close()
+                raise
+        self._sock = sock
+        self.timeout = timeout
+        self.reorder = ReorderBuffer(reorder_window_sec)
+        self.clock = BridgeClock()
+        self.device: Optional[Tuple[str, int]] = None  # The ESP32, once heard
+        self.boot_id: Optional[int] = None
+        self.malformed = 0  # Datagrams or frames that couldn't be used
+        self.line_time: Optional[float] = None  # When the last line read was sent
+        self.is_open = True
+        self._chunks: Deque[Tuple[bytes, float]] = deque()
+        self._logged_lost = 0
+        self._last_loss_log = 0.0
+
+    @property
+    def stats(self) -> Dict[str, int]:
+        """Frames received, reordered, late, lost and malformed."""
+        return {
+            "received": self.reorder.received,
+            "reordered": self.reorder.reordered,
+            "late": self.reorder.late,
+            "lost": self.reorder.lost,
+            "malformed": self.malformed,
+        }
+
+    def wait_for_device(self, timeout: float = HEARTBEAT_TIMEOUT_SEC):
+        """
+        Wait until the ESP32 has been heard from.
+
+        Raises:
+            ConnectionError: If nothing arrived within timeout
+        """
+        deadline = time.monotonic() + timeout
+        while self.device is None:
+            remaining = deadline - time.monotonic()
+            if remaining <= 0:
+                host, port = self._sock.getsockname()[:2]
+                raise ConnectionError(
+                    f"No ESP32 bridge heard on UDP {host}:{port} within {timeout:g}s"
+                )
+            self._pump(remaining)
+
+    def _pump(self, wait: float):
+        """Receive datagrams for up to wait seconds, until there is data to read."""
+        deadline = time.monotonic() + max(wait, 0.0)
+        while True:
+            now = time.monotonic()
+            remaining = min(deadline - now, self.reorder.window_sec)
+            data = None
+            try:
+                self._sock.settimeout(max(remaining, 0.0))  # 0: don't block
+                data, sender = self._sock.recvfrom(65535)
+            except (socket.timeout, BlockingIOError):
+                pass
+            except OSError as e:
+                raise serial.SerialException(f"ESP32 bridge socket error: {e}") from e
+            now = time.monotonic()
+            if data is not None:
+                self._on_datagram(data, sender, now)
+            self._queue(self.reorder.flush(now))
+            self._log_loss(now)
+            if self._chunks or (data is None and now >= deadline):
+                return
+
+    def _on_datagram(self, data: bytes, sender: Tuple[str, int], now: float):
+        try:
+            messages = decode_datagram(data)
+        except ValueError as e:
+            self.malformed += 1
+            logger.debug("Malformed datagram from %s: %s", sender, e)
+            return
+        for message in messages:
+            seq, device_us, boot = message.get("s"), message.get("t"), message.get("b")
+            if message.get("v") != PROTOCOL_VERSION or not all(
+                isinstance(v, int) for v in (seq, device_us, boot)
+            ):
+                self.malformed += 1
+                continue
+            if boot != self.boot_id:
+                if self.boot_id is not None:
+                    logger.warning("ESP32 bridge %s restarted", sender[0])
+                self.boot_id = boot
+                self.reorder.reset()
+                self.clock.reset()
+            self.device = sender
+            local = self.clock.to_local(device_us, now)
+            data = message.get("d") or b""
+            self._queue(self.reorder.push(seq, (bytes(data), local), now))
+
+    def _queue(self, items: List[Tuple[bytes, float]]):
+        self._chunks.extend(item for item in items if item[0])
+
+    def _log_loss(self, now: float):
+        lost = self.reorder.lost
+        if lost > self._logged_lost and now - self._last_loss_log >= self.LOSS_LOG_INTERVAL_SEC:
+            logger.warning(
+                "ESP32 bridge lost %d frames (%d in all) - check Wi-Fi signal at the mat",
+                lost - self._logged_lost, lost,
+            )
+            self._logged_lost = lost
+            self._last_loss_log = now
+
+    def _wait(self) -> float:
+        return 1e9 if self.timeout is None else self.timeout
+
+    @property
+    def in_waiting(self) -> int:
+        """Bytes ready to read."""
+        self._pump(0)
+        return sum(len(data) for data, _ in self._chunks)
+
+    def read(self, size: int = 1) -> bytes:
+        """Up to size bytes, waiting up to timeout for the first."""
+        if not self._chunks:
+            self._pump(self._wait())
+        out = bytearray()
+        while self._chunks and len(out) < size:
+            data, sent = self._chunks.popleft()
+            take = size - len(out)
+            out += data[:take]
+            if len(data) > take:
+                self._chunks.appendleft((data[take:], sent))
+        return bytes(out)
+
+    def readline(self) -> bytes:
+        """A line (newline included), or what arrived before timeout."""
+        deadline = time.monotonic() + self._wait()
+        line = bytearray()
+        while True:
+            while self._chunks:
+                data, sent = self._chunks.popleft()
+                self.line_time = sent
+                end = data.find(b"\n")
+                if end >= 0:
+                    line += data[:end + 1]
+                    if end + 1 < len(data):
+                        self._chunks.appendleft((data[end + 1:], sent))
+                    return bytes(line)
+                line += data
+            remaining = deadline - time.monotonic()
+            if remaining <= 0:
+                return bytes(line)
+            self._pump(remaining)
+
+    def write(self, data: bytes) -> int:
+        """
+        Send bytes to the radar.
+
+        Raises:
+            serial.SerialException: If the ESP32 hasn't been heard from
+        """
+        if self.device is None:
+            raise serial.SerialException("No ESP32 bridge heard yet")
+        try:
+            self._sock.sendto(encode_frame({"v": PROTOCOL_VERSION, "c": bytes(data)}), self.device)
+        except OSError as e:
+            raise serial.SerialException(f"ESP32 bridge send failed: {e}") from e
+        return len(data)
+
+    def flush(self):
+        """Nothing to flush: writes are sent immediately."""
+
+    def reset_input_buffer(self):
+        """Discard data not read yet."""
+        self._pump(0)
+        self._chunks.clear()
+
+    def close(self):
+        """Stop listening."""
+        self.is_open = False
+        self._sock.close()
+
+
+class EspBridgeRadar(OPS243Radar):
+    """
+    OPS243 driver for a radar behind the ESP32 bridge.
+
+    Example:
+        radar = EspBridgeRadar("0.0.0.0:4210")
+        radar.connect()
+        radar.configure_for_golf()
+        radar.start_streaming(callback=print)
+    """
+
+    def __init__(
+        self,
+        address: Optional[str] = None,
+        reorder_window_sec: float = DEFAULT_REORDER_WINDOW_SEC,
+        **kwargs,
+    ):
+        """
+        Initialize radar driver.
+
+        Args:
+            address: UDP address to listen on, "[HOST:]PORT" (default 0.0.0.0:4210)
+            reorder_window_sec: See ReorderBuffer
+            kwargs: OPS243Radar arguments other than port
+        """
+        super().__init__(port=address or f"0.0.0.0:{DEFAULT_UDP_PORT}", **kwargs)
+        self.reorder_window_sec = reorder_window_sec
+        self.bridge: Optional[EspBridgePort] = None
+
+    def connect(self, timeout: float = OPS243Radar.DEFAULT_TIMEOUT) -> bool:
+        """
+        Listen for the ESP32 and identify the radar behind it.
+
+        Raises:
+            ConnectionError: If the address can't be bound or no ESP32 is heard
+        """
+        try:
+            self.bridge = EspBridgePort(
+                self.port, timeout=timeout, reorder_window_sec=self.reorder_window_sec
+            )
+        except (OSError, ValueError) as e:
+            raise ConnectionError(f"Can't listen for the ESP32 bridge on {self.port}: {e}") from e
+        try:
+            self.bridge.wait_for_device()
+        except ConnectionError:
+            self.bridge.close()
+            self.bridge = None
+            raise
+        logger.info("ESP32 bridge at %s:%d", *self.bridge.device)
+        self.serial = self.dump.tee(self.bridge) if self.dump else self.bridge
+        self._drain_serial()
+        self.detect_model()
+        self.detect_units()
+        return True
+
+    def disconnect(self):
+        """Stop streaming and stop listening for the ESP32."""
+        super().disconnect()
+        if self.bridge and self.bridge.is_open:
+            self.bridge.close()
+        self.bridge = None
+
+    def detect_model(self) -> RadarCapabilities:
+        """The radar's capabilities, less what the bridge doesn't carry (I/Q)."""
+        capabilities = super().detect_model()
+        if capabilities.iq_output or capabilities.rolling_buffer:
+            self.capabilities = replace(capabilities, iq_output=False, rolling_buffer=False)
+        return self.capabilities
+
+    def get_info(self) -> dict:
+        """Radar module information, with the ESP32's address as Bridge."""
+        info = super().get_info()
+        if self.bridge and self.bridge.device:
+            info["Bridge"] = "{}:{}".format(*self.bridge.device)
+        return info
+
+    def decode_line(self, line: str) -> Optional[Frame]:
+        """Decode a line, timed by when the ESP32 read it (see BridgeClock)."""
+        frame = super().decode_line(line)
+        if frame is not None and self.bridge and self.bridge.line_time is not None:
+            frame.timestamp = self.bridge.line_time
+        return frame
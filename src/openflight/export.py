@@ -0,0 +1,477 @@
+"""
+CSV export of shots through column templates.
+
+Fitting spreadsheets and shot analysis apps each import their own CSV
+layout. A template describes one layout: which column headers, in what
+order, filled from which shot field, in which units. Built-in templates
+follow the TrackMan and Garmin Golf CSV exports for the values OpenFlight
+measures (columns it doesn't measure, like club path, are left blank), and
+"openflight" exports every field as-is.
+
+Custom templates are JSON files in ~/.openflight/export_templates/<name>.json
+(or any path passed as --template):
+
+    {
+      "name": "fitting-sheet",
+      "delimiter": ";",
+      "units_row": false,
+      "columns": [
+        {"header": "Datum", "field": "date"},
+        {"header": "Schläger", "field": "club_name"},
+        {"header": "Ballgeschw. (km/h)", "field": "ball_speed_mph", "unit": "km/h",
+         "decimals": 1},
+        {"header": "Carry (m)", "field": "estimated_carry_yards", "unit": "m", "decimals": 0},
+        {"header": "Ball", "field": "tags.ball_type"},
+        {"header": "Attack Angle"}
+      ]
+    }
+
+Fields are those of a shot_detected log entry (ball_speed_mph, club,
+spin_rpm, ...) plus date, time, session, club_name, tags.<name> for the
+session's tags, and ball_energy_j, club_energy_j and energy_transfer_pct
+(see energy). "values" maps field values to the app's spellings, e.g.
+{"pw": "PW"}. A column without a field stays blank.
+
+With --format html the shots are written as a single self-contained
+HTML page instead (see html_export), and with --format fit as a FIT golf
+activity for Garmin Connect and other fitness apps (see fit_export);
+templates don't apply to either.
+
+Usage:
+    openflight-export ~/openflight_sessions/session_2025*.jsonl --template trackman -o shots.csv
+    openflight-export ~/openflight_sessions/session_20250601*.jsonl --format html -o lesson.html
+    openflight-export ~/openflight_sessions/session_20250601*.jsonl --format fit -o range.fit
+    openflight-export --list-templates
+"""
+
+import argparse
+import csv
+import io
+import json
+import re
+import sys
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional, TextIO
+
+from .energy import ENERGY_FIELDS, energy_from_dict
+from .reprocess import find_sessions
+from .session_logger import SessionLogger
+from .sessions import read_shots, summarize_session
+
+DEFAULT_TEMPLATE_DIR = Path.home() / ".openflight" / "export_templates"
+
+# Template names become file names, so keep them simple
+_NAME_PATTERN = re.compile(r"^[A-Za-z0-9][A-Za-z0-9_-]{0,63}$")
+
+# Units the logged values are in, and how to convert them
+_FIELD_UNITS = {
+    "ball_speed_mph": "mph",
+    "club_speed_mph": "mph",
+    "ball_speed_raw_mph": "mph",
+    "club_speed_raw_mph": "mph",
+    "estimated_carry_yards": "yd",
+    "carry_spin_adjusted": "yd",
+}
+_CONVERSIONS = {
+    ("mph", "mph"): 1.0,
+    ("mph", "km/h"): 1.609344,
+    ("mph", "m/s"): 0.44704,
+    ("yd", "yd"): 1.0,
+    ("yd", "m"): 0.9144,
+}
+
+# Club names as most apps spell them
+CLUB_NAMES = {
+    "driver": "Driver",
+    "3-wood": "3 Wood",
+    "5-wood": "5 Wood",
+    "7-wood": "7 Wood",
+    "3-hybrid": "3 Hybrid",
+    "5-hybrid": "5 Hybrid",
+    "7-hybrid": "7 Hybrid",
+    "9-hybrid": "9 Hybrid",
+    "2-iron": "2 Iron",
+    "3-iron": "3 Iron",
+    "4-iron": "4 Iron",
+    "5-iron": "5 Iron",
+    "6-iron": "6 Iron",
+    "7-iron": "7 Iron",
+    "8-iron": "8 Iron",
+    "9-iron": "9 Iron",
+    "pw": "Pitching Wedge",
+    "gw": "Gap Wedge",
+    "sw": "Sand Wedge",
+    "lw": "Lob Wedge",
+    "unknown": "",
+}
+
+# Shots that did not come from a swing are left out unless asked for
+_SKIPPED_MODES = ("mock", "injected")
+
+
+@dataclass
+class ExportColumn:
+    """
+    One CSV column.
+
+    Attributes:
+        header: Column header
+        field: Shot field to fill it from (None = always blank)
+        unit: Target unit for speeds (mph, km/h, m/s) and distances (yd, m)
+        decimals: Round numbers to this many places (None = as logged)
+        unit_label: Text for the units row (templates with units_row)
+        values: Field value -> exported text
+    """
+
+    header: str
+    field: Optional[str] = None
+    unit: Optional[str] = None
+    decimals: Optional[int] = None
+    unit_label: str = ""
+    values: Optional[Dict[str, str]] = None
+
+
+@dataclass
+class ExportTemplate:
+    """A CSV layout for one target app."""
+
+    name: str
+    columns: List[ExportColumn]
+    delimiter: str = ","
+    units_row: bool = False
+
+    @classmethod
+    def from_dict(cls, data: Any) -> "ExportTemplate":
+        """
+        Build a template from its JSON form.
+
+        Raises:
+            ValueError: If the template is malformed
+        """
+        if not isinstance(data, dict) or not isinstance(data.get("columns"), list):
+            raise ValueError("template needs a \"columns\" list")
+        columns = []
+        for i, col in enumerate(data["columns"], start=1):
+            if not isinstance(col, dict) or not isinstance(col.get("header"), str):
+                raise ValueError(f"column {i} needs a \"header\"")
+            try:
+                column = ExportColumn(**col)
+            except TypeError as e:
+                raise ValueError(f"column {i}: {e}") from None
+            source_unit = _FIELD_UNITS.get(column.field or "")
+            if column.unit and (source_unit, column.unit) not in _CONVERSIONS:
+                raise ValueError(f"column {i}: can't convert {column.field} to {column.unit}")
+            columns.append(column)
+        delimiter = data.get("delimiter", ",")
+        if not isinstance(delimiter, str) or len(delimiter) != 1:
+            raise ValueError("delimiter must be a single character")
+        return cls(
+            name=str(data.get("name", "custom")),
+            columns=columns,
+            delimiter=delimiter,
+            units_row=bool(data.get("units_row", False)),
+        )
+
+
+def _column(header, source=None, decimals=None, unit_label=""):
+    return {"header": header, "field": source, "decimals": decimals, "unit_label": unit_label}
+
+
+BUILTIN_TEMPLATES = {
+    "openflight": {
+        "name": "openflight",
+        "columns": [
+            _column("date", "date"),
+            _column("time", "time"),
+            _column("session", "session"),
+            _column("shot_number", "shot_number"),
+            _column("club", "club"),
+            _column("ball_speed_mph", "ball_speed_mph", 1),
+            _column("club_speed_mph", "club_speed_mph", 1),
+            _column("smash_factor", "smash_factor", 2),
+            _column("ball_energy_j", "ball_energy_j", 1),
+            _column("club_energy_j", "club_energy_j", 1),
+            _column("energy_transfer_pct", "energy_transfer_pct", 1),
+            _column("carry_yards", "estimated_carry_yards", 0),
+            _column("launch_angle_vertical", "launch_angle_vertical", 1),
+            _column("launch_angle_horizontal", "launch_angle_horizontal", 1),
+            _column("spin_rpm", "spin_rpm", 0),
+            _column("hole_number", "hole_number"),
+            _column("hole_shot", "hole_shot"),
+            _column("mode", "mode"),
+        ],
+    },
+    "trackman": {
+        "name": "trackman",
+        "units_row": True,
+        "columns": [
+            _column("Date", "datetime"),
+            _column("Player", "tags.player"),
+            _column("Club", "club_name"),
+            _column("Club Speed", "club_speed_mph", 1, "[mph]"),
+            _column("Attack Angle", unit_label="[deg]"),
+            _column("Club Path", unit_label="[deg]"),
+            _column("Face Angle", unit_label="[deg]"),
+            _column("Ball Speed", "ball_speed_mph", 1, "[mph]"),
+            _column("Smash Factor", "smash_factor", 2),
+            _column("Launch Angle", "launch_angle_vertical", 1, "[deg]"),
+            _column("Launch Direction", "launch_angle_horizontal", 1, "[deg]"),
+            _column("Spin Rate", "spin_rpm", 0, "[rpm]"),
+            _column("Spin Axis", unit_label="[deg]"),
+            _column("Carry", "estimated_carry_yards", 1, "[yds]"),
+        ],
+    },
+    "garmin": {
+        "name": "garmin",
+        "units_row": True,
+        "columns": [
+            _column("Date", "datetime"),
+            _column("Player", "tags.player"),
+            _column("Club Name", "club_name"),
+            _column("Club Type", "club_name"),
+            _column("Club Speed", "club_speed_mph", 1, "[mph]"),
+            _column("Attack Angle", unit_label="[deg]"),
+            _column("Club Path", unit_label="[deg]"),
+            _column("Club Face", unit_label="[deg]"),
+            _column("Face to Path", unit_label="[deg]"),
+            _column("Ball Speed", "ball_speed_mph", 1, "[mph]"),
+            _column("Smash Factor", "smash_factor", 2),
+            _column("Launch Angle", "launch_angle_vertical", 1, "[deg]"),
+            _column("Launch Direction", "launch_angle_horizontal", 1, "[deg]"),
+            _column("Backspin", unit_label="[rpm]"),
+            _column("Sidespin", unit_label="[rpm]"),
+            _column("Spin Rate", "spin_rpm", 0, "[rpm]"),
+            _column("Spin Axis", unit_label="[deg]"),
+            _column("Carry Distance", "estimated_carry_yards", 1, "[yds]"),
+        ],
+    },
+}
+
+
+def load_template(
+    name: str, template_dir: Optional[Path] = None, allow_paths: bool = True
+) -> ExportTemplate:
+    """
+    Load a template by name (custom, then built-in) or from a file path.
+
+    Args:
+        name: Template name, or path to a template JSON file
+        template_dir: Custom template directory (default: ~/.openflight/export_templates)
+        allow_paths: Accept file paths (off for names that come from the network)
+
+    Raises:
+        FileNotFoundError: If there is no such template
+        ValueError: If the template file is malformed
+    """
+    if allow_paths and name.endswith(".json"):
+        path = Path(name).expanduser()
+    elif _NAME_PATTERN.match(name):
+        path = (template_dir or DEFAULT_TEMPLATE_DIR) / f"{name}.json"
+    else:
+        raise FileNotFoundError(f"No export template named {name!r}")
+    if path.is_file():
+        try:
+            data = json.loads(path.read_text(encoding="utf-8"))
+        except ValueError as e:
+            raise ValueError(f"{path}: {e}") from None
+        return ExportTemplate.from_dict(data)
+    if name in BUILTIN_TEMPLATES:
+        return ExportTemplate.from_dict(BUILTIN_TEMPLATES[name])
+    raise FileNotFoundError(f"No export template named {name!r}")
+
+
+def list_templates(template_dir: Optional[Path] = None) -> List[str]:
+    """Built-in and custom template names."""
+    names = set(BUILTIN_TEMPLATES)
+    directory = template_dir or DEFAULT_TEMPLATE_DIR
+    if directory.is_dir():
+        names.update(p.stem for p in directory.glob("*.json"))
+    return sorted(names)
+
+
+def _timestamp(shot: dict) -> Optional[datetime]:
+    try:
+        return datetime.fromisoformat(shot.get("ts") or shot.get("timestamp"))
+    except (TypeError, ValueError):
+        return None
+
+
+def field_value(shot: dict, name: str) -> Any:
+    """Value of a template field for one shot (a log entry or shot_to_dict)."""
+    when = _timestamp(shot)
+    if name == "date":
+        return when.date().isoformat() if when else None
+    if name == "time":
+        return when.time().isoformat(timespec="seconds") if when else None
+    if name == "datetime":
+        return when.strftime("%Y-%m-%d %H:%M:%S") if when else None
+    if name == "club_name":
+        club = shot.get("club", "driver")
+        return CLUB_NAMES.get(club, club)
+    if name.startswith("tags."):
+        return (shot.get("tags") or {}).get(name[len("tags."):])
+    if name in ENERGY_FIELDS and name not in shot:
+        return energy_from_dict(shot)[name]  # Not logged: worked out from the speeds
+    return shot.get(name)
+
+
+def _format(column: ExportColumn, value: Any) -> str:
+    if value is None:
+        return ""
+    if column.values and str(value) in column.values:
+        return column.values[str(value)]
+    if isinstance(value, (int, float)) and not isinstance(value, bool):
+        if column.unit:
+            value *= _CONVERSIONS[(_FIELD_UNITS[column.field], column.unit)]
+        if column.decimals is not None:
+            return f"{value:.{column.decimals}f}"
+    return str(value)
+
+
+def write_csv(shots: List[dict], template: ExportTemplate, out: TextIO):
+    """
+    Write shots as CSV in a template's layout.
+
+    Args:
+        shots: shot_detected log entries (with "session" and "tags" added)
+            or shot_to_dict output
+        template: Layout to write
+        out: Text stream to write to
+    """
+    writer = csv.writer(out, delimiter=template.delimiter, lineterminator="\n")
+    writer.writerow([c.header for c in template.columns])
+    if template.units_row:
+        writer.writerow([c.unit_label for c in template.columns])
+    for shot in shots:
+        writer.writerow(
+            [
+                _format(c, field_value(shot, c.field)) if c.field else ""
+                for c in template.columns
+            ]
+        )
+
+
+def to_csv(shots: List[dict], template: ExportTemplate) -> str:
+    """write_csv to a string."""
+    out = io.StringIO()
+    write_csv(shots, template, out)
+    return out.getvalue()
+
+
+def load_session_shots(
+    paths: List[str], club: Optional[str] = None, include_all: bool = False
+) -> List[dict]:
+    """
+    Read shots from session logs for export.
+
+    Args:
+        paths: Session log files
+        club: Only this club (None = all)
+        include_all: Include mock and injected shots
+
+    Returns:
+        shot_detected entries with "session" and "tags" added, in file order,
+        without mulligans
+    """
+    shots = []
+    for path in paths:
+        session = summarize_session(path).session_id or Path(path).stem
+        for shot in read_shots(path):
+            if not include_all and shot.get("mode") in _SKIPPED_MODES:
+                continue
+            if club and shot.get("club", "driver") != club:
+                continue
+            shots.append({**shot, "session": session})
+    return shots
+
+
+def main():
+    """CLI entry point for CSV export."""
+    parser = argparse.ArgumentParser(
+        description="Export recorded shots as CSV for other apps, an HTML page or a FIT activity"
+    )
+    parser.add_argument(
+        "patterns",
+        nargs="*",
+        help="Session log files or glob patterns (default: all in ~/openflight_sessions)",
+    )
+    parser.add_argument(
+        "--template",
+        "-t",
+        default="openflight",
+        help="Template name or JSON file (default: openflight)",
+    )
+    parser.add_argument(
+        "--template-dir",
+        help="Directory for custom templates (default: ~/.openflight/export_templates)",
+    )
+    parser.add_argument(
+        "--format",
+        "-f",
+        choices=["csv", "html", "fit"],
+        default="csv",
+        help="csv (through --template), html (a self-contained page to share) or fit "
+        "(a golf activity for Garmin Connect and other fitness apps)",
+    )
+    parser.add_argument("--title", help="Page title for --format html")
+    parser.add_argument("--output", "-o", help="Output file (default: stdout)")
+    parser.add_argument("--club", help="Only shots with this club, e.g. 7-iron")
+    parser.add_argument("--all", action="store_true", help="Include mock and injected shots")
+    parser.add_argument(
+        "--list-templates", action="store_true", help="List available templates and exit"
+    )
+    args = parser.parse_args()
+
+    template_dir = Path(args.template_dir).expanduser() if args.template_dir else None
+    if args.list_templates:
+        for name in list_templates(template_dir):
+            print(name)
+        return
+
+    try:
+        template = load_template(args.template, template_dir)
+    except (FileNotFoundError, ValueError) as e:
+        parser.error(str(e))
+
+    patterns = args.patterns or [str(SessionLogger.DEFAULT_LOG_DIR / "session_*.jsonl")]
+    shots = load_session_shots(find_sessions(patterns), club=args.club, include_all=args.all)
+    if not shots:
+        print("No shots found", file=sys.stderr)
+        sys.exit(1)
+
+    if args.format == "fit":
+        # Imported here: fit_export builds on this module
+        from .fit_export import to_fit  # pylint: disable=import-outside-toplevel
+
+        data = to_fit(shots)
+        if args.output:
+            Path(args.output).write_bytes(data)
+            print(f"Exported {len(shots)} shot(s) to {args.output} (fit)")
+        else:
+            sys.stdout.buffer.write(data)
+        return
+
+    if args.format == "html":
+        # Imported here: html_export builds on this module
+        from .html_export import to_html  # pylint: disable=import-outside-toplevel
+
+        page = to_html(shots, title=args.title or "OpenFlight session")
+        if args.output:
+            Path(args.output).write_text(page, encoding="utf-8")
+            print(f"Exported {len(shots)} shot(s) to {args.output} (html)")
+        else:
+            sys.stdout.write(page)
+        return
+
+    if args.output:
+        with open(args.output, "w", newline="", encoding="utf-8") as f:
+            write_csv(shots, template, f)
+        print(f"Exported {len(shots)} shot(s) to {args.output} ({template.name})")
+    else:
+        write_csv(shots, template, sys.stdout)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,172 @@
+"""
+Hot standby sim endpoints.
+
+A sim that crashes mid-round would otherwise lose every shot until it's
+restarted. With a primary and standby endpoints (GSPro on the gaming PC,
+then a logging endpoint), each shot goes to one of them:
+
+    openflight-server --sim-url http://gaming-pc:8000/shot --sim-url http://nas:9000/log
+
+- failover: a shot the active endpoint doesn't take is sent on to the
+  next endpoint in order, and the failed one is marked down
+- health checks: every interval, each endpoint's host and port are
+  probed with a TCP connect; an endpoint that stops answering is marked
+  down before a shot has to find out
+- fail-back: once a higher-priority endpoint answers again it becomes
+  active, so shots return to the primary when the sim is back
+
+Endpoints are tried once per shot (no backoff between them): a standby
+is there so that a dead endpoint needn't be waited for. When every
+endpoint is down, each shot still tries them all, primary first.
+"""
+
+import logging
+import socket
+import threading
+import urllib.parse
+from typing import Callable, Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+def tcp_probe(url: str, timeout_sec: float = 2.0) -> bool:
+    """Whether a URL's host accepts a connection on its port."""
+    parts = urllib.parse.urlsplit(url)
+    if not parts.hostname:
+        return False
+    port = parts.port or (443 if parts.scheme == "https" else 80)
+    try:
+        with socket.create_connection((parts.hostname, port), timeout=timeout_sec):
+            return True
+    except OSError:
+        return False
+
+
+class FailoverGroup:
+    """
+    Sim endpoints in priority order, of which one is active.
+
+    Example:
+        group = FailoverGroup(["http://gaming-pc:8000/shot", "http://nas:9000/log"])
+        group.start()
+        for url in group.order():
+            ...  # deliver, then group.record(url, delivered)
+    """
+
+    DEFAULT_HEALTH_INTERVAL_SEC = 10.0
+
+    def __init__(
+        self,
+        urls: List[str],
+        health_interval_sec: float = DEFAULT_HEALTH_INTERVAL_SEC,
+        probe: Callable[[str], bool] = tcp_probe,
+        on_change: Optional[Callable[[Optional[str], Optional[str]], None]] = None,
+    ):
+        """
+        Initialize the group.
+
+        Args:
+            urls: Primary endpoint, then standbys in the order to use them
+            health_interval_sec: How often endpoints are probed (0 = only
+                deliveries mark them down, and nothing fails back)
+            probe: Whether an endpoint is up (default: a TCP connect)
+            on_change: Called with the previous and new active endpoint
+                (None when all are down) when it changes
+
+        Raises:
+            ValueError: If no URLs are given or the interval is negative
+        """
+        if not urls:
+            raise ValueError("a failover group needs at least one endpoint")
+        if health_interval_sec < 0:
+            raise ValueError("health check interval can't be negative")
+        self.urls = list(urls)
+        self.health_interval_sec = health_interval_sec
+        self.probe = probe
+        self.on_change = on_change
+        self.failovers = 0
+        self._down: Dict[str, bool] = {url: False for url in self.urls}
+        self._lock = threading.Lock()
+        self._stop_event = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def active(self) -> Optional[str]:
+        """The endpoint shots go to first (None when all are down)."""
+        with self._lock:
+            return self._active()
+
+    def _active(self) -> Optional[str]:
+        return next((url for url in self.urls if not self._down[url]), None)
+
+    def order(self) -> List[str]:
+        """Endpoints to try for a shot: the active one, then the ones after it."""
+        with self._lock:
+            active = self._active()
+        if active is None:
+            return list(self.urls)
+        return self.urls[self.urls.index(active):]
+
+    def record(self, url: str, delivered: bool):
+        """Note a delivery's outcome: an endpoint that didn't take a shot is down."""
+        self._set_down(url, not delivered)
+
+    def check(self):
+        """Probe every endpoint now, marking each up or down."""
+        for url in self.urls:
+            self._set_down(url, not self.probe(url))
+
+    def _set_down(self, url: str, down: bool):
+        with self._lock:
+            previous = self._active()
+            self._down[url] = down
+            active = self._active()
+            if active == previous:
+                return
+            failed_over = previous is not None and (
+                active is None or self.urls.index(active) > self.urls.index(previous)
+            )
+            if failed_over:
+                self.failovers += 1
+        if active is None:
+            logger.error("All sim endpoints down")
+        elif failed_over:
+            logger.warning("Sim endpoint %s down: failing over to %s", previous, active)
+        else:
+            logger.info("Sim endpoint %s up: shots go there again", active)
+        if self.on_change:
+            try:
+                self.on_change(previous, active)
+            except Exception as e:  # pylint: disable=broad-except
+                logger.warning("Failover callback failed: %s", e)
+
+    def to_dict(self) -> dict:
+        """State for the REST API and status."""
+        with self._lock:
+            return {
+                "active": self._active(),
+                "endpoints": [{"url": url, "up": not self._down[url]} for url in self.urls],
+                "failovers": self.failovers,
+            }
+
+    def start(self):
+        """Start health checks in a background thread."""
+        if not self.health_interval_sec or (self._thread and self._thread.is_alive()):
+            return
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def stop(self):
+        """Stop health checks."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=5.0)
+            self._thread = None
+
+    def _run(self):
+        while not self._stop_event.wait(self.health_interval_sec):
+            try:
+                self.check()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.error("Sim endpoint health check failed: %s", e)
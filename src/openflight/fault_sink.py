@@ -0,0 +1,251 @@
+"""
+Fault-injecting webhook receiver for testing delivery retries.
+
+A local HTTP endpoint that misbehaves on purpose, according to a fault
+profile, so the webhook retry/backoff path can be exercised without
+unplugging a sim or killing its bridge by hand. Each request gets one of:
+
+- accept: 200 and the payload is recorded
+- delay: held for a random time (up to max_delay_sec), then accepted
+- error: HTTP 503 (retryable)
+- drop: body read, connection closed with no response
+- reset: connection reset before anything is read
+
+Outages (listening socket closed, so connections are refused outright) can
+be scheduled with go_down()/come_up() or --outage-every on the command line.
+
+Usage:
+    openflight-fault-sink --port 9000 --drop 0.2 --error 0.1 --delay 0.2 --max-delay 3
+    openflight-server --mock --webhook-url http://localhost:9000/hook
+"""
+
+import argparse
+import json
+import random
+import socket
+import struct
+import threading
+import time
+from dataclasses import dataclass
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import List, Optional, Tuple
+
+from .webhooks import EVENT_HEADER
+
+OUTCOMES = ("reset", "drop", "error", "delay", "accept")
+
+
+@dataclass
+class FaultProfile:
+    """
+    Probability of each fault per request; the remainder is accepted.
+
+    Attributes:
+        reset: Connection reset before the request is read
+        drop: Request read, connection closed without a response
+        error: HTTP 503 response
+        delay: Response held for up to max_delay_sec, then accepted
+        max_delay_sec: Longest delay
+        seed: Random seed for repeatable fault sequences
+    """
+
+    reset: float = 0.0
+    drop: float = 0.0
+    error: float = 0.0
+    delay: float = 0.0
+    max_delay_sec: float = 2.0
+    seed: Optional[int] = None
+
+    def __post_init__(self):
+        rates = (self.reset, self.drop, self.error, self.delay)
+        if any(rate < 0 for rate in rates) or sum(rates) > 1.0:
+            raise ValueError("fault rates must be non-negative and sum to at most 1")
+        if self.max_delay_sec < 0:
+            raise ValueError("max_delay_sec must be non-negative")
+
+    def choose(self, rng: random.Random) -> str:
+        """Pick the outcome for one request."""
+        roll = rng.random()
+        for outcome in OUTCOMES[:-1]:
+            roll -= getattr(self, outcome)
+            if roll < 0:
+                return outcome
+        return "accept"
+
+
+class FaultInjectingReceiver:
+    """
+    HTTP endpoint that applies a fault profile to each request.
+
+    Example:
+        receiver = FaultInjectingReceiver(FaultProfile(drop=0.3, seed=1))
+        sink = WebhookSink([receiver.url], backoff_sec=0.01)
+        ...
+        assert len(receiver.accepted) == shots_sent
+        receiver.close()
+    """
+
+    def __init__(self, profile: FaultProfile, host: str = "127.0.0.1", port: int = 0):
+        """
+        Initialize and start listening.
+
+        Args:
+            profile: Faults to inject
+            host: Interface to listen on
+            port: Port to listen on (0 = any free port)
+        """
+        self.profile = profile
+        self.host = host
+        self.accepted: List[dict] = []  # Payloads answered with 200
+        self.outcomes: List[str] = []  # Outcome of every request, in order
+        self._rng = random.Random(profile.seed)
+        self._lock = threading.Lock()
+        self._server: Optional[ThreadingHTTPServer] = None
+        self._thread: Optional[threading.Thread] = None
+        self.port = port
+        self.come_up()
+
+    @property
+    def url(self) -> str:
+        """Webhook URL to configure on the sender."""
+        return f"http://{self.host}:{self.port}/hook"
+
+    def _next_outcome(self) -> Tuple[str, float]:
+        with self._lock:
+            outcome = self.profile.choose(self._rng)
+            self.outcomes.append(outcome)
+            delay = self._rng.uniform(0, self.profile.max_delay_sec)
+        return outcome, delay
+
+    def _make_handler(self):
+        receiver = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_POST(self):  # pylint: disable=invalid-name
+                outcome, delay = receiver._next_outcome()  # pylint: disable=protected-access
+                if outcome == "reset":
+                    # Zero linger turns close() into a TCP RST
+                    self.connection.setsockopt(
+                        socket.SOL_SOCKET, socket.SO_LINGER, struct.pack("ii", 1, 0)
+                    )
+                    self.close_connection = True
+                    return
+
+                length = int(self.headers.get("Content-Length", 0))
+                body = self.rfile.read(length)
+
+                if outcome == "drop":
+                    self.close_connection = True
+                    return
+                if outcome == "error":
+                    self.send_response(503)
+                    self.end_headers()
+                    return
+                if outcome == "delay":
+                    time.sleep(delay)
+
+                try:
+                    payload = json.loads(body)
+                except ValueError:
+                    payload = {"raw": body.decode("utf-8", "replace")}
+                with receiver._lock:  # pylint: disable=protected-access
+                    receiver.accepted.append(payload)
+                self.send_response(200)
+                self.end_headers()
+                event = self.headers.get(EVENT_HEADER, "?")
+                if outcome == "delay":
+                    print(f"[SINK] {event} accepted after {delay:.1f}s")
+                else:
+                    print(f"[SINK] {event} accepted")
+
+            def log_message(self, *args):
+                pass
+
+        return Handler
+
+    def come_up(self):
+        """Start (or restart after an outage) listening on the same port."""
+        if self._server:
+            return
+        self._server = ThreadingHTTPServer((self.host, self.port), self._make_handler())
+        self._server.daemon_threads = True
+        self.port = self._server.server_address[1]
+        self._thread = threading.Thread(target=self._server.serve_forever, daemon=True)
+        self._thread.start()
+
+    def go_down(self):
+        """Close the listening socket so new connections are refused."""
+        if not self._server:
+            return
+        self._server.shutdown()
+        self._server.server_close()
+        self._server = None
+        self._thread = None
+
+    def close(self):
+        """Stop the receiver."""
+        self.go_down()
+
+
+def main():
+    """CLI entry point for the fault-injecting receiver."""
+    parser = argparse.ArgumentParser(
+        description="Webhook receiver that injects delays, drops and refused connections"
+    )
+    parser.add_argument("--host", default="127.0.0.1", help="Interface to listen on")
+    parser.add_argument("--port", type=int, default=9000, help="Port to listen on")
+    parser.add_argument("--reset", type=float, default=0.0, help="Fraction of connections reset")
+    parser.add_argument(
+        "--drop", type=float, default=0.0, help="Fraction of requests closed without a response"
+    )
+    parser.add_argument("--error", type=float, default=0.0, help="Fraction answered with 503")
+    parser.add_argument("--delay", type=float, default=0.0, help="Fraction of responses delayed")
+    parser.add_argument(
+        "--max-delay", type=float, default=2.0, help="Longest delay in seconds (default: 2)"
+    )
+    parser.add_argument(
+        "--outage-every",
+        type=float,
+        help="Refuse all connections for --outage-sec every this many seconds",
+    )
+    parser.add_argument(
+        "--outage-sec", type=float, default=5.0, help="Length of each outage (default: 5)"
+    )
+    parser.add_argument("--seed", type=int, help="Random seed for a repeatable fault sequence")
+    args = parser.parse_args()
+
+    try:
+        profile = FaultProfile(
+            reset=args.reset,
+            drop=args.drop,
+            error=args.error,
+            delay=args.delay,
+            max_delay_sec=args.max_delay,
+            seed=args.seed,
+        )
+    except ValueError as e:
+        parser.error(str(e))
+
+    receiver = FaultInjectingReceiver(profile, host=args.host, port=args.port)
+    print(f"[SINK] Listening on {receiver.url}")
+    try:
+        while True:
+            if not args.outage_every:
+                time.sleep(1)
+                continue
+            time.sleep(args.outage_every)
+            receiver.go_down()
+            print(f"[SINK] Outage: refusing connections for {args.outage_sec:.0f}s")
+            time.sleep(args.outage_sec)
+            receiver.come_up()
+            print("[SINK] Back up")
+    except KeyboardInterrupt:
+        pass
+    finally:
+        receiver.close()
+        counts = {o: receiver.outcomes.count(o) for o in OUTCOMES if o in receiver.outcomes}
+        print(f"[SINK] {len(receiver.accepted)} accepted; outcomes: {counts}")
+
+
+if __name__ == "__main__":
+    main()
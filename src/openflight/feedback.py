@@ -0,0 +1,275 @@
+"""
+Shot feedback at the mat: an LED color or a tone for each shot.
+
+Without a screen in view, a light or a beep says at once how the shot
+was. Each shot's value is mapped onto a 0-1 intensity curve, which drives
+an RGB LED (red at 0 through yellow to green at 1, lit for a few seconds)
+or a tone (two octaves, low to high, pitch rising with the intensity):
+
+    openflight-server --feedback tone
+    openflight-server --feedback led --led-pins 5,6,13 --feedback-metric target
+    openflight-server --feedback led --feedback tone --feedback-range 90-150
+
+Metrics (--feedback-metric), with the default --feedback-range:
+
+    ball_speed  ball speed, low-high mph (60-170)
+    carry       carry, low-high yards (50-300)
+    target      closeness of the carry to the practice target (see scoring):
+                full at low yards off or closer, none at high yards off (0-30)
+
+Feedback runs off the server's event bus (the events /api/stream gets), so
+it follows every shot however it was detected. The LED needs gpiozero; the
+tones need aplay or afplay, like the tempo cues.
+"""
+
+import logging
+import threading
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple
+
+from .tempo import play_tone
+
+logger = logging.getLogger(__name__)
+
+# Metric -> (default low, default high)
+FEEDBACK_METRICS: Dict[str, Tuple[float, float]] = {
+    "ball_speed": (60.0, 170.0),
+    "carry": (50.0, 300.0),
+    "target": (0.0, 30.0),
+}
+FEEDBACK_OUTPUTS = ("led", "tone")
+DEFAULT_LED_PINS = (5, 6, 13)  # BCM: red, green, blue
+
+LOW_HZ = 440
+HIGH_HZ = 1760  # Two octaves up
+
+
+class FeedbackCurve:
+    """
+    Maps a shot onto a 0-1 feedback intensity.
+
+    Example:
+        curve = FeedbackCurve("ball_speed", 60, 170)
+        curve.intensity(shot_to_dict(shot))  # 0.8 for 148 mph
+    """
+
+    def __init__(
+        self,
+        metric: str = "ball_speed",
+        low: Optional[float] = None,
+        high: Optional[float] = None,
+    ):
+        """
+        Initialize the curve.
+
+        Args:
+            metric: One of FEEDBACK_METRICS
+            low: Value giving intensity 0 (for target: 1), default per metric
+            high: Value giving intensity 1 (for target: 0), default per metric
+
+        Raises:
+            ValueError: If the metric is unknown or low >= high
+        """
+        if metric not in FEEDBACK_METRICS:
+            raise ValueError(
+                f"Unknown feedback metric {metric!r} "
+                f"(choose from: {', '.join(FEEDBACK_METRICS)})"
+            )
+        default_low, default_high = FEEDBACK_METRICS[metric]
+        self.metric = metric
+        self.low = default_low if low is None else low
+        self.high = default_high if high is None else high
+        if self.low >= self.high:
+            raise ValueError(f"feedback range must be low-high, got {self.low:g}-{self.high:g}")
+
+    @classmethod
+    def from_spec(cls, metric: str, range_text: Optional[str] = None) -> "FeedbackCurve":
+        """
+        Build a curve from --feedback-metric and --feedback-range ("90-150").
+
+        Raises:
+            ValueError: If either is invalid
+        """
+        if not range_text:
+            return cls(metric)
+        try:
+            low_text, high_text = range_text.split("-")
+            low, high = float(low_text), float(high_text)
+        except ValueError:
+            raise ValueError(f"feedback range must be LOW-HIGH, got {range_text!r}") from None
+        return cls(metric, low, high)
+
+    def intensity(self, shot: dict) -> Optional[float]:
+        """
+        The shot's intensity.
+
+        Args:
+            shot: The shot as shot_to_dict gives it
+
+        Returns:
+            0-1, or None if the shot lacks the value (e.g. no target set)
+        """
+        if self.metric == "ball_speed":
+            value = shot.get("ball_speed_mph")
+        elif self.metric == "carry":
+            value = shot.get("estimated_carry_yards")
+        else:
+            carry, target = shot.get("estimated_carry_yards"), shot.get("target_yards")
+            if carry is None or target is None:
+                return None
+            off = abs(carry - target)
+            return 1.0 - max(0.0, min(1.0, (off - self.low) / (self.high - self.low)))
+        if value is None:
+            return None
+        return max(0.0, min(1.0, (value - self.low) / (self.high - self.low)))
+
+    def describe(self) -> str:
+        """The curve as text, e.g. "ball_speed 60-170"."""
+        return f"{self.metric} {self.low:g}-{self.high:g}"
+
+
+def gradient_color(intensity: float) -> Tuple[float, float, float]:
+    """Red at 0, yellow at 0.5, green at 1, as (r, g, b) in 0-1."""
+    intensity = max(0.0, min(1.0, intensity))
+    if intensity < 0.5:
+        return (1.0, intensity * 2, 0.0)
+    return ((1.0 - intensity) * 2, 1.0, 0.0)
+
+
+def tone_hz(intensity: float, low_hz: int = LOW_HZ, high_hz: int = HIGH_HZ) -> int:
+    """Tone pitch for an intensity, evenly spaced in pitch (not Hz) from low to high."""
+    intensity = max(0.0, min(1.0, intensity))
+    return round(low_hz * (high_hz / low_hz) ** intensity)
+
+
+class ToneOutput:
+    """Plays a tone pitched by the intensity."""
+
+    def __init__(self, duration_sec: float = 0.25, play: Callable[[int, float], bool] = play_tone):
+        """
+        Initialize tone output.
+
+        Args:
+            duration_sec: Tone length
+            play: Tone player (for tests)
+        """
+        self.duration_sec = duration_sec
+        self._play = play
+        if not self._play(LOW_HZ, 0.01):
+            logger.warning("No audio player (aplay/afplay) found, shot tones are silent")
+
+    def show(self, intensity: float):
+        """Play the intensity's tone (doesn't wait for it)."""
+        self._play(tone_hz(intensity), self.duration_sec)
+
+    def close(self):
+        """Nothing to release."""
+
+
+class LedOutput:
+    """Lights an RGB LED in the intensity's color for a few seconds."""
+
+    def __init__(
+        self,
+        pins: Sequence[int] = DEFAULT_LED_PINS,
+        hold_sec: float = 3.0,
+        led: Any = None,
+    ):
+        """
+        Initialize LED output.
+
+        Args:
+            pins: BCM pins of the red, green and blue legs
+            hold_sec: How long the color stays on after a shot
+            led: Object with a settable color and off() (default: gpiozero RGBLED)
+
+        Raises:
+            RuntimeError: If gpiozero isn't installed or the pins can't be used
+        """
+        if led is None:
+            try:
+                from gpiozero import RGBLED  # pylint: disable=import-outside-toplevel
+            except ImportError:
+                raise RuntimeError(
+                    "LED feedback needs gpiozero. Install with: uv pip install gpiozero lgpio"
+                ) from None
+            try:
+                led = RGBLED(*pins)
+            except Exception as e:  # pylint: disable=broad-except
+                raise RuntimeError(f"Could not use GPIO pins {pins}: {e}") from None
+        self.hold_sec = hold_sec
+        self._led = led
+        self._timer: Optional[threading.Timer] = None
+        self._lock = threading.Lock()
+
+    def show(self, intensity: float):
+        """Light the intensity's color, turning it off after hold_sec."""
+        with self._lock:
+            if self._timer:
+                self._timer.cancel()
+            self._led.color = gradient_color(intensity)
+            self._timer = threading.Timer(self.hold_sec, self._led.off)
+            self._timer.daemon = True
+            self._timer.start()
+
+    def close(self):
+        """Turn the LED off."""
+        with self._lock:
+            if self._timer:
+                self._timer.cancel()
+                self._timer = None
+            self._led.off()
+
+
+class ShotFeedback:
+    """
+    Event bus consumer giving each shot's feedback on every output.
+
+    Example:
+        feedback = ShotFeedback(FeedbackCurve("ball_speed"), [ToneOutput()])
+        add_event_sink(feedback.on_event)
+    """
+
+    def __init__(self, curve: FeedbackCurve, outputs: List[Any]):
+        """
+        Initialize shot feedback.
+
+        Args:
+            curve: How shots map to intensity
+            outputs: ToneOutput / LedOutput instances
+        """
+        self.curve = curve
+        self.outputs = outputs
+
+    def on_event(self, event: str, data: dict):
+        """Handle an event bus event; only shots give feedback."""
+        if event != "shot":
+            return
+        intensity = self.curve.intensity(data.get("shot") or {})
+        if intensity is None:
+            return
+        for output in self.outputs:
+            try:
+                output.show(intensity)
+            except Exception as e:  # pylint: disable=broad-except
+                logger.warning("Shot feedback failed: %s", e)
+
+    def close(self):
+        """Release the outputs."""
+        for output in self.outputs:
+            output.close()
+
+
+def parse_pins(text: str) -> Tuple[int, int, int]:
+    """
+    Parse --led-pins ("5,6,13": red, green, blue).
+
+    Raises:
+        ValueError: If it isn't three pin numbers
+    """
+    try:
+        pins = tuple(int(p) for p in text.split(","))
+    except ValueError:
+        raise ValueError(f"expected three BCM pins like 5,6,13, got {text!r}") from None
+    if len(pins) != 3 or any(p < 0 for p in pins):
+        raise ValueError(f"expected three BCM pins like 5,6,13, got {text!r}")
+    return pins  # type: ignore[return-value]
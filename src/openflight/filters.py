@@ -0,0 +1,272 @@
+"""
+Per-reading filters, run as a configurable pipeline.
+
+Every reading passes through an ordered list of filters before it can
+join a shot. The first filter that rejects a reading drops it and gives
+the reason. The pipeline is a MonitorConfig setting, e.g.
+
+    "filters": ["clutter", "speed", "direction", "magnitude", "refractory"]
+
+so filters can be turned off, reordered or added per setup (via
+PUT /api/config or a profile's "monitor" settings). Without it, each
+radar mode keeps its default: speed, direction, magnitude, range and
+angle with the radar's internal processing, direction, range and angle
+only with I/Q streaming (CFAR already filtered speed and signal strength).
+
+Built-in filters:
+- speed: outside min_club_speed_mph (min_ball_speed_mph without club
+  detection) to max_ball_speed_mph
+- direction: not moving toward the target for the mount position
+- magnitude: weaker than min_magnitude
+- clutter: the same speed over and over (a fan, a flapping net): within
+  clutter_tolerance_mph of clutter_min_repeats earlier readings in the
+  last clutter_window_sec
+- refractory: within refractory_sec after a shot (the ball bouncing back
+  off the net, a follow-through)
+- range: nearer than min_range_m or farther than max_range_m (a car
+  passing the open garage door), for radars that report range (the
+  IWR6843's point cloud); readings without a range always pass, and
+  max_range_m = 0 leaves the far side open
+- angle: outside the launch corridor, min_azimuth_deg to max_azimuth_deg
+  (right of boresight positive) and min_elevation_deg to
+  max_elevation_deg (up positive), for radars that report angles (the
+  IWR6843); readings without angles always pass
+
+Library users can add their own with register_filter and then name them
+in the pipeline:
+
+    class MaxObjectsFilter(Filter):
+        def check(self, reading, context):
+            if len(reading.frame_objects or []) > 3:
+                return "too many objects in frame"
+            return None
+
+    register_filter("max-objects", MaxObjectsFilter)
+"""
+
+from abc import ABC, abstractmethod
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Callable, Deque, Dict, FrozenSet, List, Optional, Sequence, Tuple
+
+from .frames import Direction, SpeedReading
+
+# Pipelines used when the config doesn't name one
+LEGACY_FILTERS = ("speed", "direction", "magnitude", "range", "angle")
+IQ_STREAMING_FILTERS = ("direction", "range", "angle")
+
+
+@dataclass
+class FilterContext:
+    """What filters may need besides the reading itself."""
+
+    config: Any  # The active MonitorConfig
+    ball_directions: FrozenSet[Direction]
+    min_speed_mph: float  # Slowest reading of interest (club or ball)
+    now: float  # time.monotonic() when the reading arrived
+    last_shot_time: Optional[float] = None  # When the last shot was processed
+
+
+class Filter(ABC):
+    """One stage of the reading pipeline."""
+
+    name = ""
+
+    @abstractmethod
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        """
+        Decide whether a reading passes.
+
+        Returns:
+            None to keep the reading, otherwise why it was dropped
+        """
+
+
+class SpeedBandFilter(Filter):
+    """Drops readings outside the club-to-ball speed band."""
+
+    name = "speed"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        max_speed = context.config.max_ball_speed_mph
+        if not context.min_speed_mph <= reading.speed <= max_speed:
+            return f"Speed {reading.speed:.1f} outside range {context.min_speed_mph}-{max_speed}"
+        return None
+
+
+class DirectionFilter(Filter):
+    """Drops readings not moving toward the target."""
+
+    name = "direction"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        if reading.direction not in context.ball_directions:
+            return f"Direction {reading.direction.value} is not toward the target"
+        return None
+
+
+class MagnitudeFilter(Filter):
+    """Drops weak returns."""
+
+    name = "magnitude"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        min_magnitude = context.config.min_magnitude
+        if reading.magnitude is not None and reading.magnitude < min_magnitude:
+            return f"Magnitude {reading.magnitude:.1f} below minimum {min_magnitude}"
+        return None
+
+
+class ClutterFilter(Filter):
+    """Drops a speed that keeps repeating, which a swing never does."""
+
+    name = "clutter"
+
+    def __init__(self):
+        self._recent: Deque[Tuple[float, float]] = deque()  # (time, speed)
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        config = context.config
+        while self._recent and self._recent[0][0] < context.now - config.clutter_window_sec:
+            self._recent.popleft()
+        repeats = sum(
+            abs(speed - reading.speed) <= config.clutter_tolerance_mph for _, speed in self._recent
+        )
+        self._recent.append((context.now, reading.speed))
+        if repeats >= config.clutter_min_repeats:
+            return f"Speed {reading.speed:.1f} repeated {repeats} times (clutter)"
+        return None
+
+
+class RefractoryFilter(Filter):
+    """Drops readings right after a shot."""
+
+    name = "refractory"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        refractory_sec = context.config.refractory_sec
+        last_shot = context.last_shot_time
+        if last_shot is not None and context.now - last_shot < refractory_sec:
+            return f"Within {refractory_sec * 1000:.0f}ms of the last shot"
+        return None
+
+
+class RangeFilter(Filter):
+    """Drops targets outside the range gate."""
+
+    name = "range"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        if reading.range_m is None:
+            return None
+        config = context.config
+        if reading.range_m < config.min_range_m:
+            return f"Range {reading.range_m:.2f}m nearer than {config.min_range_m}m"
+        if config.max_range_m and reading.range_m > config.max_range_m:
+            return f"Range {reading.range_m:.2f}m beyond {config.max_range_m}m"
+        return None
+
+
+class AngleFilter(Filter):
+    """Drops targets outside the launch corridor."""
+
+    name = "angle"
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        config = context.config
+        for label, angle, low, high in (
+            ("Azimuth", reading.azimuth_deg, config.min_azimuth_deg, config.max_azimuth_deg),
+            (
+                "Elevation",
+                reading.elevation_deg,
+                config.min_elevation_deg,
+                config.max_elevation_deg,
+            ),
+        ):
+            if angle is not None and not low <= angle <= high:
+                return f"{label} {angle:.1f}° outside {low:g}° to {high:g}°"
+        return None
+
+
+_REGISTRY: Dict[str, Callable[[], Filter]] = {
+    cls.name: cls
+    for cls in (
+        SpeedBandFilter,
+        DirectionFilter,
+        MagnitudeFilter,
+        ClutterFilter,
+        RefractoryFilter,
+        RangeFilter,
+        AngleFilter,
+    )
+}
+
+
+def register_filter(name: str, factory: Callable[[], Filter]):
+    """
+    Make a filter available to pipelines by name.
+
+    Args:
+        name: Name used in the "filters" config setting
+        factory: Called with no arguments to create the filter (usually its class)
+
+    Raises:
+        ValueError: If the name is taken
+    """
+    if name in _REGISTRY:
+        raise ValueError(f"A filter named {name!r} is already registered")
+    _REGISTRY[name] = factory
+
+
+def filter_names() -> List[str]:
+    """Names that can be used in a pipeline."""
+    return sorted(_REGISTRY)
+
+
+class FilterPipeline:
+    """
+    Ordered filters applied to each reading.
+
+    Example:
+        pipeline = FilterPipeline.from_names(["speed", "direction"])
+        reason = pipeline.check(reading, context)
+        if reason:
+            print(f"[FILTER] {reason}")
+    """
+
+    def __init__(self, filters: Sequence[Filter]):
+        """
+        Initialize pipeline.
+
+        Args:
+            filters: Filters in the order they run
+        """
+        self.filters = list(filters)
+
+    @classmethod
+    def from_names(cls, names: Sequence[str]) -> "FilterPipeline":
+        """
+        Build a pipeline of registered filters.
+
+        Raises:
+            ValueError: If a name isn't registered
+        """
+        unknown = [name for name in names if name not in _REGISTRY]
+        if unknown:
+            raise ValueError(
+                f"Unknown filter(s): {', '.join(unknown)} (known: {', '.join(filter_names())})"
+            )
+        return cls([_REGISTRY[name]() for name in names])
+
+    def check(self, reading: SpeedReading, context: FilterContext) -> Optional[str]:
+        """
+        Run the filters in order until one rejects the reading.
+
+        Returns:
+            None if every filter kept the reading, otherwise the first reason
+        """
+        for stage in self.filters:
+            reason = stage.check(reading, context)
+            if reason:
+                return reason
+        return None
@@ -0,0 +1,332 @@
+"""
+FIT export of shots, for Garmin Connect and other fitness apps.
+
+FIT is the binary activity format Garmin devices write and most fitness
+platforms import (Garmin Connect, Strava, TrainingPeaks, and anything
+built on the FIT SDK). Garmin Golf keeps its own range sessions in a
+format third parties can't write, and Arccos-style apps don't import
+range data; a FIT activity is what these ecosystems do take in. For
+apps that import shot tables, use the CSV templates (see export).
+
+The export is one activity with sport "golf": file_id, then a record
+message per shot at the time it was hit, then one lap, session and
+activity spanning the shots. FIT has no shot fields, so each record
+carries the shot as developer fields (declared by field_description
+messages, with names and units apps show as-is):
+
+    shot_number, club, ball_speed (mph), club_speed (mph), smash_factor,
+    carry (yd), launch_angle (deg), spin (rpm)
+
+Values a shot doesn't have are written as FIT's invalid value, which
+readers treat as missing.
+
+The encoding follows the FIT protocol 2.0: a 14 byte header, definition
+and data messages (little endian), and a CRC-16 over the file.
+
+Usage:
+    openflight-export ~/openflight_sessions/session_20250601*.jsonl --format fit -o range.fit
+"""
+
+import struct
+import uuid
+from datetime import datetime, timezone
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+
+from .export import _timestamp
+
+PROTOCOL_VERSION = 0x20  # 2.0
+PROFILE_VERSION = 2132  # 21.32
+FIT_EPOCH = datetime(1989, 12, 31, tzinfo=timezone.utc).timestamp()
+
+# Base types: (id, struct format, invalid value)
+ENUM = (0x00, "B", 0xFF)
+UINT8 = (0x02, "B", 0xFF)
+UINT16 = (0x84, "H", 0xFFFF)
+UINT32 = (0x86, "I", 0xFFFFFFFF)
+UINT32Z = (0x8C, "I", 0)
+FLOAT32 = (0x88, "f", None)
+STRING = (0x07, "s", b"")
+BYTE = (0x0D, "B", 0xFF)
+
+# Global message numbers
+FILE_ID = 0
+SESSION = 18
+LAP = 19
+RECORD = 20
+ACTIVITY = 34
+FIELD_DESCRIPTION = 206
+DEVELOPER_DATA_ID = 207
+
+TIMESTAMP = 253  # Field number of timestamp in every message
+MESSAGE_INDEX = 254
+
+FILE_TYPE_ACTIVITY = 4
+MANUFACTURER_DEVELOPMENT = 255
+SPORT_GOLF = 25
+EVENT_SESSION = 8
+EVENT_LAP = 9
+EVENT_ACTIVITY = 26
+EVENT_TYPE_STOP = 1
+ACTIVITY_MANUAL = 0
+
+# Identifies OpenFlight as the developer of the shot fields
+APPLICATION_ID = uuid.UUID("6f70656e-666c-6967-6874-000000000001").bytes
+CLUB_SIZE = 16  # Bytes for the club name (strings have a fixed size per definition)
+
+# Developer fields: (name, base type, units, shot field)
+SHOT_FIELDS: Tuple[Tuple[str, tuple, str, str], ...] = (
+    ("shot_number", UINT16, "", "shot_number"),
+    ("club", STRING, "", "club"),
+    ("ball_speed", FLOAT32, "mph", "ball_speed_mph"),
+    ("club_speed", FLOAT32, "mph", "club_speed_mph"),
+    ("smash_factor", FLOAT32, "", "smash_factor"),
+    ("carry", FLOAT32, "yd", "estimated_carry_yards"),
+    ("launch_angle", FLOAT32, "deg", "launch_angle_vertical"),
+    ("spin", FLOAT32, "rpm", "spin_rpm"),
+)
+
+_CRC_TABLE = (
+    0x0000, 0xCC01, 0xD801, 0x1400, 0xF001, 0x3C00, 0x2800, 0xE401,
+    0xA001, 0x6C00, 0x7800, 0xB401, 0x5000, 0x9C01, 0x8801, 0x4400,
+)
+
+
+def fit_crc(data: bytes, crc: int = 0) -> int:
+    """FIT's CRC-16 of data."""
+    for byte in data:
+        for nibble in (byte & 0x0F, byte >> 4):
+            tmp = _CRC_TABLE[crc & 0x0F]
+            crc = (crc >> 4) & 0x0FFF
+            crc ^= tmp ^ _CRC_TABLE[nibble]
+    return crc
+
+
+def fit_time(when: datetime) -> int:
+    """FIT timestamp: seconds since 1989-12-31 UTC (naive times are local)."""
+    return int(when.timestamp() - FIT_EPOCH)
+
+
+def _size(base_type: tuple, size: Optional[int]) -> int:
+    return size if size is not None else struct.calcsize("<" + base_type[1])
+
+
+def _pack(base_type: tuple, value: Any, size: Optional[int] = None) -> bytes:
+    """A field value in its base type; None is the type's invalid value."""
+    _, fmt, invalid = base_type
+    if isinstance(value, bytes):
+        return value[:size].ljust(_size(base_type, size), b"\xff")
+    if fmt == "s":
+        text = str(value).encode("utf-8") if value is not None else b""
+        text = text[: (size or len(text) + 1) - 1]
+        return text + b"\x00" * (_size(base_type, size) - len(text))
+    if value is None:
+        if invalid is None:
+            return b"\xff" * _size(base_type, size)
+        value = invalid
+    elif fmt != "f":
+        value = int(round(value))
+    return struct.pack("<" + fmt, value)
+
+
+# A message field: (field number, base type, value, size for strings)
+Field = Tuple[int, tuple, Any, Optional[int]]
+
+
+class FitWriter:
+    """Builds a FIT file from messages; each message layout gets a local type."""
+
+    def __init__(self):
+        self._data = bytearray()
+        self._local_types: Dict[tuple, int] = {}
+
+    def message(
+        self,
+        global_number: int,
+        fields: Sequence[Field],
+        developer_fields: Sequence[Field] = (),
+    ):
+        """
+        Write a data message, preceded by its definition if the layout is new.
+
+        Args:
+            global_number: FIT global message number
+            fields: (field number, base type, value, size) of profile fields
+            developer_fields: The same for developer fields (developer data index 0)
+        """
+        layout = (
+            global_number,
+            tuple((num, base[0], _size(base, size)) for num, base, _, size in fields),
+            tuple((num, _size(base, size)) for num, base, _, size in developer_fields),
+        )
+        local_type = self._local_types.get(layout)
+        if local_type is None:
+            local_type = len(self._local_types) % 16
+            self._local_types = {k: v for k, v in self._local_types.items() if v != local_type}
+            self._local_types[layout] = local_type
+            self._define(local_type, layout)
+        self._data.append(local_type)
+        for _, base, value, size in (*fields, *developer_fields):
+            self._data += _pack(base, value, size)
+
+    def _define(self, local_type: int, layout: tuple):
+        global_number, fields, developer_fields = layout
+        header = 0x40 | local_type | (0x20 if developer_fields else 0)
+        self._data += struct.pack("<BBBHB", header, 0, 0, global_number, len(fields))
+        for num, base_id, size in fields:
+            self._data += struct.pack("<BBB", num, size, base_id)
+        if developer_fields:
+            self._data.append(len(developer_fields))
+            for num, size in developer_fields:
+                self._data += struct.pack("<BBB", num, size, 0)
+
+    def to_bytes(self) -> bytes:
+        """The complete file: header, messages and CRC."""
+        header = struct.pack(
+            "<BBHI4s", 14, PROTOCOL_VERSION, PROFILE_VERSION, len(self._data), b".FIT"
+        )
+        header += struct.pack("<H", fit_crc(header))
+        body = header + bytes(self._data)
+        return body + struct.pack("<H", fit_crc(body))
+
+
+def _shot_values(shot: dict) -> List[Field]:
+    fields = []
+    for number, (_, base, _, source) in enumerate(SHOT_FIELDS):
+        value = shot.get(source)
+        size = CLUB_SIZE if base is STRING else None
+        fields.append((number, base, value, size))
+    return fields
+
+
+def to_fit(shots: List[dict], created: Optional[datetime] = None) -> bytes:
+    """
+    Shots as a FIT golf activity.
+
+    Args:
+        shots: shot_detected log entries (see export.load_session_shots) or
+            shot_to_dict output, in time order
+        created: When the file was made (default: now)
+
+    Returns:
+        The FIT file
+    """
+    created = created or datetime.now()
+    times = [_timestamp(shot) or created for shot in shots] or [created]
+    start, end = fit_time(min(times)), fit_time(max(times))
+    elapsed_ms = (end - start) * 1000
+
+    fit = FitWriter()
+    fit.message(
+        FILE_ID,
+        [
+            (0, ENUM, FILE_TYPE_ACTIVITY, None),
+            (1, UINT16, MANUFACTURER_DEVELOPMENT, None),
+            (2, UINT16, 0, None),
+            (3, UINT32Z, start, None),  # serial_number: any non-zero number
+            (4, UINT32, fit_time(created), None),
+        ],
+    )
+    fit.message(DEVELOPER_DATA_ID, [(1, BYTE, APPLICATION_ID, 16), (3, UINT8, 0, None)])
+    for number, (name, base, units, _) in enumerate(SHOT_FIELDS):
+        fit.message(
+            FIELD_DESCRIPTION,
+            [
+                (0, UINT8, 0, None),
+                (1, UINT8, number, None),
+                (2, UINT8, base[0], None),
+                (3, STRING, name, 32),
+                (8, STRING, units, 16),
+            ],
+        )
+
+    for shot, when in zip(shots, times):
+        fit.message(RECORD, [(TIMESTAMP, UINT32, fit_time(when), None)], _shot_values(shot))
+
+    common = [
+        (TIMESTAMP, UINT32, end, None),
+        (2, UINT32, start, None),  # start_time
+        (7, UINT32, elapsed_ms, None),  # total_elapsed_time (ms)
+        (8, UINT32, elapsed_ms, None),  # total_timer_time (ms)
+    ]
+    fit.message(
+        LAP,
+        common + [(0, ENUM, EVENT_LAP, None), (1, ENUM, EVENT_TYPE_STOP, None)],
+    )
+    fit.message(
+        SESSION,
+        common
+        + [
+            (MESSAGE_INDEX, UINT16, 0, None),
+            (0, ENUM, EVENT_SESSION, None),
+            (1, ENUM, EVENT_TYPE_STOP, None),
+            (5, ENUM, SPORT_GOLF, None),
+            (6, ENUM, 0, None),  # sub_sport: generic
+            (25, UINT16, 0, None),  # first_lap_index
+            (26, UINT16, 1, None),  # num_laps
+        ],
+    )
+    local_end = datetime.fromtimestamp(end + FIT_EPOCH).astimezone()
+    local_offset = int(local_end.utcoffset().total_seconds())
+    fit.message(
+        ACTIVITY,
+        [
+            (TIMESTAMP, UINT32, end, None),
+            (0, UINT32, elapsed_ms, None),  # total_timer_time (ms)
+            (1, UINT16, 1, None),  # num_sessions
+            (2, ENUM, ACTIVITY_MANUAL, None),
+            (3, ENUM, EVENT_ACTIVITY, None),
+            (4, ENUM, EVENT_TYPE_STOP, None),
+            (5, UINT32, end + local_offset, None),  # local_timestamp
+        ],
+    )
+    return fit.to_bytes()
+
+
+def read_fit_records(data: bytes) -> List[Dict[str, Any]]:
+    """
+    Decode a FIT file's messages (used to check exports).
+
+    Returns:
+        One dict per data message: {"global": number, "fields": {num: raw bytes},
+        "developer": {num: raw bytes}}
+
+    Raises:
+        ValueError: If the header, sizes or CRC are wrong
+    """
+    if len(data) < 16 or data[8:12] != b".FIT":
+        raise ValueError("not a FIT file")
+    header_size = data[0]
+    data_size = struct.unpack_from("<I", data, 4)[0]
+    end = header_size + data_size
+    if len(data) != end + 2 or fit_crc(data[:end]) != struct.unpack_from("<H", data, end)[0]:
+        raise ValueError("FIT file size or CRC mismatch")
+
+    definitions: Dict[int, tuple] = {}
+    messages = []
+    pos = header_size
+    while pos < end:
+        header = data[pos]
+        pos += 1
+        local_type = header & 0x0F
+        if header & 0x40:
+            global_number, count = struct.unpack_from("<HB", data, pos + 2)
+            pos += 5
+            fields = [tuple(data[pos + 3 * i: pos + 3 * i + 2]) for i in range(count)]
+            pos += 3 * count
+            developer = []
+            if header & 0x20:
+                dev_count = data[pos]
+                developer = [
+                    tuple(data[pos + 1 + 3 * i: pos + 3 * i + 3]) for i in range(dev_count)
+                ]
+                pos += 1 + 3 * dev_count
+            definitions[local_type] = (global_number, fields, developer)
+            continue
+        global_number, fields, developer = definitions[local_type]
+        message: Dict[str, Any] = {"global": global_number, "fields": {}, "developer": {}}
+        for key, layout in (("fields", fields), ("developer", developer)):
+            for num, size in layout:
+                message[key][num] = data[pos: pos + size]
+                pos += size
+        messages.append(message)
+    return messages
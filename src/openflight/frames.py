@@ -0,0 +1,535 @@
+"""
+Typed radar frames and per-backend decoders.
+
+A radar's serial output is decoded into frames before anything else looks
+at it, so processing that needs more than a speed (FFT magnitudes, raw
+ADC samples, module status) gets it without backend-specific parsing:
+
+- SpeedTarget: the speed targets of one report, strongest first
+- FftFrame: FFT magnitudes of one report
+- RawAdc: a block of raw I/Q samples
+- Status: module info and settings answers (product, units, filters...)
+- PointCloud: detected points with position and radial velocity, from
+  mmWave sensors that measure range and angle
+
+Each backend has a decoder that turns its output into frames
+(OPS243Decoder for the OPS243's plain-number and JSON lines,
+IWR6843Decoder for the TI mmWave demo's binary TLV packets). Code that
+only wants speeds can still call SpeedTarget.to_reading(), or
+PointCloud.to_speed_target() first.
+
+SpeedReading, Direction and IQBlock live here too, so the frames don't
+depend on any one backend; ops243 re-exports them.
+
+Example:
+    decoder = OPS243Decoder()
+    frame = decoder.decode('{"speed":[-152.3,-104.1],"magnitude":[1200,800]}')
+    if isinstance(frame, SpeedTarget):
+        reading = frame.to_reading()
+"""
+
+import json
+import logging
+import math
+import struct
+import time
+from dataclasses import dataclass, field, replace
+from enum import Enum
+from typing import List, Optional
+
+from .units import MetersPerSecond, radar_unit, speed_in, to_mph
+
+logger = logging.getLogger(__name__)
+
+
+class Direction(Enum):
+    """Direction of detected object."""
+    INBOUND = "inbound"
+    OUTBOUND = "outbound"
+    UNKNOWN = "unknown"
+
+
+@dataclass
+class SpeedReading:
+    """
+    A single speed reading from the radar.
+
+    In O4 multi-object mode the radar reports several targets per frame.
+    The strongest becomes the reading itself; the rest are kept in
+    frame_objects so club and ball seen in the same frame can be paired.
+
+    speed is typed by unit (Mph for "mph", MetersPerSecond for "m/s"), so a
+    radar set to m/s can't pass its speeds off as mph; use units.to_mph().
+    """
+    speed: float
+    direction: Direction
+    magnitude: Optional[float] = None
+    timestamp: Optional[float] = None  # time.monotonic(); see clock for wall time
+    unit: str = "mph"
+    frame_objects: Optional[List["SpeedReading"]] = None
+    range_m: Optional[float] = None  # Distance from the sensor, if the radar measures it
+    azimuth_deg: Optional[float] = None  # Angles from boresight, if the radar measures them
+    elevation_deg: Optional[float] = None
+
+    def __post_init__(self):
+        self.speed = speed_in(self.unit, self.speed)
+
+
+@dataclass
+class IQBlock:
+    """
+    A block of raw I/Q samples from the radar.
+
+    Each block contains 128 samples at 30ksps (~4.3ms of data).
+    Blocks arrive every ~31ms due to serial bandwidth limits.
+    Used for continuous I/Q streaming mode where we process
+    the FFT locally instead of using the radar's internal processing.
+    """
+    i_samples: List[int]  # Raw I channel ADC values (0-4095)
+    q_samples: List[int]  # Raw Q channel ADC values (0-4095)
+    timestamp: float      # When this block was received (time.monotonic())
+
+
+@dataclass
+class Frame:
+    """Something a radar reported, with when it arrived (time.monotonic())."""
+
+    timestamp: float
+
+
+@dataclass
+class Target:
+    """One object in a speed report."""
+
+    speed: float  # Always positive; see direction
+    direction: Direction
+    magnitude: Optional[float] = None
+    range_m: Optional[float] = None  # Distance from the sensor, if the radar measures it
+    azimuth_deg: Optional[float] = None  # Angles from boresight, if the radar measures them
+    elevation_deg: Optional[float] = None
+
+
+@dataclass
+class SpeedTarget(Frame):
+    """The speed targets of one report, strongest first."""
+
+    targets: List[Target] = field(default_factory=list)
+    unit: str = "mph"
+
+    def to_reading(self) -> Optional[SpeedReading]:
+        """
+        The strongest target as a SpeedReading, with the rest as its frame_objects.
+
+        Returns:
+            None if the report had no targets
+        """
+        if not self.targets:
+            return None
+        readings = [
+            SpeedReading(
+                speed=t.speed,
+                direction=t.direction,
+                magnitude=t.magnitude,
+                timestamp=self.timestamp,
+                unit=self.unit,
+                range_m=t.range_m,
+                azimuth_deg=t.azimuth_deg,
+                elevation_deg=t.elevation_deg,
+            )
+            for t in self.targets
+        ]
+        readings[0].frame_objects = readings[1:] or None
+        return readings[0]
+
+    def in_mph(self) -> "SpeedTarget":
+        """
+        The same report with speeds converted to mph.
+
+        Raises:
+            ValueError: For a unit that can't be converted
+        """
+        if self.unit == "mph":
+            return self
+        targets = [replace(t, speed=float(to_mph(t.speed, self.unit))) for t in self.targets]
+        return SpeedTarget(timestamp=self.timestamp, targets=targets, unit="mph")
+
+
+@dataclass
+class FftFrame(Frame):
+    """FFT magnitudes of one report, one per bin."""
+
+    magnitudes: List[float] = field(default_factory=list)
+
+
+@dataclass
+class RawAdc(Frame):
+    """A block of raw I/Q ADC samples."""
+
+    i_samples: List[int] = field(default_factory=list)
+    q_samples: List[int] = field(default_factory=list)
+
+    def to_block(self) -> IQBlock:
+        """As the IQBlock the I/Q processors take."""
+        return IQBlock(i_samples=self.i_samples, q_samples=self.q_samples, timestamp=self.timestamp)
+
+
+@dataclass
+class Status(Frame):
+    """Module info or a settings answer, e.g. {"Product": "OPS243-C"}."""
+
+    values: dict = field(default_factory=dict)
+
+
+@dataclass
+class Point:
+    """One detected point: position in meters, radial velocity in m/s."""
+
+    x: float  # Right of boresight
+    y: float  # Along boresight
+    z: float  # Up
+    velocity: float  # Positive = moving away from the sensor
+    snr_db: Optional[float] = None
+
+    @property
+    def range(self) -> float:
+        """Distance from the sensor in meters."""
+        return math.sqrt(self.x**2 + self.y**2 + self.z**2)
+
+    @property
+    def azimuth_deg(self) -> float:
+        """Horizontal angle from boresight, positive to the right."""
+        return math.degrees(math.atan2(self.x, self.y))
+
+    @property
+    def elevation_deg(self) -> float:
+        """Vertical angle from boresight, positive up."""
+        return math.degrees(math.atan2(self.z, math.hypot(self.x, self.y)))
+
+
+@dataclass
+class PointCloud(Frame):
+    """The points detected in one sensor frame."""
+
+    frame_number: int = 0
+    points: List[Point] = field(default_factory=list)
+
+    def to_speed_target(self) -> SpeedTarget:
+        """
+        The moving points as speed targets, strongest first.
+
+        Magnitude is the point's SNR as a power ratio (20 dB -> 100), so
+        magnitude thresholds tuned for the OPS243 carry over roughly.
+        Static points (zero velocity) are left out.
+        """
+        points = sorted(
+            (p for p in self.points if p.velocity != 0),
+            key=lambda p: p.snr_db if p.snr_db is not None else -math.inf,
+            reverse=True,
+        )
+        targets = [
+            Target(
+                speed=float(MetersPerSecond(abs(p.velocity)).to_mph()),
+                direction=Direction.OUTBOUND if p.velocity > 0 else Direction.INBOUND,
+                magnitude=10 ** (p.snr_db / 10) if p.snr_db is not None else None,
+                range_m=p.range,
+                azimuth_deg=p.azimuth_deg,
+                elevation_deg=p.elevation_deg,
+            )
+            for p in points
+        ]
+        return SpeedTarget(timestamp=self.timestamp, targets=targets, unit="mph")
+
+
+def direction_from_signed(speed: float) -> Direction:
+    """
+    Direction of an OPS243 speed by its sign.
+
+    With R| (both directions), positive speeds are INBOUND (toward the
+    radar, e.g. backswing) and negative ones OUTBOUND (ball flight).
+    """
+    return Direction.INBOUND if speed > 0 else Direction.OUTBOUND
+
+
+def is_finite(value) -> bool:
+    """True for a number that is neither NaN nor infinite."""
+    return isinstance(value, (int, float)) and math.isfinite(value)
+
+
+class FrameDecoder:
+    """
+    Turns a backend's output lines into frames.
+
+    Speeds and magnitudes that aren't finite numbers ("nan" and "inf"
+    parse as floats, and JSON allows NaN) are dropped here and counted in
+    discarded, so nothing downstream has to order or compare them.
+    """
+
+    discarded = 0  # Degenerate samples dropped
+    corrupt = 0  # Damaged lines (partial, merged or with noise), recovered or not
+    recovered = 0  # Damaged lines a frame was still decoded from
+    _buffer = b""  # Unfinished output from the last feed()
+
+    def decode(self, line: str, timestamp: Optional[float] = None) -> Optional[Frame]:
+        """
+        Decode one line of radar output.
+
+        Args:
+            line: Line without its line ending
+            timestamp: Arrival time (default: time.monotonic())
+
+        Returns:
+            The frame, or None for lines that aren't (or don't complete) one
+        """
+        raise NotImplementedError
+
+    def reset(self):
+        """Drop any partly decoded frame, e.g. after losing sync."""
+
+    def feed(self, data: bytes, timestamp: Optional[float] = None) -> List[Frame]:
+        """
+        Decode a chunk of raw serial output, which may end mid-line.
+
+        Line-based backends only need decode(); binary ones override this.
+
+        Returns:
+            Frames completed by this chunk, in order
+        """
+        self._buffer += data
+        *lines, self._buffer = self._buffer.split(b"\n")
+        frames = []
+        for line in lines:
+            frame = self.decode(line.decode("ascii", errors="ignore"), timestamp)
+            if frame is not None:
+                frames.append(frame)
+        return frames
+
+    def _discard(self, sample):
+        self.discarded += 1
+        logger.debug("Discarded degenerate radar sample: %r", sample)
+
+
+class OPS243Decoder(FrameDecoder):
+    """
+    Decoder for OPS243 output.
+
+    Speeds come as plain signed numbers, or with OJ as JSON; in O4
+    multi-object mode speed and magnitude are arrays ordered by
+    magnitude. Compact output (Oj) with magnitudes on is one line of
+    comma-separated "magnitude,speed" pairs per report. With OU, reports
+    also name their unit ("unit" in JSON, a leading quoted field in
+    compact lines), which overrides the configured unit. Raw I/Q output alternates {"I": [...]}
+    and {"Q": [...]} lines, which are paired into one RawAdc frame.
+
+    A damaged JSON line (noise before the frame, or a lost newline that
+    merged a partial frame into the next) is resynced at its last "{",
+    so the intact frame at its end still decodes.
+    """
+
+    def __init__(self, unit: str = "mph"):
+        """
+        Initialize decoder.
+
+        Args:
+            unit: Speed unit the radar is set to
+        """
+        self.unit = unit
+        self._pending_i: Optional[List[int]] = None
+
+    def reset(self):
+        self._pending_i = None
+
+    def decode(self, line: str, timestamp: Optional[float] = None) -> Optional[Frame]:
+        timestamp = time.monotonic() if timestamp is None else timestamp
+        line = line.strip()
+        if not line:
+            return None
+        if "{" in line:
+            data = self._load_json(line)
+            if isinstance(data, dict):
+                return self._decode_json(data, timestamp)
+            return None
+        if "," in line:
+            return self._decode_compact(line, timestamp)
+        try:
+            speed = float(line)
+        except ValueError:
+            self.corrupt += 1
+            logger.debug("Failed to parse radar line: %r", line)
+            return None
+        if not math.isfinite(speed):
+            self._discard(line)
+            return None
+        return SpeedTarget(
+            timestamp=timestamp,
+            targets=[Target(abs(speed), direction_from_signed(speed))],
+            unit=self.unit,
+        )
+
+    def _decode_compact(self, line: str, timestamp: float) -> Optional[Frame]:
+        fields = [f.strip() for f in line.split(",")]
+        unit = self.unit
+        if fields[0].startswith('"'):
+            unit = radar_unit(fields.pop(0)) or unit
+        try:
+            values = [float(f) for f in fields]
+        except ValueError:
+            values = []
+        if not values or len(values) % 2:
+            # A cut-off report: the pairs no longer line up
+            self.corrupt += 1
+            logger.debug("Failed to parse compact radar line: %r", line)
+            return None
+        targets = []
+        for magnitude, speed in zip(values[::2], values[1::2]):
+            if not (is_finite(speed) and is_finite(magnitude)):
+                self._discard(line)
+                continue
+            targets.append(Target(abs(speed), direction_from_signed(speed), magnitude))
+        if not targets:
+            return None
+        return SpeedTarget(timestamp=timestamp, targets=targets, unit=unit)
+
+    def _load_json(self, line: str):
+        """Parse a JSON line, falling back to the object that starts at its last "{"."""
+        try:
+            return json.loads(line)
+        except ValueError:
+            pass
+        self.corrupt += 1
+        start = line.rfind("{")
+        try:
+            data = json.loads(line[start:])
+        except ValueError:
+            logger.debug("Failed to parse radar line: %r", line)
+            return None
+        self.recovered += 1
+        logger.debug("Resynced damaged radar line: %r", line)
+        return data
+
+    def _decode_json(self, data: dict, timestamp: float) -> Optional[Frame]:
+        if "I" in data:
+            self._pending_i = data["I"]
+            return None
+        if "Q" in data:
+            i_samples, self._pending_i = self._pending_i, None
+            if i_samples is None or len(i_samples) != len(data["Q"]):
+                return None
+            return RawAdc(timestamp=timestamp, i_samples=i_samples, q_samples=data["Q"])
+        if "FFT" in data:
+            return FftFrame(timestamp=timestamp, magnitudes=[float(m) for m in data["FFT"]])
+        if "speed" in data:
+            return self._decode_speed(data, timestamp)
+        return Status(timestamp=timestamp, values=data)
+
+    def _decode_speed(self, data: dict, timestamp: float) -> Optional[Frame]:
+        speeds = data["speed"]
+        magnitudes = data.get("magnitude")
+        if not isinstance(speeds, list):
+            speeds = [speeds]
+            magnitudes = [magnitudes] if magnitudes is not None else None
+        targets = []
+        try:
+            for i, speed in enumerate(speeds):
+                speed = float(speed)
+                magnitude = None
+                if magnitudes and i < len(magnitudes) and magnitudes[i] is not None:
+                    magnitude = float(magnitudes[i])
+                if not is_finite(speed) or not (magnitude is None or is_finite(magnitude)):
+                    self._discard(data)
+                    continue
+                targets.append(Target(abs(speed), direction_from_signed(speed), magnitude))
+        except (TypeError, ValueError):
+            logger.debug("Failed to parse speed report: %r", data)
+            return None
+        if not targets:
+            return None
+        # With OU each report names its unit, which beats the configured one
+        unit = radar_unit(str(data.get("unit", data.get("units", "")))) or self.unit
+        return SpeedTarget(timestamp=timestamp, targets=targets, unit=unit)
+
+
+class IWR6843Decoder(FrameDecoder):
+    """
+    Decoder for the TI mmWave SDK out-of-box demo's data UART output.
+
+    Each sensor frame is a binary packet: an 8-byte magic word, a header
+    (version, total length, platform, frame number, CPU cycles, number of
+    points, number of TLVs, subframe), then type-length-value blocks. The
+    detected points TLV has x, y, z and radial velocity per point as
+    float32; the side info TLV adds SNR and noise in 0.1 dB steps.
+    """
+
+    MAGIC = b"\x02\x01\x04\x03\x06\x05\x08\x07"
+    HEADER = struct.Struct("<8s8I")
+    TLV_HEADER = struct.Struct("<2I")
+    POINT = struct.Struct("<4f")
+    SIDE_INFO = struct.Struct("<2h")
+    TLV_DETECTED_POINTS = 1
+    TLV_SIDE_INFO = 7
+    MAX_PACKET = 65536  # Larger "lengths" mean a corrupt header
+
+    def __init__(self):
+        self._buffer = b""
+
+    def reset(self):
+        self._buffer = b""
+
+    def decode(self, line: str, timestamp: Optional[float] = None) -> Optional[Frame]:
+        raise TypeError("IWR6843 output is binary; use feed()")
+
+    def feed(self, data: bytes, timestamp: Optional[float] = None) -> List[Frame]:
+        timestamp = time.monotonic() if timestamp is None else timestamp
+        self._buffer += data
+        frames = []
+        while True:
+            start = self._buffer.find(self.MAGIC)
+            if start < 0:
+                # Keep a possible partial magic word
+                self._buffer = self._buffer[-(len(self.MAGIC) - 1):]
+                return frames
+            self._buffer = self._buffer[start:]
+            if len(self._buffer) < self.HEADER.size:
+                return frames
+            header = self.HEADER.unpack_from(self._buffer)
+            length, frame_number, num_tlvs = header[2], header[4], header[7]
+            if not self.HEADER.size <= length <= self.MAX_PACKET:
+                logger.debug("Bad IWR6843 packet length %d, resyncing", length)
+                self._buffer = self._buffer[len(self.MAGIC):]
+                continue
+            if len(self._buffer) < length:
+                return frames
+            packet, self._buffer = self._buffer[:length], self._buffer[length:]
+            frame = self._decode_packet(packet, frame_number, num_tlvs, timestamp)
+            if frame is not None:
+                frames.append(frame)
+
+    def _decode_packet(
+        self, packet: bytes, frame_number: int, num_tlvs: int, timestamp: float
+    ) -> Optional[PointCloud]:
+        points: List[Point] = []
+        side_info: List[tuple] = []
+        offset = self.HEADER.size
+        try:
+            for _ in range(num_tlvs):
+                tlv_type, tlv_length = self.TLV_HEADER.unpack_from(packet, offset)
+                offset += self.TLV_HEADER.size
+                payload = packet[offset:offset + tlv_length]
+                offset += tlv_length
+                if tlv_type == self.TLV_DETECTED_POINTS:
+                    points = [Point(*values) for values in self.POINT.iter_unpack(payload)]
+                elif tlv_type == self.TLV_SIDE_INFO:
+                    side_info = list(self.SIDE_INFO.iter_unpack(payload))
+        except struct.error:
+            logger.debug("Truncated IWR6843 packet (frame %d)", frame_number)
+            return None
+
+        if len(side_info) == len(points):
+            for point, (snr, _noise) in zip(points, side_info):
+                point.snr_db = snr / 10
+        finite = []
+        for point in points:
+            if all(is_finite(v) for v in (point.x, point.y, point.z, point.velocity)):
+                finite.append(point)
+            else:
+                self._discard(point)
+        return PointCloud(timestamp=timestamp, frame_number=frame_number, points=finite)
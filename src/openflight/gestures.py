@@ -0,0 +1,186 @@
+"""
+Club switching by radar gesture.
+
+Walking to a keyboard or phone between every club change gets old. With
+--club-gesture, waving the club head toward the radar three times (three
+short passes within a few seconds) selects the next club, and the
+new club is announced out loud when a speech synthesizer (espeak-ng or
+espeak) is installed.
+
+A pass is a run of readings moving toward the radar at waving speed
+(10-25 mph; the radar reports nothing under 10 mph in streaming mode),
+with a gap of no such readings after it. Anything faster cancels the
+gesture, so swings and ball flights never count; a pass that goes on too
+long (someone walking up to the radar) cancels it as well.
+"""
+
+import logging
+import shutil
+import subprocess
+import time
+from typing import List, Optional, Sequence
+
+from .launch_monitor import ClubType
+from .ops243 import Direction, SpeedReading
+
+logger = logging.getLogger(__name__)
+
+# Clubs the gesture cycles through by default, longest first
+DEFAULT_BAG = [club for club in ClubType if club != ClubType.UNKNOWN]
+
+
+class ClubGestureDetector:
+    """
+    Recognizes repeated short passes toward the radar.
+
+    Example:
+        gesture = ClubGestureDetector()
+        if gesture.update(reading):
+            monitor.set_club(next_club(monitor.get_club()))
+    """
+
+    DEFAULT_PASSES = 3
+    DEFAULT_MIN_SPEED_MPH = 10.0  # Radar's min_speed filter in streaming mode
+    DEFAULT_MAX_SPEED_MPH = 25.0
+    DEFAULT_WINDOW_SEC = 4.0
+    DEFAULT_GAP_SEC = 0.3
+    DEFAULT_MAX_PASS_SEC = 1.5
+
+    def __init__(
+        self,
+        passes: int = DEFAULT_PASSES,
+        direction: Direction = Direction.INBOUND,
+        min_speed_mph: float = DEFAULT_MIN_SPEED_MPH,
+        max_speed_mph: float = DEFAULT_MAX_SPEED_MPH,
+        window_sec: float = DEFAULT_WINDOW_SEC,
+        gap_sec: float = DEFAULT_GAP_SEC,
+        max_pass_sec: float = DEFAULT_MAX_PASS_SEC,
+    ):
+        """
+        Initialize gesture detector.
+
+        Args:
+            passes: Passes that make up the gesture
+            direction: Direction of a pass (toward the radar)
+            min_speed_mph: Slower readings are ignored as noise
+            max_speed_mph: Any faster reading cancels the gesture
+            window_sec: All passes must start within this time
+            gap_sec: Time without pass readings that ends a pass
+            max_pass_sec: A pass lasting longer cancels the gesture
+        """
+        self.passes = passes
+        self.direction = direction
+        self.min_speed_mph = min_speed_mph
+        self.max_speed_mph = max_speed_mph
+        self.window_sec = window_sec
+        self.gap_sec = gap_sec
+        self.max_pass_sec = max_pass_sec
+        self._pass_starts: List[float] = []
+        self._pass_start: Optional[float] = None
+        self._pass_last: Optional[float] = None
+
+    def reset(self):
+        """Forget any passes in progress."""
+        self._pass_starts = []
+        self._pass_start = None
+        self._pass_last = None
+
+    def update(self, reading: SpeedReading, now: Optional[float] = None) -> bool:
+        """
+        Feed a live radar reading.
+
+        Args:
+            reading: Reading in any direction
+            now: Reading time (default: time.monotonic())
+
+        Returns:
+            True when the reading completes the gesture
+        """
+        now = time.monotonic() if now is None else now
+
+        if reading.speed > self.max_speed_mph:
+            self.reset()
+            return False
+        if reading.direction != self.direction or reading.speed < self.min_speed_mph:
+            return False
+
+        if self._pass_last is not None and now - self._pass_last <= self.gap_sec:
+            self._pass_last = now
+            if now - self._pass_start > self.max_pass_sec:
+                # Still one (too long) pass until the readings stop
+                self._pass_starts = []
+            return False
+
+        # A new pass
+        self._pass_start = now
+        self._pass_last = now
+        self._pass_starts = [t for t in self._pass_starts if now - t <= self.window_sec]
+        self._pass_starts.append(now)
+        if len(self._pass_starts) < self.passes:
+            return False
+        self._pass_starts = []
+        return True
+
+
+def next_club(club: ClubType, bag: Optional[Sequence[ClubType]] = None) -> ClubType:
+    """
+    Club after this one in the bag, wrapping around.
+
+    A club that isn't in the bag moves to the first club.
+    """
+    bag = list(bag or DEFAULT_BAG)
+    if club not in bag:
+        return bag[0]
+    return bag[(bag.index(club) + 1) % len(bag)]
+
+
+def parse_bag(text: str) -> List[ClubType]:
+    """
+    Parse a comma-separated club list such as "driver,7-iron,pw".
+
+    Raises:
+        ValueError: If a club name is unknown or the list is empty
+    """
+    bag = []
+    for name in text.split(","):
+        name = name.strip().lower()
+        if not name:
+            continue
+        try:
+            bag.append(ClubType(name))
+        except ValueError:
+            raise ValueError(f"unknown club {name!r}") from None
+    if not bag:
+        raise ValueError("no clubs given")
+    return bag
+
+
+def speak(text: str) -> bool:
+    """
+    Say text with espeak-ng or espeak, without waiting for it to finish.
+
+    Returns:
+        False if no speech synthesizer is installed
+    """
+    command = shutil.which("espeak-ng") or shutil.which("espeak")
+    if not command:
+        return False
+    try:
+        subprocess.Popen(  # pylint: disable=consider-using-with
+            [command, text], stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL
+        )
+    except OSError as e:
+        logger.warning("Could not run %s: %s", command, e)
+        return False
+    return True
+
+
+def club_announcement(club: ClubType) -> str:
+    """How a club is said out loud, e.g. "7 iron" or "pitching wedge"."""
+    wedges = {
+        ClubType.PW: "pitching wedge",
+        ClubType.GW: "gap wedge",
+        ClubType.SW: "sand wedge",
+        ClubType.LW: "lob wedge",
+    }
+    return wedges.get(club, club.value.replace("-", " "))
@@ -0,0 +1,308 @@
+"""
+gRPC service for companion apps.
+
+With --grpc-port PORT, the server also serves the LaunchMonitor service
+from proto/openflight.proto: a typed alternative to the REST endpoints and
+/api/stream, for apps that would rather generate a client than parse JSON.
+
+    GetSession     -> stats and every shot so far
+    GetStats       -> session statistics
+    SetClub        -> select the club for the next shots
+    SetPlayer      -> switch player (the session's "player" tag)
+    SimulateShot   -> record a simulated shot (mock mode only)
+    StreamEvents   -> shots and other events as they happen, optionally
+                      live readings at a per-client rate
+
+Each stream event carries its full data as JSON (data_json), and shots
+and readings also as typed messages, so new event kinds reach old clients.
+
+The proto is compiled when the server starts, so only the grpc extra is
+needed (pip install openflight[grpc]); nothing generated is kept in the
+tree. --api-token and --allow apply as they do to /api: send the token as
+"authorization: Bearer <token>" or "x-openflight-token: <token>" metadata.
+
+For example, with grpcurl:
+
+    grpcurl -plaintext -import-path src/openflight/proto -proto openflight.proto \\
+        -d '{"club": "7-iron"}' localhost:50051 openflight.v1.LaunchMonitor/SetClub
+"""
+
+import json
+import logging
+from concurrent.futures import ThreadPoolExecutor
+from typing import Any, Callable, Dict, Iterator, Optional, Tuple
+from urllib.parse import unquote
+
+logger = logging.getLogger(__name__)
+
+# Relative to the sys.path entry openflight is imported from
+PROTO_FILE = "openflight/proto/openflight.proto"
+
+DEFAULT_GRPC_PORT = 50051
+
+# Unary method -> response message
+UNARY_METHODS = {
+    "GetSession": "Session",
+    "GetStats": "SessionStats",
+    "SetClub": "SetClubResponse",
+    "SetPlayer": "SetPlayerResponse",
+    "SimulateShot": "SimulateShotResponse",
+}
+
+# Stream events that also have a typed message in Event's payload
+TYPED_EVENTS = ("shot", "reading")
+
+Handler = Callable[[dict], dict]
+StreamHandler = Callable[[dict, Callable[[], bool]], Iterator[Tuple[str, dict]]]
+Authorizer = Callable[[Dict[str, str], Optional[str]], None]
+
+
+class ServiceError(Exception):
+    """Error a handler raises to end a call with a gRPC status (a grpc.StatusCode name)."""
+
+    def __init__(self, message: str, code: str = "FAILED_PRECONDITION"):
+        super().__init__(message)
+        self.code = code
+
+
+def load_proto() -> Tuple[Any, Any]:
+    """
+    Compile proto/openflight.proto.
+
+    Returns:
+        The messages and services modules (as protoc's _pb2 and _pb2_grpc)
+
+    Raises:
+        ImportError: If grpcio or grpcio-tools is not installed
+    """
+    import grpc
+
+    return grpc.protos_and_services(PROTO_FILE)
+
+
+def peer_address(peer: Optional[str]) -> Optional[str]:
+    """
+    The client IP in a gRPC peer string ("ipv4:1.2.3.4:5678", "ipv6:%5B::1%5D:5678").
+
+    Returns:
+        The address, or None for other transports (e.g. a Unix socket)
+    """
+    if not peer:
+        return None
+    transport, _, rest = unquote(peer).partition(":")
+    if transport == "ipv4":
+        return rest.rpartition(":")[0]
+    if transport == "ipv6":
+        return rest.rpartition(":")[0].strip("[]")
+    return None
+
+
+def event_fields(event: str, data: dict) -> dict:
+    """An Event message's fields for a stream event."""
+    fields = {"event": event, "data_json": json.dumps(data)}
+    if event in TYPED_EVENTS:
+        fields[event] = data
+    return fields
+
+
+class GrpcServer:
+    """
+    The LaunchMonitor gRPC service, backed by plain handlers.
+
+    Handlers take the request's fields as a dict (unset fields left out)
+    and return the response's fields as a dict; keys the message doesn't
+    have are ignored. The stream handler also gets a callable that turns
+    False once the client has gone, and yields (event, data) pairs. A
+    ServiceError ends the call with its status, a ValueError with
+    INVALID_ARGUMENT and anything else with INTERNAL, without stopping the
+    server.
+
+    Example:
+        grpc_server = GrpcServer(50051, {"GetStats": lambda params: {...}, ...}, stream)
+        grpc_server.start()
+        ...
+        grpc_server.stop()
+    """
+
+    def __init__(
+        self,
+        port: int,
+        handlers: Dict[str, Handler],
+        stream: StreamHandler,
+        authorize: Optional[Authorizer] = None,
+        host: str = "0.0.0.0",
+        max_workers: int = 8,
+    ):
+        """
+        Initialize gRPC server.
+
+        Args:
+            port: TCP port to listen on (0 picks a free one)
+            handlers: Unary method name (UNARY_METHODS) -> handler
+            stream: StreamEvents handler
+            authorize: Called with the call's metadata and client address
+                before each call; raises ServiceError to refuse it
+            host: Address to listen on
+            max_workers: Calls served at once (each open stream holds one)
+        """
+        self.port = port
+        self.host = host
+        self.handlers = dict(handlers)
+        self.stream = stream
+        self.authorize = authorize
+        self.max_workers = max_workers
+        self._server = None
+
+    def call(
+        self, method: str, params: dict, metadata: Dict[str, str], peer: Optional[str]
+    ) -> dict:
+        """
+        Run a unary method's handler.
+
+        Raises:
+            ServiceError: If the call is refused or the method is unknown
+            ValueError: If the handler rejects the request
+        """
+        if self.authorize:
+            self.authorize(metadata, peer_address(peer))
+        handler = self.handlers.get(method)
+        if handler is None:
+            raise ServiceError(f"Unknown method: {method}", "UNIMPLEMENTED")
+        return handler(params)
+
+    def events(
+        self,
+        params: dict,
+        metadata: Dict[str, str],
+        peer: Optional[str],
+        is_active: Callable[[], bool],
+    ) -> Iterator[dict]:
+        """
+        Run the stream handler, as Event message fields.
+
+        Raises:
+            ServiceError: If the call is refused (before the first event)
+        """
+        if self.authorize:
+            self.authorize(metadata, peer_address(peer))
+        return (event_fields(event, data) for event, data in self.stream(params, is_active))
+
+    def start(self):
+        """
+        Compile the proto and serve on a background thread pool.
+
+        Raises:
+            ImportError: If grpcio or grpcio-tools is not installed
+            OSError: If the port can't be bound
+        """
+        import grpc
+
+        protos, services = load_proto()
+        server = grpc.server(ThreadPoolExecutor(max_workers=self.max_workers))
+        services.add_LaunchMonitorServicer_to_server(self._servicer(protos, services), server)
+        try:
+            bound = server.add_insecure_port(f"{self.host}:{self.port}")
+        except RuntimeError:  # Newer grpcio raises instead of returning 0
+            bound = 0
+        if not bound:
+            raise OSError(f"Could not listen on {self.host}:{self.port}")
+        server.start()
+        self.port = bound
+        self._server = server
+
+    def stop(self, grace: float = 1.0):
+        """Stop serving; open streams are cancelled after grace seconds."""
+        if self._server:
+            self._server.stop(grace).wait()
+            self._server = None
+
+    def _servicer(self, protos: Any, services: Any) -> Any:
+        """A LaunchMonitorServicer whose methods run this server's handlers."""
+        import grpc
+        from google.protobuf.json_format import MessageToDict, ParseDict
+
+        grpc_server = self
+
+        def abort(context, error: Exception):
+            if isinstance(error, ServiceError):
+                context.abort(getattr(grpc.StatusCode, error.code), str(error))
+            if isinstance(error, ValueError):
+                context.abort(grpc.StatusCode.INVALID_ARGUMENT, str(error))
+            logger.exception("gRPC call failed")
+            context.abort(grpc.StatusCode.INTERNAL, str(error))
+
+        def unary(method: str, response_type: Any):
+            def handle(_servicer, request, context):
+                params = MessageToDict(request, preserving_proto_field_name=True)
+                try:
+                    result = grpc_server.call(
+                        method, params, dict(context.invocation_metadata()), context.peer()
+                    )
+                except Exception as e:  # pylint: disable=broad-except
+                    abort(context, e)
+                return ParseDict(result, response_type(), ignore_unknown_fields=True)
+
+            return handle
+
+        def stream_events(_servicer, request, context):
+            params = MessageToDict(request, preserving_proto_field_name=True)
+            try:
+                for fields in grpc_server.events(
+                    params, dict(context.invocation_metadata()), context.peer(), context.is_active
+                ):
+                    yield ParseDict(fields, protos.Event(), ignore_unknown_fields=True)
+            except Exception as e:  # pylint: disable=broad-except
+                abort(context, e)
+
+        methods = {
+            name: unary(name, getattr(protos, response)) for name, response in UNARY_METHODS.items()
+        }
+        methods["StreamEvents"] = stream_events
+        return type("LaunchMonitorServicer", (services.LaunchMonitorServicer,), methods)()
+
+
+# Global gRPC server instance
+_grpc_server: Optional[GrpcServer] = None
+
+
+def get_grpc_server() -> Optional[GrpcServer]:
+    """Get the global gRPC server (None if no --grpc-port is configured)."""
+    return _grpc_server
+
+
+def init_grpc_server(
+    port: Optional[int],
+    handlers: Dict[str, Handler],
+    stream: StreamHandler,
+    authorize: Optional[Authorizer] = None,
+    host: str = "0.0.0.0",
+) -> Optional[GrpcServer]:
+    """
+    Initialize and start the global gRPC server.
+
+    Args:
+        port: TCP port; None disables the gRPC service
+        handlers: Unary method name -> handler
+        stream: StreamEvents handler
+        authorize: Access check run before each call
+        host: Address to listen on
+
+    Returns:
+        GrpcServer instance, or None if no port was given
+
+    Raises:
+        ImportError: If grpcio or grpcio-tools is not installed
+        OSError: If the port can't be bound
+    """
+    global _grpc_server  # pylint: disable=global-statement
+
+    if _grpc_server:
+        _grpc_server.stop()
+        _grpc_server = None
+
+    if port is None:
+        return None
+
+    _grpc_server = GrpcServer(port, handlers, stream, authorize, host)
+    _grpc_server.start()
+    return _grpc_server
@@ -0,0 +1,319 @@
+"""
+Hardware-in-the-loop runs over a virtual serial port.
+
+The unit tests stop at RadarInterface: they feed the parser lines, never
+bytes through a real serial port. This runs the whole program instead,
+with a --dump-serial capture standing in for the radar:
+
+    openflight-hil capture.ofdump --expect-shots 3
+    openflight-hil capture.ofdump -- openflight --port {port} --no-iq-streaming
+
+A pseudo-terminal pair plays the radar (the same thing socat's pty
+addresses set up, without needing socat): the program opens the pty's
+port like a USB serial port, pyserial, termios settings and all. The
+capture's reads are sent with their original timing, and each of its
+writes is waited for before the reads after it, so the radar's answers
+come after the command that asked for them, as they did live. Once the
+capture ends, the program gets SIGINT (as from Ctrl+C) and its output is
+checked for the shots it printed.
+
+Replay with the command line the capture was recorded with (the default
+is `openflight --port {port}`): a program that sends other commands still
+runs, but its writes are reported as not matching the capture.
+
+Linux and macOS only (pseudo-terminals); OPS243 captures only, since the
+IWR6843's CLI port isn't captured.
+"""
+
+import argparse
+import os
+import pty
+import select
+import signal
+import subprocess
+import sys
+import tempfile
+import threading
+import time
+import tty
+from dataclasses import dataclass, field
+from typing import Iterable, List, Optional, Tuple
+
+from .serial_dump import READ, WRITE, DumpRecord, read_dump
+
+# The openflight CLI, run by this interpreter (whether or not it's on PATH)
+CLI = [
+    sys.executable,
+    "-c",
+    "import sys; from openflight.launch_monitor import main; sys.exit(main())",
+]
+DEFAULT_COMMAND = CLI + ["--port", "{port}"]
+SHOT_MARKER = "Ball Speed:"  # Printed once per shot by the openflight CLI
+
+
+class VirtualSerialPair:
+    """
+    Pseudo-terminal pair standing in for a radar's serial port.
+
+    The program under test opens `port`; bytes passed to send() arrive
+    there as if the radar sent them, and what the program writes is
+    collected for expect_written().
+
+    Example:
+        with VirtualSerialPair() as pair:
+            subprocess.Popen(["openflight", "--port", pair.port])
+            pair.expect_written(2, timeout=5.0)   # b"??"
+            pair.send(b'{"Product":"OPS243-A"}\\n')
+    """
+
+    def __init__(self):
+        self._master, self._slave = pty.openpty()
+        # Raw: no echo of the program's commands, no newline translation
+        tty.setraw(self._slave)
+        self.port = os.ttyname(self._slave)
+        self._written = bytearray()
+        self._consumed = 0
+        self._changed = threading.Condition()
+        self._closed = False
+        self._thread = threading.Thread(target=self._read_loop, daemon=True)
+        self._thread.start()
+
+    def __enter__(self) -> "VirtualSerialPair":
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+        return False
+
+    @property
+    def written(self) -> bytes:
+        """Everything the program has written to the port."""
+        with self._changed:
+            return bytes(self._written)
+
+    def send(self, data: bytes):
+        """Send bytes to the program, as the radar would."""
+        view = memoryview(data)
+        while view:
+            sent = os.write(self._master, view)
+            view = view[sent:]
+
+    def expect_written(self, count: int, timeout: float) -> bytes:
+        """
+        Wait for the program's next bytes.
+
+        Args:
+            count: Number of bytes expected
+            timeout: Seconds to wait for them
+
+        Returns:
+            The next count bytes written (fewer if the program didn't write them in time)
+        """
+        deadline = time.monotonic() + timeout
+        with self._changed:
+            while len(self._written) - self._consumed < count and not self._closed:
+                remaining = deadline - time.monotonic()
+                if remaining <= 0:
+                    break
+                self._changed.wait(remaining)
+            data = bytes(self._written[self._consumed:self._consumed + count])
+            self._consumed += len(data)
+            return data
+
+    def close(self):
+        """Close both ends of the pair."""
+        with self._changed:
+            if self._closed:
+                return
+            self._closed = True
+            self._changed.notify_all()
+        self._thread.join(timeout=1.0)
+        os.close(self._master)
+        os.close(self._slave)
+
+    def _read_loop(self):
+        # The slave end stays open here, so the program closing and reopening
+        # the port (as radar probing does) doesn't end the stream
+        while not self._closed:
+            ready, _, _ = select.select([self._master], [], [], 0.1)
+            if not ready:
+                continue
+            try:
+                data = os.read(self._master, 4096)
+            except OSError:
+                return
+            with self._changed:
+                self._written += data
+                self._changed.notify_all()
+
+
+def replay_to(
+    pair: VirtualSerialPair,
+    records: Iterable[DumpRecord],
+    speed: float = 1.0,
+    write_timeout: float = 5.0,
+) -> List[Tuple[bytes, bytes]]:
+    """
+    Play a capture's side of the conversation to the program.
+
+    Reads are sent at their captured times (divided by speed); a captured
+    write holds back the records after it until the program has written
+    as many bytes.
+
+    Args:
+        pair: Port the program has open
+        records: Capture records (from read_dump)
+        speed: Playback speed (2.0 = twice as fast)
+        write_timeout: Seconds to wait for each captured write
+
+    Returns:
+        (captured, actual) for each write the program didn't repeat exactly
+    """
+    mismatched = []
+    start_wall = None
+    start_capture = None
+    for record in records:
+        if start_wall is None:
+            start_wall, start_capture = time.monotonic(), record.timestamp
+        if record.direction == WRITE:
+            actual = pair.expect_written(len(record.data), write_timeout)
+            if actual != record.data:
+                mismatched.append((record.data, actual))
+            # Time what follows from when the program asked, not from the capture's clock
+            start_wall, start_capture = time.monotonic(), record.timestamp
+            continue
+        if record.direction != READ:
+            continue
+        delay = (record.timestamp - start_capture) / speed - (time.monotonic() - start_wall)
+        if delay > 0:
+            time.sleep(delay)
+        pair.send(record.data)
+    return mismatched
+
+
+@dataclass
+class HilResult:
+    """How the program handled a replayed capture."""
+
+    returncode: Optional[int]  # None if it had to be killed
+    output: str  # stdout and stderr
+    written: bytes  # Everything it wrote to the port
+    mismatched_writes: List[Tuple[bytes, bytes]] = field(default_factory=list)
+
+    @property
+    def shots(self) -> int:
+        """Number of shots the program printed."""
+        return sum(line.strip().startswith(SHOT_MARKER) for line in self.output.splitlines())
+
+
+def run(
+    capture: str,
+    command: Optional[List[str]] = None,
+    speed: float = 1.0,
+    settle_sec: float = 2.0,
+    timeout: float = 10.0,
+) -> HilResult:
+    """
+    Run a program against a capture replayed on a virtual serial port.
+
+    Args:
+        capture: Capture file written with --dump-serial
+        command: Program to run; "{port}" in an argument becomes the port
+            (default: the openflight CLI)
+        speed: Playback speed
+        settle_sec: Time after the capture ends before stopping the program
+            (for its last shot to complete), unless it exits by itself
+        timeout: Seconds to wait for the program to exit after SIGINT
+            (and for each captured write) before killing it
+
+    Returns:
+        The program's exit code and output, and its writes
+
+    Raises:
+        ValueError: If the capture isn't an OPS243 serial capture
+    """
+    header, records = read_dump(capture)
+    if header.get("radar", "ops243") != "ops243":
+        raise ValueError(f"{capture}: only OPS243 captures can be replayed to a program")
+
+    with VirtualSerialPair() as pair, tempfile.TemporaryFile(mode="w+") as output:
+        args = [arg.replace("{port}", pair.port) for arg in (command or DEFAULT_COMMAND)]
+        # Output goes to a file: a full pipe would stall the program mid-replay
+        proc = subprocess.Popen(args, stdout=output, stderr=subprocess.STDOUT)
+        try:
+            mismatched = replay_to(pair, records, speed=speed, write_timeout=timeout)
+            try:
+                returncode = proc.wait(timeout=settle_sec)
+            except subprocess.TimeoutExpired:
+                proc.send_signal(signal.SIGINT)
+                returncode = proc.wait(timeout=timeout)
+        except subprocess.TimeoutExpired:
+            proc.kill()
+            proc.wait()
+            returncode = None
+        finally:
+            if proc.poll() is None:
+                proc.kill()
+                proc.wait()
+        output.seek(0)
+        return HilResult(returncode, output.read(), pair.written, mismatched)
+
+
+def main():
+    """CLI entry point: run a program against a replayed serial capture."""
+    parser = argparse.ArgumentParser(
+        description="Run openflight end to end against a --dump-serial capture on a virtual port",
+        usage="%(prog)s [options] capture [-- command ...]",
+        epilog="The command after -- is run instead of openflight --port {port}; "
+        "{port} in it becomes the virtual port.",
+    )
+    parser.add_argument("capture", help="Capture file written with --dump-serial")
+    parser.add_argument(
+        "--speed", type=float, default=1.0, help="Playback speed (default: %(default)s)"
+    )
+    parser.add_argument(
+        "--settle",
+        type=float,
+        default=2.0,
+        help="Seconds to keep the program running after the capture ends (default: %(default)s)",
+    )
+    parser.add_argument(
+        "--expect-shots", type=int, help="Fail unless the program prints this many shots"
+    )
+    parser.add_argument(
+        "--verbose", "-v", action="store_true", help="Print the program's output"
+    )
+    # Everything after -- is the program to run, options and all
+    argv = sys.argv[1:]
+    command = []
+    if "--" in argv:
+        split = argv.index("--")
+        argv, command = argv[:split], argv[split + 1:]
+    args = parser.parse_args(argv)
+
+    if args.speed <= 0:
+        parser.error("--speed must be positive")
+    try:
+        result = run(args.capture, command or None, speed=args.speed, settle_sec=args.settle)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+
+    if args.verbose or result.returncode != 0:
+        print(result.output)
+    for captured, actual in result.mismatched_writes:
+        print(f"Write differs from the capture: expected {captured!r}, got {actual!r}")
+    exit_text = "killed" if result.returncode is None else f"exit code {result.returncode}"
+    print(
+        f"{args.capture}: {exit_text}, {result.shots} shot(s), "
+        f"{len(result.written)} bytes written"
+    )
+
+    failed = result.returncode != 0 or bool(result.mismatched_writes)
+    if args.expect_shots is not None and result.shots != args.expect_shots:
+        print(f"Expected {args.expect_shots} shot(s), got {result.shots}")
+        failed = True
+    sys.exit(1 if failed else 0)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,367 @@
+"""
+Single-file HTML session viewer.
+
+Renders shots as one self-contained HTML page: a summary per club, a
+table that sorts by any column when its header is clicked, and inline SVG
+charts of ball speed per shot and carry per club. Shots with a horizontal
+launch angle also get a landing scatter with each club's dispersion
+ellipse and target basket (see dispersion), and each club's best ball
+speed and carry are listed per player (see records). Styles, script and
+charts are all inside the file, so it opens in any browser without a
+server and can be emailed to a coach as an attachment.
+
+Usage:
+    openflight-export ~/openflight_sessions/session_20250601*.jsonl --format html -o lesson.html
+"""
+
+import html
+import statistics
+from typing import Callable, Dict, List, Optional, Sequence, Tuple
+
+from .dispersion import Dispersion, club_dispersion
+from .export import CLUB_NAMES, field_value
+from .records import best_shots
+
+# (header, field, decimals); speeds in mph, distances in yards as logged
+_COLUMNS = [
+    ("#", "shot_number", 0),
+    ("Time", "time", None),
+    ("Club", "club_name", None),
+    ("Ball (mph)", "ball_speed_mph", 1),
+    ("Club (mph)", "club_speed_mph", 1),
+    ("Smash", "smash_factor", 2),
+    ("Energy (%)", "energy_transfer_pct", 0),
+    ("Launch (°)", "launch_angle_vertical", 1),
+    ("Spin (rpm)", "spin_rpm", 0),
+    ("Carry (yd)", "estimated_carry_yards", 0),
+]
+
+_CHART_WIDTH = 640
+_CHART_HEIGHT = 220
+_MARGIN = 40
+
+_SCATTER_HEIGHT = 420
+_CLUB_COLORS = ["#2f6fdf", "#3a9d5d", "#d9822b", "#a23fbf", "#c8323b", "#1f9fa8", "#7a6a2f"]
+
+_STYLE = """
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 2em auto;
+       max-width: 960px; padding: 0 1em; color: #1d2330; }
+h1 { font-size: 1.5em; margin-bottom: 0.2em; }
+.meta { color: #667; margin-top: 0; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0 2em; font-size: 0.9em; }
+th, td { padding: 0.35em 0.6em; text-align: right; border-bottom: 1px solid #e3e6ec; }
+th:nth-child(-n+3), td:nth-child(-n+3) { text-align: left; }
+#shots th { cursor: pointer; user-select: none; white-space: nowrap; }
+#shots th.asc::after { content: " \\25B2"; }
+#shots th.desc::after { content: " \\25BC"; }
+tbody tr:nth-child(odd) { background: #f6f7f9; }
+svg { display: block; margin: 0.5em 0 2em; max-width: 100%; height: auto; }
+svg text { font-size: 11px; fill: #556; }
+"""
+
+# Sorts the shots table by the clicked column (numbers numerically, blanks last)
+_SCRIPT = """
+document.querySelectorAll("#shots th").forEach(function (th, col) {
+  th.addEventListener("click", function () {
+    var tbody = document.querySelector("#shots tbody");
+    var asc = !th.classList.contains("asc");
+    document.querySelectorAll("#shots th").forEach(function (h) {
+      h.classList.remove("asc", "desc");
+    });
+    th.classList.add(asc ? "asc" : "desc");
+    var rows = Array.prototype.slice.call(tbody.rows);
+    rows.sort(function (a, b) {
+      var x = a.cells[col].dataset.value, y = b.cells[col].dataset.value;
+      if (x === "") return 1;
+      if (y === "") return -1;
+      var nx = parseFloat(x), ny = parseFloat(y);
+      var cmp = isNaN(nx) || isNaN(ny) ? x.localeCompare(y) : nx - ny;
+      return asc ? cmp : -cmp;
+    });
+    rows.forEach(function (row) { tbody.appendChild(row); });
+  });
+});
+"""
+
+
+def _cell(value, decimals: Optional[int]) -> str:
+    if value is None:
+        return '<td data-value=""></td>'
+    if decimals is not None and isinstance(value, (int, float)) and not isinstance(value, bool):
+        text = f"{value:.{decimals}f}"
+    else:
+        text = str(value)
+    return f'<td data-value="{html.escape(str(value))}">{html.escape(text)}</td>'
+
+
+def _by_club(shots: List[dict]) -> Dict[str, List[dict]]:
+    clubs: Dict[str, List[dict]] = {}
+    for shot in shots:
+        clubs.setdefault(shot.get("club", "driver"), []).append(shot)
+    return clubs
+
+
+def _mean(shots: List[dict], name: str) -> Optional[float]:
+    values = [s[name] for s in shots if isinstance(s.get(name), (int, float))]
+    return statistics.mean(values) if values else None
+
+
+def _summary_table(shots: List[dict]) -> str:
+    rows = []
+    for club, club_shots in _by_club(shots).items():
+        cells = [
+            f"<td>{html.escape(CLUB_NAMES.get(club, club))}</td>",
+            f"<td>{len(club_shots)}</td>",
+        ]
+        for name, decimals in (
+            ("ball_speed_mph", 1),
+            ("club_speed_mph", 1),
+            ("smash_factor", 2),
+            ("estimated_carry_yards", 0),
+        ):
+            mean = _mean(club_shots, name)
+            cells.append(f"<td>{'' if mean is None else f'{mean:.{decimals}f}'}</td>")
+        rows.append(f"<tr>{''.join(cells)}</tr>")
+    return (
+        "<table><thead><tr><th>Club</th><th>Shots</th><th>Avg ball (mph)</th>"
+        "<th>Avg club (mph)</th><th>Avg smash</th><th>Avg carry (yd)</th></tr></thead>"
+        f"<tbody>{''.join(rows)}</tbody></table>"
+    )
+
+
+def _shots_table(shots: List[dict]) -> str:
+    header = "".join(f"<th>{html.escape(title)}</th>" for title, _, _ in _COLUMNS)
+    rows = []
+    for number, shot in enumerate(shots, start=1):
+        cells = []
+        for _, name, decimals in _COLUMNS:
+            value = field_value(shot, name)
+            if name == "shot_number" and value is None:
+                value = number
+            cells.append(_cell(value, decimals))
+        rows.append(f"<tr>{''.join(cells)}</tr>")
+    return (
+        f'<table id="shots"><thead><tr>{header}</tr></thead>'
+        f"<tbody>{''.join(rows)}</tbody></table>"
+    )
+
+
+def _chart(
+    points: Sequence[Tuple[str, float]],
+    title: str,
+    draw: Callable[[float, float, float, float], str],
+) -> str:
+    """SVG frame with axis labels; draw(x, y, slot_width, bottom) draws one point."""
+    top = max(value for _, value in points) * 1.1 or 1.0
+    plot_width = _CHART_WIDTH - 2 * _MARGIN
+    plot_height = _CHART_HEIGHT - 2 * _MARGIN
+    slot = plot_width / len(points)
+    bottom = _CHART_HEIGHT - _MARGIN
+    parts = [
+        f'<svg viewBox="0 0 {_CHART_WIDTH} {_CHART_HEIGHT}" role="img" '
+        f'aria-label="{html.escape(title)}">',
+        f'<text x="{_MARGIN}" y="16">{html.escape(title)}</text>',
+        f'<line x1="{_MARGIN}" y1="{bottom}" x2="{_CHART_WIDTH - _MARGIN}" y2="{bottom}" '
+        'stroke="#99a"/>',
+        f'<text x="4" y="{_MARGIN + 4}">{top:.0f}</text>',
+        f'<text x="4" y="{bottom}">0</text>',
+    ]
+    for i, (label, value) in enumerate(points):
+        x = _MARGIN + slot * (i + 0.5)
+        y = bottom - plot_height * value / top
+        parts.append(draw(x, y, slot, bottom))
+        if len(points) <= 20:
+            parts.append(
+                f'<text x="{x:.1f}" y="{bottom + 14}" text-anchor="middle">'
+                f"{html.escape(label)}</text>"
+            )
+    parts.append("</svg>")
+    return "".join(parts)
+
+
+def _ball_speed_chart(shots: List[dict]) -> str:
+    points = [
+        (str(i), s["ball_speed_mph"])
+        for i, s in enumerate(shots, start=1)
+        if isinstance(s.get("ball_speed_mph"), (int, float))
+    ]
+    if not points:
+        return ""
+    return _chart(
+        points,
+        "Ball speed by shot (mph)",
+        lambda x, y, slot, bottom: f'<circle cx="{x:.1f}" cy="{y:.1f}" r="4" fill="#2f6fdf"/>',
+    )
+
+
+def _carry_chart(shots: List[dict]) -> str:
+    points = []
+    for club, club_shots in _by_club(shots).items():
+        mean = _mean(club_shots, "estimated_carry_yards")
+        if mean is not None:
+            points.append((CLUB_NAMES.get(club, club), mean))
+    if not points:
+        return ""
+    return _chart(
+        points,
+        "Average carry by club (yd)",
+        lambda x, y, slot, bottom: (
+            f'<rect x="{x - slot * 0.3:.1f}" y="{y:.1f}" width="{slot * 0.6:.1f}" '
+            f'height="{bottom - y:.1f}" fill="#3a9d5d"/>'
+        ),
+    )
+
+
+def _dispersion_table(dispersion: List[Dispersion]) -> str:
+    rows = []
+    for d in dispersion:
+        summary = d.to_dict()
+        ellipse = summary["ellipse"]
+        size = f"{ellipse['length']:.0f} × {ellipse['width']:.0f}" if ellipse else ""
+        offline = summary["avg_offline"]
+        side = "R" if offline > 0 else "L" if offline < 0 else ""
+        rows.append(
+            f"<tr><td>{html.escape(CLUB_NAMES.get(d.club, d.club))}</td><td>{d.shots}</td>"
+            f"<td>{summary['avg_downrange']:.0f}</td><td>{abs(offline):.0f} {side}</td>"
+            f"<td>{summary['downrange_std_dev']:.1f}</td><td>{summary['offline_std_dev']:.1f}</td>"
+            f"<td>{size}</td><td>{summary['in_basket_pct']:.0f}%</td></tr>"
+        )
+    return (
+        "<table><thead><tr><th>Club</th><th>Shots</th><th>Avg carry (yd)</th>"
+        "<th>Avg offline (yd)</th><th>Carry spread (yd)</th><th>Offline spread (yd)</th>"
+        "<th>Ellipse (yd)</th><th>In basket</th></tr></thead>"
+        f"<tbody>{''.join(rows)}</tbody></table>"
+    )
+
+
+def _dispersion_chart(dispersion: List[Dispersion]) -> str:
+    """Landing points seen from above: target line up the middle, offline across."""
+    far = max(max(p[0] for p in d.points) for d in dispersion) * 1.1 or 1.0
+    wide = max(max(abs(p[1]) for p in d.points) for d in dispersion)
+    for d in dispersion:
+        if d.ellipse:
+            e = d.ellipse
+            far = max(far, e.downrange + e.length / 2)
+            wide = max(wide, abs(e.offline) + e.length / 2)
+    wide = max(wide * 1.1, far * 0.1)
+    plot_width = _CHART_WIDTH - 2 * _MARGIN
+    plot_height = _SCATTER_HEIGHT - 2 * _MARGIN
+    centre = _MARGIN + plot_width / 2
+    bottom = _SCATTER_HEIGHT - _MARGIN
+    # Same scale both ways, so ellipses keep their shape
+    scale = min(plot_height / far, plot_width / (2 * wide))
+
+    def x(offline: float) -> float:
+        return centre + offline * scale
+
+    def y(downrange: float) -> float:
+        return bottom - downrange * scale
+
+    parts = [
+        f'<svg viewBox="0 0 {_CHART_WIDTH} {_SCATTER_HEIGHT}" role="img" '
+        'aria-label="Landing dispersion">',
+        f'<text x="{_MARGIN}" y="16">Landing dispersion (yd)</text>',
+        f'<line x1="{centre}" y1="{bottom}" x2="{centre}" y2="{_MARGIN}" '
+        'stroke="#99a" stroke-dasharray="4 4"/>',
+        f'<text x="{centre + 4:.1f}" y="{_MARGIN + 4}">{far:.0f}</text>',
+        f'<text x="{_MARGIN}" y="{bottom + 14}">L</text>',
+        f'<text x="{_CHART_WIDTH - _MARGIN}" y="{bottom + 14}" text-anchor="end">R</text>',
+    ]
+    for i, d in enumerate(dispersion):
+        color = _CLUB_COLORS[i % len(_CLUB_COLORS)]
+        name = html.escape(CLUB_NAMES.get(d.club, d.club))
+        target = d.to_dict()["avg_downrange"]
+        parts.append(
+            f'<circle cx="{centre:.1f}" cy="{y(target):.1f}" r="{d.basket_radius * scale:.1f}" '
+            f'fill="none" stroke="{color}" stroke-dasharray="2 3"/>'
+        )
+        if d.ellipse:
+            e = d.ellipse
+            # SVG rotates clockwise; the long axis is tilted toward the right for + angles
+            parts.append(
+                f'<ellipse cx="{x(e.offline):.1f}" cy="{y(e.downrange):.1f}" '
+                f'rx="{e.width / 2 * scale:.1f}" ry="{e.length / 2 * scale:.1f}" '
+                f'transform="rotate({e.angle_deg:.1f} {x(e.offline):.1f} {y(e.downrange):.1f})" '
+                f'fill="{color}" fill-opacity="0.12" stroke="{color}"/>'
+            )
+        for downrange, offline in d.points:
+            parts.append(
+                f'<circle cx="{x(offline):.1f}" cy="{y(downrange):.1f}" r="3" fill="{color}"/>'
+            )
+        parts.append(
+            f'<text x="{_CHART_WIDTH - _MARGIN}" y="{_MARGIN + 14 * i}" text-anchor="end" '
+            f'fill="{color}">{name}</text>'
+        )
+    parts.append("</svg>")
+    return "".join(parts)
+
+
+def _dispersion_section(shots: List[dict]) -> str:
+    dispersion = club_dispersion(shots)
+    if not dispersion:
+        return ""
+    return (
+        f"<h2>Dispersion</h2>{_dispersion_table(dispersion)}{_dispersion_chart(dispersion)}"
+    )
+
+
+def _records_section(shots: List[dict]) -> str:
+    records = best_shots(shots)
+    if not records:
+        return ""
+    # (player, club) -> {metric: record}
+    rows: Dict[Tuple[Optional[str], str], dict] = {}
+    for record in records:
+        rows.setdefault((record.player, record.club), {})[record.metric] = record
+    with_players = any(player for player, _ in rows)
+    body = []
+    for (player, club), by_metric in rows.items():
+        cells = [f"<td>{html.escape(player or '')}</td>"] if with_players else []
+        cells.append(f"<td>{html.escape(CLUB_NAMES.get(club, club))}</td>")
+        for metric, decimals in (("ball_speed", 1), ("carry", 0)):
+            record = by_metric.get(metric)
+            if record is None:
+                cells.append("<td></td><td></td>")
+            else:
+                cells.append(
+                    f"<td>{record.value:.{decimals}f}</td><td>{html.escape(record.date or '')}</td>"
+                )
+        body.append(f"<tr>{''.join(cells)}</tr>")
+    player_header = "<th>Player</th>" if with_players else ""
+    return (
+        f"<h2>Best shots</h2><table><thead><tr>{player_header}<th>Club</th>"
+        "<th>Ball (mph)</th><th>Date</th><th>Carry (yd)</th><th>Date</th></tr></thead>"
+        f"<tbody>{''.join(body)}</tbody></table>"
+    )
+
+
+def to_html(shots: List[dict], title: str = "OpenFlight session") -> str:
+    """
+    Render shots as a self-contained HTML page.
+
+    Args:
+        shots: shot_detected log entries or shot_to_dict output, in order
+        title: Page heading
+
+    Returns:
+        The HTML document
+    """
+    sessions = sorted({str(s["session"]) for s in shots if s.get("session")})
+    dates = sorted({d for d in (field_value(s, "date") for s in shots) if d})
+    meta = f"{len(shots)} shot(s)"
+    if dates:
+        meta += f" · {dates[0]}" + (f" to {dates[-1]}" if dates[-1] != dates[0] else "")
+    if sessions:
+        meta += f" · {', '.join(sessions)}"
+    return (
+        "<!DOCTYPE html>\n"
+        '<html lang="en"><head><meta charset="utf-8">'
+        '<meta name="viewport" content="width=device-width, initial-scale=1">'
+        f"<title>{html.escape(title)}</title><style>{_STYLE}</style></head><body>"
+        f"<h1>{html.escape(title)}</h1><p class=\"meta\">{html.escape(meta)}</p>"
+        f"<h2>By club</h2>{_summary_table(shots)}"
+        f"{_ball_speed_chart(shots)}{_carry_chart(shots)}{_dispersion_section(shots)}"
+        f"{_records_section(shots)}"
+        f"<h2>Shots</h2>{_shots_table(shots)}"
+        f"<script>{_SCRIPT}</script></body></html>\n"
+    )
@@ -0,0 +1,103 @@
+"""
+When impact happened within a shot, and how consistent it is.
+
+The radar sees the club coming down and then the ball leaving, a frame
+apart at best, so impact is somewhere between the last frame with only
+the club and the first frame with the ball. Each frame integrates its
+whole window, so a ball that was there for only part of the window
+returns less than its full signal: the first ball frame's magnitude
+against the ball's peak says how far into the gap impact was.
+
+    impact = first_ball - fraction * (first_ball - last_club)
+    fraction = first ball magnitude / peak ball magnitude (0.5 without magnitudes)
+
+A ball at full strength in its first frame was hit at the start of the
+gap, a faint one near its end.
+
+impact_offset_ms is impact minus the time of the club's fastest reading:
+near zero when the club is still at top speed at the ball, larger when
+it peaked early and slowed into the ball. Its spread over a session
+(impact_timing_sd_ms) is a consistency metric: a repeatable release
+puts the peak at the same place every swing.
+"""
+
+import statistics
+from dataclasses import dataclass
+from typing import Any, Dict, Iterable, List, Optional
+
+from .ops243 import SpeedReading
+
+
+@dataclass(frozen=True)
+class ImpactTiming:
+    """Estimated impact of one shot."""
+
+    impact_time: float  # Same clock as the readings (time.monotonic())
+    offset_ms: float  # Impact minus the club's fastest reading
+    window_ms: float  # Gap between the last club frame and the first ball frame
+
+
+def _frame_speeds(reading: SpeedReading) -> List[float]:
+    return [reading.speed, *(o.speed for o in reading.frame_objects or [])]
+
+
+def estimate_impact(
+    readings: Iterable[SpeedReading], ball_speed_mph: float, club_speed_mph: float
+) -> Optional[ImpactTiming]:
+    """
+    Estimate when the club hit the ball.
+
+    Args:
+        readings: The shot's readings (any order)
+        ball_speed_mph: Detected ball speed
+        club_speed_mph: Detected club speed
+
+    Returns:
+        The impact timing, or None without timed club and ball frames
+    """
+    timed = sorted((r for r in readings if r.timestamp is not None), key=lambda r: r.timestamp)
+    # Halfway between the club and ball speeds separates the two
+    split = (ball_speed_mph + club_speed_mph) / 2
+
+    first_ball = next((r for r in timed if max(_frame_speeds(r)) >= split), None)
+    if first_ball is None:
+        return None
+    club = [
+        r
+        for r in timed
+        if r.timestamp < first_ball.timestamp and max(_frame_speeds(r)) < split
+    ]
+    if not club:
+        return None
+    last_club = club[-1].timestamp
+    peak_club = max(club, key=lambda r: r.speed).timestamp
+
+    ball_magnitudes = [r.magnitude for r in timed if r.speed >= split and r.magnitude]
+    if first_ball.speed >= split and first_ball.magnitude and ball_magnitudes:
+        fraction = min(1.0, first_ball.magnitude / max(ball_magnitudes))
+    else:
+        fraction = 0.5
+    window = first_ball.timestamp - last_club
+    impact = first_ball.timestamp - fraction * window
+    return ImpactTiming(
+        impact_time=impact,
+        offset_ms=(impact - peak_club) * 1000,
+        window_ms=window * 1000,
+    )
+
+
+def impact_stats(shots: Iterable[Any]) -> Dict[str, Optional[float]]:
+    """
+    Session impact timing statistics for get_session_stats().
+
+    Args:
+        shots: Shot objects
+
+    Returns:
+        avg_impact_offset_ms and impact_timing_sd_ms (None without two timed shots)
+    """
+    offsets = [s.impact_offset_ms for s in shots if s.impact_offset_ms is not None]
+    return {
+        "avg_impact_offset_ms": statistics.mean(offsets) if offsets else None,
+        "impact_timing_sd_ms": statistics.stdev(offsets) if len(offsets) > 1 else None,
+    }
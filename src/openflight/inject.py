@@ -0,0 +1,112 @@
+"""
+Inject synthetic shots into a running OpenFlight server.
+
+Sends a shot to the server's /api/inject endpoint, which delivers it
+through the normal path (UI, session log, stream, webhooks) so sim
+mappings and overlays can be checked without hitting balls.
+
+Usage:
+    openflight-inject --ball 150 --vla 14
+    openflight-inject --ball 120 --club 7-iron --spin 6500 --url http://pi.local:8080
+"""
+
+import argparse
+import json
+import os
+import sys
+import urllib.error
+import urllib.request
+from typing import Optional
+
+from .access import TOKEN_HEADER
+from .launch_monitor import ClubType
+
+
+def inject_shot(url: str, payload: dict, token: Optional[str] = None, timeout: float = 5.0) -> dict:
+    """
+    POST a shot to a server's /api/inject endpoint.
+
+    Args:
+        url: Server base URL, e.g. "http://localhost:8080"
+        payload: Shot fields (ball_speed_mph, launch_angle_vertical, ...)
+        token: API token, if the server requires one
+        timeout: HTTP timeout in seconds
+
+    Returns:
+        The server's JSON response
+
+    Raises:
+        RuntimeError: If the server rejects the shot or is unreachable
+    """
+    headers = {"Content-Type": "application/json"}
+    if token:
+        headers[TOKEN_HEADER] = token
+
+    request = urllib.request.Request(
+        url.rstrip("/") + "/api/inject",
+        data=json.dumps(payload).encode("utf-8"),
+        headers=headers,
+        method="POST",
+    )
+    try:
+        with urllib.request.urlopen(request, timeout=timeout) as response:
+            return json.loads(response.read())
+    except urllib.error.HTTPError as e:
+        try:
+            message = json.loads(e.read()).get("error", e.reason)
+        except ValueError:
+            message = e.reason
+        raise RuntimeError(f"Server rejected shot (HTTP {e.code}): {message}") from None
+    except (urllib.error.URLError, OSError) as e:
+        raise RuntimeError(f"Could not reach {url}: {e}") from None
+
+
+def main():
+    """CLI entry point."""
+    parser = argparse.ArgumentParser(description="Inject a synthetic shot into OpenFlight")
+    parser.add_argument("--ball", type=float, required=True, help="Ball speed (mph)")
+    parser.add_argument("--club-speed", type=float, help="Club head speed (mph)")
+    parser.add_argument("--vla", type=float, help="Vertical launch angle (degrees)")
+    parser.add_argument("--hla", type=float, help="Horizontal launch angle (degrees)")
+    parser.add_argument("--spin", type=float, help="Spin rate (rpm)")
+    parser.add_argument(
+        "--club",
+        choices=[c.value for c in ClubType],
+        default=ClubType.DRIVER.value,
+        help="Club (default: driver)",
+    )
+    parser.add_argument(
+        "--url", default="http://localhost:8080", help="Server URL (default: http://localhost:8080)"
+    )
+    parser.add_argument(
+        "--token",
+        default=os.environ.get("OPENFLIGHT_API_TOKEN"),
+        help="API token (or OPENFLIGHT_API_TOKEN env var)",
+    )
+    args = parser.parse_args()
+
+    payload = {
+        "ball_speed_mph": args.ball,
+        "club_speed_mph": args.club_speed,
+        "launch_angle_vertical": args.vla,
+        "launch_angle_horizontal": args.hla,
+        "spin_rpm": args.spin,
+        "club": args.club,
+    }
+    payload = {k: v for k, v in payload.items() if v is not None}
+
+    try:
+        result = inject_shot(args.url, payload, token=args.token)
+    except RuntimeError as e:
+        print(f"Error: {e}", file=sys.stderr)
+        sys.exit(1)
+
+    shot = result["shot"]
+    print(
+        f"Injected: {shot['ball_speed_mph']} mph ball, "
+        f"{shot['estimated_carry_yards']} yds carry ({shot['club']})"
+    )
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,314 @@
+"""
+TI IWR6843 mmWave radar driver.
+
+The IWR6843 evaluation boards (IWR6843ISK, IWR6843AOPEVM) running the
+mmWave SDK out-of-box demo expose two serial ports:
+
+- CLI port (115200 baud): text commands that set up the chirps, CFAR and
+  outputs, one per line, each answered with "Done" or "Error"
+- Data port (921600 baud): one binary packet per frame with the detected
+  points (position and radial velocity) and their SNR
+
+Unlike the OPS243, the sensor measures range and angle natively, so each
+point comes with where it is as well as how fast it moves. Points are
+decoded into PointCloud frames (see frames) and the moving ones become
+SpeedReadings, so LaunchMonitor runs on this sensor unchanged.
+
+GOLF_CONFIG is a starting point, not a calibrated profile: one TX antenna
+and 14 us chirps for an unambiguous velocity of about +/-89 m/s (~200
+mph), 100 frames per second, static clutter removed. With a single TX only
+azimuth is resolved. Pass config_file to use your own .cfg from TI's
+mmWave Demo Visualizer.
+
+Example:
+    radar = IWR6843Radar(cli_port="/dev/ttyUSB0", data_port="/dev/ttyUSB1")
+    radar.connect()
+    radar.configure_for_golf()
+    radar.start_streaming(callback=print)
+"""
+
+import logging
+import threading
+import time
+from pathlib import Path
+from typing import Callable, Dict, List, Optional
+
+import serial
+
+from .frames import Frame, IWR6843Decoder, PointCloud, SpeedReading
+from .radar_detect import resolve_port
+from .serial_dump import SerialDump
+
+logger = logging.getLogger(__name__)
+
+
+class IWR6843Radar:
+    """Driver for a TI IWR6843 running the mmWave SDK out-of-box demo."""
+
+    CLI_BAUD = 115200
+    DATA_BAUD = 921600
+    COMMAND_TIMEOUT = 1.0
+
+    GOLF_CONFIG = [
+        "sensorStop",
+        "flushCfg",
+        "dfeDataOutputMode 1",
+        "channelCfg 15 1 0",  # 4 RX, 1 TX: shortest chirp period
+        "adcCfg 2 1",
+        "adcbufCfg -1 0 1 1 1",
+        # 60 GHz start, 5 us idle + 9 us ramp (14 us chirps), 64 samples at 12.5 Msps
+        "profileCfg 0 60 5 3 9 0 0 100 1 64 12500 0 0 30",
+        "chirpCfg 0 0 0 0 0 0 0 1",
+        "frameCfg 0 0 128 0 10 1",  # 128 chirps, 10 ms frames
+        "lowPower 0 0",
+        "guiMonitor -1 1 0 0 0 0 0",  # Detected points with side info only
+        "cfarCfg -1 0 2 8 4 3 0 15 1",
+        "cfarCfg -1 1 0 4 2 3 1 15 1",
+        "multiObjBeamForming -1 1 0.5",
+        "clutterRemoval -1 1",  # Drop static returns (mat, net, walls)
+        "calibDcRangeSig -1 0 -5 8 256",
+        "extendedMaxVelocity -1 0",
+        "lvdsStreamCfg -1 0 0 0",
+        "compRangeBiasAndRxChanPhase 0.0 1 0 1 0 1 0 1 0 1 0 1 0 1 0 1 0 1 0 1 0 1 0 1 0",
+        "measureRangeBiasAndRxChanPhase 0 1.5 0.2",
+        "CQRxSatMonitor 0 3 5 121 0",
+        "CQSigImgMonitor 0 127 4",
+        "analogMonitor 0 0",
+        "aoaFovCfg -1 -90 90 -90 90",
+        "cfarFovCfg -1 0 0 8.92",  # Range, meters
+        "cfarFovCfg -1 1 -89 89",  # Velocity, m/s
+        "calibData 0 0 0",
+        "sensorStart",
+    ]
+
+    product = "IWR6843"
+    dump: Optional[SerialDump] = None  # Set before connect() to capture the data port
+
+    def __init__(
+        self,
+        cli_port: Optional[str] = None,
+        data_port: Optional[str] = None,
+        config_file: Optional[str] = None,
+    ):
+        """
+        Initialize radar driver.
+
+        Args:
+            cli_port: Serial port for configuration commands
+            data_port: Serial port the detected points arrive on
+            config_file: mmWave demo .cfg to send instead of GOLF_CONFIG
+        """
+        self.port = cli_port
+        self.data_port = data_port
+        self.config_file = config_file
+        self.cli: Optional[serial.Serial] = None
+        self.data: Optional[serial.Serial] = None
+        self._decoder = IWR6843Decoder()
+        self._streaming = False
+        self._stream_thread: Optional[threading.Thread] = None
+        self._callback: Optional[Callable[[SpeedReading], None]] = None
+        self._frame_callback: Optional[Callable[[Frame], None]] = None
+        # Software filters (the demo has no speed or magnitude filter commands)
+        self._min_speed = 0.0
+        self._max_speed = 0.0  # 0 = no limit
+        self._min_magnitude = 0.0
+
+    def connect(self) -> bool:
+        """
+        Open the CLI and data ports.
+
+        Returns:
+            True if connection successful
+
+        Raises:
+            ConnectionError: If a port is missing or can't be opened
+        """
+        if not self.port or not self.data_port:
+            raise ConnectionError(
+                "The IWR6843 needs both its CLI port and its data port "
+                "(e.g. --port /dev/ttyUSB0 --data-port /dev/ttyUSB1)"
+            )
+        self.port, self.data_port = resolve_port(self.port), resolve_port(self.data_port)
+        try:
+            self.cli = serial.Serial(port=self.port, baudrate=self.CLI_BAUD, timeout=0.1)
+            self.data = serial.Serial(port=self.data_port, baudrate=self.DATA_BAUD, timeout=0.1)
+        except serial.SerialException as e:
+            self.disconnect()
+            raise ConnectionError(f"Failed to connect to IWR6843: {e}") from e
+        self.data.reset_input_buffer()
+        if self.dump:
+            self.data = self.dump.tee(self.data)
+        return True
+
+    def disconnect(self):
+        """Stop streaming and the sensor, and close both ports."""
+        self.stop_streaming()
+        if self.cli and self.cli.is_open:
+            try:
+                self.send_command("sensorStop")
+            except (RuntimeError, serial.SerialException) as e:
+                logger.debug("Could not stop IWR6843: %s", e)
+        for port in (self.cli, self.data):
+            if port and port.is_open:
+                port.close()
+        self.cli = None
+        self.data = None
+
+    def send_command(self, command: str) -> str:
+        """
+        Send one CLI command and wait for the demo to answer.
+
+        Args:
+            command: Command line without line ending, e.g. "sensorStop"
+
+        Returns:
+            The demo's answer, prompt and echo included
+
+        Raises:
+            ConnectionError: If not connected
+            RuntimeError: If the demo rejects the command
+        """
+        if not self.cli or not self.cli.is_open:
+            raise ConnectionError("Not connected to radar")
+
+        self.cli.reset_input_buffer()
+        self.cli.write(f"{command}\n".encode("ascii"))
+        response = ""
+        deadline = time.monotonic() + self.COMMAND_TIMEOUT
+        while time.monotonic() < deadline:
+            response += self.cli.read(256).decode("ascii", errors="ignore")
+            if "Done" in response or "Error" in response:
+                break
+        if "Error" in response:
+            raise RuntimeError(f"IWR6843 rejected {command!r}: {response.strip()}")
+        return response
+
+    def config_lines(self) -> List[str]:
+        """The configuration to send: config_file's commands or GOLF_CONFIG."""
+        if not self.config_file:
+            return list(self.GOLF_CONFIG)
+        lines = Path(self.config_file).read_text(encoding="utf-8").splitlines()
+        return [line.strip() for line in lines if line.strip() and not line.startswith("%")]
+
+    def configure_for_golf(self):
+        """Send the chirp and detection configuration and start the sensor."""
+        lines = self.config_lines()
+        for line in lines:
+            logger.debug("IWR6843 CLI: %s", line)
+            self.send_command(line)
+        self._decoder.reset()
+        logger.info("Configured IWR6843 (%d commands)", len(lines))
+
+    def get_info(self) -> Dict[str, str]:
+        """
+        Get radar module information from the demo's version command.
+
+        Returns:
+            Dict with Product, Version (the mmWave SDK version) and whatever
+            else the demo reports as "key : value" lines
+        """
+        info = {"Product": self.product}
+        for line in self.send_command("version").splitlines():
+            key, sep, value = line.partition(":")
+            if sep and key.strip() and value.strip():
+                info[key.strip()] = value.strip()
+        sdk = next((v for k, v in info.items() if "SDK" in k), None)
+        info["Version"] = sdk or "unknown"
+        return info
+
+    def set_min_speed_filter(self, min_speed: float):
+        """Ignore targets slower than this (mph)."""
+        self._min_speed = min_speed
+
+    def set_max_speed_filter(self, max_speed: float):
+        """Ignore targets faster than this (mph, 0 = no limit)."""
+        self._max_speed = max_speed
+
+    def set_magnitude_filter(self, min_mag: int = 0, max_mag: int = 0):
+        """Ignore targets weaker than min_mag (SNR as a power ratio, see PointCloud)."""
+        del max_mag  # Not supported; kept for the OPS243 signature
+        self._min_magnitude = min_mag
+
+    def set_transmit_power(self, level: int):
+        """Transmit power is part of the chirp profile (profileCfg), not a setting."""
+        raise ValueError("Set IWR6843 transmit power in the profileCfg of a config file")
+
+    def reading_from_frame(self, frame: PointCloud) -> Optional[SpeedReading]:
+        """
+        The frame's moving points as a SpeedReading (strongest first, the
+        rest as frame_objects), after the software filters.
+
+        Returns:
+            None if no point passed the filters
+        """
+        target = frame.to_speed_target()
+        target.targets = [
+            t for t in target.targets
+            if t.speed >= self._min_speed
+            and (not self._max_speed or t.speed <= self._max_speed)
+            and (t.magnitude is None or t.magnitude >= self._min_magnitude)
+        ]
+        return target.to_reading()
+
+    def start_streaming(
+        self,
+        callback: Callable[[SpeedReading], None],
+        frame_callback: Optional[Callable[[Frame], None]] = None,
+    ):
+        """
+        Start reading frames in a background thread.
+
+        Args:
+            callback: Function called with each frame's SpeedReading
+            frame_callback: Function called with every PointCloud, for
+                processing that wants positions too
+        """
+        if self._streaming:
+            return
+
+        self._callback = callback
+        self._frame_callback = frame_callback
+        self._streaming = True
+        self._stream_thread = threading.Thread(target=self._stream_loop, daemon=True)
+        self._stream_thread.start()
+
+    def stop_streaming(self):
+        """Stop the background reader."""
+        self._streaming = False
+        if self._stream_thread:
+            self._stream_thread.join(timeout=2.0)
+            self._stream_thread = None
+
+    def _stream_loop(self):
+        while self._streaming:
+            try:
+                chunk = self.data.read(4096)
+            except serial.SerialException as e:
+                logger.error("IWR6843 data port error: %s", e)
+                break
+            if chunk:
+                self.process_data(chunk)
+
+    def process_data(self, chunk: bytes, timestamp: Optional[float] = None):
+        """Decode a chunk from the data port and call the callbacks."""
+        for frame in self._decoder.feed(chunk, timestamp):
+            if not isinstance(frame, PointCloud):
+                continue
+            if self._frame_callback:
+                self._frame_callback(frame)
+            reading = self.reading_from_frame(frame)
+            if reading and self._callback:
+                self._callback(reading)
+
+    @property
+    def discarded_samples(self) -> int:
+        """Points dropped for non-finite coordinates or velocity."""
+        return self._decoder.discarded
+
+    def __enter__(self):
+        self.connect()
+        self.configure_for_golf()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.disconnect()
@@ -5,16 +5,36 @@ This module provides the main launch monitor functionality,
 detecting golf ball speeds and displaying results.
 """
 
+import difflib
+import hashlib
+import json
+import math
 import statistics
+import threading
 import time
-from dataclasses import dataclass, field
+from collections import deque
+from dataclasses import asdict, dataclass, field, fields, replace
 from datetime import datetime
 from enum import Enum
-from typing import Callable, List, Optional
-
-from .ops243 import Direction, OPS243Radar, SpeedReading
+from typing import Any, Callable, Deque, Dict, List, Optional, Tuple
+
+from .classifier import ShotClassifier
+from .clock import get_clock
+from .energy import energy_stats, shot_energy
+from .filters import IQ_STREAMING_FILTERS, LEGACY_FILTERS, FilterContext, FilterPipeline
+from .frames import is_finite
+from .impact import estimate_impact, impact_stats
+from .ops243 import OUTPUT_FORMATS, MountPosition, OPS243Radar, SpeedReading
+from .reading_loss import analyze_loss
+from .segmentation import CHANGE_POINT, SEGMENTATION_MODES, TIMEOUT, ChangePointSegmenter
 from .session_logger import get_session_logger
+from .shot_card import ShotCard
+from .smash import smash_window
 from .streaming import StreamingSpeedDetector
+from .takeaway import TakeawayWindow, backswing_direction, relaxed_config
+from .tempo import TEMPO_TOLERANCE
+from .tracing import traced
+from .units import Degrees, MetersPerSecond, Mph, Rpm
 
 
 class ClubType(Enum):
@@ -214,6 +234,53 @@ def adjust_carry_for_launch_angle(
     return base_carry - penalty
 
 
+def _is_valid_reading(reading: SpeedReading) -> bool:
+    """True if a reading's speed, magnitude and time (when set) are finite numbers."""
+    values = (reading.speed, reading.magnitude, reading.timestamp)
+    return all(is_finite(v) for v in values if v is not None)
+
+
+# Sort keys that order every reading, even one with a missing or NaN value
+def _by_time(reading: SpeedReading) -> float:
+    return reading.timestamp if is_finite(reading.timestamp) else 0.0
+
+
+def _by_speed(reading: SpeedReading) -> float:
+    return reading.speed if is_finite(reading.speed) else -math.inf
+
+
+def _by_magnitude(reading: SpeedReading) -> float:
+    return reading.magnitude if is_finite(reading.magnitude) else -math.inf
+
+
+def _reading_entry(reading: SpeedReading) -> dict:
+    """A reading as logged with its shot."""
+    entry = {
+        "speed": reading.speed,
+        "direction": reading.direction.value,
+        "magnitude": reading.magnitude,
+        "timestamp": get_clock().to_wall(reading.timestamp),
+    }
+    if reading.frame_objects:
+        entry["frame_objects"] = [
+            {"speed": o.speed, "direction": o.direction.value, "magnitude": o.magnitude}
+            for o in reading.frame_objects
+        ]
+    return entry
+
+
+_SHOT_UNITS = {
+    "ball_speed_mph": Mph,
+    "club_speed_mph": Mph,
+    "ball_speed_raw_mph": Mph,
+    "club_speed_raw_mph": Mph,
+    "ball_speed_reading_mph": Mph,
+    "launch_angle_vertical": Degrees,
+    "launch_angle_horizontal": Degrees,
+    "spin_rpm": Rpm,
+}
+
+
 @dataclass
 class Shot:
     """
@@ -235,34 +302,85 @@ class Shot:
         carry_spin_adjusted: Carry distance adjusted for spin (yards)
         mode: Shot source — "streaming", "rolling-buffer", or "mock"
         readings_data: Serialized readings for session logging
+        ball_speed_raw_mph: Ball speed as measured, before cosine correction
+        club_speed_raw_mph: Club speed as measured, before cosine correction
+        hole_number: Hole being played on the sim, if it reported one
+        hole_shot: Stroke index on that hole (1 = tee shot)
+        delivery_status: Webhook delivery - "pending", "delivered", "failed",
+            "merged" (throttled, a later shot sent instead), or None when no
+            webhooks are configured
+        log_number: Shot number in the session log (None if logging is off)
+        tempo_backswing_sec: Measured takeaway-to-top time (with --tempo)
+        tempo_downswing_sec: Measured top-to-impact time (with --tempo)
+        tempo_target_ratio: Tempo the shot was practiced at (3.0 for 3:1)
+        target_yards: Practice target distance the shot was scored against
+        strokes_gained: Strokes gained against that target (see scoring)
+        ball_type: Ball hit (see ball_types), which scales the carry
+        pre_trigger_readings: Readings the filters rejected from just before
+            and during the shot (e.g. the club approaching), oldest first
+        pre_trigger_data: Serialized pre_trigger_readings for session logging
+        impact_time: Estimated impact, on the readings' clock (see impact)
+        impact_offset_ms: Impact minus the time of the club's peak speed
+        lost_frames: Radar frames lost in the ball's readings and just
+            before them (see reading_loss)
+        max_reading_gap_ms: Longest time between the ball's frames
+        ball_speed_reading_mph: Fastest ball reading, when the ball frames
+            lost before it put ball_speed_mph above it
     """
 
-    ball_speed_mph: float
+    ball_speed_mph: Mph
     timestamp: datetime
-    club_speed_mph: Optional[float] = None
+    club_speed_mph: Optional[Mph] = None
     peak_magnitude: Optional[float] = None
     readings: List[SpeedReading] = field(default_factory=list)
     club: ClubType = ClubType.DRIVER
-    launch_angle_vertical: Optional[float] = None
-    launch_angle_horizontal: Optional[float] = None
+    launch_angle_vertical: Optional[Degrees] = None
+    launch_angle_horizontal: Optional[Degrees] = None
     launch_angle_confidence: Optional[float] = None
-    spin_rpm: Optional[float] = None
+    spin_rpm: Optional[Rpm] = None
     spin_confidence: Optional[float] = None
     carry_spin_adjusted: Optional[float] = None
     mode: str = "streaming"
     readings_data: Optional[list] = None
+    ball_speed_raw_mph: Optional[Mph] = None
+    club_speed_raw_mph: Optional[Mph] = None
+    hole_number: Optional[int] = None
+    hole_shot: Optional[int] = None
+    delivery_status: Optional[str] = None
+    log_number: Optional[int] = None
+    tempo_backswing_sec: Optional[float] = None
+    tempo_downswing_sec: Optional[float] = None
+    tempo_target_ratio: Optional[float] = None
+    target_yards: Optional[float] = None
+    strokes_gained: Optional[float] = None
+    ball_type: Optional[str] = None
+    pre_trigger_readings: List[SpeedReading] = field(default_factory=list)
+    pre_trigger_data: Optional[list] = None
+    impact_time: Optional[float] = None
+    impact_offset_ms: Optional[float] = None
+    lost_frames: Optional[int] = None
+    max_reading_gap_ms: Optional[float] = None
+    ball_speed_reading_mph: Optional[Mph] = None
+
+    def __setattr__(self, name, value):
+        # Unit-typed fields are checked on every assignment, so e.g. a
+        # MetersPerSecond given for a mph field raises TypeError
+        unit = _SHOT_UNITS.get(name)
+        if unit is not None and value is not None:
+            value = unit(value)
+        super().__setattr__(name, value)
 
     @property
-    def ball_speed_ms(self) -> float:
+    def ball_speed_ms(self) -> MetersPerSecond:
         """Ball speed in meters per second."""
-        return self.ball_speed_mph * 0.44704
+        return self.ball_speed_mph.to_mps()
 
     @property
-    def club_speed_ms(self) -> Optional[float]:
+    def club_speed_ms(self) -> Optional[MetersPerSecond]:
         """Club speed in meters per second."""
         if self.club_speed_mph is None:
             return None
-        return self.club_speed_mph * 0.44704
+        return self.club_speed_mph.to_mps()
 
     @property
     def smash_factor(self) -> Optional[float]:
@@ -281,18 +399,19 @@ class Shot:
             return None
         return self.ball_speed_mph / self.club_speed_mph
 
+    @property
+    def energy(self) -> Dict[str, Optional[float]]:
+        """Ball and club kinetic energy and the percentage transferred (see energy)."""
+        return shot_energy(self.ball_speed_mph, self.club_speed_mph, self.club.value)
+
     @property
     def estimated_carry_yards(self) -> float:
-        """Estimated carry distance based on ball speed, club type, and launch angle."""
-        base = estimate_carry_distance(self.ball_speed_mph, self.club)
-        if self.launch_angle_vertical is not None:
-            return adjust_carry_for_launch_angle(
-                base,
-                self.launch_angle_vertical,
-                self.club,
-                self.launch_angle_confidence or 0.2,
-            )
-        return base
+        """Estimated carry distance from the carry model selected for this club."""
+        # pylint: disable=import-outside-toplevel,cyclic-import
+        from .ball_types import carry_scale
+        from .carry import get_carry_model
+
+        return get_carry_model(self.club).estimate(self) * carry_scale(self.ball_type)
 
     @property
     def estimated_carry_range(self) -> tuple:
@@ -335,6 +454,280 @@ class Shot:
             return "medium"
         return "low"
 
+    @property
+    def tempo_ratio(self) -> Optional[float]:
+        """Backswing time over downswing time, or None if tempo wasn't measured."""
+        if not self.tempo_backswing_sec or not self.tempo_downswing_sec:
+            return None
+        return self.tempo_backswing_sec / self.tempo_downswing_sec
+
+    @property
+    def tempo_on_target(self) -> Optional[bool]:
+        """Whether the tempo was within TEMPO_TOLERANCE of the target ratio."""
+        if self.tempo_ratio is None or not self.tempo_target_ratio:
+            return None
+        error = abs(self.tempo_ratio - self.tempo_target_ratio) / self.tempo_target_ratio
+        return error <= TEMPO_TOLERANCE
+
+
+# MonitorConfig fields that may be negative (left of / below boresight)
+_SIGNED_ANGLES = ("min_azimuth_deg", "max_azimuth_deg", "min_elevation_deg", "max_elevation_deg")
+
+# MonitorConfig fields that count something, so must be whole numbers >= 1
+_COUNTS = (
+    "min_readings_for_shot",
+    "clutter_min_repeats",
+    "takeaway_min_readings",
+    "pre_trigger_buffer_size",
+    "pre_trigger_decimation",
+)
+
+# Upper bounds beyond which a setting can only be a mistake
+MAX_BALL_SPEED_LIMIT_MPH = 250.0  # Long-drive records are around 230 mph
+MAX_CLUB_SPEED_LIMIT_MPH = 180.0  # Long-drive records are around 150 mph
+SMASH_FACTOR_LIMIT = 2.0  # Physics caps a driver near 1.56
+
+
+class ConfigError(ValueError):
+    """An invalid MonitorConfig setting; the message starts with its key."""
+
+    def __init__(self, key: str, message: str):
+        super().__init__(f"{key}: {message}")
+        self.key = key
+
+
+def _unknown_field_error(name: str, known: List[str]) -> ConfigError:
+    """Error for a setting MonitorConfig doesn't have, suggesting the closest names."""
+    close = difflib.get_close_matches(name, known, n=2)
+    hint = f" (did you mean {' or '.join(close)}?)" if close else ""
+    return ConfigError(name, f"unknown setting{hint}")
+
+
+@dataclass(frozen=True)
+class MonitorConfig:
+    """
+    Shot detection thresholds for LaunchMonitor.
+
+    Frozen so a config can be swapped atomically while the monitor is
+    running: readers take a reference once and never see a half-applied
+    update. Use with_updates() to derive a validated copy.
+    """
+
+    # Speed thresholds
+    min_club_speed_mph: float = 30  # Minimum club speed (allows short game)
+    max_club_speed_mph: float = 140  # Maximum realistic club speed
+    min_ball_speed_mph: float = 30  # Minimum ball speed (allows chips/pitches)
+    max_ball_speed_mph: float = 220  # Maximum realistic ball speed
+
+    # Signal filtering
+    min_magnitude: float = 20  # Minimum signal strength to accept reading
+    min_shot_magnitude: float = 100  # Minimum peak magnitude for valid shot (filters walking)
+
+    # Shot detection timing
+    shot_timeout_sec: float = 0.5  # Gap to consider shot complete
+    min_readings_for_shot: int = 1  # Lowered: high-speed ball readings are transient (1-2 blocks)
+    max_shot_duration_sec: float = 0.3  # Real shots complete within 300ms
+
+    # Club/ball separation parameters
+    club_ball_window_sec: float = 0.3  # Max time window for club before ball
+    club_speed_min_ratio: float = 0.50  # Club must be >= 50% of ball speed
+    club_speed_max_ratio: float = 0.85  # Club must be <= 85% of ball speed
+    smash_factor_min: float = 1.1  # Minimum valid smash factor
+    smash_factor_max: float = 1.7  # Maximum valid smash factor
+    # With the club known the smash window comes from its loft instead (see
+    # smash); allowance over that bound for radar error, 0 = off
+    club_smash_margin: float = 0.05
+    same_frame_ball_tolerance: float = 0.05  # Same-frame pairing: fastest object within 5% of ball
+
+    # How the reading stream is split into shots: "timeout" (a gap longer
+    # than shot_timeout_sec) or "change-point" (see segmentation)
+    segmentation: str = TIMEOUT
+    change_point_threshold: float = 4.0  # CUSUM sum that marks a new shot
+    change_point_drift: float = 0.5  # Novelty allowed per reading
+    change_point_min_gap_sec: float = 0.1  # Closer readings are one burst
+    change_point_max_gap_sec: float = 1.0  # A longer pause always ends the shot
+
+    # Ordered reading filters (see filters); None = the radar mode's default
+    filters: Optional[Tuple[str, ...]] = None
+    clutter_window_sec: float = 1.0  # How far back the clutter filter looks
+    clutter_tolerance_mph: float = 0.5  # Speeds this close count as repeats
+    clutter_min_repeats: int = 5  # Repeats that make a speed clutter
+    refractory_sec: float = 1.0  # Readings ignored after a shot by the refractory filter
+    min_range_m: float = 0.0  # Range gate of the range filter, for radars that report range
+    max_range_m: float = 0.0  # 0 = no far limit
+    # Launch corridor of the angle filter, for radars that report angles (degrees from boresight)
+    min_azimuth_deg: float = -90.0  # Left
+    max_azimuth_deg: float = 90.0  # Right
+    min_elevation_deg: float = -90.0  # Down
+    max_elevation_deg: float = 90.0  # Up
+
+    # Relaxed thresholds after a backswing is seen (see takeaway); 0 = off
+    takeaway_window_sec: float = 0.0  # How long thresholds stay relaxed after the takeaway
+    takeaway_threshold_scale: float = 0.5  # Magnitude thresholds are multiplied by this
+    takeaway_min_readings: int = 2  # Backswing readings in a row that make a takeaway
+
+    # Readings the filters reject are kept in a rolling buffer and the ones
+    # from just before a shot attached to it, so the approach can be analyzed
+    pre_trigger_sec: float = 0.2  # How far before the shot's first reading to attach; 0 = off
+    pre_trigger_buffer_size: int = 256  # Rejected readings kept
+    pre_trigger_decimation: int = 1  # Keep every Nth rejected reading
+
+    # Lost radar frames (see reading_loss): runs of up to this many are
+    # filled in, and ball speed taken back across them; 0 = off
+    max_filled_frames: int = 3
+
+    def __post_init__(self):
+        # JSON gives lists; keep the frozen config hashable
+        if isinstance(self.filters, list):
+            object.__setattr__(self, "filters", tuple(self.filters))
+
+    def validate(self):
+        """
+        Check that thresholds are internally consistent.
+
+        Raises:
+            ConfigError: Naming the first invalid setting found, its value
+                and the range it needs to be in
+        """
+        if self.segmentation not in SEGMENTATION_MODES:
+            raise ConfigError(
+                "segmentation",
+                f"{self.segmentation!r} is not a mode (use {' or '.join(SEGMENTATION_MODES)})",
+            )
+        if self.filters is not None:
+            if not isinstance(self.filters, tuple) or not all(
+                isinstance(name, str) for name in self.filters
+            ):
+                raise ConfigError("filters", "must be a list of filter names")
+            try:
+                FilterPipeline.from_names(self.filters)
+            except ValueError as e:
+                raise ConfigError("filters", str(e)) from None
+        for f in fields(self):
+            if f.name in ("segmentation", "filters"):
+                continue
+            value = getattr(self, f.name)
+            if isinstance(value, bool) or not isinstance(value, (int, float)):
+                raise ConfigError(f.name, f"must be a number, got {value!r} (default {f.default})")
+            if not math.isfinite(value):
+                raise ConfigError(f.name, f"must be a finite number, got {value}")
+            if f.name in _COUNTS and (value < 1 or int(value) != value):
+                raise ConfigError(f.name, f"must be a whole number >= 1, got {value:g}")
+            if value < 0 and f.name not in _SIGNED_ANGLES:
+                raise ConfigError(f.name, f"must be >= 0, got {value:g} (default {f.default})")
+
+        self._check_range("club_speed_mph", MAX_CLUB_SPEED_LIMIT_MPH, "mph")
+        self._check_range("ball_speed_mph", MAX_BALL_SPEED_LIMIT_MPH, "mph")
+        for name in ("shot_timeout_sec", "max_shot_duration_sec"):
+            if getattr(self, name) <= 0:
+                raise ConfigError(name, f"must be > 0 seconds, got {getattr(self, name):g}")
+        for name in ("club_speed_min_ratio", "club_speed_max_ratio"):
+            ratio = getattr(self, name)
+            if not 0 < ratio <= 1:
+                raise ConfigError(
+                    name, f"must be in (0, 1] (club speed over ball speed), got {ratio:g}"
+                )
+        if self.club_speed_min_ratio > self.club_speed_max_ratio:
+            raise ConfigError(
+                "club_speed_min_ratio",
+                f"{self.club_speed_min_ratio:g} is above club_speed_max_ratio "
+                f"({self.club_speed_max_ratio:g})",
+            )
+        if self.smash_factor_min < 1:
+            raise ConfigError(
+                "smash_factor_min",
+                "must be >= 1 (the ball leaves faster than the club), "
+                f"got {self.smash_factor_min:g}",
+            )
+        if self.smash_factor_max > SMASH_FACTOR_LIMIT:
+            raise ConfigError(
+                "smash_factor_max",
+                f"must be <= {SMASH_FACTOR_LIMIT:g} (even a driver stays under 1.6), "
+                f"got {self.smash_factor_max:g}",
+            )
+        if self.smash_factor_min > self.smash_factor_max:
+            raise ConfigError(
+                "smash_factor_min",
+                f"{self.smash_factor_min:g} is above smash_factor_max ({self.smash_factor_max:g})",
+            )
+        if self.change_point_min_gap_sec <= 0:
+            raise ConfigError(
+                "change_point_min_gap_sec", f"must be > 0, got {self.change_point_min_gap_sec:g}"
+            )
+        if self.change_point_min_gap_sec > self.change_point_max_gap_sec:
+            raise ConfigError(
+                "change_point_min_gap_sec",
+                f"{self.change_point_min_gap_sec:g} is above change_point_max_gap_sec "
+                f"({self.change_point_max_gap_sec:g})",
+            )
+        if self.max_range_m and self.min_range_m >= self.max_range_m:
+            raise ConfigError(
+                "min_range_m",
+                f"{self.min_range_m:g} must be below max_range_m ({self.max_range_m:g}), "
+                "or set max_range_m to 0 for no far limit",
+            )
+        for axis in ("azimuth", "elevation"):
+            for name in (f"min_{axis}_deg", f"max_{axis}_deg"):
+                if not -90 <= getattr(self, name) <= 90:
+                    raise ConfigError(
+                        name, f"must be between -90 and 90 degrees, got {getattr(self, name):g}"
+                    )
+            low, high = getattr(self, f"min_{axis}_deg"), getattr(self, f"max_{axis}_deg")
+            if low >= high:
+                raise ConfigError(
+                    f"min_{axis}_deg", f"{low:g} must be below max_{axis}_deg ({high:g})"
+                )
+        if not 0 < self.takeaway_threshold_scale <= 1:
+            raise ConfigError(
+                "takeaway_threshold_scale",
+                f"must be in (0, 1], got {self.takeaway_threshold_scale:g}",
+            )
+        if int(self.max_filled_frames) != self.max_filled_frames:
+            raise ConfigError(
+                "max_filled_frames", f"must be a whole number, got {self.max_filled_frames:g}"
+            )
+
+    def _check_range(self, metric: str, limit: float, unit: str):
+        """Check a min_/max_ speed pair is ordered and below limit."""
+        low, high = getattr(self, f"min_{metric}"), getattr(self, f"max_{metric}")
+        if high > limit:
+            raise ConfigError(f"max_{metric}", f"must be <= {limit:g} {unit}, got {high:g}")
+        if low > high:
+            raise ConfigError(
+                f"min_{metric}", f"{low:g} {unit} is above max_{metric} ({high:g} {unit})"
+            )
+
+    def with_updates(self, updates: Dict[str, Any]) -> "MonitorConfig":
+        """
+        Return a validated copy with some thresholds changed.
+
+        Args:
+            updates: Mapping of field name to new value
+
+        Returns:
+            New MonitorConfig (self is unchanged)
+
+        Raises:
+            ConfigError: If a key is unknown or the result is invalid
+        """
+        known = [f.name for f in fields(self)]
+        unknown = sorted(set(updates) - set(known))
+        if unknown:
+            raise _unknown_field_error(unknown[0], known)
+
+        config = replace(self, **updates)
+        config.validate()
+        return config
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Serialize to a plain dict (for JSON/session logs)."""
+        return asdict(self)
+
+    def fingerprint(self) -> str:
+        """Short hash of the thresholds, to tell at a glance whether two configs differ."""
+        body = json.dumps(self.to_dict(), sort_keys=True)
+        return hashlib.sha256(body.encode("utf-8")).hexdigest()[:12]
+
 
 class LaunchMonitor:
     """
@@ -349,9 +742,14 @@ class LaunchMonitor:
 
     Separation uses three criteria:
     1. Temporal: Club appears 0-300ms before ball
-    2. Speed ratio: Club is 50-85% of ball speed (smash factor 1.1-1.7)
+    2. Speed ratio: Club is 50-85% of ball speed (smash factor 1.1-1.7), or
+       within the smash window of the selected club's loft (see smash)
     3. Magnitude: Club head has larger RCS = stronger signal
 
+    When a single O4 frame holds both the ball and a slower target in the
+    club speed range (right at impact), that same-frame pair is preferred
+    over the temporal search, since both were measured at the same instant.
+
     Example:
         monitor = LaunchMonitor()
         monitor.start()
@@ -363,34 +761,17 @@ class LaunchMonitor:
             print(f"Smash Factor: {shot.smash_factor:.2f}")
     """
 
-    # Speed thresholds
-    MIN_CLUB_SPEED_MPH = 30  # Minimum club speed (allows short game)
-    MAX_CLUB_SPEED_MPH = 140  # Maximum realistic club speed
-    MIN_BALL_SPEED_MPH = 30  # Minimum ball speed (allows chips/pitches)
-    MAX_BALL_SPEED_MPH = 220  # Maximum realistic ball speed
-
-    # Signal filtering
-    MIN_MAGNITUDE = 20  # Minimum signal strength to accept reading
-    MIN_SHOT_MAGNITUDE = 100  # Minimum peak magnitude for valid shot (filters walking)
-
-    # Shot detection timing
-    SHOT_TIMEOUT_SEC = 0.5  # Gap to consider shot complete
-    MIN_READINGS_FOR_SHOT = 1  # Lowered: high-speed ball readings are transient (1-2 blocks)
-    MAX_SHOT_DURATION_SEC = 0.3  # Real shots complete within 300ms
-
-    # Club/ball separation parameters
-    CLUB_BALL_WINDOW_SEC = 0.3  # Max time window for club before ball
-    CLUB_SPEED_MIN_RATIO = 0.50  # Club must be >= 50% of ball speed
-    CLUB_SPEED_MAX_RATIO = 0.85  # Club must be <= 85% of ball speed
-    SMASH_FACTOR_MIN = 1.1  # Minimum valid smash factor
-    SMASH_FACTOR_MAX = 1.7  # Maximum valid smash factor
-
     def __init__(
         self,
         port: Optional[str] = None,
         detect_club_speed: bool = True,
         use_iq_streaming: bool = True,
         debug: bool = False,
+        config: Optional[MonitorConfig] = None,
+        mount_position: MountPosition = MountPosition.BEHIND,
+        skip_configure: bool = False,
+        classifier: Optional[ShotClassifier] = None,
+        radar: Optional[Any] = None,
     ):
         """
         Initialize launch monitor.
@@ -403,8 +784,19 @@ class LaunchMonitor:
                              with local FFT processing. If False, use radar's
                              internal speed processing.
             debug: If True, print verbose FFT/CFAR debug output.
+            config: Shot detection thresholds. Uses MonitorConfig defaults if None.
+            mount_position: Where the radar sits; decides which Doppler
+                           direction counts as ball flight.
+            skip_configure: If True, trust the radar's current settings and
+                           send no configuration commands on connect.
+            classifier: Learned shot/not-shot model that replaces the
+                       duration, magnitude and minimum speed rules.
+            radar: Radar driver to use instead of an OPS243 on port, e.g.
+                  an IWR6843Radar. Needs connect, configure_for_golf,
+                  start_streaming, stop_streaming, disconnect and get_info.
         """
-        self.radar = OPS243Radar(port=port)
+        self.radar = radar or OPS243Radar(port=port)
+        self._classifier = classifier
         self._running = False
         self._detect_club_speed = detect_club_speed
         self._use_iq_streaming = use_iq_streaming
@@ -416,17 +808,116 @@ class LaunchMonitor:
         self._shot_callback: Optional[Callable[[Shot], None]] = None
         self._live_callback: Optional[Callable[[SpeedReading], None]] = None
         self._current_club: ClubType = ClubType.DRIVER
+        self._club_timing: Dict[str, Dict[str, float]] = {}
+        self._idle_callbacks: List[Callable[[], None]] = []
+        self._idle_lock = threading.Lock()
         self._iq_detector: Optional[StreamingSpeedDetector] = None
+        self._config = config or MonitorConfig()
+        self._config.validate()
+        self._ball_directions = mount_position.ball_directions
+        self._skip_configure = skip_configure
+        self._discarded_readings = 0
+        self._segmenter: Optional[ChangePointSegmenter] = None
+        self._segmenter_config: Optional[MonitorConfig] = None
+        self._pipeline: Optional[FilterPipeline] = None
+        self._pipeline_config: Optional[MonitorConfig] = None
+        self._last_shot_time: Optional[float] = None
+        self._takeaway: Optional[TakeawayWindow] = None
+        self._takeaway_config: Optional[MonitorConfig] = None
+        self._relaxed: Optional[MonitorConfig] = None
+        self._shot_in_window = False
+        self._last_block_time: Optional[float] = None
+        self._pre_trigger: Optional[Deque[SpeedReading]] = None
+        self._rejected_count = 0
+
+    @property
+    def config(self) -> MonitorConfig:
+        """Active shot detection thresholds."""
+        return self._config
+
+    def set_config(self, config: MonitorConfig):
+        """
+        Replace the active thresholds while running.
+
+        The swap is a single reference assignment, so a reading or shot
+        being processed concurrently sees either the old or the new config,
+        never a mix.
+
+        Args:
+            config: New thresholds (validated before applying)
+
+        Raises:
+            ValueError: If the config is invalid
+        """
+        config.validate()
+        self._config = config
+
+    def set_club_timing(self, clubs: Dict[str, Dict[str, float]]):
+        """
+        Use per-club shot timing while each club is selected (see shot_timing).
+
+        Args:
+            clubs: shot_timeout_sec, max_shot_duration_sec and
+                club_ball_window_sec overrides by club value; they replace
+                the config's values
+        """
+        self._club_timing = {club: dict(values) for club, values in clubs.items()}
+
+    def _timing(self, config: MonitorConfig, name: str) -> float:
+        """A shot timing threshold for the selected club (its override, else config's)."""
+        overrides = self._club_timing.get(self._current_club.value, {})
+        return overrides.get(name, getattr(config, name))
+
+    @property
+    def is_idle(self) -> bool:
+        """True when no shot is being accumulated (safe moment to change settings)."""
+        return not self._current_readings
+
+    def call_when_idle(self, callback: Callable[[], None]):
+        """
+        Run callback between shots, so a shot never sees half of a change.
+
+        Runs it now if the monitor is idle, otherwise right after the shot
+        being accumulated has been processed (on the reading thread).
+
+        Args:
+            callback: Function to run, with no arguments
+        """
+        with self._idle_lock:
+            if not self.is_idle:
+                self._idle_callbacks.append(callback)
+                return
+        callback()
+
+    def _run_idle_callbacks(self):
+        """Run the callbacks call_when_idle deferred until the current shot was processed."""
+        with self._idle_lock:
+            callbacks, self._idle_callbacks = self._idle_callbacks, []
+        for callback in callbacks:
+            callback()
 
     def connect(self) -> bool:
         """
         Connect to radar and configure for golf.
 
+        I/Q streaming falls back to the radar's internal processing on a
+        model without raw I/Q output (e.g. OPS241-A, or a radar other than
+        OmniPreSense).
+
         Returns:
             True if successful
         """
         self.radar.connect()
-        if self._use_iq_streaming:
+        capabilities = getattr(self.radar, "capabilities", None)
+        if self._use_iq_streaming and not (capabilities and capabilities.iq_output):
+            print(
+                f"[RADAR] {self.radar.product} has no raw I/Q output - "
+                "using the radar's internal processing"
+            )
+            self._use_iq_streaming = False
+        if self._skip_configure:
+            print("[RADAR] Skipping configuration - trusting current radar settings")
+        elif self._use_iq_streaming:
             self.radar.configure_for_iq_streaming()
         else:
             self.radar.configure_for_golf()
@@ -471,13 +962,53 @@ class LaunchMonitor:
                 config=None,  # Use CFAR-tuned defaults
                 debug=self._debug,
             )
+            self._last_block_time = time.monotonic()
             self.radar.start_iq_streaming(
-                callback=self._iq_detector.on_block, error_callback=self._on_iq_error
+                callback=self._on_iq_block, error_callback=self._on_iq_error
             )
         else:
             # Use radar's internal speed processing
             self.radar.start_streaming(self._on_reading)
 
+    def _on_iq_block(self, block):
+        """Note the time and hand an I/Q block to the detector."""
+        self._last_block_time = time.monotonic()
+        detector = self._iq_detector
+        if detector:
+            detector.on_block(block)
+
+    @property
+    def radar_data_age_sec(self) -> Optional[float]:
+        """
+        Seconds since the radar last sent data (None unless streaming I/Q).
+
+        An I/Q stream never pauses on its own, so a growing age means the
+        radar or its serial link is wedged. The radar's own processing only
+        reports when something moves, so its silence says nothing.
+        """
+        if not self._running or not self._use_iq_streaming or self._last_block_time is None:
+            return None
+        return time.monotonic() - self._last_block_time
+
+    def debug_state(self) -> Dict[str, Any]:
+        """Where shot detection is right now (for crash reports and GET /api/state)."""
+        now = time.monotonic()
+        return {
+            "running": self._running,
+            "iq_streaming": self._use_iq_streaming,
+            "buffered_readings": len(self._current_readings),
+            "shot_window_sec": now - self._shot_start_time if self._current_readings else None,
+            "since_last_reading_sec": (
+                now - self._last_reading_time if self._last_reading_time else None
+            ),
+            "since_last_shot_sec": now - self._last_shot_time if self._last_shot_time else None,
+            "takeaway_window_open": bool(self._takeaway and self._takeaway.is_open(now)),
+            "shots": len(self._shots),
+            "club": self._current_club.value,
+            "discarded_readings": self.discarded_readings,
+            "radar_data_age_sec": self.radar_data_age_sec,
+        }
+
     def _on_iq_error(self, error: str):
         """Handle errors from I/Q streaming."""
         print(f"[IQ ERROR] {error}")
@@ -489,82 +1020,290 @@ class LaunchMonitor:
         # Process any pending readings
         if self._current_readings:
             self._process_shot()
+            self._run_idle_callbacks()
         # Clean up I/Q detector
         self._iq_detector = None
 
     def _on_reading(self, reading: SpeedReading):
         """Process incoming speed readings."""
-        now = time.time()
+        now = time.monotonic()
         logger = get_session_logger()
 
+        if not self._accept(reading):
+            return
+        config = self._thresholds(reading, now)
+
         # Call live callback if set
         if self._live_callback:
             self._live_callback(reading)
 
-        # In I/Q streaming mode, CFAR already filters by speed, SNR, and signal quality
-        # We only need to filter by direction here (outbound = moving away from radar)
-        if self._use_iq_streaming:
-            if reading.direction != Direction.OUTBOUND:
-                return
-        else:
-            # Legacy mode: apply old filters for radar's internal processing
-            if self._detect_club_speed:
-                min_speed = self.MIN_CLUB_SPEED_MPH
-            else:
-                min_speed = self.MIN_BALL_SPEED_MPH
-
-            if not min_speed <= reading.speed <= self.MAX_BALL_SPEED_MPH:
-                print(
-                    f"[FILTER] Speed {reading.speed:.1f} outside range {min_speed}-{self.MAX_BALL_SPEED_MPH}"
-                )
-                return
-
-            if reading.direction != Direction.OUTBOUND:
-                print(f"[FILTER] Direction {reading.direction.value} is not outbound")
-                return
-
-            if reading.magnitude is not None and reading.magnitude < self.MIN_MAGNITUDE:
-                print(
-                    f"[FILTER] Magnitude {reading.magnitude:.1f} below minimum {self.MIN_MAGNITUDE}"
-                )
-                return
+        reason = self._filter_pipeline().check(
+            reading,
+            FilterContext(
+                config=config,
+                ball_directions=self._ball_directions,
+                min_speed_mph=(
+                    config.min_club_speed_mph
+                    if self._detect_club_speed
+                    else config.min_ball_speed_mph
+                ),
+                now=now,
+                last_shot_time=self._last_shot_time,
+            ),
+        )
+        if reason:
+            self._buffer_rejected(reading)
+            # In I/Q streaming mode every backswing is filtered; don't print those
+            if not self._use_iq_streaming:
+                print(f"[FILTER] {reason}")
+            return
 
         # Show timing info for debugging
         time_gap = (now - self._last_reading_time) if self._last_reading_time else 0
+        magnitude = "-" if reading.magnitude is None else f"{reading.magnitude:.3f}"
         print(
-            f"[ACCEPTED] {reading.speed:.1f} mph {reading.direction.value} mag={reading.magnitude:.3f} "
+            f"[ACCEPTED] {reading.speed:.1f} mph {reading.direction.value} mag={magnitude} "
             f"- buffered: {len(self._current_readings)}, gap: {time_gap * 1000:.0f}ms"
         )
         if logger:
             logger.log_accepted_reading(reading)
 
         # Check if this is part of current shot or new shot
-        if self._current_readings and time_gap > self.SHOT_TIMEOUT_SEC:
+        timeout = self._timing(config, "shot_timeout_sec")
+        if config.segmentation == CHANGE_POINT:
+            self._split_at_change_point(reading, now)
+        elif self._current_readings and time_gap > timeout:
             # Previous shot complete, process it
             print(
-                f"[TIMEOUT] {time_gap * 1000:.0f}ms gap > {self.SHOT_TIMEOUT_SEC * 1000:.0f}ms - processing {len(self._current_readings)} readings"
+                f"[TIMEOUT] {time_gap * 1000:.0f}ms gap > {timeout * 1000:.0f}ms - processing {len(self._current_readings)} readings"
             )
             self._process_shot()
+            self._run_idle_callbacks()
 
         # Track shot start time
         if not self._current_readings:
             self._shot_start_time = now
+            self._shot_in_window = config is self._relaxed and self._takeaway.is_open(now)
             print(f"[SHOT START] Beginning new shot window")
 
         # Add to current readings
         self._current_readings.append(reading)
         self._last_reading_time = now
 
+    def _buffer_rejected(self, reading: SpeedReading):
+        """Keep a filtered-out reading in the pre-trigger buffer (every Nth, if decimated)."""
+        config = self._config
+        if config.pre_trigger_sec <= 0:
+            return
+        size = int(config.pre_trigger_buffer_size)
+        if self._pre_trigger is None or self._pre_trigger.maxlen != size:
+            self._pre_trigger = deque(self._pre_trigger or (), maxlen=size)
+        self._rejected_count += 1
+        if self._rejected_count % int(config.pre_trigger_decimation) == 0:
+            self._pre_trigger.append(reading)
+
+    def _pre_trigger_readings(self, start: float, end: float) -> List[SpeedReading]:
+        """
+        Buffered rejected readings from pre_trigger_sec before start up to end.
+
+        Args:
+            start: The shot's first reading's time
+            end: The shot's last reading's time
+        """
+        if not self._pre_trigger or self._config.pre_trigger_sec <= 0:
+            return []
+        since = start - self._config.pre_trigger_sec
+        return [r for r in self._pre_trigger if since <= _by_time(r) <= end]
+
+    def _thresholds(self, reading: SpeedReading, now: float) -> MonitorConfig:
+        """
+        Feed the takeaway window and return the thresholds for this reading.
+
+        Returns:
+            The active config, or its relaxed copy while the window is open
+        """
+        config = self._config
+        if config.takeaway_window_sec <= 0:
+            return config
+        if self._takeaway is None or self._takeaway_config is not config:
+            self._takeaway = TakeawayWindow(
+                backswing_direction(self._ball_directions),
+                window_sec=config.takeaway_window_sec,
+                min_readings=int(config.takeaway_min_readings),
+            )
+            self._takeaway_config = config
+            self._relaxed = relaxed_config(config)
+        if self._takeaway.update(reading, now):
+            print(
+                f"[TAKEAWAY] Backswing seen - thresholds relaxed for "
+                f"{config.takeaway_window_sec:.1f}s"
+            )
+        return self._relaxed if self._takeaway.is_open(now) else config
+
+    def _filter_pipeline(self) -> FilterPipeline:
+        """
+        The reading filters for the active config, rebuilt when it changes.
+
+        In I/Q streaming mode, CFAR already filters by speed, SNR and signal
+        quality, so by default only direction (outbound = moving away from a
+        radar behind the ball; see MountPosition for other placements) and
+        the range gate are checked. With the radar's internal processing,
+        speed band and magnitude are checked too.
+        """
+        config = self._config
+        if self._pipeline is None or self._pipeline_config is not config:
+            names = config.filters
+            if names is None:
+                names = IQ_STREAMING_FILTERS if self._use_iq_streaming else LEGACY_FILTERS
+            self._pipeline = FilterPipeline.from_names(names)
+            self._pipeline_config = config
+        return self._pipeline
+
+    def set_filter_pipeline(self, pipeline: FilterPipeline):
+        """
+        Use a pipeline of filter instances instead of the config's names.
+
+        Kept until the config is next replaced.
+
+        Args:
+            pipeline: Filters to run on each reading, in order
+        """
+        self._pipeline = pipeline
+        self._pipeline_config = self._config
+
+    def _split_at_change_point(self, reading: SpeedReading, now: float):
+        """Process the shot in progress if this reading starts a new one."""
+        config = self._config
+        if self._segmenter is None or self._segmenter_config is not config:
+            self._segmenter = ChangePointSegmenter(
+                threshold=config.change_point_threshold,
+                drift=config.change_point_drift,
+                min_gap_sec=config.change_point_min_gap_sec,
+                max_gap_sec=config.change_point_max_gap_sec,
+            )
+            self._segmenter_config = config
+        if not self._current_readings:
+            self._segmenter.reset()
+
+        completed = self._segmenter.update(reading, now)
+        if not completed:
+            return
+        pending = self._current_readings[completed:]
+        self._current_readings = self._current_readings[:completed]
+        print(
+            f"[CHANGE POINT] New shot after {completed} readings "
+            f"({len(pending)} carried over) - processing"
+        )
+        self._process_shot()
+        self._run_idle_callbacks()
+        self._current_readings = pending
+
+    def _accept(self, reading: SpeedReading) -> bool:
+        """
+        Check a reading for NaN or infinite values, dropping bad frame objects.
+
+        Returns:
+            False (and counts the reading as discarded) if the reading itself is bad
+        """
+        if not _is_valid_reading(reading):
+            self._discarded_readings += 1
+            print(f"[FILTER] Discarded degenerate reading: {reading.speed} mph")
+            return False
+        if reading.frame_objects:
+            valid = [o for o in reading.frame_objects if _is_valid_reading(o)]
+            self._discarded_readings += len(reading.frame_objects) - len(valid)
+            reading.frame_objects = valid or None
+        return True
+
+    def _smash_window(self, config: MonitorConfig) -> Tuple[float, float]:
+        """Smash factors accepted for the selected club (the global window for "unknown")."""
+        window = smash_window(self._current_club.value, config.club_smash_margin)
+        return window or (config.smash_factor_min, config.smash_factor_max)
+
+    def _club_speed_range(self, config: MonitorConfig, ball_speed: float) -> Tuple[float, float]:
+        """Club speeds that can go with a ball speed: from the club's smash window, if it has one."""
+        window = smash_window(self._current_club.value, config.club_smash_margin)
+        if window:
+            low, high = ball_speed / window[1], ball_speed / window[0]
+        else:
+            low = ball_speed * config.club_speed_min_ratio
+            high = ball_speed * config.club_speed_max_ratio
+        return max(config.min_club_speed_mph, low), min(config.max_club_speed_mph, high)
+
+    def _find_same_frame_club(
+        self,
+        readings: List[SpeedReading],
+        ball_speed: float,
+        ball_time: float,
+        measured_speed: Optional[float] = None,
+    ) -> Optional[SpeedReading]:
+        """
+        Find club head reading in a multi-object frame that also holds the ball.
+
+        Args:
+            readings: Readings in the shot window (may carry frame_objects)
+            ball_speed: Ball speed in mph, as it will be reported
+            ball_time: Timestamp of ball reading (later frames are ignored)
+            measured_speed: Fastest ball reading, when ball_speed was
+                recovered from lost frames (default: ball_speed)
+
+        Returns:
+            Club SpeedReading if a same-frame pair was found, None otherwise
+        """
+        config = self._config
+        measured_speed = measured_speed or ball_speed
+        smash_min, smash_max = self._smash_window(config)
+        club_speed_min, club_speed_max = self._club_speed_range(config, ball_speed)
+
+        best: Optional[SpeedReading] = None
+        for r in readings:
+            if not r.frame_objects or _by_time(r) > ball_time:
+                continue
+
+            frame = [o for o in [r, *r.frame_objects] if o.direction in self._ball_directions]
+            if len(frame) < 2:
+                continue
+
+            # Only frames where the ball itself is visible
+            fastest = max(frame, key=_by_speed)
+            if fastest.speed < measured_speed * (1 - config.same_frame_ball_tolerance):
+                continue
+
+            for candidate in frame:
+                if candidate is fastest:
+                    continue
+                if not club_speed_min <= candidate.speed <= club_speed_max:
+                    continue
+                # Checked against the shot's ball speed, which is what gets reported
+                smash = ball_speed / candidate.speed
+                if not smash_min <= smash <= smash_max:
+                    continue
+                if best is None or _by_magnitude(candidate) > _by_magnitude(best):
+                    best = candidate
+
+        if best:
+            print(
+                f"[CLUB DETECTED] {best.speed:.1f} mph from same frame as ball "
+                f"(mag={best.magnitude})"
+            )
+        return best
+
     def _find_club_speed(
-        self, readings: List[SpeedReading], ball_speed: float, ball_time: float
+        self,
+        readings: List[SpeedReading],
+        ball_speed: float,
+        ball_time: float,
+        measured_speed: Optional[float] = None,
     ) -> Optional[SpeedReading]:
         """
         Find club head reading using temporal and magnitude analysis.
 
         Args:
             readings: Sorted list of readings (by timestamp)
-            ball_speed: Detected ball speed in mph
+            ball_speed: Ball speed in mph, as it will be reported
             ball_time: Timestamp of ball reading
+            measured_speed: Fastest ball reading, when ball_speed was
+                recovered from lost frames (default: ball_speed)
 
         Returns:
             Club SpeedReading if found, None otherwise
@@ -572,21 +1311,27 @@ class LaunchMonitor:
         if len(readings) < 2:
             return None
 
-        # Speed range: club should be 50-85% of ball speed
-        club_speed_min = max(self.MIN_CLUB_SPEED_MPH, ball_speed * self.CLUB_SPEED_MIN_RATIO)
-        club_speed_max = min(self.MAX_CLUB_SPEED_MPH, ball_speed * self.CLUB_SPEED_MAX_RATIO)
+        config = self._config
+        measured_speed = measured_speed or ball_speed
+        smash_min, smash_max = self._smash_window(config)
+
+        # Speed range: club should be 50-85% of ball speed (or the club's smash window)
+        club_speed_min, club_speed_max = self._club_speed_range(config, ball_speed)
 
         # Find candidate club readings (before ball, in speed range)
         club_candidates = []
         for r in readings:
-            r_time = r.timestamp or 0
+            r_time = _by_time(r)
 
-            # Must be before the ball reading
-            if r_time >= ball_time:
-                continue
+            # Must be before the ball reading. Readings that arrived together
+            # (a USB burst) can share its timestamp; they're in arrival order.
+            if r_time > ball_time:
+                break
+            if r_time == ball_time and r.speed >= measured_speed:
+                break
 
             # Must be within time window (not too early)
-            if ball_time - r_time > self.CLUB_BALL_WINDOW_SEC:
+            if ball_time - r_time > self._timing(config, "club_ball_window_sec"):
                 continue
 
             # Must be in realistic club speed range
@@ -594,7 +1339,7 @@ class LaunchMonitor:
                 continue
 
             # Must be less than ball speed
-            if r.speed >= ball_speed:
+            if r.speed >= measured_speed:
                 continue
 
             club_candidates.append(r)
@@ -610,27 +1355,28 @@ class LaunchMonitor:
         candidates_with_mag = [c for c in club_candidates if c.magnitude]
 
         if candidates_with_mag:
-            club_reading = max(candidates_with_mag, key=lambda r: r.magnitude)
+            club_reading = max(candidates_with_mag, key=_by_magnitude)
             print(
                 f"[CLUB DETECTED] {club_reading.speed:.1f} mph selected by magnitude "
                 f"(mag={club_reading.magnitude})"
             )
         else:
             # No magnitude data - use reading closest in time to ball
-            club_reading = max(club_candidates, key=lambda r: r.timestamp or 0)
+            club_reading = max(club_candidates, key=_by_time)
             print(f"[CLUB DETECTED] {club_reading.speed:.1f} mph selected by timing")
 
         # Validate smash factor
         smash = ball_speed / club_reading.speed
-        if not (self.SMASH_FACTOR_MIN <= smash <= self.SMASH_FACTOR_MAX):
+        if not smash_min <= smash <= smash_max:
             print(
                 f"[CLUB REJECTED] Smash factor {smash:.2f} outside range "
-                f"{self.SMASH_FACTOR_MIN}-{self.SMASH_FACTOR_MAX}"
+                f"{smash_min:.2f}-{smash_max:.2f}"
             )
             return None
 
         return club_reading
 
+    @traced("shot", "detect")
     def _process_shot(self):
         """
         Process accumulated readings into a shot.
@@ -644,35 +1390,65 @@ class LaunchMonitor:
         - Peak magnitude above threshold (strong radar return)
         - Ball speed above minimum for golf shots
         """
-        if len(self._current_readings) < self.MIN_READINGS_FOR_SHOT:
+        config = self._config
+        if self._shot_in_window and self._relaxed is not None:
+            # Started inside a takeaway window; judged by the thresholds it started with
+            if self._takeaway_config is config:
+                config = self._relaxed
+            self._shot_in_window = False
+            self._takeaway.close()
+
+        # Readings with a NaN or infinite value can't be ordered
+        self._current_readings = [r for r in self._current_readings if self._accept(r)]
+
+        if len(self._current_readings) < config.min_readings_for_shot:
             speeds = [f"{r.speed:.1f}" for r in self._current_readings]
             print(
                 f"[REJECTED] Only {len(self._current_readings)} readings "
-                f"(need {self.MIN_READINGS_FOR_SHOT}): {', '.join(speeds)} mph"
+                f"(need {config.min_readings_for_shot}): {', '.join(speeds)} mph"
             )
             self._current_readings = []
             return
 
         # Sort readings by timestamp for temporal analysis
-        sorted_readings = sorted(self._current_readings, key=lambda r: r.timestamp or 0)
+        sorted_readings = sorted(self._current_readings, key=_by_time)
+
+        if self._classifier is not None:
+            probability = self._classifier.classify(sorted_readings)
+            if probability < self._classifier.threshold:
+                print(
+                    f"[REJECTED] Classifier: p(shot)={probability:.2f} "
+                    f"below {self._classifier.threshold:.2f}"
+                )
+                self._current_readings = []
+                return
 
         # Check shot duration - real shots happen fast (<300ms)
-        first_time = sorted_readings[0].timestamp or 0
-        last_time = sorted_readings[-1].timestamp or 0
+        first_time = _by_time(sorted_readings[0])
+        last_time = _by_time(sorted_readings[-1])
         shot_duration = last_time - first_time
 
-        if shot_duration > self.MAX_SHOT_DURATION_SEC:
+        max_duration = self._timing(config, "max_shot_duration_sec")
+        if self._classifier is None and shot_duration > max_duration:
             print(
                 f"[REJECTED] Shot duration {shot_duration * 1000:.0f}ms exceeds "
-                f"max {self.MAX_SHOT_DURATION_SEC * 1000:.0f}ms (likely not a golf shot)"
+                f"max {max_duration * 1000:.0f}ms (likely not a golf shot)"
             )
             self._current_readings = []
             return
 
-        # Find ball: peak speed reading
-        ball_reading = max(sorted_readings, key=lambda r: r.speed)
+        # Find ball: peak speed reading. At impact the club head is usually the
+        # strongest object in an O4 frame, so the ball may only be a secondary one.
+        ball_candidates = list(sorted_readings)
+        for r in sorted_readings:
+            ball_candidates.extend(
+                o
+                for o in r.frame_objects or []
+                if o.direction in self._ball_directions and o.speed <= config.max_ball_speed_mph
+            )
+        ball_reading = max(ball_candidates, key=_by_speed)
         ball_speed = ball_reading.speed
-        ball_time = ball_reading.timestamp or 0
+        ball_time = _by_time(ball_reading)
 
         # Get peak magnitude
         magnitudes = [r.magnitude for r in sorted_readings if r.magnitude]
@@ -680,28 +1456,48 @@ class LaunchMonitor:
 
         # In I/Q streaming mode, CFAR already validated signal quality - skip magnitude check
         # In legacy mode, validate peak magnitude for strong radar returns
-        if not self._use_iq_streaming:
-            if peak_mag is not None and peak_mag < self.MIN_SHOT_MAGNITUDE:
+        # (a classifier, if set, has already decided)
+        if not self._use_iq_streaming and self._classifier is None:
+            if peak_mag is not None and peak_mag < config.min_shot_magnitude:
                 print(
                     f"[REJECTED] Peak magnitude {peak_mag:.0f} below minimum "
-                    f"{self.MIN_SHOT_MAGNITUDE} (weak signal, likely not a golf shot)"
+                    f"{config.min_shot_magnitude} (weak signal, likely not a golf shot)"
                 )
                 self._current_readings = []
                 return
 
             # Validate ball speed - must be a real golf shot speed
-            if ball_speed < self.MIN_BALL_SPEED_MPH:
+            if ball_speed < config.min_ball_speed_mph:
                 print(
                     f"[REJECTED] Ball speed {ball_speed:.1f} mph below minimum "
-                    f"{self.MIN_BALL_SPEED_MPH} mph (too slow for golf shot)"
+                    f"{config.min_ball_speed_mph} mph (too slow for golf shot)"
                 )
                 self._current_readings = []
                 return
 
-        # Find club speed
+        loss, hidden_speed = analyze_loss(
+            sorted_readings, ball_speed, int(config.max_filled_frames)
+        )
+        ball_speed_reading = None
+        if hidden_speed is not None:
+            print(
+                f"[LOST FRAMES] {loss.lost_leading} ball frame(s) lost before "
+                f"{ball_speed:.1f} mph - ball speed {hidden_speed:.1f} mph"
+            )
+            ball_speed_reading = ball_speed
+            ball_speed = hidden_speed
+
+        # Find club speed, paired against the ball speed that gets reported
         club_speed = None
         if self._detect_club_speed:
-            club_reading = self._find_club_speed(sorted_readings, ball_speed, ball_time)
+            measured = ball_speed_reading
+            club_reading = self._find_same_frame_club(
+                sorted_readings, ball_speed, ball_time, measured
+            )
+            if not club_reading:
+                club_reading = self._find_club_speed(
+                    sorted_readings, ball_speed, ball_time, measured
+                )
             if club_reading:
                 club_speed = club_reading.speed
 
@@ -717,9 +1513,19 @@ class LaunchMonitor:
             peak_magnitude=peak_mag,
             readings=self._current_readings.copy(),
             club=self._current_club,
+            lost_frames=loss.lost_frames if loss.spacing_ms is not None else None,
+            max_reading_gap_ms=loss.max_gap_ms,
+            ball_speed_reading_mph=ball_speed_reading,
         )
 
+        if club_speed:
+            impact = estimate_impact(sorted_readings, ball_speed, club_speed)
+            if impact:
+                shot.impact_time = impact.impact_time
+                shot.impact_offset_ms = impact.offset_ms
+
         self._shots.append(shot)
+        self._last_shot_time = time.monotonic()
 
         if club_speed:
             print(
@@ -730,15 +1536,10 @@ class LaunchMonitor:
             print(f"[SHOT CREATED] Ball: {ball_speed:.1f} mph (club not detected)")
 
         # Attach serialized readings for session logging
-        shot.readings_data = [
-            {
-                "speed": r.speed,
-                "direction": r.direction.value,
-                "magnitude": r.magnitude,
-                "timestamp": r.timestamp,
-            }
-            for r in self._current_readings
-        ]
+        shot.readings_data = [_reading_entry(r) for r in self._current_readings]
+        shot.pre_trigger_readings = self._pre_trigger_readings(first_time, last_time)
+        if shot.pre_trigger_readings:
+            shot.pre_trigger_data = [_reading_entry(r) for r in shot.pre_trigger_readings]
 
         # Log I/Q blocks for this shot (for post-session analysis)
         logger = get_session_logger()
@@ -753,6 +1554,23 @@ class LaunchMonitor:
         # Clear for next shot
         self._current_readings = []
 
+    def analyze_readings(self, readings: List[SpeedReading]) -> Optional[Shot]:
+        """
+        Run shot detection over a recorded set of readings.
+
+        Used for offline reprocessing; no radar connection is needed.
+
+        Args:
+            readings: Readings from one shot window
+
+        Returns:
+            The detected shot, or None if the readings are rejected
+        """
+        shot_count = len(self._shots)
+        self._current_readings = list(readings)
+        self._process_shot()
+        return self._shots[-1] if len(self._shots) > shot_count else None
+
     def wait_for_shot(self, timeout: float = 60) -> Optional[Shot]:
         """
         Wait for a shot to be detected.
@@ -771,14 +1589,20 @@ class LaunchMonitor:
         original_callback = self._shot_callback
         self._shot_callback = on_shot
 
-        start = time.time()
-        while not shot_detected and (time.time() - start) < timeout:
+        start = time.monotonic()
+        while not shot_detected and (time.monotonic() - start) < timeout:
             time.sleep(0.1)
 
         self._shot_callback = original_callback
 
         return shot_detected[0] if shot_detected else None
 
+    @property
+    def discarded_readings(self) -> int:
+        """Readings and speed samples dropped for NaN or infinite values."""
+        radar = getattr(self, "radar", None)
+        return self._discarded_readings + getattr(radar, "discarded_samples", 0)
+
     def get_session_stats(self) -> dict:
         """
         Get statistics for the current session.
@@ -795,11 +1619,18 @@ class LaunchMonitor:
                 "avg_club_speed": None,
                 "avg_smash_factor": None,
                 "avg_carry_est": 0,
+                **energy_stats([]),
+                **impact_stats([]),
+                "avg_tempo_ratio": None,
+                "tempo_on_target_pct": None,
+                "discarded_readings": self.discarded_readings,
             }
 
         ball_speeds = [s.ball_speed_mph for s in self._shots]
         club_speeds = [s.club_speed_mph for s in self._shots if s.club_speed_mph]
         smash_factors = [s.smash_factor for s in self._shots if s.smash_factor]
+        tempo_ratios = [s.tempo_ratio for s in self._shots if s.tempo_ratio]
+        on_target = [s.tempo_on_target for s in self._shots if s.tempo_on_target is not None]
 
         return {
             "shot_count": len(self._shots),
@@ -810,20 +1641,47 @@ class LaunchMonitor:
             "avg_club_speed": statistics.mean(club_speeds) if club_speeds else None,
             "avg_smash_factor": statistics.mean(smash_factors) if smash_factors else None,
             "avg_carry_est": statistics.mean([s.estimated_carry_yards for s in self._shots]),
+            **energy_stats(self._shots),
+            **impact_stats(self._shots),
+            "avg_tempo_ratio": statistics.mean(tempo_ratios) if tempo_ratios else None,
+            "tempo_on_target_pct": (
+                100 * sum(on_target) / len(on_target) if on_target else None
+            ),
+            "discarded_readings": self.discarded_readings,
         }
 
     def get_shots(self) -> List[Shot]:
         """Get all detected shots."""
         return self._shots.copy()
 
+    def discard_last_shot(self) -> Optional[Shot]:
+        """Remove the most recent shot (a mulligan); None if there are no shots."""
+        return self._shots.pop() if self._shots else None
+
+    def remove_shot(self, shot: Shot) -> bool:
+        """Remove a shot deleted after the fact; False if it isn't in the session."""
+        for i, recorded in enumerate(self._shots):
+            if recorded is shot:
+                del self._shots[i]
+                return True
+        return False
+
     def clear_session(self):
         """Clear all recorded shots."""
         self._shots = []
 
+    def restore_shots(self, shots: List[Shot]):
+        """Put back shots from before a restart (e.g. from a resumed session log)."""
+        self._shots = list(shots) + self._shots
+
     def set_club(self, club: ClubType):
         """Set the current club for future shots."""
         self._current_club = club
 
+    def get_club(self) -> ClubType:
+        """Get the club used for future shots."""
+        return self._current_club
+
     def __enter__(self):
         """Context manager entry."""
         self.connect()
@@ -840,7 +1698,7 @@ def main():
     import argparse
 
     parser = argparse.ArgumentParser(description="Golf Launch Monitor")
-    parser.add_argument("--port", "-p", help="Serial port (auto-detect if not specified)")
+    parser.add_argument("--port", "-p", help="Serial port or tcp:// bridge URL (auto-detect if not specified)")
     parser.add_argument("--live", "-l", action="store_true", help="Show live readings")
     parser.add_argument("--info", "-i", action="store_true", help="Show radar info and exit")
     parser.add_argument(
@@ -848,8 +1706,31 @@ def main():
         action="store_true",
         help="Disable I/Q streaming mode (use radar's internal processing)",
     )
+    parser.add_argument(
+        "--skip-configure",
+        action="store_true",
+        help="Don't send configuration commands (radar already set up, e.g. kiosk reboot)",
+    )
+    parser.add_argument(
+        "--radar-output",
+        choices=OUTPUT_FORMATS,
+        default="json",
+        help="Speed report format with --no-iq-streaming: json (default) or compact "
+        "(fewer bytes per report; falls back to json if lines arrive damaged)",
+    )
+    parser.add_argument(
+        "--shot-card",
+        default="full",
+        help="What to print for each shot: full (default), compact, or a template like "
+        "\"Ball {ball_speed:.1f} mph | Carry {carry@m:.0f} m\" (see openflight.shot_card)",
+    )
     args = parser.parse_args()
 
+    try:
+        card = ShotCard.load(args.shot_card)
+    except ValueError as e:
+        parser.error(f"--shot-card: {e}")
+
     use_iq = not args.no_iq_streaming
 
     print("=" * 50)
@@ -865,9 +1746,13 @@ def main():
     print()
 
     try:
-        with LaunchMonitor(port=args.port, use_iq_streaming=use_iq) as monitor:
+        with LaunchMonitor(
+            radar=OPS243Radar(port=args.port, output_format=args.radar_output),
+            use_iq_streaming=use_iq,
+            skip_configure=args.skip_configure,
+        ) as monitor:
             info = monitor.get_radar_info()
-            print(f"Connected to: {info.get('Product', 'OPS243')}")
+            print(f"Connected to: {monitor.radar.product or 'OPS243'}")
             print(f"Firmware: {info.get('Version', 'unknown')}")
             print()
 
@@ -882,18 +1767,7 @@ def main():
             print()
 
             def on_shot(shot):
-                carry_low, carry_high = shot.estimated_carry_range
-                print("-" * 40)
-                if shot.club_speed_mph:
-                    print(f"  Club Speed:   {shot.club_speed_mph:.1f} mph")
-                print(f"  Ball Speed:   {shot.ball_speed_mph:.1f} mph")
-                if shot.smash_factor:
-                    print(f"  Smash Factor: {shot.smash_factor:.2f}")
-                print(f"  Est. Carry:   {shot.estimated_carry_yards:.0f} yards")
-                print(f"  Range:        {carry_low:.0f}-{carry_high:.0f} yards")
-                if shot.peak_magnitude:
-                    print(f"  Signal:       {shot.peak_magnitude:.0f}")
-                print("-" * 40)
+                print(card.render(shot))
                 print()
 
             def on_live(reading):
@@ -917,6 +1791,12 @@ def main():
                     print(f"  Max Ball Speed: {stats['max_ball_speed']:.1f} mph")
                     if stats["avg_smash_factor"]:
                         print(f"  Avg Smash:      {stats['avg_smash_factor']:.2f}")
+                    transfer = stats["avg_energy_transfer_pct"]
+                    if transfer:
+                        print(f"  Avg Energy:     {transfer:.0f}% transferred")
+                    spread = stats["impact_timing_sd_ms"]
+                    if spread is not None:
+                        print(f"  Impact Timing:  ±{spread:.1f} ms")
                     print(f"  Avg Est. Carry: {stats['avg_carry_est']:.0f} yards")
                 print("\nGoodbye!")
 
@@ -0,0 +1,311 @@
+"""
+Simulated OPS243 radar driven by the ball flight model.
+
+MockRadar stands in for OPS243Radar: given target launch conditions it
+flies the ball with the same physics the carry estimate uses
+(PhysicsCarryModel), and reports what a radar at the configured mounting
+position would see:
+
+- the club head closing on the ball before impact, then the ball
+- only the radial speed component (velocity projected onto the line of
+  sight from the radar), so off-line mounting and launch angle read low
+  just as they do on real hardware
+- signal strength falling off with distance, readings ending once the ball
+  is out of range, and random frame dropout plus speed noise
+- optionally, the timing of the link to the host (ReadingTiming): the
+  real driver stamps a reading when it arrives, and USB hands frames over
+  late and in bursts, so club and ball can arrive together with nearly
+  the same timestamp. hit(realtime=True) also paces the deliveries, so
+  detection sees the gaps between them as it would from the serial port
+
+End-to-end tests feed these readings through LaunchMonitor and check that
+the detected speeds and estimated carry recover the launch conditions.
+
+Example:
+    radar = MockRadar(MountingGeometry(lateral_offset_m=0.3), seed=1)
+    monitor = LaunchMonitor(use_iq_streaming=False)
+    monitor.radar = radar
+    monitor.start(shot_callback=print)
+    radar.hit(LaunchConditions(ball_speed_mph=150, launch_angle_vertical=12))
+    monitor.stop()
+"""
+
+import math
+import random
+import time
+from dataclasses import dataclass
+from typing import Callable, List, Optional, Tuple
+
+from .carry import _TYPICAL_SPIN_RPM, PhysicsCarryModel
+from .geometry import MountingGeometry
+from .launch_monitor import ClubType
+from .ops243 import Direction, MountPosition, SpeedReading
+from .units import MetersPerSecond, Mph
+
+
+@dataclass
+class LaunchConditions:
+    """
+    What the ball actually did, for the mock radar to reproduce.
+
+    Attributes:
+        ball_speed_mph: Ball speed off the face
+        launch_angle_vertical: Vertical launch angle in degrees
+        launch_angle_horizontal: Horizontal launch angle in degrees (+ = right)
+        spin_rpm: Backspin (None = club typical)
+        club_speed_mph: Club head speed at impact (None = ball speed / 1.45)
+        club: Club used, for the typical spin
+    """
+
+    ball_speed_mph: float
+    launch_angle_vertical: float = 12.0
+    launch_angle_horizontal: float = 0.0
+    spin_rpm: Optional[float] = None
+    club_speed_mph: Optional[float] = None
+    club: ClubType = ClubType.DRIVER
+
+    @property
+    def effective_spin_rpm(self) -> float:
+        """Spin used for the flight."""
+        return self.spin_rpm or _TYPICAL_SPIN_RPM.get(self.club, 5000)
+
+    @property
+    def effective_club_speed_mph(self) -> float:
+        """Club speed used for the pre-impact readings."""
+        return self.club_speed_mph or self.ball_speed_mph / 1.45
+
+
+@dataclass
+class ReadingTiming:
+    """
+    How readings reach the host, and the timestamps they get there.
+
+    Attributes:
+        latency_ms: Mean delay from a frame to its arrival at the host
+        latency_jitter_ms: Standard deviation of that delay (never below 0)
+        timestamp_jitter_ms: Host scheduling delay before a reading is
+            stamped (half-normal, this standard deviation)
+        burst_size: Frames handed over together, arriving when the last is
+            ready (1 = each frame on its own)
+    """
+
+    latency_ms: float = 0.0
+    latency_jitter_ms: float = 0.0
+    timestamp_jitter_ms: float = 0.0
+    burst_size: int = 1
+
+    def __post_init__(self):
+        for name in ("latency_ms", "latency_jitter_ms", "timestamp_jitter_ms"):
+            if getattr(self, name) < 0:
+                raise ValueError(f"{name} can't be negative")
+        if self.burst_size < 1 or int(self.burst_size) != self.burst_size:
+            raise ValueError("burst_size must be a whole number >= 1")
+
+
+# A burst of readings and when it arrives (seconds relative to impact)
+Delivery = Tuple[float, List[SpeedReading]]
+
+
+class MockRadar:
+    """
+    Radar stand-in that synthesizes readings from the flight model.
+
+    Implements the parts of OPS243Radar that LaunchMonitor uses in legacy
+    (radar-processed) mode. I/Q streaming is not simulated.
+    """
+
+    FRAME_INTERVAL_SEC = 0.01  # ~100 readings per second, like the radar's speed output
+    CLUB_WINDOW_SEC = 0.03  # Club head visible this long before impact
+    OBSERVE_SEC = 0.2  # Ball tracked at most this long after impact
+    MAX_RANGE_M = 25.0  # Beyond this a golf ball is lost in the noise
+
+    # Magnitude at 1 m; the club head's larger cross section reflects more
+    CLUB_MAGNITUDE_AT_1M = 3000.0
+    BALL_MAGNITUDE_AT_1M = 1200.0
+    MIN_MAGNITUDE = 20.0  # Radar reports nothing weaker
+
+    def __init__(
+        self,
+        geometry: MountingGeometry = MountingGeometry(),
+        mount_position: MountPosition = MountPosition.BEHIND,
+        dropout: float = 0.1,
+        noise_mph: float = 0.3,
+        seed: Optional[int] = None,
+        timing: Optional[ReadingTiming] = None,
+    ):
+        """
+        Initialize mock radar.
+
+        Args:
+            geometry: Radar position relative to the ball
+            mount_position: Behind the ball, or down-range facing the player
+                (geometry distance is then measured toward the target)
+            dropout: Probability that any one frame is missed
+            noise_mph: Standard deviation of speed noise
+            seed: Random seed for repeatable readings
+            timing: Latency, jitter and bursts of the link to the host
+                (None = every reading arrives at its frame time)
+        """
+        self.geometry = geometry
+        self.mount_position = mount_position
+        self.dropout = dropout
+        self.noise_mph = noise_mph
+        self.timing = timing
+        self._rng = random.Random(seed)
+        self._physics = PhysicsCarryModel()
+        self._callback: Optional[Callable[[SpeedReading], None]] = None
+
+    def connect(self):
+        """Nothing to connect to."""
+
+    def disconnect(self):
+        """Stop streaming."""
+        self.stop_streaming()
+
+    def configure_for_golf(self):
+        """Nothing to configure."""
+
+    def get_info(self) -> dict:
+        """Module info in the shape the real radar reports."""
+        return {"Product": "MockRadar", "Version": "flight-model"}
+
+    def start_streaming(self, callback: Callable[[SpeedReading], None]):
+        """Deliver readings from hit() to callback."""
+        self._callback = callback
+
+    def stop_streaming(self):
+        """Stop delivering readings."""
+        self._callback = None
+
+    def hit(self, launch: LaunchConditions, realtime: bool = False) -> List[SpeedReading]:
+        """
+        Simulate a shot and stream its readings to the callback.
+
+        Args:
+            launch: Launch conditions to reproduce
+            realtime: Deliver each reading when the host would stamp it, with
+                timestamps on time.monotonic() like the real driver's
+                (otherwise all at once, with timestamps relative to impact)
+
+        Returns:
+            The readings delivered (also returned when not streaming)
+        """
+        readings = self.readings_for(launch)
+        start = time.monotonic() - readings[0].timestamp if realtime and readings else 0.0
+        for reading in readings:
+            if realtime:
+                # Handed over when the host stamps it
+                reading.timestamp += start
+                delay = reading.timestamp - time.monotonic()
+                if delay > 0:
+                    time.sleep(delay)
+            if self._callback:
+                self._callback(reading)
+        return readings
+
+    def _radar_position(self):
+        # Ball at the origin, x toward the target, y up, z to the right
+        g = self.geometry
+        along = g.distance_m if self.mount_position == MountPosition.FRONT else -g.distance_m
+        return (along, g.height_offset_m, g.lateral_offset_m)
+
+    def _observe(self, position, velocity, magnitude_at_1m, timestamp) -> Optional[SpeedReading]:
+        radar = self._radar_position()
+        los = [p - r for p, r in zip(position, radar)]
+        distance = math.sqrt(sum(c * c for c in los))
+        if distance > self.MAX_RANGE_M:
+            return None
+        magnitude = magnitude_at_1m / max(distance, 0.1) ** 2
+        if magnitude < self.MIN_MAGNITUDE or self._rng.random() < self.dropout:
+            return None
+
+        radial_ms = sum(v * c for v, c in zip(velocity, los)) / distance
+        speed = MetersPerSecond(abs(radial_ms)).to_mph() + self._rng.gauss(0, self.noise_mph)
+        return SpeedReading(
+            speed=round(max(speed, 0.0), 1),
+            direction=Direction.OUTBOUND if radial_ms > 0 else Direction.INBOUND,
+            magnitude=round(magnitude, 1),
+            timestamp=timestamp,
+        )
+
+    def readings_for(self, launch: LaunchConditions) -> List[SpeedReading]:
+        """
+        Readings the radar would report for a shot, in time order.
+
+        Timestamps are seconds relative to impact (club readings negative),
+        when the host would stamp them (see ReadingTiming).
+
+        Args:
+            launch: Launch conditions to reproduce
+
+        Returns:
+            Club then ball readings, after dropout and range limits
+        """
+        return [reading for _, burst in self.deliveries_for(launch) for reading in burst]
+
+    def deliveries_for(self, launch: LaunchConditions) -> List[Delivery]:
+        """
+        A shot's readings in the bursts the host receives them in.
+
+        Returns:
+            (arrival time, readings) in arrival order, times relative to impact
+        """
+        return self._deliver(self._frames(launch))
+
+    def _deliver(self, readings: List[SpeedReading]) -> List[Delivery]:
+        """Group frames into bursts and stamp them as the host would."""
+        timing = self.timing
+        if timing is None:
+            return [(r.timestamp, [r]) for r in readings]
+        deliveries: List[Delivery] = []
+        size = int(timing.burst_size)
+        arrival = stamp = -math.inf
+        for i in range(0, len(readings), size):
+            burst = readings[i : i + size]
+            latency = max(0.0, self._rng.gauss(timing.latency_ms, timing.latency_jitter_ms))
+            # Arrivals and timestamps never go back in time: the port is read in order
+            arrival = max(arrival, burst[-1].timestamp + latency / 1000)
+            for reading in burst:
+                delay = abs(self._rng.gauss(0, timing.timestamp_jitter_ms)) / 1000
+                stamp = max(stamp, arrival + delay)
+                reading.timestamp = stamp
+            deliveries.append((arrival, burst))
+        return deliveries
+
+    def _frames(self, launch: LaunchConditions) -> List[SpeedReading]:
+        """Club then ball readings at their frame times, relative to impact."""
+        readings = []
+
+        # Club head: travelling along the target line into the ball
+        club_ms = Mph(launch.effective_club_speed_mph).to_mps()
+        frames = int(self.CLUB_WINDOW_SEC / self.FRAME_INTERVAL_SEC)
+        for i in range(frames, 0, -1):
+            t = -i * self.FRAME_INTERVAL_SEC
+            reading = self._observe(
+                (club_ms * t, 0.0, 0.0), (club_ms, 0.0, 0.0), self.CLUB_MAGNITUDE_AT_1M, t
+            )
+            if reading:
+                readings.append(reading)
+
+        # Ball: the flight model's trajectory, turned by the horizontal launch angle
+        heading = math.radians(launch.launch_angle_horizontal)
+        points = self._physics.trajectory(
+            launch.ball_speed_mph, launch.launch_angle_vertical, launch.effective_spin_rpm
+        )
+        step = max(1, round(self.FRAME_INTERVAL_SEC / self._physics.TIME_STEP_SEC))
+        for t, x, y, vx, vy in points[step::step]:
+            if t > self.OBSERVE_SEC:
+                break
+            position = (x * math.cos(heading), y, x * math.sin(heading))
+            velocity = (vx * math.cos(heading), vy, vx * math.sin(heading))
+            reading = self._observe(position, velocity, self.BALL_MAGNITUDE_AT_1M, t)
+            if reading:
+                readings.append(reading)
+
+        return readings
+
+    def true_carry_yards(self, launch: LaunchConditions) -> float:
+        """Carry of the simulated flight, for comparison with the estimate."""
+        return self._physics.simulate(
+            launch.ball_speed_mph, launch.launch_angle_vertical, launch.effective_spin_rpm
+        )
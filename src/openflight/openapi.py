@@ -0,0 +1,464 @@
+"""
+OpenAPI document for the REST API.
+
+The UI, the phone remote and third-party apps all talk to /api; this is
+their contract. The server serves it, with a Swagger UI page to try the
+endpoints from a browser:
+
+    curl http://raspberrypi.local:8080/openapi.json
+    open http://raspberrypi.local:8080/docs
+
+and it can be written out without a running server, e.g. to generate a
+typed client when building the UI:
+
+    openflight-openapi -o openapi.json
+
+Paths come from the app's routes, so an endpoint is in the document as
+soon as it exists, with its docstring as summary and description.
+Request bodies, query parameters and response schemas are declared per
+endpoint in OPERATIONS; the tests check that every /api endpoint has an
+entry and that the Shot schema matches shot_to_dict.
+"""
+
+import argparse
+import inspect
+import json
+import re
+import sys
+import typing
+from dataclasses import fields
+from typing import Any, Dict, List, Optional, Sequence
+
+from . import __version__
+from .access import TOKEN_HEADER
+from .launch_monitor import ClubType, MonitorConfig
+from .segmentation import SEGMENTATION_MODES
+
+OPENAPI_VERSION = "3.1.0"
+
+JsonSchema = Dict[str, Any]
+
+
+def _ref(name: str) -> JsonSchema:
+    return {"$ref": f"#/components/schemas/{name}"}
+
+
+def _object(properties: Dict[str, JsonSchema], required: Sequence[str] = ()) -> JsonSchema:
+    schema: JsonSchema = {"type": "object", "properties": properties}
+    if required:
+        schema["required"] = list(required)
+    return schema
+
+
+def _nullable(type_name: str, **extra: Any) -> JsonSchema:
+    return {"type": [type_name, "null"], **extra}
+
+
+def _query(name: str, schema: JsonSchema, description: str) -> dict:
+    return {"name": name, "in": "query", "schema": schema, "description": description}
+
+
+# shot_to_dict's fields
+SHOT_SCHEMA = _object(
+    {
+        "ball_speed_mph": {"type": "number"},
+        "club_speed_mph": _nullable("number"),
+        "smash_factor": _nullable("number"),
+        "ball_energy_j": _nullable("number", description="Ball kinetic energy (J)"),
+        "club_energy_j": _nullable("number", description="Club head kinetic energy (J)"),
+        "energy_transfer_pct": _nullable("number", description="Ball energy / club energy"),
+        "estimated_carry_yards": {"type": "integer"},
+        "carry_range": {
+            "type": "array",
+            "items": {"type": "integer"},
+            "minItems": 2,
+            "maxItems": 2,
+            "description": "Low and high carry estimate",
+        },
+        "club": _ref("Club"),
+        "timestamp": {"type": "string", "format": "date-time"},
+        "peak_magnitude": _nullable("number"),
+        "launch_angle_vertical": _nullable("number", description="Degrees, up from level"),
+        "launch_angle_horizontal": _nullable("number", description="Degrees, right positive"),
+        "launch_angle_confidence": _nullable("number", minimum=0, maximum=1),
+        "spin_rpm": _nullable("integer"),
+        "spin_confidence": _nullable("number", minimum=0, maximum=1),
+        "spin_quality": _nullable("string", enum=["high", "medium", "low", None]),
+        "carry_spin_adjusted": _nullable("integer"),
+        "mode": {
+            "type": "string",
+            "enum": ["streaming", "rolling-buffer", "mock", "injected"],
+        },
+        "ball_speed_raw_mph": _nullable("number", description="Before cosine correction"),
+        "club_speed_raw_mph": _nullable("number", description="Before cosine correction"),
+        "shot_number": _nullable("integer", description="Number in the session log"),
+        "hole_number": _nullable("integer"),
+        "hole_shot": _nullable("integer"),
+        "delivery_status": _nullable(
+            "string", enum=["pending", "delivered", "failed", "merged", None]
+        ),
+        "tempo_ratio": _nullable("number"),
+        "tempo_backswing_sec": _nullable("number"),
+        "tempo_downswing_sec": _nullable("number"),
+        "tempo_on_target": _nullable("boolean"),
+        "impact_offset_ms": _nullable("number"),
+        "lost_frames": _nullable("integer"),
+        "max_reading_gap_ms": _nullable("number"),
+        "ball_speed_reading_mph": _nullable(
+            "number", description="Fastest reading, when lost frames raised ball speed"
+        ),
+        "target_yards": _nullable("number"),
+        "strokes_gained": _nullable("number"),
+        "ball_type": _nullable("string"),
+    },
+    required=["ball_speed_mph", "estimated_carry_yards", "club", "timestamp", "mode"],
+)
+
+_SHOT_RESPONSE = _object({"shot": _ref("Shot")})
+_SHOT_AND_STATS = _object({"shot": _ref("Shot"), "stats": _ref("SessionStats")})
+_TAGS = _object({"tags": _ref("Tags")})
+_CONFIG = _object({"config": _ref("MonitorConfig")})
+
+# Endpoint function name -> what its route takes and returns:
+#   body: JSON request body schema; params: query parameters;
+#   response: 200 JSON schema; content: 200 media type when it isn't JSON
+OPERATIONS: Dict[str, dict] = {
+    "api_get_session": {
+        "response": _object(
+            {
+                "stats": _ref("SessionStats"),
+                "shots": {"type": "array", "items": _ref("Shot")},
+                "mock_mode": {"type": "boolean"},
+            }
+        ),
+    },
+    "api_clear_session": {"response": _object({"cleared": {"type": "boolean"}})},
+    "api_get_session_tags": {"response": _TAGS},
+    "api_update_session_tags": {
+        "body": {
+            "type": "object",
+            "additionalProperties": _nullable("string"),
+            "description": "Tags to set; null or an empty string removes one",
+        },
+        "response": _TAGS,
+    },
+    "api_get_shots": {
+        "params": [
+            _query("limit", {"type": "integer", "minimum": 0}, "Only the last N shots"),
+        ],
+        "response": _object({"shots": {"type": "array", "items": _ref("Shot")}}),
+    },
+    "api_export_shots": {
+        "params": [
+            _query("format", {"type": "string", "enum": ["csv", "html", "fit"]}, "Default csv"),
+            _query(
+                "template",
+                {"type": "string"},
+                "CSV column template: openflight (default), trackman, garmin or a custom name",
+            ),
+        ],
+        "content": ["text/csv", "text/html", "application/vnd.ant.fit"],
+    },
+    "api_get_stats": {
+        "params": [
+            _query(
+                "basket_pct",
+                {"type": "number", "exclusiveMinimum": 0, "maximum": 100},
+                "Target basket as a percentage of the target carry",
+            ),
+        ],
+        "response": _ref("SessionStats"),
+    },
+    "api_set_club": {
+        "body": _object({"club": _ref("Club")}, required=["club"]),
+        "response": _object({"club": _ref("Club")}),
+    },
+    "api_mulligan": {"response": _SHOT_AND_STATS},
+    "api_delete_shot": {
+        "params": [
+            _query("shot_number", {"type": "integer"}, "The shot's number in the session log"),
+            _query("reason", {"type": "string"}, "Why, kept in the session log"),
+        ],
+        "response": _SHOT_AND_STATS,
+    },
+    "api_edit_shot": {
+        "params": [
+            _query("shot_number", {"type": "integer"}, "The shot's number in the session log"),
+        ],
+        "body": _object(
+            {"club": _ref("Club"), "reason": {"type": "string"}},
+            required=["club"],
+        ),
+        "response": _SHOT_AND_STATS,
+    },
+    "api_arm": {"response": _object({"ready": {"type": "boolean"}})},
+    "api_simulate_shot": {
+        "body": _object({"ball_speed": {"type": "number"}}),
+        "response": _SHOT_RESPONSE,
+    },
+    "api_inject_shot": {
+        "body": _object(
+            {
+                "ball_speed_mph": {"type": "number", "exclusiveMinimum": 0},
+                "club_speed_mph": {"type": "number", "exclusiveMinimum": 0},
+                "launch_angle_vertical": {"type": "number"},
+                "launch_angle_horizontal": {"type": "number"},
+                "spin_rpm": {"type": "number"},
+                "club": _ref("Club"),
+            },
+            required=["ball_speed_mph"],
+        ),
+        "response": _SHOT_RESPONSE,
+    },
+    "api_companion_launch_angle": {
+        "body": _object(
+            {
+                "vertical": {"type": "number"},
+                "horizontal": {"type": "number", "default": 0.0},
+                "confidence": {"type": "number", "minimum": 0, "maximum": 1, "default": 1.0},
+                "time": {"type": "number", "description": "Companion's time.monotonic()"},
+                "clock_offset_sec": {
+                    "type": "number",
+                    "description": "Monitor minus companion clock (see timesync)",
+                },
+            },
+            required=["vertical"],
+        ),
+        "response": _object({"monitor_time": {"type": "number"}}),
+    },
+    "api_get_round": {"response": _ref("Round")},
+    "api_set_round": {
+        "body": _object(
+            {"hole": {"type": "integer", "minimum": 1}, "shot": {"type": "integer", "minimum": 1}},
+            required=["hole"],
+        ),
+        "response": _ref("Round"),
+    },
+    "api_clear_round": {"response": _ref("Round")},
+    "api_get_target": {"response": _ref("Target")},
+    "api_set_target": {
+        "body": _object({"yards": {"type": "number", "exclusiveMinimum": 0}}, required=["yards"]),
+        "response": _ref("Target"),
+    },
+    "api_clear_target": {"response": _ref("Target")},
+    "api_get_speed_training": {"response": _ref("SpeedTraining")},
+    "api_reset_speed_training": {"response": _ref("SpeedTraining")},
+    "api_get_state": {
+        "response": _object(
+            {
+                "timestamp": {"type": "string", "format": "date-time"},
+                "mode": {"type": "string"},
+                "mock": {"type": "boolean"},
+                "ready": {"type": "string", "enum": ["stopped", "disarmed", "waiting", "ready"]},
+                "monitor": {"type": "object", "description": "Shot detection's internal state"},
+                "config": {"type": ["object", "null"]},
+                "config_hash": _nullable("string"),
+                "connections": {"type": "object"},
+                "sim_endpoints": {"type": ["object", "null"]},
+            }
+        ),
+    },
+    "api_get_config": {"response": _CONFIG},
+    "api_set_config": {
+        "body": {**_ref("MonitorConfig"), "description": "Fields to change"},
+        "response": _CONFIG,
+    },
+    "api_list_profiles": {
+        "response": _object(
+            {
+                "profiles": {"type": "array", "items": {"type": "string"}},
+                "active": _nullable("string"),
+            }
+        ),
+    },
+    "api_save_profile": {
+        "body": _object({"name": {"type": "string"}}, required=["name"]),
+        "response": _object({"profile": {"type": "object"}}),
+    },
+    "api_stream": {
+        "params": [
+            _query("readings", {"type": "string", "enum": ["0", "1", "true"]}, "Add live readings"),
+            _query(
+                "reading_hz",
+                {"type": "number", "minimum": 0},
+                "Most readings per second (default 20, 0 = every reading)",
+            ),
+        ],
+        "content": ["text/event-stream"],
+    },
+}
+
+
+def _config_property(annotation: Any) -> JsonSchema:
+    if annotation is int:
+        return {"type": "integer"}
+    if annotation is float:
+        return {"type": "number"}
+    if annotation is str:
+        return {"type": "string"}
+    # Optional[Tuple[str, ...]]: the filter names
+    return _nullable("array", items={"type": "string"})
+
+
+def monitor_config_schema() -> JsonSchema:
+    """MonitorConfig's fields, with their defaults."""
+    hints = typing.get_type_hints(MonitorConfig)
+    defaults = MonitorConfig().to_dict()
+    properties = {}
+    for f in fields(MonitorConfig):
+        schema = _config_property(hints[f.name])
+        schema["default"] = defaults[f.name]
+        properties[f.name] = schema
+    properties["segmentation"]["enum"] = list(SEGMENTATION_MODES)
+    return _object(properties)
+
+
+def _components() -> dict:
+    return {
+        "schemas": {
+            "Shot": SHOT_SCHEMA,
+            "Club": {"type": "string", "enum": [c.value for c in ClubType]},
+            "MonitorConfig": monitor_config_schema(),
+            "SessionStats": {
+                "type": "object",
+                "description": "Shot count and averages, per club and overall",
+                "additionalProperties": True,
+            },
+            "Tags": {"type": "object", "additionalProperties": {"type": "string"}},
+            "Round": _object(
+                {"hole": _nullable("integer"), "next_shot": _nullable("integer")}
+            ),
+            "Target": _object(
+                {"target_yards": _nullable("number"), "baseline_strokes": _nullable("number")}
+            ),
+            "SpeedTraining": {
+                "type": "object",
+                "description": "Reps per set with their club speeds, and protocol compliance",
+                "additionalProperties": True,
+            },
+            "Error": _object({"error": {"type": "string"}}, required=["error"]),
+        },
+        "responses": {
+            "Error": {
+                "description": "Error",
+                "content": {"application/json": {"schema": _ref("Error")}},
+            },
+        },
+        "securitySchemes": {
+            # Only enforced when the server is started with --api-token
+            "bearer": {"type": "http", "scheme": "bearer"},
+            "token": {"type": "apiKey", "in": "header", "name": TOKEN_HEADER},
+        },
+    }
+
+
+def _docstring(func: Any) -> List[str]:
+    doc = inspect.getdoc(func) or ""
+    summary, _, description = doc.partition("\n\n")
+    return [" ".join(summary.split()), description.strip()]
+
+
+def _operation(endpoint: str, func: Any, method: str) -> dict:
+    summary, description = _docstring(func)
+    declared = OPERATIONS.get(endpoint, {})
+    operation: dict = {"operationId": endpoint, "summary": summary}
+    if description:
+        operation["description"] = description
+    if declared.get("params"):
+        operation["parameters"] = declared["params"]
+    if declared.get("body") and method in ("post", "put", "patch"):
+        operation["requestBody"] = {
+            "content": {"application/json": {"schema": declared["body"]}},
+        }
+    if declared.get("content"):
+        ok = {"description": "OK", "content": {media: {} for media in declared["content"]}}
+    else:
+        schema = declared.get("response", {"type": "object"})
+        ok = {"description": "OK", "content": {"application/json": {"schema": schema}}}
+    operation["responses"] = {"200": ok, "default": {"$ref": "#/components/responses/Error"}}
+    return operation
+
+
+def build_spec(app: Any) -> dict:
+    """
+    The OpenAPI document for an app's /api routes.
+
+    Args:
+        app: The Flask app (openflight.server.app)
+
+    Returns:
+        OpenAPI 3.1 document as a dict
+    """
+    paths: Dict[str, dict] = {}
+    for rule in sorted(app.url_map.iter_rules(), key=lambda r: r.rule):
+        if not rule.rule.startswith("/api/"):
+            continue
+        path = re.sub(r"<(?:[^:<>]+:)?([^<>]+)>", r"{\1}", rule.rule)
+        tag = path.split("/")[2]
+        func = app.view_functions[rule.endpoint]
+        for method in sorted(rule.methods - {"HEAD", "OPTIONS"}):
+            operation = _operation(rule.endpoint, func, method.lower())
+            operation["tags"] = [tag]
+            paths.setdefault(path, {})[method.lower()] = operation
+    return {
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "OpenFlight API",
+            "version": __version__,
+            "description": "Shots, session and settings of an OpenFlight launch monitor.",
+        },
+        "paths": paths,
+        "components": _components(),
+        "security": [{}, {"bearer": []}, {"token": []}],
+    }
+
+
+SWAGGER_UI_VERSION = "5"
+
+SWAGGER_UI_PAGE = """<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>OpenFlight API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@{version}/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@{version}/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({{ url: "{spec_url}", dom_id: "#swagger-ui" }});
+  </script>
+</body>
+</html>
+"""
+
+
+def swagger_ui_page(spec_url: str = "/openapi.json") -> str:
+    """The Swagger UI page for the document (loads Swagger UI from unpkg, so needs internet)."""
+    return SWAGGER_UI_PAGE.format(version=SWAGGER_UI_VERSION, spec_url=spec_url)
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """CLI entry point: write the OpenAPI document."""
+    parser = argparse.ArgumentParser(description="Write the OpenFlight REST API's OpenAPI document")
+    parser.add_argument("-o", "--output", metavar="FILE", help="Write to FILE instead of stdout")
+    args = parser.parse_args(argv)
+
+    from .server import app  # pylint: disable=import-outside-toplevel,cyclic-import
+
+    text = json.dumps(build_spec(app), indent=2) + "\n"
+    if not args.output:
+        sys.stdout.write(text)
+        return 0
+    try:
+        with open(args.output, "w", encoding="utf-8") as f:
+            f.write(text)
+    except OSError as e:
+        print(f"Error: {e}")
+        return 1
+    print(f"Wrote {args.output}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -21,18 +21,40 @@ Speed limits by sample rate:
 - 30kHz (S=30): max 208 mph - RECOMMENDED for golf
 - 50kHz (SL): max 347 mph   - overkill, lower resolution
 - 100kHz (SC): max 695 mph  - overkill
+
+Other OmniPreSense modules take the same commands. The OPS7243 matches the
+OPS243; the cheaper OPS241-A tops out at 20 kHz (~139 mph) and has no raw
+I/Q or rolling buffer output, so it only works with the radar's internal
+processing (--no-iq-streaming). See MODEL_CAPABILITIES.
 """
 
 import json
 import logging
 import threading
 import time
+from collections import deque
 from dataclasses import dataclass
 from enum import Enum
-from typing import Callable, List, Optional
+from pathlib import Path
+from typing import Callable, Deque, Dict, FrozenSet, List, Optional
 
 import serial
-import serial.tools.list_ports
+
+from .frames import (
+    Direction,
+    Frame,
+    IQBlock,
+    OPS243Decoder,
+    RawAdc,
+    SpeedReading,
+    SpeedTarget,
+)
+from .container import device_hint
+from .radar_detect import choose_radar, detect_radars, resolve_port
+from .serial_dump import SerialDump
+from .tracing import get_tracer, trace_span
+from .transport import connection_hint, is_url, open_port
+from .units import radar_unit
 
 # Configure logging for raw radar data
 logger = logging.getLogger("ops243")
@@ -57,6 +79,15 @@ class SpeedUnit(Enum):
     CMS = "UC"      # centimeters per second
 
 
+_UNIT_NAMES = {
+    SpeedUnit.MPS: "m/s",
+    SpeedUnit.MPH: "mph",
+    SpeedUnit.KPH: "kph",
+    SpeedUnit.FPS: "fps",
+    SpeedUnit.CMS: "cm/s",
+}
+
+
 class PowerMode(Enum):
     """Power modes for OPS243-A."""
     ACTIVE = "PA"   # Normal operating mode
@@ -64,41 +95,163 @@ class PowerMode(Enum):
     PULSE = "PP"    # Single pulse mode (must be in IDLE first)
 
 
-class Direction(Enum):
-    """Direction of detected object."""
-    INBOUND = "inbound"
-    OUTBOUND = "outbound"
-    UNKNOWN = "unknown"
+class MountPosition(Enum):
+    """
+    Where the radar sits relative to the hitting area.
 
+    Determines which Doppler direction a struck ball shows up as.
+    """
+    BEHIND = "behind"  # Behind the ball facing the target: ball moves away (outbound)
+    FRONT = "front"    # Down-range facing the player: ball approaches (inbound)
+    SIDE = "side"      # Beside the mat: sign depends on exact placement, accept both
+
+    @property
+    def ball_directions(self) -> FrozenSet[Direction]:
+        """Directions that can be a ball (or club) moving toward the target."""
+        if self == MountPosition.FRONT:
+            return frozenset({Direction.INBOUND})
+        if self == MountPosition.SIDE:
+            return frozenset({Direction.INBOUND, Direction.OUTBOUND})
+        return frozenset({Direction.OUTBOUND})
+
+
+@dataclass(frozen=True)
+class RadarCapabilities:
+    """What one OmniPreSense model can do, per its datasheet and API doc."""
+
+    max_sample_rate: int  # samples/second
+    magnitude: bool  # Magnitude reporting (OM)
+    fft_output: bool  # FFT output (OF)
+    iq_output: bool  # Raw I/Q ADC output (OR), needed for I/Q streaming mode
+    rolling_buffer: bool  # Rolling buffer capture (GC), needed for spin
+
+    @property
+    def max_speed_mph(self) -> float:
+        """Fastest speed the top sample rate can report without aliasing."""
+        return self.max_sample_rate * MPH_PER_SAMPLE_RATE
+
+
+# Max speed per sample/second (30 ksps -> ~208.5 mph)
+MPH_PER_SAMPLE_RATE = 0.0069508
+
+# Capabilities by product name prefix, as reported in "??" info
+# ({"Product":"OPS241-A", ...}). Longer prefixes are matched first.
+MODEL_CAPABILITIES = {
+    "OPS243": RadarCapabilities(
+        max_sample_rate=100000, magnitude=True, fft_output=True, iq_output=True,
+        rolling_buffer=True,
+    ),
+    "OPS7243": RadarCapabilities(
+        max_sample_rate=100000, magnitude=True, fft_output=True, iq_output=True,
+        rolling_buffer=True,
+    ),
+    # Cheaper Doppler-only module: no raw output or rolling buffer
+    "OPS241": RadarCapabilities(
+        max_sample_rate=20000, magnitude=True, fft_output=False, iq_output=False,
+        rolling_buffer=False,
+    ),
+}
+DEFAULT_MODEL = "OPS243"
+
+# Speed report formats: "json" (OJ) or "compact" comma-separated numbers
+# (Oj), about half the bytes per report at the cost of self-describing lines
+OUTPUT_FORMATS = ("json", "compact")
+
+
+def capabilities_for(product: Optional[str]) -> RadarCapabilities:
+    """
+    Capabilities of an OmniPreSense model.
 
-@dataclass
-class SpeedReading:
-    """A single speed reading from the radar."""
-    speed: float
-    direction: Direction
-    magnitude: Optional[float] = None
-    timestamp: Optional[float] = None
-    unit: str = "mph"
+    Args:
+        product: Product name from the "??" info (e.g. "OPS241-A")
 
+    Returns:
+        The model's capabilities (OPS243's for an unknown or missing name)
+    """
+    name = (product or "").upper()
+    for prefix in sorted(MODEL_CAPABILITIES, key=len, reverse=True):
+        if name.startswith(prefix):
+            return MODEL_CAPABILITIES[prefix]
+    if product:
+        logger.warning("Unknown radar model %s, assuming %s capabilities", product, DEFAULT_MODEL)
+    return MODEL_CAPABILITIES[DEFAULT_MODEL]
+
+
+def _json_field(response: str, *names: str):
+    """First of the named fields in a JSON query answer (None if absent or not JSON)."""
+    for line in response.splitlines():
+        try:
+            data = json.loads(line)
+        except json.JSONDecodeError:
+            continue
+        if isinstance(data, dict):
+            for name in names:
+                if name in data:
+                    return data[name]
+    return None
 
-@dataclass
-class IQBlock:
+
+class RadarStateCache:
     """
-    A block of raw I/Q samples from the radar.
+    Last settings sent to each radar, keyed by serial number.
 
-    Each block contains 128 samples at 30ksps (~4.3ms of data).
-    Blocks arrive every ~31ms due to serial bandwidth limits.
-    Used for continuous I/Q streaming mode where we process
-    the FFT locally instead of using the radar's internal processing.
+    Most OPS243 settings can't be read back, so the host remembers what it
+    sent. configure_for_golf() uses this to skip commands the radar has
+    already applied, and any other settings change drops the radar's entry.
     """
-    i_samples: List[int]  # Raw I channel ADC values (0-4095)
-    q_samples: List[int]  # Raw Q channel ADC values (0-4095)
-    timestamp: float      # When this block was received
+
+    DEFAULT_PATH = Path.home() / ".openflight" / "radar_state.json"
+
+    def __init__(self, path: Optional[Path] = None):
+        """
+        Initialize state cache.
+
+        Args:
+            path: JSON file to store state in (default: ~/.openflight/radar_state.json)
+        """
+        self.path = Path(path) if path else self.DEFAULT_PATH
+
+    def _load(self) -> Dict[str, Dict[str, str]]:
+        try:
+            data = json.loads(self.path.read_text())
+        except (OSError, ValueError):
+            return {}
+        return data if isinstance(data, dict) else {}
+
+    def _store(self, data: Dict[str, Dict[str, str]]):
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            tmp_path = self.path.with_suffix(".tmp")
+            tmp_path.write_text(json.dumps(data, indent=2))
+            tmp_path.replace(self.path)
+        except OSError as e:
+            logger.warning("Could not save radar state to %s: %s", self.path, e)
+
+    def get(self, serial_number: str) -> Dict[str, str]:
+        """Settings last applied to a radar (empty if unknown)."""
+        settings = self._load().get(serial_number)
+        return dict(settings) if isinstance(settings, dict) else {}
+
+    def put(self, serial_number: str, settings: Dict[str, str]):
+        """Record the settings now applied to a radar."""
+        data = self._load()
+        data[serial_number] = dict(settings)
+        self._store(data)
+
+    def invalidate(self, serial_number: str):
+        """Forget a radar's settings (they were changed outside configure_for_golf)."""
+        data = self._load()
+        if data.pop(serial_number, None) is not None:
+            self._store(data)
 
 
 class OPS243Radar:
     """
-    Driver for OPS243-A Doppler radar sensor.
+    Driver for OmniPreSense Doppler radar sensors (OPS243-A and family).
+
+    The same commands drive the OPS241-A and OPS7243; on connect the model
+    is read from the "??" info and its capabilities (see MODEL_CAPABILITIES)
+    limit the sample rate and which output modes can be used.
 
     Example usage:
         radar = OPS243Radar()
@@ -120,51 +273,100 @@ class OPS243Radar:
     DEFAULT_BAUD = 57600
     DEFAULT_TIMEOUT = 1.0
 
-    # Common USB identifiers for OPS243
-    VENDOR_IDS = [0x0483]  # STMicroelectronics
-
-    def __init__(self, port: Optional[str] = None, baud: int = DEFAULT_BAUD):
+    # Settings sent by configure_for_golf, in order. Keys name each setting
+    # so a cached copy can be diffed against it (see RadarStateCache).
+    GOLF_SETTINGS = {
+        "units": SpeedUnit.MPH.value,
+        "sample_rate": "S=30",  # 30ksps, max ~208 mph
+        "buffer_size": "S(",  # 128 samples
+        "fft_size": "X=32",  # 4096 FFT, ±0.1 mph @ ~56 Hz
+        "magnitude_report": "OM",
+        "direction_filter": "R|",  # Both directions; sign gives direction
+        "min_speed": "R>10",
+        "min_magnitude": "M>50",
+        "transmit_power": "P0",  # Max power for best range
+        "num_reports": "O4",  # Club and ball in the same frame
+        "json_output": "OJ",  # After O4, which may reset it
+        "peak_averaging": "K+",
+    }
+
+    # Queries whose answers are kept verbatim in a settings snapshot
+    READBACK_QUERIES = ("??", "?V", "?N", "U?", "S?", "R?", "X?")
+
+    # Damaged lines (USB noise, lost bytes) are logged as a rate over the last
+    # CORRUPTION_WINDOW lines (once CORRUPTION_MIN_LINES were read), at most
+    # every CORRUPTION_LOG_INTERVAL_SEC
+    CORRUPTION_WINDOW = 200
+    CORRUPTION_MIN_LINES = 50
+    CORRUPTION_WARN_RATE = 0.05
+    CORRUPTION_LOG_INTERVAL_SEC = 10.0
+    # While the radar sends plain speeds instead of JSON, OJ is resent this often
+    JSON_RESTORE_INTERVAL_SEC = 5.0
+    # Compact output falls back to JSON when this share of lines is damaged
+    COMPACT_FALLBACK_RATE = 0.2
+
+    # Cache of applied settings (None = always send every command)
+    state_cache: Optional[RadarStateCache] = None
+    _state_stale = False  # Set once this process changes settings outside configure_for_golf
+    _discarded_samples = 0  # NaN/infinite speed samples dropped by decode_line
+    _corrupt_lines = 0  # Damaged lines seen by decode_line (recovered or not)
+    _recent_lines: Optional[Deque[bool]] = None  # Corrupt flag of the last lines
+    _last_corruption_log = float("-inf")
+    _json_lost = False  # Plain speeds arriving although JSON output was set
+    _last_json_restore = float("-inf")
+    output_format = "json"  # One of OUTPUT_FORMATS, applied by configure_for_golf
+    _configured_serial: Optional[str] = None  # Serial number configure_for_golf cached
+    product: Optional[str] = None  # Model name from "??" info, once connected
+    capabilities = MODEL_CAPABILITIES[DEFAULT_MODEL]
+    dump: Optional[SerialDump] = None  # Set before connect() to capture serial traffic
+
+    def __init__(
+        self,
+        port: Optional[str] = None,
+        baud: int = DEFAULT_BAUD,
+        state_cache: Optional[RadarStateCache] = None,
+        output_format: str = "json",
+    ):
         """
         Initialize radar driver.
 
         Args:
             port: Serial port (e.g., '/dev/ttyACM0'). If None, auto-detect.
             baud: Baud rate (default 57600 per datasheet)
-        """
+            state_cache: Where applied settings are remembered between runs
+                         (default: ~/.openflight/radar_state.json)
+            output_format: Speed report format configure_for_golf sets up,
+                           "json" or "compact" (see OUTPUT_FORMATS)
+        """
+        if output_format not in OUTPUT_FORMATS:
+            raise ValueError(
+                f"Unknown output format {output_format!r} (use {', '.join(OUTPUT_FORMATS)})"
+            )
         self.port = port
+        self.output_format = output_format
         self.baud = baud
+        self.state_cache = state_cache or RadarStateCache()
         self.serial: Optional[serial.Serial] = None
         self._streaming = False
         self._stream_thread: Optional[threading.Thread] = None
         self._callback: Optional[Callable[[SpeedReading], None]] = None
+        self._frame_callback: Optional[Callable[[Frame], None]] = None
         self._iq_callback: Optional[Callable[[IQBlock], None]] = None
         self._iq_error_callback: Optional[Callable[[str], None]] = None
         self._unit = "mph"
         self._json_mode = False
         self._magnitude_enabled = False
 
-    @staticmethod
-    def find_radar_ports() -> List[str]:
-        """
-        Find potential OPS243 radar ports.
-
-        Returns:
-            List of port names that might be OPS243 devices
-        """
-        ports = []
-        for port in serial.tools.list_ports.comports():
-            # OPS243 shows up as USB serial device
-            if port.vid in OPS243Radar.VENDOR_IDS or "ACM" in port.device:
-                ports.append(port.device)
-            # Also check description for OmniPreSense
-            elif port.description and "OmniPreSense" in port.description:
-                ports.append(port.device)
-        return ports
-
     def connect(self, timeout: float = DEFAULT_TIMEOUT) -> bool:
         """
         Connect to the radar sensor.
 
+        Without a port, USB serial ports are probed for an OmniPreSense
+        radar (see radar_detect); a port with glob characters is expanded
+        to the first matching device, and a tcp:// or rfc2217:// URL opens a
+        serial bridge (see transport). The model is then read from the radar's
+        info to look up its capabilities.
+
         Args:
             timeout: Serial read timeout in seconds
 
@@ -172,28 +374,84 @@ class OPS243Radar:
             True if connection successful
         """
         if self.port is None:
-            ports = self.find_radar_ports()
-            if not ports:
-                raise ConnectionError(
-                    "No OPS243 radar found. Check USB connection and try specifying port manually."
-                )
-            self.port = ports[0]
+            self.port = choose_radar(detect_radars()).port
+        else:
+            self.port = resolve_port(self.port)
 
         try:
-            self.serial = serial.Serial(
-                port=self.port,
-                baudrate=self.baud,
-                timeout=timeout,
+            self.serial = open_port(
+                self.port,
+                self.baud,
+                timeout,
                 bytesize=serial.EIGHTBITS,
                 parity=serial.PARITY_NONE,
                 stopbits=serial.STOPBITS_ONE
             )
+            if self.dump:
+                self.serial = self.dump.tee(self.serial)
             # Drain any in-progress dump (e.g. radar triggered while no software was running).
             # Opening the port unblocks the radar's UART TX, so we read until silence.
             self._drain_serial()
-            return True
-        except serial.SerialException as e:
-            raise ConnectionError(f"Failed to connect to {self.port}: {e}") from e
+        except (serial.SerialException, ValueError) as e:
+            hint = connection_hint(self.port) if is_url(self.port) else device_hint()
+            raise ConnectionError(
+                f"Failed to connect to {self.port}: {e}" + (f". {hint}" if hint else "")
+            ) from e
+        self.detect_model()
+        self.detect_units()
+        return True
+
+    def detect_model(self) -> RadarCapabilities:
+        """
+        Read the product name from the radar and look up its capabilities.
+
+        Returns:
+            The connected model's capabilities
+        """
+        self.product = self.get_info().get("Product")
+        self.capabilities = capabilities_for(self.product)
+        logger.info(
+            "Radar model %s: max %d ksps (~%.0f mph)",
+            self.product or f"unknown (assuming {DEFAULT_MODEL})",
+            self.capabilities.max_sample_rate // 1000,
+            self.capabilities.max_speed_mph,
+        )
+        return self.capabilities
+
+    def detect_units(self) -> str:
+        """
+        Read the speed unit the radar is set to ("U?").
+
+        A radar left in km/h or m/s by other software would otherwise have
+        its speeds taken as mph. Readings are converted to mph either way.
+
+        Returns:
+            The unit name ("mph", "m/s", "kph", "fps" or "cm/s")
+        """
+        self._note_unit(self.get_current_units(), "U?")
+        return self._unit
+
+    def _note_unit(self, text: str, source: str):
+        """Adopt the unit the radar says it uses, warning if it isn't the assumed one."""
+        if not text:
+            return
+        unit = radar_unit(text)
+        if unit is None:
+            logger.warning(
+                "Radar %s gave an unknown speed unit %r, assuming %s", source, text, self._unit
+            )
+            return
+        if unit != self._unit:
+            logger.warning(
+                "Radar %s says speeds are in %s, but %s was assumed - converting %s to mph",
+                source, unit, self._unit, unit,
+            )
+            self._unit = unit
+
+    def _require(self, supported: bool, feature: str):
+        """Raise if the connected model lacks a feature."""
+        if not supported:
+            raise RuntimeError(f"{self.product or DEFAULT_MODEL} does not support {feature}")
 
     def disconnect(self):
         """Disconnect from the radar sensor."""
@@ -244,6 +502,13 @@ class OPS243Radar:
         if not self.serial or not self.serial.is_open:
             raise ConnectionError("Not connected to radar")
 
+        # Any settings change outside configure_for_golf makes the cached state stale
+        is_query = cmd.startswith("?") or cmd.endswith("?")
+        if not is_query and not self._state_stale and self.state_cache:
+            self._state_stale = True
+            if self.state_cache.path.exists():
+                self.state_cache.invalidate(self.get_serial_number())
+
         # Clear input buffer
         self.serial.reset_input_buffer()
 
@@ -285,6 +550,8 @@ class OPS243Radar:
                 except json.JSONDecodeError:
                     pass
 
+        if "Units" in info:
+            self._note_unit(str(info["Units"]), "info")
         return info
 
     def get_firmware_version(self) -> str:
@@ -304,14 +571,7 @@ class OPS243Radar:
             unit: SpeedUnit enum value
         """
         self._send_command(unit.value)
-        unit_names = {
-            SpeedUnit.MPS: "m/s",
-            SpeedUnit.MPH: "mph",
-            SpeedUnit.KPH: "kph",
-            SpeedUnit.FPS: "fps",
-            SpeedUnit.CMS: "cm/s"
-        }
-        self._unit = unit_names[unit]
+        self._unit = _UNIT_NAMES[unit]
 
     def set_sample_rate(self, rate: int):
         """
@@ -328,7 +588,24 @@ class OPS243Radar:
         Args:
             rate: Sample rate in samples/second
                   Common values: 10000, 20000, 30000 (recommended), 50000, 100000
+
+        Raises:
+            ValueError: If the connected model can't sample that fast
         """
+        self._send_command(self._sample_rate_command(rate))
+
+    def _sample_rate_command(self, rate: int) -> str:
+        """
+        Command for a sample rate.
+
+        Raises:
+            ValueError: If the connected model can't sample that fast
+        """
+        if rate > self.capabilities.max_sample_rate:
+            raise ValueError(
+                f"{self.product or DEFAULT_MODEL} supports at most "
+                f"{self.capabilities.max_sample_rate} samples/second"
+            )
         rate_commands = {
             1000: "SI",
             5000: "SV",
@@ -337,14 +614,11 @@ class OPS243Radar:
             50000: "SL",
             100000: "SC"
         }
-
         if rate in rate_commands:
-            self._send_command(rate_commands[rate])
-        else:
-            # Use configurable rate command (S=nn where nn is in ksps)
-            # 30ksps is recommended for golf (S=30)
-            ksps = rate // 1000
-            self._send_command(f"S={ksps}")
+            return rate_commands[rate]
+        # Use configurable rate command (S=nn where nn is in ksps)
+        # 30ksps is recommended for golf (S=30)
+        return f"S={rate // 1000}"
 
     def set_buffer_size(self, size: int):
         """
@@ -436,6 +710,8 @@ class OPS243Radar:
         Args:
             enabled: True to include magnitude in readings
         """
+        if enabled:
+            self._require(self.capabilities.magnitude, "magnitude reporting")
         self._send_command("OM" if enabled else "Om")
         self._magnitude_enabled = enabled
 
@@ -468,68 +744,151 @@ class OPS243Radar:
         - Negative speed = OUTBOUND (away from radar) - recorded as shot
 
         Positioning: Place radar 6-8 feet behind ball, angled 10° upward.
-        """
-        # Set units to MPH
-        self.set_units(SpeedUnit.MPH)
 
-        # 30ksps sample rate per OmniPreSense golf recommendation
-        # Max detectable speed ~208 mph (sufficient for golf, pros max ~190 mph)
-        # Lower than 50ksps but better resolution tradeoff
-        self.set_sample_rate(30000)
-        logger.info("Sample rate: 30ksps")
+        Only commands that differ from the settings last sent to this radar
+        (looked up by serial number in the state cache) are sent. Units revert
+        to m/s on power-up unless saved with A!, so a radar that doesn't
+        report mph is always fully configured.
+        """
+        settings = self.golf_settings()
+        serial_number = None
+        applied: Dict[str, str] = {}
+
+        if self.state_cache:
+            serial_number = self.get_serial_number()
+            if not serial_number or serial_number == "unknown":
+                serial_number = None  # Radars shipped before May 2020 have no serial
+            elif "mph" in self.get_current_units().lower():
+                applied = self.state_cache.get(serial_number)
+
+        changed = [key for key, cmd in settings.items() if applied.get(key) != cmd]
+        if "num_reports" in changed and "json_output" not in changed:
+            changed.append("json_output")  # O4 may reset JSON output
+
+        # Drop the cached entry first, so an interrupted configure can't leave it stale
+        self._state_stale = False
+        for key, cmd in settings.items():
+            if key in changed:
+                logger.info("Setting %s: %s", key, cmd)
+                self._send_command(cmd)
 
-        # 128 buffer per OmniPreSense recommendation
-        # Combined with 30ksps gives good base for FFT
-        self.set_buffer_size(128)
-        logger.info("Buffer size: 128")
+        self._unit = "mph"
+        self._json_mode = settings["json_output"] == "OJ"
+        self._magnitude_enabled = "magnitude_report" in settings
+        self._configured_serial = serial_number
+        logger.info(
+            "Configured for golf (%d of %d settings sent)", len(changed), len(settings)
+        )
 
-        # FFT size 4096 (X=32 multiplier with 128 buffer)
-        # This gives: ~56 Hz report rate, ±0.1 mph resolution
-        self.set_fft_size(32)
-        logger.info("FFT size: 4096 (X=32) - ±0.1 mph resolution @ ~56 Hz")
+        if self.state_cache and serial_number:
+            self.state_cache.put(serial_number, settings)
+            self._state_stale = False
 
-        # Enable magnitude to help filter weak signals
-        # Magnitude helps distinguish club (larger RCS, higher mag) from ball
-        self.enable_magnitude_report(True)
+        # Verify settings were applied
+        logger.info("Verifying configuration...")
+        filter_settings = self.get_speed_filter()
+        logger.info("Current filter settings: %s", filter_settings)
 
-        # Clear direction filter to get BOTH directions
-        # Direction is determined by the SIGN of the speed value.
-        # Per API docs: positive = inbound, negative = outbound
-        # This allows us to filter inbound readings (backswing) in software
-        self.set_direction_filter(None)
+    def golf_settings(self) -> Dict[str, str]:
+        """
+        GOLF_SETTINGS adapted to the connected model.
 
-        # Minimum speed 10 mph to filter very slow movements
-        # We filter higher speeds (backswing) in software based on direction
-        self.set_min_speed_filter(10)
+        A model that can't sample at 30 ksps gets its fastest rate (and a
+        warning, since faster shots will alias), and magnitude settings are
+        left out for a model without magnitude reporting.
+        """
+        settings = dict(self.GOLF_SETTINGS)
+        caps = self.capabilities
+        if caps.max_sample_rate < 30000:
+            settings["sample_rate"] = self._sample_rate_command(caps.max_sample_rate)
+            logger.warning(
+                "%s samples at most %d ksps: speeds above ~%.0f mph can't be measured",
+                self.product or DEFAULT_MODEL,
+                caps.max_sample_rate // 1000,
+                caps.max_speed_mph,
+            )
+        if not caps.magnitude:
+            del settings["magnitude_report"]
+            del settings["min_magnitude"]
+        if self.output_format == "compact":
+            settings["json_output"] = "Oj"
+        return settings
 
-        # Minimum magnitude filter to reject weak signals (walking, noise)
-        # Real golf shots have magnitude 100+, walking is typically 20-30
-        self.set_magnitude_filter(min_mag=50)
-        logger.info("Minimum magnitude filter: 50")
+    def read_settings(self) -> Dict[str, str]:
+        """
+        What the radar reports about its configuration.
 
-        # Max transmit power for best range
-        self.set_transmit_power(0)
+        Returns:
+            Query (see READBACK_QUERIES) -> the radar's answer, verbatim
+        """
+        return {query: self._send_command(query) for query in self.READBACK_QUERIES}
 
-        # Enable JSON for easier parsing
-        self.enable_json_output(True)
+    def restorable_settings(self, readback: Optional[Dict[str, str]] = None) -> Dict[str, str]:
+        """
+        Commands that would put the radar back in its current configuration.
 
-        # Enable multi-object reporting to detect both club head AND ball
-        # O4 reports up to 4 objects per sample cycle, ordered by magnitude
-        self.set_num_reports(4)
-        logger.info("Multi-object reporting enabled (O4)")
+        Most settings can't be read back: those come from the state cache,
+        if it still matches the radar (configure_for_golf's check). Units
+        and sample rate are read from the radar itself.
 
-        # Re-enable JSON output after O4 (in case it was reset)
-        self.enable_json_output(True)
+        Args:
+            readback: read_settings() result, if already queried
 
-        # Enable peak speed averaging per OmniPreSense recommendation
-        # Helps provide cleaner speed readings
-        self.enable_peak_averaging(True)
-        logger.info("Peak averaging enabled (K+)")
+        Returns:
+            Setting name (as in GOLF_SETTINGS) -> command
+        """
+        readback = readback if readback is not None else self.read_settings()
+        settings: Dict[str, str] = {}
+        serial_number = _json_field(readback.get("?N", ""), "SerialNumber")
+        unit = radar_unit(str(_json_field(readback.get("U?", ""), "Units") or ""))
+        trusted = unit == "mph" and not self._state_stale
+        if self.state_cache and serial_number and serial_number != "unknown" and trusted:
+            settings.update(self.state_cache.get(serial_number))
+
+        unit_commands = {name: command.value for command, name in _UNIT_NAMES.items()}
+        if unit in unit_commands:
+            settings["units"] = unit_commands[unit]
+        sample_rate = _json_field(readback.get("S?", ""), "SampleRate", "Sampling Rate")
+        if isinstance(sample_rate, int) and sample_rate > 0:
+            try:
+                settings["sample_rate"] = self._sample_rate_command(sample_rate)
+            except ValueError:
+                pass
+        return settings
 
-        # Verify settings were applied
-        logger.info("Verifying configuration...")
-        filter_settings = self.get_speed_filter()
-        logger.info("Current filter settings: %s", filter_settings)
+    def apply_settings(self, settings: Dict[str, str]):
+        """
+        Send a restorable_settings() result back to the radar.
+
+        Settings go in GOLF_SETTINGS order (JSON output after the number of
+        reports, which may reset it), then any others; the state cache is
+        updated to match.
+        """
+        order = [key for key in self.GOLF_SETTINGS if key in settings]
+        order += [key for key in settings if key not in self.GOLF_SETTINGS]
+        for key in order:
+            logger.info("Restoring %s: %s", key, settings[key])
+            self._send_command(settings[key])
+        if "units" in settings:
+            self._unit = _UNIT_NAMES[SpeedUnit(settings["units"])]
+        if "json_output" in settings:
+            self._json_mode = settings["json_output"] == "OJ"
+
+        serial_number = self.get_serial_number()
+        if self.state_cache and serial_number and serial_number != "unknown":
+            self.state_cache.put(serial_number, settings)
+            self._state_stale = False
+
+    def enable_fft_output(self, enabled: bool = True):
+        """
+        Enable/disable FFT output (OF) alongside speed reports.
+
+        Args:
+            enabled: True to output FFT bins
+        """
+        if enabled:
+            self._require(self.capabilities.fft_output, "FFT output")
+        self._send_command("OF" if enabled else "Of")
 
     def enable_peak_averaging(self, enabled: bool = True):
         """
@@ -674,12 +1033,28 @@ class OPS243Radar:
         Returns:
             SpeedReading object or None if no valid reading
         """
+        return self._reading_from_frame(self.read_frame())
+
+    def read_frame(self) -> Optional[Frame]:
+        """
+        Read and decode one line of radar output (blocking).
+
+        Returns:
+            The frame (speed report, FFT, status...), or None if the line
+            wasn't one
+        """
         if not self.serial or not self.serial.is_open:
             raise ConnectionError("Not connected to radar")
 
         try:
             # Read raw bytes first to see exactly what's coming in
+            read_start = time.monotonic()
             raw_bytes = self.serial.readline()
+            tracer = get_tracer()
+            if tracer and raw_bytes:
+                tracer.complete(
+                    "readline", "read", read_start, time.monotonic(), bytes=len(raw_bytes)
+                )
 
             if _show_raw_readings and raw_bytes:
                 print(f"[BYTES] {raw_bytes!r}")
@@ -691,12 +1066,123 @@ class OPS243Radar:
             # Log raw data for debugging
             raw_logger.debug(f"RAW: {line}")
 
-            return self._parse_reading(line)
+            with trace_span("decode", "parse"):
+                return self.decode_line(line)
         except serial.SerialException as e:
             if _show_raw_readings:
                 print(f"[SERIAL ERROR] {e}")
             return None
 
+    def decode_line(self, line: str) -> Optional[Frame]:
+        """
+        Decode a line of radar output into a typed frame (see frames).
+
+        Speed reports, FFT output and status answers are complete in one
+        line; I/Q output needs the streaming decoder, which pairs I and Q.
+
+        Returns:
+            The frame, or None if the line isn't one
+        """
+        # Always log raw line when debugging enabled (before any parsing)
+        if _show_raw_readings:
+            print(f"[SERIAL] {line!r}")
+        decoder = OPS243Decoder(unit=self._unit)
+        frame = decoder.decode(line)
+        self._discarded_samples += decoder.discarded
+        self._track_line(line, frame, corrupt=decoder.corrupt > 0)
+        if isinstance(frame, SpeedTarget) and frame.unit != self._unit:
+            self._note_unit(frame.unit, "speed reports")
+        return frame
+
+    def _track_line(self, line: str, frame: Optional[Frame], corrupt: bool):
+        """Keep the corruption rate and notice the radar dropping out of JSON mode."""
+        if self._recent_lines is None:
+            self._recent_lines = deque(maxlen=self.CORRUPTION_WINDOW)
+        self._recent_lines.append(corrupt)
+        if corrupt:
+            self._corrupt_lines += 1
+            now = time.monotonic()
+            rate = self.corruption_rate
+            enough_lines = len(self._recent_lines) >= self.CORRUPTION_MIN_LINES
+            if (
+                self.output_format == "compact"
+                and enough_lines
+                and rate >= self.COMPACT_FALLBACK_RATE
+            ):
+                self._fall_back_to_json(rate)
+                return
+            if (
+                enough_lines
+                and rate >= self.CORRUPTION_WARN_RATE
+                and now - self._last_corruption_log >= self.CORRUPTION_LOG_INTERVAL_SEC
+            ):
+                self._last_corruption_log = now
+                logger.warning(
+                    "%.0f%% of the last %d radar lines were damaged (%d in total) - "
+                    "check the USB cable and power supply",
+                    rate * 100,
+                    len(self._recent_lines),
+                    self._corrupt_lines,
+                )
+
+        if not self._json_mode or not isinstance(frame, SpeedTarget):
+            return
+        plain = "{" not in line
+        if plain and not self._json_lost:
+            self._json_lost = True
+            logger.warning(
+                "Radar is sending plain speeds instead of JSON (reset?) - "
+                "readings continue without magnitudes"
+            )
+        elif not plain and self._json_lost:
+            self._json_lost = False
+            logger.info("Radar is back in JSON output mode")
+
+    @property
+    def discarded_samples(self) -> int:
+        """Speed samples dropped for being NaN or infinite."""
+        return self._discarded_samples
+
+    @property
+    def corrupt_lines(self) -> int:
+        """Damaged lines (partial, merged or with noise) seen so far."""
+        return self._corrupt_lines
+
+    @property
+    def corruption_rate(self) -> float:
+        """Share of damaged lines among the last CORRUPTION_WINDOW lines."""
+        if not self._recent_lines:
+            return 0.0
+        return sum(self._recent_lines) / len(self._recent_lines)
+
+    @property
+    def json_output_lost(self) -> bool:
+        """Whether the radar fell out of JSON mode and sends plain speeds."""
+        return self._json_lost
+
+    def _fall_back_to_json(self, rate: float):
+        """Switch compact output back to JSON, whose lines resync on their own."""
+        logger.warning(
+            "%.0f%% of compact radar lines were damaged - switching to JSON output", rate * 100
+        )
+        self.output_format = "json"
+        self._json_mode = True
+        self._recent_lines.clear()
+        if self.state_cache and self._configured_serial:
+            # The cache says Oj: have the next configure_for_golf check everything
+            self.state_cache.invalidate(self._configured_serial)
+        if self.serial and self.serial.is_open:
+            self.serial.write(b"OJ")
+
+    def _restore_json_output(self):
+        """Resend OJ while the radar sends plain speeds (rate limited)."""
+        now = time.monotonic()
+        if now - self._last_json_restore < self.JSON_RESTORE_INTERVAL_SEC:
+            return
+        self._last_json_restore = now
+        # Written directly: the answer arrives in the stream, read by the caller
+        self.serial.write(b"OJ")
+
     def _parse_reading(self, line: str) -> Optional[SpeedReading]:
         """
         Parse a reading from the radar output.
@@ -708,96 +1194,59 @@ class OPS243Radar:
         - Positive speed = INBOUND (toward radar - backswing)
 
         With O4 (multi-object) mode, speed and magnitude are arrays.
-        We return the first/strongest reading here; the full array is
-        available via read_speed_multi().
+        We return the first/strongest reading here; the other objects in
+        the frame are attached as its frame_objects.
 
         Args:
             line: Raw line from serial output
 
         Returns:
-            SpeedReading or None if parse fails
+            SpeedReading or None if the line isn't a speed report
         """
-        # Always log raw line when debugging enabled (before any parsing)
-        if _show_raw_readings:
-            print(f"[SERIAL] {line!r}")
+        return self._reading_from_frame(self.decode_line(line))
 
-        try:
-            if self._json_mode and line.startswith('{'):
-                data = json.loads(line)
-                speed_data = data.get('speed', 0)
-                magnitude_data = data.get('magnitude')
-
-                # Handle array format from O4 multi-object mode
-                # Arrays are ordered by magnitude (strongest first)
-                if isinstance(speed_data, list):
-                    if not speed_data:
-                        return None
-                    speed = float(speed_data[0])
-                    magnitude = float(magnitude_data[0]) if magnitude_data else None
-
-                    if _show_raw_readings:
-                        print(f"[MULTI] {len(speed_data)} objects: speeds={speed_data} mags={magnitude_data}")
-                else:
-                    speed = float(speed_data)
-                    magnitude = float(magnitude_data) if magnitude_data else None
-
-                # Direction from sign of speed value
-                # Negative = OUTBOUND (away from radar - golf ball flight)
-                # Positive = INBOUND (toward radar - backswing)
-                if speed > 0:
-                    direction = Direction.INBOUND
-                else:
-                    direction = Direction.OUTBOUND
-
-                # Debug: print raw reading to console (sign indicates direction)
-                if _show_raw_readings:
-                    print(f"[RAW] {speed:+.1f} mph -> {direction.value} (mag: {magnitude})")
-
-                # Log parsed reading for debugging
-                logger.debug(f"PARSED: raw_speed={speed:.2f} abs_speed={abs(speed):.2f} dir={direction.value} mag={magnitude}")
-
-                return SpeedReading(
-                    speed=abs(speed),
-                    direction=direction,
-                    magnitude=magnitude,
-                    timestamp=time.time(),
-                    unit=self._unit
-                )
-
-            # Plain number format - direction from sign
-            speed = float(line)
-            if speed > 0:
-                direction = Direction.INBOUND
-            else:
-                direction = Direction.OUTBOUND
-
-            # Debug: print raw reading to console
-            if _show_raw_readings:
-                print(f"[RAW] {speed:+.1f} mph -> {direction.value}")
-
-            logger.debug(f"PARSED (plain): raw_speed={speed:.2f} abs_speed={abs(speed):.2f} dir={direction.value}")
+    def _reading_from_frame(self, frame: Optional[Frame]) -> Optional[SpeedReading]:
+        """The strongest target of a speed report as a SpeedReading."""
+        if not isinstance(frame, SpeedTarget):
+            return None
+        reading = frame.in_mph().to_reading()
+        if reading is None:
+            return None
 
-            return SpeedReading(
-                speed=abs(speed),
-                direction=direction,
-                timestamp=time.time(),
-                unit=self._unit
+        if _show_raw_readings:
+            if reading.frame_objects:
+                speeds = [t.speed for t in frame.targets]
+                print(f"[MULTI] {len(speeds)} objects: speeds={speeds}")
+            print(
+                f"[RAW] {reading.speed:.1f} mph -> {reading.direction.value} "
+                f"(mag: {reading.magnitude})"
             )
-        except (ValueError, json.JSONDecodeError) as e:
-            logger.warning(f"Failed to parse reading: {line!r} - {e}")
-            return None
+        logger.debug(
+            "PARSED: speed=%.2f dir=%s mag=%s",
+            reading.speed,
+            reading.direction.value,
+            reading.magnitude,
+        )
+        return reading
 
-    def start_streaming(self, callback: Callable[[SpeedReading], None]):
+    def start_streaming(
+        self,
+        callback: Callable[[SpeedReading], None],
+        frame_callback: Optional[Callable[[Frame], None]] = None,
+    ):
         """
         Start continuous speed streaming with callback.
 
         Args:
             callback: Function called with each SpeedReading
+            frame_callback: Function called with every decoded frame,
+                including FFT and status output
         """
         if self._streaming:
             return
 
         self._callback = callback
+        self._frame_callback = frame_callback
         self._streaming = True
         self._stream_thread = threading.Thread(target=self._stream_loop, daemon=True)
         self._stream_thread.start()
@@ -809,6 +1258,7 @@ class OPS243Radar:
             self._stream_thread.join(timeout=2.0)
             self._stream_thread = None
         self._callback = None
+        self._frame_callback = None
 
         # If we were doing I/Q streaming, tell radar to stop
         if self._iq_callback is not None:
@@ -821,7 +1271,12 @@ class OPS243Radar:
         """Internal streaming loop."""
         while self._streaming:
             try:
-                reading = self.read_speed()
+                frame = self.read_frame()
+                if self._json_lost:
+                    self._restore_json_output()
+                if frame and self._frame_callback:
+                    self._frame_callback(frame)
+                reading = self._reading_from_frame(frame)
                 if reading and self._callback:
                     self._callback(reading)
             except Exception:
@@ -865,6 +1320,7 @@ class OPS243Radar:
         """
         if not self.serial or not self.serial.is_open:
             raise ConnectionError("Not connected to radar")
+        self._require(self.capabilities.rolling_buffer, "rolling buffer mode")
 
         print(f"[RADAR] Entering rolling buffer mode (S#{pre_trigger_segments}, S={sample_rate_ksps})...")
         logger.info("Entering rolling buffer mode (pre_trigger_segments=%d)...",
@@ -1059,17 +1515,17 @@ class OPS243Radar:
         self.serial.flush()
 
         response_lines = []
-        start_time = time.time()
+        start_time = time.monotonic()
         last_data_time = start_time
         bytes_received = 0
 
         # Read data until timeout or complete response
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             if self.serial.in_waiting:
                 chunk = self.serial.read(self.serial.in_waiting)
                 response_lines.append(chunk.decode('ascii', errors='ignore'))
                 bytes_received += len(chunk)
-                last_data_time = time.time()
+                last_data_time = time.monotonic()
 
                 # Check if we have complete data (Q array ends the response)
                 full_response = ''.join(response_lines)
@@ -1084,7 +1540,7 @@ class OPS243Radar:
             else:
                 # No data available
                 # If we've received some data and haven't gotten more in 0.5s, consider done
-                if bytes_received > 100 and (time.time() - last_data_time) > 0.5:
+                if bytes_received > 100 and (time.monotonic() - last_data_time) > 0.5:
                     full_response = ''.join(response_lines)
                     if '"Q"' in full_response:
                         break
@@ -1122,16 +1578,16 @@ class OPS243Radar:
         self.serial.reset_input_buffer()
 
         response_lines = []
-        start_time = time.time()
+        start_time = time.monotonic()
         last_data_time = None
         bytes_received = 0
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             if self.serial.in_waiting:
                 chunk = self.serial.read(self.serial.in_waiting)
                 response_lines.append(chunk.decode('ascii', errors='ignore'))
                 bytes_received += len(chunk)
-                last_data_time = time.time()
+                last_data_time = time.monotonic()
 
                 # Check if we have complete I/Q data
                 full_response = ''.join(response_lines)
@@ -1147,7 +1603,7 @@ class OPS243Radar:
                 time.sleep(0.01)
             else:
                 # If we've started receiving data, use shorter timeout
-                if last_data_time and (time.time() - last_data_time) > 0.5:
+                if last_data_time and (time.monotonic() - last_data_time) > 0.5:
                     full_response = ''.join(response_lines)
                     if '"Q"' in full_response:
                         break
@@ -1396,6 +1852,7 @@ class OPS243Radar:
         logger.info("Enabling raw I/Q output (OR command)...")
         if not self.serial or not self.serial.is_open:
             raise ConnectionError("Not connected to radar")
+        self._require(self.capabilities.iq_output, "raw I/Q output")
 
         # Clear buffer before sending
         self.serial.reset_input_buffer()
@@ -1445,6 +1902,7 @@ class OPS243Radar:
         We do our own 4096-point FFT in software with zero-padding.
         """
         logger.info("Configuring for continuous I/Q streaming...")
+        self._require(self.capabilities.iq_output, "raw I/Q output")
 
         # First, stop any existing I/Q streaming (from previous run or crash)
         # This ensures _send_command won't hang on buffered I/Q data
@@ -1567,7 +2025,7 @@ class OPS243Radar:
         - Processes data in chunks to minimize syscalls
         - No blocking operations in the critical path
         """
-        pending_i = None
+        decoder = OPS243Decoder(unit=self._unit)
         buffer = bytearray()
         error_count = 0
 
@@ -1581,7 +2039,8 @@ class OPS243Radar:
                 waiting = self.serial.in_waiting
                 if waiting > 0:
                     # Read directly into bytearray (efficient)
-                    chunk = self.serial.read(waiting)
+                    with trace_span("read", "read", bytes=waiting):
+                        chunk = self.serial.read(waiting)
                     buffer.extend(chunk)
 
                     # Process complete lines
@@ -1599,35 +2058,31 @@ class OPS243Radar:
                         if not line_bytes:
                             continue
 
-                        # Fast check for JSON object
-                        if line_bytes[0:1] != b'{' or line_bytes[-1:] != b'}':
+                        # Fast check for JSON object; a line that isn't one is a
+                        # partial frame (sync lost) and is skipped to the next newline
+                        if line_bytes[-1:] != b'}' or b'{' not in line_bytes:
+                            self._track_line("", None, corrupt=True)
                             continue
 
+                        corrupt = decoder.corrupt
                         try:
-                            # Decode and parse only valid JSON lines
-                            data = json.loads(line_bytes)
-
-                            if "I" in data:
-                                pending_i = data["I"]
-                            elif "Q" in data and pending_i is not None:
-                                q_samples = data["Q"]
-                                if len(pending_i) == len(q_samples):
-                                    block = IQBlock(
-                                        i_samples=pending_i,
-                                        q_samples=q_samples,
-                                        timestamp=time.time()
-                                    )
-                                    if self._iq_callback:
-                                        self._iq_callback(block)
-                                pending_i = None
-
-                        except (json.JSONDecodeError, UnicodeDecodeError):
+                            line = line_bytes.decode("ascii")
+                        except UnicodeDecodeError:
                             error_count += 1
+                            self._track_line("", None, corrupt=True)
+                            continue
+                        # Noise or a partial frame before the "{" is resynced by the decoder
+                        with trace_span("decode", "parse"):
+                            frame = decoder.decode(line)
+                        self._track_line(line, frame, corrupt=decoder.corrupt > corrupt)
+                        if isinstance(frame, RawAdc) and self._iq_callback:
+                            self._iq_callback(frame.to_block())
 
                     # Prevent buffer overflow (sync lost)
                     if len(buffer) > 8192:
                         buffer.clear()
-                        pending_i = None
+                        decoder.reset()
+                        self._track_line("", None, corrupt=True)
                 else:
                     # No data - very brief sleep to avoid busy-waiting
                     time.sleep(0.0001)
@@ -0,0 +1,157 @@
+"""
+Where each shot falls in the player's history.
+
+A number on its own says little: 152 mph is a great drive for one player
+and a poor one for another. The server prints each shot's percentile
+among the player's earlier shots with the same club, read from the
+session logs, and whether it's the best of the day:
+
+    [HISTORY] Ball 152 mph — 78th percentile (driver, 412 shots), best today
+
+The player is the session's "player" tag (set from the phone remote or
+--tag player=NAME), so each player is compared with their own shots;
+shots logged without one form their own history. Percentiles need
+MIN_HISTORY earlier shots with the club; until then only "best today" is
+shown. Mock and injected shots are left out. For purists who'd rather
+just see the numbers, start the server with --no-percentiles.
+"""
+
+import bisect
+import threading
+from datetime import date, datetime
+from typing import Dict, List, Optional, Tuple
+
+from .launch_monitor import ClubType
+from .sessions import read_shots
+from .trends import METRICS
+
+MIN_HISTORY = 10
+METRIC = "ball_speed"
+
+# Shots that did not come from a swing aren't history
+_SKIPPED_MODES = ("mock", "injected")
+
+HistoryKey = Tuple[Optional[str], str]  # (player, club)
+
+
+def ordinal(n: int) -> str:
+    """1 -> "1st", 22 -> "22nd", 13 -> "13th"."""
+    if 10 <= n % 100 <= 20:
+        suffix = "th"
+    else:
+        suffix = {1: "st", 2: "nd", 3: "rd"}.get(n % 10, "th")
+    return f"{n}{suffix}"
+
+
+class ShotHistory:
+    """
+    Ball speeds by player and club, for ranking new shots.
+
+    Example:
+        history = ShotHistory()
+        history.load(find_sessions([str(log_dir / "session_*.jsonl")]))
+        history.describe(shot_to_dict(shot), player="sam")  # "Ball 152 mph — 78th ..."
+    """
+
+    def __init__(self):
+        self.field, self.unit = METRICS[METRIC]
+        self._values: Dict[HistoryKey, List[float]] = {}  # Sorted
+        self._best_today: Dict[HistoryKey, float] = {}
+        self._day = date.today()
+        self._lock = threading.Lock()
+
+    def shot_count(self, player: Optional[str], club: str) -> int:
+        """Shots in the history for a player and club."""
+        with self._lock:
+            return len(self._values.get((player, club), []))
+
+    def load(self, paths: List[str]) -> int:
+        """
+        Add the shots in session logs to the history.
+
+        Args:
+            paths: Session log files
+
+        Returns:
+            Shots added
+        """
+        added = 0
+        for path in paths:
+            for shot in read_shots(path):
+                if shot.get("mode") in _SKIPPED_MODES or shot.get(self.field) is None:
+                    continue
+                try:
+                    when = datetime.fromisoformat(shot["ts"])
+                except (KeyError, TypeError, ValueError):
+                    when = None
+                club = shot.get("club") or ClubType.DRIVER.value
+                self.add(shot["tags"].get("player"), club, float(shot[self.field]), when)
+                added += 1
+        return added
+
+    def add(
+        self, player: Optional[str], club: str, value: float, when: Optional[datetime] = None
+    ):
+        """Add one shot (when: its time, for "best today"; default now)."""
+        key = (player, club)
+        day = (when or datetime.now()).date()
+        with self._lock:
+            bisect.insort(self._values.setdefault(key, []), value)
+            self._roll_day()
+            if day == self._day:
+                self._best_today[key] = max(value, self._best_today.get(key, value))
+
+    def percentile(self, player: Optional[str], club: str, value: float) -> Optional[int]:
+        """
+        The value's percentile rank among the player's shots with the club.
+
+        Returns:
+            1-99, or None with fewer than MIN_HISTORY shots
+        """
+        with self._lock:
+            values = self._values.get((player, club), [])
+            if len(values) < MIN_HISTORY:
+                return None
+            below = bisect.bisect_left(values, value)
+            equal = bisect.bisect_right(values, value) - below
+            rank = 100 * (below + equal / 2) / len(values)
+        return min(99, max(1, round(rank)))
+
+    def describe(self, shot: dict, player: Optional[str] = None) -> Optional[str]:
+        """
+        Describe a new shot against the history, then add it.
+
+        Args:
+            shot: The shot as shot_to_dict() gives it
+            player: The session's player tag
+
+        Returns:
+            e.g. "Ball 152 mph — 78th percentile (driver, 412 shots), best today",
+            or None if the shot has no value or nothing to compare with
+        """
+        value = shot.get(self.field)
+        if value is None or shot.get("mode") in _SKIPPED_MODES:
+            return None
+        club = shot.get("club") or ClubType.DRIVER.value
+        key = (player, club)
+
+        percentile = self.percentile(player, club, value)
+        with self._lock:
+            self._roll_day()
+            best = self._best_today.get(key)
+            count = len(self._values.get(key, []))
+        self.add(player, club, value)
+
+        parts = []
+        if percentile is not None:
+            parts.append(f"{ordinal(percentile)} percentile ({club}, {count} shots)")
+        if best is not None and value > best:
+            parts.append("best today")
+        if not parts:
+            return None
+        return f"Ball {value:.0f} {self.unit} — " + ", ".join(parts)
+
+    def _roll_day(self):
+        if date.today() != self._day:
+            self._day = date.today()
+            self._best_today.clear()
@@ -0,0 +1,318 @@
+"""
+Named calibration profiles.
+
+People move their rigs between locations (garage net, backyard, range
+cart), and each spot needs its own mounting geometry and thresholds. A
+profile stores those settings under a name so they can be restored with
+`openflight-server --profile garage-net`.
+
+Profiles are JSON files in ~/.openflight/profiles/<name>.json:
+
+    {
+      "name": "garage-net",
+      "mounting": {"lateral_offset_m": 0.3, "height_offset_m": 0.0, "distance_m": 1.2},
+      "launch_angle_offset_deg": -1.5,
+      "monitor": {"min_shot_magnitude": 80},
+      "carry_models": {"driver": {"model": "physics"}},
+      "schedule": {"quiet_hours": ["22:00-07:00"], "idle_disarm_min": 30},
+      "shot_timing": {"clubs": {"sw": {"shot_timeout_sec": 0.8}}}
+    }
+
+"monitor" holds MonitorConfig fields; any not listed keep their defaults. A
+profile with an invalid setting fails to load with the setting's key, e.g.
+"monitor.smash_factor_max: must be <= 2 ..., got 3".
+"carry_models" selects a carry model per club (see carry.py).
+"schedule" disarms the monitor at set hours or when idle (see schedule.py).
+"shot_timing" sets the shot timing thresholds per radar mode and per club
+(see shot_timing.py).
+"""
+
+import json
+import logging
+import re
+import threading
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .carry import parse_carry_models
+from .geometry import MountingGeometry
+from .launch_monitor import MonitorConfig
+from .schedule import ArmSchedule
+from .shot_timing import ShotTiming
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_PROFILE_DIR = Path.home() / ".openflight" / "profiles"
+
+# Profile names become file names, so keep them simple
+_NAME_PATTERN = re.compile(r"^[A-Za-z0-9][A-Za-z0-9_.-]{0,63}$")
+
+
+@dataclass
+class CalibrationProfile:
+    """
+    Installation-specific calibration.
+
+    Attributes:
+        name: Profile name (e.g. "garage-net")
+        mounting: Radar position relative to the ball
+        launch_angle_offset_deg: Added to camera vertical launch angles to
+            correct for camera tilt at this location
+        monitor: Shot detection thresholds
+        carry_models: Carry model entry per club value (or "default")
+        schedule: Quiet hours and idle disarm at this location
+        shot_timing: Shot timing thresholds per radar mode and club
+    """
+
+    name: str
+    mounting: MountingGeometry = field(default_factory=MountingGeometry)
+    launch_angle_offset_deg: float = 0.0
+    monitor: MonitorConfig = field(default_factory=MonitorConfig)
+    carry_models: Dict[str, dict] = field(default_factory=dict)
+    schedule: ArmSchedule = field(default_factory=ArmSchedule)
+    shot_timing: ShotTiming = field(default_factory=ShotTiming)
+
+    def to_dict(self) -> dict:
+        """Serialize to a JSON-compatible dict."""
+        return {
+            "name": self.name,
+            "mounting": asdict(self.mounting),
+            "launch_angle_offset_deg": self.launch_angle_offset_deg,
+            "monitor": self.monitor.to_dict(),
+            "carry_models": dict(self.carry_models),
+            "schedule": self.schedule.to_dict(),
+            "shot_timing": self.shot_timing.to_dict(),
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict) -> "CalibrationProfile":
+        """
+        Build a profile from a dict, validating every section.
+
+        Raises:
+            ValueError: If any field is unknown or invalid
+        """
+        try:
+            mounting = MountingGeometry(**data.get("mounting", {}))
+        except TypeError as e:
+            raise ValueError(f"Invalid mounting section: {e}") from None
+
+        carry_models = data.get("carry_models", {})
+        if not isinstance(carry_models, dict):
+            raise ValueError("carry_models must be an object")
+        parse_carry_models(carry_models)
+
+        monitor = data.get("monitor", {})
+        if not isinstance(monitor, dict):
+            raise ValueError("monitor must be an object")
+        try:
+            monitor_config = MonitorConfig().with_updates(monitor)
+        except ValueError as e:
+            raise ValueError(f"monitor.{e}") from None
+
+        return cls(
+            name=validate_name(data.get("name", "")),
+            mounting=mounting,
+            launch_angle_offset_deg=float(data.get("launch_angle_offset_deg", 0.0)),
+            monitor=monitor_config,
+            carry_models=carry_models,
+            schedule=ArmSchedule.from_dict(data.get("schedule", {})),
+            shot_timing=ShotTiming.from_dict(data.get("shot_timing", {})),
+        )
+
+
+def validate_name(name: str) -> str:
+    """
+    Check a profile name is safe to use as a file name.
+
+    Raises:
+        ValueError: If the name is empty or contains path characters
+    """
+    if not _NAME_PATTERN.match(name or ""):
+        raise ValueError(
+            f"Invalid profile name {name!r} (use letters, digits, '-', '_' or '.')"
+        )
+    return name
+
+
+def profile_path(name: str, directory: Optional[Path] = None) -> Path:
+    """Get the file path for a profile name."""
+    return (directory or DEFAULT_PROFILE_DIR) / f"{validate_name(name)}.json"
+
+
+def list_profiles(directory: Optional[Path] = None) -> List[str]:
+    """List saved profile names, sorted."""
+    directory = directory or DEFAULT_PROFILE_DIR
+    if not directory.exists():
+        return []
+    return sorted(p.stem for p in directory.glob("*.json"))
+
+
+def load_profile(name: str, directory: Optional[Path] = None) -> CalibrationProfile:
+    """
+    Load a saved profile.
+
+    Args:
+        name: Profile name
+        directory: Profile directory (default: ~/.openflight/profiles)
+
+    Raises:
+        FileNotFoundError: If no profile with that name exists
+        ValueError: If the profile file is malformed
+    """
+    path = profile_path(name, directory)
+    if not path.exists():
+        available = ", ".join(list_profiles(directory)) or "none saved"
+        raise FileNotFoundError(f"Profile '{name}' not found (available: {available})")
+
+    try:
+        data = json.loads(path.read_text())
+    except json.JSONDecodeError as e:
+        raise ValueError(f"Profile '{name}' is not valid JSON: {e}") from None
+
+    data["name"] = name
+    try:
+        return CalibrationProfile.from_dict(data)
+    except ValueError as e:
+        raise ValueError(f"Profile '{name}' ({path}): {e}") from None
+
+
+def save_profile(profile: CalibrationProfile, directory: Optional[Path] = None) -> Path:
+    """
+    Save a profile, replacing any existing one with the same name.
+
+    Args:
+        profile: Profile to save
+        directory: Profile directory (default: ~/.openflight/profiles)
+
+    Returns:
+        Path the profile was written to
+    """
+    path = profile_path(profile.name, directory)
+    path.parent.mkdir(parents=True, exist_ok=True)
+
+    # Write to a temp file and rename so a crash never leaves a half-written profile
+    tmp_path = path.with_suffix(".json.tmp")
+    tmp_path.write_text(json.dumps(profile.to_dict(), indent=2) + "\n")
+    tmp_path.replace(path)
+    return path
+
+
+def diff_profiles(
+    old: CalibrationProfile, new: CalibrationProfile
+) -> Dict[str, Tuple[Any, Any]]:
+    """
+    List settings that differ between two profiles.
+
+    Returns:
+        Mapping of dotted setting name (e.g. "monitor.min_shot_magnitude")
+        to (old value, new value)
+    """
+
+    def flatten(profile: CalibrationProfile) -> Dict[str, Any]:
+        flat = {}
+        for section, values in profile.to_dict().items():
+            if isinstance(values, dict):
+                flat.update({f"{section}.{k}": v for k, v in values.items()})
+            else:
+                flat[section] = values
+        return flat
+
+    before, after = flatten(old), flatten(new)
+    return {k: (before.get(k), after.get(k)) for k in after if before.get(k) != after.get(k)}
+
+
+class ProfileWatcher:
+    """
+    Reloads a profile when its file changes on disk.
+
+    Polls the file's modification time (no extra dependencies, works on
+    any filesystem). Invalid edits are logged and ignored, so a typo in
+    the file never replaces a working profile.
+
+    Example:
+        watcher = ProfileWatcher("garage-net", on_change=apply_profile)
+        watcher.start()
+    """
+
+    DEFAULT_INTERVAL_SEC = 1.0
+
+    def __init__(
+        self,
+        name: str,
+        on_change: Callable[[CalibrationProfile, CalibrationProfile], None],
+        directory: Optional[Path] = None,
+        interval_sec: float = DEFAULT_INTERVAL_SEC,
+    ):
+        """
+        Initialize profile watcher.
+
+        Args:
+            name: Profile to watch
+            on_change: Called with (old profile, new profile) after a valid edit
+            directory: Profile directory (default: ~/.openflight/profiles)
+            interval_sec: Polling interval
+        """
+        self.name = name
+        self.directory = directory
+        self.on_change = on_change
+        self.interval_sec = interval_sec
+        self.path = profile_path(name, directory)
+        self.current = load_profile(name, directory)
+        self._mtime = self._read_mtime()
+        self._stop_event = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    def _read_mtime(self) -> Optional[float]:
+        try:
+            return self.path.stat().st_mtime
+        except OSError:
+            return None
+
+    def check(self) -> bool:
+        """
+        Reload the profile if the file changed since the last check.
+
+        Returns:
+            True if a changed, valid profile was applied
+        """
+        mtime = self._read_mtime()
+        if mtime is None or mtime == self._mtime:
+            return False
+        self._mtime = mtime
+
+        try:
+            new = load_profile(self.name, self.directory)
+        except (OSError, ValueError) as e:
+            logger.warning("Ignoring invalid profile edit: %s", e)
+            return False
+
+        if new == self.current:
+            return False
+
+        old, self.current = self.current, new
+        self.on_change(old, new)
+        return True
+
+    def start(self):
+        """Start polling in a background thread."""
+        if self._thread and self._thread.is_alive():
+            return
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def stop(self):
+        """Stop polling."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=2.0)
+            self._thread = None
+
+    def _run(self):
+        while not self._stop_event.wait(self.interval_sec):
+            try:
+                self.check()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.error("Profile reload failed: %s", e)
@@ -0,0 +1,215 @@
+"""
+Radar auto-detection.
+
+When no --port is given, every USB serial port is probed to find out what
+is attached instead of assuming the first ACM device is an OPS243:
+
+- OmniPreSense (OPS243, OPS241, OPS7243): answers the "??" query with a
+  JSON product line ({"Product":"OPS243-C", ...}) at 57600 baud. All of
+  them are driven by the OPS243 backend, which looks up what each model
+  can do from the product name
+- K-LD7 (RFbeam): answers an INIT frame with a RESP frame at 115200 baud,
+  8E1, after which it is released again with GBYE
+
+Only the OmniPreSense family has a backend so far. A K-LD7 is recognized
+so it can be reported by name (and skipped when an OPS243 is attached as
+well) rather than being fed OPS243 commands.
+"""
+
+import glob
+import json
+import logging
+import struct
+from dataclasses import dataclass
+from enum import Enum
+from typing import Callable, List, Optional
+
+import serial
+import serial.tools.list_ports
+
+from .container import device_hint
+from .transport import is_url
+
+logger = logging.getLogger(__name__)
+
+OPS243_BAUD = 57600
+KLD7_BAUD = 115200
+PROBE_TIMEOUT = 0.5
+
+
+class RadarModel(Enum):
+    """Radar models that can be recognized."""
+
+    OPS243 = "ops243"
+    KLD7 = "k-ld7"
+
+
+# Models with a backend, in order of preference
+SUPPORTED_MODELS = (RadarModel.OPS243,)
+
+
+@dataclass
+class DetectedRadar:
+    """A radar found on a serial port."""
+
+    port: str
+    model: RadarModel
+    product: Optional[str] = None  # e.g. "OPS243-C", when the radar reports it
+
+
+def candidate_ports() -> List[str]:
+    """USB serial ports that might have a radar attached."""
+    return [
+        port.device
+        for port in serial.tools.list_ports.comports()
+        if port.vid is not None or "ACM" in port.device
+    ]
+
+
+def resolve_port(port: str) -> str:
+    """
+    The serial port a --port value names, expanding glob patterns.
+
+    A pattern such as '/dev/serial/by-id/usb-OmniPreSense*' picks the
+    first matching device by name, so the radar is still found when
+    replugging it changes its ttyACM number. Serial bridge URLs (see
+    transport) are returned as they are.
+
+    Raises:
+        ConnectionError: If a pattern matches no device
+    """
+    if is_url(port) or not glob.has_magic(port):
+        return port
+    matches = sorted(glob.glob(port))
+    if not matches:
+        hint = device_hint()
+        raise ConnectionError(f"No serial port matches {port}" + (f". {hint}" if hint else ""))
+    if len(matches) > 1:
+        logger.warning("%s matches %s - using %s", port, ", ".join(matches), matches[0])
+    return matches[0]
+
+
+def _kld7_frame(header: bytes, payload: bytes = b"") -> bytes:
+    return header + struct.pack("<I", len(payload)) + payload
+
+
+def probe_ops243(port: str, open_port: Callable = serial.Serial) -> Optional[str]:
+    """
+    Ask a port for OmniPreSense module info.
+
+    Returns:
+        The product name (or "OPS243" if it isn't reported), None if the
+        device didn't answer like an OmniPreSense radar
+    """
+    with open_port(port=port, baudrate=OPS243_BAUD, timeout=PROBE_TIMEOUT) as ser:
+        ser.reset_input_buffer()
+        ser.write(b"??")
+        response = ser.read(1024).decode("ascii", errors="ignore")
+
+    for line in response.splitlines():
+        line = line.strip()
+        if not (line.startswith("{") and line.endswith("}")):
+            continue
+        try:
+            product = json.loads(line).get("Product")
+        except (ValueError, AttributeError):
+            continue
+        if product:
+            return str(product)
+    if any(name in response for name in ("OPS24", "OPS7243", "OmniPreSense")):
+        return "OPS243"
+    return None
+
+
+def probe_kld7(port: str, open_port: Callable = serial.Serial) -> bool:
+    """True if the port answers a K-LD7 INIT frame."""
+    with open_port(
+        port=port,
+        baudrate=KLD7_BAUD,
+        parity=serial.PARITY_EVEN,
+        timeout=PROBE_TIMEOUT,
+    ) as ser:
+        ser.reset_input_buffer()
+        ser.write(_kld7_frame(b"INIT", struct.pack("<I", 0)))  # Keep 115200 baud
+        response = ser.read(9)
+        if response[:4] == b"RESP":
+            ser.write(_kld7_frame(b"GBYE"))
+            return True
+    return False
+
+
+def probe_port(port: str, open_port: Callable = serial.Serial) -> Optional[DetectedRadar]:
+    """
+    Work out which radar, if any, is on a port.
+
+    The OPS243 query is tried first: its two-character commands are
+    harmless to other devices, while a K-LD7 INIT frame is not.
+    """
+    try:
+        product = probe_ops243(port, open_port)
+        if product:
+            return DetectedRadar(port=port, model=RadarModel.OPS243, product=product)
+        if probe_kld7(port, open_port):
+            return DetectedRadar(port=port, model=RadarModel.KLD7, product="K-LD7")
+    except (serial.SerialException, OSError) as e:
+        logger.debug("Could not probe %s: %s", port, e)
+    return None
+
+
+def detect_radars(
+    ports: Optional[List[str]] = None, open_port: Callable = serial.Serial
+) -> List[DetectedRadar]:
+    """
+    Probe serial ports for radars.
+
+    Args:
+        ports: Ports to probe (default: candidate_ports())
+        open_port: Serial port factory (for tests)
+
+    Returns:
+        Radars found, in port order
+    """
+    ports = candidate_ports() if ports is None else ports
+    radars = []
+    for port in ports:
+        radar = probe_port(port, open_port)
+        if radar:
+            logger.info("Found %s on %s", radar.product or radar.model.value, port)
+            radars.append(radar)
+    return radars
+
+
+def choose_radar(radars: List[DetectedRadar]) -> DetectedRadar:
+    """
+    Pick the radar to use from those detected.
+
+    The first radar of the most preferred supported model wins; others are
+    logged and left alone.
+
+    Raises:
+        ConnectionError: If no supported radar was found
+    """
+    for model in SUPPORTED_MODELS:
+        matches = [r for r in radars if r.model == model]
+        if matches:
+            chosen = matches[0]
+            for other in radars:
+                if other is not chosen:
+                    logger.warning(
+                        "Ignoring %s on %s (using %s on %s)",
+                        other.product or other.model.value,
+                        other.port,
+                        chosen.product or chosen.model.value,
+                        chosen.port,
+                    )
+            return chosen
+
+    if radars:
+        found = ", ".join(f"{r.product or r.model.value} on {r.port}" for r in radars)
+        raise ConnectionError(
+            f"Found {found}, but only OmniPreSense radars are supported so far"
+        )
+    raise ConnectionError(
+        device_hint()
+        or "No OPS243 radar found. Check USB connection and try specifying port manually."
+    )
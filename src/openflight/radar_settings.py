@@ -0,0 +1,173 @@
+"""
+Radar settings snapshots.
+
+Trying other sample rates or filters on the bench leaves the sensor in
+whatever state the experiment ended in. Save its settings first and put
+them back afterwards:
+
+    openflight-radar save-settings -o radar.json
+    ... experiment ...
+    openflight-radar restore-settings radar.json --persist
+
+A snapshot is JSON with the radar's own answers to its queries (product,
+firmware, serial number, units, sample rate, speed filter, FFT size),
+verbatim, which is what to attach to a bug report, and the commands that
+restore the configuration. Most OPS243 settings can't be read back, so
+those commands come from what openflight last sent the radar (its state
+cache), as long as the radar still holds them; settings that couldn't be
+captured are listed as "unrestorable".
+
+--persist writes the settings to the radar's flash (A!), so they survive
+a power cycle.
+"""
+
+import argparse
+import json
+import sys
+from datetime import datetime
+from typing import Dict, List, Optional
+
+from .ops243 import OPS243Radar
+
+SNAPSHOT_VERSION = 1
+
+
+def save_settings(radar: OPS243Radar) -> dict:
+    """
+    Snapshot a connected radar's settings.
+
+    Returns:
+        The snapshot (JSON-serializable)
+    """
+    readback = radar.read_settings()
+    settings = radar.restorable_settings(readback)
+    return {
+        "version": SNAPSHOT_VERSION,
+        "saved_at": datetime.now().isoformat(),
+        "port": radar.port,
+        "product": radar.product,
+        "serial_number": radar.get_serial_number(),
+        "readback": readback,
+        "settings": settings,
+        "unrestorable": [key for key in radar.golf_settings() if key not in settings],
+    }
+
+
+def restore_settings(
+    radar: OPS243Radar, snapshot: dict, persist: bool = False, force: bool = False
+) -> Dict[str, str]:
+    """
+    Put a connected radar back in a snapshot's configuration.
+
+    Args:
+        radar: Connected radar
+        snapshot: save_settings() result
+        persist: Also write the settings to the radar's flash (A!)
+        force: Restore even if the snapshot was taken from another radar
+
+    Returns:
+        The settings sent (setting name -> command)
+
+    Raises:
+        ValueError: If the snapshot is malformed or from another radar
+    """
+    settings = snapshot.get("settings") if isinstance(snapshot, dict) else None
+    if not isinstance(settings, dict) or not all(
+        isinstance(k, str) and isinstance(v, str) for k, v in settings.items()
+    ):
+        raise ValueError("not a radar settings snapshot")
+    saved_serial = snapshot.get("serial_number")
+    serial_number = radar.get_serial_number()
+    if not force and saved_serial and saved_serial != serial_number:
+        raise ValueError(
+            f"snapshot is from radar {saved_serial}, this one is {serial_number} "
+            "(--force restores it anyway)"
+        )
+    radar.apply_settings(settings)
+    if persist:
+        radar.save_config()
+    return settings
+
+
+def load_snapshot(path: str) -> dict:
+    """
+    Read a snapshot file.
+
+    Raises:
+        OSError: If the file can't be read
+        ValueError: If it isn't JSON
+    """
+    with open(path, encoding="utf-8") as f:
+        return json.load(f)
+
+
+def main(argv: Optional[List[str]] = None):
+    """CLI entry point: save or restore radar settings."""
+    parser = argparse.ArgumentParser(description="Save and restore OPS243 radar settings")
+    parser.add_argument("--port", "-p", help="Serial port (default: auto-detect)")
+    commands = parser.add_subparsers(dest="command", required=True)
+
+    save = commands.add_parser("save-settings", help="Snapshot the radar's settings")
+    save.add_argument("--output", "-o", help="Snapshot file (default: print it)")
+    save.add_argument(
+        "--persist", action="store_true", help="Also write the current settings to flash (A!)"
+    )
+
+    restore = commands.add_parser("restore-settings", help="Send a snapshot back to the radar")
+    restore.add_argument("snapshot", help="File written by save-settings")
+    restore.add_argument(
+        "--persist", action="store_true", help="Also write the settings to flash (A!)"
+    )
+    restore.add_argument(
+        "--force", action="store_true", help="Restore a snapshot taken from another radar"
+    )
+    args = parser.parse_args(argv)
+
+    snapshot = None
+    if args.command == "restore-settings":
+        try:
+            snapshot = load_snapshot(args.snapshot)
+        except (OSError, ValueError) as e:
+            parser.error(f"{args.snapshot}: {e}")
+
+    radar = OPS243Radar(port=args.port)
+    try:
+        radar.connect()
+    except ConnectionError as e:
+        print(f"Error: {e}")
+        return 1
+    try:
+        if args.command == "save-settings":
+            snapshot = save_settings(radar)
+            if args.persist:
+                radar.save_config()
+            text = json.dumps(snapshot, indent=2)
+            if args.output:
+                with open(args.output, "w", encoding="utf-8") as f:
+                    f.write(text + "\n")
+                print(f"Saved {len(snapshot['settings'])} settings to {args.output}")
+            else:
+                print(text)
+            if snapshot["unrestorable"]:
+                print(
+                    "Not captured (not sent by openflight since the radar was last "
+                    f"configured): {', '.join(snapshot['unrestorable'])}",
+                    file=sys.stderr,
+                )
+        else:
+            try:
+                settings = restore_settings(
+                    radar, snapshot, persist=args.persist, force=args.force
+                )
+            except ValueError as e:
+                print(f"Error: {args.snapshot}: {e}")
+                return 1
+            saved = " and saved to flash" if args.persist else ""
+            print(f"Restored {len(settings)} settings{saved}: {' '.join(settings.values())}")
+    finally:
+        radar.disconnect()
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
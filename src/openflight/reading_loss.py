@@ -0,0 +1,176 @@
+"""
+Radar frames lost within a shot, and the ball speed they take with them.
+
+While it sees a target the radar reports at a steady rate, so a gap in
+a shot's readings much longer than the usual spacing is frames that
+never arrived: dropped on a lossy USB link or over the ESP32 bridge.
+The spacing is the median of the whole shot's, and a gap of more than
+GAP_FACTOR spacings is counted as round(gap / spacing) - 1 lost frames.
+
+Ball speed is the fastest ball reading, which is the ball's first frame:
+drag slows it from there. When the first frames are the ones lost, the
+fastest reading is already a slowed one. Over the ~100 ms the radar sees
+the ball, the slowdown is close to a straight line, so the ball's
+readings are fitted with one and the fit taken back to where the first
+ball frame should have been, one spacing after the last club frame:
+
+    ball speed = max(fastest reading, fit at the first lost frame)
+
+The fit's slope is capped at MAX_DECAY_MPH_PER_SEC, so a noisy fit
+can't add more than drag could have taken off.
+
+Gaps of up to max_filled_frames lost frames are filled in by
+interpolation before fitting, so the fit weighs each stretch of the
+flight by its length rather than by how many of its readings survived.
+Longer gaps, at the start or within the ball's readings, are reported
+but not filled.
+"""
+
+import statistics
+from dataclasses import dataclass
+from typing import List, Optional, Sequence, Tuple
+
+GAP_FACTOR = 1.5  # Gaps longer than this many spacings have lost frames
+BALL_FRACTION = 0.9  # Readings this close to the fastest are the ball
+# Drag slows a 170 mph ball by ~50 mph/s; a steeper fit is noise
+MAX_DECAY_MPH_PER_SEC = 100.0
+MIN_FIT_POINTS = 3
+
+Point = Tuple[float, float]  # (time, speed)
+
+
+@dataclass(frozen=True)
+class ReadingLoss:
+    """Lost frames in one shot."""
+
+    spacing_ms: Optional[float]  # Usual time between frames (None under 2 frames)
+    gaps: int  # Gaps with lost frames
+    lost_frames: int  # Within the ball's readings and before the first
+    max_gap_ms: Optional[float]  # Longest time between ball frames (None under 2)
+    lost_leading: int = 0  # Ball frames lost between the club and the first ball frame
+
+
+def frame_spacing(times: Sequence[float]) -> Optional[float]:
+    """Median time between consecutive frames (None under two distinct times)."""
+    ordered = sorted(set(times))
+    spacings = [b - a for a, b in zip(ordered, ordered[1:])]
+    return statistics.median(spacings) if spacings else None
+
+
+def lost_between(start: float, end: float, spacing: float) -> int:
+    """Frames lost between two frames spacing apart at the usual rate."""
+    if end - start <= GAP_FACTOR * spacing:
+        return 0
+    return max(round((end - start) / spacing) - 1, 1)
+
+
+def fill_gaps(points: Sequence[Point], spacing: float, max_frames: int) -> List[Point]:
+    """
+    Points with gaps of up to max_frames lost frames interpolated.
+
+    Args:
+        points: (time, speed) in time order
+        spacing: Usual time between frames
+        max_frames: Longest run of lost frames to fill
+    """
+    filled = list(points[:1])
+    for (t0, v0), (t1, v1) in zip(points, points[1:]):
+        lost = lost_between(t0, t1, spacing)
+        if 0 < lost <= max_frames:
+            for i in range(1, lost + 1):
+                fraction = i / (lost + 1)
+                filled.append((t0 + fraction * (t1 - t0), v0 + fraction * (v1 - v0)))
+        filled.append((t1, v1))
+    return filled
+
+
+def fit_line(points: Sequence[Point]) -> Optional[Tuple[float, float]]:
+    """
+    Least-squares line through points.
+
+    Returns:
+        (speed at time 0, slope), or None under MIN_FIT_POINTS points or
+        with all points at one time
+    """
+    if len(points) < MIN_FIT_POINTS:
+        return None
+    mean_t = statistics.mean(t for t, _ in points)
+    mean_v = statistics.mean(v for _, v in points)
+    var_t = sum((t - mean_t) ** 2 for t, _ in points)
+    if var_t == 0:
+        return None
+    slope = sum((t - mean_t) * (v - mean_v) for t, v in points) / var_t
+    return mean_v - slope * mean_t, slope
+
+
+def ball_points(readings: Sequence, ball_speed: float) -> List[Point]:
+    """
+    The ball's frames as (time, speed), one per frame in time order.
+
+    Args:
+        readings: The shot's SpeedReadings (frame_objects included)
+        ball_speed: Fastest ball reading
+    """
+    points = []
+    for r in readings:
+        if r.timestamp is None:
+            continue
+        speeds = [r.speed, *(o.speed for o in r.frame_objects or [])]
+        ball = [s for s in speeds if BALL_FRACTION * ball_speed <= s <= ball_speed]
+        if ball:
+            points.append((r.timestamp, max(ball)))
+    points.sort()
+    return points
+
+
+def analyze_loss(
+    readings: Sequence,
+    ball_speed: float,
+    max_filled_frames: int = 3,
+) -> Tuple[ReadingLoss, Optional[float]]:
+    """
+    Find the shot's lost frames and the ball speed they hid.
+
+    Args:
+        readings: The shot's SpeedReadings
+        ball_speed: Fastest ball reading
+        max_filled_frames: Longest run of lost frames to fill (0 = none)
+
+    Returns:
+        The loss, and the ball speed at the first lost ball frame when
+        that is faster than ball_speed (otherwise None)
+    """
+    times = [r.timestamp for r in readings if r.timestamp is not None]
+    spacing = frame_spacing(times)
+    ball = ball_points(readings, ball_speed)
+    if spacing is None or not ball:
+        return ReadingLoss(spacing_ms=None, gaps=0, lost_frames=0, max_gap_ms=None), None
+
+    ball_times = [t for t, _ in ball]
+    interior = [lost_between(a, b, spacing) for a, b in zip(ball_times, ball_times[1:])]
+    earlier = [t for t in times if t < ball_times[0]]
+    leading = lost_between(max(earlier), ball_times[0], spacing) if earlier else 0
+    gaps = [lost for lost in [leading, *interior] if lost]
+    loss = ReadingLoss(
+        spacing_ms=spacing * 1000,
+        gaps=len(gaps),
+        lost_frames=sum(gaps),
+        max_gap_ms=(
+            max(b - a for a, b in zip(ball_times, ball_times[1:])) * 1000
+            if len(ball_times) > 1
+            else None
+        ),
+        lost_leading=leading,
+    )
+
+    if not 0 < leading <= max_filled_frames:
+        return loss, None
+    # Timed from the first ball frame, so the fit is at the lost frames' scale
+    start = ball_times[0]
+    fit = fit_line(fill_gaps([(t - start, v) for t, v in ball], spacing, max_filled_frames))
+    if fit is None or fit[1] >= 0:
+        return loss, None  # Not slowing down: nothing to take back
+    intercept, slope = fit
+    back = leading * spacing
+    speed = min(intercept - slope * back, ball_speed + MAX_DECAY_MPH_PER_SEC * back)
+    return loss, speed if speed > ball_speed else None
@@ -0,0 +1,192 @@
+"""
+Personal records per club.
+
+Every player's best ball speed and carry with each club, across all their
+sessions. The server reads the records from the session logs at startup
+and checks each shot against them; a new one is celebrated on the
+console, on /api/stream and the UI ("personal_record" event), to the
+webhooks, and out loud with --speak-records:
+
+    🎉🎉🎉 NEW PERSONAL RECORD! 🎉🎉🎉
+    Driver ball speed: 158.3 mph (old record 155.1 mph, +3.2)
+
+Records are kept per "player" session tag, like the percentile context
+(see percentiles). A club's first shot only sets the record; there's
+nothing to beat yet, so it isn't announced. Mock and injected shots never
+count. /api/stats has the player's table under "personal_records", and
+the HTML report lists the best shots of the sessions it covers.
+"""
+
+import threading
+from dataclasses import asdict, dataclass
+from typing import Dict, List, Optional, Tuple
+
+from .export import CLUB_NAMES, field_value
+from .launch_monitor import ClubType
+from .sessions import read_shots
+
+# Metric name -> (shot field, unit, spoken name)
+RECORD_METRICS: Dict[str, Tuple[str, str, str]] = {
+    "ball_speed": ("ball_speed_mph", "mph", "ball speed"),
+    "carry": ("estimated_carry_yards", "yd", "carry"),
+}
+
+# Shots that did not come from a swing can't set records
+_SKIPPED_MODES = ("mock", "injected")
+
+_CLUB_ORDER = [c.value for c in ClubType]
+_SPOKEN_UNITS = {"mph": "miles per hour", "yd": "yards"}
+
+
+@dataclass
+class PersonalRecord:
+    """A player's best value of one metric with one club."""
+
+    player: Optional[str]
+    club: str
+    metric: str
+    value: float
+    date: Optional[str] = None  # YYYY-MM-DD of the shot
+
+    @property
+    def unit(self) -> str:
+        """The metric's unit."""
+        return RECORD_METRICS[self.metric][1]
+
+    def to_dict(self) -> dict:
+        """Serialize for the API, stream and webhooks."""
+        return {**asdict(self), "unit": self.unit}
+
+
+@dataclass
+class NewRecord:
+    """A shot that beat a personal record."""
+
+    record: PersonalRecord
+    previous: float
+
+    def to_dict(self) -> dict:
+        """Serialize for the stream and webhooks."""
+        return {**self.record.to_dict(), "previous": self.previous}
+
+    def describe(self) -> str:
+        """e.g. "Driver ball speed: 158.3 mph (old record 155.1 mph, +3.2)"."""
+        r = self.record
+        club = CLUB_NAMES.get(r.club, r.club)
+        metric = RECORD_METRICS[r.metric][2]
+        return (
+            f"{club} {metric}: {r.value:.1f} {r.unit} "
+            f"(old record {self.previous:.1f} {r.unit}, +{r.value - self.previous:.1f})"
+        )
+
+    def announcement(self) -> str:
+        """What to say, e.g. "New personal record! Driver ball speed, 158 miles per hour"."""
+        r = self.record
+        club = CLUB_NAMES.get(r.club, r.club)
+        metric = RECORD_METRICS[r.metric][2]
+        return f"New personal record! {club} {metric}, {r.value:.0f} {_SPOKEN_UNITS[r.unit]}"
+
+
+def _sort_key(record: PersonalRecord):
+    club = _CLUB_ORDER.index(record.club) if record.club in _CLUB_ORDER else len(_CLUB_ORDER)
+    return (record.player or "", club, list(RECORD_METRICS).index(record.metric))
+
+
+def best_shots(shots: List[dict]) -> List[PersonalRecord]:
+    """
+    The best value of each metric per player and club among shots.
+
+    Args:
+        shots: shot_detected log entries or shot_to_dict output
+
+    Returns:
+        Records ordered by player, then club (driver first), then metric
+    """
+    best: Dict[Tuple[Optional[str], str, str], PersonalRecord] = {}
+    for shot in shots:
+        if shot.get("mode") in _SKIPPED_MODES:
+            continue
+        player = (shot.get("tags") or {}).get("player")
+        club = shot.get("club") or ClubType.DRIVER.value
+        for metric, (field, _, _) in RECORD_METRICS.items():
+            value = shot.get(field)
+            if value is None:
+                continue
+            key = (player, club, metric)
+            if key not in best or value > best[key].value:
+                best[key] = PersonalRecord(
+                    player, club, metric, float(value), field_value(shot, "date")
+                )
+    return sorted(best.values(), key=_sort_key)
+
+
+class PersonalRecords:
+    """
+    The players' records, checked against each new shot.
+
+    Example:
+        records = PersonalRecords()
+        records.load(find_sessions([str(log_dir / "session_*.jsonl")]))
+        for new in records.check(shot_to_dict(shot), player="sam"):
+            print(new.describe())
+    """
+
+    def __init__(self):
+        self._records: Dict[Tuple[Optional[str], str, str], PersonalRecord] = {}
+        self._lock = threading.Lock()
+
+    def load(self, paths: List[str]) -> int:
+        """
+        Take the records from session logs.
+
+        Args:
+            paths: Session log files
+
+        Returns:
+            Number of records (player, club and metric combinations)
+        """
+        shots = [shot for path in paths for shot in read_shots(path)]
+        with self._lock:
+            for record in best_shots(shots):
+                key = (record.player, record.club, record.metric)
+                current = self._records.get(key)
+                if current is None or record.value > current.value:
+                    self._records[key] = record
+            return len(self._records)
+
+    def check(self, shot: dict, player: Optional[str] = None) -> List[NewRecord]:
+        """
+        Check a new shot against the records, updating them.
+
+        Args:
+            shot: The shot as shot_to_dict() gives it
+            player: The session's player tag
+
+        Returns:
+            The records it beat (none for a club's first shot)
+        """
+        if shot.get("mode") in _SKIPPED_MODES:
+            return []
+        club = shot.get("club") or ClubType.DRIVER.value
+        new = []
+        with self._lock:
+            for metric, (field, _, _) in RECORD_METRICS.items():
+                value = shot.get(field)
+                if value is None:
+                    continue
+                key = (player, club, metric)
+                current = self._records.get(key)
+                if current is not None and value <= current.value:
+                    continue
+                date = field_value(shot, "date")
+                record = PersonalRecord(player, club, metric, float(value), date)
+                self._records[key] = record
+                if current is not None:
+                    new.append(NewRecord(record, current.value))
+        return new
+
+    def table(self, player: Optional[str] = None) -> List[PersonalRecord]:
+        """A player's records, driver first."""
+        with self._lock:
+            records = [r for r in self._records.values() if r.player == player]
+        return sorted(records, key=_sort_key)
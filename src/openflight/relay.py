@@ -0,0 +1,372 @@
+"""
+Remote viewing through a WebSocket relay.
+
+With --relay-url, the monitor opens an outbound WebSocket connection to a
+relay server and pushes every /api/stream event (shots, mulligans, club
+changes, ready) over it. A coach connected to the same relay elsewhere
+watches the numbers live. The connection is outbound, so the Pi needs no
+port forwarding or public address.
+
+The relay token is sent in the handshake as "Authorization: Bearer
+<token>"; use a wss:// URL so it isn't sent in clear text. Each event is
+one JSON text message:
+
+    {"event": "shot", "data": {"shot": {...}, "stats": {...}}, "ts": "2025-06-01T10:15:30"}
+
+The connection is kept alive with pings and re-established with capped
+exponential backoff after a drop. While disconnected, the most recent
+events are queued and sent on reconnect; older ones are dropped.
+
+Only the client is built in (stdlib only, no extra dependency). Any relay
+that authenticates the monitor by its bearer token and forwards its
+messages to viewers works.
+"""
+
+import base64
+import hashlib
+import json
+import logging
+import os
+import queue
+import select
+import socket
+import ssl
+import struct
+import threading
+import time
+from datetime import datetime
+from typing import Any, Dict, Optional, Tuple
+from urllib.parse import urlsplit
+
+logger = logging.getLogger(__name__)
+
+# RFC 6455 opcodes
+OP_TEXT = 0x1
+OP_CLOSE = 0x8
+OP_PING = 0x9
+OP_PONG = 0xA
+
+_ACCEPT_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+
+
+def accept_key(key: str) -> str:
+    """Sec-WebSocket-Accept value a server must answer a handshake key with."""
+    digest = hashlib.sha1((key + _ACCEPT_GUID).encode("ascii")).digest()
+    return base64.b64encode(digest).decode("ascii")
+
+
+def encode_frame(opcode: int, payload: bytes, mask: Optional[bytes] = None) -> bytes:
+    """
+    Build one final WebSocket frame.
+
+    Args:
+        opcode: Frame type (OP_TEXT, OP_PING, ...)
+        payload: Frame data
+        mask: 4-byte masking key (clients must mask; None = unmasked)
+
+    Returns:
+        Frame bytes
+    """
+    header = bytes([0x80 | opcode])
+    mask_bit = 0x80 if mask else 0
+    length = len(payload)
+    if length < 126:
+        header += bytes([mask_bit | length])
+    elif length < 1 << 16:
+        header += bytes([mask_bit | 126]) + struct.pack(">H", length)
+    else:
+        header += bytes([mask_bit | 127]) + struct.pack(">Q", length)
+    if not mask:
+        return header + payload
+    return header + mask + bytes(b ^ mask[i % 4] for i, b in enumerate(payload))
+
+
+def _read_exact(sock: socket.socket, count: int) -> bytes:
+    data = b""
+    while len(data) < count:
+        chunk = sock.recv(count - len(data))
+        if not chunk:
+            raise ConnectionError("Relay closed the connection")
+        data += chunk
+    return data
+
+
+def read_frame(sock: socket.socket) -> Tuple[int, bytes]:
+    """
+    Read one WebSocket frame.
+
+    Returns:
+        (opcode, payload), unmasked
+
+    Raises:
+        ConnectionError: If the connection closes mid-frame
+    """
+    first, second = _read_exact(sock, 2)
+    length = second & 0x7F
+    if length == 126:
+        (length,) = struct.unpack(">H", _read_exact(sock, 2))
+    elif length == 127:
+        (length,) = struct.unpack(">Q", _read_exact(sock, 8))
+    mask = _read_exact(sock, 4) if second & 0x80 else None
+    payload = _read_exact(sock, length)
+    if mask:
+        payload = bytes(b ^ mask[i % 4] for i, b in enumerate(payload))
+    return first & 0x0F, payload
+
+
+class RelayClient:
+    """
+    Pushes stream events to a WebSocket relay on a background thread.
+
+    Example:
+        relay = RelayClient("wss://relay.example.com/monitor", token="s3cret")
+        relay.start()
+        relay.send("shot", {"shot": {...}, "stats": {...}})
+        ...
+        relay.stop()
+    """
+
+    MAX_QUEUED_EVENTS = 100  # Kept while disconnected; older ones are dropped
+    PING_INTERVAL_SEC = 20.0  # Keeps proxies from closing an idle connection
+    BACKOFF_SEC = 1.0  # Delay before the first reconnect, doubled each failure
+    MAX_BACKOFF_SEC = 60.0
+    TIMEOUT_SEC = 10.0  # Connect and handshake timeout
+
+    def __init__(self, url: str, token: Optional[str] = None):
+        """
+        Initialize relay client.
+
+        Args:
+            url: ws:// or wss:// relay URL
+            token: Bearer token the relay authenticates the monitor with
+
+        Raises:
+            ValueError: If the URL isn't a WebSocket URL
+        """
+        parts = urlsplit(url)
+        if parts.scheme not in ("ws", "wss") or not parts.hostname:
+            raise ValueError(f"Relay URL must be ws:// or wss://, got {url!r}")
+        self.url = url
+        self.token = token
+        self._secure = parts.scheme == "wss"
+        self._host = parts.hostname
+        self._port = parts.port or (443 if self._secure else 80)
+        self._path = (parts.path or "/") + (f"?{parts.query}" if parts.query else "")
+
+        self._queue: "queue.Queue[Dict[str, Any]]" = queue.Queue(self.MAX_QUEUED_EVENTS)
+        self._pending: Optional[Dict[str, Any]] = None
+        self._sock: Optional[socket.socket] = None
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    @property
+    def connected(self) -> bool:
+        """Whether the relay connection is open."""
+        return self._sock is not None
+
+    def to_dict(self) -> dict:
+        """Connection state for GET /api/state."""
+        return {"url": self.url, "connected": self.connected, "queued": self._queue.qsize()}
+
+    def start(self):
+        """Start connecting and sending on a background thread."""
+        if self._thread and self._thread.is_alive():
+            return
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._worker, daemon=True)
+        self._thread.start()
+
+    def stop(self, timeout: float = 5.0):
+        """Close the connection and stop the thread."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=timeout)
+            self._thread = None
+
+    def send(self, event: str, data: Dict[str, Any]):
+        """
+        Queue an event for the relay (never blocks).
+
+        Args:
+            event: Event name, as on /api/stream
+            data: JSON-serializable event data
+        """
+        message = {"event": event, "data": data, "ts": datetime.now().isoformat()}
+        while True:
+            try:
+                self._queue.put_nowait(message)
+                return
+            except queue.Full:
+                try:
+                    self._queue.get_nowait()
+                except queue.Empty:
+                    pass
+
+    def connect(self) -> socket.socket:
+        """
+        Open the connection and complete the WebSocket handshake.
+
+        Returns:
+            Connected socket
+
+        Raises:
+            ConnectionError: If the relay is unreachable or refuses the handshake
+            PermissionError: If the relay rejects the token (401/403)
+        """
+        try:
+            sock = socket.create_connection((self._host, self._port), timeout=self.TIMEOUT_SEC)
+        except OSError as e:
+            raise ConnectionError(f"Can't reach relay {self._host}:{self._port}: {e}") from e
+        try:
+            if self._secure:
+                context = ssl.create_default_context()
+                sock = context.wrap_socket(sock, server_hostname=self._host)
+            key = base64.b64encode(os.urandom(16)).decode("ascii")
+            lines = [
+                f"GET {self._path} HTTP/1.1",
+                f"Host: {self._host}:{self._port}",
+                "Upgrade: websocket",
+                "Connection: Upgrade",
+                f"Sec-WebSocket-Key: {key}",
+                "Sec-WebSocket-Version: 13",
+                "User-Agent: OpenFlight-Relay",
+            ]
+            if self.token:
+                lines.append(f"Authorization: Bearer {self.token}")
+            sock.sendall(("\r\n".join(lines) + "\r\n\r\n").encode("ascii"))
+
+            # Byte by byte, so no frame sent right after the handshake is swallowed
+            response = b""
+            while not response.endswith(b"\r\n\r\n"):
+                chunk = sock.recv(1)
+                if not chunk:
+                    raise ConnectionError("Relay closed the connection during the handshake")
+                response += chunk
+                if len(response) > 16384:
+                    raise ConnectionError("Relay sent an oversized handshake response")
+            head = response[:-4].decode("latin-1").split("\r\n")
+            status = head[0].split(" ")
+            code = status[1] if len(status) > 1 else "?"
+            if code in ("401", "403"):
+                raise PermissionError(f"Relay rejected the token ({head[0]})")
+            if code != "101":
+                raise ConnectionError(f"Relay refused the WebSocket upgrade ({head[0]})")
+            headers = {
+                name.strip().lower(): value.strip()
+                for name, _, value in (line.partition(":") for line in head[1:])
+            }
+            if headers.get("sec-websocket-accept") != accept_key(key):
+                raise ConnectionError("Relay answered with a bad Sec-WebSocket-Accept")
+        except OSError:
+            sock.close()
+            raise
+        sock.settimeout(self.TIMEOUT_SEC)
+        return sock
+
+    def _send_frame(self, opcode: int, payload: bytes):
+        self._sock.sendall(encode_frame(opcode, payload, mask=os.urandom(4)))
+
+    def _readable(self) -> bool:
+        # TLS can hold decrypted bytes that select() doesn't see
+        if isinstance(self._sock, ssl.SSLSocket) and self._sock.pending():
+            return True
+        return bool(select.select([self._sock], [], [], 0)[0])
+
+    def _handle_incoming(self):
+        """Answer pings and notice closes (viewers' messages are ignored)."""
+        while self._readable():
+            opcode, payload = read_frame(self._sock)
+            if opcode == OP_PING:
+                self._send_frame(OP_PONG, payload)
+            elif opcode == OP_CLOSE:
+                raise ConnectionError("Relay closed the connection")
+
+    def _close(self):
+        if not self._sock:
+            return
+        try:
+            self._send_frame(OP_CLOSE, struct.pack(">H", 1000))
+        except OSError:
+            pass
+        self._sock.close()
+        self._sock = None
+
+    def _worker(self):
+        """Connect, send queued events and keep the connection alive until stopped."""
+        backoff = self.BACKOFF_SEC
+        while not self._stop_event.is_set():
+            try:
+                self._sock = self.connect()
+            except PermissionError as e:
+                logger.error("Relay %s: %s", self.url, e)
+                backoff = self.MAX_BACKOFF_SEC
+            except OSError as e:  # Unreachable, TLS or handshake failure
+                logger.warning("Relay %s: %s (retrying in %.0fs)", self.url, e, backoff)
+            else:
+                logger.info("Connected to relay %s", self.url)
+                backoff = self.BACKOFF_SEC
+                try:
+                    self._session()
+                except OSError as e:
+                    logger.warning("Relay connection lost: %s", e)
+                finally:
+                    self._close()
+            if self._stop_event.wait(backoff):
+                break
+            backoff = min(backoff * 2, self.MAX_BACKOFF_SEC)
+
+    def _session(self):
+        """Send events over an open connection until it drops or the client stops."""
+        last_sent = time.monotonic()
+        while not self._stop_event.is_set():
+            if self._pending is None:
+                try:
+                    self._pending = self._queue.get(timeout=0.5)
+                except queue.Empty:
+                    pass
+            if self._pending is not None:
+                # Kept until sent, so a message the connection dropped on goes out again
+                self._send_frame(OP_TEXT, json.dumps(self._pending).encode("utf-8"))
+                self._pending = None
+                last_sent = time.monotonic()
+            elif time.monotonic() - last_sent > self.PING_INTERVAL_SEC:
+                self._send_frame(OP_PING, b"")
+                last_sent = time.monotonic()
+            self._handle_incoming()
+
+
+# Global relay client instance
+_relay: Optional[RelayClient] = None
+
+
+def get_relay() -> Optional[RelayClient]:
+    """Get the global relay client (None if no relay is configured)."""
+    return _relay
+
+
+def init_relay(url: Optional[str], token: Optional[str] = None) -> Optional[RelayClient]:
+    """
+    Initialize and start the global relay client.
+
+    Args:
+        url: Relay URL; None disables the relay
+        token: Bearer token for the relay
+
+    Returns:
+        RelayClient instance, or None if no URL was given
+
+    Raises:
+        ValueError: If the URL isn't a WebSocket URL
+    """
+    global _relay  # pylint: disable=global-statement
+
+    if _relay:
+        _relay.stop()
+        _relay = None
+
+    if not url:
+        return None
+
+    _relay = RelayClient(url, token=token)
+    _relay.start()
+    return _relay
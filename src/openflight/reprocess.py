@@ -0,0 +1,375 @@
+"""
+Offline reprocessing of recorded sessions.
+
+Reruns the current shot detector and carry model over the shots stored in
+session logs, so improvements to detection or physics can update historical
+stats. Sessions are processed in parallel, one worker process per file, and
+each input gets a sibling output file:
+
+    session_20250101_120000_range.jsonl -> session_20250101_120000_range.reprocessed.jsonl
+
+Shots logged with their readings are re-detected from those readings (a
+shot the current detector rejects is reported as such). Shots without
+readings (rolling-buffer mode) keep their measured speeds and only get the
+carry model rerun.
+
+Usage:
+    openflight-reprocess "~/openflight_sessions/session_*.jsonl"
+    openflight-reprocess "logs/**/*.jsonl" --profile garage-net --jobs 4
+"""
+
+import argparse
+import glob
+import json
+import os
+import sys
+from concurrent.futures import ProcessPoolExecutor
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, List, Optional
+
+from .carry import set_carry_models
+from .geometry import MountingGeometry, apply_cosine_correction
+from .launch_monitor import ClubType, LaunchMonitor, MonitorConfig, Shot
+from .ops243 import Direction, SpeedReading
+from .profiles import load_profile
+
+OUTPUT_SUFFIX = ".reprocessed.jsonl"
+
+# Shots that did not come from the radar have nothing to re-detect
+_SKIPPED_MODES = ("mock", "injected")
+
+
+@dataclass
+class ReprocessResult:
+    """Summary of reprocessing one session file."""
+
+    path: str
+    output_path: str
+    shots: int = 0
+    redetected: int = 0
+    rejected: int = 0
+    skipped: int = 0
+    error: Optional[str] = None
+
+
+def readings_from_entry(entry: dict) -> List[SpeedReading]:
+    """
+    Rebuild SpeedReadings from a logged shot_detected entry.
+
+    Args:
+        entry: shot_detected log entry with a "readings" list
+
+    Returns:
+        Readings in logged order (empty if none were logged)
+    """
+
+    def to_reading(data: dict, timestamp: Optional[float]) -> SpeedReading:
+        return SpeedReading(
+            speed=data["speed"],
+            direction=Direction(data["direction"]),
+            magnitude=data.get("magnitude"),
+            timestamp=timestamp,
+        )
+
+    readings = []
+    for data in entry.get("readings") or []:
+        reading = to_reading(data, data.get("timestamp"))
+        if data.get("frame_objects"):
+            reading.frame_objects = [
+                to_reading(o, reading.timestamp) for o in data["frame_objects"]
+            ]
+        readings.append(reading)
+    return readings
+
+
+def _club_from_entry(entry: dict) -> ClubType:
+    try:
+        return ClubType(entry.get("club", "driver"))
+    except ValueError:
+        return ClubType.UNKNOWN
+
+
+def shot_from_entry(entry: dict) -> Shot:
+    """
+    Rebuild a Shot from a logged shot_detected entry, as it was logged.
+
+    Args:
+        entry: shot_detected log entry
+
+    Returns:
+        Shot with the logged speeds, club, spin and launch angles
+    """
+    return Shot(
+        ball_speed_mph=entry["ball_speed_mph"],
+        club_speed_mph=entry.get("club_speed_mph"),
+        timestamp=datetime.now(),
+        peak_magnitude=entry.get("peak_magnitude"),
+        club=_club_from_entry(entry),
+        launch_angle_vertical=entry.get("launch_angle_vertical"),
+        launch_angle_horizontal=entry.get("launch_angle_horizontal"),
+        launch_angle_confidence=entry.get("launch_angle_confidence"),
+        spin_rpm=entry.get("spin_rpm"),
+        spin_confidence=entry.get("spin_confidence"),
+        carry_spin_adjusted=entry.get("carry_spin_adjusted"),
+        mode=entry.get("mode", "streaming"),
+        ball_speed_raw_mph=entry.get("ball_speed_raw_mph"),
+        club_speed_raw_mph=entry.get("club_speed_raw_mph"),
+        hole_number=entry.get("hole_number"),
+        hole_shot=entry.get("hole_shot"),
+        target_yards=entry.get("target_yards"),
+        strokes_gained=entry.get("strokes_gained"),
+        ball_type=entry.get("ball_type"),
+    )
+
+
+def reprocess_entry(
+    entry: dict,
+    monitor: LaunchMonitor,
+    geometry: Optional[MountingGeometry] = None,
+) -> Optional[dict]:
+    """
+    Rerun detection and physics for one logged shot.
+
+    Args:
+        entry: shot_detected log entry
+        monitor: Detector to run (not connected to a radar)
+        geometry: Mounting geometry for cosine correction (None = reuse the
+            correction the shot was logged with)
+
+    Returns:
+        shot_reprocessed entry, or None if the shot was not from the radar
+    """
+    mode = entry.get("mode", "streaming")
+    if mode in _SKIPPED_MODES:
+        return None
+
+    club = _club_from_entry(entry)
+    readings = readings_from_entry(entry)
+    monitor.set_club(club)
+
+    if readings:
+        shot = monitor.analyze_readings(readings)
+        if shot is None:
+            return {
+                "type": "shot_reprocessed",
+                "shot_number": entry.get("shot_number"),
+                "ts": entry.get("ts"),
+                "status": "rejected",
+                "original": _shot_fields(entry),
+            }
+        shot.mode = mode
+        if geometry is not None:
+            apply_cosine_correction(shot, geometry)
+        else:
+            _reapply_logged_correction(shot, entry)
+        status = "redetected"
+    else:
+        # No readings logged: keep the measured speeds, rerun the physics only
+        shot = shot_from_entry(entry)
+        status = "physics_only"
+
+    # Launch angles come from the camera and can't be recomputed from the log
+    shot.launch_angle_vertical = entry.get("launch_angle_vertical")
+    shot.launch_angle_horizontal = entry.get("launch_angle_horizontal")
+    shot.launch_angle_confidence = entry.get("launch_angle_confidence")
+
+    return {
+        "type": "shot_reprocessed",
+        "shot_number": entry.get("shot_number"),
+        "ts": entry.get("ts"),
+        "status": status,
+        "ball_speed_mph": shot.ball_speed_mph,
+        "club_speed_mph": shot.club_speed_mph,
+        "smash_factor": shot.smash_factor,
+        "estimated_carry_yards": shot.estimated_carry_yards,
+        "club": shot.club.value,
+        "mode": shot.mode,
+        "ball_speed_raw_mph": shot.ball_speed_raw_mph,
+        "club_speed_raw_mph": shot.club_speed_raw_mph,
+        "hole_number": entry.get("hole_number"),
+        "hole_shot": entry.get("hole_shot"),
+        "original": _shot_fields(entry),
+    }
+
+
+def _reapply_logged_correction(shot: Shot, entry: dict):
+    """Scale re-detected (raw) speeds by the cosine correction the shot was logged with."""
+    raw = entry.get("ball_speed_raw_mph")
+    if not raw:
+        return
+    factor = entry["ball_speed_mph"] / raw
+    shot.ball_speed_raw_mph = shot.ball_speed_mph
+    shot.club_speed_raw_mph = shot.club_speed_mph
+    shot.ball_speed_mph *= factor
+    if shot.club_speed_mph is not None:
+        shot.club_speed_mph *= factor
+
+
+def _shot_fields(entry: dict) -> dict:
+    """Headline numbers of a logged shot, kept for before/after comparison."""
+    keys = ("ball_speed_mph", "club_speed_mph", "smash_factor", "estimated_carry_yards")
+    return {key: entry.get(key) for key in keys}
+
+
+def output_path_for(path: Path, output_dir: Optional[Path] = None) -> Path:
+    """Where the reprocessed copy of a session file is written."""
+    name = path.name[: -len(".jsonl")] if path.name.endswith(".jsonl") else path.name
+    return (output_dir or path.parent) / f"{name}{OUTPUT_SUFFIX}"
+
+
+def reprocess_file(
+    path: str,
+    config: Optional[MonitorConfig] = None,
+    geometry: Optional[MountingGeometry] = None,
+    use_iq_streaming: bool = True,
+    output_dir: Optional[str] = None,
+    carry_models: Optional[Dict[str, dict]] = None,
+    club_timing: Optional[Dict[str, Dict[str, float]]] = None,
+) -> ReprocessResult:
+    """
+    Reprocess every shot in one session log.
+
+    Runs in a worker process, so all arguments are plain picklable values.
+
+    Args:
+        path: Session JSONL file
+        config: Shot detection thresholds (None = current defaults)
+        geometry: Mounting geometry for cosine correction (None = as logged)
+        use_iq_streaming: Whether the session was recorded in I/Q streaming mode
+            (legacy radar-internal sessions also get the magnitude checks)
+        output_dir: Directory for the output file (default: next to the input)
+        carry_models: Per-club carry model selection (None = keep current)
+        club_timing: Per-club shot timing (see LaunchMonitor.set_club_timing)
+
+    Returns:
+        Summary of what was reprocessed
+    """
+    source = Path(path)
+    output = output_path_for(source, Path(output_dir) if output_dir else None)
+    result = ReprocessResult(path=str(source), output_path=str(output))
+    if carry_models is not None:
+        set_carry_models(carry_models)
+    monitor = LaunchMonitor(use_iq_streaming=use_iq_streaming, config=config)
+    if club_timing:
+        monitor.set_club_timing(club_timing)
+
+    try:
+        with open(source, encoding="utf-8") as f_in, open(output, "w", encoding="utf-8") as f_out:
+            for line in f_in:
+                try:
+                    entry = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                if entry.get("type") != "shot_detected":
+                    continue
+
+                result.shots += 1
+                updated = reprocess_entry(entry, monitor, geometry)
+                if updated is None:
+                    result.skipped += 1
+                    continue
+                if updated["status"] == "rejected":
+                    result.rejected += 1
+                elif updated["status"] == "redetected":
+                    result.redetected += 1
+                f_out.write(json.dumps(updated) + "\n")
+    except OSError as e:
+        result.error = str(e)
+
+    return result
+
+
+def find_sessions(patterns: List[str]) -> List[str]:
+    """Expand glob patterns to session files, skipping earlier reprocessed output."""
+    paths = set()
+    for pattern in patterns:
+        for match in glob.glob(os.path.expanduser(pattern), recursive=True):
+            if not match.endswith(OUTPUT_SUFFIX) and os.path.isfile(match):
+                paths.add(match)
+    return sorted(paths)
+
+
+def _silence_worker():
+    """Drop the detector's per-shot console output in worker processes."""
+    sys.stdout = open(os.devnull, "w", encoding="utf-8")  # pylint: disable=consider-using-with
+
+
+def main():
+    """CLI entry point for batch reprocessing."""
+    parser = argparse.ArgumentParser(
+        description="Rerun shot detection and carry estimation over recorded sessions"
+    )
+    parser.add_argument("patterns", nargs="+", help="Session log files or glob patterns")
+    parser.add_argument(
+        "--jobs", "-j", type=int, default=os.cpu_count() or 1, help="Parallel worker processes"
+    )
+    parser.add_argument(
+        "--profile",
+        help="Apply a calibration profile's thresholds, mounting geometry and carry models",
+    )
+    parser.add_argument("--profile-dir", help="Directory for calibration profiles")
+    parser.add_argument(
+        "--no-iq-streaming",
+        action="store_true",
+        help="Sessions were recorded with the radar's internal processing",
+    )
+    parser.add_argument("--output-dir", help="Write outputs here instead of next to each input")
+    args = parser.parse_args()
+
+    config = None
+    geometry = None
+    carry_models = None
+    club_timing = None
+    if args.profile:
+        profile_dir = Path(args.profile_dir).expanduser() if args.profile_dir else None
+        try:
+            profile = load_profile(args.profile, profile_dir)
+        except (FileNotFoundError, ValueError) as e:
+            parser.error(str(e))
+        config = profile.monitor.with_updates(profile.shot_timing.for_mode("streaming"))
+        geometry = profile.mounting
+        carry_models = profile.carry_models
+        club_timing = profile.shot_timing.clubs
+
+    paths = find_sessions(args.patterns)
+    if not paths:
+        print("No session files matched")
+        sys.exit(1)
+    if args.output_dir:
+        Path(args.output_dir).mkdir(parents=True, exist_ok=True)
+
+    print(f"Reprocessing {len(paths)} session(s) with {args.jobs} worker(s)")
+    failed = 0
+    with ProcessPoolExecutor(max_workers=max(1, args.jobs), initializer=_silence_worker) as pool:
+        futures = [
+            pool.submit(
+                reprocess_file,
+                path,
+                config,
+                geometry,
+                not args.no_iq_streaming,
+                args.output_dir,
+                carry_models,
+                club_timing,
+            )
+            for path in paths
+        ]
+        for future in futures:
+            result = future.result()
+            if result.error:
+                failed += 1
+                print(f"[FAILED] {result.path}: {result.error}")
+                continue
+            print(
+                f"[DONE] {result.path} -> {result.output_path}: {result.shots} shots "
+                f"({result.redetected} re-detected, {result.rejected} now rejected, "
+                f"{result.skipped} skipped)"
+            )
+
+    sys.exit(1 if failed else 0)
+
+
+if __name__ == "__main__":
+    main()
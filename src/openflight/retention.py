@@ -0,0 +1,250 @@
+"""
+Retention for session logs.
+
+Per-reading telemetry (accepted readings, I/Q blocks, raw rolling buffer
+samples, the readings behind and just before each shot and the raw radar
+serial log) is what makes a session reprocessable, and also what fills a
+Pi's SD card.
+After a number of days it is dropped: session logs are compacted to their
+shot-level entries (shots, camera data, deliveries, config changes, tags)
+and raw radar logs are deleted. Optionally, whole sessions (with their
+voice memos) are deleted after a longer period.
+
+Compacted shots can still be reprocessed with new carry models, but not
+re-detected (see reprocess_entry's "physics_only" status).
+
+Usage:
+    openflight-vacuum --keep-days 30
+    openflight-vacuum --keep-days 14 --delete-after 365 --dry-run
+    openflight-server --retain-readings-days 30
+"""
+
+import argparse
+import json
+import os
+import time
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import List, Optional
+
+from .reprocess import OUTPUT_SUFFIX
+from .session_logger import SessionLogger
+from .voice_memo import session_memos
+
+DEFAULT_KEEP_DAYS = 30
+
+# Entry types that are nothing but per-reading telemetry
+_DROPPED_TYPES = ("reading_accepted", "iq_reading", "iq_blocks")
+
+# Per-reading fields stripped from entries that are otherwise kept
+_STRIPPED_FIELDS = {
+    "shot_detected": ("readings", "pre_trigger_readings"),
+    "rolling_buffer_capture": ("i_samples", "q_samples"),
+    "trigger_diagnostic": ("all_outbound_speeds", "all_inbound_speeds"),
+}
+
+COMPACTED_TYPE = "session_compacted"
+
+
+@dataclass
+class CompactResult:
+    """Outcome of compacting one session log."""
+
+    path: str
+    bytes_before: int = 0
+    bytes_after: int = 0
+    dropped_entries: int = 0
+    already_compacted: bool = False
+
+
+@dataclass
+class VacuumResult:
+    """What a vacuum pass did (or would do, for a dry run)."""
+
+    compacted: List[CompactResult] = field(default_factory=list)
+    deleted: List[str] = field(default_factory=list)
+    bytes_freed: int = 0
+
+
+def _compact_entry(entry: dict) -> Optional[dict]:
+    """Entry without its per-reading telemetry, or None to drop it."""
+    entry_type = entry.get("type")
+    if entry_type in _DROPPED_TYPES:
+        return None
+    stripped = _STRIPPED_FIELDS.get(entry_type, ())
+    return {k: v for k, v in entry.items() if k not in stripped}
+
+
+def compact_session(path: str, dry_run: bool = False) -> CompactResult:
+    """
+    Rewrite a session log with shot-level entries only.
+
+    The file is replaced atomically and keeps its modification time, so
+    retention ages stay the same. A session_compacted entry is appended
+    so the file is left alone next time.
+
+    Args:
+        path: Session JSONL file
+        dry_run: Only work out how much would be saved
+
+    Returns:
+        Sizes before and after, and how many entries were dropped
+    """
+    source = Path(path)
+    result = CompactResult(path=str(source), bytes_before=source.stat().st_size)
+    temp = source.with_name(source.name + ".compacting")
+
+    lines = []
+    with open(source, encoding="utf-8") as f:
+        for line in f:
+            try:
+                entry = json.loads(line)
+            except ValueError:
+                continue
+            if entry.get("type") == COMPACTED_TYPE:
+                result.already_compacted = True
+                result.bytes_after = result.bytes_before
+                return result
+            compacted = _compact_entry(entry)
+            if compacted is None:
+                result.dropped_entries += 1
+                continue
+            lines.append(json.dumps(compacted) + "\n")
+
+    lines.append(
+        json.dumps(
+            {
+                "ts": datetime.now().isoformat(),
+                "type": COMPACTED_TYPE,
+                "dropped_entries": result.dropped_entries,
+                "bytes_before": result.bytes_before,
+            }
+        )
+        + "\n"
+    )
+    result.bytes_after = sum(len(line.encode("utf-8")) for line in lines)
+    if dry_run:
+        return result
+
+    stat = source.stat()
+    with open(temp, "w", encoding="utf-8") as f:
+        f.writelines(lines)
+    os.utime(temp, (stat.st_atime, stat.st_mtime))
+    os.replace(temp, source)
+    return result
+
+
+def _age_days(path: Path, now: float) -> float:
+    return (now - path.stat().st_mtime) / 86400
+
+
+def vacuum(
+    log_dir: Path,
+    keep_days: float = DEFAULT_KEEP_DAYS,
+    delete_after_days: Optional[float] = None,
+    dry_run: bool = False,
+    now: Optional[float] = None,
+) -> VacuumResult:
+    """
+    Apply the retention policy to a session log directory.
+
+    Args:
+        log_dir: Directory holding session_*.jsonl and radar_raw_*.log files
+        keep_days: Keep per-reading telemetry this long
+        delete_after_days: Delete whole sessions this old (None = never)
+        dry_run: Report what would be done without changing anything
+        now: Current time as a Unix timestamp (for tests)
+
+    Returns:
+        Sessions compacted, files deleted and bytes freed
+
+    Raises:
+        ValueError: If delete_after_days is shorter than keep_days
+    """
+    if delete_after_days is not None and delete_after_days < keep_days:
+        raise ValueError("delete_after_days must be at least keep_days")
+    now = time.time() if now is None else now
+    result = VacuumResult()
+    log_dir = Path(log_dir)
+    if not log_dir.is_dir():
+        return result
+
+    paths = sorted(log_dir.glob("session_*.jsonl")) + sorted(log_dir.glob("radar_raw_*.log"))
+    for path in paths:
+        age = _age_days(path, now)
+        is_raw = path.name.startswith("radar_raw_")
+        expired = delete_after_days is not None and age > delete_after_days
+        if expired or (is_raw and age > keep_days):
+            # A deleted session takes its voice memos with it
+            for doomed in [path] + ([] if is_raw else session_memos(path)):
+                result.bytes_freed += doomed.stat().st_size
+                result.deleted.append(str(doomed))
+                if not dry_run:
+                    doomed.unlink()
+        elif not is_raw and age > keep_days and not path.name.endswith(OUTPUT_SUFFIX):
+            compacted = compact_session(str(path), dry_run=dry_run)
+            if not compacted.already_compacted:
+                result.compacted.append(compacted)
+                result.bytes_freed += compacted.bytes_before - compacted.bytes_after
+
+    return result
+
+
+def _format_bytes(count: int) -> str:
+    for unit in ("B", "KB", "MB"):
+        if count < 1024:
+            return f"{count:.0f} {unit}"
+        count /= 1024
+    return f"{count:.1f} GB"
+
+
+def format_result(result: VacuumResult) -> str:
+    """One-line summary of a vacuum pass."""
+    return (
+        f"{len(result.compacted)} session(s) compacted, {len(result.deleted)} file(s) deleted, "
+        f"{_format_bytes(result.bytes_freed)} freed"
+    )
+
+
+def main():
+    """CLI entry point for the vacuum command."""
+    parser = argparse.ArgumentParser(
+        description="Compact old session logs to shot-level entries and delete raw radar logs"
+    )
+    parser.add_argument(
+        "--log-dir", help="Directory for session logs (default: ~/openflight_sessions)"
+    )
+    parser.add_argument(
+        "--keep-days",
+        type=float,
+        default=DEFAULT_KEEP_DAYS,
+        help=f"Keep per-reading telemetry this many days (default: {DEFAULT_KEEP_DAYS})",
+    )
+    parser.add_argument(
+        "--delete-after", type=float, help="Delete whole sessions older than this many days"
+    )
+    parser.add_argument(
+        "--dry-run", action="store_true", help="Show what would be done without changing files"
+    )
+    args = parser.parse_args()
+
+    log_dir = Path(args.log_dir).expanduser() if args.log_dir else SessionLogger.DEFAULT_LOG_DIR
+    try:
+        result = vacuum(log_dir, args.keep_days, args.delete_after, dry_run=args.dry_run)
+    except ValueError as e:
+        parser.error(str(e))
+
+    for compacted in result.compacted:
+        print(
+            f"  compact {Path(compacted.path).name}: {_format_bytes(compacted.bytes_before)}"
+            f" -> {_format_bytes(compacted.bytes_after)}"
+        )
+    for path in result.deleted:
+        print(f"  delete  {Path(path).name}")
+    prefix = "Dry run: " if args.dry_run else ""
+    print(f"{prefix}{format_result(result)}")
+
+
+if __name__ == "__main__":
+    main()
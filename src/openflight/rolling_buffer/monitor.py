@@ -12,9 +12,12 @@ import time
 from datetime import datetime
 from typing import Callable, List, Optional
 
+from ..energy import energy_stats
+from ..impact import impact_stats
 from ..launch_monitor import ClubType, Shot, estimate_carry_distance
 from ..ops243 import OPS243Radar, SpeedReading
 from ..session_logger import get_session_logger
+from ..tracing import trace_span
 from .processor import RollingBufferProcessor
 from .trigger import create_trigger
 from .types import ProcessedCapture
@@ -372,7 +375,7 @@ class RollingBufferMonitor:
         """Main capture loop - wait for trigger, process, emit shot."""
         while self._running:
             try:
-                trigger_start = time.time()
+                trigger_start = time.monotonic()
 
                 # Wait for trigger and capture
                 # Use a long timeout so sound/hardware triggers can wait
@@ -383,7 +386,7 @@ class RollingBufferMonitor:
                     timeout=30.0,
                 )
 
-                trigger_latency_ms = (time.time() - trigger_start) * 1000
+                trigger_latency_ms = (time.monotonic() - trigger_start) * 1000
 
                 # Always drain trigger diagnostics (captures in-loop rejections)
                 self._emit_diagnostics(trigger_latency_ms)
@@ -392,7 +395,8 @@ class RollingBufferMonitor:
                     continue
 
                 # Process capture
-                processed = self.processor.process_capture(capture)
+                with trace_span("process_capture", "detect"):
+                    processed = self.processor.process_capture(capture)
 
                 if processed is None:
                     logger.warning("Failed to process capture")
@@ -638,8 +642,8 @@ class RollingBufferMonitor:
         original_callback = self._shot_callback
         self._shot_callback = on_shot
 
-        start = time.time()
-        while not shot_detected and (time.time() - start) < timeout:
+        start = time.monotonic()
+        while not shot_detected and (time.monotonic() - start) < timeout:
             time.sleep(0.1)
 
         self._shot_callback = original_callback
@@ -662,6 +666,8 @@ class RollingBufferMonitor:
                 "avg_club_speed": None,
                 "avg_smash_factor": None,
                 "avg_carry_est": 0,
+                **energy_stats([]),
+                **impact_stats([]),
                 "avg_spin_rpm": None,
                 "mode": "rolling-buffer",
             }
@@ -686,6 +692,8 @@ class RollingBufferMonitor:
             "avg_club_speed": statistics.mean(club_speeds) if club_speeds else None,
             "avg_smash_factor": statistics.mean(smash_factors) if smash_factors else None,
             "avg_carry_est": statistics.mean([s.estimated_carry_yards for s in self._shots]),
+            **energy_stats(self._shots),
+            **impact_stats(self._shots),
             "avg_spin_rpm": statistics.mean(spin_rpms) if spin_rpms else None,
             "spin_detection_rate": len(spin_rpms) / len(self._shots) if self._shots else 0,
             "mode": "rolling-buffer",
@@ -695,14 +703,26 @@ class RollingBufferMonitor:
         """Get all detected shots."""
         return self._shots.copy()
 
+    def discard_last_shot(self) -> Optional[Shot]:
+        """Remove the most recent shot (a mulligan); None if there are no shots."""
+        return self._shots.pop() if self._shots else None
+
     def clear_session(self):
         """Clear all recorded shots."""
         self._shots = []
 
+    def restore_shots(self, shots: List[Shot]):
+        """Put back shots from before a restart (e.g. from a resumed session log)."""
+        self._shots = list(shots) + self._shots
+
     def set_club(self, club: ClubType):
         """Set the current club for future shots."""
         self._current_club = club
 
+    def get_club(self) -> ClubType:
+        """Get the club used for future shots."""
+        return self._current_club
+
     def __enter__(self):
         """Context manager entry."""
         self.connect()
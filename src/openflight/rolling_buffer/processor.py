@@ -11,6 +11,8 @@ from typing import List, Optional, Tuple
 
 import numpy as np
 
+from ..units import MPH_TO_MPS, MetersPerSecond
+
 from .types import (
     IQCapture,
     ProcessedCapture,
@@ -48,7 +50,7 @@ class RollingBufferProcessor:
     # For 24.125 GHz radar: wavelength = c / f = 0.01243 m
     # Simplified: bin * 0.0063 * (sample_rate / fft_size) gives m/s
     WAVELENGTH_M = 0.01243  # meters (24.125 GHz)
-    MPS_TO_MPH = 2.23694
+    MPS_TO_MPH = 1 / MPH_TO_MPS
 
     # Signal processing
     ADC_RANGE = 4096  # 12-bit ADC
@@ -274,7 +276,7 @@ class RollingBufferProcessor:
             for peak_bin, peak_mag in self._find_peaks(magnitude, dc_mask, half):
                 freq_hz = peak_bin * self.SAMPLE_RATE / self.FFT_SIZE
                 speed_mps = freq_hz * self.WAVELENGTH_M / 2
-                speed_mph = speed_mps * self.MPS_TO_MPH
+                speed_mph = MetersPerSecond(speed_mps).to_mph()
                 results.append((speed_mph, float(peak_mag), "outbound"))
 
         # Inbound peaks: search negative frequencies, skipping DC mask bins
@@ -290,7 +292,7 @@ class RollingBufferProcessor:
                 abs_bin = self.FFT_SIZE - neg_peak_bin
                 freq_hz = abs_bin * self.SAMPLE_RATE / self.FFT_SIZE
                 speed_mps = freq_hz * self.WAVELENGTH_M / 2
-                speed_mph = speed_mps * self.MPS_TO_MPH
+                speed_mph = MetersPerSecond(speed_mps).to_mph()
                 results.append((speed_mph, float(neg_peak_mag), "inbound"))
 
         return results
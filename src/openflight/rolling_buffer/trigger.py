@@ -149,9 +149,9 @@ class PollingTrigger(TriggerStrategy):
         timeout: float = 30.0,
     ) -> Optional[IQCapture]:
         """Poll for activity and return capture when detected."""
-        start_time = time.time()
+        start_time = time.monotonic()
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             try:
                 # Trigger capture (10s timeout for large I/Q data transfer)
                 response = radar.trigger_capture(timeout=10.0)
@@ -237,9 +237,9 @@ class ThresholdTrigger(TriggerStrategy):
         radar's internal threshold trigger may not be available in G1 mode.
         For production use, consider external GPIO trigger.
         """
-        start_time = time.time()
+        start_time = time.monotonic()
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             try:
                 # Capture and check for threshold (10s timeout for large I/Q data)
                 response = radar.trigger_capture(timeout=10.0)
@@ -309,9 +309,9 @@ class ManualTrigger(TriggerStrategy):
         timeout: float = 30.0,
     ) -> Optional[IQCapture]:
         """Wait for manual trigger request."""
-        start_time = time.time()
+        start_time = time.monotonic()
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             if self._trigger_requested:
                 self._trigger_requested = False
                 logger.info("Manual trigger activated")
@@ -403,17 +403,17 @@ class SpeedTriggeredCapture(TriggerStrategy):
                 radar.serial.reset_input_buffer()
             time.sleep(0.1)
 
-        start_time = time.time()
+        start_time = time.monotonic()
         logger.info(f"Waiting for speed trigger >= {self.min_trigger_speed_mph} mph...")
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             # Non-blocking speed read
             reading = radar.read_speed_nonblocking()
 
             if reading and reading.speed >= self.min_trigger_speed_mph:
                 # Speed detected - this is likely the club
                 self._last_trigger_speed = reading.speed
-                trigger_time = time.time()
+                trigger_time = time.monotonic()
 
                 logger.info(f"Trigger: {reading.speed:.1f} mph detected, "
                            f"switching to rolling buffer...")
@@ -433,7 +433,7 @@ class SpeedTriggeredCapture(TriggerStrategy):
                 capture = processor.parse_capture(response)
 
                 # Calculate timing
-                capture_time = time.time()
+                capture_time = time.monotonic()
                 total_delay_ms = (capture_time - trigger_time) * 1000
                 logger.info("Buffer captured %.1fms after trigger", total_delay_ms)
 
@@ -537,7 +537,7 @@ class GPIOSoundTrigger(TriggerStrategy):
             return False
 
         def on_trigger():
-            self._trigger_event["edge_time"] = time.time()
+            self._trigger_event["edge_time"] = time.monotonic()
             self._trigger_event["triggered"] = True
 
         self._button = Button(
@@ -578,16 +578,16 @@ class GPIOSoundTrigger(TriggerStrategy):
             self.gpio_pin, timeout, self.pre_trigger_segments
         )
 
-        start_time = time.time()
+        start_time = time.monotonic()
         self._trigger_event["triggered"] = False
 
-        while (time.time() - start_time) < timeout:
+        while (time.monotonic() - start_time) < timeout:
             if self._trigger_event["triggered"]:
                 edge_time = self._trigger_event["edge_time"]
                 self._trigger_event["triggered"] = False
 
                 # Measure edge-to-S! latency (from GPIO callback to now)
-                trigger_latency = (time.time() - edge_time) * 1000
+                trigger_latency = (time.monotonic() - edge_time) * 1000
                 logger.info("GPIO edge detected on GPIO%d (%.1fms ago), sending S! trigger...",
                            self.gpio_pin, trigger_latency)
                 response = radar.trigger_capture(timeout=5.0)
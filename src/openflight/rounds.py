@@ -0,0 +1,96 @@
+"""
+Hole context for shots played on a simulator.
+
+Sims that track course state (or a bridge forwarding it) report the current
+hole through PUT /api/round. Each shot detected afterwards is tagged with
+that hole number and its stroke index on the hole, so a session log or
+shot export can be reviewed hole by hole after the round:
+
+    PUT /api/round {"hole": 1}          -> next shot is hole 1, shot 1
+    (shot)                              -> hole 1, shot 1
+    (shot)                              -> hole 1, shot 2
+    PUT /api/round {"hole": 2}          -> next shot is hole 2, shot 1
+    PUT /api/round {"hole": 2, "shot": 3}  (sim added a penalty stroke)
+
+Without a reported hole, shots are left untagged.
+"""
+
+import threading
+from typing import Optional
+
+from .launch_monitor import Shot
+
+
+def _is_positive_int(value) -> bool:
+    return isinstance(value, int) and not isinstance(value, bool) and value >= 1
+
+
+class RoundTracker:
+    """
+    Tracks the hole being played and numbers the shots on it.
+
+    Example:
+        round_tracker = RoundTracker()
+        round_tracker.set_hole(4)
+        round_tracker.tag(shot)  # shot.hole_number == 4, shot.hole_shot == 1
+    """
+
+    def __init__(self):
+        """Initialize with no hole reported."""
+        self.hole: Optional[int] = None
+        self.next_shot = 1
+        self._lock = threading.Lock()
+
+    def set_hole(self, hole: int, shot: Optional[int] = None):
+        """
+        Report the hole being played.
+
+        Args:
+            hole: Hole number (from 1)
+            shot: Stroke index of the next shot; defaults to 1 on a new hole
+                and is left unchanged when the same hole is reported again
+
+        Raises:
+            ValueError: If the hole or shot index is out of range
+        """
+        if not _is_positive_int(hole):
+            raise ValueError("hole must be a whole number of at least 1")
+        if shot is not None and not _is_positive_int(shot):
+            raise ValueError("shot must be a whole number of at least 1")
+
+        with self._lock:
+            if shot is not None:
+                self.next_shot = shot
+            elif hole != self.hole:
+                self.next_shot = 1
+            self.hole = hole
+
+    def clear(self):
+        """Forget the hole (round finished or abandoned)."""
+        with self._lock:
+            self.hole = None
+            self.next_shot = 1
+
+    def tag(self, shot: Shot):
+        """Stamp a shot with the current hole and advance the stroke index."""
+        with self._lock:
+            if self.hole is None:
+                return
+            shot.hole_number = self.hole
+            shot.hole_shot = self.next_shot
+            self.next_shot += 1
+
+    def untag(self, shot: Shot):
+        """Give back a mulliganed shot's stroke if it was the last one on this hole."""
+        with self._lock:
+            if (
+                shot.hole_number is not None
+                and shot.hole_number == self.hole
+                and shot.hole_shot == self.next_shot - 1
+            ):
+                self.next_shot -= 1
+
+    def to_dict(self) -> dict:
+        """Current state for the REST API."""
+        with self._lock:
+            return {"hole": self.hole, "next_shot": self.next_shot if self.hole else None}
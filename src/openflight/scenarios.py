@@ -0,0 +1,269 @@
+"""
+Scripted MockRadar scenarios that pin shot detection behavior.
+
+A scenario is a JSON file describing a radar setup and a sequence of
+shots, each with the outcome detection should produce. Every file in
+tests/scenarios/ is run as its own test case by tests/test_scenarios.py,
+so a new scenario is a new regression test with no test code to write:
+
+    {
+      "description": "Driver and wedge from a radar 30 cm off the target line",
+      "radar": {"lateral_offset_m": 0.3, "seed": 3},
+      "shots": [
+        {
+          "launch": {"ball_speed_mph": 150, "launch_angle_vertical": 11, "club": "driver"},
+          "expect": {"ball_speed_mph": [145, 155], "carry_yards": [230, 260]}
+        },
+        {"launch": {"ball_speed_mph": 20, "club": "lw"}, "expect": {"detected": false}}
+      ]
+    }
+
+radar: MockRadar settings: lateral_offset_m, height_offset_m, distance_m,
+mount_position ("behind" or "front"), dropout, noise_mph and seed, and
+"timing" with ReadingTiming fields (latency_ms, latency_jitter_ms,
+timestamp_jitter_ms, burst_size). The seed makes the readings, and so
+the outcomes, the same on every run.
+
+monitor: MonitorConfig fields to change from the defaults (optional).
+
+launch: LaunchConditions fields, with the club by name.
+
+expect: "detected" (default true), and [min, max] ranges for any of
+ball_speed_mph, club_speed_mph, smash_factor and carry_yards. Carry is
+estimated with the launch angle and spin a camera would have supplied
+(those of the launch), by the physics model.
+
+To write the expectations for a new scenario, run it and look at what
+detection makes of each shot:
+
+    python -m openflight.scenarios tests/scenarios/my_scenario.json
+"""
+
+import argparse
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from .carry import PhysicsCarryModel
+from .geometry import MountingGeometry, apply_cosine_correction
+from .launch_monitor import ClubType, LaunchMonitor, MonitorConfig, Shot
+from .mock_radar import LaunchConditions, MockRadar, ReadingTiming
+from .ops243 import MountPosition
+
+# Metrics an expectation can give a [min, max] range for
+RANGE_METRICS = ("ball_speed_mph", "club_speed_mph", "smash_factor", "carry_yards")
+
+_GEOMETRY_FIELDS = ("lateral_offset_m", "height_offset_m", "distance_m")
+_RADAR_FIELDS = ("dropout", "noise_mph", "seed")
+
+
+@dataclass
+class ScenarioShot:
+    """One scripted shot and what detection should make of it."""
+
+    launch: LaunchConditions
+    detected: bool = True
+    ranges: Dict[str, Tuple[float, float]] = field(default_factory=dict)
+
+
+@dataclass
+class Scenario:
+    """A radar setup and its scripted shots."""
+
+    name: str
+    description: str
+    radar: Dict[str, Any]
+    monitor: Dict[str, Any]
+    shots: List[ScenarioShot]
+
+    @property
+    def mount_position(self) -> MountPosition:
+        """Where the radar is mounted."""
+        return MountPosition(self.radar.get("mount_position", MountPosition.BEHIND.value))
+
+    def build_radar(self) -> MockRadar:
+        """A fresh MockRadar for the scenario (same seed, same readings)."""
+        geometry = MountingGeometry(
+            **{k: self.radar[k] for k in _GEOMETRY_FIELDS if k in self.radar}
+        )
+        timing = self.radar.get("timing")
+        return MockRadar(
+            geometry,
+            mount_position=self.mount_position,
+            timing=ReadingTiming(**timing) if timing is not None else None,
+            **{k: self.radar[k] for k in _RADAR_FIELDS if k in self.radar},
+        )
+
+
+@dataclass
+class ShotOutcome:
+    """What detection made of one scripted shot."""
+
+    index: int
+    shot: Optional[Shot]
+    carry_yards: Optional[float]
+
+    def metric(self, name: str) -> Optional[float]:
+        """A RANGE_METRICS value (None if not detected or not measured)."""
+        if self.shot is None:
+            return None
+        if name == "carry_yards":
+            return self.carry_yards
+        return getattr(self.shot, name)
+
+
+def _parse_launch(data: dict) -> LaunchConditions:
+    data = dict(data)
+    if "club" in data:
+        data["club"] = ClubType(data["club"])
+    return LaunchConditions(**data)
+
+
+def _parse_shot(index: int, data: dict) -> ScenarioShot:
+    expect = dict(data.get("expect", {}))
+    detected = expect.pop("detected", True)
+    ranges = {}
+    for name, bounds in expect.items():
+        if name not in RANGE_METRICS:
+            raise ValueError(f"shot {index}: unknown expectation {name!r}")
+        if not isinstance(bounds, list) or len(bounds) != 2 or bounds[0] > bounds[1]:
+            raise ValueError(f"shot {index}: {name} must be [min, max]")
+        ranges[name] = (float(bounds[0]), float(bounds[1]))
+    if ranges and not detected:
+        raise ValueError(f"shot {index}: ranges given for a shot expected not to be detected")
+    return ScenarioShot(launch=_parse_launch(data["launch"]), detected=detected, ranges=ranges)
+
+
+def load_scenario(path: Path) -> Scenario:
+    """
+    Read a scenario file.
+
+    Raises:
+        ValueError: If the file isn't a valid scenario
+        OSError: If it can't be read
+    """
+    try:
+        data = json.loads(Path(path).read_text())
+        shots = [_parse_shot(i, shot) for i, shot in enumerate(data["shots"], 1)]
+        scenario = Scenario(
+            name=Path(path).stem,
+            description=data.get("description", ""),
+            radar=data.get("radar", {}),
+            monitor=data.get("monitor", {}),
+            shots=shots,
+        )
+        scenario.build_radar()
+        MonitorConfig().with_updates(scenario.monitor)
+    except (KeyError, TypeError) as e:
+        raise ValueError(f"{path}: invalid scenario ({e})") from e
+    except ValueError as e:
+        raise ValueError(f"{path}: {e}") from e
+    if not shots:
+        raise ValueError(f"{path}: no shots")
+    return scenario
+
+
+def find_scenarios(directory: Path) -> List[Path]:
+    """Scenario files in a directory, by name."""
+    return sorted(Path(directory).glob("*.json"))
+
+
+def run_scenario(scenario: Scenario) -> List[ShotOutcome]:
+    """
+    Play a scenario's shots through one LaunchMonitor, in order.
+
+    Returns:
+        What detection made of each shot
+    """
+    radar = scenario.build_radar()
+    monitor = LaunchMonitor(
+        use_iq_streaming=False,
+        mount_position=scenario.mount_position,
+        config=MonitorConfig().with_updates(scenario.monitor),
+    )
+    physics = PhysicsCarryModel()
+    outcomes = []
+    for index, scripted in enumerate(scenario.shots, 1):
+        launch = scripted.launch
+        monitor.set_club(launch.club)
+        shot = monitor.analyze_readings(radar.readings_for(launch))
+        carry = None
+        if shot is not None:
+            apply_cosine_correction(shot, radar.geometry)
+            # The camera would supply these
+            shot.launch_angle_vertical = launch.launch_angle_vertical
+            shot.spin_rpm = launch.effective_spin_rpm
+            carry = physics.estimate(shot)
+        outcomes.append(ShotOutcome(index=index, shot=shot, carry_yards=carry))
+    return outcomes
+
+
+def check_scenario(
+    scenario: Scenario, outcomes: Optional[List[ShotOutcome]] = None
+) -> List[str]:
+    """
+    Compare each shot's outcome with its expectations.
+
+    Args:
+        scenario: Scenario to check
+        outcomes: Its run_scenario() outcomes (run here if not given)
+
+    Returns:
+        One message per unmet expectation (empty if all were met)
+    """
+    if outcomes is None:
+        outcomes = run_scenario(scenario)
+    failures = []
+    for scripted, outcome in zip(scenario.shots, outcomes):
+        label = f"shot {outcome.index} ({scripted.launch.ball_speed_mph:g} mph)"
+        if scripted.detected != (outcome.shot is not None):
+            expected = "detected" if scripted.detected else "rejected"
+            failures.append(f"{label}: expected {expected}, was not")
+            continue
+        for name, (low, high) in scripted.ranges.items():
+            value = outcome.metric(name)
+            if value is None:
+                failures.append(f"{label}: {name} not measured, expected {low:g}-{high:g}")
+            elif not low <= value <= high:
+                failures.append(f"{label}: {name} {value:.1f} outside {low:g}-{high:g}")
+    return failures
+
+
+def describe_outcome(outcome: ShotOutcome) -> str:
+    """One line on what detection made of a shot."""
+    if outcome.shot is None:
+        return f"shot {outcome.index}: not detected"
+    values = [
+        f"{name} {value:.2f}"
+        for name in RANGE_METRICS
+        if (value := outcome.metric(name)) is not None
+    ]
+    return f"shot {outcome.index}: " + ", ".join(values)
+
+
+def main(argv: Optional[List[str]] = None):
+    """CLI entry point: show what detection makes of each shot in scenario files."""
+    parser = argparse.ArgumentParser(
+        description="Run MockRadar scenarios and show each shot's detected outcome"
+    )
+    parser.add_argument("scenarios", nargs="+", help="Scenario JSON files")
+    args = parser.parse_args(argv)
+
+    for path in args.scenarios:
+        try:
+            scenario = load_scenario(Path(path))
+        except (OSError, ValueError) as e:
+            parser.error(str(e))
+        print(f"{scenario.name}: {scenario.description}")
+        outcomes = run_scenario(scenario)
+        for outcome in outcomes:
+            print(f"  {describe_outcome(outcome)}")
+        failures = check_scenario(scenario, outcomes)
+        print(f"  {'FAIL' if failures else 'ok'}")
+        for failure in failures:
+            print(f"    {failure}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,269 @@
+"""
+Arm/disarm schedules and quiet hours.
+
+A radar left running in the garage sees the door opener, the cat and the
+car, and each can land a "shot" in a sim session left open overnight. A
+schedule disarms the monitor when nobody should be hitting:
+
+- quiet hours: windows of the day (local time) when it is disarmed,
+  e.g. "22:00-07:00" (a window may cross midnight); it re-arms by itself
+  when the window ends
+- idle disarm: disarmed after this many minutes without a shot; it stays
+  disarmed until re-armed by hand (POST /api/arm, the phone remote), so a
+  false trigger hours later can't re-arm it
+
+While disarmed, shots are still detected, shown and logged, but not sent
+to the sim, and no "ready" is sent. Re-arming by hand during quiet hours
+overrides the rest of that window.
+
+The schedule comes from the calibration profile's "schedule" section
+(reloaded when the file is edited) or --quiet-hours / --idle-disarm:
+
+    "schedule": {"quiet_hours": ["22:00-07:00"], "idle_disarm_min": 30}
+"""
+
+import logging
+import threading
+import time
+from dataclasses import dataclass
+from datetime import datetime
+from datetime import time as day_time
+from typing import Callable, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+QUIET_HOURS = "quiet_hours"
+IDLE = "idle"
+
+
+def parse_window(text: str) -> Tuple[day_time, day_time]:
+    """
+    Parse a quiet hours window, e.g. "22:00-07:00".
+
+    Raises:
+        ValueError: If it isn't HH:MM-HH:MM with distinct times
+    """
+    try:
+        start_text, end_text = text.split("-")
+        start = datetime.strptime(start_text.strip(), "%H:%M").time()
+        end = datetime.strptime(end_text.strip(), "%H:%M").time()
+    except ValueError:
+        raise ValueError(f"quiet hours must be HH:MM-HH:MM, got {text!r}") from None
+    if start == end:
+        raise ValueError(f"quiet hours window {text!r} is empty")
+    return start, end
+
+
+def _format_window(window: Tuple[day_time, day_time]) -> str:
+    return f"{window[0]:%H:%M}-{window[1]:%H:%M}"
+
+
+@dataclass(frozen=True)
+class ArmSchedule:
+    """
+    When the monitor is disarmed.
+
+    Attributes:
+        quiet_hours: (start, end) windows of local time, end exclusive
+        idle_disarm_min: Minutes without a shot before disarming (None = never)
+    """
+
+    quiet_hours: Tuple[Tuple[day_time, day_time], ...] = ()
+    idle_disarm_min: Optional[float] = None
+
+    @property
+    def enabled(self) -> bool:
+        """Whether the schedule ever disarms."""
+        return bool(self.quiet_hours) or self.idle_disarm_min is not None
+
+    @classmethod
+    def from_dict(cls, data: dict) -> "ArmSchedule":
+        """
+        Build a schedule from a profile's "schedule" section.
+
+        Raises:
+            ValueError: If a field is unknown or invalid
+        """
+        if not isinstance(data, dict):
+            raise ValueError("schedule must be an object")
+        unknown = set(data) - {"quiet_hours", "idle_disarm_min"}
+        if unknown:
+            raise ValueError(f"Unknown schedule field(s): {', '.join(sorted(unknown))}")
+        windows = data.get("quiet_hours", [])
+        if not isinstance(windows, list) or not all(isinstance(w, str) for w in windows):
+            raise ValueError('quiet_hours must be a list like ["22:00-07:00"]')
+        idle = data.get("idle_disarm_min")
+        if idle is not None and (
+            isinstance(idle, bool) or not isinstance(idle, (int, float)) or idle <= 0
+        ):
+            raise ValueError("idle_disarm_min must be a positive number of minutes")
+        return cls(
+            quiet_hours=tuple(parse_window(w) for w in windows),
+            idle_disarm_min=float(idle) if idle is not None else None,
+        )
+
+    def to_dict(self) -> dict:
+        """Serialize like the profile section."""
+        data: dict = {}
+        if self.quiet_hours:
+            data["quiet_hours"] = [_format_window(w) for w in self.quiet_hours]
+        if self.idle_disarm_min is not None:
+            data["idle_disarm_min"] = self.idle_disarm_min
+        return data
+
+    def in_quiet_hours(self, now: datetime) -> bool:
+        """Whether a local time falls in one of the quiet hours windows."""
+        moment = now.time()
+        for start, end in self.quiet_hours:
+            if start < end:
+                if start <= moment < end:
+                    return True
+            elif moment >= start or moment < end:
+                return True
+        return False
+
+
+class ScheduledDisarm:
+    """
+    Applies an ArmSchedule: tracks whether the monitor is disarmed, and why.
+
+    Polls on a background thread so quiet hours start (and end) without a
+    shot; on_change is called with "disarmed" or "armed" and the reason.
+
+    Example:
+        disarm = ScheduledDisarm(ArmSchedule.from_dict({"idle_disarm_min": 30}),
+                                 on_change=lambda event, reason: print(event, reason))
+        disarm.start()
+        if not disarm.disarmed:
+            send_to_sim(shot)
+        disarm.on_shot()
+    """
+
+    DEFAULT_INTERVAL_SEC = 5.0
+
+    def __init__(
+        self,
+        schedule: ArmSchedule,
+        on_change: Optional[Callable[[str, Optional[str]], None]] = None,
+        interval_sec: float = DEFAULT_INTERVAL_SEC,
+    ):
+        """
+        Initialize scheduled disarm.
+
+        Args:
+            schedule: When to disarm
+            on_change: Called with ("disarmed", reason) or ("armed", None)
+            interval_sec: How often the schedule is checked
+        """
+        self.schedule = schedule
+        self.on_change = on_change
+        self.interval_sec = interval_sec
+        self.reason: Optional[str] = None  # QUIET_HOURS or IDLE while disarmed
+        self._last_shot = time.monotonic()
+        self._override_quiet = False  # Re-armed by hand during this quiet window
+        self._lock = threading.Lock()
+        self._stop_event = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def disarmed(self) -> bool:
+        """Whether shots should be kept from the sim."""
+        return self.reason is not None
+
+    def check(self, now: Optional[datetime] = None, mono: Optional[float] = None):
+        """
+        Re-evaluate the schedule.
+
+        Args:
+            now: Local time (default: datetime.now())
+            mono: Monotonic time (default: time.monotonic())
+        """
+        now = now or datetime.now()
+        mono = time.monotonic() if mono is None else mono
+        with self._lock:
+            quiet = self.schedule.in_quiet_hours(now)
+            if not quiet:
+                self._override_quiet = False
+            idle_min = self.schedule.idle_disarm_min
+            if self.reason == IDLE:
+                reason: Optional[str] = IDLE  # Until re-armed by hand
+            elif quiet and not self._override_quiet:
+                reason = QUIET_HOURS
+            elif idle_min is not None and mono - self._last_shot >= idle_min * 60:
+                reason = IDLE
+            else:
+                reason = None
+        self._set_reason(reason)
+
+    def on_shot(self, mono: Optional[float] = None):
+        """Note a shot, restarting the idle timer."""
+        with self._lock:
+            self._last_shot = time.monotonic() if mono is None else mono
+
+    def rearm(self, now: Optional[datetime] = None, mono: Optional[float] = None):
+        """Re-arm by hand: clears an idle disarm and overrides the current quiet window."""
+        now = now or datetime.now()
+        with self._lock:
+            self._last_shot = time.monotonic() if mono is None else mono
+            self._override_quiet = self.schedule.in_quiet_hours(now)
+        self._set_reason(None)
+
+    def set_schedule(self, schedule: ArmSchedule):
+        """Swap in an edited schedule and re-evaluate it."""
+        with self._lock:
+            self.schedule = schedule
+            if self.reason == IDLE and schedule.idle_disarm_min is None:
+                self.reason = None  # The idle disarm no longer applies
+        self.check()
+
+    def _set_reason(self, reason: Optional[str]):
+        with self._lock:
+            if reason == self.reason:
+                return
+            self.reason = reason
+        if reason:
+            logger.info("Disarmed (%s)", reason)
+        else:
+            logger.info("Armed")
+        if self.on_change:
+            self.on_change("disarmed" if reason else "armed", reason)
+
+    def to_dict(self) -> dict:
+        """Current state for the REST API."""
+        return {"disarmed": self.disarmed, "reason": self.reason, **self.schedule.to_dict()}
+
+    def start(self):
+        """Start checking the schedule in a background thread."""
+        if self._thread and self._thread.is_alive():
+            return
+        self._stop_event.clear()
+        self.check()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def stop(self):
+        """Stop checking."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=2.0)
+            self._thread = None
+
+    def _run(self):
+        while not self._stop_event.wait(self.interval_sec):
+            try:
+                self.check()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.error("Schedule check failed: %s", e)
+
+
+def parse_quiet_hours(text: str) -> List[str]:
+    """
+    Split a --quiet-hours value ("22:00-07:00,12:00-13:00") into windows.
+
+    Raises:
+        ValueError: If a window is invalid
+    """
+    windows = [w.strip() for w in text.split(",") if w.strip()]
+    for window in windows:
+        parse_window(window)
+    return windows
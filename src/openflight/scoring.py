@@ -0,0 +1,199 @@
+"""
+Strokes-gained practice scoring.
+
+Hitting balls at a target is more engaging with a score than with a list
+of carries. With a target distance selected (PUT /api/target or
+--target), each shot is scored the way strokes gained rates tour shots:
+by how much closer to holing out it left the ball, against a baseline of
+the strokes an average tour player needs from each distance:
+
+    strokes_gained = expected(target) - 1 - expected(remaining)
+
+where remaining is the distance from the landing point (see dispersion)
+to the target. A shot at a 150 yard target that stops 3 yards from it
+gains about +0.4; one left 40 yards short loses about -0.65. Within
+GREEN_RADIUS_YD of the target the ball counts as on the green and the
+putting baseline applies; further away, the fairway one.
+
+Shots without a horizontal launch angle (radar only) are scored on
+distance alone, as if they started on the target line. The values are
+summed per session in /api/stats, overall and per club.
+"""
+
+import bisect
+import math
+import statistics
+import threading
+from typing import Dict, List, Optional, Sequence, Tuple
+
+from .dispersion import landing
+from .launch_monitor import Shot
+
+GREEN_RADIUS_YD = 10.0  # Landing this close to the target is on the green
+MAX_TARGET_YD = 350.0
+
+# Expected strokes to hole out, approximately the PGA Tour averages
+# published by Mark Broadie (Every Shot Counts, 2014)
+FAIRWAY_BASELINE: Sequence[Tuple[float, float]] = [  # (yards, strokes)
+    (10, 2.18),
+    (20, 2.40),
+    (40, 2.60),
+    (60, 2.70),
+    (80, 2.75),
+    (100, 2.80),
+    (120, 2.85),
+    (140, 2.91),
+    (160, 2.98),
+    (180, 3.08),
+    (200, 3.19),
+    (220, 3.32),
+    (240, 3.45),
+    (260, 3.58),
+    (280, 3.69),
+    (300, 3.78),
+    (350, 3.98),
+]
+GREEN_BASELINE: Sequence[Tuple[float, float]] = [  # (feet, strokes)
+    (0, 1.00),
+    (2, 1.01),
+    (3, 1.04),
+    (4, 1.13),
+    (5, 1.23),
+    (6, 1.34),
+    (8, 1.50),
+    (10, 1.61),
+    (15, 1.78),
+    (20, 1.87),
+    (30, 1.98),
+]
+
+
+def _interpolate(table: Sequence[Tuple[float, float]], distance: float) -> float:
+    """Linear interpolation in a (distance, strokes) table, clamped at its ends."""
+    distances = [d for d, _ in table]
+    if distance <= distances[0]:
+        return table[0][1]
+    if distance >= distances[-1]:
+        return table[-1][1]
+    i = bisect.bisect_right(distances, distance)
+    (d0, s0), (d1, s1) = table[i - 1], table[i]
+    return s0 + (s1 - s0) * (distance - d0) / (d1 - d0)
+
+
+def expected_strokes(distance_yd: float) -> float:
+    """Baseline strokes to hole out from a distance (yards) to the target."""
+    if distance_yd <= GREEN_RADIUS_YD:
+        return _interpolate(GREEN_BASELINE, distance_yd * 3)
+    return _interpolate(FAIRWAY_BASELINE, distance_yd)
+
+
+def strokes_gained(
+    target_yd: float, carry_yd: float, angle_deg: Optional[float] = None
+) -> Tuple[float, float]:
+    """
+    Score one shot at a target.
+
+    Args:
+        target_yd: Target distance along the target line
+        carry_yd: Shot's carry
+        angle_deg: Horizontal launch angle, + right (None = on the target line)
+
+    Returns:
+        (strokes gained, distance left to the target in yards)
+    """
+    downrange, offline = landing(carry_yd, angle_deg or 0.0)
+    remaining = math.hypot(target_yd - downrange, offline)
+    return expected_strokes(target_yd) - 1 - expected_strokes(remaining), remaining
+
+
+class PracticeTarget:
+    """
+    The target distance shots are scored against.
+
+    Example:
+        practice_target = PracticeTarget()
+        practice_target.set_target(150)
+        practice_target.score(shot)  # shot.target_yards == 150, shot.strokes_gained set
+    """
+
+    def __init__(self):
+        """Initialize with no target (shots aren't scored)."""
+        self.target_yards: Optional[float] = None
+        self._lock = threading.Lock()
+
+    def set_target(self, yards: float):
+        """
+        Select the target distance.
+
+        Raises:
+            ValueError: If the distance isn't a number from 1 to MAX_TARGET_YD
+        """
+        if (
+            isinstance(yards, bool)
+            or not isinstance(yards, (int, float))
+            or not 1 <= yards <= MAX_TARGET_YD
+        ):
+            raise ValueError(f"target must be a distance from 1 to {MAX_TARGET_YD:.0f} yards")
+        with self._lock:
+            self.target_yards = float(yards)
+
+    def clear(self):
+        """Stop scoring shots."""
+        with self._lock:
+            self.target_yards = None
+
+    def score(self, shot: Shot):
+        """Stamp a shot with the target and its strokes gained (if a target is set)."""
+        with self._lock:
+            target = self.target_yards
+        if target is None:
+            return
+        carry = shot.carry_spin_adjusted or shot.estimated_carry_yards
+        gained, _ = strokes_gained(target, carry, shot.launch_angle_horizontal)
+        shot.target_yards = target
+        shot.strokes_gained = gained
+
+    def to_dict(self) -> dict:
+        """Current state for the REST API."""
+        with self._lock:
+            target = self.target_yards
+        return {
+            "target_yards": target,
+            "baseline_strokes": round(expected_strokes(target), 2) if target else None,
+        }
+
+
+def _summary(values: List[float]) -> dict:
+    return {
+        "shots": len(values),
+        "total": round(sum(values), 2),
+        "average": round(statistics.mean(values), 3),
+        "best": round(max(values), 2),
+    }
+
+
+def session_score(shots: List[dict]) -> Optional[dict]:
+    """
+    Strokes gained over a session's scored shots.
+
+    Args:
+        shots: shot_detected log entries or shot_to_dict output
+
+    Returns:
+        {"shots", "total", "average", "best", "by_club": {club: {...}}},
+        or None if no shot was scored
+    """
+    by_club: Dict[str, List[float]] = {}
+    values = []
+    for shot in shots:
+        gained = shot.get("strokes_gained")
+        if not isinstance(gained, (int, float)):
+            continue
+        values.append(gained)
+        by_club.setdefault(shot.get("club", "driver"), []).append(gained)
+    if not values:
+        return None
+    return {
+        **_summary(values),
+        "by_club": {club: _summary(club_values) for club, club_values in by_club.items()},
+    }
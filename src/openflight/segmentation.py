@@ -0,0 +1,180 @@
+"""
+Shot segmentation by change-point detection.
+
+By default a shot ends when no reading arrives for shot_timeout_sec (0.5 s).
+One gap suits neither end of the bag: a slow wedge's sparse readings can
+drift apart by more than the timeout, while two quick drives can run
+together. With segmentation="change-point" the reading stream is instead
+split where it stops looking like the shot in progress:
+
+- Each reading after a pause (min_gap_sec or more) gets a novelty score:
+  how unusual its gap, speed and log-magnitude are compared to the
+  readings of the current shot, in standard deviations, capped at half
+  the threshold. Readings in the same burst score zero, so club and ball
+  in one swing are never split.
+- Scores are accumulated with a CUSUM (Page's test): each reading adds its
+  score minus a drift allowance, and the sum never drops below zero. When
+  it passes threshold, the shot is taken to have ended where the sum last
+  started rising.
+- A pause of max_gap_sec or more always ends the shot.
+
+Example:
+    segmenter = ChangePointSegmenter()
+    completed = segmenter.update(reading, time.monotonic())
+    if completed:
+        process(buffer[:completed])
+        buffer = buffer[completed:]
+"""
+
+import math
+from dataclasses import dataclass
+from typing import List, Optional, Tuple
+
+from .frames import SpeedReading
+
+TIMEOUT = "timeout"
+CHANGE_POINT = "change-point"
+SEGMENTATION_MODES = (TIMEOUT, CHANGE_POINT)
+
+
+@dataclass
+class _RunningStats:
+    """Mean and variance of a stream of values (Welford's algorithm)."""
+
+    count: int = 0
+    mean: float = 0.0
+    m2: float = 0.0
+
+    def add(self, value: float):
+        self.count += 1
+        delta = value - self.mean
+        self.mean += delta / self.count
+        self.m2 += delta * (value - self.mean)
+
+    def z(self, value: float, min_std: float) -> float:
+        """Signed distance from the mean in standard deviations (0 with no data)."""
+        if self.count == 0:
+            return 0.0
+        std = math.sqrt(self.m2 / self.count)
+        return (value - self.mean) / max(std, min_std)
+
+
+class ChangePointSegmenter:
+    """
+    Online CUSUM change-point detector that splits readings into shots.
+
+    Fed every accepted reading in order; readings must be the same ones
+    the caller buffers, so the counts it returns index that buffer.
+    """
+
+    # Smallest standard deviations used when scoring, so a handful of
+    # near-identical readings doesn't make every later one look novel
+    MIN_LOG_GAP_STD = 0.5
+    MIN_SPEED_STD_MPH = 5.0
+    MIN_LOG_MAGNITUDE_STD = 0.5
+
+    def __init__(
+        self,
+        threshold: float = 4.0,
+        drift: float = 0.5,
+        min_gap_sec: float = 0.1,
+        max_gap_sec: float = 1.0,
+    ):
+        """
+        Initialize segmenter.
+
+        Args:
+            threshold: CUSUM sum that marks a change point
+            drift: Score each reading may have without adding to the sum
+            min_gap_sec: Readings closer together than this are one burst
+            max_gap_sec: A pause this long always ends the shot
+        """
+        self.threshold = threshold
+        self.drift = drift
+        self.min_gap_sec = min_gap_sec
+        self.max_gap_sec = max_gap_sec
+        self._readings: List[Tuple[float, SpeedReading]] = []
+        self._gaps = _RunningStats()
+        self._speeds = _RunningStats()
+        self._magnitudes = _RunningStats()
+        self._cusum = 0.0
+        self._run_start = 0
+
+    def reset(self):
+        """Forget the shot in progress (e.g. after it was processed elsewhere)."""
+        self._start([])
+
+    def update(self, reading: SpeedReading, now: float) -> int:
+        """
+        Feed the next reading.
+
+        Args:
+            reading: Accepted reading, about to be buffered
+            now: Arrival time (time.monotonic())
+
+        Returns:
+            How many readings buffered before this one make up a complete
+            shot (0 while the shot is still going)
+        """
+        if not self._readings:
+            self._start([(now, reading)])
+            return 0
+
+        gap = now - self._readings[-1][0]
+        if gap >= self.max_gap_sec:
+            completed = len(self._readings)
+            self._start([(now, reading)])
+            return completed
+
+        score = self._score(gap, reading)
+        if self._cusum == 0 and score > self.drift:
+            self._run_start = len(self._readings)
+        self._cusum = max(0.0, self._cusum + score - self.drift)
+
+        if self._cusum > self.threshold:
+            completed = self._run_start
+            self._start(self._readings[completed:] + [(now, reading)])
+            return completed
+
+        self._add(gap, reading)
+        self._readings.append((now, reading))
+        return 0
+
+    def _score(self, gap: float, reading: SpeedReading) -> float:
+        if gap < self.min_gap_sec:
+            return 0.0
+        # Only a longer pause than usual counts; a shorter one is no news
+        score = max(0.0, self._gaps.z(math.log(gap), self.MIN_LOG_GAP_STD))
+        score += abs(self._speeds.z(reading.speed, self.MIN_SPEED_STD_MPH))
+        log_magnitude = _log_magnitude(reading)
+        if log_magnitude is not None:
+            score += abs(self._magnitudes.z(log_magnitude, self.MIN_LOG_MAGNITUDE_STD))
+        # One odd reading (a follow-through, a stray object) can't end a shot alone
+        return min(score, self.threshold / 2)
+
+    def _add(self, gap: Optional[float], reading: SpeedReading):
+        if gap:
+            self._gaps.add(math.log(gap))
+        self._speeds.add(reading.speed)
+        log_magnitude = _log_magnitude(reading)
+        if log_magnitude is not None:
+            self._magnitudes.add(log_magnitude)
+
+    def _start(self, readings: List[Tuple[float, SpeedReading]]):
+        """Begin a new shot with these (time, reading) pairs."""
+        self._readings = []
+        self._gaps = _RunningStats()
+        self._speeds = _RunningStats()
+        self._magnitudes = _RunningStats()
+        self._cusum = 0.0
+        self._run_start = 0
+        for now, reading in readings:
+            gap = now - self._readings[-1][0] if self._readings else None
+            self._add(gap, reading)
+            self._readings.append((now, reading))
+
+
+def _log_magnitude(reading: SpeedReading) -> Optional[float]:
+    if reading.magnitude is None or reading.magnitude <= 0:
+        return None
+    return math.log(reading.magnitude)
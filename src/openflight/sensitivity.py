@@ -0,0 +1,83 @@
+"""
+Named shot detection sensitivity presets.
+
+MonitorConfig has a couple of dozen thresholds, and most people setting up
+for the first time know what's wrong ("it misses my wedges", "it fires when
+someone walks past") rather than which of them to change. A preset changes
+a coherent set of them at once:
+
+    openflight-server --sensitivity high
+
+- low: only strong, fast shots count (busy rooms, people walking past)
+- normal: the MonitorConfig defaults
+- high: weak and slow shots count too (chips, pitches, soft wedges)
+- indoor-net: the ball is only seen for the few feet before the net, and
+  walls and the net reflect strongly, so shots are short and thresholds
+  strict until a backswing is seen (see takeaway)
+- outdoor-range: nothing nearby reflects and the ball is tracked into the
+  distance, so weaker returns are accepted
+
+A preset only sets the fields listed here; the rest keep the values of the
+config it is applied to (a calibration profile's, or the defaults).
+"""
+
+from typing import Any, Dict
+
+PRESETS: Dict[str, Dict[str, Any]] = {
+    "low": {
+        "min_magnitude": 40,
+        "min_shot_magnitude": 200,
+        "min_club_speed_mph": 40,
+        "min_ball_speed_mph": 50,
+    },
+    "normal": {},
+    "high": {
+        "min_magnitude": 10,
+        "min_shot_magnitude": 50,
+        "min_club_speed_mph": 20,
+        "min_ball_speed_mph": 20,
+    },
+    "indoor-net": {
+        "min_shot_magnitude": 150,
+        "shot_timeout_sec": 0.3,
+        "max_shot_duration_sec": 0.2,
+        "takeaway_window_sec": 2.0,
+        "takeaway_threshold_scale": 0.5,
+    },
+    "outdoor-range": {
+        "min_magnitude": 15,
+        "min_shot_magnitude": 70,
+        "max_shot_duration_sec": 0.4,
+    },
+}
+
+SENSITIVITY_LEVELS = tuple(PRESETS)
+
+
+def apply_sensitivity(config: Any, name: str) -> Any:
+    """
+    The config with a preset's thresholds applied.
+
+    Args:
+        config: MonitorConfig to start from
+        name: Preset name (one of SENSITIVITY_LEVELS)
+
+    Returns:
+        New MonitorConfig (config is unchanged)
+
+    Raises:
+        ValueError: If the preset is unknown
+    """
+    if name not in PRESETS:
+        raise ValueError(
+            f"Unknown sensitivity {name!r} (choose from: {', '.join(SENSITIVITY_LEVELS)})"
+        )
+    return config.with_updates(PRESETS[name])
+
+
+def describe(name: str) -> str:
+    """The preset's changes as text, e.g. "min_magnitude=10, min_shot_magnitude=50"."""
+    changes = PRESETS[name]
+    if not changes:
+        return "defaults"
+    return ", ".join(f"{key}={value:g}" for key, value in changes.items())
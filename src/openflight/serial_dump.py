@@ -0,0 +1,343 @@
+"""
+Raw serial capture and replay.
+
+With --dump-serial FILE, every byte read from the radar (and every command
+written to it) is teed into a capture file with the time it arrived. When
+a user reports "garbage readings", their capture replays through the same
+parser here, byte for byte:
+
+    openflight-parse-dump capture.ofdump
+    openflight-parse-dump capture.ofdump --frames
+
+Unlike the raw radar log of the session logger, which records lines after
+they were decoded as text, the capture keeps the bytes as they came off
+the port: partial lines, stray control characters and binary IWR6843
+packets included.
+
+File format: one JSON header line, e.g.
+
+    {"format": "openflight-serial-dump", "version": 1, "radar": "ops243", "started": "..."}
+
+then one record per read or write: a little-endian float64 Unix time, a
+direction byte ("<" read from the radar, ">" written to it), a uint32
+length and the bytes. For the IWR6843 only its data port is captured.
+"""
+
+import argparse
+import json
+import struct
+import threading
+import time
+from collections import Counter
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import BinaryIO, Iterator, List, Optional, Tuple
+
+from .frames import (
+    FftFrame,
+    Frame,
+    IWR6843Decoder,
+    OPS243Decoder,
+    PointCloud,
+    RawAdc,
+    SpeedTarget,
+    Status,
+)
+
+FORMAT_NAME = "openflight-serial-dump"
+FORMAT_VERSION = 1
+RADAR_MODELS = ("ops243", "iwr6843")
+
+READ = b"<"
+WRITE = b">"
+
+_RECORD_HEADER = struct.Struct("<dcI")
+
+
+@dataclass
+class DumpRecord:
+    """One read from or write to the radar."""
+
+    timestamp: float  # Unix time
+    direction: bytes  # READ or WRITE
+    data: bytes
+
+
+class SerialDump:
+    """
+    Capture file that serial traffic is recorded into.
+
+    Example:
+        dump = SerialDump("capture.ofdump", radar="ops243")
+        radar.dump = dump   # before radar.connect()
+        ...
+        dump.close()
+    """
+
+    def __init__(self, path: str, radar: str = "ops243"):
+        """
+        Create the capture file (overwriting an existing one).
+
+        Args:
+            path: Capture file
+            radar: Radar model the capture is from ("ops243" or "iwr6843")
+        """
+        if radar not in RADAR_MODELS:
+            raise ValueError(f"Unknown radar model: {radar!r}")
+        self.path = path
+        self.radar = radar
+        self._lock = threading.Lock()
+        self._file: Optional[BinaryIO] = open(path, "wb")  # pylint: disable=consider-using-with
+        header = {
+            "format": FORMAT_NAME,
+            "version": FORMAT_VERSION,
+            "radar": radar,
+            "started": datetime.now().isoformat(),
+        }
+        self._file.write(json.dumps(header).encode("utf-8") + b"\n")
+        self._file.flush()
+
+    def record(self, direction: bytes, data: bytes, timestamp: Optional[float] = None):
+        """
+        Append a record (ignored once closed, or for empty data).
+
+        Args:
+            direction: READ or WRITE
+            data: Bytes read or written
+            timestamp: Unix time (default: now)
+        """
+        if not data:
+            return
+        timestamp = time.time() if timestamp is None else timestamp
+        with self._lock:
+            if self._file is None:
+                return
+            self._file.write(_RECORD_HEADER.pack(timestamp, direction, len(data)) + bytes(data))
+            # Flushed every record so a crash or power cut keeps what led up to it
+            self._file.flush()
+
+    def tee(self, port) -> "TeeSerial":
+        """Wrap an open serial port so its traffic is recorded here."""
+        return TeeSerial(port, self)
+
+    def close(self):
+        """Close the capture file."""
+        with self._lock:
+            if self._file is not None:
+                self._file.close()
+                self._file = None
+
+
+class TeeSerial:
+    """
+    Serial port wrapper that records reads and writes into a SerialDump.
+
+    Everything else (timeout, in_waiting, reset_input_buffer...) goes
+    straight to the wrapped port.
+    """
+
+    def __init__(self, port, dump: SerialDump):
+        self._port = port
+        self._dump = dump
+
+    def read(self, *args, **kwargs) -> bytes:
+        data = self._port.read(*args, **kwargs)
+        self._dump.record(READ, data)
+        return data
+
+    def readline(self, *args, **kwargs) -> bytes:
+        data = self._port.readline(*args, **kwargs)
+        self._dump.record(READ, data)
+        return data
+
+    def read_until(self, *args, **kwargs) -> bytes:
+        data = self._port.read_until(*args, **kwargs)
+        self._dump.record(READ, data)
+        return data
+
+    def write(self, data):
+        self._dump.record(WRITE, bytes(data))
+        return self._port.write(data)
+
+    def __getattr__(self, name):
+        return getattr(self._port, name)
+
+    def __setattr__(self, name, value):
+        if name.startswith("_"):
+            super().__setattr__(name, value)
+        else:
+            setattr(self._port, name, value)
+
+
+def read_dump(path: str) -> Tuple[dict, Iterator[DumpRecord]]:
+    """
+    Open a capture file.
+
+    Returns:
+        (header, records); records stop at a truncated final record
+
+    Raises:
+        ValueError: If the file isn't a serial capture
+    """
+    f = open(path, "rb")  # pylint: disable=consider-using-with
+    try:
+        header = json.loads(f.readline())
+    except ValueError:
+        header = None
+    if not isinstance(header, dict) or header.get("format") != FORMAT_NAME:
+        f.close()
+        raise ValueError(f"{path} is not an OpenFlight serial capture")
+    if header.get("version") != FORMAT_VERSION:
+        f.close()
+        raise ValueError(f"{path}: unsupported capture version {header.get('version')}")
+
+    def records() -> Iterator[DumpRecord]:
+        with f:
+            while True:
+                head = f.read(_RECORD_HEADER.size)
+                if len(head) < _RECORD_HEADER.size:
+                    return
+                timestamp, direction, length = _RECORD_HEADER.unpack(head)
+                data = f.read(length)
+                if len(data) < length:
+                    return
+                yield DumpRecord(timestamp, direction, data)
+
+    return header, records()
+
+
+@dataclass
+class ReplayResult:
+    """What the parser made of a capture."""
+
+    radar: str
+    frames: List[Tuple[float, Frame]] = field(default_factory=list)  # (Unix time, frame)
+    unparsed: List[Tuple[float, bytes]] = field(default_factory=list)  # Lines that weren't frames
+    discarded: int = 0  # NaN/infinite samples dropped by the decoder
+    bytes_read: int = 0
+    bytes_written: int = 0
+    first_time: Optional[float] = None
+    last_time: Optional[float] = None
+
+    @property
+    def duration_sec(self) -> float:
+        """Time from the first to the last record."""
+        if self.first_time is None:
+            return 0.0
+        return self.last_time - self.first_time
+
+    def frame_counts(self) -> Counter:
+        """Number of frames of each type."""
+        return Counter(type(frame).__name__ for _, frame in self.frames)
+
+
+def replay(header: dict, records: Iterator[DumpRecord]) -> ReplayResult:
+    """
+    Run the capture's reads through the radar's parser.
+
+    Args:
+        header: Capture header (from read_dump)
+        records: Capture records (from read_dump)
+
+    Returns:
+        Decoded frames, unparsed lines and byte counts
+    """
+    result = ReplayResult(radar=header.get("radar", "ops243"))
+    decoder = IWR6843Decoder() if result.radar == "iwr6843" else OPS243Decoder()
+    pending = b""  # Unfinished OPS243 line
+    for record in records:
+        if result.first_time is None:
+            result.first_time = record.timestamp
+        result.last_time = record.timestamp
+        if record.direction == WRITE:
+            result.bytes_written += len(record.data)
+            continue
+        result.bytes_read += len(record.data)
+        if result.radar == "iwr6843":
+            for frame in decoder.feed(record.data, record.timestamp):
+                result.frames.append((record.timestamp, frame))
+            continue
+        pending += record.data
+        *lines, pending = pending.split(b"\n")
+        for raw in lines:
+            text = raw.decode("ascii", errors="ignore").strip()
+            discarded = decoder.discarded
+            frame = decoder.decode(text, record.timestamp)
+            if frame is not None:
+                result.frames.append((record.timestamp, frame))
+            elif raw.strip() and decoder.discarded == discarded and not text.startswith('{"I"'):
+                # NaN samples are counted apart; I lines only become frames with their Q line
+                result.unparsed.append((record.timestamp, raw))
+    result.discarded = decoder.discarded
+    return result
+
+
+def describe_frame(frame: Frame) -> str:
+    """One-line summary of a frame."""
+    if isinstance(frame, SpeedTarget):
+        return ", ".join(
+            f"{t.speed:.1f} {frame.unit} {t.direction.value}"
+            + (f" mag {t.magnitude:.0f}" if t.magnitude is not None else "")
+            for t in frame.targets
+        )
+    if isinstance(frame, PointCloud):
+        return f"frame {frame.frame_number}, {len(frame.points)} point(s)"
+    if isinstance(frame, RawAdc):
+        return f"{len(frame.i_samples)} I/Q samples"
+    if isinstance(frame, FftFrame):
+        return f"{len(frame.magnitudes)} bins"
+    if isinstance(frame, Status):
+        return json.dumps(frame.values)
+    return ""
+
+
+def main():
+    """CLI entry point: replay a serial capture through the parser."""
+    parser = argparse.ArgumentParser(
+        description="Re-run the radar parser over a --dump-serial capture"
+    )
+    parser.add_argument("capture", help="Capture file written with --dump-serial")
+    parser.add_argument(
+        "--frames", action="store_true", help="Print every decoded frame, not just a summary"
+    )
+    parser.add_argument(
+        "--radar",
+        choices=RADAR_MODELS,
+        help="Parse as this radar's output (default: the model in the capture header)",
+    )
+    args = parser.parse_args()
+
+    try:
+        header, records = read_dump(args.capture)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+    if args.radar:
+        header["radar"] = args.radar
+    result = replay(header, records)
+
+    def offset(timestamp: float) -> str:
+        return f"{timestamp - result.first_time:9.3f}s"
+
+    if args.frames:
+        for timestamp, frame in result.frames:
+            print(f"{offset(timestamp)}  {type(frame).__name__:<12} {describe_frame(frame)}")
+        print()
+
+    print(
+        f"{args.capture}: {result.radar}, {result.duration_sec:.1f}s, "
+        f"{result.bytes_read} bytes read, {result.bytes_written} bytes written"
+    )
+    print(f"Started: {header.get('started', 'unknown')}")
+    print("Frames:")
+    for name, count in sorted(result.frame_counts().items()):
+        print(f"  {name:<12} {count}")
+    if not result.frames:
+        print("  (none)")
+    print(f"Discarded samples (NaN/infinite): {result.discarded}")
+    print(f"Unparsed lines: {len(result.unparsed)}")
+    for timestamp, raw in result.unparsed:
+        print(f"  {offset(timestamp)}  {raw!r}")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,28 +1,129 @@
 """
 WebSocket server for OpenFlight UI.
 
-Provides real-time shot data to the web frontend via Flask-SocketIO.
+Provides real-time shot data to the web frontend via Flask-SocketIO, and a
+JSON REST API under /api for companion apps and scripts.
 """
 
+import _thread
 import json
 import logging
+import math
 import os
+import queue
 import random
+import signal
 import statistics
+import sys
 import threading
 import time
 from datetime import datetime
 from pathlib import Path
-from typing import List, Optional
+from typing import Any, Callable, Dict, List, Optional
 
-from flask import Flask, Response, send_from_directory
+from flask import Flask, Response, jsonify, request, send_from_directory
 from flask_cors import CORS
 from flask_socketio import SocketIO
 
-from .launch_monitor import ClubType, LaunchMonitor, Shot
-from .ops243 import Direction, SpeedReading, set_show_raw_readings
+from .access import TOKEN_HEADER, AccessPolicy
+from .arming import ArmingStateMachine
+from .ball_types import ball_type_for
+from .ball_types import describe as describe_ball_type
+from .bays import shot_payload
+from .bluetooth import DEFAULT_CHANNEL, get_bluetooth_serial, init_bluetooth_serial
+from .capture import CaptureWriter
+from .carry import get_carry_model_specs, set_carry_models
+from .classifier import ShotClassifier, load_classifier
+from .clock import get_clock
+from .cloud_sync import get_cloud_sync, init_cloud_sync, session_files
+from .config import apply_env_defaults, build_monitor_layers, parse_assignments
+from .connector import ConnectorFeed
+from .container import device_hint, in_container
+from .control import ControlError, default_socket_path, get_control_server, init_control_server
+from .crash_report import get_crash_reporter, init_crash_reporter
+from .dispersion import DEFAULT_BASKET_PCT, club_dispersion
+from .energy import energy_stats
+from .esp_bridge import EspBridgeRadar
+from .export import load_template, to_csv
+from .failover import FailoverGroup
+from .feedback import (
+    FEEDBACK_METRICS,
+    FEEDBACK_OUTPUTS,
+    FeedbackCurve,
+    LedOutput,
+    ShotFeedback,
+    ToneOutput,
+    parse_pins,
+)
+from .geometry import MountingGeometry, apply_cosine_correction
+from .gestures import ClubGestureDetector, club_announcement, next_club, parse_bag, speak
+from .fit_export import to_fit
+from .grpc_service import DEFAULT_GRPC_PORT, ServiceError, get_grpc_server, init_grpc_server
+from .html_export import to_html
+from .impact import impact_stats
+from .iwr6843 import IWR6843Radar
+from .launch_monitor import ClubType, LaunchMonitor, MonitorConfig, Shot
+from .openapi import build_spec, swagger_ui_page
+from .ops243 import Direction, MountPosition, SpeedReading, set_show_raw_readings
 from .rolling_buffer.monitor import get_optimal_spin_for_ball_speed
-from .session_logger import get_session_logger, init_session_logger
+from .rounds import RoundTracker
+from .percentiles import ShotHistory
+from .profiles import (
+    DEFAULT_PROFILE_DIR,
+    CalibrationProfile,
+    ProfileWatcher,
+    diff_profiles,
+    list_profiles,
+    load_profile,
+    save_profile,
+)
+from .records import PersonalRecords
+from .relay import get_relay, init_relay
+from .reprocess import find_sessions, shot_from_entry
+from .retention import format_result, vacuum
+from .schedule import ArmSchedule, ScheduledDisarm, parse_quiet_hours
+from .shot_timing import ShotTiming
+from .scoring import PracticeTarget, session_score
+from .sensitivity import SENSITIVITY_LEVELS, describe
+from .serial_dump import SerialDump
+from .speed_training import (
+    DEFAULT_REST_SEC,
+    SpeedTrainingSession,
+    SwingDetector,
+    format_summary,
+    parse_protocol,
+)
+from .status_feed import StatusFeed, default_status_socket_path, radar_health
+from .session_logger import (
+    SessionLogger,
+    get_session_logger,
+    init_session_logger,
+    parse_tag,
+    validate_tags,
+)
+from .session_split import PLAYER, SessionSplitter
+from .sessions import read_shots
+from .supervisor import supervise
+from .swing_speed import SwingSpeedMonitor
+from .tempo import DEFAULT_BACKSWING_SEC, TempoCues, TempoMeter, compare_tempo, parse_ratio
+from .throttle import RateLimiter, parse_reading_hz
+from .timesync import (
+    DEFAULT_MATCH_WAIT_SEC,
+    DEFAULT_MATCH_WINDOW_SEC,
+    DEFAULT_TIME_SYNC_PORT,
+    CompanionEvents,
+    TimeSyncResponder,
+)
+from .tracing import get_tracer, init_tracer, trace_span, traced
+from .voice_memo import DEFAULT_MEMO_SEC, VoiceMemoRecorder, memo_path, watch_button, watch_key
+from .webhooks import (
+    MERGED_ERROR,
+    THROTTLE_POLICIES,
+    DeliveryReceipt,
+    get_webhook_sink,
+    init_webhook_sink,
+)
+from .winservice import watch_commands
 
 # Configure logging
 logger = logging.getLogger(__name__)
@@ -46,16 +147,60 @@ except ImportError:
     PICAMERA_AVAILABLE = False
 
 
+RADAR_MODES = ("streaming", "rolling-buffer", "swing-speed")
+# Radar backends without the OPS243 rolling buffer over a local serial port
+STREAMING_ONLY_RADARS = ("iwr6843", "esp-bridge")
+
 app = Flask(__name__, static_folder="../../ui/dist", static_url_path="")
+REMOTE_DIR = Path(__file__).parent / "remote"
 CORS(app)
 socketio = SocketIO(app, cors_allowed_origins="*", async_mode="threading")
 
 # Global state
 monitor: Optional["LaunchMonitor | MockLaunchMonitor"] = None
 mock_mode: bool = False
+monitor_mode: str = "streaming"  # One of RADAR_MODES
+monitor_args: Dict[str, Any] = {}  # start_monitor arguments, reused by set_mode
+resume_session_path: Optional[Path] = None  # Session log the next start continues
 debug_mode: bool = False
 debug_log_file = None
 debug_log_path: Optional[Path] = None
+access_policy = AccessPolicy()  # Open by default; see --api-token / --allow
+mounting_geometry = MountingGeometry()  # On the target line = no cosine correction
+mount_position = MountPosition.BEHIND  # Decides which Doppler direction is ball flight
+launch_angle_offset_deg: float = 0.0  # Camera tilt correction from the calibration profile
+active_profile: Optional[str] = None
+loaded_profile: Optional[CalibrationProfile] = None  # The --profile, for its shot timing
+sensitivity: Optional[str] = None  # --sensitivity preset, applied over the profile
+ball_type: Optional[str] = None  # --ball-type naming a ball type, applied over the preset
+profile_dir: Path = DEFAULT_PROFILE_DIR
+config_assignments: Dict[str, Any] = {}  # --set thresholds, applied over everything else
+arming: Optional[ArmingStateMachine] = None  # Set by --auto-arm
+scheduled_disarm: Optional[ScheduledDisarm] = None  # Quiet hours / idle disarm, if scheduled
+schedule_flags: dict = {}  # --quiet-hours / --idle-disarm, applied over the profile's schedule
+session_splitter: Optional[SessionSplitter] = None  # --session-idle / --session-per-player
+profile_watcher: Optional[ProfileWatcher] = None  # Hot reload of the --profile file
+round_tracker = RoundTracker()  # Hole context reported by the sim (PUT /api/round)
+practice_target = PracticeTarget()  # Target distance shots are scored against (PUT /api/target)
+club_gesture: Optional[ClubGestureDetector] = None  # Set by --club-gesture
+club_gesture_bag: Optional[List[ClubType]] = None  # Clubs the gesture cycles through
+tempo_meter: Optional[TempoMeter] = None  # Set by --tempo
+tempo_target: Optional[float] = None  # Target backswing:downswing ratio
+tempo_cues: Optional[TempoCues] = None  # Set by --tempo-cues
+speed_training: Optional[SpeedTrainingSession] = None  # Set by --speed-training
+speed_swings: Optional[SwingDetector] = None  # Club-only swings for --speed-training
+shot_feedback: Optional[ShotFeedback] = None  # LED / tone per shot, set by --feedback
+sensor_capture: Optional[CaptureWriter] = None  # Multi-sensor timeline, set by --capture
+time_sync: Optional[TimeSyncResponder] = None  # Companion clock sync, set by --time-sync
+companion_angles = CompanionEvents()  # Launch angles from a companion camera, in monitor time
+voice_memo: Optional[VoiceMemoRecorder] = None  # Set by --voice-memo
+memo_button: Any = None  # gpiozero Button for --memo-pin (kept so it stays active)
+connector_feed: Optional[ConnectorFeed] = None  # Tablet app polling feed, set by --connector
+status_feed: Optional[StatusFeed] = None  # Menu bar status, set by --status-socket/--status-port
+shot_history: Optional[ShotHistory] = None  # Percentile context per shot (--no-percentiles)
+personal_records: Optional[PersonalRecords] = None  # Per-club bests (--no-records)
+speak_records: bool = False  # Say new personal records out loud (--speak-records)
+webhook_payload = "full"  # "sim": webhooks get only the fields a sim needs
 
 # Camera state
 camera: Optional["Picamera2"] = None
@@ -197,6 +342,11 @@ def shot_to_dict(shot: Shot) -> dict:
         "ball_speed_mph": round(shot.ball_speed_mph, 1),
         "club_speed_mph": round(shot.club_speed_mph, 1) if shot.club_speed_mph else None,
         "smash_factor": round(shot.smash_factor, 2) if shot.smash_factor else None,
+        # Kinetic energy in joules (None without a club speed, except the ball's)
+        **{
+            name: round(value, 1) if value is not None else None
+            for name, value in shot.energy.items()
+        },
         "estimated_carry_yards": round(shot.estimated_carry_yards),
         "carry_range": [
             round(shot.estimated_carry_range[0]),
@@ -216,6 +366,49 @@ def shot_to_dict(shot: Shot) -> dict:
         "carry_spin_adjusted": round(shot.carry_spin_adjusted)
         if shot.carry_spin_adjusted
         else None,
+        "mode": shot.mode,
+        # Measured speeds before cosine correction (None if radar is on the target line)
+        "ball_speed_raw_mph": round(shot.ball_speed_raw_mph, 1)
+        if shot.ball_speed_raw_mph
+        else None,
+        "club_speed_raw_mph": round(shot.club_speed_raw_mph, 1)
+        if shot.club_speed_raw_mph
+        else None,
+        # Number in the session log, for DELETE/PATCH /api/shot (None if logging is off)
+        "shot_number": shot.log_number,
+        # Sim round context (None unless the sim reported a hole)
+        "hole_number": shot.hole_number,
+        "hole_shot": shot.hole_shot,
+        # Webhook delivery (None when no webhooks are configured)
+        "delivery_status": shot.delivery_status,
+        # Swing tempo (None unless --tempo measured it)
+        "tempo_ratio": round(shot.tempo_ratio, 2) if shot.tempo_ratio else None,
+        "tempo_backswing_sec": round(shot.tempo_backswing_sec, 2)
+        if shot.tempo_backswing_sec
+        else None,
+        "tempo_downswing_sec": round(shot.tempo_downswing_sec, 2)
+        if shot.tempo_downswing_sec
+        else None,
+        "tempo_on_target": shot.tempo_on_target,
+        # Impact minus the club's peak speed (None without a club speed)
+        "impact_offset_ms": round(shot.impact_offset_ms, 1)
+        if shot.impact_offset_ms is not None
+        else None,
+        # Radar frames lost in the ball's readings (None without timed readings)
+        "lost_frames": shot.lost_frames,
+        "max_reading_gap_ms": round(shot.max_reading_gap_ms, 1)
+        if shot.max_reading_gap_ms is not None
+        else None,
+        "ball_speed_reading_mph": round(shot.ball_speed_reading_mph, 1)
+        if shot.ball_speed_reading_mph
+        else None,
+        # Practice scoring (None unless a target is set)
+        "target_yards": shot.target_yards,
+        "strokes_gained": round(shot.strokes_gained, 2)
+        if shot.strokes_gained is not None
+        else None,
+        # Ball type the thresholds and carry were adjusted for (None = premium defaults)
+        "ball_type": shot.ball_type,
     }
 
 
@@ -225,6 +418,24 @@ def index():
     return send_from_directory(app.static_folder, "index.html")
 
 
+@app.route("/remote")
+def remote():
+    """Serve the phone remote (club, player, mulligan, re-arm)."""
+    return send_from_directory(REMOTE_DIR, "index.html")
+
+
+@app.route("/openapi.json")
+def openapi_spec():
+    """Serve the REST API's OpenAPI document."""
+    return jsonify(build_spec(app))
+
+
+@app.route("/docs")
+def api_docs():
+    """Serve Swagger UI for the REST API."""
+    return Response(swagger_ui_page(), mimetype="text/html")
+
+
 @app.route("/<path:path>")
 def static_files(path):
     """Serve static files."""
@@ -335,6 +546,11 @@ def camera_processing_loop():
                         },
                     )
 
+                if arming:
+                    arm_event = arming.update(new_detected)
+                    if arm_event:
+                        publish_arm_event(arm_event)
+
                 # Get debug frame with overlay if streaming
                 if camera_streaming:
                     frame = camera_tracker.get_debug_frame(frame)
@@ -352,6 +568,51 @@ def camera_processing_loop():
             time.sleep(0.1)
 
 
+def publish_arm_event(event: str):
+    """Tell the UI, stream subscribers and webhooks the monitor is (not) ready."""
+    if event == "ready" and scheduled_disarm and scheduled_disarm.disarmed:
+        print(f"[ARM] Not ready: disarmed ({scheduled_disarm.reason.replace('_', ' ')})")
+        return
+    print(f"[ARM] {event.replace('_', ' ').upper()}")
+    payload = {"ready": event == "ready", "timestamp": datetime.now().isoformat()}
+    socketio.emit("arm_state", payload)
+    publish_stream_event(event, payload)
+
+    webhook_sink = get_webhook_sink()
+    if webhook_sink:
+        webhook_sink.send({"event": event, **payload})
+
+
+def on_schedule_change(event: str, reason: Optional[str]):
+    """Publish the schedule disarming ("disarmed") or re-arming ("armed") the monitor."""
+    state = scheduled_disarm.to_dict() if scheduled_disarm else {"disarmed": False}
+    print(f"[SCHEDULE] {event.upper()}" + (f" ({reason.replace('_', ' ')})" if reason else ""))
+    socketio.emit("schedule_state", state)
+    publish_stream_event(event, state)
+    if event == "disarmed" and arming and arming.armed:
+        arming.on_shot()
+        publish_arm_event("not_ready")
+
+
+def build_schedule(profile_schedule: ArmSchedule) -> ArmSchedule:
+    """The profile's schedule with --quiet-hours / --idle-disarm applied over it."""
+    return ArmSchedule.from_dict({**profile_schedule.to_dict(), **schedule_flags})
+
+
+def apply_schedule(schedule: ArmSchedule):
+    """Start, update or stop scheduled disarming."""
+    global scheduled_disarm  # pylint: disable=global-statement
+
+    if scheduled_disarm:
+        scheduled_disarm.set_schedule(schedule)
+        if not schedule.enabled:
+            scheduled_disarm.stop()
+            scheduled_disarm = None
+    elif schedule.enabled:
+        scheduled_disarm = ScheduledDisarm(schedule, on_change=on_schedule_change)
+        scheduled_disarm.start()
+
+
 def start_camera_thread():
     """Start the camera processing thread."""
     global camera_thread, camera_stop_event  # pylint: disable=global-statement
@@ -530,28 +791,112 @@ def log_debug_reading(reading: SpeedReading):
         )
 
 
+def cycle_club():
+    """Select the next club in the gesture bag and announce it."""
+    club = next_club(monitor.get_club(), club_gesture_bag)
+    monitor.set_club(club)
+    print(f"[GESTURE] Club: {club.value}")
+    socketio.emit("club_changed", {"club": club.value})
+    publish_stream_event("club_changed", {"club": club.value, "source": "gesture"})
+    speak(club_announcement(club))
+
+
+def record_speed_swing(speed_mph: Optional[float]):
+    """Count a club-only swing towards the --speed-training protocol."""
+    if speed_mph is None or not speed_training:
+        return
+    rep = speed_training.record_swing(speed_mph)
+    if rep is None:
+        return
+    print(
+        f"[SPEED] {rep['label']} rep {rep['rep']}/{rep['reps']}: {rep['speed_mph']:.1f} mph "
+        f"(best {rep['set_best_mph']:.1f})"
+    )
+    session_logger = get_session_logger()
+    if session_logger:
+        session_logger.log_speed_swing(rep)
+    socketio.emit("speed_rep", rep)
+    publish_stream_event("speed_rep", rep)
+    if rep["complete"]:
+        summary = speed_training.summary()
+        for line in format_summary(summary):
+            print(f"[SPEED] {line}")
+        if session_logger:
+            session_logger.log_speed_training_summary(summary)
+        socketio.emit("speed_training_summary", summary)
+        publish_stream_event("speed_training_summary", summary)
+
+
+def on_swing_detected(swing):
+    """Callback when a club-only swing is detected (--mode swing-speed)."""
+    session_logger = get_session_logger()
+    if session_logger:
+        session_logger.log_swing(
+            swing.club_speed_mph, swing.club.value, swing.peak_magnitude, swing.readings_data
+        )
+    data = swing.to_dict()
+    stats = monitor.get_session_stats() if monitor else {}
+    socketio.emit("swing", {"swing": data, "stats": stats})
+    publish_stream_event("swing", data)
+    if not speed_swings:
+        record_speed_swing(swing.club_speed_mph)
+
+
+def _speed_swing_loop():
+    """End the last swing once the readings stop (no reading comes to end it)."""
+    while True:
+        time.sleep(0.1)
+        if speed_swings:
+            record_speed_swing(speed_swings.poll())
+
+
 def on_live_reading(reading: SpeedReading):
-    """Callback for live radar readings - used in debug mode."""
+    """Callback for live radar readings - debug mode, gestures, tempo and speed training."""
+    crash_reporter = get_crash_reporter()
+    if crash_reporter:
+        crash_reporter.record_reading(reading)
+    if club_gesture and monitor and club_gesture.update(reading):
+        cycle_club()
+    if tempo_meter:
+        tempo_meter.update(reading)
+    if speed_swings:
+        record_speed_swing(speed_swings.update(reading))
+    if sensor_capture:
+        sensor_capture.record_reading(reading)
+
     # Log ALL readings first (before filtering) so we can debug direction issues
     if debug_mode:
         log_debug_reading(reading)
 
-        # Emit ALL readings to UI debug panel (including inbound)
-        socketio.emit(
-            "debug_reading",
+        # Emit ALL readings to UI debug panel (including inbound), at each
+        # client's own rate so a slow one can't back up the rest
+        data = {
+            "speed": reading.speed,
+            "direction": reading.direction.value,
+            "magnitude": reading.magnitude,
+            "timestamp": datetime.now().isoformat(),
+            "filtered": reading.direction not in mount_position.ball_directions,
+        }
+        with _socket_limits_lock:
+            recipients = [sid for sid, limiter in _socket_reading_limits.items() if limiter.allow()]
+        for sid in recipients:
+            socketio.emit("debug_reading", data, to=sid)
+
+    if _stream_subscribers:
+        publish_stream_event(
+            "reading",
             {
                 "speed": reading.speed,
                 "direction": reading.direction.value,
                 "magnitude": reading.magnitude,
-                "timestamp": datetime.now().isoformat(),
-                "filtered": reading.direction != Direction.OUTBOUND,
+                "timestamp": get_clock().to_wall(reading.timestamp),
             },
         )
 
-    # Filter out inbound readings for shot detection
+    # Filter out readings moving away from the target for shot detection
     # Note: shot filtering happens in launch_monitor.py but we also filter here
-    # for any UI purposes that need only outbound readings
-    if reading.direction != Direction.OUTBOUND:
+    # for any UI purposes that need only ball-direction readings
+    if reading.direction not in mount_position.ball_directions:
         return
 
 
@@ -563,7 +908,7 @@ def _get_trigger_status() -> dict:
     session_logger = get_session_logger()
     stats = session_logger.stats if session_logger else {}
 
-    mode = "mock" if mock_mode else ("rolling-buffer" if is_rolling_buffer else "streaming")
+    mode = "mock" if mock_mode else monitor_mode
     trigger_type = None
     radar_port = None
 
@@ -584,8 +929,29 @@ def _get_trigger_status() -> dict:
 
 
 @socketio.on("connect")
-def handle_connect():
-    """Handle client connection."""
+def handle_connect(auth=None):
+    """Handle client connection.
+
+    With --api-token, the client must send the token in the connect auth
+    payload ({"token": ...}) or the handshake's token headers, since the
+    socket's events change settings just like /api calls.
+    """
+    if not access_policy.is_address_allowed(request.remote_addr):
+        print(f"[ACCESS] Rejected WebSocket client {request.remote_addr} (not in allowlist)")
+        return False
+    token = auth.get("token") if isinstance(auth, dict) else None
+    if not access_policy.is_token_valid(
+        request.headers.get("Authorization"), token or request.headers.get(TOKEN_HEADER)
+    ):
+        print(f"[ACCESS] Rejected WebSocket client {request.remote_addr} (missing or bad token)")
+        return False
+    try:
+        reading_hz = parse_reading_hz(request.args.get("reading_hz"))
+    except ValueError as e:
+        print(f"[WARN] {e}; using the default live reading rate")
+        reading_hz = parse_reading_hz(None)
+    with _socket_limits_lock:
+        _socket_reading_limits[request.sid] = RateLimiter(reading_hz)
     print("Client connected")
     if monitor:
         stats = monitor.get_session_stats()
@@ -609,6 +975,8 @@ def handle_connect():
 @socketio.on("disconnect")
 def handle_disconnect():
     """Handle client disconnection."""
+    with _socket_limits_lock:
+        _socket_reading_limits.pop(request.sid, None)
     print("Client disconnected")
 
 
@@ -760,235 +1128,1738 @@ def handle_set_radar_config(data):
         socketio.emit("radar_config_error", {"error": str(e)})
 
 
-def on_shot_detected(shot: Shot):
-    """Callback when a shot is detected - emit to all clients."""
-    global ball_detected, ball_detection_confidence  # pylint: disable=global-statement
+# =============================================================================
+# REST API
+#
+# JSON endpoints for companion apps and scripts that don't want to speak
+# Socket.IO. /api/stream delivers the same shot (and live reading) events as
+# Server-Sent Events.
+# =============================================================================
 
-    logger.debug("Shot callback triggered: %.1f mph", shot.ball_speed_mph)
+class StreamSubscriber:
+    """
+    One /api/stream client's queue and reading filter.
 
-    # Try to get launch angle from camera BEFORE emitting shot
-    # Skip camera for mock shots — they already have simulated launch angle
-    camera_data = None
-    try:
-        if camera_tracker and camera_enabled and shot.mode != "mock":
-            launch_angle = camera_tracker.calculate_launch_angle()
-            if launch_angle:
-                # Update shot object with launch angle data
-                shot.launch_angle_vertical = launch_angle.vertical
-                shot.launch_angle_horizontal = launch_angle.horizontal
-                shot.launch_angle_confidence = launch_angle.confidence
+    Readings are only queued if the client asked for them, at no more than
+    its reading rate, and never into the last quarter of the queue - that
+    space is kept for shot and state events when a client falls behind.
+    """
 
-                camera_data = {
-                    "launch_angle_vertical": launch_angle.vertical,
-                    "launch_angle_horizontal": launch_angle.horizontal,
-                    "launch_angle_confidence": launch_angle.confidence,
-                    "positions_tracked": len(launch_angle.positions),
-                    "launch_detected": camera_tracker.launch_detected,
-                }
-                logger.info(
-                    "Launch angle: %.1f° V, %.1f° H (conf: %.0f%%)",
-                    launch_angle.vertical,
-                    launch_angle.horizontal,
-                    launch_angle.confidence * 100,
-                )
+    QUEUE_SIZE = 1000
 
-            # Reset camera tracker for next shot
-            camera_tracker.reset()
-            ball_detected = False
-            ball_detection_confidence = 0.0
-    except Exception as e:
-        logger.warning("Camera processing error: %s", e)
-        camera_data = None
+    def __init__(self, include_readings: bool = False, reading_hz: float = 0.0):
+        self.queue: "queue.Queue[dict]" = queue.Queue(maxsize=self.QUEUE_SIZE)
+        self.include_readings = include_readings
+        self.limiter = RateLimiter(reading_hz)
 
-    # If no camera launch angle, estimate from club type and ball speed
-    if shot.launch_angle_vertical is None and shot.mode != "mock":
-        estimated = estimate_launch_angle(
-            shot.club,
-            shot.ball_speed_mph,
-            club_speed_mph=shot.club_speed_mph,
-            spin_rpm=shot.spin_rpm,
-        )
-        shot.launch_angle_vertical = estimated[0]
-        shot.launch_angle_horizontal = 0.0
-        shot.launch_angle_confidence = estimated[1]
-        logger.info(
-            "Estimated launch angle: %.1f° (conf: %.0f%%)", estimated[0], estimated[1] * 100
-        )
+    def offer(self, event: str, data: dict):
+        """Queue an event for this client, dropping it if the client is backed up."""
+        if event == "reading":
+            if not self.include_readings:
+                return
+            if self.queue.qsize() >= self.QUEUE_SIZE * 3 // 4 or not self.limiter.allow():
+                return
+        try:
+            self.queue.put_nowait({"event": event, "data": data})
+        except queue.Full:
+            pass
 
-    # Log shot with all data (radar + spin + camera) in one entry
-    try:
-        session_log = get_session_logger()
-        if session_log:
-            session_log.log_shot(
-                ball_speed_mph=shot.ball_speed_mph,
-                club_speed_mph=shot.club_speed_mph,
-                smash_factor=shot.smash_factor,
-                estimated_carry_yards=shot.estimated_carry_yards,
-                club=shot.club.value,
-                peak_magnitude=shot.peak_magnitude,
-                readings_count=len(shot.readings),
-                readings=shot.readings_data,
-                spin_rpm=shot.spin_rpm,
-                spin_confidence=shot.spin_confidence,
-                spin_quality=shot.spin_quality,
-                carry_spin_adjusted=shot.carry_spin_adjusted,
-                mode=shot.mode,
-                launch_angle_vertical=shot.launch_angle_vertical,
-                launch_angle_horizontal=shot.launch_angle_horizontal,
-                launch_angle_confidence=shot.launch_angle_confidence,
-            )
-    except Exception as e:
-        logger.warning("Failed to log shot: %s", e)
 
-    # Emit shot with launch angle data included
-    try:
-        shot_data = shot_to_dict(shot)
-        stats = monitor.get_session_stats() if monitor else {}
-        socketio.emit("shot", {"shot": shot_data, "stats": stats})
+_stream_subscribers: List[StreamSubscriber] = []
+_stream_lock = threading.Lock()
 
-        # Log shot info
-        angle_str = ""
-        if shot.launch_angle_vertical is not None:
-            angle_str = f", Launch: {shot.launch_angle_vertical:.1f}°"
-        logger.info(
-            "Shot: ball=%.1f mph, carry=%.0f yds%s",
-            shot.ball_speed_mph,
-            shot.estimated_carry_yards,
-            angle_str,
-        )
-    except Exception as e:
-        logger.error("Failed to emit shot: %s", e)
-        return
+# In-process consumers of the same events (e.g. --feedback LED and tones)
+_event_sinks: List[Callable[[str, dict], None]] = []
 
-    # Debug logging (optional)
-    if debug_mode:
-        try:
-            debug_log_entry = {
-                "type": "shot",
-                "timestamp": datetime.now().isoformat(),
-                "radar": {
-                    "ball_speed_mph": shot_data["ball_speed_mph"],
-                    "club_speed_mph": shot_data["club_speed_mph"],
-                    "smash_factor": shot_data["smash_factor"],
-                    "peak_magnitude": shot_data["peak_magnitude"],
-                },
-                "camera": camera_data,
-                "club": shot_data["club"],
-            }
+# Live reading rate limit per Socket.IO connection (sid -> limiter)
+_socket_reading_limits: Dict[str, RateLimiter] = {}
+_socket_limits_lock = threading.Lock()
 
-            if debug_log_file:
-                debug_log_file.write(json.dumps(debug_log_entry) + "\n")
-                debug_log_file.flush()
 
-            socketio.emit("debug_shot", debug_log_entry)
-        except Exception as e:
-            print(f"[WARN] Debug logging error: {e}")
+def publish_stream_event(event: str, data: dict):
+    """
+    Push an event to all /api/stream subscribers (drops if a client is backed up),
+    the in-process event sinks and the remote viewing relay, if one is configured.
+    """
+    with _stream_lock:
+        subscribers = list(_stream_subscribers)
+        sinks = list(_event_sinks)
+    for subscriber in subscribers:
+        subscriber.offer(event, data)
+    for sink in sinks:
+        sink(event, data)
+    relay = get_relay()
+    if relay:
+        relay.send(event, data)
+
+
+def add_event_sink(sink: Callable[[str, dict], None]):
+    """Have sink(event, data) called with every event published to /api/stream."""
+    with _stream_lock:
+        _event_sinks.append(sink)
+
+
+def _api_error(message: str, status: int):
+    """Build a JSON error response."""
+    return jsonify({"error": message}), status
+
+
+@app.before_request
+def enforce_access_policy():
+    """Reject clients outside the allowlist, and /api calls without a valid token."""
+    if not access_policy.is_address_allowed(request.remote_addr):
+        logger.warning("Rejected request from %s (not in allowlist)", request.remote_addr)
+        return _api_error("Forbidden", 403)
+    if request.path.startswith("/api/") and not access_policy.is_token_valid(
+        request.headers.get("Authorization"), request.headers.get(TOKEN_HEADER)
+    ):
+        return _api_error("Missing or invalid API token", 401)
+    return None
+
+
+@app.route("/api/session", methods=["GET"])
+def api_get_session():
+    """Get session stats, shots and current club."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    return jsonify(
+        {
+            "stats": monitor.get_session_stats(),
+            "shots": [shot_to_dict(s) for s in monitor.get_shots()],
+            "mock_mode": mock_mode,
+        }
+    )
 
 
-def start_monitor(
-    port: Optional[str] = None,
-    mock: bool = False,
-    mode: str = "streaming",
-    trigger_type: str = "polling",
-    debug: bool = False,
-    trigger_kwargs: Optional[dict] = None,
-    sample_rate_ksps: int = 30,
-):
-    """
-    Start the launch monitor.
+@app.route("/api/session/tags", methods=["GET"])
+def api_get_session_tags():
+    """Get the session's tags (location, ball type, weather, notes...)."""
+    session_logger = get_session_logger()
+    return jsonify({"tags": session_logger.tags if session_logger else {}})
 
-    Args:
-        port: Serial port for radar
-        mock: Run in mock mode without radar
-        mode: "streaming" (default) or "rolling-buffer"
-        trigger_type: Trigger strategy for rolling-buffer mode
-        debug: Enable verbose FFT/CFAR debug output
+
+@app.route("/api/session/tags", methods=["PATCH"])
+def api_update_session_tags():
     """
-    global monitor, mock_mode  # pylint: disable=global-statement
+    Set or remove session tags. Body: {"weather": "windy", "notes": null}.
 
-    # Stop any existing monitor first
-    if monitor is not None:
-        print("[MONITOR] Stopping existing monitor before starting new one")
-        stop_monitor()
+    Tags not in the body are kept; null or "" removes a tag. The change is
+    written to the session log so later analysis sees it. With
+    --session-per-player, a new player starts a new session instead.
+    """
+    if not get_session_logger():
+        return _api_error("Session logging not initialized", 503)
+    try:
+        changes = validate_tags(request.get_json(silent=True))
+    except ValueError as e:
+        return _api_error(str(e), 400)
+    return jsonify({"tags": update_session_tags(changes)})
 
-    mock_mode = mock
-    if mock:
-        # Mock mode for testing without radar
-        monitor = MockLaunchMonitor()
-    elif mode == "rolling-buffer":
-        # Rolling buffer mode for spin detection
-        from .rolling_buffer import RollingBufferMonitor
 
-        monitor = RollingBufferMonitor(
-            port=port,
-            trigger_type=trigger_type,
-            sample_rate_ksps=sample_rate_ksps,
-            **(trigger_kwargs or {}),
-        )
-        print(
-            f"[MODE] Rolling buffer mode enabled (trigger: {trigger_type}, sample_rate: {sample_rate_ksps}ksps)"
-        )
-    else:
-        # Default streaming mode
-        monitor = LaunchMonitor(port=port, debug=debug)
-        print(f"[MODE] Streaming mode enabled (debug={debug})")
+def update_session_tags(changes: Dict[str, Optional[str]]) -> Dict[str, str]:
+    """
+    Apply validated tag changes to the session and announce the new tags.
 
-    monitor.connect()
+    With --session-per-player, a new player starts a new session instead.
 
-    # Start session logging
+    Returns:
+        The session's tags afterwards
+    """
     session_logger = get_session_logger()
-    if session_logger:
-        radar_info = monitor.get_radar_info() if not mock else {}
-        session_logger.start_session(
-            radar_port=port if not mock else "mock",
-            firmware_version=radar_info.get("Version"),
-            camera_enabled=camera is not None,
-            camera_model="hough" if (camera_tracker and camera_tracker.use_hough) else None,
-            config=radar_config.copy(),
-            mode="mock" if mock else mode,
-            trigger_type=trigger_type if mode == "rolling-buffer" else None,
-        )
+    if session_splitter and session_splitter.player_changed(
+        current_player(), changes.get("player")
+    ):
+        # A new player starts a session of their own, tagged from the start
+        session_splitter.split(PLAYER, changes)
+        tags = session_logger.tags
+    else:
+        tags = session_logger.update_tags(changes)
+    socketio.emit("session_tags", {"tags": tags})
+    publish_stream_event("session_tags", {"tags": tags})
+    return tags
 
-    if mode == "rolling-buffer":
 
-        def on_trigger_diagnostic(data: dict):
-            """Forward trigger diagnostics to connected UI clients."""
-            socketio.emit("trigger_diagnostic", data)
+@app.route("/api/session", methods=["DELETE"])
+def api_clear_session():
+    """Clear all recorded shots."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    monitor.clear_session()
+    socketio.emit("session_cleared")
+    return jsonify({"cleared": True})
+
+
+@app.route("/api/shots", methods=["GET"])
+def api_get_shots():
+    """Get recorded shots, optionally only the last N (?limit=N)."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    shots = [shot_to_dict(s) for s in monitor.get_shots()]
+    limit = request.args.get("limit", type=int)
+    if limit is not None and limit >= 0:
+        shots = shots[-limit:] if limit else []
+    return jsonify({"shots": shots})
+
+
+@app.route("/api/export", methods=["GET"])
+def api_export_shots():
+    """
+    Download this session's shots as CSV (?template=trackman, garmin or a custom name),
+    as a self-contained HTML page (?format=html) or as a FIT golf activity (?format=fit).
+    """
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    export_format = request.args.get("format", "csv")
+    if export_format not in ("csv", "html", "fit"):
+        return _api_error("format must be csv, html or fit", 400)
+    try:
+        template = load_template(request.args.get("template", "openflight"), allow_paths=False)
+    except FileNotFoundError as e:
+        return _api_error(str(e), 404)
+    except ValueError as e:
+        return _api_error(str(e), 400)
 
-        monitor.start(  # pylint: disable=unexpected-keyword-arg
-            shot_callback=on_shot_detected,
-            live_callback=on_live_reading,
-            diagnostic_callback=on_trigger_diagnostic,
-        )
+    session_logger = get_session_logger()
+    tags = session_logger.tags if session_logger else {}
+    session = session_logger.session_id if session_logger else None
+    shots = [
+        {**shot_to_dict(shot), "shot_number": i, "session": session, "tags": tags}
+        for i, shot in enumerate(monitor.get_shots(), start=1)
+    ]
+    mimetype = f"text/{export_format}"
+    if export_format == "fit":
+        body = to_fit(shots)
+        filename = f"openflight_{session or 'session'}.fit"
+        mimetype = "application/vnd.ant.fit"
+    elif export_format == "html":
+        body = to_html(shots, title=f"OpenFlight session {session or ''}".strip())
+        filename = f"openflight_{session or 'session'}.html"
     else:
-        monitor.start(shot_callback=on_shot_detected, live_callback=on_live_reading)
+        body = to_csv(shots, template)
+        filename = f"openflight_{session or 'session'}_{template.name}.csv"
+    return Response(
+        body,
+        mimetype=mimetype,
+        headers={"Content-Disposition": f'attachment; filename="{filename}"'},
+    )
 
 
-def stop_monitor():
-    """Stop the launch monitor."""
-    global monitor  # pylint: disable=global-statement
+@app.route("/api/stats", methods=["GET"])
+def api_get_stats():
+    """
+    Get session statistics.
+
+    Includes "dispersion": the landing scatter, ellipse and target basket of
+    each club, for shots with a horizontal launch angle (see dispersion).
+    ?basket_pct=5 narrows the basket to 5% of the target carry.
+    "strokes_gained" totals the shots scored against a practice target
+    (see scoring), or is None if none were. "personal_records" is the
+    player's best ball speed and carry per club (see records).
+    """
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    try:
+        basket_pct = float(request.args.get("basket_pct", DEFAULT_BASKET_PCT))
+    except ValueError:
+        return _api_error("basket_pct must be a number", 400)
+    if not 0 < basket_pct <= 100:
+        return _api_error("basket_pct must be between 0 and 100", 400)
+    stats = monitor.get_session_stats()
+    shots = [shot_to_dict(shot) for shot in monitor.get_shots()]
+    stats["dispersion"] = [d.to_dict() for d in club_dispersion(shots, basket_pct=basket_pct)]
+    stats["strokes_gained"] = session_score(shots)
+    records = personal_records.table(current_player()) if personal_records else []
+    stats["personal_records"] = [r.to_dict() for r in records]
+    return jsonify(stats)
+
+
+@app.route("/api/club", methods=["PUT"])
+def api_set_club():
+    """Set the current club. Body: {"club": "7-iron"}."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    data = request.get_json(silent=True) or {}
+    try:
+        club = ClubType(data.get("club"))
+    except ValueError:
+        valid = ", ".join(c.value for c in ClubType)
+        return _api_error(f"Unknown club '{data.get('club')}' (valid: {valid})", 400)
+    monitor.set_club(club)
+    socketio.emit("club_changed", {"club": club.value})
+    return jsonify({"club": club.value})
+
+
+@app.route("/api/mulligan", methods=["POST"])
+def api_mulligan():
+    """Take back the last shot: drop it from the session and mark it in the log."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    shot = monitor.discard_last_shot()
+    if not shot:
+        return _api_error("No shots to take back", 409)
+    round_tracker.untag(shot)
 
-    # End session logging
+    session_logger = get_session_logger()
+    if session_logger and shot.log_number is not None:
+        session_logger.log_mulligan(shot.log_number)
+
+    shot_data = shot_to_dict(shot)
+    stats = monitor.get_session_stats()
+    print(f"[MULLIGAN] Removed {shot.ball_speed_mph:.1f} mph {shot.club.value} shot")
+    socketio.emit("shot_removed", {"shot": shot_data, "stats": stats})
+    publish_stream_event("mulligan", {"shot": shot_data, "stats": stats})
+    webhook_sink = get_webhook_sink()
+    if webhook_sink:
+        webhook_sink.send({"event": "mulligan", "shot": shot_data})
+    return jsonify({"shot": shot_data, "stats": stats})
+
+
+def _find_shot(shot_number: Optional[int]) -> Optional[Shot]:
+    """The session's shot with this session log number."""
+    if shot_number is None or not monitor:
+        return None
+    for shot in monitor.get_shots():
+        if shot.log_number == shot_number:
+            return shot
+    return None
+
+
+@app.route("/api/shot", methods=["DELETE"])
+def api_delete_shot():
+    """
+    Delete a shot after the fact (?shot_number=N&reason=shank).
+
+    Unlike a mulligan it can be any shot in the session. The shot stays
+    in the session log, with a shot_deleted entry that analysis skips it by.
+    """
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    shot_number = request.args.get("shot_number", type=int)
+    shot = _find_shot(shot_number)
+    if not shot:
+        return _api_error(f"No shot {shot_number} in this session", 404)
+    monitor.remove_shot(shot)
+    round_tracker.untag(shot)
+
+    reason = request.args.get("reason") or None
     session_logger = get_session_logger()
     if session_logger:
-        session_logger.end_session()
+        session_logger.log_shot_deleted(shot_number, reason=reason)
 
-    if monitor:
-        monitor.stop()
-        monitor.disconnect()
-        monitor = None
+    shot_data = shot_to_dict(shot)
+    stats = monitor.get_session_stats()
+    print(f"[EDIT] Deleted shot {shot_number}" + (f" ({reason})" if reason else ""))
+    socketio.emit("shot_removed", {"shot": shot_data, "stats": stats})
+    publish_stream_event("shot_deleted", {"shot": shot_data, "stats": stats, "reason": reason})
+    webhook_sink = get_webhook_sink()
+    if webhook_sink:
+        webhook_sink.send({"event": "shot_deleted", "shot": shot_data, "reason": reason})
+    return jsonify({"shot": shot_data, "stats": stats})
 
 
-class MockLaunchMonitor:
-    """Mock launch monitor for UI development without radar hardware."""
+@app.route("/api/shot", methods=["PATCH"])
+def api_edit_shot():
+    """
+    Correct a shot's club (?shot_number=N). Body: {"club": "7-iron", "reason": "..."}.
 
-    # TrackMan averages for amateur golfers: (avg_ball_speed, std_dev, smash_factor)
-    _CLUB_BALL_SPEEDS = {
-        ClubType.DRIVER: (143, 12, 1.45),
-        ClubType.WOOD_3: (135, 10, 1.42),
-        ClubType.WOOD_5: (128, 10, 1.40),
-        ClubType.WOOD_7: (122, 9, 1.40),
-        ClubType.HYBRID_3: (123, 9, 1.39),
+    The carry is re-estimated for the new club. The old club is kept in a
+    shot_edited entry in the session log.
+    """
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    shot_number = request.args.get("shot_number", type=int)
+    shot = _find_shot(shot_number)
+    if not shot:
+        return _api_error(f"No shot {shot_number} in this session", 404)
+    data = request.get_json(silent=True) or {}
+    try:
+        club = ClubType(data.get("club"))
+    except ValueError:
+        valid = ", ".join(c.value for c in ClubType)
+        return _api_error(f"Unknown club '{data.get('club')}' (valid: {valid})", 400)
+
+    previous = {"club": shot.club.value, "estimated_carry_yards": shot.estimated_carry_yards}
+    shot.club = club
+    changes = {"club": club.value, "estimated_carry_yards": shot.estimated_carry_yards}
+    reason = data.get("reason") or None
+    session_logger = get_session_logger()
+    if session_logger:
+        session_logger.log_shot_edited(shot_number, changes, previous, reason=reason)
+
+    shot_data = shot_to_dict(shot)
+    stats = monitor.get_session_stats()
+    print(f"[EDIT] Shot {shot_number}: {previous['club']} -> {club.value}")
+    socketio.emit("shot_updated", {"shot": shot_data, "stats": stats})
+    publish_stream_event("shot_edited", {"shot": shot_data, "stats": stats, "reason": reason})
+    webhook_sink = get_webhook_sink()
+    if webhook_sink:
+        webhook_sink.send({"event": "shot_edited", "shot": shot_data, "previous": previous})
+    return jsonify({"shot": shot_data, "stats": stats})
+
+
+def take_voice_memo():
+    """Record a voice memo for the last shot (memo key or button)."""
+    session_logger = get_session_logger()
+    shots = monitor.get_shots() if monitor else []
+    shot_number = shots[-1].log_number if shots else None
+    if not voice_memo or not session_logger or not session_logger.session_path:
+        return
+    if shot_number is None:
+        print("[MEMO] No logged shot to attach a memo to")
+        return
+    path = memo_path(session_logger.session_path, shot_number)
+
+    def on_done(ok: bool):
+        if not ok:
+            print(f"[MEMO] Recording failed for shot {shot_number}")
+            return
+        session_logger.log_voice_memo(shot_number, path.name, voice_memo.duration_sec)
+        print(f"[MEMO] Saved {path.name}")
+        socketio.emit("voice_memo", {"shot_number": shot_number, "file": path.name})
+
+    if voice_memo.start(path, on_done):
+        print(f"[MEMO] Recording {voice_memo.duration_sec:g}s for shot {shot_number}...")
+    else:
+        print("[MEMO] Already recording")
+
+
+@app.route("/api/arm", methods=["POST"])
+def api_arm():
+    """Re-arm by hand, e.g. after teeing up a ball the camera didn't see settle."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    if scheduled_disarm:
+        scheduled_disarm.rearm()
+    publish_arm_event(arming.arm() if arming else "ready")
+    return jsonify({"ready": True})
+
+
+@app.route("/api/simulate", methods=["POST"])
+def api_simulate_shot():
+    """Simulate a shot (mock mode only). Optional body: {"ball_speed": 150}."""
+    if not monitor or not isinstance(monitor, MockLaunchMonitor):
+        return _api_error("Shot simulation requires mock mode", 409)
+    data = request.get_json(silent=True) or {}
+    ball_speed = data.get("ball_speed")
+    if ball_speed is not None:
+        try:
+            ball_speed = float(ball_speed)
+        except (TypeError, ValueError):
+            return _api_error("ball_speed must be a number", 400)
+        if not math.isfinite(ball_speed):
+            return _api_error("ball_speed must be a finite number", 400)
+    shot = monitor.simulate_shot(ball_speed=ball_speed)
+    return jsonify({"shot": shot_to_dict(shot)})
+
+
+def shot_from_payload(data: dict) -> Shot:
+    """
+    Build a synthetic shot from an /api/inject request body.
+
+    Args:
+        data: Dict with ball_speed_mph (required) and optional club_speed_mph,
+              launch_angle_vertical, launch_angle_horizontal, spin_rpm, club
+
+    Returns:
+        Shot with mode "injected"
+
+    Raises:
+        ValueError: If a field is missing, non-numeric or out of range
+    """
+
+    def number(key: str, required: bool = False) -> Optional[float]:
+        value = data.get(key)
+        if value is None:
+            if required:
+                raise ValueError(f"{key} is required")
+            return None
+        try:
+            parsed = float(value)
+        except (TypeError, ValueError):
+            raise ValueError(f"{key} must be a number") from None
+        if not math.isfinite(parsed):
+            raise ValueError(f"{key} must be a finite number")
+        return parsed
+
+    ball_speed = number("ball_speed_mph", required=True)
+    if ball_speed <= 0:
+        raise ValueError("ball_speed_mph must be positive")
+    club_speed = number("club_speed_mph")
+    if club_speed is not None and club_speed <= 0:
+        raise ValueError("club_speed_mph must be positive")
+
+    club = ClubType(data.get("club") or ClubType.DRIVER.value)
+    launch_v = number("launch_angle_vertical")
+    launch_h = number("launch_angle_horizontal")
+    if launch_v is not None and launch_h is None:
+        launch_h = 0.0
+    spin = number("spin_rpm")
+
+    return Shot(
+        ball_speed_mph=ball_speed,
+        club_speed_mph=club_speed,
+        timestamp=datetime.now(),
+        club=club,
+        launch_angle_vertical=launch_v,
+        launch_angle_horizontal=launch_h,
+        launch_angle_confidence=1.0 if launch_v is not None else None,
+        spin_rpm=spin,
+        spin_confidence=1.0 if spin is not None else None,
+        mode="injected",
+    )
+
+
+@app.route("/api/inject", methods=["POST"])
+def api_inject_shot():
+    """
+    Inject a synthetic shot through the normal delivery path.
+
+    The shot is logged, emitted to the UI, streamed and sent to webhooks
+    exactly like a detected shot, but is tagged mode "injected" and not
+    added to the monitor's session stats. Lets users check their sim and
+    overlay wiring without hitting balls.
+
+    Because this spoofs shots, it is refused from other machines unless an
+    API token is configured.
+    """
+    if not access_policy.token and not AccessPolicy.is_loopback(request.remote_addr):
+        return _api_error("Shot injection from the network requires --api-token", 403)
+
+    data = request.get_json(silent=True)
+    if not isinstance(data, dict):
+        return _api_error("Body must be a JSON object", 400)
+
+    try:
+        shot = shot_from_payload(data)
+    except ValueError as e:
+        return _api_error(str(e), 400)
+
+    print(f"[INJECT] Synthetic shot: {shot.ball_speed_mph:.1f} mph from {request.remote_addr}")
+    on_shot_detected(shot)
+    return jsonify({"shot": shot_to_dict(shot)})
+
+
+@app.route("/api/launch_angle", methods=["POST"])
+def api_companion_launch_angle():
+    """
+    Launch angle from a companion camera.
+
+    Body: {"vertical": 12.5, "horizontal": -1.2, "confidence": 0.9,
+    "time": 5123.456, "clock_offset_sec": 0.0421}. time is the companion's
+    time.monotonic() at impact and clock_offset_sec its offset to this
+    monitor as measured against --time-sync (see timesync); without them
+    the angle is taken as of now. The shot nearest it in time gets it.
+    """
+    data = request.get_json(silent=True)
+    if not isinstance(data, dict):
+        return _api_error("Body must be a JSON object", 400)
+
+    def number(key: str, default: Optional[float] = None) -> Optional[float]:
+        value = data.get(key, default)
+        if value is None:
+            return None
+        if isinstance(value, bool) or not isinstance(value, (int, float)):
+            raise ValueError(f"{key} must be a number")
+        if not math.isfinite(value):
+            raise ValueError(f"{key} must be a finite number")
+        return float(value)
+
+    try:
+        vertical = number("vertical")
+        if vertical is None:
+            raise ValueError("vertical is required")
+        horizontal = number("horizontal", 0.0)
+        confidence = number("confidence", 1.0)
+        if not 0.0 <= confidence <= 1.0:
+            raise ValueError("confidence must be 0-1")
+        companion_time = number("time")
+        offset = number("clock_offset_sec")
+        if (companion_time is None) != (offset is None):
+            raise ValueError("time and clock_offset_sec must be given together")
+    except ValueError as e:
+        return _api_error(str(e), 400)
+
+    monitor_time = time.monotonic() if companion_time is None else companion_time + offset
+    companion_angles.add(
+        monitor_time,
+        {"vertical": vertical, "horizontal": horizontal, "confidence": confidence},
+    )
+    return jsonify({"monitor_time": monitor_time})
+
+
+@app.route("/api/round", methods=["GET"])
+def api_get_round():
+    """Get the hole the sim last reported and the next shot's index on it."""
+    return jsonify(round_tracker.to_dict())
+
+
+@app.route("/api/round", methods=["PUT"])
+def api_set_round():
+    """
+    Report the hole being played. Body: {"hole": 4} or {"hole": 4, "shot": 2}.
+
+    Shots detected afterwards are tagged with the hole and their stroke
+    index on it. A new hole restarts the index at 1 unless "shot" is given.
+    """
+    data = request.get_json(silent=True) or {}
+    try:
+        round_tracker.set_hole(data.get("hole"), data.get("shot"))
+    except ValueError as e:
+        return _api_error(str(e), 400)
+    state = round_tracker.to_dict()
+    socketio.emit("round_changed", state)
+    return jsonify(state)
+
+
+@app.route("/api/round", methods=["DELETE"])
+def api_clear_round():
+    """Stop tagging shots with a hole (round finished)."""
+    round_tracker.clear()
+    state = round_tracker.to_dict()
+    socketio.emit("round_changed", state)
+    return jsonify(state)
+
+
+@app.route("/api/target", methods=["GET"])
+def api_get_target():
+    """Get the practice target distance shots are scored against."""
+    return jsonify(practice_target.to_dict())
+
+
+@app.route("/api/target", methods=["PUT"])
+def api_set_target():
+    """
+    Select a practice target. Body: {"yards": 150}.
+
+    Shots detected afterwards are scored in strokes gained against it.
+    """
+    data = request.get_json(silent=True) or {}
+    try:
+        practice_target.set_target(data.get("yards"))
+    except ValueError as e:
+        return _api_error(str(e), 400)
+    state = practice_target.to_dict()
+    socketio.emit("target_changed", state)
+    return jsonify(state)
+
+
+@app.route("/api/target", methods=["DELETE"])
+def api_clear_target():
+    """Stop scoring shots."""
+    practice_target.clear()
+    state = practice_target.to_dict()
+    socketio.emit("target_changed", state)
+    return jsonify(state)
+
+
+@app.route("/api/speed-training", methods=["GET"])
+def api_get_speed_training():
+    """
+    Get the speed training compliance summary.
+
+    Reps made per set with their club speeds, and how closely the session
+    followed the --speed-training protocol.
+    """
+    if not speed_training:
+        return _api_error("Speed training not enabled (start with --speed-training)", 404)
+    return jsonify(speed_training.summary())
+
+
+@app.route("/api/speed-training", methods=["DELETE"])
+def api_reset_speed_training():
+    """Start the speed training protocol over."""
+    if not speed_training:
+        return _api_error("Speed training not enabled (start with --speed-training)", 404)
+    speed_training.reset()
+    summary = speed_training.summary()
+    socketio.emit("speed_training_summary", summary)
+    return jsonify(summary)
+
+
+@app.route("/api/config", methods=["GET"])
+def api_get_config():
+    """Get the active shot detection thresholds."""
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    if not hasattr(monitor, "set_config"):
+        return _api_error("Thresholds are not configurable in this mode", 409)
+    return jsonify({"config": monitor.config.to_dict()})
+
+
+@app.route("/api/state", methods=["GET"])
+def api_get_state():
+    """
+    Dump the monitor's internal state for debugging.
+
+    Buffered readings, time since the last reading and shot, whether shots
+    count, the active config and its hash, and the sims' connections, so
+    a monitor that stopped detecting can be looked at without a restart.
+    """
+    return jsonify(state_snapshot())
+
+
+@app.route("/api/config", methods=["PUT"])
+def api_set_config():
+    """
+    Update shot detection thresholds without restarting.
+
+    Body is a partial MonitorConfig, e.g. {"min_shot_magnitude": 80}. The new
+    config is validated as a whole and swapped in atomically; nothing is
+    applied if any field is invalid.
+    """
+    if not monitor:
+        return _api_error("Monitor not running", 503)
+    if not hasattr(monitor, "set_config"):
+        return _api_error("Thresholds are not configurable in this mode", 409)
+
+    data = request.get_json(silent=True)
+    if not isinstance(data, dict):
+        return _api_error("Body must be a JSON object", 400)
+
+    try:
+        config = monitor.config.with_updates(data)
+    except (TypeError, ValueError) as e:
+        return _api_error(str(e), 400)
+
+    monitor.set_config(config)
+    print(f"[CONFIG] Thresholds updated via API: {data}")
+
+    session_logger = get_session_logger()
+    if session_logger:
+        session_logger.log_monitor_config(config.to_dict(), source="api")
+
+    socketio.emit("monitor_config", config.to_dict())
+    return jsonify({"config": config.to_dict()})
+
+
+@app.route("/api/profiles", methods=["GET"])
+def api_list_profiles():
+    """List saved calibration profiles and the one in use."""
+    return jsonify({"profiles": list_profiles(profile_dir), "active": active_profile})
+
+
+@app.route("/api/profiles", methods=["POST"])
+def api_save_profile():
+    """
+    Save the current calibration as a named profile. Body: {"name": "garage-net"}.
+
+    Captures the mounting geometry, launch angle offset and the monitor's
+    live thresholds, so settings tuned via /api/config can be kept.
+    """
+    global active_profile  # pylint: disable=global-statement
+
+    data = request.get_json(silent=True) or {}
+    try:
+        profile = CalibrationProfile(
+            name=data.get("name", ""),
+            mounting=mounting_geometry,
+            launch_angle_offset_deg=launch_angle_offset_deg,
+            monitor=monitor.config if monitor and hasattr(monitor, "config") else MonitorConfig(),
+            carry_models=get_carry_model_specs(),
+            schedule=scheduled_disarm.schedule if scheduled_disarm else ArmSchedule(),
+            shot_timing=loaded_profile.shot_timing if loaded_profile else ShotTiming(),
+        )
+        path = save_profile(profile, profile_dir)
+    except ValueError as e:
+        return _api_error(str(e), 400)
+
+    active_profile = profile.name
+    print(f"[PROFILE] Saved '{profile.name}' to {path}")
+    return jsonify({"profile": profile.to_dict()})
+
+
+def apply_profile_reload(old: CalibrationProfile, new: CalibrationProfile):
+    """
+    Apply an edited calibration profile without restarting.
+
+    The profile is applied between shots so a shot is never evaluated with
+    a mix of old and new settings: at once if the monitor is idle, else
+    kept pending until the shot in progress has been processed.
+
+    Args:
+        old: Profile as previously loaded
+        new: Profile as now on disk
+    """
+    if monitor and hasattr(monitor, "call_when_idle"):
+        if not monitor.is_idle:
+            print(f"[PROFILE] '{new.name}' changed during a shot; applying it after the shot")
+        monitor.call_when_idle(lambda: _apply_profile(old, new))
+    else:
+        _apply_profile(old, new)
+
+
+def _apply_profile(old: CalibrationProfile, new: CalibrationProfile):
+    """
+    Swap in the sections of an edited profile that changed.
+
+    Only changed sections are applied, so --mount-* flags stay in effect
+    unless the file's mounting section was edited.
+    """
+    global mounting_geometry, launch_angle_offset_deg  # pylint: disable=global-statement
+    global loaded_profile  # pylint: disable=global-statement
+
+    changes = {
+        key: {"old": before, "new": after}
+        for key, (before, after) in diff_profiles(old, new).items()
+    }
+    if any(key.startswith(("monitor.", "shot_timing.")) for key in changes):
+        if monitor and hasattr(monitor, "set_config"):
+            layers = build_monitor_layers(
+                new,
+                os.environ,
+                sensitivity,
+                ball_type,
+                config_assignments,
+                mode="mock" if mock_mode else monitor_mode,
+            )
+            monitor.set_config(layers.config)
+        if monitor and hasattr(monitor, "set_club_timing"):
+            monitor.set_club_timing(new.shot_timing.clubs)
+    loaded_profile = new
+    if any(key.startswith("mounting.") for key in changes):
+        mounting_geometry = new.mounting
+    if any(key.startswith("carry_models.") for key in changes):
+        set_carry_models(new.carry_models)
+    if new.schedule != old.schedule:
+        apply_schedule(build_schedule(new.schedule))
+    launch_angle_offset_deg = new.launch_angle_offset_deg
+
+    for key, change in changes.items():
+        print(f"[PROFILE] {key}: {change['old']} -> {change['new']}")
+
+    session_logger = get_session_logger()
+    if session_logger:
+        session_logger.log_config_reload(new.name, changes)
+
+    event = {"profile": new.name, "changes": changes}
+    socketio.emit("config_reloaded", event)
+    publish_stream_event("config_reloaded", event)
+
+
+@app.route("/api/stream", methods=["GET"])
+def api_stream():
+    """
+    Stream shot events as Server-Sent Events.
+
+    ?readings=1 adds live readings, at most ?reading_hz=N per second
+    (default 20, 0 = every reading). Shot events are never limited.
+    """
+    try:
+        reading_hz = parse_reading_hz(request.args.get("reading_hz"))
+    except ValueError as e:
+        return _api_error(str(e), 400)
+    subscriber = StreamSubscriber(
+        include_readings=request.args.get("readings", "0") in ("1", "true"),
+        reading_hz=reading_hz,
+    )
+    with _stream_lock:
+        _stream_subscribers.append(subscriber)
+
+    def generate():
+        try:
+            yield ": connected\n\n"
+            while True:
+                try:
+                    item = subscriber.queue.get(timeout=15)
+                except queue.Empty:
+                    # Comment line keeps idle connections from timing out
+                    yield ": keepalive\n\n"
+                    continue
+                yield f"event: {item['event']}\ndata: {json.dumps(item['data'])}\n\n"
+        finally:
+            with _stream_lock:
+                _stream_subscribers.remove(subscriber)
+
+    return Response(generate(), mimetype="text/event-stream")
+
+
+@app.route("/connector/status")
+def connector_status():
+    """Connector feed: whether the monitor is connected and ready, and the last shot's number."""
+    if not connector_feed:
+        return _api_error("Connector feed not enabled (start with --connector)", 404)
+    return jsonify(connector_feed.status(connected=monitor is not None))
+
+
+@app.route("/connector/shot")
+def connector_last_shot():
+    """Connector feed: the last shot (204 until there is one)."""
+    if not connector_feed:
+        return _api_error("Connector feed not enabled (start with --connector)", 404)
+    shot = connector_feed.latest()
+    if shot is None:
+        return "", 204
+    return jsonify(shot)
+
+
+@app.route("/connector/shots")
+def connector_shots():
+    """Connector feed: shots after ?since=N, for an app catching up."""
+    if not connector_feed:
+        return _api_error("Connector feed not enabled (start with --connector)", 404)
+    try:
+        since = int(request.args.get("since", 0))
+    except ValueError:
+        return _api_error("since must be a shot number", 400)
+    return jsonify(
+        {"Shots": connector_feed.since(since), "LastShotNumber": connector_feed.last_shot_number}
+    )
+
+
+def on_shot_delivered(shot: Shot, shot_number: Optional[int], receipts: List[DeliveryReceipt]):
+    """
+    Record the webhook receipts for a shot (called on the delivery thread).
+
+    The shot counts as delivered only if every endpoint accepted it, and
+    as merged if --webhook-throttle merge sent a later shot in its place.
+    """
+    if receipts and all(r.error == MERGED_ERROR for r in receipts):
+        shot.delivery_status = "merged"
+        print(f"[WEBHOOK] Shot {shot_number or '?'} merged into a later shot (throttled)")
+    elif all(r.delivered for r in receipts):
+        shot.delivery_status = "delivered"
+    else:
+        shot.delivery_status = "failed"
+    if shot.delivery_status == "failed":
+        failed = ", ".join(f"{r.url} ({r.error})" for r in receipts if not r.delivered)
+        print(f"[WEBHOOK] Shot {shot_number or '?'} not delivered: {failed}")
+
+    receipt_data = [r.to_dict() for r in receipts]
+    session_log = get_session_logger()
+    if session_log:
+        session_log.log_shot_delivery(shot_number, shot.delivery_status, receipt_data)
+
+    payload = {
+        "timestamp": shot.timestamp.isoformat(),
+        "shot_number": shot_number,
+        "status": shot.delivery_status,
+        "receipts": receipt_data,
+    }
+    socketio.emit("shot_delivery", payload)
+    publish_stream_event("shot_delivery", payload)
+
+
+def measure_tempo(shot: Shot):
+    """Add the swing's tempo to a shot, timed from the backswing to its ball reading."""
+    timed = [r for r in shot.readings if r.timestamp is not None]
+    if not tempo_meter or not timed:
+        return
+    impact = shot.impact_time or max(timed, key=lambda r: r.speed).timestamp
+    tempo = tempo_meter.measure(impact)
+    if tempo is None:
+        print("[TEMPO] No backswing seen")
+        return
+    shot.tempo_backswing_sec = tempo.backswing_sec
+    shot.tempo_downswing_sec = tempo.downswing_sec
+    shot.tempo_target_ratio = tempo_target
+    result = compare_tempo(tempo, tempo_target)
+    print(
+        f"[TEMPO] {result['ratio']:.1f}:1, {result['error_pct']:+.0f}% vs {tempo_target:g}:1 "
+        f"(backswing {tempo.backswing_sec:.2f}s, downswing {tempo.downswing_sec:.2f}s)"
+    )
+
+
+def record_practice_shot(shot: Shot) -> Optional[int]:
+    """
+    Practice channel: log the shot at full fidelity, raw readings included.
+
+    Returns:
+        The shot's number in the session log, or None if it wasn't logged
+    """
+    session_log = get_session_logger()
+    if not session_log:
+        return None
+    try:
+        session_log.log_shot(
+            ball_speed_mph=shot.ball_speed_mph,
+            club_speed_mph=shot.club_speed_mph,
+            smash_factor=shot.smash_factor,
+            estimated_carry_yards=shot.estimated_carry_yards,
+            club=shot.club.value,
+            peak_magnitude=shot.peak_magnitude,
+            readings_count=len(shot.readings),
+            readings=shot.readings_data,
+            spin_rpm=shot.spin_rpm,
+            spin_confidence=shot.spin_confidence,
+            spin_quality=shot.spin_quality,
+            carry_spin_adjusted=shot.carry_spin_adjusted,
+            mode=shot.mode,
+            launch_angle_vertical=shot.launch_angle_vertical,
+            launch_angle_horizontal=shot.launch_angle_horizontal,
+            launch_angle_confidence=shot.launch_angle_confidence,
+            ball_speed_raw_mph=shot.ball_speed_raw_mph,
+            club_speed_raw_mph=shot.club_speed_raw_mph,
+            hole_number=shot.hole_number,
+            hole_shot=shot.hole_shot,
+            target_yards=shot.target_yards,
+            strokes_gained=shot.strokes_gained,
+            ball_type=shot.ball_type,
+            pre_trigger_readings=shot.pre_trigger_data,
+            lost_frames=shot.lost_frames,
+            max_reading_gap_ms=shot.max_reading_gap_ms,
+            ball_speed_reading_mph=shot.ball_speed_reading_mph,
+        )
+    except Exception as e:
+        print(f"[PRACTICE LOG] Failed to log shot: {e}")
+        return None
+    shot.log_number = session_log.stats.get("shots_detected")
+    return shot.log_number
+
+
+def sim_failover_state() -> Optional[dict]:
+    """The --sim-url endpoints and which one is active (None without them)."""
+    webhook_sink = get_webhook_sink()
+    failover = webhook_sink.failover if webhook_sink else None
+    return failover.to_dict() if failover else None
+
+
+def on_sim_failover(previous: Optional[str], active: Optional[str]):
+    """Tell the UI and stream subscribers shots go to another sim endpoint."""
+    if active is None:
+        print("[SIM] All sim endpoints down: each shot still tries them in order")
+    else:
+        print(f"[SIM] Shots now go to {active} (was {previous or 'none up'})")
+    payload = {"previous": previous, "active": active, **(sim_failover_state() or {})}
+    socketio.emit("sim_endpoint", payload)
+    publish_stream_event("sim_endpoint", payload)
+
+
+def send_to_sim(shot: Shot, shot_number: Optional[int]):
+    """
+    Sim channel: queue the shot for the configured webhooks.
+
+    With --webhook-payload sim, only the fields a sim needs are sent.
+    Delivery happens on the sink's thread and its result is recorded by
+    on_shot_delivered; a failure here only marks the shot undelivered.
+    """
+    webhook_sink = get_webhook_sink()
+    if not webhook_sink:
+        return
+    try:
+        session_log = get_session_logger()
+        webhook_sink.send(
+            {
+                "event": "shot",
+                "session_id": session_log.session_id if session_log else None,
+                "shot": shot_payload(shot) if webhook_payload == "sim" else shot_to_dict(shot),
+            },
+            on_result=lambda receipts: on_shot_delivered(shot, shot_number, receipts),
+        )
+    except Exception as e:
+        shot.delivery_status = "failed"
+        print(f"[SIM] Failed to send shot {shot_number or '?'}: {e}")
+
+
+def shot_impact_time(shot: Shot) -> float:
+    """time.monotonic() of the shot's fastest reading (now if its readings have no times)."""
+    timed = [r for r in shot.readings if r.timestamp is not None]
+    if not timed:
+        return time.monotonic()
+    return max(timed, key=lambda r: r.speed).timestamp
+
+
+def apply_companion_angle(shot: Shot) -> Optional[dict]:
+    """
+    Give a shot the companion camera's launch angle from around its impact.
+
+    This runs on the monitor's reading thread, so it only waits for an
+    angle while the impact is still within the match window (an angle
+    for it could still be on its way); past that, it takes what has
+    already arrived.
+
+    Returns:
+        Camera data for the debug log, or None if no angle arrived in time
+    """
+    impact = shot_impact_time(shot)
+    remaining = impact + DEFAULT_MATCH_WINDOW_SEC - time.monotonic()
+    if remaining > 0:
+        match = companion_angles.wait_for(impact, timeout=min(remaining, DEFAULT_MATCH_WAIT_SEC))
+    else:
+        match = companion_angles.nearest(impact)
+    if match is None:
+        print("[COMPANION] No launch angle for the shot")
+        return None
+    event_time, angle = match
+    shot.launch_angle_vertical = angle["vertical"] + launch_angle_offset_deg
+    shot.launch_angle_horizontal = angle["horizontal"]
+    shot.launch_angle_confidence = angle["confidence"]
+    if sensor_capture:
+        sensor_capture.record_camera(
+            angle["vertical"], angle["horizontal"], angle["confidence"], timestamp=event_time
+        )
+    logger.info(
+        "Companion launch angle: %.1f° V, %.1f° H (%+.0f ms from impact)",
+        angle["vertical"],
+        angle["horizontal"],
+        (event_time - impact) * 1000,
+    )
+    return {
+        "launch_angle_vertical": angle["vertical"],
+        "launch_angle_horizontal": angle["horizontal"],
+        "launch_angle_confidence": angle["confidence"],
+        "source": "companion",
+        "offset_ms": round((event_time - impact) * 1000, 1),
+    }
+
+
+def current_player() -> Optional[str]:
+    """The session's "player" tag, if one is set."""
+    session_log = get_session_logger()
+    return session_log.tags.get("player") if session_log else None
+
+
+def announce_records(shot_data: dict, player: Optional[str]):
+    """Celebrate each personal record a shot beat: console, UI, stream, webhooks and speech."""
+    for new in personal_records.check(shot_data, player):
+        print("[PR] 🎉🎉🎉 NEW PERSONAL RECORD! 🎉🎉🎉")
+        print(f"[PR] {new.describe()}")
+        payload = {"record": new.to_dict(), "timestamp": datetime.now().isoformat()}
+        socketio.emit("personal_record", payload)
+        publish_stream_event("personal_record", payload)
+        webhook_sink = get_webhook_sink()
+        if webhook_sink:
+            webhook_sink.send({"event": "personal_record", **payload})
+        if speak_records:
+            speak(new.announcement())
+
+
+@traced("on_shot", "deliver")
+def on_shot_detected(shot: Shot):
+    """Callback when a shot is detected - emit to all clients."""
+    global ball_detected, ball_detection_confidence  # pylint: disable=global-statement
+
+    logger.debug("Shot callback triggered: %.1f mph", shot.ball_speed_mph)
+
+    if arming:
+        arming.on_shot()
+
+    round_tracker.tag(shot)
+
+    # Correct radar speeds for off-line mounting (synthetic shots are already "true")
+    if shot.mode not in ("mock", "injected"):
+        apply_cosine_correction(shot, mounting_geometry)
+        measure_tempo(shot)
+        shot.ball_type = ball_type
+
+    # Try to get launch angle from camera BEFORE emitting shot
+    # Skip camera for mock shots — they already have simulated launch angle
+    camera_data = None
+    try:
+        if camera_tracker and camera_enabled and shot.mode not in ("mock", "injected"):
+            launch_angle = camera_tracker.calculate_launch_angle()
+            if launch_angle:
+                # Update shot object with launch angle data
+                shot.launch_angle_vertical = launch_angle.vertical + launch_angle_offset_deg
+                shot.launch_angle_horizontal = launch_angle.horizontal
+                shot.launch_angle_confidence = launch_angle.confidence
+
+                if sensor_capture:
+                    sensor_capture.record_camera(
+                        launch_angle.vertical, launch_angle.horizontal, launch_angle.confidence
+                    )
+                camera_data = {
+                    "launch_angle_vertical": launch_angle.vertical,
+                    "launch_angle_horizontal": launch_angle.horizontal,
+                    "launch_angle_confidence": launch_angle.confidence,
+                    "positions_tracked": len(launch_angle.positions),
+                    "launch_detected": camera_tracker.launch_detected,
+                }
+                logger.info(
+                    "Launch angle: %.1f° V, %.1f° H (conf: %.0f%%)",
+                    launch_angle.vertical,
+                    launch_angle.horizontal,
+                    launch_angle.confidence * 100,
+                )
+
+            # Reset camera tracker for next shot
+            camera_tracker.reset()
+            ball_detected = False
+            ball_detection_confidence = 0.0
+    except Exception as e:
+        logger.warning("Camera processing error: %s", e)
+        camera_data = None
+
+    # Or from a companion camera (POST /api/launch_angle), matched by time
+    if (
+        shot.launch_angle_vertical is None
+        and shot.mode not in ("mock", "injected")
+        and companion_angles.active
+    ):
+        camera_data = apply_companion_angle(shot) or camera_data
+
+    # If no camera launch angle, estimate from club type and ball speed
+    if shot.launch_angle_vertical is None and shot.mode not in ("mock", "injected"):
+        estimated = estimate_launch_angle(
+            shot.club,
+            shot.ball_speed_mph,
+            club_speed_mph=shot.club_speed_mph,
+            spin_rpm=shot.spin_rpm,
+        )
+        # Only the vertical angle can be estimated; leaving the horizontal one
+        # unset keeps these shots out of the landing dispersion
+        shot.launch_angle_vertical = estimated[0]
+        shot.launch_angle_confidence = estimated[1]
+        logger.info(
+            "Estimated launch angle: %.1f° (conf: %.0f%%)", estimated[0], estimated[1] * 100
+        )
+
+    # Scored once the landing point is known (horizontal angle from the camera)
+    practice_target.score(shot)
+
+    # Outside the schedule (quiet hours, idle) shots are kept from the sim
+    disarmed_reason = scheduled_disarm.reason if scheduled_disarm else None
+    webhook_sink = get_webhook_sink()
+    if webhook_sink and not disarmed_reason:
+        shot.delivery_status = "pending"
+
+    # Practice log and sim are separate channels: either can fail without
+    # costing the other (or the UI) the shot
+    shot_number = record_practice_shot(shot)
+    if disarmed_reason:
+        print(f"[SCHEDULE] Shot not sent to sim: disarmed ({disarmed_reason.replace('_', ' ')})")
+    else:
+        with trace_span("send_to_sim", "deliver"):
+            send_to_sim(shot, shot_number)
+    if scheduled_disarm:
+        scheduled_disarm.on_shot()
+    if session_splitter and shot_number is not None:
+        session_splitter.on_shot()
+
+    # Emit shot with launch angle data included
+    try:
+        shot_data = shot_to_dict(shot)
+        stats = monitor.get_session_stats() if monitor else {}
+        with trace_span("emit", "deliver"):
+            socketio.emit("shot", {"shot": shot_data, "stats": stats})
+            publish_stream_event("shot", {"shot": shot_data, "stats": stats})
+        if sensor_capture:
+            sensor_capture.record_shot(shot_data)
+        bluetooth_serial = get_bluetooth_serial()
+        if bluetooth_serial:
+            bluetooth_serial.send_shot(shot, shot_data)
+
+        # Log shot info
+        angle_str = ""
+        if shot.launch_angle_vertical is not None:
+            angle_str = f", Launch: {shot.launch_angle_vertical:.1f}°"
+        logger.info(
+            "Shot: ball=%.1f mph, carry=%.0f yds%s",
+            shot.ball_speed_mph,
+            shot.estimated_carry_yards,
+            angle_str,
+        )
+    except Exception as e:
+        logger.error("Failed to emit shot: %s", e)
+        return
+
+    if shot_history or personal_records:
+        player = current_player()
+        context = shot_history.describe(shot_data, player) if shot_history else None
+        if context:
+            print(f"[HISTORY] {context}")
+        if personal_records:
+            announce_records(shot_data, player)
+
+    # Debug logging (optional)
+    if debug_mode:
+        try:
+            debug_log_entry = {
+                "type": "shot",
+                "timestamp": datetime.now().isoformat(),
+                "radar": {
+                    "ball_speed_mph": shot_data["ball_speed_mph"],
+                    "club_speed_mph": shot_data["club_speed_mph"],
+                    "smash_factor": shot_data["smash_factor"],
+                    "peak_magnitude": shot_data["peak_magnitude"],
+                },
+                "camera": camera_data,
+                "club": shot_data["club"],
+            }
+
+            if debug_log_file:
+                debug_log_file.write(json.dumps(debug_log_entry) + "\n")
+                debug_log_file.flush()
+
+            socketio.emit("debug_shot", debug_log_entry)
+        except Exception as e:
+            print(f"[WARN] Debug logging error: {e}")
+
+
+def start_monitor(
+    port: Optional[str] = None,
+    mock: bool = False,
+    mode: str = "streaming",
+    trigger_type: str = "polling",
+    debug: bool = False,
+    trigger_kwargs: Optional[dict] = None,
+    sample_rate_ksps: int = 30,
+    monitor_config: Optional[MonitorConfig] = None,
+    position: MountPosition = MountPosition.BEHIND,
+    skip_configure: bool = False,
+    shot_classifier: Optional[ShotClassifier] = None,
+    radar_model: str = "ops243",
+    data_port: Optional[str] = None,
+    radar_config_file: Optional[str] = None,
+    serial_dump: Optional[SerialDump] = None,
+):
+    """
+    Start the launch monitor.
+
+    Args:
+        port: Serial port for radar
+        mock: Run in mock mode without radar
+        mode: "streaming" (default), "rolling-buffer" or "swing-speed"
+        trigger_type: Trigger strategy for rolling-buffer mode
+        debug: Enable verbose FFT/CFAR debug output
+        monitor_config: Shot detection thresholds (streaming and mock modes)
+        position: Radar mounting position (streaming mode)
+        skip_configure: Trust the radar's current settings (streaming mode)
+        shot_classifier: Learned shot filter replacing the rule-based one (streaming mode)
+        radar_model: "ops243" (OmniPreSense family), "iwr6843" or "esp-bridge"
+            (streaming mode; for esp-bridge, port is the UDP address to listen on)
+        data_port: IWR6843 data port (port is its CLI port)
+        radar_config_file: IWR6843 .cfg to send instead of the built-in one
+        serial_dump: Capture file the radar's serial traffic is teed into
+    """
+    global monitor, mock_mode, monitor_mode  # pylint: disable=global-statement
+    global resume_session_path  # pylint: disable=global-statement
+
+    # Stop any existing monitor first
+    if monitor is not None:
+        print("[MONITOR] Stopping existing monitor before starting new one")
+        stop_monitor()
+
+    mock_mode = mock
+    monitor_mode = mode
+    if mock:
+        # Mock mode for testing without radar
+        monitor = MockLaunchMonitor()
+        if monitor_config:
+            monitor.set_config(monitor_config)
+    elif mode == "rolling-buffer":
+        # Rolling buffer mode for spin detection
+        from .rolling_buffer import RollingBufferMonitor
+
+        monitor = RollingBufferMonitor(
+            port=port,
+            trigger_type=trigger_type,
+            sample_rate_ksps=sample_rate_ksps,
+            **(trigger_kwargs or {}),
+        )
+        print(
+            f"[MODE] Rolling buffer mode enabled (trigger: {trigger_type}, sample_rate: {sample_rate_ksps}ksps)"
+        )
+        if position != MountPosition.BEHIND:
+            print(
+                "[MODE] WARNING: rolling buffer mode assumes the radar is behind the ball, "
+                f"ignoring --mount-position {position.value}"
+            )
+    else:
+        # Default streaming mode
+        radar = None
+        if radar_model == "iwr6843":
+            radar = IWR6843Radar(cli_port=port, data_port=data_port, config_file=radar_config_file)
+        elif radar_model == "esp-bridge":
+            radar = EspBridgeRadar(port)
+        monitor_class = SwingSpeedMonitor if mode == "swing-speed" else LaunchMonitor
+        monitor = monitor_class(
+            radar=radar,
+            port=port,
+            debug=debug,
+            config=monitor_config,
+            mount_position=position,
+            skip_configure=skip_configure,
+            classifier=shot_classifier,
+        )
+        if mode == "swing-speed":
+            print("[MODE] Swing speed mode enabled - club speed only, no ball")
+        else:
+            print(f"[MODE] Streaming mode enabled (debug={debug})")
+
+    if loaded_profile and hasattr(monitor, "set_club_timing"):
+        monitor.set_club_timing(loaded_profile.shot_timing.clubs)
+    if serial_dump and not mock:
+        monitor.radar.dump = serial_dump
+    monitor.connect()
+
+    # Start session logging (continuing the session from before a restart, once)
+    session_logger = get_session_logger()
+    resume = resume_session_path
+    resume_session_path = None
+    if session_logger:
+        radar_info = monitor.get_radar_info() if not mock else {}
+        session_logger.start_session(
+            radar_port=port if not mock else "mock",
+            firmware_version=radar_info.get("Version"),
+            camera_enabled=camera is not None,
+            camera_model="hough" if (camera_tracker and camera_tracker.use_hough) else None,
+            config=radar_config.copy(),
+            mode="mock" if mock else mode,
+            trigger_type=trigger_type if mode == "rolling-buffer" else None,
+            resume=resume,
+        )
+        if resume and session_logger.session_path == resume:
+            restore_session_shots(resume)
+        if hasattr(monitor, "config"):
+            session_logger.log_monitor_config(monitor.config.to_dict(), source="startup")
+
+    if mode == "rolling-buffer":
+
+        def on_trigger_diagnostic(data: dict):
+            """Forward trigger diagnostics to connected UI clients."""
+            socketio.emit("trigger_diagnostic", data)
+            if sensor_capture and str(data.get("trigger_type", "")).startswith("sound"):
+                sensor_capture.record_audio_gate(
+                    trigger=data["trigger_type"],
+                    accepted=data.get("accepted"),
+                    reason=data.get("reason", ""),
+                    latency_ms=data.get("latency_ms"),
+                )
+
+        monitor.start(  # pylint: disable=unexpected-keyword-arg
+            shot_callback=on_shot_detected,
+            live_callback=on_live_reading,
+            diagnostic_callback=on_trigger_diagnostic,
+        )
+    elif mode == "swing-speed" and not mock:
+        monitor.start(  # pylint: disable=unexpected-keyword-arg
+            shot_callback=on_shot_detected,
+            live_callback=on_live_reading,
+            swing_callback=on_swing_detected,
+        )
+    else:
+        monitor.start(shot_callback=on_shot_detected, live_callback=on_live_reading)
+
+
+def restore_session_shots(path: Path):
+    """Put a resumed session's shots (and last club) back on the monitor for the UI."""
+    shots = []
+    for entry in read_shots(str(path)):
+        shot = shot_from_entry(entry)
+        shot.log_number = entry.get("shot_number")
+        try:
+            shot.timestamp = datetime.fromisoformat(entry["ts"])
+        except (KeyError, TypeError, ValueError):
+            pass
+        shots.append(shot)
+    monitor.restore_shots(shots)
+    if shots:
+        monitor.set_club(shots[-1].club)
+    print(f"[SESSION] Restored {len(shots)} shot(s) from before the restart")
+
+
+def stop_monitor():
+    """Stop the launch monitor."""
+    global monitor  # pylint: disable=global-statement
+
+    # End session logging
+    session_logger = get_session_logger()
+    if session_logger:
+        session_path = session_logger.session_path
+        session_logger.end_session()
+        cloud_sync = get_cloud_sync()
+        if cloud_sync and session_path:
+            cloud_sync.enqueue(*session_files(str(session_path)))
+
+    if monitor:
+        monitor.stop()
+        monitor.disconnect()
+        monitor = None
+
+
+def split_session(reason: str, tag_changes: Dict[str, Optional[str]]):
+    """Start a new logged session (--session-idle / --session-per-player)."""
+    session_logger = get_session_logger()
+    if not session_logger or not session_logger.session_path:
+        return
+    ended = session_logger.session_path
+    session_logger.rollover(reason, tag_changes)
+    cloud_sync = get_cloud_sync()
+    if cloud_sync:
+        cloud_sync.enqueue(*session_files(str(ended)))
+    if monitor:
+        monitor.clear_session()
+        if hasattr(monitor, "config"):
+            session_logger.log_monitor_config(monitor.config.to_dict(), source=reason)
+    print(f"[SESSION] New session ({reason}): {session_logger.session_path}")
+    socketio.emit("session_cleared")
+    tags = session_logger.tags
+    socketio.emit("session_tags", {"tags": tags})
+    publish_stream_event("session_tags", {"tags": tags})
+
+
+def ready_state() -> str:
+    """Whether shots count: "stopped", "disarmed", "waiting" (for --auto-arm) or "ready"."""
+    if monitor is None:
+        return "stopped"
+    if scheduled_disarm and scheduled_disarm.disarmed:
+        return "disarmed"
+    if arming and not arming.armed:
+        return "waiting"
+    return "ready"
+
+
+def crash_snapshot() -> dict:
+    """What the server and monitor were doing, for a crash report."""
+    session_log = get_session_logger()
+    config = getattr(monitor, "config", None)
+    debug_state = getattr(monitor, "debug_state", None)
+    return {
+        "mode": monitor_mode,
+        "mock": mock_mode,
+        "monitor": debug_state() if debug_state else {"running": monitor is not None},
+        "config": config.to_dict() if config else None,
+        "radar_config": dict(radar_config),
+        "mount_position": mount_position.value,
+        "arming": arming.state.value if arming else None,
+        "schedule": scheduled_disarm.to_dict() if scheduled_disarm else None,
+        "session_split": session_splitter.to_dict() if session_splitter else None,
+        "sim_endpoints": sim_failover_state(),
+        "session_path": str(session_log.session_path) if session_log else None,
+        "camera_enabled": camera_enabled,
+    }
+
+
+def state_snapshot() -> dict:
+    """
+    The crash report's snapshot plus readiness, config hash and sim connections.
+
+    For "it stopped detecting" reports: buffered readings and reading ages
+    are in "monitor", and the hash tells whether two servers (or the same
+    one before and after) run the same thresholds.
+    """
+    config = getattr(monitor, "config", None)
+    webhook_sink = get_webhook_sink()
+    relay = get_relay()
+    bluetooth_serial = get_bluetooth_serial()
+    return {
+        "timestamp": datetime.now().isoformat(),
+        **crash_snapshot(),
+        "ready": ready_state(),
+        "config_hash": config.fingerprint() if config else None,
+        "connections": {
+            "webhooks": webhook_sink.to_dict() if webhook_sink else None,
+            "relay": relay.to_dict() if relay else None,
+            "bluetooth_clients": bluetooth_serial.clients if bluetooth_serial else None,
+        },
+    }
+
+
+def control_state(params: dict) -> dict:
+    """Control socket: the monitor's internal state (as GET /api/state)."""
+    return state_snapshot()
+
+
+def control_status(params: dict) -> dict:
+    """Control socket: what the server is doing."""
+    session_log = get_session_logger()
+    session_path = session_log.session_path if session_log else None
+    stats = monitor.get_session_stats() if monitor else {}
+    return {
+        "mode": monitor_mode,
+        "mock": mock_mode,
+        "running": monitor is not None,
+        "shots": stats.get("shot_count", 0),
+        "club": monitor.get_club().value if monitor else None,
+        "session_id": session_log.session_id if session_log else None,
+        "session_path": str(session_path) if session_path else None,
+        "radar_data_age_sec": getattr(monitor, "radar_data_age_sec", None),
+        "camera_enabled": camera_enabled,
+    }
+
+
+def control_set_mode(params: dict) -> dict:
+    """Control socket: restart the monitor in another radar mode."""
+    mode = params.get("mode")
+    if mode not in RADAR_MODES:
+        raise ValueError(f"mode must be one of {', '.join(RADAR_MODES)}")
+    if mock_mode:
+        raise ControlError("Mock mode has no radar modes")
+    radar_model = monitor_args.get("radar_model")
+    if mode == "rolling-buffer" and radar_model in STREAMING_ONLY_RADARS:
+        raise ControlError(f"--radar {radar_model} has no rolling-buffer mode")
+    with _control_lock:
+        if mode != monitor_mode or monitor is None:
+            print(f"[CONTROL] Switching to {mode} mode")
+            monitor_args["mode"] = mode
+            if loaded_profile and loaded_profile.shot_timing.modes:
+                # The profile's shot timing for the new mode replaces the old one's
+                monitor_args["monitor_config"] = build_monitor_layers(
+                    loaded_profile,
+                    os.environ,
+                    sensitivity,
+                    ball_type,
+                    config_assignments,
+                    mode=mode,
+                ).config
+            start_monitor(**monitor_args)
+    return control_status(params)
+
+
+def control_shutdown(params: dict) -> dict:
+    """Control socket: stop the server as Ctrl+C would, after answering."""
+    print("[CONTROL] Shutdown requested")
+    threading.Timer(0.2, os.kill, (os.getpid(), signal.SIGINT)).start()
+    return {"shutting_down": True}
+
+
+def menu_bar_status() -> dict:
+    """Status feed: whether the monitor is ready and the radar is talking."""
+    running = monitor is not None
+    age = getattr(monitor, "radar_data_age_sec", None) if running else None
+    stats = monitor.get_session_stats() if running else {}
+    return {
+        "state": ready_state(),
+        "mode": monitor_mode,
+        "shots": stats.get("shot_count", 0),
+        "club": monitor.get_club().value if running else None,
+        "connection": {
+            "radar": radar_health(running, mock_mode, age),
+            "radar_data_age_sec": round(age, 1) if age is not None else None,
+        },
+    }
+
+
+def service_pause():
+    """Windows service pause: end the session and release the radar until continued."""
+    with _control_lock:
+        if monitor is not None:
+            print("[SERVICE] Pausing - releasing the radar")
+            stop_monitor()
+
+
+def service_continue():
+    """Windows service continue: reconnect the radar and start a new session."""
+    with _control_lock:
+        if monitor is None and monitor_args:
+            print("[SERVICE] Continuing - reconnecting the radar")
+            start_monitor(**monitor_args)
+
+
+def service_stop():
+    """Windows service stop: stop the server as Ctrl+C would."""
+    print("[SERVICE] Stop requested")
+    _thread.interrupt_main()
+
+
+SERVICE_HANDLERS = {
+    "stop": service_stop,
+    "pause": service_pause,
+    "continue": service_continue,
+}
+
+CONTROL_METHODS = {
+    "status": control_status,
+    "state": control_state,
+    "set_mode": control_set_mode,
+    "shutdown": control_shutdown,
+}
+_control_lock = threading.Lock()
+
+
+def _grpc_monitor():
+    """The running monitor, for a gRPC call that needs one."""
+    if not monitor:
+        raise ServiceError("Monitor not running", "UNAVAILABLE")
+    return monitor
+
+
+def grpc_get_session(params: dict) -> dict:
+    """gRPC GetSession: stats and every shot so far (as GET /api/session)."""
+    running = _grpc_monitor()
+    return {
+        "stats": running.get_session_stats(),
+        "shots": [shot_to_dict(s) for s in running.get_shots()],
+        "mock_mode": mock_mode,
+    }
+
+
+def grpc_get_stats(params: dict) -> dict:
+    """gRPC GetStats: session statistics."""
+    return _grpc_monitor().get_session_stats()
+
+
+def grpc_set_club(params: dict) -> dict:
+    """gRPC SetClub: select the club for the next shots (as PUT /api/club)."""
+    running = _grpc_monitor()
+    try:
+        club = ClubType(params.get("club"))
+    except ValueError:
+        valid = ", ".join(c.value for c in ClubType)
+        raise ValueError(f"Unknown club '{params.get('club', '')}' (valid: {valid})") from None
+    running.set_club(club)
+    socketio.emit("club_changed", {"club": club.value})
+    publish_stream_event("club_changed", {"club": club.value, "source": "grpc"})
+    return {"club": club.value}
+
+
+def grpc_set_player(params: dict) -> dict:
+    """gRPC SetPlayer: switch player, as the "player" session tag ("" clears it)."""
+    if not get_session_logger():
+        raise ServiceError("Session logging not initialized", "UNAVAILABLE")
+    tags = update_session_tags(validate_tags({"player": params.get("player")}))
+    return {"player": tags.get("player"), "tags": tags}
+
+
+def grpc_simulate_shot(params: dict) -> dict:
+    """gRPC SimulateShot: record a simulated shot (mock mode only)."""
+    if not isinstance(monitor, MockLaunchMonitor):
+        raise ServiceError("Shot simulation requires mock mode")
+    ball_speed = params.get("ball_speed")
+    if ball_speed is not None and not (math.isfinite(ball_speed) and ball_speed > 0):
+        raise ValueError("ball_speed must be a positive number")
+    return {"shot": shot_to_dict(monitor.simulate_shot(ball_speed=ball_speed))}
+
+
+def grpc_stream_events(params: dict, is_active: Callable[[], bool]):
+    """gRPC StreamEvents: the /api/stream events until the client goes away."""
+    subscriber = StreamSubscriber(
+        include_readings=bool(params.get("readings")),
+        reading_hz=parse_reading_hz(params.get("reading_hz")),
+    )
+    with _stream_lock:
+        _stream_subscribers.append(subscriber)
+    try:
+        while is_active():
+            try:
+                item = subscriber.queue.get(timeout=1.0)
+            except queue.Empty:
+                continue
+            yield item["event"], item["data"]
+    finally:
+        with _stream_lock:
+            _stream_subscribers.remove(subscriber)
+
+
+def grpc_authorize(metadata: Dict[str, str], address: Optional[str]):
+    """Apply --allow and --api-token to a gRPC call, as to /api requests."""
+    if not access_policy.is_address_allowed(address):
+        raise ServiceError("Forbidden", "PERMISSION_DENIED")
+    if not access_policy.is_token_valid(
+        metadata.get("authorization"), metadata.get(TOKEN_HEADER.lower())
+    ):
+        raise ServiceError("Missing or invalid API token", "UNAUTHENTICATED")
+
+
+GRPC_METHODS = {
+    "GetSession": grpc_get_session,
+    "GetStats": grpc_get_stats,
+    "SetClub": grpc_set_club,
+    "SetPlayer": grpc_set_player,
+    "SimulateShot": grpc_simulate_shot,
+}
+
+
+class MockLaunchMonitor:
+    """Mock launch monitor for UI development without radar hardware."""
+
+    # TrackMan averages for amateur golfers: (avg_ball_speed, std_dev, smash_factor)
+    _CLUB_BALL_SPEEDS = {
+        ClubType.DRIVER: (143, 12, 1.45),
+        ClubType.WOOD_3: (135, 10, 1.42),
+        ClubType.WOOD_5: (128, 10, 1.40),
+        ClubType.WOOD_7: (122, 9, 1.40),
+        ClubType.HYBRID_3: (123, 9, 1.39),
         ClubType.HYBRID_5: (118, 9, 1.37),
         ClubType.HYBRID_7: (112, 8, 1.35),
         ClubType.HYBRID_9: (106, 8, 1.33),
@@ -1063,6 +2934,17 @@ class MockLaunchMonitor:
         self._running = False
         self._shot_callback = None
         self._current_club = ClubType.DRIVER
+        self._config = MonitorConfig()
+
+    @property
+    def config(self) -> MonitorConfig:
+        """Active shot detection thresholds (recorded but unused by the mock)."""
+        return self._config
+
+    def set_config(self, config: MonitorConfig):
+        """Replace the active thresholds."""
+        config.validate()
+        self._config = config
 
     def connect(self):
         """Connect to mock radar (no-op)."""
@@ -1136,6 +3018,8 @@ class MockLaunchMonitor:
                 "avg_club_speed": None,
                 "avg_smash_factor": None,
                 "avg_carry_est": 0,
+                **energy_stats([]),
+                **impact_stats([]),
             }
 
         ball_speeds = [s.ball_speed_mph for s in self._shots]
@@ -1151,25 +3035,86 @@ class MockLaunchMonitor:
             "avg_club_speed": statistics.mean(club_speeds) if club_speeds else None,
             "avg_smash_factor": statistics.mean(smash_factors) if smash_factors else None,
             "avg_carry_est": statistics.mean([s.estimated_carry_yards for s in self._shots]),
+            **energy_stats(self._shots),
+            **impact_stats(self._shots),
         }
 
+    def discard_last_shot(self) -> Optional[Shot]:
+        """Remove the most recent shot (a mulligan); None if there are no shots."""
+        return self._shots.pop() if self._shots else None
+
+    def remove_shot(self, shot: Shot) -> bool:
+        """Remove a shot deleted after the fact; False if it isn't in the session."""
+        for i, recorded in enumerate(self._shots):
+            if recorded is shot:
+                del self._shots[i]
+                return True
+        return False
+
     def clear_session(self):
         """Clear all recorded shots."""
         self._shots = []
 
+    def restore_shots(self, shots: List[Shot]):
+        """Put back shots from before a restart (e.g. from a resumed session log)."""
+        self._shots = list(shots) + self._shots
+
     def set_club(self, club: ClubType):
         """Set the current club for future shots."""
         self._current_club = club
 
+    def get_club(self) -> ClubType:
+        """Get the club used for future shots."""
+        return self._current_club
 
-def main():
-    """Run the server."""
+
+def _apply_retention(log_dir: Path, keep_days: float):
+    """Compact old session logs in the background so startup isn't held up."""
+    try:
+        result = vacuum(log_dir, keep_days)
+    except OSError as e:
+        print(f"[RETENTION] Failed: {e}")
+        return
+    if result.compacted or result.deleted:
+        print(f"[RETENTION] {format_result(result)}")
+
+
+def _load_history(log_dir: Path):
+    """Read the earlier sessions' shots in the background so startup isn't held up."""
+    paths = find_sessions([str(log_dir / "session_*.jsonl")])
+    try:
+        if shot_history:
+            count = shot_history.load(paths)
+            print(f"[HISTORY] {count} shots from {len(paths)} sessions")
+        if personal_records:
+            count = personal_records.load(paths)
+            print(f"[PR] {count} personal records")
+    except OSError as e:
+        print(f"[HISTORY] Failed to read session logs: {e}")
+
+
+def build_parser():
+    """The openflight-server command line parser."""
     import argparse  # pylint: disable=import-outside-toplevel
 
-    parser = argparse.ArgumentParser(description="OpenFlight UI Server")
-    parser.add_argument("--port", "-p", help="Serial port for radar")
+    parser = argparse.ArgumentParser(
+        description="OpenFlight UI Server",
+        epilog="Options can also be set with OPENFLIGHT_<OPTION> environment variables, "
+        "e.g. OPENFLIGHT_WEB_PORT=8081 (see openflight-config show --effective)",
+    )
+    parser.add_argument(
+        "--port",
+        "-p",
+        help="Serial port for radar; a glob such as '/dev/serial/by-id/usb-OmniPreSense*' "
+        "uses the first match, and a URL such as tcp://host:2000 or rfc2217://host:2217 "
+        "a network serial bridge",
+    )
     parser.add_argument("--mock", "-m", action="store_true", help="Run in mock mode without radar")
-    parser.add_argument("--host", default="0.0.0.0", help="Host to bind to (default: 0.0.0.0)")
+    parser.add_argument(
+        "--host",
+        default="0.0.0.0",
+        help="Address the web server, --grpc-port and --time-sync listen on (default: 0.0.0.0)",
+    )
     parser.add_argument(
         "--web-port", type=int, default=8080, help="Web server port (default: 8080)"
     )
@@ -1237,12 +3182,456 @@ def main():
         "--log-dir", help="Directory for session logs (default: ~/openflight_sessions)"
     )
     parser.add_argument("--no-logging", action="store_true", help="Disable session logging")
+    parser.add_argument(
+        "--no-percentiles",
+        action="store_true",
+        help="Don't print where each shot falls in the player's history with the club",
+    )
+    parser.add_argument(
+        "--no-records",
+        action="store_true",
+        help="Don't track and announce personal records (best ball speed and carry per club)",
+    )
+    parser.add_argument(
+        "--speak-records",
+        action="store_true",
+        help="Announce new personal records out loud (needs espeak-ng)",
+    )
+    parser.add_argument(
+        "--ball-type",
+        help="Session tag: ball used (e.g., 'pro-v1'); premium, range or foam also adjust "
+        "detection thresholds and carry for that ball",
+    )
+    parser.add_argument("--weather", help="Session tag: conditions (e.g., 'windy, 55F')")
+    parser.add_argument("--notes", help="Session tag: free-form notes")
+    parser.add_argument(
+        "--tag",
+        action="append",
+        default=[],
+        metavar="KEY=VALUE",
+        help="Extra session tag (repeatable)",
+    )
+    parser.add_argument(
+        "--sync-to",
+        metavar="URL",
+        help="Upload each session log when it ends: s3://bucket/prefix or a WebDAV URL",
+    )
+    parser.add_argument(
+        "--sync-endpoint", help="S3-compatible endpoint for --sync-to (MinIO, B2...)"
+    )
+    parser.add_argument(
+        "--retain-readings-days",
+        type=float,
+        metavar="DAYS",
+        help="At startup, compact session logs older than this to shot-level entries "
+        "and delete old raw radar logs",
+    )
+    parser.add_argument(
+        "--webhook-url",
+        action="append",
+        default=[],
+        help="POST each shot as JSON to this URL (repeat for multiple URLs)",
+    )
+    parser.add_argument(
+        "--webhook-secret",
+        default=os.environ.get("OPENFLIGHT_WEBHOOK_SECRET"),
+        help="Shared secret for HMAC-SHA256 webhook signatures (or OPENFLIGHT_WEBHOOK_SECRET env var)",
+    )
+    parser.add_argument(
+        "--webhook-payload",
+        choices=("full", "sim"),
+        default="full",
+        help="Shot fields sent to webhooks: everything the UI gets (default) or just what "
+        "a sim needs (speeds, club, launch, spin, carry)",
+    )
+    parser.add_argument(
+        "--webhook-min-interval",
+        type=float,
+        default=0.0,
+        metavar="SEC",
+        help="Least time between shots sent to webhooks, for sims that can't keep up with "
+        "rapid-fire shots (default: 0, no limit)",
+    )
+    parser.add_argument(
+        "--webhook-throttle",
+        choices=THROTTLE_POLICIES,
+        default="queue",
+        help="Shots detected within --webhook-min-interval: queue them all (default) or "
+        "merge them, sending only the latest",
+    )
+    parser.add_argument(
+        "--sim-url",
+        action="append",
+        default=[],
+        metavar="URL",
+        help="Sim endpoint with hot standbys: repeat for standbys in priority order; each shot "
+        "goes to the first one up, failing back to the primary when it returns",
+    )
+    parser.add_argument(
+        "--sim-health-interval",
+        type=float,
+        default=FailoverGroup.DEFAULT_HEALTH_INTERVAL_SEC,
+        metavar="SEC",
+        help="How often the --sim-url endpoints are probed (default: 10; 0 = never, "
+        "so no fail-back)",
+    )
+    parser.add_argument(
+        "--relay-url",
+        metavar="URL",
+        help="Push live events to this WebSocket relay (wss://...) so a coach can watch remotely",
+    )
+    parser.add_argument(
+        "--relay-token",
+        default=os.environ.get("OPENFLIGHT_RELAY_TOKEN"),
+        help="Token the relay authenticates this monitor with (or OPENFLIGHT_RELAY_TOKEN env var)",
+    )
+    parser.add_argument(
+        "--bluetooth-serial",
+        action="store_true",
+        help="Write shot lines to devices connected over Bluetooth serial (RFCOMM, Linux only)",
+    )
+    parser.add_argument(
+        "--bluetooth-channel",
+        type=int,
+        default=DEFAULT_CHANNEL,
+        help=f"RFCOMM channel for --bluetooth-serial (default: {DEFAULT_CHANNEL})",
+    )
+    parser.add_argument(
+        "--bluetooth-format",
+        default="json",
+        help="Shot line format for --bluetooth-serial: json, csv or a shot card "
+        "(compact, full or a template; default: json)",
+    )
+    parser.add_argument(
+        "--voice-memo",
+        type=float,
+        nargs="?",
+        const=DEFAULT_MEMO_SEC,
+        metavar="SEC",
+        help="Record a voice memo for the last shot when Enter is pressed in this terminal "
+        "(or the --memo-pin button), saved next to the session log "
+        f"(default: {DEFAULT_MEMO_SEC:g}s)",
+    )
+    parser.add_argument(
+        "--memo-device",
+        metavar="DEVICE",
+        help="ALSA capture device for --voice-memo, e.g. plughw:1,0 (default: ALSA's default)",
+    )
+    parser.add_argument(
+        "--memo-pin",
+        type=int,
+        metavar="PIN",
+        help="GPIO pin (BCM) of a button, wired to ground, that records a --voice-memo",
+    )
+    parser.add_argument(
+        "--time-sync",
+        type=int,
+        nargs="?",
+        const=DEFAULT_TIME_SYNC_PORT,
+        metavar="PORT",
+        help="Answer clock sync requests from companion devices (e.g. a camera Pi posting "
+        f"to /api/launch_angle) on this UDP port (default port: {DEFAULT_TIME_SYNC_PORT})",
+    )
+    parser.add_argument(
+        "--control-socket",
+        nargs="?",
+        const=default_socket_path(),
+        metavar="PATH",
+        help="Accept JSON-RPC control requests (status, set_mode, shutdown) on this Unix "
+        f"socket (default path: {default_socket_path()})",
+    )
+    parser.add_argument(
+        "--status-socket",
+        nargs="?",
+        const=default_status_socket_path(),
+        metavar="PATH",
+        help="Push status lines (state, last shot, radar health) for menu bar widgets on this "
+        f"Unix socket (default path: {default_status_socket_path()})",
+    )
+    parser.add_argument(
+        "--grpc-port",
+        type=int,
+        nargs="?",
+        const=DEFAULT_GRPC_PORT,
+        metavar="PORT",
+        help="Serve the gRPC API (proto/openflight.proto) on this port, on --host "
+        f"(default port: {DEFAULT_GRPC_PORT}; needs the grpc extra)",
+    )
+    parser.add_argument(
+        "--status-port",
+        type=int,
+        metavar="PORT",
+        help="Push the same status lines on this TCP port (on --status-host)",
+    )
+    parser.add_argument(
+        "--status-host",
+        default="127.0.0.1",
+        help="Address --status-port listens on (default: 127.0.0.1; 0.0.0.0 to publish "
+        "it from a container)",
+    )
+    parser.add_argument(
+        "--crash-dir",
+        metavar="DIR",
+        help="Write crash reports (recent readings, config, detection state) here "
+        "(default: crashes/ in the session log directory)",
+    )
+    parser.add_argument(
+        "--no-crash-reports", action="store_true", help="Don't write crash reports"
+    )
+    parser.add_argument(
+        "--crash-report-url",
+        metavar="URL",
+        help="Also POST each crash report as JSON to this URL",
+    )
+    parser.add_argument(
+        "--supervise",
+        action="store_true",
+        help="Run the server as a child process and restart it if it crashes, hangs or its "
+        "radar stops sending data, continuing the same session (for kiosks)",
+    )
+    parser.add_argument(
+        "--service",
+        action="store_true",
+        help="Run under openflight-service (Windows): take stop, pause and continue commands "
+        "on stdin instead of console input",
+    )
+    parser.add_argument(
+        "--wedge-timeout",
+        type=float,
+        default=30.0,
+        metavar="SEC",
+        help="With --supervise, restart after this long without I/Q data from the radar "
+        "(0 = never; default: 30)",
+    )
+    parser.add_argument(
+        "--resume-session",
+        type=Path,
+        metavar="FILE",
+        help="Continue this session log instead of starting a new one (used by --supervise)",
+    )
+    parser.add_argument(
+        "--api-token",
+        default=os.environ.get("OPENFLIGHT_API_TOKEN"),
+        help="Require this token for /api requests and UI sockets (or OPENFLIGHT_API_TOKEN env var)",
+    )
+    parser.add_argument(
+        "--allow",
+        action="append",
+        default=[],
+        metavar="ADDR[/PREFIX]",
+        help="Only accept clients from this address or network, e.g. 192.168.1.0/24 "
+        "(repeatable; localhost is always allowed)",
+    )
+    parser.add_argument(
+        "--mount-offset",
+        type=float,
+        metavar="CM",
+        help="Radar sideways distance from the target line in cm, for cosine correction",
+    )
+    parser.add_argument(
+        "--mount-height",
+        type=float,
+        metavar="CM",
+        help="Radar height above the ball in cm, for cosine correction",
+    )
+    parser.add_argument(
+        "--mount-distance",
+        type=float,
+        metavar="CM",
+        help="Radar distance behind the ball along the target line in cm (default: 150)",
+    )
+    parser.add_argument(
+        "--mount-position",
+        choices=[p.value for p in MountPosition],
+        default=MountPosition.BEHIND.value,
+        help="Where the radar sits: behind the ball (default), in front facing the player, "
+        "or beside the mat",
+    )
+    parser.add_argument(
+        "--sensitivity",
+        choices=SENSITIVITY_LEVELS,
+        help="Shot detection preset: low (fewer false shots), high (catches chips and soft "
+        "wedges), indoor-net or outdoor-range; overrides the profile's thresholds",
+    )
+    parser.add_argument(
+        "--profile",
+        help="Load a saved calibration profile (mounting, angle offset, thresholds) by name",
+    )
+    parser.add_argument(
+        "--profile-dir",
+        help="Directory for calibration profiles (default: ~/.openflight/profiles)",
+    )
+    parser.add_argument(
+        "--no-profile-watch",
+        action="store_true",
+        help="Don't reload the --profile file when it is edited",
+    )
+    parser.add_argument(
+        "--set",
+        action="append",
+        default=[],
+        metavar="FIELD=VALUE",
+        help="Set a shot detection threshold (a MonitorConfig field), e.g. "
+        "--set min_shot_magnitude=80; overrides the profile, OPENFLIGHT_MONITOR_* "
+        "variables and --sensitivity/--ball-type (repeatable)",
+    )
+    parser.add_argument(
+        "--auto-arm",
+        action="store_true",
+        help="Send a 'ready' event only when the camera sees a ball settled on the mat",
+    )
+    parser.add_argument(
+        "--quiet-hours",
+        metavar="HH:MM-HH:MM",
+        help="Disarm (no shots to the sim) during these hours, e.g. 22:00-07:00; "
+        "several comma-separated, 'off' to ignore the profile's",
+    )
+    parser.add_argument(
+        "--idle-disarm",
+        type=float,
+        metavar="MIN",
+        help="Disarm after this many minutes without a shot, until re-armed (POST /api/arm)",
+    )
+    parser.add_argument(
+        "--session-idle",
+        type=float,
+        metavar="MIN",
+        help="Start a new session after this many minutes without a shot, so a kiosk "
+        "running for days logs each visit on its own",
+    )
+    parser.add_argument(
+        "--session-per-player",
+        action="store_true",
+        help="Start a new session when the player tag changes to someone else",
+    )
+    parser.add_argument(
+        "--club-gesture",
+        action="store_true",
+        help="Wave the club toward the radar three times to select the next club",
+    )
+    parser.add_argument(
+        "--club-gesture-bag",
+        metavar="CLUBS",
+        help="Clubs the gesture cycles through, e.g. driver,5-wood,7-iron,pw (default: all)",
+    )
+    parser.add_argument(
+        "--tempo",
+        metavar="RATIO",
+        help="Measure swing tempo against a target backswing:downswing ratio, e.g. 3:1",
+    )
+    parser.add_argument(
+        "--tempo-cues",
+        action="store_true",
+        help="Play takeaway, top and impact tones at the --tempo ratio (needs aplay or afplay)",
+    )
+    parser.add_argument(
+        "--tempo-backswing",
+        type=float,
+        default=DEFAULT_BACKSWING_SEC,
+        metavar="SEC",
+        help=f"Backswing time of the tempo cues (default: {DEFAULT_BACKSWING_SEC})",
+    )
+    parser.add_argument(
+        "--speed-training",
+        metavar="PROTOCOL",
+        help="Overspeed training: count club-only swings through sets, e.g. "
+        "light:5,normal:5,heavy:5 or 3x5 (see GET /api/speed-training)",
+    )
+    parser.add_argument(
+        "--speed-training-rest",
+        type=float,
+        default=DEFAULT_REST_SEC,
+        metavar="SEC",
+        help=f"Rest expected between speed training sets (default: {DEFAULT_REST_SEC:g})",
+    )
+    parser.add_argument(
+        "--feedback",
+        action="append",
+        choices=FEEDBACK_OUTPUTS,
+        help="Per-shot feedback at the mat: an RGB LED colored red to green, or a tone "
+        "rising in pitch (repeatable)",
+    )
+    parser.add_argument(
+        "--feedback-metric",
+        choices=sorted(FEEDBACK_METRICS),
+        default="ball_speed",
+        help="What the feedback shows: ball speed, carry, or how close the carry is to the "
+        "--target (default: ball_speed)",
+    )
+    parser.add_argument(
+        "--feedback-range",
+        metavar="LOW-HIGH",
+        help="Values mapped to the ends of the feedback scale (default: 60-170 mph, "
+        "50-300 yd, 0-30 yd off target)",
+    )
+    parser.add_argument(
+        "--led-pins",
+        default="5,6,13",
+        metavar="R,G,B",
+        help="BCM GPIO pins of the feedback LED's red, green and blue legs (default: 5,6,13)",
+    )
+    parser.add_argument(
+        "--target",
+        type=float,
+        metavar="YARDS",
+        help="Score shots in strokes gained against a target distance (change via /api/target)",
+    )
     parser.add_argument(
         "--mode",
         "-M",
-        choices=["streaming", "rolling-buffer"],
+        choices=RADAR_MODES,
         default="streaming",
-        help="Radar mode: streaming (default, real-time) or rolling-buffer (higher resolution, spin detection)",
+        help="Radar mode: streaming (default, real-time), rolling-buffer (higher resolution, "
+        "spin detection) or swing-speed (club speed of swings with no ball)",
+    )
+    parser.add_argument(
+        "--skip-configure",
+        action="store_true",
+        help="Trust the radar's current settings instead of configuring it (streaming mode)",
+    )
+    parser.add_argument(
+        "--radar",
+        choices=["ops243", "iwr6843", "esp-bridge"],
+        default="ops243",
+        help="Radar backend: ops243 (OmniPreSense, default), iwr6843 (TI mmWave, streaming "
+        "mode; --port is its CLI port) or esp-bridge (OPS243 behind an ESP32 over Wi-Fi, "
+        "streaming mode; --port is the UDP [HOST:]PORT to listen on, default 0.0.0.0:4210)",
+    )
+    parser.add_argument("--data-port", help="IWR6843 data port (e.g. /dev/ttyUSB1)")
+    parser.add_argument(
+        "--radar-cfg",
+        metavar="FILE",
+        help="IWR6843 mmWave demo .cfg to use instead of the built-in one",
+    )
+    parser.add_argument(
+        "--dump-serial",
+        metavar="FILE",
+        help="Capture every byte read from (and written to) the radar into FILE, "
+        "for replay with openflight-parse-dump",
+    )
+    parser.add_argument(
+        "--trace",
+        metavar="FILE",
+        help="Write a timing trace of reading, parsing, detecting and delivering each shot "
+        "into FILE, for chrome://tracing or ui.perfetto.dev",
+    )
+    parser.add_argument(
+        "--capture",
+        metavar="FILE",
+        help="Record radar frames, sound trigger gates, camera launch angles and shots "
+        "on one timeline into FILE, for openflight-capture",
+    )
+    parser.add_argument(
+        "--connector",
+        action="store_true",
+        help="Serve the polling shot feed tablet golf apps use for third-party launch "
+        "monitors (/connector/status, /connector/shot)",
+    )
+    parser.add_argument(
+        "--shot-classifier",
+        metavar="MODEL",
+        help="Model file (JSON) that decides shot vs not-shot instead of the "
+        "duration/magnitude/speed rules (streaming mode)",
     )
     parser.add_argument(
         "--trigger",
@@ -1274,8 +3663,46 @@ def main():
         default=200,
         help="Debounce time in ms for sound-gpio trigger (default: 200)",
     )
+    return parser
+
+
+def main():
+    """Run the server."""
+    global access_policy, mounting_geometry  # pylint: disable=global-statement
+    global active_profile, profile_dir, launch_angle_offset_deg  # pylint: disable=global-statement
+    global arming, mount_position, profile_watcher, sensitivity  # pylint: disable=global-statement
+    global ball_type, config_assignments, loaded_profile  # pylint: disable=global-statement
+    global club_gesture, club_gesture_bag  # pylint: disable=global-statement
+    global tempo_meter, tempo_target, tempo_cues, shot_feedback  # pylint: disable=global-statement
+    global speed_training, speed_swings  # pylint: disable=global-statement
+    global sensor_capture, time_sync, voice_memo, memo_button  # pylint: disable=global-statement
+    global connector_feed, status_feed, shot_history  # pylint: disable=global-statement
+    global personal_records, speak_records  # pylint: disable=global-statement
+    global webhook_payload, resume_session_path  # pylint: disable=global-statement
+    global session_splitter  # pylint: disable=global-statement
+
+    parser = build_parser()
+    try:
+        apply_env_defaults(parser, os.environ)
+    except ValueError as e:
+        parser.error(str(e))
     args = parser.parse_args()
 
+    if args.service and args.supervise:
+        parser.error("--service can't be combined with --supervise")
+    if args.supervise:
+        control_socket = args.control_socket or default_socket_path()
+        child_args = [arg for arg in sys.argv[1:] if arg != "--supervise"]
+        if not args.control_socket:
+            child_args += ["--control-socket", control_socket]
+        command = [sys.executable, "-m", "openflight.server", *child_args]
+        sys.exit(supervise(command, control_socket, wedge_timeout_sec=args.wedge_timeout))
+    if args.service:
+        # Output goes to the Event Log through a pipe: send it line by line
+        sys.stdout.reconfigure(line_buffering=True)
+    if args.resume_session:
+        resume_session_path = args.resume_session
+
     # Configure logging - always show INFO and above for openflight modules
     # This ensures trigger events and important messages are visible
     logging.basicConfig(
@@ -1286,22 +3713,213 @@ def main():
     logging.getLogger("openflight.rolling_buffer.trigger").setLevel(logging.INFO)
     logging.getLogger("openflight.rolling_buffer.monitor").setLevel(logging.INFO)
 
+    if not args.no_crash_reports:
+        log_dir = Path(args.log_dir) if args.log_dir else SessionLogger.DEFAULT_LOG_DIR
+        crash_dir = Path(args.crash_dir) if args.crash_dir else log_dir / "crashes"
+        init_crash_reporter(crash_dir, snapshot=crash_snapshot, endpoint=args.crash_report_url)
+
     print("=" * 50)
     print("  OpenFlight UI Server")
     print("=" * 50)
     print()
 
+    try:
+        session_tags = dict(parse_tag(t) for t in args.tag)
+    except ValueError as e:
+        parser.error(f"--tag: {e}")
+    for key in ("ball_type", "weather", "notes"):
+        if getattr(args, key):
+            session_tags[key] = getattr(args, key)
+
     # Initialize session logger (enabled for both real and mock modes)
     if not args.no_logging:
-        from pathlib import Path
-
         log_dir = Path(args.log_dir) if args.log_dir else None
-        init_session_logger(log_dir=log_dir, location=args.session_location, enabled=True)
+        init_session_logger(
+            log_dir=log_dir, location=args.session_location, enabled=True, tags=session_tags
+        )
         print(f"Session logging enabled (location: {args.session_location})")
+        if args.retain_readings_days is not None:
+            threading.Thread(
+                target=_apply_retention,
+                args=(get_session_logger().log_dir, args.retain_readings_days),
+                daemon=True,
+            ).start()
     else:
-        init_session_logger(enabled=False)
+        init_session_logger(enabled=False, tags=session_tags)
         print("Session logging DISABLED")
 
+    if not args.no_percentiles:
+        shot_history = ShotHistory()
+    if not args.no_records:
+        personal_records = PersonalRecords()
+        speak_records = args.speak_records
+    if shot_history or personal_records:
+        history_dir = Path(args.log_dir) if args.log_dir else SessionLogger.DEFAULT_LOG_DIR
+        threading.Thread(target=_load_history, args=(history_dir,), daemon=True).start()
+
+    # Upload session logs after each session
+    if args.sync_to:
+        if args.no_logging:
+            parser.error("--sync-to needs session logging")
+        try:
+            cloud_sync = init_cloud_sync(
+                args.sync_to, get_session_logger().log_dir, endpoint=args.sync_endpoint
+            )
+        except ValueError as e:
+            parser.error(f"--sync-to: {e}")
+        print(f"Syncing session logs to {cloud_sync.uploader.describe()}")
+
+    if args.voice_memo is not None:
+        if args.no_logging:
+            parser.error("--voice-memo needs session logging")
+        try:
+            voice_memo = VoiceMemoRecorder(args.voice_memo, device=args.memo_device)
+        except (ValueError, RuntimeError) as e:
+            parser.error(f"--voice-memo: {e}")
+        triggers = []
+        if args.memo_pin is not None:
+            try:
+                memo_button = watch_button(args.memo_pin, take_voice_memo)
+            except RuntimeError as e:
+                parser.error(f"--memo-pin: {e}")
+            triggers.append(f"button on GPIO{args.memo_pin}")
+        if sys.stdin.isatty() and not args.service:
+            watch_key(take_voice_memo)
+            triggers.append("Enter")
+        print(
+            f"Voice memos: {args.voice_memo:g}s after a shot, recorded with "
+            f"{' or '.join(triggers) or 'nothing (no terminal; add --memo-pin)'}"
+        )
+    elif args.memo_pin is not None:
+        parser.error("--memo-pin needs --voice-memo")
+
+    # Initialize webhook output sink
+    if args.webhook_min_interval < 0:
+        parser.error("--webhook-min-interval can't be negative")
+    if args.sim_health_interval < 0:
+        parser.error("--sim-health-interval can't be negative")
+    failover = None
+    if args.sim_url:
+        failover = FailoverGroup(
+            args.sim_url, health_interval_sec=args.sim_health_interval, on_change=on_sim_failover
+        )
+    if args.webhook_url or failover:
+        init_webhook_sink(
+            args.webhook_url,
+            secret=args.webhook_secret,
+            min_interval_sec=args.webhook_min_interval,
+            throttle_policy=args.webhook_throttle,
+            failover=failover,
+        )
+        webhook_payload = args.webhook_payload
+        signed = " (signed)" if args.webhook_secret else ""
+        if args.webhook_url:
+            print(f"Webhooks enabled{signed}: {', '.join(args.webhook_url)}")
+        if failover:
+            print(f"Sim endpoints{signed}, in failover order: {', '.join(args.sim_url)}")
+        if args.webhook_min_interval:
+            print(
+                f"Webhook shots at least {args.webhook_min_interval:g}s apart "
+                f"({args.webhook_throttle} the ones in between)"
+            )
+
+    if args.relay_url:
+        if args.relay_url.startswith("ws://") and args.relay_token:
+            print("Warning: --relay-url is ws://, so the relay token is sent unencrypted")
+        try:
+            init_relay(args.relay_url, token=args.relay_token)
+        except ValueError as e:
+            parser.error(f"--relay-url: {e}")
+        print(f"Relaying live events to {args.relay_url}")
+
+    if args.bluetooth_serial:
+        try:
+            init_bluetooth_serial(args.bluetooth_channel, args.bluetooth_format)
+        except (ValueError, RuntimeError, OSError) as e:
+            parser.error(f"--bluetooth-serial: {e}")
+        print(
+            f"Bluetooth serial: {args.bluetooth_format} shot lines on RFCOMM channel "
+            f"{args.bluetooth_channel}"
+        )
+
+    if args.time_sync is not None:
+        time_sync = TimeSyncResponder(host=args.host, port=args.time_sync)
+        try:
+            time_sync.start()
+        except OSError as e:
+            parser.error(f"--time-sync: {e}")
+        print(f"Time sync: answering companion devices on UDP port {args.time_sync}")
+
+    # Restrict network access if requested
+    try:
+        access_policy = AccessPolicy(token=args.api_token, allowed=args.allow)
+    except ValueError as e:
+        parser.error(f"--allow: {e}")
+    if access_policy.token:
+        print("API token required for /api requests")
+    if access_policy.networks:
+        print(f"Client allowlist: {', '.join(str(n) for n in access_policy.networks)} (+ localhost)")
+
+    mount_position = MountPosition(args.mount_position)
+    if mount_position != MountPosition.BEHIND:
+        print(f"Radar mount position: {mount_position.value}")
+
+    # Calibration profile (explicit --mount-* flags override the profile)
+    if args.profile_dir:
+        profile_dir = Path(args.profile_dir).expanduser()
+    profile = CalibrationProfile(name="default")
+    if args.profile:
+        try:
+            profile = load_profile(args.profile, profile_dir)
+        except (FileNotFoundError, ValueError) as e:
+            parser.error(str(e))
+        active_profile = profile.name
+        loaded_profile = profile
+        launch_angle_offset_deg = profile.launch_angle_offset_deg
+        set_carry_models(profile.carry_models)
+        print(f"Calibration profile: {profile.name}")
+    # Thresholds: profile < OPENFLIGHT_MONITOR_* < --sensitivity/--ball-type < --set
+    sensitivity = args.sensitivity
+    ball_type = ball_type_for(args.ball_type)
+    try:
+        config_assignments = parse_assignments(args.set)
+        layers = build_monitor_layers(
+            profile if args.profile else None,
+            os.environ,
+            sensitivity=sensitivity,
+            ball_type=ball_type,
+            assignments=config_assignments,
+            mode="mock" if args.mock else args.mode,
+        )
+    except ValueError as e:
+        parser.error(str(e))
+    monitor_config = layers.config if layers.changed else None
+    if sensitivity:
+        print(f"Sensitivity: {sensitivity} ({describe(sensitivity)})")
+    if ball_type:
+        print(f"Ball type: {ball_type} ({describe_ball_type(ball_type)})")
+    for key, value, source in layers.rows(effective=False):
+        if not source.startswith(("profile ", "--sensitivity ", "--ball-type ")):
+            print(f"Detection: {key}={value} ({source})")
+
+    # Cosine-error compensation for radars mounted off the target line
+    def mount_m(flag_cm: Optional[float], profile_m: float) -> float:
+        return flag_cm / 100 if flag_cm is not None else profile_m
+
+    try:
+        mounting_geometry = MountingGeometry(
+            lateral_offset_m=mount_m(args.mount_offset, profile.mounting.lateral_offset_m),
+            height_offset_m=mount_m(args.mount_height, profile.mounting.height_offset_m),
+            distance_m=mount_m(args.mount_distance, profile.mounting.distance_m),
+        )
+    except ValueError as e:
+        parser.error(str(e))
+    if not mounting_geometry.is_aligned:
+        print(
+            f"Cosine correction: radar {mounting_geometry.angle_deg:.1f}° off target line "
+            f"(speeds x{mounting_geometry.correction_factor:.3f})"
+        )
+
     # Configure radar logging if requested
     if args.radar_log:
         logging.basicConfig(
@@ -1348,7 +3966,183 @@ def main():
     else:
         print("Camera disabled by --no-camera flag")
 
-    start_monitor(
+    if args.club_gesture_bag:
+        try:
+            club_gesture_bag = parse_bag(args.club_gesture_bag)
+        except ValueError as e:
+            parser.error(f"--club-gesture-bag: {e}")
+    if args.club_gesture:
+        club_gesture = ClubGestureDetector()
+        print("Club gesture enabled - wave the club toward the radar 3 times to switch")
+
+    if args.tempo_cues and not args.tempo:
+        parser.error("--tempo-cues needs a --tempo ratio, e.g. --tempo 3:1")
+    if args.tempo:
+        try:
+            tempo_target = parse_ratio(args.tempo)
+        except ValueError as e:
+            parser.error(f"--tempo: {e}")
+        if mount_position == MountPosition.SIDE:
+            print("Tempo needs the radar behind or in front of the ball - not measuring tempo")
+        else:
+            backswing = (
+                Direction.OUTBOUND if mount_position == MountPosition.FRONT else Direction.INBOUND
+            )
+            tempo_meter = TempoMeter(backswing_direction=backswing)
+            print(f"Measuring swing tempo against {args.tempo}")
+    if args.tempo_cues:
+        if args.tempo_backswing <= 0:
+            parser.error("--tempo-backswing must be positive")
+        tempo_cues = TempoCues(ratio=tempo_target, backswing_sec=args.tempo_backswing)
+        tempo_cues.start()
+        print(
+            f"Tempo cues: {args.tempo_backswing:g}s backswing, "
+            f"{tempo_cues.downswing_sec:.2f}s downswing"
+        )
+
+    if args.speed_training:
+        try:
+            protocol = parse_protocol(args.speed_training)
+        except ValueError as e:
+            parser.error(f"--speed-training: {e}")
+        if args.speed_training_rest < 0:
+            parser.error("--speed-training-rest can't be negative")
+        speed_training = SpeedTrainingSession(protocol, rest_sec=args.speed_training_rest)
+        if args.mode != "swing-speed":
+            # Otherwise the monitor's swings are counted (see on_swing_detected)
+            if mount_position == MountPosition.SIDE:
+                parser.error("--speed-training needs the radar behind or in front of the ball")
+            through_impact = (
+                Direction.INBOUND if mount_position == MountPosition.FRONT else Direction.OUTBOUND
+            )
+            speed_swings = SwingDetector(direction=through_impact)
+            threading.Thread(target=_speed_swing_loop, daemon=True).start()
+        print(
+            f"Speed training: {protocol} ({protocol.total_reps} swings, no ball), "
+            f"{args.speed_training_rest:g}s rest between sets"
+        )
+
+    if args.target is not None:
+        try:
+            practice_target.set_target(args.target)
+        except ValueError as e:
+            parser.error(f"--target: {e}")
+        print(f"Scoring shots against a {args.target:g} yard target")
+
+    if args.feedback:
+        try:
+            curve = FeedbackCurve.from_spec(args.feedback_metric, args.feedback_range)
+            pins = parse_pins(args.led_pins)
+        except ValueError as e:
+            parser.error(f"--feedback: {e}")
+        outputs: Dict[str, Any] = {}
+        for name in dict.fromkeys(args.feedback):
+            if name == "tone":
+                outputs[name] = ToneOutput()
+                continue
+            try:
+                outputs[name] = LedOutput(pins)
+            except RuntimeError as e:
+                print(f"LED feedback unavailable: {e}")
+        if outputs:
+            shot_feedback = ShotFeedback(curve, list(outputs.values()))
+            add_event_sink(shot_feedback.on_event)
+            print(f"Shot feedback: {', '.join(outputs)} ({curve.describe()})")
+
+    shot_classifier = None
+    if args.shot_classifier:
+        try:
+            shot_classifier = load_classifier(args.shot_classifier)
+        except (OSError, ValueError) as e:
+            parser.error(f"--shot-classifier: {e}")
+        print(f"Shot classifier: {args.shot_classifier} (threshold {shot_classifier.threshold})")
+
+    if args.radar in STREAMING_ONLY_RADARS and args.mode == "rolling-buffer" and not args.mock:
+        parser.error(f"--radar {args.radar} has no rolling-buffer mode")
+
+    serial_dump = None
+    if args.dump_serial:
+        if args.mock:
+            print("--dump-serial ignored in mock mode (no radar)")
+        else:
+            try:
+                # The ESP32 bridge carries an OPS243's serial traffic
+                dump_radar = "iwr6843" if args.radar == "iwr6843" else "ops243"
+                serial_dump = SerialDump(args.dump_serial, radar=dump_radar)
+            except OSError as e:
+                parser.error(f"--dump-serial: {e}")
+            print(f"Capturing radar serial traffic to {args.dump_serial}")
+
+    if args.trace:
+        try:
+            init_tracer(args.trace)
+        except OSError as e:
+            parser.error(f"--trace: {e}")
+        print(f"Writing pipeline timing trace to {args.trace}")
+
+    if args.capture:
+        try:
+            sensor_capture = CaptureWriter(
+                args.capture, radar="mock" if args.mock else args.radar
+            )
+        except OSError as e:
+            parser.error(f"--capture: {e}")
+        print(f"Capturing sensor timeline to {args.capture}")
+
+    if args.auto_arm:
+        if camera_tracker:
+            arming = ArmingStateMachine()
+            print("Auto-arming enabled - 'ready' sent when a ball is placed")
+        else:
+            print("Auto-arming needs camera ball detection - disabled")
+
+    if args.connector:
+        connector_feed = ConnectorFeed(ready=arming is None)
+        add_event_sink(connector_feed.on_event)
+        print("Tablet app connector feed at /connector/status")
+
+    if args.quiet_hours is not None:
+        try:
+            windows = [] if args.quiet_hours == "off" else parse_quiet_hours(args.quiet_hours)
+        except ValueError as e:
+            parser.error(f"--quiet-hours: {e}")
+        schedule_flags["quiet_hours"] = windows
+    if args.idle_disarm is not None:
+        schedule_flags["idle_disarm_min"] = args.idle_disarm
+    try:
+        schedule = build_schedule(profile.schedule)
+    except ValueError as e:
+        parser.error(f"schedule: {e}")
+    apply_schedule(schedule)
+    if schedule.enabled:
+        print(f"Arm schedule: {schedule.to_dict()}")
+
+    if args.session_idle is not None or args.session_per_player:
+        try:
+            session_splitter = SessionSplitter(
+                idle_min=args.session_idle,
+                on_player=args.session_per_player,
+                on_split=split_session,
+            )
+        except ValueError as e:
+            parser.error(f"--session-idle: {e}")
+        session_splitter.start()
+        when = []
+        if args.session_idle is not None:
+            when.append(f"after {args.session_idle:g} min without a shot")
+        if args.session_per_player:
+            when.append("when the player changes")
+        print(f"New sessions {' and '.join(when)}")
+
+    if in_container():
+        print("Running in a container")
+        hint = None if args.mock or args.radar == "esp-bridge" else device_hint()
+        if hint:
+            print(f"Warning: {hint}")
+        if args.host in ("127.0.0.1", "localhost"):
+            print(f"Warning: --host {args.host} can't be reached from outside the container")
+
+    monitor_args.update(
         port=args.port,
         mock=args.mock,
         mode=args.mode,
@@ -1356,7 +4150,60 @@ def main():
         debug=args.debug,
         trigger_kwargs=trigger_kwargs,
         sample_rate_ksps=args.sample_rate,
+        monitor_config=monitor_config,
+        position=mount_position,
+        skip_configure=args.skip_configure,
+        shot_classifier=shot_classifier,
+        radar_model=args.radar,
+        data_port=args.data_port,
+        radar_config_file=args.radar_cfg,
+        serial_dump=serial_dump,
     )
+    start_monitor(**monitor_args)
+
+    if args.control_socket:
+        try:
+            init_control_server(args.control_socket, CONTROL_METHODS)
+        except OSError as e:
+            parser.error(f"--control-socket: {e}")
+        print(f"Control socket: {args.control_socket}")
+
+    if args.grpc_port is not None:
+        try:
+            grpc_server = init_grpc_server(
+                args.grpc_port, GRPC_METHODS, grpc_stream_events, grpc_authorize, args.host
+            )
+        except ImportError:
+            parser.error("--grpc-port needs grpcio and grpcio-tools: pip install openflight[grpc]")
+        except OSError as e:
+            parser.error(f"--grpc-port: {e}")
+        print(f"gRPC API: {args.host}:{grpc_server.port}")
+
+    if args.status_socket or args.status_port is not None:
+        status_feed = StatusFeed(menu_bar_status)
+        try:
+            if args.status_socket:
+                status_feed.listen_unix(args.status_socket)
+                print(f"Status socket: {args.status_socket}")
+            if args.status_port is not None:
+                status_feed.listen_tcp(args.status_port, host=args.status_host)
+                print(f"Status feed on {args.status_host}:{args.status_port}")
+        except OSError as e:
+            status_feed.stop()
+            parser.error(f"--status-socket/--status-port: {e}")
+        add_event_sink(status_feed.on_event)
+        status_feed.start()
+
+    if args.profile and not args.no_profile_watch:
+        profile_watcher = ProfileWatcher(
+            profile.name, on_change=apply_profile_reload, directory=profile_dir
+        )
+        profile_watcher.start()
+        print(f"Watching {profile_watcher.path} for changes")
+
+    if args.service:
+        watch_commands(SERVICE_HANDLERS)
+        print("Service mode: stop, pause and continue commands read from stdin")
 
     if args.mock:
         print("Running in MOCK mode - no radar required")
@@ -1372,11 +4219,48 @@ def main():
             app, host=args.host, port=args.web_port, debug=False, allow_unsafe_werkzeug=True
         )
     finally:
+        control = get_control_server()
+        if control:
+            control.stop()
+        grpc_server = get_grpc_server()
+        if grpc_server:
+            grpc_server.stop()
+        if profile_watcher:
+            profile_watcher.stop()
+        if status_feed:
+            status_feed.stop()
+        if scheduled_disarm:
+            scheduled_disarm.stop()
+        if session_splitter:
+            session_splitter.stop()
         stop_camera_thread()
         if camera:
             camera.stop()
             camera.close()
         stop_monitor()
+        if serial_dump:
+            serial_dump.close()
+        if sensor_capture:
+            sensor_capture.close()
+        webhook_sink = get_webhook_sink()
+        if webhook_sink:
+            webhook_sink.stop()
+        tracer = get_tracer()
+        if tracer:
+            tracer.close()
+        relay = get_relay()
+        if relay:
+            relay.stop()
+        bluetooth_serial = get_bluetooth_serial()
+        if bluetooth_serial:
+            bluetooth_serial.stop()
+        if shot_feedback:
+            shot_feedback.close()
+        if time_sync:
+            time_sync.stop()
+        cloud_sync = get_cloud_sync()
+        if cloud_sync:
+            cloud_sync.stop()
 
 
 if __name__ == "__main__":
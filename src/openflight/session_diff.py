@@ -0,0 +1,316 @@
+"""
+Compare two sessions recorded with different configurations.
+
+After changing a threshold, a profile or where the radar sits, hit a
+session with the old setup and one with the new, then diff them to see
+what the change did:
+
+    openflight-diff-sessions before.jsonl after.jsonl
+    openflight-diff-sessions before.jsonl after.jsonl --club 7-iron --json
+
+    Metric           A (n)    A mean    A sd B (n)    B mean    B sd     Delta      t
+    ball_speed          20    148.03    2.94    22    151.85    2.22     +3.81   4.7*
+    ...
+    Detection                    A       B     Delta
+    triggers accepted        80.0%   95.7%  +15.7 pt   (20/25 vs 22/23)
+
+Metrics are compared by their distributions (count, mean, standard
+deviation, median); t is Welch's t for the difference in means, starred
+when |t| >= 2, roughly where the difference stops looking like noise.
+Detection rates are the share of triggers that produced a shot (rolling
+buffer mode) and the share of shots with club speed, spin and launch
+angle. Mulligans are left out.
+"""
+
+import argparse
+import json
+import math
+import statistics
+import sys
+from collections import Counter
+from dataclasses import asdict, dataclass, field
+from typing import Dict, List, Optional
+
+from .launch_monitor import ClubType
+from .sessions import _read_entries, read_shots
+from .trends import METRICS
+
+# Metric name -> (shot_detected field, unit); the trend metrics plus signal quality
+DIFF_METRICS = {
+    **METRICS,
+    "magnitude": ("peak_magnitude", ""),
+    "readings": ("readings_count", ""),
+}
+
+# Detection rate name -> shot_detected field that must be present
+SHOT_RATES = {
+    "club speed detected": "club_speed_mph",
+    "spin detected": "spin_rpm",
+    "launch angle measured": "launch_angle_vertical",
+}
+
+SIGNIFICANT_T = 2.0
+
+
+@dataclass
+class MetricStats:
+    """Distribution of one metric in one session."""
+
+    count: int
+    mean: float
+    stdev: float  # 0 with a single value
+    median: float
+    minimum: float
+    maximum: float
+
+    @classmethod
+    def of(cls, values: List[float]) -> Optional["MetricStats"]:
+        """Stats for some values (None if there are none)."""
+        if not values:
+            return None
+        return cls(
+            count=len(values),
+            mean=statistics.mean(values),
+            stdev=statistics.stdev(values) if len(values) > 1 else 0.0,
+            median=statistics.median(values),
+            minimum=min(values),
+            maximum=max(values),
+        )
+
+
+@dataclass
+class Rate:
+    """How many of a session's events had something."""
+
+    hits: int
+    total: int
+
+    @property
+    def value(self) -> Optional[float]:
+        """Fraction, or None with nothing to count."""
+        return self.hits / self.total if self.total else None
+
+
+@dataclass
+class SessionProfile:
+    """What a session log says about detection, for comparing configurations."""
+
+    path: str
+    shots: int = 0
+    clubs: Dict[str, int] = field(default_factory=dict)
+    metrics: Dict[str, Optional[MetricStats]] = field(default_factory=dict)
+    rates: Dict[str, Rate] = field(default_factory=dict)
+
+
+@dataclass
+class MetricDiff:
+    """One metric compared between sessions A and B."""
+
+    metric: str
+    unit: str
+    a: Optional[MetricStats]
+    b: Optional[MetricStats]
+
+    @property
+    def delta(self) -> Optional[float]:
+        """Change in the mean, B - A."""
+        if self.a is None or self.b is None:
+            return None
+        return self.b.mean - self.a.mean
+
+    @property
+    def t(self) -> Optional[float]:
+        """Welch's t for the change in the mean (None if it can't be estimated)."""
+        if self.a is None or self.b is None or self.a.count < 2 or self.b.count < 2:
+            return None
+        error = math.sqrt(self.a.stdev**2 / self.a.count + self.b.stdev**2 / self.b.count)
+        return self.delta / error if error > 0 else None
+
+    @property
+    def significant(self) -> bool:
+        """Whether the change is larger than session-to-session noise would explain."""
+        return self.t is not None and abs(self.t) >= SIGNIFICANT_T
+
+
+@dataclass
+class RateDiff:
+    """One detection rate compared between sessions A and B."""
+
+    name: str
+    a: Rate
+    b: Rate
+
+    @property
+    def delta(self) -> Optional[float]:
+        """Change in the rate, B - A, as a fraction."""
+        if self.a.value is None or self.b.value is None:
+            return None
+        return self.b.value - self.a.value
+
+
+@dataclass
+class SessionDiff:
+    """Sessions A and B compared."""
+
+    a: SessionProfile
+    b: SessionProfile
+    metrics: List[MetricDiff]
+    rates: List[RateDiff]
+
+    def to_dict(self) -> dict:
+        """JSON-serializable form, with the derived deltas."""
+        return {
+            "a": asdict(self.a),
+            "b": asdict(self.b),
+            "metrics": [
+                {**asdict(m), "delta": m.delta, "t": m.t, "significant": m.significant}
+                for m in self.metrics
+            ],
+            "rates": [
+                {"name": r.name, "a": r.a.value, "b": r.b.value, "delta": r.delta}
+                for r in self.rates
+            ],
+        }
+
+
+def _count_triggers(path: str) -> Rate:
+    # Every rolling buffer trigger gets a trigger_diagnostic entry; older
+    # logs only have trigger_event
+    diagnostics = Rate(0, 0)
+    events = Rate(0, 0)
+    for entry in _read_entries(path):
+        entry_type = entry.get("type")
+        if entry_type == "trigger_diagnostic":
+            rate = diagnostics
+        elif entry_type == "trigger_event":
+            rate = events
+        else:
+            continue
+        rate.total += 1
+        rate.hits += 1 if entry.get("accepted") else 0
+    return diagnostics if diagnostics.total else events
+
+
+def profile_session(path: str, club: Optional[ClubType] = None) -> SessionProfile:
+    """
+    Read a session log into the numbers that are compared.
+
+    Args:
+        path: Session log file
+        club: Only shots with this club (trigger counts always cover the session)
+
+    Returns:
+        Session profile
+    """
+    shots = read_shots(path)
+    if club is not None:
+        shots = [s for s in shots if s.get("club", ClubType.DRIVER.value) == club.value]
+
+    profile = SessionProfile(path=path, shots=len(shots))
+    profile.clubs = dict(Counter(s.get("club", ClubType.DRIVER.value) for s in shots))
+    for metric, (name, _) in DIFF_METRICS.items():
+        values = [float(s[name]) for s in shots if isinstance(s.get(name), (int, float))]
+        profile.metrics[metric] = MetricStats.of(values)
+    profile.rates["triggers accepted"] = _count_triggers(path)
+    for rate_name, name in SHOT_RATES.items():
+        hits = sum(1 for s in shots if s.get(name) is not None)
+        profile.rates[rate_name] = Rate(hits, len(shots))
+    return profile
+
+
+def diff_sessions(path_a: str, path_b: str, club: Optional[ClubType] = None) -> SessionDiff:
+    """
+    Compare two session logs.
+
+    Args:
+        path_a: The "before" session
+        path_b: The "after" session
+        club: Only compare shots with this club
+
+    Returns:
+        Per-metric and per-rate comparison, deltas as B - A
+    """
+    a = profile_session(path_a, club)
+    b = profile_session(path_b, club)
+    metrics = [
+        MetricDiff(metric=metric, unit=unit, a=a.metrics[metric], b=b.metrics[metric])
+        for metric, (_, unit) in DIFF_METRICS.items()
+        if a.metrics[metric] or b.metrics[metric]
+    ]
+    rates = [
+        RateDiff(name=name, a=a.rates[name], b=b.rates[name])
+        for name in a.rates
+        if a.rates[name].total or b.rates[name].total
+    ]
+    return SessionDiff(a=a, b=b, metrics=metrics, rates=rates)
+
+
+def _stat_columns(stats: Optional[MetricStats]) -> str:
+    if stats is None:
+        return f"{0:>5} {'-':>9} {'-':>7}"
+    return f"{stats.count:>5} {stats.mean:>9.2f} {stats.stdev:>7.2f}"
+
+
+def _percent(value: Optional[float]) -> str:
+    return f"{value * 100:.1f}%" if value is not None else "-"
+
+
+def format_diff(diff: SessionDiff) -> str:
+    """The comparison as a text table."""
+    lines = [
+        f"A: {diff.a.path} ({diff.a.shots} shots)",
+        f"B: {diff.b.path} ({diff.b.shots} shots)",
+        "",
+        f"{'Metric':<16} {'A (n)':>5} {'A mean':>9} {'A sd':>7} "
+        f"{'B (n)':>5} {'B mean':>9} {'B sd':>7} {'Delta':>9} {'t':>6}",
+    ]
+    for m in diff.metrics:
+        delta = f"{m.delta:+9.2f}" if m.delta is not None else f"{'-':>9}"
+        t = f"{m.t:5.1f}{'*' if m.significant else ' '}" if m.t is not None else f"{'-':>5} "
+        lines.append(
+            f"{m.metric:<16} {_stat_columns(m.a)} {_stat_columns(m.b)} {delta} {t}"
+        )
+    if diff.rates:
+        lines += ["", f"{'Detection':<22} {'A':>7} {'B':>7} {'Delta':>9}"]
+        for r in diff.rates:
+            delta = f"{r.delta * 100:+.1f} pt" if r.delta is not None else "-"
+            lines.append(
+                f"{r.name:<22} {_percent(r.a.value):>7} {_percent(r.b.value):>7} {delta:>9}"
+                f"   ({r.a.hits}/{r.a.total} vs {r.b.hits}/{r.b.total})"
+            )
+    return "\n".join(lines)
+
+
+def main(argv: Optional[List[str]] = None):
+    """CLI entry point for comparing two sessions."""
+    parser = argparse.ArgumentParser(
+        description="Compare two sessions recorded with different configurations"
+    )
+    parser.add_argument("session_a", help="Session log before the change")
+    parser.add_argument("session_b", help="Session log after the change")
+    parser.add_argument("--club", help="Only compare shots with this club")
+    parser.add_argument("--json", action="store_true", help="Print JSON instead of a table")
+    args = parser.parse_args(argv)
+
+    club = None
+    if args.club:
+        try:
+            club = ClubType(args.club)
+        except ValueError:
+            parser.error(f"--club: unknown club {args.club!r}")
+
+    try:
+        diff = diff_sessions(args.session_a, args.session_b, club)
+    except OSError as e:
+        print(f"Error: {e}")
+        return 1
+
+    if args.json:
+        print(json.dumps(diff.to_dict(), indent=2))
+    else:
+        print(format_diff(diff))
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -7,13 +7,80 @@ for analysis and debugging.
 
 import json
 import logging
+import threading
 from dataclasses import asdict, dataclass
 from datetime import datetime
 from pathlib import Path
-from typing import Any, Dict, List, Optional
+from typing import Any, Dict, List, Optional, Tuple
 
+from .clock import reset_clock
 from .ops243 import SpeedReading
 
+# Tags with a CLI flag of their own; any other key can be set with --tag
+SESSION_TAG_KEYS = ("location", "ball_type", "weather", "notes")
+
+
+def validate_tags(data: Any) -> Dict[str, Optional[str]]:
+    """
+    Check a set of session tag changes.
+
+    Args:
+        data: Tag name -> value; None or "" removes the tag, numbers are
+            stored as text
+
+    Returns:
+        Tag name -> stripped value (None to remove)
+
+    Raises:
+        ValueError: If data is not an object of text values
+    """
+    if not isinstance(data, dict):
+        raise ValueError("tags must be an object, e.g. {\"weather\": \"windy\"}")
+    tags = {}
+    for key, value in data.items():
+        if not isinstance(key, str) or not key.strip():
+            raise ValueError("tag names must be non-empty text")
+        if isinstance(value, bool) or not isinstance(value, (str, int, float, type(None))):
+            raise ValueError(f"tag {key!r} must be text or null")
+        value = str(value).strip() if value is not None else None
+        tags[key.strip()] = value or None
+    return tags
+
+
+def shot_deleted_entry(shot_number: int, reason: Optional[str], source: str) -> Dict[str, Any]:
+    """Data of a shot_deleted log entry."""
+    return {"shot_number": shot_number, "reason": reason, "source": source}
+
+
+def shot_edited_entry(
+    shot_number: int,
+    changes: Dict[str, Any],
+    previous: Dict[str, Any],
+    reason: Optional[str],
+    source: str,
+) -> Dict[str, Any]:
+    """Data of a shot_edited log entry."""
+    return {
+        "shot_number": shot_number,
+        "changes": changes,
+        "previous": previous,
+        "reason": reason,
+        "source": source,
+    }
+
+
+def parse_tag(text: str) -> Tuple[str, str]:
+    """
+    Parse a KEY=VALUE command line tag.
+
+    Raises:
+        ValueError: If there is no '=' or the key is empty
+    """
+    key, sep, value = text.partition("=")
+    if not sep or not key.strip():
+        raise ValueError(f"expected KEY=VALUE, got {text!r}")
+    return key.strip(), value.strip()
+
 
 @dataclass
 class SessionMetadata:
@@ -25,8 +92,10 @@ class SessionMetadata:
     camera_enabled: bool
     camera_model: Optional[str]
     config: Dict[str, Any]
-    mode: str  # "streaming" or "rolling-buffer"
+    mode: str  # "streaming", "rolling-buffer" or "swing-speed"
     trigger_type: Optional[str]  # For rolling-buffer mode: "polling", "threshold", etc.
+    tags: Dict[str, str]  # Location, ball type, weather, notes...
+    clock: Optional[Dict[str, float]] = None  # Monotonic -> wall mapping (see clock)
 
 
 class SessionLogger:
@@ -38,12 +107,20 @@ class SessionLogger:
     - radar_raw_YYYYMMDD_HHMMSS.log - Raw radar serial data
 
     Log entry types:
-    - session_start: Session metadata
-    - session_end: Session summary
+    - session_start: Session metadata, including tags
+    - session_tags: Tags changed mid-session (all current tags)
+    - session_end: Session summary (with the reason when rolled over)
+    - session_resume: Logging continued in the same file after a restart
     - reading_accepted: Reading that passed all filters
     - shot_detected: A shot was recorded
     - shot_camera: Camera tracking data for a shot
+    - shot_delivery: Webhook delivery receipts for a shot
+    - shot_mulligan: A shot taken back; analysis should ignore it
+    - shot_deleted: A shot removed after the fact (a shank); analysis should ignore it
+    - shot_edited: A shot's club corrected after the fact, with the old values
     - config_change: Radar configuration changed
+    - monitor_config: Shot detection thresholds set or changed
+    - config_reload: Calibration profile file edited while running (old/new values)
     - error: Any errors during processing
     """
 
@@ -53,7 +130,8 @@ class SessionLogger:
         self,
         log_dir: Optional[Path] = None,
         location: str = "range",
-        enabled: bool = True
+        enabled: bool = True,
+        tags: Optional[Dict[str, str]] = None,
     ):
         """
         Initialize session logger.
@@ -62,16 +140,21 @@ class SessionLogger:
             log_dir: Directory for log files (default: ~/openflight_sessions)
             location: Location identifier for file naming (e.g., "range", "course", "home")
             enabled: Whether logging is enabled
+            tags: Session tags (ball type, weather, notes...); location is
+                included unless given here
         """
         self.log_dir = Path(log_dir) if log_dir else self.DEFAULT_LOG_DIR
         self.location = location
         self.enabled = enabled
+        self._tags: Dict[str, str] = {"location": location, **(tags or {})}
 
         self._session_id: Optional[str] = None
         self._session_file: Optional[Any] = None
         self._raw_file: Optional[Any] = None
         self._session_path: Optional[Path] = None
         self._raw_path: Optional[Path] = None
+        self._start_args: Dict[str, Any] = {}  # For starting the next session on rollover
+        self._lock = threading.RLock()
 
         # Counters for session summary
         self._stats = {
@@ -92,7 +175,8 @@ class SessionLogger:
         camera_model: Optional[str] = None,
         config: Optional[Dict[str, Any]] = None,
         mode: str = "streaming",
-        trigger_type: Optional[str] = None
+        trigger_type: Optional[str] = None,
+        resume: Optional[Path] = None,
     ) -> str:
         """
         Start a new logging session.
@@ -103,21 +187,45 @@ class SessionLogger:
             camera_enabled: Whether camera is enabled
             camera_model: Camera/YOLO model being used
             config: Current radar configuration
-            mode: Radar mode ("streaming" or "rolling-buffer")
+            mode: Radar mode ("streaming", "rolling-buffer" or "swing-speed")
             trigger_type: Trigger strategy for rolling-buffer mode
+            resume: Session log to continue instead (e.g. after a restart);
+                a new session is started if it can't be read
 
         Returns:
             Session ID
         """
         if not self.enabled:
             return ""
+        self._start_args = {
+            "radar_port": radar_port,
+            "firmware_version": firmware_version,
+            "camera_enabled": camera_enabled,
+            "camera_model": camera_model,
+            "config": config,
+            "mode": mode,
+            "trigger_type": trigger_type,
+        }
+        if resume is not None:
+            session_id = self._resume_session(
+                Path(resume), radar_port=radar_port, mode=mode, trigger_type=trigger_type
+            )
+            if session_id:
+                return session_id
 
         # Create log directory
         self.log_dir.mkdir(parents=True, exist_ok=True)
 
         # Generate session ID and filenames
-        timestamp = datetime.now()
+        clock = reset_clock()
+        timestamp = datetime.fromtimestamp(clock.wall_epoch)
         self._session_id = timestamp.strftime("%Y%m%d_%H%M%S")
+        # A session rolled over within the same second gets a suffix
+        suffix = 2
+        base_id = self._session_id
+        while (self.log_dir / f"session_{self._session_id}_{self.location}.jsonl").exists():
+            self._session_id = f"{base_id}_{suffix}"
+            suffix += 1
 
         # Semantic file naming: session_DATE_TIME_LOCATION.jsonl
         session_filename = f"session_{self._session_id}_{self.location}.jsonl"
@@ -146,7 +254,9 @@ class SessionLogger:
             camera_model=camera_model,
             config=config or {},
             mode=mode,
-            trigger_type=trigger_type
+            trigger_type=trigger_type,
+            tags=dict(self._tags),
+            clock=clock.to_dict(),
         )
 
         self._write_entry("session_start", asdict(metadata))
@@ -157,6 +267,69 @@ class SessionLogger:
 
         return self._session_id
 
+    def _resume_session(
+        self,
+        path: Path,
+        radar_port: Optional[str],
+        mode: str,
+        trigger_type: Optional[str],
+    ) -> Optional[str]:
+        """
+        Reopen a session log for appending, restoring its id, tags and counters.
+
+        Returns:
+            Session ID, or None if the file isn't a readable session log
+        """
+        header = None
+        stats = {k: 0 for k in self._stats}
+        tags = None
+        try:
+            with open(path) as f:
+                for line in f:
+                    try:
+                        entry = json.loads(line)
+                    except ValueError:
+                        continue  # Last line cut short by the crash
+                    entry_type = entry.get("type")
+                    if entry_type == "session_start":
+                        header = entry
+                        tags = entry.get("tags")
+                    elif entry_type == "session_tags":
+                        tags = entry.get("tags")
+                    elif entry_type == "reading_accepted":
+                        stats["readings_accepted"] += 1
+                    elif entry_type == "shot_detected":
+                        stats["shots_detected"] += 1
+                    elif entry_type == "error":
+                        stats["errors"] += 1
+        except OSError as e:
+            print(f"[SESSION] Can't resume {path}: {e}")
+            return None
+        if header is None or not header.get("session_id"):
+            print(f"[SESSION] Can't resume {path}: not a session log")
+            return None
+
+        clock = reset_clock()
+        self._session_id = header["session_id"]
+        self._session_path = path
+        self._raw_path = path.parent / f"radar_raw_{self._session_id}.log"
+        self._session_file = open(self._session_path, "a")
+        self._raw_file = open(self._raw_path, "a")
+        self._setup_raw_logging()
+        self._stats = stats
+        if isinstance(tags, dict):
+            self._tags = dict(tags)
+
+        self._write_entry("session_resume", {
+            "session_id": self._session_id,
+            "radar_port": radar_port,
+            "mode": mode,
+            "trigger_type": trigger_type,
+            "clock": clock.to_dict(),
+        })
+        print(f"[SESSION] Resumed logging: {self._session_path} ({stats['shots_detected']} shots)")
+        return self._session_id
+
     def _setup_raw_logging(self):
         """Configure Python logging for raw radar data."""
         # Remove existing handlers
@@ -178,8 +351,13 @@ class SessionLogger:
         self._radar_logger.addHandler(file_handler)
         self._radar_logger.setLevel(logging.DEBUG)
 
-    def end_session(self):
-        """End the current logging session and write summary."""
+    def end_session(self, reason: Optional[str] = None):
+        """
+        End the current logging session and write summary.
+
+        Args:
+            reason: Why the session was ended early, e.g. "idle" (see rollover)
+        """
         if not self.enabled or not self._session_file:
             return
 
@@ -190,11 +368,14 @@ class SessionLogger:
         summary = {
             "end_time": end_time.isoformat(),
             "stats": self._stats.copy(),
+            "tags": dict(self._tags),
             "shot_rate": (
                 self._stats["shots_detected"] / max(1, self._stats["readings_accepted"])
                 if self._stats["readings_accepted"] > 0 else 0
             ),
         }
+        if reason:
+            summary["reason"] = reason
 
         self._write_entry("session_end", summary)
 
@@ -218,19 +399,79 @@ class SessionLogger:
         print(f"[SESSION] Ended. Total shots: {self._stats['shots_detected']}")
         print(f"[SESSION] Logs saved to: {self._session_path}")
 
+    def rollover(self, reason: str, tag_changes: Optional[Dict[str, Optional[str]]] = None) -> str:
+        """
+        End the current session and start the next one in its place.
+
+        The new session has the same radar, mode and config as the one
+        it replaces, and its tags with tag_changes applied, so a kiosk
+        that runs for days logs each player or visit on its own.
+
+        Args:
+            reason: Why, written to the ended session's session_end
+            tag_changes: Tags to set or remove (None or "") for the new session
+
+        Returns:
+            The new session's ID ("" if logging is disabled)
+
+        Raises:
+            ValueError: If the tag changes are not text values
+        """
+        changes = validate_tags(tag_changes or {})
+        with self._lock:
+            self.end_session(reason=reason)
+            for key, value in changes.items():
+                if value is None:
+                    self._tags.pop(key, None)
+                else:
+                    self._tags[key] = value
+            return self.start_session(**self._start_args)
+
     def _write_entry(self, entry_type: str, data: Dict[str, Any]):
         """Write a log entry to the session file."""
-        if not self._session_file:
-            return
+        with self._lock:
+            if not self._session_file:
+                return
 
-        entry = {
-            "ts": datetime.now().isoformat(),
-            "type": entry_type,
-            **data
-        }
+            entry = {
+                "ts": datetime.now().isoformat(),
+                "type": entry_type,
+                **data
+            }
+
+            self._session_file.write(json.dumps(entry) + "\n")
+            self._session_file.flush()
 
-        self._session_file.write(json.dumps(entry) + "\n")
-        self._session_file.flush()
+    def update_tags(self, changes: Dict[str, Optional[str]]) -> Dict[str, str]:
+        """
+        Set or remove session tags mid-session.
+
+        The full set of tags is logged after each change, so the last
+        session_tags entry (or session_start if there is none) holds the
+        session's final tags.
+
+        Args:
+            changes: Tag name -> value (None or "" removes the tag)
+
+        Returns:
+            All current tags
+
+        Raises:
+            ValueError: If the changes are not text values
+        """
+        changes = validate_tags(changes)
+        for key, value in changes.items():
+            if value is None:
+                self._tags.pop(key, None)
+            else:
+                self._tags[key] = value
+
+        if self.enabled:
+            self._write_entry("session_tags", {
+                "tags": dict(self._tags),
+                "changed": sorted(changes),
+            })
+        return self.tags
 
     def log_accepted_reading(self, reading: SpeedReading):
         """Log a reading that passed all filters and will be processed."""
@@ -263,6 +504,17 @@ class SessionLogger:
         launch_angle_vertical: Optional[float] = None,
         launch_angle_horizontal: Optional[float] = None,
         launch_angle_confidence: Optional[float] = None,
+        ball_speed_raw_mph: Optional[float] = None,
+        club_speed_raw_mph: Optional[float] = None,
+        hole_number: Optional[int] = None,
+        hole_shot: Optional[int] = None,
+        target_yards: Optional[float] = None,
+        strokes_gained: Optional[float] = None,
+        ball_type: Optional[str] = None,
+        pre_trigger_readings: Optional[List[Dict]] = None,
+        lost_frames: Optional[int] = None,
+        max_reading_gap_ms: Optional[float] = None,
+        ball_speed_reading_mph: Optional[float] = None,
     ):
         """
         Log a detected shot with all metrics.
@@ -281,6 +533,17 @@ class SessionLogger:
             spin_quality: Quality assessment ("high", "medium", "low")
             carry_spin_adjusted: Carry distance adjusted for spin (rolling buffer mode only)
             mode: Radar mode ("streaming" or "rolling-buffer")
+            ball_speed_raw_mph: Measured ball speed before cosine correction
+            club_speed_raw_mph: Measured club speed before cosine correction
+            hole_number: Sim hole the shot was played on
+            hole_shot: Stroke index on that hole
+            target_yards: Practice target the shot was scored against
+            strokes_gained: Strokes gained against that target
+            ball_type: Ball type the carry was scaled for (see ball_types)
+            pre_trigger_readings: Filtered-out readings from just before the shot
+            lost_frames: Radar frames lost in and just before the ball's readings
+            max_reading_gap_ms: Longest time between the ball's frames
+            ball_speed_reading_mph: Fastest ball reading, if lost frames raised ball speed
         """
         if not self.enabled:
             return
@@ -305,6 +568,17 @@ class SessionLogger:
             "launch_angle_vertical": launch_angle_vertical,
             "launch_angle_horizontal": launch_angle_horizontal,
             "launch_angle_confidence": launch_angle_confidence,
+            "ball_speed_raw_mph": ball_speed_raw_mph,
+            "club_speed_raw_mph": club_speed_raw_mph,
+            "hole_number": hole_number,
+            "hole_shot": hole_shot,
+            "target_yards": target_yards,
+            "strokes_gained": strokes_gained,
+            "ball_type": ball_type,
+            "pre_trigger_readings": pre_trigger_readings,
+            "lost_frames": lost_frames,
+            "max_reading_gap_ms": max_reading_gap_ms,
+            "ball_speed_reading_mph": ball_speed_reading_mph,
         })
 
     def log_camera_data(
@@ -339,6 +613,189 @@ class SessionLogger:
             "source": source,
         })
 
+    def log_monitor_config(self, config: Dict[str, Any], source: str = "startup"):
+        """
+        Log the active shot detection thresholds.
+
+        Written at session start and on every change, so each shot in the
+        log can be matched to the thresholds that were active when it was
+        detected.
+
+        Args:
+            config: MonitorConfig as a dict
+            source: What applied the config ("startup", "api", ...)
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("monitor_config", {
+            "config": config,
+            "source": source,
+        })
+
+    def log_config_reload(self, profile: str, changes: Dict[str, Dict[str, Any]]):
+        """
+        Log settings changed by a hot reload of the calibration profile.
+
+        Args:
+            profile: Name of the reloaded profile
+            changes: Setting name -> {"old": value, "new": value}
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("config_reload", {
+            "profile": profile,
+            "changes": changes,
+        })
+
+    def log_shot_delivery(self, shot_number: Optional[int], status: str, receipts: List[Dict]):
+        """
+        Log whether a shot reached its webhook endpoints.
+
+        Args:
+            shot_number: Shot number from the shot_detected entry
+            status: "delivered", "failed" or "merged" (throttled)
+            receipts: One DeliveryReceipt.to_dict() per endpoint
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("shot_delivery", {
+            "shot_number": shot_number,
+            "status": status,
+            "receipts": receipts,
+        })
+
+    def log_mulligan(self, shot_number: Optional[int]):
+        """
+        Log that a shot was taken back (mulligan).
+
+        The shot_detected entry stays in the log for diagnostics; trends and
+        exports skip shot numbers with a mulligan entry.
+
+        Args:
+            shot_number: Shot number from the shot_detected entry
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("shot_mulligan", {"shot_number": shot_number})
+
+    def log_shot_deleted(self, shot_number: int, reason: Optional[str] = None, source: str = "api"):
+        """
+        Log that a shot was deleted after the fact.
+
+        Like a mulligan, the shot_detected entry stays in the log and
+        analysis skips the shot; this entry is the audit trail.
+
+        Args:
+            shot_number: Shot number from the shot_detected entry
+            reason: Why, as given by whoever deleted it
+            source: What deleted it ("api", "cli")
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("shot_deleted", shot_deleted_entry(shot_number, reason, source))
+
+    def log_shot_edited(
+        self,
+        shot_number: int,
+        changes: Dict[str, Any],
+        previous: Dict[str, Any],
+        reason: Optional[str] = None,
+        source: str = "api",
+    ):
+        """
+        Log a correction to a shot (e.g. its club).
+
+        The shot_detected entry is left as recorded; analysis applies the
+        changes over it (see sessions.read_shots).
+
+        Args:
+            shot_number: Shot number from the shot_detected entry
+            changes: Field -> new value
+            previous: Field -> value before the change
+            reason: Why, as given by whoever edited it
+            source: What edited it ("api", "cli")
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry(
+            "shot_edited", shot_edited_entry(shot_number, changes, previous, reason, source)
+        )
+
+    def log_voice_memo(self, shot_number: int, file: str, duration_sec: float):
+        """
+        Log a voice memo recorded for a shot (see voice_memo).
+
+        Args:
+            shot_number: Shot number from the shot_detected entry
+            file: Memo file name, next to the session log
+            duration_sec: Memo length
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("shot_memo", {
+            "shot_number": shot_number,
+            "file": file,
+            "duration_sec": duration_sec,
+        })
+
+    def log_swing(
+        self,
+        club_speed_mph: float,
+        club: str,
+        peak_magnitude: Optional[float],
+        readings: Optional[List[Dict]] = None,
+    ):
+        """
+        Log a club-only swing (--mode swing-speed).
+
+        Args:
+            club_speed_mph: Peak club head speed
+            club: Club type used
+            peak_magnitude: Peak radar magnitude
+            readings: Readings that made up the swing
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("swing_detected", {
+            "club_speed_mph": club_speed_mph,
+            "club": club,
+            "peak_magnitude": peak_magnitude,
+            "readings_count": len(readings or []),
+            "readings": readings or [],
+        })
+
+    def log_speed_swing(self, rep: Dict[str, Any]):
+        """
+        Log a club-only swing counted by --speed-training.
+
+        Args:
+            rep: SpeedTrainingSession.record_swing() result
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("speed_swing", rep)
+
+    def log_speed_training_summary(self, summary: Dict[str, Any]):
+        """
+        Log the compliance summary of a finished speed training protocol.
+
+        Args:
+            summary: SpeedTrainingSession.summary() result
+        """
+        if not self.enabled:
+            return
+
+        self._write_entry("speed_training_summary", summary)
+
     def log_iq_reading(
         self,
         speed_mph: float,
@@ -611,6 +1068,11 @@ class SessionLogger:
         """Get the current session ID."""
         return self._session_id
 
+    @property
+    def tags(self) -> Dict[str, str]:
+        """Get the current session tags."""
+        return dict(self._tags)
+
     @property
     def stats(self) -> Dict[str, int]:
         """Get current session statistics."""
@@ -629,7 +1091,8 @@ def get_session_logger() -> Optional[SessionLogger]:
 def init_session_logger(
     log_dir: Optional[Path] = None,
     location: str = "range",
-    enabled: bool = True
+    enabled: bool = True,
+    tags: Optional[Dict[str, str]] = None,
 ) -> SessionLogger:
     """
     Initialize and return the global session logger.
@@ -638,10 +1101,13 @@ def init_session_logger(
         log_dir: Directory for log files
         location: Location identifier
         enabled: Whether logging is enabled
+        tags: Session tags written to the session header
 
     Returns:
         SessionLogger instance
     """
     global _session_logger
-    _session_logger = SessionLogger(log_dir=log_dir, location=location, enabled=enabled)
+    _session_logger = SessionLogger(
+        log_dir=log_dir, location=location, enabled=enabled, tags=tags
+    )
     return _session_logger
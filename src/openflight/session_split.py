@@ -0,0 +1,144 @@
+"""
+Splitting a long-running server's log into sessions.
+
+A kiosk server runs for days, and without splitting all of it is one
+session. A SessionSplitter starts a new session (see
+SessionLogger.rollover):
+
+- idle: after this many minutes without a shot, so each visit to the bay
+  gets a session of its own; the new session starts then and waits for
+  the next shot, and a session without shots is never split for idling.
+  The "player" tag is cleared, since whoever was hitting has left.
+- player: when the "player" tag is set to someone else (the phone remote's
+  player picker, PATCH /api/session/tags) while the session has shots;
+  the new session starts with the new player.
+
+Example:
+    splitter = SessionSplitter(idle_min=30, on_split=lambda reason, tags: print(reason, tags))
+    splitter.start()
+    splitter.on_shot()
+"""
+
+import logging
+import threading
+import time
+from typing import Callable, Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+IDLE = "idle"
+PLAYER = "player"
+
+
+class SessionSplitter:
+    """Decides when to start a new session; on_split does the starting."""
+
+    DEFAULT_INTERVAL_SEC = 5.0
+
+    def __init__(
+        self,
+        idle_min: Optional[float] = None,
+        on_player: bool = False,
+        on_split: Optional[Callable[[str, Dict[str, Optional[str]]], None]] = None,
+        interval_sec: float = DEFAULT_INTERVAL_SEC,
+    ):
+        """
+        Initialize the splitter.
+
+        Args:
+            idle_min: Minutes without a shot before a new session (None = never)
+            on_player: Start a new session when the player changes
+            on_split: Called with IDLE or PLAYER and the tag changes for the new
+                session (see SessionLogger.rollover) to start it
+            interval_sec: How often the idle time is checked
+        """
+        if idle_min is not None and idle_min <= 0:
+            raise ValueError("idle time must be a positive number of minutes")
+        self.idle_min = idle_min
+        self.on_player = on_player
+        self.on_split = on_split
+        self.interval_sec = interval_sec
+        self.splits = 0
+        self._last_shot: Optional[float] = None  # None until the session has a shot
+        self._lock = threading.Lock()
+        self._stop_event = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def enabled(self) -> bool:
+        """Whether sessions are ever split."""
+        return self.idle_min is not None or self.on_player
+
+    def on_shot(self, mono: Optional[float] = None):
+        """Note a shot in the current session, restarting the idle timer."""
+        with self._lock:
+            self._last_shot = time.monotonic() if mono is None else mono
+
+    def check(self, mono: Optional[float] = None) -> bool:
+        """
+        Start a new session if the current one has been idle long enough.
+
+        Returns:
+            Whether a new session was started
+        """
+        mono = time.monotonic() if mono is None else mono
+        with self._lock:
+            idle = (
+                self.idle_min is not None
+                and self._last_shot is not None
+                and mono - self._last_shot >= self.idle_min * 60
+            )
+        if idle:
+            self.split(IDLE, {"player": None})
+        return idle
+
+    def player_changed(self, player: Optional[str], new_player: Optional[str]) -> bool:
+        """
+        Whether setting the player tag to new_player should start a new session.
+
+        Clearing the player, or setting the one already playing, doesn't.
+        """
+        with self._lock:
+            has_shots = self._last_shot is not None
+        return self.on_player and has_shots and bool(new_player) and new_player != player
+
+    def split(self, reason: str, tag_changes: Optional[Dict[str, Optional[str]]] = None):
+        """
+        Start a new session now.
+
+        Args:
+            reason: IDLE or PLAYER
+            tag_changes: Tags to set or remove (None) for the new session
+        """
+        with self._lock:
+            self._last_shot = None
+            self.splits += 1
+        logger.info("New session (%s)", reason)
+        if self.on_split:
+            self.on_split(reason, tag_changes or {})
+
+    def to_dict(self) -> dict:
+        """Settings for the REST API and status."""
+        return {"idle_min": self.idle_min, "on_player": self.on_player, "splits": self.splits}
+
+    def start(self):
+        """Start checking the idle time in a background thread."""
+        if self.idle_min is None or (self._thread and self._thread.is_alive()):
+            return
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def stop(self):
+        """Stop checking."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=2.0)
+            self._thread = None
+
+    def _run(self):
+        while not self._stop_event.wait(self.interval_sec):
+            try:
+                self.check()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.error("Session idle check failed: %s", e)
@@ -0,0 +1,194 @@
+"""
+List recorded sessions and find them by tag.
+
+Sessions are tagged with where and how they were recorded (location, ball
+type, weather, notes) at startup or mid-session through the API. Months
+later, those tags are what make results comparable: indoor vs outdoor,
+range balls vs premium, calm vs windy.
+
+Usage:
+    openflight-sessions
+    openflight-sessions --tag ball_type=pro-v1 --tag location=range
+    openflight-sessions ~/openflight_sessions/session_2025*.jsonl --json
+"""
+
+import argparse
+import json
+import sys
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Dict, Iterator, List, Optional
+
+from .reprocess import find_sessions
+from .session_logger import SessionLogger, parse_tag
+
+
+@dataclass
+class SessionSummary:
+    """What a session log says about its session."""
+
+    path: str
+    session_id: Optional[str] = None
+    start_time: Optional[str] = None
+    mode: Optional[str] = None
+    shots: int = 0  # Not counting mulligans and deleted shots
+    tags: Dict[str, str] = field(default_factory=dict)
+
+
+def _read_entries(path: str) -> Iterator[dict]:
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.strip()
+            if not line:
+                continue
+            try:
+                yield json.loads(line)
+            except ValueError:
+                continue
+
+
+def _location_from_name(path: str) -> Optional[str]:
+    parts = Path(path).stem.split("_", 3)
+    if len(parts) == 4 and parts[0] == "session":
+        return parts[3]
+    return None
+
+
+def read_shots(path: str) -> List[dict]:
+    """
+    Read a session's shots, leaving out mulligans and deleted shots.
+
+    Each shot_detected entry gets a "tags" key with the session tags in
+    effect when it was hit, so a mid-session player or weather change is
+    attributed to the right shots, and a "memo" key with its voice memo's
+    path if one was recorded. Corrections (shot_edited) are applied over
+    the recorded values, and the shot gets an "edits" key listing them.
+
+    Args:
+        path: Session log file
+
+    Returns:
+        shot_detected entries in file order
+    """
+    shots = []
+    mulligans = set()
+    memos: Dict[int, str] = {}
+    edits: Dict[int, List[dict]] = {}
+    tags: Dict[str, str] = {}
+    location = _location_from_name(path)
+    for entry in _read_entries(path):
+        entry_type = entry.get("type")
+        if entry_type in ("session_start", "session_tags"):
+            tags = dict(entry.get("tags") or {})
+        elif entry_type in ("shot_mulligan", "shot_deleted"):
+            mulligans.add(entry.get("shot_number"))
+        elif entry_type == "shot_edited" and isinstance(entry.get("changes"), dict):
+            edits.setdefault(entry.get("shot_number"), []).append(entry)
+        elif entry_type == "shot_memo" and entry.get("file"):
+            memos[entry.get("shot_number")] = str(Path(path).with_name(entry["file"]))
+        elif entry_type == "shot_detected":
+            shot_tags = dict(tags)
+            if "location" not in shot_tags and location:
+                shot_tags["location"] = location
+            shots.append({**entry, "tags": shot_tags})
+    mulligans.discard(None)
+    for shot in shots:
+        if shot.get("shot_number") in memos:
+            shot["memo"] = memos[shot["shot_number"]]
+        for edit in edits.get(shot.get("shot_number"), []):
+            shot.update(edit["changes"])
+            shot.setdefault("edits", []).append(
+                {key: edit.get(key) for key in ("ts", "changes", "previous", "reason", "source")}
+            )
+    return [shot for shot in shots if shot.get("shot_number") not in mulligans]
+
+
+def summarize_session(path: str) -> SessionSummary:
+    """
+    Read a session log's header, final tags and shot count.
+
+    Logs written before session tags existed get their location from the
+    file name (session_DATE_TIME_LOCATION.jsonl).
+
+    Args:
+        path: Session log file
+
+    Returns:
+        Session summary
+    """
+    summary = SessionSummary(path=path)
+    for entry in _read_entries(path):
+        entry_type = entry.get("type")
+        if entry_type == "session_start":
+            summary.session_id = entry.get("session_id")
+            summary.start_time = entry.get("start_time")
+            summary.mode = entry.get("mode")
+            summary.tags = dict(entry.get("tags") or {})
+        elif entry_type == "session_tags":
+            summary.tags = dict(entry.get("tags") or {})
+        elif entry_type == "shot_detected":
+            summary.shots += 1
+        elif entry_type in ("shot_mulligan", "shot_deleted"):
+            summary.shots -= 1
+
+    location = _location_from_name(path)
+    if "location" not in summary.tags and location:
+        summary.tags["location"] = location
+    return summary
+
+
+def matches_tags(tags: Dict[str, str], filters: Dict[str, str]) -> bool:
+    """
+    True if every filter value appears in the tag of the same name.
+
+    Matching is case-insensitive and by substring, so "pro" finds
+    "Pro V1" and a word finds notes containing it.
+    """
+    for key, wanted in filters.items():
+        if wanted.lower() not in tags.get(key, "").lower():
+            return False
+    return True
+
+
+def main():
+    """CLI entry point for listing sessions."""
+    parser = argparse.ArgumentParser(description="List recorded sessions and their tags")
+    parser.add_argument(
+        "patterns",
+        nargs="*",
+        help="Session log files or glob patterns (default: all in ~/openflight_sessions)",
+    )
+    parser.add_argument(
+        "--tag",
+        action="append",
+        default=[],
+        metavar="KEY=VALUE",
+        help="Only sessions whose tag contains VALUE (repeatable)",
+    )
+    parser.add_argument("--json", action="store_true", help="Print JSON instead of a table")
+    args = parser.parse_args()
+
+    try:
+        filters = dict(parse_tag(t) for t in args.tag)
+    except ValueError as e:
+        parser.error(f"--tag: {e}")
+
+    patterns = args.patterns or [str(SessionLogger.DEFAULT_LOG_DIR / "session_*.jsonl")]
+    sessions = [summarize_session(path) for path in find_sessions(patterns)]
+    sessions = [s for s in sessions if matches_tags(s.tags, filters)]
+
+    if args.json:
+        print(json.dumps([asdict(s) for s in sessions], indent=2))
+        return
+    if not sessions:
+        print("No sessions found")
+        sys.exit(1)
+
+    print(f"{'Session':<17} {'Shots':>5}  Tags")
+    for session in sessions:
+        tags = ", ".join(f"{k}={v}" for k, v in session.tags.items())
+        print(f"{session.session_id or Path(session.path).stem:<17} {session.shots:>5}  {tags}")
+
+
+if __name__ == "__main__":
+    main()
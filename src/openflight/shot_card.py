@@ -0,0 +1,168 @@
+"""
+Console shot card templates.
+
+The openflight CLI prints a card for every shot. --shot-card chooses what
+is on it: a built-in card ("full", the default, or "compact") or a
+template string, so a player can keep to ball speed and carry while a
+coach gets every number:
+
+    openflight --shot-card compact
+    openflight --shot-card "Ball {ball_speed:.1f} mph | Carry {carry:.0f} yd"
+    openflight --shot-card "{club}\\nBall {ball_speed@km/h:.0f} km/h\\nCarry {carry@m:.0f} m"
+
+Placeholders are {field}, {field:spec} or {field@unit:spec}, with spec as
+in Python's format() (fractional numbers default to one decimal). Fields:
+
+    ball_speed, club_speed   mph (or km/h, m/s)
+    carry, carry_low, carry_high   yd (or m)
+    smash, signal, spin, launch_angle, tempo, club
+
+A line with a field the shot doesn't have (no club speed detected, no
+spin outside rolling buffer mode) is left out. "\\n" in a template given
+on the command line starts a new line.
+"""
+
+import string
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+# Field -> (value from a Shot, unit it is in)
+FIELDS: Dict[str, Tuple[Callable[[Any], Any], Optional[str]]] = {
+    "ball_speed": (lambda shot: shot.ball_speed_mph, "mph"),
+    "club_speed": (lambda shot: shot.club_speed_mph, "mph"),
+    "smash": (lambda shot: shot.smash_factor, None),
+    "carry": (lambda shot: shot.estimated_carry_yards, "yd"),
+    "carry_low": (lambda shot: shot.estimated_carry_range[0], "yd"),
+    "carry_high": (lambda shot: shot.estimated_carry_range[1], "yd"),
+    "signal": (lambda shot: shot.peak_magnitude, None),
+    "spin": (lambda shot: shot.spin_rpm, None),
+    "launch_angle": (lambda shot: shot.launch_angle_vertical, None),
+    "tempo": (lambda shot: shot.tempo_ratio, None),
+    "club": (lambda shot: shot.club.value, None),
+}
+
+_CONVERSIONS = {
+    ("mph", "mph"): 1.0,
+    ("mph", "km/h"): 1.609344,
+    ("mph", "m/s"): 0.44704,
+    ("yd", "yd"): 1.0,
+    ("yd", "m"): 0.9144,
+}
+
+_RULE = "-" * 40
+
+BUILTIN_CARDS = {
+    "full": "\n".join(
+        [
+            _RULE,
+            "  Club Speed:   {club_speed:.1f} mph",
+            "  Ball Speed:   {ball_speed:.1f} mph",
+            "  Smash Factor: {smash:.2f}",
+            "  Est. Carry:   {carry:.0f} yards",
+            "  Range:        {carry_low:.0f}-{carry_high:.0f} yards",
+            "  Signal:       {signal:.0f}",
+            _RULE,
+        ]
+    ),
+    "compact": "Ball {ball_speed:.1f} mph | Carry {carry:.0f} yd",
+}
+
+
+class ShotCard:
+    """
+    A console shot card from a template.
+
+    Example:
+        card = ShotCard.load("Ball {ball_speed:.1f} mph | Carry {carry:.0f} yd")
+        print(card.render(shot))
+    """
+
+    def __init__(self, template: str):
+        """
+        Parse a template.
+
+        Raises:
+            ValueError: If a placeholder names an unknown field or unit
+        """
+        self.template = template
+        self._lines: List[List[Tuple[str, Optional[str], Optional[str], str]]] = [
+            self._parse(line) for line in template.split("\n")
+        ]
+
+    @classmethod
+    def load(cls, text: str) -> "ShotCard":
+        """
+        A built-in card by name, or a card from a template string.
+
+        Raises:
+            ValueError: If the template is invalid
+        """
+        if text in BUILTIN_CARDS:
+            return cls(BUILTIN_CARDS[text])
+        if "{" not in text:
+            raise ValueError(
+                f"{text!r} is neither a card ({', '.join(BUILTIN_CARDS)}) "
+                "nor a template with {field} placeholders"
+            )
+        return cls(text.replace("\\n", "\n"))
+
+    @staticmethod
+    def _parse(line: str) -> List[Tuple[str, Optional[str], Optional[str], str]]:
+        """Split a line into (text, field, unit, spec) parts."""
+        parts = []
+        try:
+            parsed = list(string.Formatter().parse(line))
+        except ValueError as e:
+            raise ValueError(f"bad template line {line!r}: {e}") from None
+        for text, name, spec, _ in parsed:
+            if name is None:
+                parts.append((text, None, None, ""))
+                continue
+            name, _, unit = name.partition("@")
+            if name not in FIELDS:
+                raise ValueError(f"unknown field {name!r} (fields: {', '.join(FIELDS)})")
+            source_unit = FIELDS[name][1]
+            if unit and (source_unit, unit) not in _CONVERSIONS:
+                raise ValueError(f"can't show {name} in {unit}")
+            try:
+                format("driver" if name == "club" else 1.0, spec)
+            except ValueError:
+                raise ValueError(f"bad format {spec!r} for {name}") from None
+            parts.append((text, name, unit or None, spec or ""))
+        return parts
+
+    def render(self, shot: Any) -> str:
+        """
+        The card for a shot.
+
+        Args:
+            shot: Shot to show
+
+        Returns:
+            The card's lines, without those whose fields the shot lacks
+        """
+        lines = []
+        for parts in self._lines:
+            line = []
+            for text, name, unit, spec in parts:
+                line.append(text)
+                if name is None:
+                    continue
+                value = _value(shot, name, unit)
+                if value is None:
+                    break
+                if isinstance(value, float) and not spec:
+                    spec = ".1f"
+                line.append(format(value, spec))
+            else:
+                lines.append("".join(line))
+        return "\n".join(lines)
+
+
+def _value(shot: Any, name: str, unit: Optional[str]) -> Any:
+    getter, source_unit = FIELDS[name]
+    value = getter(shot)
+    if value is None:
+        return None
+    if unit:
+        value = float(value) * _CONVERSIONS[(source_unit, unit)]
+    return value
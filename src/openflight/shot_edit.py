@@ -0,0 +1,206 @@
+"""
+Delete shots or correct their club in a recorded session log.
+
+The obvious shank or the shots hit after forgetting to change clubs
+otherwise stay in every stat built from the log. Edits are appended to
+the log as shot_deleted / shot_edited entries; the shot_detected entries
+stay as recorded, so the log keeps an audit trail of what was changed,
+when and why, and analysis (sessions.read_shots) applies the edits.
+
+A running server's session is edited through the API instead (DELETE and
+PATCH /api/shot), since the server has the log open: a session log
+without a session_end entry is refused unless --force is given.
+
+Usage:
+    openflight-edit-shot session_20250601_101500_range.jsonl 12 --delete --reason shank
+    openflight-edit-shot session_20250601_101500_range.jsonl 14 15 16 --club 7-iron
+    openflight-edit-shot session_20250601_101500_range.jsonl --history
+"""
+
+import argparse
+import json
+import sys
+from datetime import datetime
+from typing import Any, Dict, List, Optional
+
+from .launch_monitor import ClubType
+from .reprocess import shot_from_entry
+from .session_logger import shot_deleted_entry, shot_edited_entry
+from .sessions import _read_entries, read_shots
+
+EDIT_TYPES = ("shot_mulligan", "shot_deleted", "shot_edited")
+
+
+def _append(path: str, entry_type: str, data: Dict[str, Any]) -> dict:
+    """Append an entry to a session log, as SessionLogger writes it."""
+    entry = {"ts": datetime.now().isoformat(), "type": entry_type, **data}
+    with open(path, "a", encoding="utf-8") as f:
+        f.write(json.dumps(entry) + "\n")
+    return entry
+
+
+def session_ended(path: str) -> bool:
+    """Whether the log has a session_end entry (it isn't still being written)."""
+    ended = False
+    for entry in _read_entries(path):
+        entry_type = entry.get("type")
+        if entry_type == "session_end":
+            ended = True
+        elif entry_type == "session_resume":
+            ended = False
+    return ended
+
+
+def _shots_by_number(path: str, shot_numbers: List[int]) -> Dict[int, dict]:
+    """
+    The session's current shots with these numbers.
+
+    Raises:
+        ValueError: If one isn't in the session (or was already deleted)
+    """
+    shots = {shot.get("shot_number"): shot for shot in read_shots(path)}
+    missing = [str(n) for n in shot_numbers if n not in shots]
+    if missing:
+        raise ValueError(f"no shot {', '.join(missing)} in {path} (or already deleted)")
+    return {n: shots[n] for n in shot_numbers}
+
+
+def delete_shots(
+    path: str, shot_numbers: List[int], reason: Optional[str] = None, source: str = "cli"
+) -> List[dict]:
+    """
+    Delete shots from a session log.
+
+    Returns:
+        The shot_deleted entries written
+
+    Raises:
+        ValueError: If a shot isn't in the session
+    """
+    return [
+        _append(path, "shot_deleted", shot_deleted_entry(n, reason, source))
+        for n in _shots_by_number(path, shot_numbers)
+    ]
+
+
+def set_club(
+    path: str,
+    shot_numbers: List[int],
+    club: ClubType,
+    reason: Optional[str] = None,
+    source: str = "cli",
+) -> List[dict]:
+    """
+    Correct the club of shots in a session log, re-estimating their carry.
+
+    Shots already on that club are left alone.
+
+    Returns:
+        The shot_edited entries written
+
+    Raises:
+        ValueError: If a shot isn't in the session
+    """
+    written = []
+    for number, entry in _shots_by_number(path, shot_numbers).items():
+        shot = shot_from_entry(entry)
+        if shot.club == club:
+            continue
+        previous = {
+            "club": entry.get("club"),
+            "estimated_carry_yards": entry.get("estimated_carry_yards"),
+        }
+        shot.club = club
+        changes = {"club": club.value, "estimated_carry_yards": shot.estimated_carry_yards}
+        written.append(
+            _append(
+                path, "shot_edited", shot_edited_entry(number, changes, previous, reason, source)
+            )
+        )
+    return written
+
+
+def edit_history(path: str) -> List[dict]:
+    """The log's mulligans, deletions and corrections, oldest first."""
+    return [entry for entry in _read_entries(path) if entry.get("type") in EDIT_TYPES]
+
+
+def format_edit(entry: dict) -> str:
+    """One line describing an edit entry."""
+    number = entry.get("shot_number")
+    when = str(entry.get("ts", ""))[:19].replace("T", " ")
+    entry_type = entry.get("type")
+    if entry_type == "shot_mulligan":
+        text = f"shot {number} taken back (mulligan)"
+    elif entry_type == "shot_deleted":
+        text = f"shot {number} deleted"
+    else:
+        previous = entry.get("previous") or {}
+        changes = entry.get("changes") or {}
+        text = f"shot {number} club {previous.get('club')} -> {changes.get('club')}"
+    if entry.get("reason"):
+        text += f": {entry['reason']}"
+    source = entry.get("source")
+    return f"{when}  {text}" + (f" [{source}]" if source else "")
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """CLI entry point for editing shots in a session log."""
+    parser = argparse.ArgumentParser(
+        description="Delete shots or correct their club in a session log (kept as an audit trail)"
+    )
+    parser.add_argument("session", help="Session log file")
+    parser.add_argument("shots", nargs="*", type=int, help="Shot numbers to edit")
+    action = parser.add_mutually_exclusive_group(required=True)
+    action.add_argument("--delete", action="store_true", help="Delete the shots")
+    action.add_argument("--club", help="Set the shots' club, e.g. 7-iron")
+    action.add_argument("--history", action="store_true", help="List the log's edits")
+    parser.add_argument("--reason", help="Why, kept with the edit")
+    parser.add_argument(
+        "--force",
+        action="store_true",
+        help="Edit a log without a session_end entry (the server must not be writing it)",
+    )
+    args = parser.parse_args(argv)
+
+    try:
+        if args.history:
+            history = edit_history(args.session)
+            for entry in history:
+                print(format_edit(entry))
+            if not history:
+                print("No edits")
+            return 0
+
+        if not args.shots:
+            parser.error("give the shot numbers to edit")
+        club = None
+        if args.club:
+            try:
+                club = ClubType(args.club)
+            except ValueError:
+                parser.error(f"--club: unknown club {args.club!r}")
+        if not args.force and not session_ended(args.session):
+            print(
+                f"Error: {args.session} has no session_end: if the server is still writing it, "
+                "edit through the API (DELETE/PATCH /api/shot); otherwise add --force"
+            )
+            return 1
+
+        if club is not None:
+            written = set_club(args.session, args.shots, club, reason=args.reason)
+        else:
+            written = delete_shots(args.session, args.shots, reason=args.reason)
+    except (OSError, ValueError) as e:
+        print(f"Error: {e}")
+        return 1
+
+    for entry in written:
+        print(format_edit(entry))
+    if not written:
+        print("Nothing to change")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,109 @@
+"""
+Shot timing per radar mode and per club.
+
+The gap that ends a shot (shot_timeout_sec), the longest a shot may last
+(max_shot_duration_sec) and how long before the ball the club may be seen
+(club_ball_window_sec) suit some swings better than others: a driver's
+fast, long flight keeps the radar busy for longer than a chip, and a
+wedge's sparse readings need a longer gap to stay one shot. A profile's
+"shot_timing" section sets them per mode and per club:
+
+    "shot_timing": {
+      "modes": {"swing-speed": {"shot_timeout_sec": 0.3}},
+      "clubs": {
+        "driver": {"max_shot_duration_sec": 0.4},
+        "sw": {"shot_timeout_sec": 0.8, "club_ball_window_sec": 0.5}
+      }
+    }
+
+- modes: layered over the profile's "monitor" thresholds for the radar
+  mode the monitor runs in ("streaming", "swing-speed" or "mock"), so
+  OPENFLIGHT_MONITOR_* variables, presets and --set still override them
+- clubs: replace the thresholds while that club is selected, whatever
+  layer they came from
+
+Fields not listed keep the monitor's values.
+"""
+
+from dataclasses import dataclass, field
+from typing import Dict
+
+from .launch_monitor import ClubType, MonitorConfig
+
+TIMING_FIELDS = ("shot_timeout_sec", "max_shot_duration_sec", "club_ball_window_sec")
+
+# Radar modes whose shots are detected with MonitorConfig thresholds
+TIMING_MODES = ("streaming", "swing-speed", "mock")
+
+CLUB_NAMES = tuple(club.value for club in ClubType if club != ClubType.UNKNOWN)
+
+
+def _parse_overrides(section: str, data: dict, names: tuple) -> Dict[str, Dict[str, float]]:
+    """Validate one of the section's {name: {field: seconds}} maps."""
+    if not isinstance(data, dict):
+        raise ValueError(f"shot_timing.{section} must be an object")
+    parsed = {}
+    for name, overrides in data.items():
+        key = f"shot_timing.{section}.{name}"
+        if name not in names:
+            raise ValueError(f"{key}: unknown {section[:-1]} (use one of {', '.join(names)})")
+        if not isinstance(overrides, dict):
+            raise ValueError(f"{key} must be an object")
+        unknown = sorted(set(overrides) - set(TIMING_FIELDS))
+        if unknown:
+            raise ValueError(
+                f"{key}: unknown field {unknown[0]!r} (use {', '.join(TIMING_FIELDS)})"
+            )
+        try:
+            MonitorConfig().with_updates(overrides)
+        except ValueError as e:
+            raise ValueError(f"{key}.{e}") from None
+        parsed[name] = {k: float(v) for k, v in overrides.items()}
+    return parsed
+
+
+@dataclass(frozen=True)
+class ShotTiming:
+    """
+    Shot timing thresholds that differ by radar mode or club.
+
+    Attributes:
+        modes: Timing fields by radar mode (TIMING_MODES)
+        clubs: Timing fields by club (ClubType value)
+    """
+
+    modes: Dict[str, Dict[str, float]] = field(default_factory=dict)
+    clubs: Dict[str, Dict[str, float]] = field(default_factory=dict)
+
+    @classmethod
+    def from_dict(cls, data: dict) -> "ShotTiming":
+        """
+        Build from a profile's "shot_timing" section.
+
+        Raises:
+            ValueError: If a mode, club, field or value is invalid, naming its key
+        """
+        if not isinstance(data, dict):
+            raise ValueError("shot_timing must be an object")
+        unknown = set(data) - {"modes", "clubs"}
+        if unknown:
+            raise ValueError(f"Unknown shot_timing field(s): {', '.join(sorted(unknown))}")
+        return cls(
+            modes=_parse_overrides("modes", data.get("modes", {}), TIMING_MODES),
+            clubs=_parse_overrides("clubs", data.get("clubs", {}), CLUB_NAMES),
+        )
+
+    def to_dict(self) -> dict:
+        """Serialize like the profile section."""
+        return {
+            "modes": {name: dict(values) for name, values in self.modes.items()},
+            "clubs": {name: dict(values) for name, values in self.clubs.items()},
+        }
+
+    def for_mode(self, mode: str) -> Dict[str, float]:
+        """The timing fields a radar mode overrides (empty for none)."""
+        return dict(self.modes.get(mode, {}))
+
+    def for_club(self, club: str) -> Dict[str, float]:
+        """The timing fields a club overrides (empty for none)."""
+        return dict(self.clubs.get(club, {}))
@@ -0,0 +1,92 @@
+"""
+Smash factor windows from the club's loft.
+
+One global window (smash_factor_min/max, 1.1-1.7) is too tight for
+wedges, whose ball speed is barely above the club's, and too loose for
+a driver, which physics caps near 1.5. With the club known, the window
+comes from the collision instead:
+
+    smash <= (1 + COR) / (1 + m_ball / m_head) * cos(delivered loft)
+
+- COR: 0.83, the Rules of Golf limit for a driver face (irons are
+  below it, so this is an upper bound for every club)
+- m_head: the club's typical head mass (see energy.CLUB_HEAD_MASS_KG)
+- delivered loft: the ball is struck along the face normal, which a
+  normal strike presents at about three quarters of the static loft
+  (shaft lean, attack angle)
+
+The window runs from LOW_FRACTION of that bound (a thin or toe strike)
+up to the bound plus a margin for radar error (club_smash_margin), and
+never below 1. For a driver that is about 1.1-1.52; for a sand wedge
+1.0-1.23.
+
+The club "unknown" (or one not in the tables) keeps the global window.
+"""
+
+import math
+from typing import Dict, Optional, Tuple
+
+from .energy import BALL_MASS_KG, CLUB_HEAD_MASS_KG
+
+FACE_COR = 0.83  # Rules of Golf limit (coefficient of restitution)
+DELIVERED_LOFT_FRACTION = 0.75  # Delivered over static loft for a normal strike
+LOW_FRACTION = 0.75  # Lowest smash accepted, as a fraction of the bound
+
+# Typical static loft by club, degrees
+CLUB_LOFT_DEG: Dict[str, float] = {
+    "driver": 10.5,
+    "3-wood": 15.0,
+    "5-wood": 18.0,
+    "7-wood": 21.0,
+    "3-hybrid": 19.0,
+    "5-hybrid": 25.0,
+    "7-hybrid": 31.0,
+    "9-hybrid": 37.0,
+    "2-iron": 18.0,
+    "3-iron": 21.0,
+    "4-iron": 24.0,
+    "5-iron": 27.0,
+    "6-iron": 30.0,
+    "7-iron": 34.0,
+    "8-iron": 38.0,
+    "9-iron": 42.0,
+    "pw": 46.0,
+    "gw": 50.0,
+    "sw": 56.0,
+    "lw": 60.0,
+}
+
+
+def max_smash(club: str) -> Optional[float]:
+    """
+    Highest smash factor the collision allows for a club.
+
+    Args:
+        club: Club name (ClubType value)
+
+    Returns:
+        The bound, or None for a club without a loft and head mass
+    """
+    loft = CLUB_LOFT_DEG.get(club)
+    head_mass = CLUB_HEAD_MASS_KG.get(club)
+    if loft is None or head_mass is None:
+        return None
+    head_on = (1 + FACE_COR) / (1 + BALL_MASS_KG / head_mass)
+    return head_on * math.cos(math.radians(loft * DELIVERED_LOFT_FRACTION))
+
+
+def smash_window(club: str, margin: float) -> Optional[Tuple[float, float]]:
+    """
+    Smash factors accepted for a club.
+
+    Args:
+        club: Club name (ClubType value)
+        margin: Allowance over the bound for radar error (0 = no club windows)
+
+    Returns:
+        (min, max), or None to use the global smash_factor_min/max
+    """
+    bound = max_smash(club)
+    if bound is None or margin <= 0:
+        return None
+    return max(1.0, bound * LOW_FRACTION), bound + margin
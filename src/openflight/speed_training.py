@@ -0,0 +1,341 @@
+"""
+Overspeed training: sets of max-intent swings, with no ball.
+
+Speed programs have you make sets of all-out swings, often with a light,
+a normal and a heavy club, and come back to them several times a week.
+With --speed-training PROTOCOL the server counts those swings from the
+club alone and works through the protocol:
+
+    openflight-server --speed-training light:5,normal:5,heavy:5 --speed-training-rest 60
+
+A protocol is a list of sets, LABEL:REPS each, or SETSxREPS for
+unlabeled sets ("3x5" is three sets of five). Each swing's peak club
+speed is recorded against the current set, the next set starts once a
+set has all its reps, and swings after the last set are ignored.
+
+The compliance summary (GET /api/speed-training) says how closely the
+session followed the protocol:
+
+- a rep is at max intent when it is within INTENT_PCT of the set's best;
+  slower reps were probably eased off (or fatigue has set in)
+- a set was rested when at least the rest interval passed since the
+  last swing of the set before it
+- compliance_pct is the share of planned reps made, at max intent
+
+A swing is a run of readings in the club's direction through impact
+(away from a radar behind the ball) faster than min_speed_mph, ended by
+gap_sec without one. Its speed is the run's peak. The backswing moves
+the other way, and practice swings slower than min_speed_mph are not
+counted.
+"""
+
+import re
+import statistics
+import threading
+import time
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional, Tuple
+
+from .ops243 import Direction, SpeedReading
+
+INTENT_PCT = 95.0  # Reps slower than this % of the set's best aren't max intent
+DEFAULT_REST_SEC = 60.0
+
+
+@dataclass(frozen=True)
+class TrainingSet:
+    """One set of a protocol."""
+
+    label: str
+    reps: int
+
+
+@dataclass(frozen=True)
+class Protocol:
+    """The sets of a speed training session, in order."""
+
+    sets: Tuple[TrainingSet, ...]
+
+    @property
+    def total_reps(self) -> int:
+        """Reps planned over all sets."""
+        return sum(s.reps for s in self.sets)
+
+    def __str__(self) -> str:
+        return ",".join(f"{s.label}:{s.reps}" for s in self.sets)
+
+
+def parse_protocol(text: str) -> Protocol:
+    """
+    Parse a protocol such as "light:5,normal:5,heavy:5" or "3x5".
+
+    Raises:
+        ValueError: If the protocol is empty or a set has no reps
+    """
+    text = text.strip().lower()
+    match = re.fullmatch(r"(\d+)\s*x\s*(\d+)", text)
+    if match:
+        count, reps = int(match.group(1)), int(match.group(2))
+        if count < 1 or reps < 1:
+            raise ValueError("sets and reps must be at least 1")
+        return Protocol(tuple(TrainingSet(f"set {i}", reps) for i in range(1, count + 1)))
+
+    sets = []
+    for item in text.split(","):
+        item = item.strip()
+        if not item:
+            continue
+        label, sep, reps = item.rpartition(":")
+        if not sep or not label.strip():
+            raise ValueError(f"expected LABEL:REPS, got {item!r}")
+        try:
+            count = int(reps)
+        except ValueError:
+            raise ValueError(f"reps must be a whole number, got {item!r}") from None
+        if count < 1:
+            raise ValueError(f"{label.strip()}: reps must be at least 1")
+        sets.append(TrainingSet(label.strip(), count))
+    if not sets:
+        raise ValueError("no sets given")
+    return Protocol(tuple(sets))
+
+
+class SwingDetector:
+    """
+    Finds club-only swings in live readings.
+
+    Example:
+        detector = SwingDetector()
+        speed = detector.update(reading)  # every live reading
+        speed = speed or detector.poll()  # now and then, to end the last swing
+    """
+
+    def __init__(
+        self,
+        direction: Direction = Direction.OUTBOUND,
+        min_speed_mph: float = 50.0,
+        max_speed_mph: float = 180.0,
+        gap_sec: float = 0.3,
+    ):
+        """
+        Initialize swing detector.
+
+        Args:
+            direction: Direction the club moves in through impact
+                (OUTBOUND for a radar behind the ball, INBOUND in front)
+            min_speed_mph: Slower readings are not part of a swing
+            max_speed_mph: Faster readings are noise (no club gets there)
+            gap_sec: Time without swing readings that ends a swing
+        """
+        self.direction = direction
+        self.min_speed_mph = min_speed_mph
+        self.max_speed_mph = max_speed_mph
+        self.gap_sec = gap_sec
+        self._peak: Optional[float] = None
+        self._last: Optional[float] = None
+
+    def update(self, reading: SpeedReading, now: Optional[float] = None) -> Optional[float]:
+        """
+        Feed a live radar reading.
+
+        Args:
+            reading: Reading in any direction
+            now: Reading time (default: time.monotonic())
+
+        Returns:
+            The peak speed of a swing that ended before this reading, if any
+        """
+        now = time.monotonic() if now is None else now
+        ended = self.poll(now)
+        if (
+            reading.direction == self.direction
+            and self.min_speed_mph <= reading.speed <= self.max_speed_mph
+        ):
+            self._peak = max(self._peak or 0.0, reading.speed)
+            self._last = now
+        return ended
+
+    def poll(self, now: Optional[float] = None) -> Optional[float]:
+        """
+        The peak speed of the swing in progress, if gap_sec has passed since it.
+
+        Args:
+            now: Current time (default: time.monotonic())
+        """
+        now = time.monotonic() if now is None else now
+        if self._last is None or now - self._last <= self.gap_sec:
+            return None
+        peak = self._peak
+        self._peak = None
+        self._last = None
+        return peak
+
+
+@dataclass
+class SetRecord:
+    """The swings made in one set."""
+
+    label: str
+    reps_planned: int
+    speeds: List[float] = field(default_factory=list)
+    times: List[float] = field(default_factory=list)
+    rest_sec: Optional[float] = None  # Since the last swing of the set before
+
+    @property
+    def best_mph(self) -> Optional[float]:
+        """Fastest rep."""
+        return max(self.speeds) if self.speeds else None
+
+    @property
+    def complete(self) -> bool:
+        """Whether all planned reps were made."""
+        return len(self.speeds) >= self.reps_planned
+
+    def intent_reps(self, intent_pct: float = INTENT_PCT) -> int:
+        """Reps within intent_pct of the set's best."""
+        best = self.best_mph
+        return sum(1 for s in self.speeds if s >= best * intent_pct / 100) if best else 0
+
+
+class SpeedTrainingSession:
+    """
+    Works through a protocol, one swing at a time.
+
+    Example:
+        session = SpeedTrainingSession(parse_protocol("light:5,heavy:5"), rest_sec=60)
+        event = session.record_swing(112.4)  # None once the protocol is done
+        summary = session.summary()
+    """
+
+    def __init__(self, protocol: Protocol, rest_sec: float = DEFAULT_REST_SEC):
+        """
+        Initialize session.
+
+        Args:
+            protocol: Sets to work through
+            rest_sec: Rest expected between sets
+        """
+        self.protocol = protocol
+        self.rest_sec = rest_sec
+        self._lock = threading.Lock()
+        self._sets: List[SetRecord] = []
+        self.reset()
+
+    def reset(self):
+        """Start the protocol over."""
+        with self._lock:
+            self._sets = [SetRecord(s.label, s.reps) for s in self.protocol.sets]
+
+    def _current(self) -> Optional[int]:
+        for index, record in enumerate(self._sets):
+            if not record.complete:
+                return index
+        return None
+
+    @property
+    def complete(self) -> bool:
+        """Whether every set has all its reps."""
+        with self._lock:
+            return self._current() is None
+
+    def record_swing(self, speed_mph: float, now: Optional[float] = None) -> Optional[Dict]:
+        """
+        Record a swing against the current set.
+
+        Args:
+            speed_mph: Peak club speed
+            now: Swing time (default: time.monotonic())
+
+        Returns:
+            The rep (set, label, rep, reps, speed_mph, set_best_mph, set_complete,
+            complete), or None if the protocol was already done
+        """
+        now = time.monotonic() if now is None else now
+        with self._lock:
+            index = self._current()
+            if index is None:
+                return None
+            record = self._sets[index]
+            if not record.speeds and index > 0:
+                record.rest_sec = now - self._sets[index - 1].times[-1]
+            record.speeds.append(float(speed_mph))
+            record.times.append(now)
+            return {
+                "set": index + 1,
+                "label": record.label,
+                "rep": len(record.speeds),
+                "reps": record.reps_planned,
+                "speed_mph": float(speed_mph),
+                "set_best_mph": record.best_mph,
+                "set_complete": record.complete,
+                "complete": self._current() is None,
+            }
+
+    def _set_summary(self, record: SetRecord) -> Dict[str, Any]:
+        speeds = record.speeds
+        return {
+            "label": record.label,
+            "reps_planned": record.reps_planned,
+            "reps_done": len(speeds),
+            "speeds_mph": list(speeds),
+            "best_mph": record.best_mph,
+            "avg_mph": statistics.mean(speeds) if speeds else None,
+            # Last rep against the first: negative when the set tailed off
+            "change_mph": speeds[-1] - speeds[0] if len(speeds) > 1 else None,
+            "intent_reps": record.intent_reps(),
+            "rest_sec": record.rest_sec,
+            "rested": None if record.rest_sec is None else record.rest_sec >= self.rest_sec,
+        }
+
+    def summary(self) -> Dict[str, Any]:
+        """
+        How closely the session followed the protocol.
+
+        Returns:
+            protocol, rest_sec, complete, sets (one entry per set with its
+            speeds, best, average, change, intent reps and rest), reps_planned,
+            reps_done, intent_pct, sets_rested (of set_breaks), best_mph,
+            best_set and compliance_pct
+        """
+        with self._lock:
+            sets = [self._set_summary(record) for record in self._sets]
+            complete = self._current() is None
+
+        reps_planned = self.protocol.total_reps
+        reps_done = sum(s["reps_done"] for s in sets)
+        intent_reps = sum(s["intent_reps"] for s in sets)
+        rests = [s["rested"] for s in sets if s["rested"] is not None]
+        best = max(sets, key=lambda s: s["best_mph"] or 0.0)
+        return {
+            "protocol": str(self.protocol),
+            "rest_sec": self.rest_sec,
+            "complete": complete,
+            "sets": sets,
+            "reps_planned": reps_planned,
+            "reps_done": reps_done,
+            "intent_pct": 100 * intent_reps / reps_done if reps_done else None,
+            "sets_rested": sum(rests),
+            "set_breaks": len(rests),
+            "best_mph": best["best_mph"],
+            "best_set": best["label"] if best["best_mph"] else None,
+            "compliance_pct": 100 * intent_reps / reps_planned,
+        }
+
+
+def format_summary(summary: Dict[str, Any]) -> List[str]:
+    """Console lines for a summary()."""
+    lines = [f"Speed training: {summary['reps_done']}/{summary['reps_planned']} reps"]
+    for s in summary["sets"]:
+        if not s["reps_done"]:
+            lines.append(f"  {s['label']}: not started")
+            continue
+        rest = ""
+        if s["rest_sec"] is not None:
+            rest = f", rested {s['rest_sec']:.0f}s" + ("" if s["rested"] else " (short)")
+        lines.append(
+            f"  {s['label']}: {s['reps_done']}/{s['reps_planned']} reps, "
+            f"best {s['best_mph']:.1f} mph, avg {s['avg_mph']:.1f} mph, "
+            f"{s['intent_reps']} at max intent{rest}"
+        )
+    lines.append(f"  Compliance: {summary['compliance_pct']:.0f}%")
+    return lines
@@ -0,0 +1,291 @@
+"""
+Status feed for menu bar widgets.
+
+Mac sim setups often keep the server out of sight and want a glance at it
+from the menu bar: is it ready, did it see the last shot, is the radar
+still talking. With --status-socket PATH (a Unix socket, mode 0600) or
+--status-port PORT (TCP, on 127.0.0.1 unless --status-host says otherwise)
+the server writes that as one JSON object per line to every connected
+client:
+
+    $ nc -U /tmp/openflight-status.sock
+    {"schema": 1, "state": "ready", "mode": "streaming", "shots": 12, ...}
+
+Keep-alive: a client gets a line as soon as it connects, another whenever
+the status changes (a shot, arming, the radar going quiet), and the
+current status again every KEEPALIVE_SEC (5 s) if nothing has changed.
+So a widget can hold the connection open, redraw on every line, and
+treat 3 keep-alives without a line (15 s) as the server being gone and
+reconnect. Clients don't send anything (what they send is ignored) and
+can disconnect at any time; a client that stops reading is dropped.
+
+Schema version 1; within a version, fields are only ever added:
+
+    schema        1
+    ts            When the line was written (ISO 8601, local time)
+    state         "ready"     Waiting for a shot
+                  "waiting"   Auto-arming: no ball settled on the mat yet
+                  "disarmed"  Quiet hours or idle (re-arm with POST /api/arm)
+                  "stopped"   Radar released (service paused, or not started)
+    mode          "streaming" or "rolling-buffer"
+    shots         Shots this session
+    club          Selected club (null when stopped)
+    last_shot     {"ball_speed_mph", "carry_yards", "club", "timestamp"} or null
+    connection    {"radar": "ok" | "stale" | "down" | "mock",
+                   "radar_data_age_sec": seconds since the radar sent data, or null}
+
+"stale" means the radar has sent no data for RADAR_STALE_SEC: a wedged
+radar or serial link (only detectable while streaming I/Q).
+"""
+
+import json
+import logging
+import os
+import socket
+import threading
+import time
+from datetime import datetime
+from typing import Callable, List, Optional
+
+from .control import remove_stale_socket
+
+logger = logging.getLogger(__name__)
+
+SCHEMA_VERSION = 1
+KEEPALIVE_SEC = 5.0
+RADAR_STALE_SEC = 3.0  # An I/Q stream sends blocks many times a second
+
+# Fields that change on every line without the status changing
+_VOLATILE_FIELDS = ("ts",)
+
+
+def default_status_socket_path() -> str:
+    """The per-user runtime directory's openflight-status.sock (/tmp if there is none)."""
+    return os.path.join(os.environ.get("XDG_RUNTIME_DIR") or "/tmp", "openflight-status.sock")
+
+
+def radar_health(running: bool, mock: bool, data_age_sec: Optional[float]) -> str:
+    """
+    The connection.radar value.
+
+    Args:
+        running: Whether the monitor is running
+        mock: Whether it's the mock monitor
+        data_age_sec: Seconds since the radar last sent data (None if unknown)
+    """
+    if not running:
+        return "down"
+    if mock:
+        return "mock"
+    if data_age_sec is not None and data_age_sec > RADAR_STALE_SEC:
+        return "stale"
+    return "ok"
+
+
+def last_shot_summary(shot: dict) -> dict:
+    """The last_shot object for a shot as shot_to_dict() gives it."""
+    return {
+        "ball_speed_mph": shot.get("ball_speed_mph"),
+        "carry_yards": shot.get("estimated_carry_yards"),
+        "club": shot.get("club"),
+        "timestamp": shot.get("timestamp"),
+    }
+
+
+class StatusFeed:
+    """
+    Pushes the server's status to menu bar widgets over local sockets.
+
+    The server supplies everything but last_shot through the status
+    callback, polled every poll_sec; last_shot follows the event bus.
+
+    Example:
+        feed = StatusFeed(menu_bar_status)
+        add_event_sink(feed.on_event)
+        feed.listen_unix("/tmp/openflight-status.sock")
+        feed.start()
+        ...
+        feed.stop()
+    """
+
+    def __init__(
+        self,
+        status: Callable[[], dict],
+        keepalive_sec: float = KEEPALIVE_SEC,
+        poll_sec: float = 1.0,
+    ):
+        """
+        Initialize the feed.
+
+        Args:
+            status: Returns state, mode, shots, club and connection
+            keepalive_sec: Resend an unchanged status this often
+            poll_sec: How often status is checked for changes
+        """
+        self._status = status
+        self.keepalive_sec = keepalive_sec
+        self.poll_sec = poll_sec
+        self._last_shot: Optional[dict] = None
+        self._listeners: List[socket.socket] = []
+        self._unix_paths: List[str] = []
+        self._clients: List[socket.socket] = []
+        self._lock = threading.Lock()
+        self._wake = threading.Event()
+        self._stop_event = threading.Event()
+        self._threads: List[threading.Thread] = []
+
+    def on_event(self, event: str, data: dict):
+        """Event sink: follow the last shot, and push arming changes straight away."""
+        with self._lock:
+            if event == "shot" and data.get("shot"):
+                self._last_shot = last_shot_summary(data["shot"])
+            elif event == "mulligan":
+                self._last_shot = None
+        self._wake.set()
+
+    def current(self) -> dict:
+        """The status line as it would be sent now."""
+        with self._lock:
+            last_shot = self._last_shot
+        return {
+            "schema": SCHEMA_VERSION,
+            "ts": datetime.now().isoformat(),
+            **self._status(),
+            "last_shot": last_shot,
+        }
+
+    def listen_unix(self, path: str):
+        """
+        Serve the feed on a Unix socket only the current user can open.
+
+        Raises:
+            OSError: If another server is listening on the path, or it can't be bound
+        """
+        remove_stale_socket(path)
+        sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            sock.bind(path)
+            os.chmod(path, 0o600)
+            sock.listen(4)
+        except OSError:
+            sock.close()
+            raise
+        self._listeners.append(sock)
+        self._unix_paths.append(path)
+
+    def listen_tcp(self, port: int, host: str = "127.0.0.1") -> int:
+        """
+        Serve the feed on a TCP port (localhost unless host says otherwise).
+
+        Returns:
+            The port bound (useful with port 0)
+
+        Raises:
+            OSError: If the port can't be bound
+        """
+        sock = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+        sock.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+        try:
+            sock.bind((host, port))
+            sock.listen(4)
+        except OSError:
+            sock.close()
+            raise
+        self._listeners.append(sock)
+        return sock.getsockname()[1]
+
+    def start(self):
+        """Accept clients and push status on background threads."""
+        self._stop_event.clear()
+        for listener in self._listeners:
+            listener.settimeout(0.5)
+            self._threads.append(
+                threading.Thread(target=self._accept_loop, args=(listener,), daemon=True)
+            )
+        self._threads.append(threading.Thread(target=self._push_loop, daemon=True))
+        for thread in self._threads:
+            thread.start()
+
+    def stop(self, timeout: float = 2.0):
+        """Disconnect clients, stop listening and remove the socket files."""
+        self._stop_event.set()
+        self._wake.set()
+        for thread in self._threads:
+            thread.join(timeout=timeout)
+        self._threads = []
+        with self._lock:
+            clients, self._clients = self._clients, []
+        for sock in clients + self._listeners:
+            sock.close()
+        self._listeners = []
+        for path in self._unix_paths:
+            try:
+                os.unlink(path)
+            except OSError:
+                pass
+        self._unix_paths = []
+
+    @property
+    def client_count(self) -> int:
+        """Number of connected clients."""
+        with self._lock:
+            return len(self._clients)
+
+    def _accept_loop(self, listener: socket.socket):
+        while not self._stop_event.is_set():
+            try:
+                conn, _ = listener.accept()
+            except socket.timeout:
+                continue
+            except OSError:
+                return
+            conn.settimeout(1.0)  # A client that stops reading is dropped, not waited on
+            if not self._send(conn, self._encode(self.current())):
+                conn.close()
+                continue
+            with self._lock:
+                self._clients.append(conn)
+
+    def _push_loop(self):
+        """Send the status on change, and unchanged every keepalive_sec."""
+        last_sent: Optional[dict] = None
+        sent_at = time.monotonic()
+        while not self._stop_event.is_set():
+            if self._wake.wait(self.poll_sec):
+                self._wake.clear()
+            if self._stop_event.is_set():
+                return
+            try:
+                status = self.current()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.warning("Status feed: could not read status: %s", e)
+                continue
+            comparable = {k: v for k, v in status.items() if k not in _VOLATILE_FIELDS}
+            if comparable == last_sent and time.monotonic() - sent_at < self.keepalive_sec:
+                continue
+            last_sent, sent_at = comparable, time.monotonic()
+            self.broadcast(status)
+
+    def broadcast(self, status: dict):
+        """Send a status line to every client, dropping those that are gone."""
+        line = self._encode(status)
+        with self._lock:
+            clients = list(self._clients)
+        gone = [conn for conn in clients if not self._send(conn, line)]
+        if gone:
+            with self._lock:
+                self._clients = [c for c in self._clients if c not in gone]
+            for conn in gone:
+                conn.close()
+
+    @staticmethod
+    def _encode(status: dict) -> bytes:
+        return json.dumps(status).encode("utf-8") + b"\n"
+
+    @staticmethod
+    def _send(conn: socket.socket, line: bytes) -> bool:
+        try:
+            conn.sendall(line)
+        except OSError:
+            return False
+        return True
@@ -13,8 +13,10 @@ from typing import Callable, List, Optional
 
 import numpy as np
 
+from ..clock import get_clock
 from ..ops243 import Direction, IQBlock, SpeedReading
 from ..session_logger import get_session_logger
+from ..tracing import trace_span
 from .cfar import CFAR2DDetector, CFARConfig
 
 
@@ -234,12 +236,13 @@ class StreamingSpeedDetector:
         # Capture I/Q data for logging
         if self._capture_iq and self._iq_buffer is not None:
             self._iq_buffer.append({
-                "timestamp": block.timestamp,
+                "timestamp": get_clock().to_wall(block.timestamp),
                 "i_samples": list(block.i_samples),
                 "q_samples": list(block.q_samples),
             })
 
-        reading = self.processor.process_block(block)
+        with trace_span("process_block", "detect"):
+            reading = self.processor.process_block(block)
         if reading:
             self.readings_emitted += 1
 
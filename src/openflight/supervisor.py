@@ -0,0 +1,175 @@
+"""
+Watchdog for unattended (kiosk) deployments.
+
+openflight-server --supervise runs the server as a child process and
+restarts it when it:
+
+- exits with an error (an unhandled exception, or the radar unplugged),
+- stops answering on its control socket (hung), or
+- stops receiving radar data while streaming I/Q (a wedged serial port;
+  the stream never pauses on its own, see LaunchMonitor.radar_data_age_sec).
+
+Restarts back off (1 s, doubling up to 60 s) so a radar that is missing
+for good doesn't spin the CPU; a child that ran for STABLE_SEC resets the
+backoff. Each restart passes --resume-session with the session the child
+was logging, so shots keep their numbers and the UI keeps the session's
+shots. A clean exit (e.g. openflight-ctl shutdown) ends supervision.
+
+    openflight-server --supervise --mode streaming --port /dev/ttyACM0
+"""
+
+import signal
+import subprocess
+import threading
+import time
+from typing import Callable, List, Optional
+
+from .control import ControlError, call
+
+
+class Supervisor:
+    """
+    Runs a command and restarts it when it dies or its health checks fail.
+
+    Example:
+        supervisor = Supervisor(
+            ["python", "-m", "openflight.server", "--control-socket", path], path
+        )
+        exit_code = supervisor.run()
+    """
+
+    BACKOFF_SEC = 1.0  # Delay before the first restart, doubled each failure
+    MAX_BACKOFF_SEC = 60.0
+    STABLE_SEC = 60.0  # A child that ran this long resets the backoff
+    CHECK_INTERVAL_SEC = 5.0  # Time between health checks
+    STARTUP_GRACE_SEC = 30.0  # No health checks while the child connects the radar
+    MAX_MISSED_CHECKS = 3  # Unanswered health checks in a row that mean it's hung
+    STOP_TIMEOUT_SEC = 10.0  # Wait after SIGINT before killing the child
+
+    def __init__(
+        self,
+        command: List[str],
+        control_socket: str,
+        wedge_timeout_sec: float = 30.0,
+        popen: Callable[[List[str]], subprocess.Popen] = subprocess.Popen,
+        status: Callable[[str], dict] = lambda path: call(path, "status", timeout=5.0),
+    ):
+        """
+        Initialize supervisor.
+
+        Args:
+            command: Server command line (must listen on control_socket)
+            control_socket: Control socket the child serves status on
+            wedge_timeout_sec: Restart after this long without radar data (0 = never)
+            popen: Starts the child (for testing)
+            status: Fetches the child's status (for testing)
+        """
+        self.command = list(command)
+        self.control_socket = control_socket
+        self.wedge_timeout_sec = wedge_timeout_sec
+        self._popen = popen
+        self._status = status
+        self.session_path: Optional[str] = None  # Session the child was last logging
+        self.restarts = 0
+        self._child: Optional[subprocess.Popen] = None
+        self._stop_event = threading.Event()
+
+    def child_command(self) -> List[str]:
+        """The command line for the next start (resuming the last session)."""
+        if self.session_path:
+            return self.command + ["--resume-session", self.session_path]
+        return list(self.command)
+
+    def stop(self):
+        """Stop the child and end supervision (e.g. from a signal handler)."""
+        self._stop_event.set()
+
+    def run(self) -> int:
+        """
+        Supervise until the child exits cleanly or stop() is called.
+
+        Returns:
+            The child's last exit code
+        """
+        backoff = self.BACKOFF_SEC
+        while True:
+            started = time.monotonic()
+            self._child = self._popen(self.child_command())
+            reason = self._watch()
+            if reason:
+                print(f"[SUPERVISOR] Restarting: {reason}")
+                self._stop_child()
+            code = self._child.wait()
+            if self._stop_event.is_set():
+                return code
+            if code == 0 and not reason:
+                print("[SUPERVISOR] Server exited cleanly")
+                return 0
+            if not reason:
+                print(f"[SUPERVISOR] Server exited with code {code}")
+            if time.monotonic() - started >= self.STABLE_SEC:
+                backoff = self.BACKOFF_SEC
+            print(f"[SUPERVISOR] Restarting in {backoff:.0f}s")
+            if self._stop_event.wait(backoff):
+                return code
+            backoff = min(backoff * 2, self.MAX_BACKOFF_SEC)
+            self.restarts += 1
+
+    def _watch(self) -> Optional[str]:
+        """
+        Wait for the child to exit or fail a health check.
+
+        Returns:
+            Why the child should be restarted, or None if it exited (or
+            supervision was stopped)
+        """
+        started = time.monotonic()
+        missed = 0
+        while not self._stop_event.wait(self.CHECK_INTERVAL_SEC):
+            if self._child.poll() is not None:
+                return None
+            if time.monotonic() - started < self.STARTUP_GRACE_SEC:
+                continue
+            try:
+                status = self._status(self.control_socket)
+            except (OSError, ControlError, ValueError):
+                missed += 1
+                if missed >= self.MAX_MISSED_CHECKS:
+                    return f"no answer on {self.control_socket} ({missed} checks)"
+                continue
+            missed = 0
+            self.session_path = status.get("session_path") or self.session_path
+            age = status.get("radar_data_age_sec")
+            if self.wedge_timeout_sec and age is not None and age > self.wedge_timeout_sec:
+                return f"no radar data for {age:.0f}s (serial port wedged?)"
+        self._stop_child()
+        return None
+
+    def _stop_child(self):
+        """Ask the child to stop as Ctrl+C would (closing its session log), then kill it."""
+        if self._child.poll() is not None:
+            return
+        self._child.send_signal(signal.SIGINT)
+        try:
+            self._child.wait(timeout=self.STOP_TIMEOUT_SEC)
+        except subprocess.TimeoutExpired:
+            self._child.kill()
+
+
+def supervise(command: List[str], control_socket: str, wedge_timeout_sec: float) -> int:
+    """
+    Run the server under a Supervisor until it exits cleanly or we're signalled.
+
+    Returns:
+        Exit code for the supervisor process
+    """
+    supervisor = Supervisor(command, control_socket, wedge_timeout_sec=wedge_timeout_sec)
+
+    def on_signal(signum, frame):  # pylint: disable=unused-argument
+        print("[SUPERVISOR] Stopping")
+        supervisor.stop()
+
+    signal.signal(signal.SIGINT, on_signal)
+    signal.signal(signal.SIGTERM, on_signal)
+    print(f"[SUPERVISOR] Supervising: {' '.join(command)}")
+    return supervisor.run()
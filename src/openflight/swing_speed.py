@@ -0,0 +1,181 @@
+"""
+Club-only swing speed mode: swings with no ball.
+
+Shot detection takes the fastest reading of a shot as the ball and looks
+for the club before it, so a swing with no ball is either rejected or
+reported as a slow ball with no club. With --mode swing-speed the server
+uses SwingSpeedMonitor instead, which reports each swing's club head
+speed and nothing else: no ball speed, smash factor or carry, and no
+ball speed, club/ball ratio or smash checks.
+
+A swing is a burst of readings in the direction of the target (as a
+shot is), ended by shot_timeout_sec without one. Its club speed is the
+burst's peak, which must be within min_club_speed_mph to
+max_club_speed_mph; the duration and magnitude checks of shot detection
+still apply, so walking past the radar isn't a swing.
+
+Swings go to swing_callback, not to the shot callback, so they never
+reach a sim or the shot history.
+"""
+
+import statistics
+import time
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Any, Callable, Dict, List, Optional
+
+from .launch_monitor import (
+    ClubType,
+    LaunchMonitor,
+    Shot,
+    _by_speed,
+    _by_time,
+    _reading_entry,
+)
+from .ops243 import SpeedReading
+from .tracing import traced
+
+
+@dataclass
+class Swing:
+    """A club-only swing."""
+
+    club_speed_mph: float
+    timestamp: datetime
+    club: ClubType = ClubType.DRIVER
+    peak_magnitude: Optional[float] = None
+    readings: List[SpeedReading] = field(default_factory=list)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """The swing as sent to the UI and the stream."""
+        return {
+            "club_speed_mph": round(self.club_speed_mph, 1),
+            "club": self.club.value,
+            "timestamp": self.timestamp.isoformat(),
+            "peak_magnitude": self.peak_magnitude,
+            "readings_count": len(self.readings),
+        }
+
+    @property
+    def readings_data(self) -> List[dict]:
+        """Serialized readings for session logging."""
+        return [_reading_entry(r) for r in self.readings]
+
+
+def swing_stats(swings: List[Swing]) -> Dict[str, Any]:
+    """swing_count, avg_swing_speed and max_swing_speed (None with no swings)."""
+    speeds = [s.club_speed_mph for s in swings]
+    return {
+        "swing_count": len(speeds),
+        "avg_swing_speed": statistics.mean(speeds) if speeds else None,
+        "max_swing_speed": max(speeds) if speeds else None,
+    }
+
+
+class SwingSpeedMonitor(LaunchMonitor):
+    """
+    LaunchMonitor that reports club-only swings instead of shots.
+
+    Example:
+        monitor = SwingSpeedMonitor()
+        monitor.connect()
+        monitor.start(swing_callback=lambda swing: print(swing.club_speed_mph))
+    """
+
+    def __init__(self, *args, **kwargs):
+        """Initialize as LaunchMonitor (see LaunchMonitor.__init__)."""
+        super().__init__(*args, **kwargs)
+        self._swings: List[Swing] = []
+        self._swing_callback: Optional[Callable[[Swing], None]] = None
+
+    def start(
+        self,
+        shot_callback: Optional[Callable[[Shot], None]] = None,
+        live_callback: Optional[Callable[[SpeedReading], None]] = None,
+        swing_callback: Optional[Callable[[Swing], None]] = None,
+    ):
+        """
+        Start monitoring for swings.
+
+        Args:
+            shot_callback: Never called (there are no shots)
+            live_callback: Called for each raw speed reading
+            swing_callback: Called when a complete swing is detected
+        """
+        self._swing_callback = swing_callback
+        super().start(shot_callback=shot_callback, live_callback=live_callback)
+
+    @traced("swing", "detect")
+    def _process_shot(self):
+        """Process accumulated readings into a swing."""
+        config = self._config
+        if self._shot_in_window and self._takeaway is not None:
+            self._takeaway.close()
+        self._shot_in_window = False
+
+        readings = sorted((r for r in self._current_readings if self._accept(r)), key=_by_time)
+        self._current_readings = []
+        if len(readings) < config.min_readings_for_shot:
+            print(f"[REJECTED] Only {len(readings)} readings for a swing")
+            return
+
+        duration = _by_time(readings[-1]) - _by_time(readings[0])
+        if duration > self._timing(config, "max_shot_duration_sec"):
+            print(f"[REJECTED] Swing duration {duration * 1000:.0f}ms exceeds max")
+            return
+
+        magnitudes = [r.magnitude for r in readings if r.magnitude]
+        peak_mag = max(magnitudes) if magnitudes else None
+        if not self._use_iq_streaming and peak_mag is not None:
+            if peak_mag < config.min_shot_magnitude:
+                print(f"[REJECTED] Peak magnitude {peak_mag:.0f} too weak for a swing")
+                return
+
+        club_speed = max(readings, key=_by_speed).speed
+        if not config.min_club_speed_mph <= club_speed <= config.max_club_speed_mph:
+            print(
+                f"[REJECTED] Swing speed {club_speed:.1f} mph outside "
+                f"{config.min_club_speed_mph:g}-{config.max_club_speed_mph:g} mph"
+            )
+            return
+
+        swing = Swing(
+            club_speed_mph=club_speed,
+            timestamp=datetime.now(),
+            club=self._current_club,
+            peak_magnitude=peak_mag,
+            readings=readings,
+        )
+        self._swings.append(swing)
+        self._last_shot_time = time.monotonic()
+        print(f"[SWING] Club: {club_speed:.1f} mph ({swing.club.value})")
+        if self._swing_callback:
+            self._swing_callback(swing)
+
+    def analyze_readings(self, readings: List[SpeedReading]) -> Optional[Swing]:
+        """
+        Run swing detection over a recorded set of readings.
+
+        Args:
+            readings: Readings from one swing
+
+        Returns:
+            The detected swing, or None if the readings are rejected
+        """
+        count = len(self._swings)
+        self._current_readings = list(readings)
+        self._process_shot()
+        return self._swings[-1] if len(self._swings) > count else None
+
+    def get_swings(self) -> List[Swing]:
+        """Get all detected swings."""
+        return self._swings.copy()
+
+    def get_session_stats(self) -> dict:
+        """Session statistics: no shots, and the swing count and speeds."""
+        return {**super().get_session_stats(), **swing_stats(self._swings)}
+
+    def clear_session(self):
+        """Clear all recorded swings."""
+        super().clear_session()
+        self._swings = []
@@ -0,0 +1,115 @@
+"""
+Takeaway-keyed arming window.
+
+Most false shots (someone walking past, a practice swing's follow-through
+seen from the wrong side) don't start with a backswing; real shots do. The
+club moving away from the target shows up as readings in the opposite
+direction to ball flight, so a short run of them marks a takeaway. From
+then until takeaway_window_sec later (long enough for the rest of the
+backswing, the downswing and impact), a LaunchMonitor with the window
+enabled uses relaxed thresholds; the rest of the time it keeps the
+configured, strict ones:
+
+    strict --backswing seen--> relaxed --window over or shot--> strict
+
+Only the magnitude thresholds (min_magnitude, min_shot_magnitude) are
+relaxed, multiplied by takeaway_threshold_scale, so they apply with the
+radar's internal processing; in I/Q streaming mode CFAR decides signal
+strength. A side mount has no single backswing direction, so the window
+never opens there.
+"""
+
+from dataclasses import replace
+from typing import Any, Optional
+
+from .frames import Direction, SpeedReading
+
+
+class TakeawayWindow:
+    """
+    Opens a window of relaxed thresholds when a backswing is seen.
+
+    Example:
+        window = TakeawayWindow(Direction.INBOUND, window_sec=2.0)
+        window.update(reading, now)        # every reading, before filtering
+        if window.is_open(now):
+            config = relaxed_config(config)
+    """
+
+    MAX_GAP_SEC = 0.4  # Longer gaps between backswing readings start a new run
+
+    def __init__(
+        self,
+        backswing_direction: Optional[Direction],
+        window_sec: float,
+        min_readings: int = 2,
+    ):
+        """
+        Initialize window.
+
+        Args:
+            backswing_direction: Direction the club moves in on the backswing
+                (None: no window, e.g. for a side mount)
+            window_sec: How long the window stays open after the takeaway
+            min_readings: Backswing readings in a row that make a takeaway
+        """
+        self.backswing_direction = backswing_direction
+        self.window_sec = window_sec
+        self.min_readings = min_readings
+        self._run = 0
+        self._last_backswing: Optional[float] = None
+        self._opened_at: Optional[float] = None
+
+    def update(self, reading: SpeedReading, now: float) -> bool:
+        """
+        Add a reading (of any direction).
+
+        Returns:
+            True if this reading opened the window
+        """
+        if reading.direction != self.backswing_direction:
+            return False
+        if self._last_backswing is None or now - self._last_backswing > self.MAX_GAP_SEC:
+            self._run = 0
+        self._run += 1
+        self._last_backswing = now
+        if self._run >= self.min_readings and not self.is_open(now):
+            self._opened_at = now
+            return True
+        return False
+
+    def is_open(self, now: float) -> bool:
+        """Whether relaxed thresholds apply at this time."""
+        return self._opened_at is not None and now - self._opened_at <= self.window_sec
+
+    def close(self):
+        """Close the window (after a shot), back to strict thresholds."""
+        self._opened_at = None
+        self._run = 0
+        self._last_backswing = None
+
+
+def relaxed_config(config: Any) -> Any:
+    """
+    The config with its magnitude thresholds scaled for an open window.
+
+    Args:
+        config: MonitorConfig with takeaway_threshold_scale set
+
+    Returns:
+        New MonitorConfig (config is unchanged)
+    """
+    scale = config.takeaway_threshold_scale
+    return replace(
+        config,
+        min_magnitude=config.min_magnitude * scale,
+        min_shot_magnitude=config.min_shot_magnitude * scale,
+    )
+
+
+def backswing_direction(ball_directions) -> Optional[Direction]:
+    """The backswing direction for a mount's ball directions (None if ambiguous)."""
+    if len(ball_directions) != 1:
+        return None
+    (ball,) = ball_directions
+    return Direction.INBOUND if ball == Direction.OUTBOUND else Direction.OUTBOUND
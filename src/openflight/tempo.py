@@ -0,0 +1,285 @@
+"""
+Swing tempo: audio cues and measurement.
+
+Tempo is backswing time over downswing time; most good swings are close
+to 3:1 whatever their speed. With --tempo 3:1 the backswing of every shot
+is found in the readings just before impact and its tempo is compared to
+the target:
+
+- Takeaway: the first reading of the club moving away from the target
+  (toward a radar behind the ball), in the run of such readings that ends
+  at the top
+- Top: the last of those readings before impact
+- Impact: the ball reading of the shot
+
+The radar can't see the club below its minimum speed (10 mph with the
+radar's internal processing, ~35 mph in I/Q streaming mode), so the very
+start of the takeaway is missed and measured backswings run short. Use
+--no-iq-streaming for tempo work, and compare tempos with each other
+rather than with a camera-based trainer.
+
+With --tempo-cues, a metronome plays three tones per swing (takeaway, top,
+impact) spaced at the target ratio, Garmin-style: swing along with them.
+Tones need aplay (ALSA) or afplay (macOS).
+"""
+
+import logging
+import math
+import shutil
+import struct
+import subprocess
+import tempfile
+import threading
+import time
+import wave
+from collections import deque
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Deque, Dict, Optional
+
+from .frames import Direction, SpeedReading
+
+logger = logging.getLogger(__name__)
+
+# Tempo within this share of the target counts as on target
+TEMPO_TOLERANCE = 0.10
+
+# Backswing of the 21/7 preset (frames at 30 fps) of Tour Tempo and
+# Garmin's tempo training; 18/6 is 0.6 s, 24/8 is 0.8 s
+DEFAULT_BACKSWING_SEC = 0.7
+
+
+def parse_ratio(text: str) -> float:
+    """
+    Parse a tempo ratio such as "3:1", "2:1" or "3".
+
+    Raises:
+        ValueError: If it isn't a positive ratio
+    """
+    back, _, down = text.partition(":")
+    try:
+        ratio = float(back) / float(down or 1)
+    except (ValueError, ZeroDivisionError):
+        raise ValueError(f"not a tempo ratio: {text!r} (e.g. 3:1)") from None
+    if not math.isfinite(ratio) or ratio <= 0:
+        raise ValueError(f"not a tempo ratio: {text!r} (e.g. 3:1)")
+    return ratio
+
+
+@dataclass
+class SwingTempo:
+    """Measured backswing and downswing times of one swing."""
+
+    backswing_sec: float
+    downswing_sec: float
+
+    @property
+    def ratio(self) -> float:
+        """Backswing time over downswing time (3.0 for 3:1)."""
+        return self.backswing_sec / self.downswing_sec
+
+
+class TempoMeter:
+    """
+    Finds the backswing in recent readings and measures tempo at impact.
+
+    Fed every reading, including those the shot detector filters out:
+    the backswing moves the other way from the ball.
+
+    Example:
+        meter = TempoMeter()
+        meter.update(reading)          # every live reading
+        tempo = meter.measure(ball_reading.timestamp)
+    """
+
+    HISTORY_SEC = 3.0  # Longest swing looked back over
+    MAX_GAP_SEC = 0.4  # Longer gaps between backswing readings end the run
+    MAX_DOWNSWING_SEC = 1.0  # The top can't be longer ago than this
+    MIN_BACKSWING_READINGS = 2
+
+    def __init__(self, backswing_direction: Direction = Direction.INBOUND):
+        """
+        Initialize tempo meter.
+
+        Args:
+            backswing_direction: Direction the club moves in on the backswing
+                (INBOUND for a radar behind the ball, OUTBOUND in front)
+        """
+        self.backswing_direction = backswing_direction
+        self._readings: Deque[SpeedReading] = deque()
+
+    def update(self, reading: SpeedReading):
+        """Add a live reading."""
+        if reading.timestamp is None:
+            return
+        self._readings.append(reading)
+        while self._readings and self._readings[0].timestamp < reading.timestamp - self.HISTORY_SEC:
+            self._readings.popleft()
+
+    def measure(self, impact_time: float) -> Optional[SwingTempo]:
+        """
+        Tempo of the swing that hit the ball at impact_time.
+
+        Args:
+            impact_time: Time of the ball reading (time.monotonic())
+
+        Returns:
+            The tempo, or None if no backswing was seen
+        """
+        backswing = [
+            r.timestamp
+            for r in self._readings
+            if r.direction == self.backswing_direction
+            and impact_time - self.HISTORY_SEC <= r.timestamp < impact_time
+        ]
+        if not backswing:
+            return None
+        top = backswing[-1]
+        if impact_time - top > self.MAX_DOWNSWING_SEC:
+            return None
+
+        takeaway = top
+        count = 1
+        for t in reversed(backswing[:-1]):
+            if takeaway - t > self.MAX_GAP_SEC:
+                break
+            takeaway = t
+            count += 1
+        if count < self.MIN_BACKSWING_READINGS or top == takeaway:
+            return None
+        return SwingTempo(backswing_sec=top - takeaway, downswing_sec=impact_time - top)
+
+
+def _tone_file(frequency: int, duration_sec: float) -> Path:
+    """A WAV file of a short sine tone (written once, then reused)."""
+    path = Path(tempfile.gettempdir()) / f"openflight_tone_{frequency}_{duration_sec}.wav"
+    if path.exists():
+        return path
+    rate = 22050
+    frames = b"".join(
+        struct.pack("<h", int(12000 * math.sin(2 * math.pi * frequency * i / rate)))
+        for i in range(int(rate * duration_sec))
+    )
+    with wave.open(str(path), "wb") as f:
+        f.setnchannels(1)
+        f.setsampwidth(2)
+        f.setframerate(rate)
+        f.writeframes(frames)
+    return path
+
+
+def play_tone(frequency: int = 880, duration_sec: float = 0.08) -> bool:
+    """
+    Play a short tone with aplay or afplay, without waiting for it to finish.
+
+    Returns:
+        False if no audio player is installed
+    """
+    command = shutil.which("aplay") or shutil.which("afplay")
+    if not command:
+        return False
+    args = [command, "-q"] if Path(command).name == "aplay" else [command]
+    try:
+        subprocess.Popen(  # pylint: disable=consider-using-with
+            args + [str(_tone_file(frequency, duration_sec))],
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+        )
+    except OSError as e:
+        logger.warning("Could not run %s: %s", command, e)
+        return False
+    return True
+
+
+class TempoCues:
+    """
+    Metronome that plays takeaway, top and impact tones at a tempo.
+
+    Example:
+        cues = TempoCues(ratio=3.0, backswing_sec=0.7)
+        cues.start()
+    """
+
+    # Tones: low at takeaway and top, high at impact
+    TAKEAWAY_HZ = 660
+    TOP_HZ = 660
+    IMPACT_HZ = 990
+
+    def __init__(
+        self,
+        ratio: float = 3.0,
+        backswing_sec: float = DEFAULT_BACKSWING_SEC,
+        pause_sec: float = 4.0,
+        play=play_tone,
+    ):
+        """
+        Initialize metronome.
+
+        Args:
+            ratio: Target backswing:downswing ratio
+            backswing_sec: Time from takeaway tone to top tone
+            pause_sec: Silence between swings (time to set up)
+            play: Tone player (for tests)
+        """
+        self.ratio = ratio
+        self.backswing_sec = backswing_sec
+        self.pause_sec = pause_sec
+        self._play = play
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def downswing_sec(self) -> float:
+        """Time from top tone to impact tone."""
+        return self.backswing_sec / self.ratio
+
+    def start(self):
+        """Start playing cues in a background thread."""
+        if self._thread and self._thread.is_alive():
+            return
+        if not self._play(self.TAKEAWAY_HZ, 0.01):
+            logger.warning("No audio player (aplay/afplay) found, tempo cues are silent")
+        self._stop.clear()
+        self._thread = threading.Thread(target=self._loop, daemon=True)
+        self._thread.start()
+
+    def stop(self):
+        """Stop the metronome."""
+        self._stop.set()
+        if self._thread:
+            self._thread.join(timeout=1.0)
+            self._thread = None
+
+    def play_swing(self):
+        """Play one swing's three tones (blocks for the length of the swing)."""
+        start = time.monotonic()
+        for offset, frequency in (
+            (0.0, self.TAKEAWAY_HZ),
+            (self.backswing_sec, self.TOP_HZ),
+            (self.backswing_sec + self.downswing_sec, self.IMPACT_HZ),
+        ):
+            # Wait against the start time so the tones don't drift
+            if self._stop.wait(max(0.0, start + offset - time.monotonic())):
+                return
+            self._play(frequency, 0.08)
+
+    def _loop(self):
+        while not self._stop.is_set():
+            self.play_swing()
+            self._stop.wait(self.pause_sec)
+
+
+def compare_tempo(tempo: SwingTempo, target_ratio: float) -> Dict[str, float]:
+    """
+    Tempo compared to a target ratio.
+
+    Returns:
+        ratio, error_pct (signed: negative = quicker backswing than the
+        target) and on_target (within TEMPO_TOLERANCE)
+    """
+    error = (tempo.ratio - target_ratio) / target_ratio
+    return {
+        "ratio": round(tempo.ratio, 2),
+        "error_pct": round(error * 100, 1),
+        "on_target": abs(error) <= TEMPO_TOLERANCE,
+    }
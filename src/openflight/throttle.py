@@ -0,0 +1,79 @@
+"""
+Per-subscriber rate limiting for live reading streams.
+
+The radar can report a hundred or more readings a second. Broadcasting all
+of them to every UI and /api/stream client lets one slow client (a phone on
+weak Wi-Fi) back up the server, so each subscriber gets its own limit on
+readings. Shot and state events are never limited.
+"""
+
+import time
+from typing import Optional
+
+DEFAULT_READING_HZ = 20.0  # Plenty for a live speed readout
+
+
+class RateLimiter:
+    """
+    Lets at most max_hz events through per second, dropping the rest.
+
+    Example:
+        limiter = RateLimiter(20)
+        if limiter.allow():
+            send(reading)
+    """
+
+    def __init__(self, max_hz: float = DEFAULT_READING_HZ):
+        """
+        Initialize rate limiter.
+
+        Args:
+            max_hz: Maximum events per second (0 = unlimited)
+
+        Raises:
+            ValueError: If max_hz is negative
+        """
+        if max_hz < 0:
+            raise ValueError("max_hz must be 0 (unlimited) or positive")
+        self.max_hz = max_hz
+        self._interval = 1.0 / max_hz if max_hz else 0.0
+        self._next_allowed = 0.0
+        self.dropped = 0
+
+    def allow(self, now: Optional[float] = None) -> bool:
+        """True if an event may be sent now (and counts it as sent)."""
+        if not self._interval:
+            return True
+        now = time.monotonic() if now is None else now
+        if now < self._next_allowed:
+            self.dropped += 1
+            return False
+        # Keep to the schedule while events are steady, so the average rate
+        # holds even when they don't line up with the interval; restart it
+        # after a pause
+        if now - self._next_allowed < self._interval:
+            self._next_allowed += self._interval
+        else:
+            self._next_allowed = now + self._interval
+        return True
+
+
+def parse_reading_hz(value: Optional[str], default: float = DEFAULT_READING_HZ) -> float:
+    """
+    Parse a per-connection reading rate query parameter.
+
+    Args:
+        value: e.g. "20", "0" (full fidelity), or None for the default
+
+    Raises:
+        ValueError: If the value is not a non-negative number
+    """
+    if value is None or value == "":
+        return default
+    try:
+        hz = float(value)
+    except ValueError:
+        raise ValueError(f"reading rate must be a number, got {value!r}") from None
+    if hz < 0:
+        raise ValueError("reading rate must be 0 (unlimited) or positive")
+    return hz
@@ -0,0 +1,345 @@
+"""
+Clock synchronization with companion devices.
+
+A second Pi running the camera can send launch angles to the monitor
+(POST /api/launch_angle), but its clock isn't the monitor's: even with
+NTP the two are tens of milliseconds apart, and time.monotonic() on two
+machines has nothing in common at all. Matching a launch angle to a shot
+needs the companion's timestamps in the monitor's time.
+
+The monitor answers a small UDP exchange (NTP's offset calculation,
+without the rest of NTP):
+
+    openflight-server --time-sync             # UDP port 9873
+
+The companion sends its send time t0; the monitor replies with when it
+received (t1) and replied (t2), and the companion notes when the reply
+arrived (t3). Then
+
+    offset = ((t1 - t0) + (t2 - t3)) / 2     # monitor - companion
+    delay  = (t3 - t0) - (t2 - t1)           # network round trip
+
+Of several exchanges, the one with the least delay is kept, as NTP does:
+it was least held up on the way, so its offset is the most accurate. All
+times are time.monotonic(), so neither side's system clock stepping
+matters. On the companion:
+
+    offset = measure_offset("monitor.local")
+    requests.post(url, json={..., "time": t, "clock_offset_sec": offset.offset_sec})
+
+or, to check a link, `openflight-timesync monitor.local`. Offsets drift
+slowly (clock crystals differ by a few ppm), so re-measure every few
+minutes.
+"""
+
+import argparse
+import json
+import logging
+import socket
+import sys
+import threading
+import time
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Callable, Deque, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_TIME_SYNC_PORT = 9873
+DEFAULT_SAMPLES = 8
+DEFAULT_MATCH_WINDOW_SEC = 0.5  # How far from the shot a companion event may be
+DEFAULT_MATCH_WAIT_SEC = 0.5  # How long a shot waits for a companion event still on its way
+
+_MAX_PACKET = 512
+
+
+@dataclass
+class ClockOffset:
+    """A companion's clock relative to the monitor's."""
+
+    offset_sec: float  # Monitor time - companion time
+    delay_sec: float  # Round trip of the exchange it came from
+
+    def to_monitor(self, companion_time: float) -> float:
+        """A companion time.monotonic() value in the monitor's time.monotonic()."""
+        return companion_time + self.offset_sec
+
+
+def offset_from_times(t0: float, t1: float, t2: float, t3: float) -> ClockOffset:
+    """
+    The offset and delay from one exchange.
+
+    Args:
+        t0: Companion's send time
+        t1: Monitor's receive time
+        t2: Monitor's reply time
+        t3: Companion's receive time
+    """
+    return ClockOffset(
+        offset_sec=((t1 - t0) + (t2 - t3)) / 2,
+        delay_sec=(t3 - t0) - (t2 - t1),
+    )
+
+
+class TimeSyncResponder:
+    """
+    UDP responder the companions measure their offset against.
+
+    Example:
+        responder = TimeSyncResponder(port=9873)
+        responder.start()
+        ...
+        responder.stop()
+    """
+
+    RECV_TIMEOUT_SEC = 0.5  # How often the loop checks for stop()
+
+    def __init__(
+        self,
+        host: str = "0.0.0.0",
+        port: int = DEFAULT_TIME_SYNC_PORT,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        """
+        Initialize the responder.
+
+        Args:
+            host: Address to listen on
+            port: UDP port (0 for any free port)
+            clock: Time source (for tests)
+        """
+        self.host = host
+        self.port = port
+        self._clock = clock
+        self._sock: Optional[socket.socket] = None
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    @property
+    def address(self) -> Tuple[str, int]:
+        """The bound (host, port), once started."""
+        return self._sock.getsockname() if self._sock else (self.host, self.port)
+
+    def start(self):
+        """
+        Bind and answer requests on a background thread.
+
+        Raises:
+            OSError: If the port can't be bound
+        """
+        if self._thread and self._thread.is_alive():
+            return
+        sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+        try:
+            sock.bind((self.host, self.port))
+        except OSError:
+            sock.close()
+            raise
+        sock.settimeout(self.RECV_TIMEOUT_SEC)
+        self._sock = sock
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._serve, daemon=True)
+        self._thread.start()
+
+    def stop(self, timeout: float = 2.0):
+        """Stop answering."""
+        self._stop_event.set()
+        if self._thread:
+            self._thread.join(timeout=timeout)
+            self._thread = None
+        if self._sock:
+            self._sock.close()
+            self._sock = None
+
+    def _serve(self):
+        while not self._stop_event.is_set():
+            try:
+                packet, sender = self._sock.recvfrom(_MAX_PACKET)
+            except socket.timeout:
+                continue
+            except OSError as e:
+                if not self._stop_event.is_set():
+                    logger.warning("Time sync receive failed: %s", e)
+                continue
+            received = self._clock()
+            try:
+                request = json.loads(packet)
+                t0 = float(request["t0"])
+            except (ValueError, TypeError, KeyError):
+                continue
+            reply = {"t0": t0, "t1": received, "t2": self._clock()}
+            try:
+                self._sock.sendto(json.dumps(reply).encode("utf-8"), sender)
+            except OSError as e:
+                logger.debug("Time sync reply to %s failed: %s", sender, e)
+
+
+def measure_offset(
+    host: str,
+    port: int = DEFAULT_TIME_SYNC_PORT,
+    samples: int = DEFAULT_SAMPLES,
+    timeout: float = 0.5,
+    clock: Callable[[], float] = time.monotonic,
+) -> ClockOffset:
+    """
+    Measure this machine's offset to a monitor's --time-sync responder.
+
+    Args:
+        host: Monitor's address
+        port: Its time sync port
+        samples: Exchanges to make (the least delayed one is used)
+        timeout: How long to wait for each reply
+
+    Returns:
+        ClockOffset; to_monitor() converts this machine's time.monotonic()
+
+    Raises:
+        TimeoutError: If the monitor never replied
+        OSError: If the monitor can't be reached
+    """
+    results: List[ClockOffset] = []
+    with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as sock:
+        sock.settimeout(timeout)
+        sock.connect((host, port))
+        for _ in range(samples):
+            t0 = clock()
+            sock.send(json.dumps({"t0": t0}).encode("utf-8"))
+            try:
+                reply = _reply_to(sock, t0)
+            except socket.timeout:
+                continue
+            if reply is not None:
+                results.append(offset_from_times(t0, reply["t1"], reply["t2"], clock()))
+    if not results:
+        raise TimeoutError(f"No time sync reply from {host}:{port}")
+    return min(results, key=lambda r: r.delay_sec)
+
+
+def _reply_to(sock: socket.socket, t0: float) -> Optional[dict]:
+    """The reply to the request sent at t0, skipping late replies to earlier ones."""
+    while True:
+        try:
+            reply = json.loads(sock.recv(_MAX_PACKET))
+            if reply["t0"] == t0:
+                return {"t1": float(reply["t1"]), "t2": float(reply["t2"])}
+        except (ValueError, TypeError, KeyError):
+            return None
+
+
+class CompanionEvents:
+    """
+    Recent companion events (in monitor time) for matching to shots.
+
+    Events are added from the API thread and looked up from the shot
+    callback, which can wait briefly for one still on its way: a camera
+    works the angle out from frames after impact, so it often reports
+    after the radar.
+
+    Example:
+        events = CompanionEvents()
+        events.add(offset.to_monitor(t), {"vertical": 12.5, ...})
+        event = events.wait_for(impact_time)
+    """
+
+    def __init__(self, maxlen: int = 32):
+        """
+        Initialize the event buffer.
+
+        Args:
+            maxlen: Events kept (older ones are dropped)
+        """
+        self._events: Deque[Tuple[float, Any]] = deque(maxlen=maxlen)
+        self._condition = threading.Condition()
+        self._seen = False
+
+    @property
+    def active(self) -> bool:
+        """Whether a companion has sent events (so shots are worth waiting for)."""
+        with self._condition:
+            return self._seen
+
+    def add(self, monitor_time: float, data: Any):
+        """Add an event that happened at monitor_time (time.monotonic())."""
+        with self._condition:
+            self._events.append((monitor_time, data))
+            self._seen = True
+            self._condition.notify_all()
+
+    def nearest(
+        self, at: float, window_sec: float = DEFAULT_MATCH_WINDOW_SEC
+    ) -> Optional[Tuple[float, Any]]:
+        """
+        The event closest to a time, taking it out of the buffer.
+
+        Returns:
+            (monitor_time, data), or None if none is within window_sec
+        """
+        with self._condition:
+            return self._take_nearest(at, window_sec)
+
+    def wait_for(
+        self,
+        at: float,
+        window_sec: float = DEFAULT_MATCH_WINDOW_SEC,
+        timeout: float = DEFAULT_MATCH_WAIT_SEC,
+    ) -> Optional[Tuple[float, Any]]:
+        """Like nearest(), but waits up to timeout for a matching event to arrive."""
+        deadline = time.monotonic() + timeout
+        with self._condition:
+            while True:
+                match = self._take_nearest(at, window_sec)
+                remaining = deadline - time.monotonic()
+                if match is not None or remaining <= 0:
+                    return match
+                self._condition.wait(remaining)
+
+    def _take_nearest(self, at: float, window_sec: float) -> Optional[Tuple[float, Any]]:
+        near = [e for e in self._events if abs(e[0] - at) <= window_sec]
+        if not near:
+            return None
+        match = min(near, key=lambda e: abs(e[0] - at))
+        self._events.remove(match)
+        return match
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """CLI entry point: measure this machine's clock offset to a monitor."""
+    parser = argparse.ArgumentParser(
+        description="Measure the clock offset to an openflight-server --time-sync"
+    )
+    parser.add_argument("host", help="Monitor's address")
+    parser.add_argument(
+        "--port",
+        type=int,
+        default=DEFAULT_TIME_SYNC_PORT,
+        help=f"Monitor's time sync port (default: {DEFAULT_TIME_SYNC_PORT})",
+    )
+    parser.add_argument(
+        "--samples",
+        type=int,
+        default=DEFAULT_SAMPLES,
+        help=f"Exchanges to make (default: {DEFAULT_SAMPLES})",
+    )
+    parser.add_argument("--json", action="store_true", help="Print the result as JSON")
+    args = parser.parse_args(argv)
+    if args.samples < 1:
+        parser.error("--samples must be at least 1")
+
+    try:
+        offset = measure_offset(args.host, args.port, args.samples)
+    except OSError as e:  # TimeoutError included
+        print(f"Error: {e}")
+        return 1
+
+    if args.json:
+        print(json.dumps({"offset_sec": offset.offset_sec, "delay_sec": offset.delay_sec}))
+    else:
+        print(
+            f"Offset to {args.host}: {offset.offset_sec * 1000:+.2f} ms "
+            f"(round trip {offset.delay_sec * 1000:.2f} ms)"
+        )
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,196 @@
+"""
+Pipeline timing trace in Chrome trace format.
+
+With --trace FILE the server writes a span for each stage a shot goes
+through, so the latency on slow hardware can be looked at in
+chrome://tracing or https://ui.perfetto.dev:
+
+- read: bytes from the radar (serial, network bridge)
+- parse: decoding radar lines into frames
+- detect: I/Q processing and turning a shot's readings into a Shot
+- deliver: the server handling the shot, sim and UI output, webhooks
+
+Spans are "complete" events (ph "X") timed by time.monotonic(), in
+microseconds, on the thread they ran on; each thread is named in the
+trace the first time it records a span. Events are written as they
+happen into a JSON array, so a trace cut short by a crash still opens
+(the format allows the closing "]" to be missing).
+
+Reading the radar records a span per line, which is a lot of events:
+max_events caps the file, after which spans are counted but not
+written.
+"""
+
+import json
+import logging
+import os
+import threading
+import time
+from contextlib import contextmanager, nullcontext
+from functools import wraps
+from typing import Any, Callable, ContextManager, Iterator, Optional, TextIO
+
+logger = logging.getLogger(__name__)
+
+CATEGORIES = ("read", "parse", "detect", "deliver")
+DEFAULT_MAX_EVENTS = 1_000_000
+
+
+def _micros(monotonic_time: float) -> float:
+    """Trace timestamp (microseconds) for a time.monotonic() time."""
+    return round(monotonic_time * 1_000_000, 1)
+
+
+class Tracer:
+    """Writes trace events to a file as they happen."""
+
+    def __init__(self, path: str, max_events: int = DEFAULT_MAX_EVENTS):
+        """
+        Args:
+            path: Trace file to write (overwritten)
+            max_events: Most events to write; later ones are dropped
+        """
+        self.path = path
+        self.max_events = max_events
+        self.events = 0
+        self.dropped = 0
+        self._pid = os.getpid()
+        self._threads: set = set()
+        self._lock = threading.Lock()
+        self._file: Optional[TextIO] = open(path, "w", encoding="utf-8")
+        self._file.write("[\n")
+        self._write(
+            {"ph": "M", "name": "process_name", "pid": self._pid, "args": {"name": "openflight"}}
+        )
+
+    def _write(self, event: dict):
+        """Append one event (lock held)."""
+        if self._file is None:
+            return
+        if self.events >= self.max_events:
+            self.dropped += 1
+            return
+        self._file.write(("" if self.events == 0 else ",\n") + json.dumps(event))
+        self.events += 1
+
+    def _record(self, event: dict):
+        """Append an event from the calling thread, naming the thread first."""
+        tid = threading.get_ident()
+        event.update(pid=self._pid, tid=tid)
+        with self._lock:
+            if tid not in self._threads:
+                self._threads.add(tid)
+                self._write(
+                    {
+                        "ph": "M",
+                        "name": "thread_name",
+                        "pid": self._pid,
+                        "tid": tid,
+                        "args": {"name": threading.current_thread().name},
+                    }
+                )
+            self._write(event)
+
+    def complete(self, name: str, cat: str, start: float, end: float, **args: Any):
+        """
+        Record a span that already happened on the calling thread.
+
+        Args:
+            name: Span name
+            cat: Pipeline stage (see CATEGORIES)
+            start: time.monotonic() when it started
+            end: time.monotonic() when it ended
+            **args: Details shown with the span
+        """
+        event = {
+            "ph": "X",
+            "name": name,
+            "cat": cat,
+            "ts": _micros(start),
+            "dur": round((end - start) * 1_000_000, 1),
+        }
+        if args:
+            event["args"] = args
+        self._record(event)
+
+    @contextmanager
+    def span(self, name: str, cat: str, **args: Any) -> Iterator[dict]:
+        """
+        Record the time spent in a with block.
+
+        Yields the span's args, so details only known inside the block
+        can be added to it.
+        """
+        start = time.monotonic()
+        try:
+            yield args
+        finally:
+            self.complete(name, cat, start, time.monotonic(), **args)
+
+    def close(self):
+        """Finish the JSON array and close the file."""
+        with self._lock:
+            if self._file is None:
+                return
+            self._file.write("\n]\n")
+            self._file.close()
+            self._file = None
+        if self.dropped:
+            logger.warning(
+                "Trace %s: %d events over the %d limit were dropped",
+                self.path,
+                self.dropped,
+                self.max_events,
+            )
+
+
+# Global tracer instance
+_tracer: Optional[Tracer] = None
+
+
+def get_tracer() -> Optional[Tracer]:
+    """Get the global tracer (None when tracing is off)."""
+    return _tracer
+
+
+def init_tracer(path: Optional[str], max_events: int = DEFAULT_MAX_EVENTS) -> Optional[Tracer]:
+    """
+    Start writing a trace, replacing any earlier one.
+
+    Args:
+        path: Trace file, or None to turn tracing off
+
+    Returns:
+        Tracer instance, or None if path is None
+    """
+    global _tracer  # pylint: disable=global-statement
+
+    if _tracer:
+        _tracer.close()
+        _tracer = None
+
+    if path:
+        _tracer = Tracer(path, max_events=max_events)
+    return _tracer
+
+
+def trace_span(name: str, cat: str, **args: Any) -> ContextManager[dict]:
+    """Tracer.span on the global tracer; does nothing when tracing is off."""
+    tracer = _tracer
+    if tracer is None:
+        return nullcontext(args)
+    return tracer.span(name, cat, **args)
+
+
+def traced(name: str, cat: str) -> Callable:
+    """Decorator recording a span for each call of the function."""
+
+    def decorator(func: Callable) -> Callable:
+        @wraps(func)
+        def wrapper(*args, **kwargs):
+            with trace_span(name, cat):
+                return func(*args, **kwargs)
+
+        return wrapper
+
+    return decorator
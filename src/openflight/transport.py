@@ -0,0 +1,73 @@
+"""
+What a radar's serial link runs over.
+
+The radar usually plugs into a USB port of the machine running the
+monitor. It can also sit on a Wi-Fi microcontroller or a Pi Zero next to
+the mat, its serial port bridged to TCP by ser2net or ESP-Link, while the
+monitor runs on the sim PC or a NAS. A --port that is a URL opens such a
+bridge instead of a local device:
+
+    tcp://mat-radar.local:2000      raw TCP (ser2net "raw", ESP-Link's port 23)
+    rfc2217://mat-radar.local:2217  RFC 2217 (ser2net "telnet"), which also
+                                    passes baud rate changes to the bridge
+
+Either way the radar drivers get a pyserial port, so everything that
+works over USB works over the bridge. Over raw TCP the bridge's UART
+runs at the rate it was set up with (57600 for an OPS243 on its UART
+pins); Wi-Fi keeps up with the radar's speed reports, but raw I/Q
+streaming needs more than most bridges sustain, so use --no-iq-streaming.
+"""
+
+from typing import Any
+
+import serial
+
+URL_SCHEMES = ("tcp", "socket", "rfc2217")
+
+
+def is_url(port: str) -> bool:
+    """Whether a --port value names a network bridge rather than a device."""
+    return "://" in port
+
+
+def _pyserial_url(url: str) -> str:
+    """pyserial's name for a bridge URL (tcp:// is socket:// to pyserial)."""
+    scheme, _, rest = url.partition("://")
+    scheme = scheme.lower()
+    if scheme not in URL_SCHEMES:
+        raise ValueError(
+            f"unsupported port URL {url!r} (use {' or '.join(s + '://' for s in URL_SCHEMES)})"
+        )
+    if not rest:
+        raise ValueError(f"port URL {url!r} has no host:port")
+    return f"{'socket' if scheme == 'tcp' else scheme}://{rest}"
+
+
+def open_port(port: str, baudrate: int, timeout: float, **settings: Any) -> Any:
+    """
+    Open a radar's serial link: a local device or a TCP bridge URL.
+
+    Args:
+        port: Device path (e.g. /dev/ttyACM0, COM3) or bridge URL
+        baudrate: Baud rate (sent to RFC 2217 bridges, ignored by raw TCP)
+        timeout: Read timeout in seconds
+        settings: Other pyserial settings (bytesize, parity, stopbits)
+
+    Returns:
+        An open pyserial port
+
+    Raises:
+        serial.SerialException: If the device or bridge can't be opened
+        ValueError: If the URL isn't one of URL_SCHEMES
+    """
+    if is_url(port):
+        url = _pyserial_url(port)
+        return serial.serial_for_url(url, baudrate=baudrate, timeout=timeout, **settings)
+    return serial.Serial(port=port, baudrate=baudrate, timeout=timeout, **settings)
+
+
+def connection_hint(port: str) -> str:
+    """What to check when a bridge can't be reached ("" for a local device)."""
+    if not is_url(port):
+        return ""
+    return "check that the serial bridge is running and reachable from this machine"
@@ -0,0 +1,210 @@
+"""
+Unit-typed quantities.
+
+Speeds, angles and spin rates are plain numbers to most of the code, which
+makes it easy for a new backend to hand m/s to a field documented as mph.
+These float subclasses carry the unit with the number:
+
+- Mph, MetersPerSecond: speeds, converted with to_mps() / to_mph()
+- Degrees: angles, to_radians() for the math module
+- Rpm: spin rates, to_rad_per_sec() for the flight model
+
+They behave like floats everywhere (formatting, JSON, comparisons), but
+mixing two different units (adding, subtracting, dividing or comparing
+them, == included) is a TypeError instead of a wrong number:
+
+    Mph(100) + MetersPerSecond(10)      # TypeError
+    Mph(MetersPerSecond(44.7))          # TypeError - use .to_mph()
+    MetersPerSecond(44.7).to_mph()      # Mph(99.99...)
+
+Adding or subtracting keeps the unit. Multiplying or dividing gives a
+plain float, since formulas like carry = speed * factor change the unit;
+wrap the result again (Mph(speed * correction)) where it is still a speed.
+"""
+
+import math
+from typing import Optional
+
+MPH_TO_MPS = 0.44704  # Exact, by definition of the international mile
+
+
+class Quantity(float):
+    """A float tagged with its unit. Subclasses set UNIT."""
+
+    UNIT = ""
+
+    def __new__(cls, value):
+        if isinstance(value, Quantity) and not isinstance(value, cls):
+            raise TypeError(
+                f"{type(value).__name__} is not {cls.__name__}; convert it explicitly"
+            )
+        return super().__new__(cls, value)
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}({float(self)!r})"
+
+    def __str__(self) -> str:
+        return float.__repr__(self)
+
+    def _check(self, other, op: str):
+        if isinstance(other, Quantity) and type(other) is not type(self):
+            raise TypeError(
+                f"unsupported operand units for {op}: "
+                f"{type(self).__name__} and {type(other).__name__}"
+            )
+
+    def __add__(self, other):
+        self._check(other, "+")
+        return type(self)(float(self) + float(other))
+
+    __radd__ = __add__
+
+    def __sub__(self, other):
+        self._check(other, "-")
+        return type(self)(float(self) - float(other))
+
+    def __rsub__(self, other):
+        self._check(other, "-")
+        return type(self)(float(other) - float(self))
+
+    def __truediv__(self, other):
+        self._check(other, "/")
+        return float(self) / float(other)
+
+    def __eq__(self, other):
+        self._check(other, "==")
+        return float(self) == other
+
+    def __ne__(self, other):
+        self._check(other, "!=")
+        return float(self) != other
+
+    # Defining __eq__ would otherwise drop the hash; keep it equal to the float's
+    __hash__ = float.__hash__
+
+    def __lt__(self, other):
+        self._check(other, "<")
+        return float(self) < other
+
+    def __le__(self, other):
+        self._check(other, "<=")
+        return float(self) <= other
+
+    def __gt__(self, other):
+        self._check(other, ">")
+        return float(self) > other
+
+    def __ge__(self, other):
+        self._check(other, ">=")
+        return float(self) >= other
+
+    def __neg__(self):
+        return type(self)(-float(self))
+
+    def __abs__(self):
+        return type(self)(abs(float(self)))
+
+
+class Mph(Quantity):
+    """Speed in miles per hour."""
+
+    UNIT = "mph"
+
+    def to_mps(self) -> "MetersPerSecond":
+        """The same speed in meters per second."""
+        return MetersPerSecond(float(self) * MPH_TO_MPS)
+
+
+class MetersPerSecond(Quantity):
+    """Speed in meters per second."""
+
+    UNIT = "m/s"
+
+    def to_mph(self) -> Mph:
+        """The same speed in miles per hour."""
+        return Mph(float(self) / MPH_TO_MPS)
+
+
+class Degrees(Quantity):
+    """Angle in degrees."""
+
+    UNIT = "deg"
+
+    def to_radians(self) -> float:
+        """The same angle in radians."""
+        return math.radians(self)
+
+
+class Rpm(Quantity):
+    """Spin rate in revolutions per minute."""
+
+    UNIT = "rpm"
+
+    def to_rad_per_sec(self) -> float:
+        """The same spin rate in radians per second."""
+        return float(self) * 2 * math.pi / 60
+
+
+# Radar speed unit names (as in SpeedReading.unit) with a quantity type
+SPEED_UNITS = {Mph.UNIT: Mph, MetersPerSecond.UNIT: MetersPerSecond}
+
+# mph per unit, for the radar units without a quantity type
+MPH_PER_UNIT = {"kph": 1 / 1.609344, "fps": 3600 / 5280, "cm/s": 0.01 / MPH_TO_MPS}
+
+# How radars spell units (OPS243 "U?" answers and report fields) -> unit name
+_UNIT_SPELLINGS = {
+    "mph": "mph",
+    "mi/h": "mph",
+    "m/s": "m/s",
+    "mps": "m/s",
+    "m-per-sec": "m/s",
+    "kph": "kph",
+    "km/h": "kph",
+    "kmh": "kph",
+    "km-per-hr": "kph",
+    "fps": "fps",
+    "ft/s": "fps",
+    "ft-per-sec": "fps",
+    "cm/s": "cm/s",
+    "cms": "cm/s",
+    "cm-per-sec": "cm/s",
+}
+
+
+def radar_unit(text: str) -> Optional[str]:
+    """
+    Unit name for a radar's spelling of a speed unit, e.g. "km-per-hr" -> "kph".
+
+    Returns:
+        The unit name, or None if it isn't a known speed unit
+    """
+    return _UNIT_SPELLINGS.get(text.strip().strip('"').lower())
+
+
+def speed_in(unit: str, value: float) -> float:
+    """
+    A radar speed typed by its unit name, e.g. speed_in("m/s", 44.7).
+
+    Units without a quantity type (kph, fps, cm/s) stay plain floats.
+    """
+    cls = SPEED_UNITS.get(unit)
+    return cls(value) if cls else float(value)
+
+
+def to_mph(speed: float, unit: str = Mph.UNIT) -> Mph:
+    """
+    Convert a radar speed to mph.
+
+    Raises:
+        ValueError: For a unit that can't be converted
+    """
+    if isinstance(speed, MetersPerSecond):
+        return speed.to_mph()
+    if isinstance(speed, Mph):
+        return speed
+    if unit in MPH_PER_UNIT:
+        return Mph(float(speed) * MPH_PER_UNIT[unit])
+    cls = SPEED_UNITS.get(unit)
+    if cls is None:
+        raise ValueError(f"can't convert {unit} to mph")
+    return to_mph(cls(speed))
@@ -0,0 +1,185 @@
+"""
+Voice memos on shots.
+
+The numbers say what the ball did; how the shot felt ("thinned it",
+"felt perfect", "tried the new grip") is gone by the end of the session.
+With --voice-memo, pressing a key or a button after a shot records a few
+seconds from the microphone, saved next to the session log and linked to
+the shot:
+
+    openflight-server --voice-memo                 # 5 s, Enter in the terminal
+    openflight-server --voice-memo 8 --memo-pin 26 --memo-device plughw:1,0
+
+Memos go to <session log>_shotNNN.wav, and a shot_memo entry in the log
+links the file to the shot, so read_shots() (and everything built on it)
+gets the memo with the shot. Recording needs arecord (alsa-utils) and a
+USB microphone; the button needs gpiozero and is wired from the pin to
+ground.
+"""
+
+import logging
+import shutil
+import subprocess
+import sys
+import threading
+from pathlib import Path
+from typing import Callable, List, Optional, TextIO
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_MEMO_SEC = 5.0
+SAMPLE_RATE = 16000  # Speech; keeps a memo around 160 KB
+
+
+def memo_path(session_path: Path, shot_number: int) -> Path:
+    """Where a shot's memo goes: next to its session log."""
+    return session_path.with_name(f"{session_path.stem}_shot{shot_number:03d}.wav")
+
+
+def session_memos(session_path: Path) -> List[Path]:
+    """The memos recorded for a session's shots (see memo_path), in shot order."""
+    return sorted(session_path.parent.glob(f"{session_path.stem}_shot*.wav"))
+
+
+class VoiceMemoRecorder:
+    """
+    Records memos from the microphone, one at a time, in the background.
+
+    Example:
+        recorder = VoiceMemoRecorder(duration_sec=5)
+        recorder.start(memo_path(session_path, 12), on_done=lambda ok: ...)
+    """
+
+    def __init__(
+        self,
+        duration_sec: float = DEFAULT_MEMO_SEC,
+        device: Optional[str] = None,
+        run: Optional[Callable[..., subprocess.CompletedProcess]] = None,
+    ):
+        """
+        Initialize the recorder.
+
+        Args:
+            duration_sec: Memo length
+            device: ALSA capture device, e.g. "plughw:1,0" (default: ALSA's default)
+            run: Replaces subprocess.run (for tests)
+
+        Raises:
+            ValueError: If the duration isn't positive
+            RuntimeError: If arecord isn't installed
+        """
+        if duration_sec <= 0:
+            raise ValueError(f"memo length must be positive, got {duration_sec:g}")
+        self.duration_sec = duration_sec
+        self.device = device
+        self._run = run or subprocess.run
+        self._command = "arecord" if run else shutil.which("arecord")
+        if not self._command:
+            raise RuntimeError(
+                "Voice memos need arecord. Install with: sudo apt install alsa-utils"
+            )
+        self._busy = threading.Lock()
+
+    @property
+    def recording(self) -> bool:
+        """Whether a memo is being recorded."""
+        return self._busy.locked()
+
+    def record(self, path: Path) -> bool:
+        """
+        Record a memo into path, waiting until it's done.
+
+        Returns:
+            False if arecord failed (no microphone, device busy)
+        """
+        args = [self._command, "-q"]
+        if self.device:
+            args += ["-D", self.device]
+        # arecord takes whole seconds
+        seconds = max(1, round(self.duration_sec))
+        args += ["-f", "S16_LE", "-r", str(SAMPLE_RATE), "-c", "1", "-d", str(seconds), str(path)]
+        try:
+            result = self._run(
+                args,
+                stdout=subprocess.DEVNULL,
+                stderr=subprocess.PIPE,
+                timeout=seconds + 5,
+                check=False,
+            )
+        except (OSError, subprocess.TimeoutExpired) as e:
+            logger.warning("Voice memo recording failed: %s", e)
+            return False
+        if result.returncode != 0:
+            error = (result.stderr or b"").decode("utf-8", "replace").strip()
+            logger.warning("arecord failed: %s", error or f"exit {result.returncode}")
+            return False
+        return True
+
+    def start(self, path: Path, on_done: Optional[Callable[[bool], None]] = None) -> bool:
+        """
+        Record a memo on a background thread.
+
+        Args:
+            path: File to record into
+            on_done: Called with whether it worked, once it's done
+
+        Returns:
+            False (and records nothing) if a memo is already being recorded
+        """
+        if not self._busy.acquire(blocking=False):
+            return False
+
+        def worker():
+            try:
+                ok = self.record(path)
+            finally:
+                self._busy.release()
+            if on_done:
+                on_done(ok)
+
+        threading.Thread(target=worker, daemon=True).start()
+        return True
+
+
+def watch_key(callback: Callable[[], None], stream: TextIO = sys.stdin) -> threading.Thread:
+    """
+    Call callback each time Enter is pressed (each line read from stream).
+
+    Returns:
+        The watching thread (a daemon; it ends with the stream)
+    """
+
+    def loop():
+        for _ in stream:
+            try:
+                callback()
+            except Exception as e:  # pylint: disable=broad-except
+                logger.warning("Memo key handler failed: %s", e)
+
+    thread = threading.Thread(target=loop, daemon=True)
+    thread.start()
+    return thread
+
+
+def watch_button(pin: int, callback: Callable[[], None]):
+    """
+    Call callback when a button from pin to ground is pressed.
+
+    Returns:
+        The gpiozero Button (keep a reference, or it stops)
+
+    Raises:
+        RuntimeError: If gpiozero isn't installed or the pin can't be used
+    """
+    try:
+        from gpiozero import Button  # pylint: disable=import-outside-toplevel
+    except ImportError:
+        raise RuntimeError(
+            "The memo button needs gpiozero. Install with: uv pip install gpiozero lgpio"
+        ) from None
+    try:
+        button = Button(pin, pull_up=True, bounce_time=0.05)
+    except Exception as e:  # pylint: disable=broad-except
+        raise RuntimeError(f"Could not use GPIO pin {pin}: {e}") from None
+    button.when_pressed = callback
+    return button
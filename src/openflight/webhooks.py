@@ -0,0 +1,455 @@
+"""
+Webhook output sink for OpenFlight.
+
+POSTs each detected shot as JSON to one or more user-configured URLs so
+shots can be piped into other services (golf journals, Notion, custom
+apps) without writing an integration.
+
+Deliveries run on a background thread so a slow or unreachable endpoint
+never blocks shot detection. Failed deliveries are retried with capped
+exponential backoff. When a secret is configured, each request carries an
+HMAC-SHA256 signature of the body so receivers can verify its origin:
+
+    X-OpenFlight-Signature: sha256=<hex digest>
+
+Senders can pass a callback to send() to get a receipt once every URL has
+been tried, e.g. to mark a shot delivered or failed. A receiver's JSON
+response body is parsed into the receipt so acknowledgments it returns
+(ids, messages) are kept.
+
+Sims take a few seconds to play out a shot and can drop or choke on shots
+that arrive faster. With min_interval_sec, deliveries are spaced at least
+that far apart, and shots detected in between wait by one of two
+policies:
+
+- "queue": every shot is delivered, in order, one interval apart
+- "merge": only the latest waiting shot is delivered; the ones it
+  replaced get receipts with error MERGED_ERROR and are never sent
+
+Only delivery is throttled: detection, the session log and the UI see
+every shot as it happens.
+
+A sink can also have a failover group of sim endpoints (see failover):
+each payload goes to the group's active endpoint, or the next one that
+takes it, and is reported as one receipt from the endpoint that did.
+"""
+
+import hashlib
+import hmac
+import json
+import logging
+import queue
+import threading
+import time
+import urllib.error
+import urllib.request
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .failover import FailoverGroup
+from .tracing import trace_span
+
+logger = logging.getLogger(__name__)
+
+SIGNATURE_HEADER = "X-OpenFlight-Signature"
+EVENT_HEADER = "X-OpenFlight-Event"
+
+THROTTLE_POLICIES = ("queue", "merge")
+MERGED_ERROR = "merged into a later shot"
+
+
+def sign_payload(body: bytes, secret: str) -> str:
+    """
+    Compute the signature header value for a request body.
+
+    Args:
+        body: Raw request body bytes
+        secret: Shared secret configured on both ends
+
+    Returns:
+        Signature in the form "sha256=<hex digest>"
+    """
+    digest = hmac.new(secret.encode("utf-8"), body, hashlib.sha256).hexdigest()
+    return f"sha256={digest}"
+
+
+def verify_signature(body: bytes, secret: str, signature: str) -> bool:
+    """Check a received signature header against the body (constant-time)."""
+    return hmac.compare_digest(sign_payload(body, secret), signature)
+
+
+@dataclass
+class DeliveryReceipt:
+    """
+    Outcome of delivering one payload to one URL.
+
+    Attributes:
+        url: Endpoint the payload was sent to
+        delivered: True if the endpoint accepted it (2xx)
+        status_code: Last HTTP status received (None if it never answered)
+        attempts: Attempts made
+        response: Parsed JSON response body, if the receiver returned one
+        error: Last error, if not delivered
+    """
+
+    url: str
+    delivered: bool
+    status_code: Optional[int] = None
+    attempts: int = 0
+    response: Optional[Any] = None
+    error: Optional[str] = None
+
+    def to_dict(self) -> dict:
+        """Serialize for logs and the API."""
+        return {
+            "url": self.url,
+            "delivered": self.delivered,
+            "status_code": self.status_code,
+            "attempts": self.attempts,
+            "response": self.response,
+            "error": self.error,
+        }
+
+
+def _parse_response(body: bytes) -> Optional[Any]:
+    """JSON acknowledgment from a receiver, if it sent one."""
+    if not body:
+        return None
+    try:
+        return json.loads(body)
+    except ValueError:
+        return None
+
+
+class WebhookSink:
+    """
+    Delivers shot payloads to HTTP endpoints with retry and backoff.
+
+    Example:
+        sink = WebhookSink(["https://example.com/hook"], secret="s3cret")
+        sink.start()
+        sink.send({"event": "shot", "shot": {...}})
+        ...
+        sink.stop()
+    """
+
+    DEFAULT_MAX_ATTEMPTS = 5  # Total attempts per URL (1 initial + 4 retries)
+    DEFAULT_BACKOFF_SEC = 1.0  # Delay before first retry, doubled each attempt
+    MAX_BACKOFF_SEC = 30.0  # Cap on delay between retries
+    DEFAULT_TIMEOUT_SEC = 5.0  # Per-request HTTP timeout
+
+    def __init__(
+        self,
+        urls: List[str],
+        secret: Optional[str] = None,
+        max_attempts: int = DEFAULT_MAX_ATTEMPTS,
+        backoff_sec: float = DEFAULT_BACKOFF_SEC,
+        timeout_sec: float = DEFAULT_TIMEOUT_SEC,
+        min_interval_sec: float = 0.0,
+        throttle_policy: str = "queue",
+        failover: Optional[FailoverGroup] = None,
+    ):
+        """
+        Initialize webhook sink.
+
+        Args:
+            urls: Endpoints to POST each payload to
+            secret: Optional shared secret for HMAC-SHA256 signing
+            max_attempts: Total delivery attempts per URL before giving up
+            backoff_sec: Initial retry delay in seconds (doubles per attempt)
+            timeout_sec: HTTP request timeout in seconds
+            min_interval_sec: Least time between deliveries (0 = no throttling)
+            throttle_policy: What happens to payloads sent within the
+                interval: "queue" or "merge" (see THROTTLE_POLICIES)
+            failover: Sim endpoints that each take a payload in turn, besides urls
+
+        Raises:
+            ValueError: If the interval is negative or the policy unknown
+        """
+        if min_interval_sec < 0:
+            raise ValueError("min_interval_sec can't be negative")
+        if throttle_policy not in THROTTLE_POLICIES:
+            raise ValueError(f"throttle policy must be one of {', '.join(THROTTLE_POLICIES)}")
+        self.urls = list(urls)
+        self.secret = secret
+        self.max_attempts = max(1, max_attempts)
+        self.backoff_sec = backoff_sec
+        self.timeout_sec = timeout_sec
+        self.min_interval_sec = min_interval_sec
+        self.throttle_policy = throttle_policy
+        self.failover = failover
+        self._last_delivery: Optional[float] = None
+        self._last_receipts: List[DeliveryReceipt] = []
+
+        self._queue: "queue.Queue[Optional[tuple]]" = queue.Queue()
+        self._thread: Optional[threading.Thread] = None
+        self._stop_event = threading.Event()
+
+    def start(self):
+        """Start the background delivery thread."""
+        if self._thread and self._thread.is_alive():
+            return
+
+        self._stop_event.clear()
+        self._thread = threading.Thread(target=self._worker, daemon=True)
+        self._thread.start()
+        if self.failover:
+            self.failover.start()
+        logger.info("Webhook sink started (%d URL(s))", len(self.urls))
+
+    def stop(self, timeout: float = 5.0):
+        """
+        Stop the delivery thread after draining queued payloads.
+
+        Args:
+            timeout: Seconds to wait for pending deliveries to finish
+        """
+        if not self._thread:
+            return
+
+        self._queue.put(None)
+        self._thread.join(timeout=timeout)
+        # Abort any in-progress backoff sleep if the drain took too long
+        self._stop_event.set()
+        self._thread = None
+        if self.failover:
+            self.failover.stop()
+
+    def to_dict(self) -> dict:
+        """Delivery state for GET /api/state: the queue and the last delivery's receipts."""
+        since = time.monotonic() - self._last_delivery if self._last_delivery else None
+        return {
+            "urls": list(self.urls),
+            "running": bool(self._thread and self._thread.is_alive()),
+            "queued": self._queue.qsize(),
+            "since_last_delivery_sec": since,
+            "last_delivery": [receipt.to_dict() for receipt in self._last_receipts],
+        }
+
+    def send(
+        self,
+        payload: Dict[str, Any],
+        on_result: Optional[Callable[[List[DeliveryReceipt]], None]] = None,
+    ):
+        """
+        Queue a payload for delivery to all configured URLs.
+
+        Args:
+            payload: JSON-serializable dict
+            on_result: Called on the delivery thread with one receipt per
+                URL (and one for the failover group) once every URL has been tried
+        """
+        self._queue.put((payload, on_result))
+
+    def _worker(self):
+        """Background loop that delivers queued payloads."""
+        stopping = False
+        while not stopping:
+            item = self._queue.get()
+            if item is None:
+                break
+
+            self._wait_for_interval()
+            if self.throttle_policy == "merge":
+                item, stopping = self._merge_waiting(item)
+
+            payload, on_result = item
+            body = json.dumps(payload).encode("utf-8")
+            event = str(payload.get("event", "shot"))
+            with trace_span("webhook", "deliver", event=event, urls=len(self.urls)):
+                receipts = [self.deliver_with_receipt(url, body, event=event) for url in self.urls]
+                if self.failover:
+                    receipts.append(self.deliver_failover(body, event=event))
+            self._last_delivery = time.monotonic()
+            self._last_receipts = receipts
+            self._report(on_result, receipts)
+
+    def _wait_for_interval(self):
+        """Sleep until min_interval_sec has passed since the last delivery."""
+        if not self.min_interval_sec or self._last_delivery is None:
+            return
+        remaining = self._last_delivery + self.min_interval_sec - time.monotonic()
+        if remaining > 0:
+            self._stop_event.wait(remaining)
+
+    def _merge_waiting(self, item: tuple) -> Tuple[tuple, bool]:
+        """
+        Replace item with the latest payload queued behind it.
+
+        Returns:
+            The payload to deliver, and whether the sink was stopped meanwhile
+        """
+        while True:
+            try:
+                newer = self._queue.get_nowait()
+            except queue.Empty:
+                return item, False
+            if newer is None:
+                return item, True
+            _, on_result = item
+            urls = self.urls + ([self.failover.active or ""] if self.failover else [])
+            merged = [DeliveryReceipt(url=url, delivered=False, error=MERGED_ERROR) for url in urls]
+            self._report(on_result, merged)
+            item = newer
+
+    @staticmethod
+    def _report(
+        on_result: Optional[Callable[[List[DeliveryReceipt]], None]],
+        receipts: List[DeliveryReceipt],
+    ):
+        """Give a sender its receipts."""
+        if on_result:
+            try:
+                on_result(receipts)
+            except Exception as e:  # pylint: disable=broad-except
+                logger.warning("Webhook result callback failed: %s", e)
+
+    def _build_request(self, url: str, body: bytes, event: str) -> urllib.request.Request:
+        """Build a signed POST request for one delivery attempt."""
+        headers = {
+            "Content-Type": "application/json",
+            "User-Agent": "OpenFlight-Webhook",
+            EVENT_HEADER: event,
+        }
+        if self.secret:
+            headers[SIGNATURE_HEADER] = sign_payload(body, self.secret)
+        return urllib.request.Request(url, data=body, headers=headers, method="POST")
+
+    def deliver(self, url: str, body: bytes, event: str = "shot") -> bool:
+        """
+        deliver_with_receipt, reporting only whether the body was delivered.
+
+        Returns:
+            True if the endpoint accepted the payload (2xx)
+        """
+        return self.deliver_with_receipt(url, body, event).delivered
+
+    def deliver_failover(self, body: bytes, event: str = "shot") -> DeliveryReceipt:
+        """
+        POST a body to the failover group's active endpoint, or the next that takes it.
+
+        Each endpoint is tried once, and marked down in the group if it
+        doesn't take the body.
+
+        Returns:
+            Receipt from the endpoint that took it, or the last one tried
+        """
+        receipt = None
+        for url in self.failover.order():
+            receipt = self.deliver_with_receipt(url, body, event=event, max_attempts=1)
+            self.failover.record(url, receipt.delivered)
+            if receipt.delivered:
+                break
+        return receipt
+
+    def deliver_with_receipt(
+        self, url: str, body: bytes, event: str = "shot", max_attempts: Optional[int] = None
+    ) -> DeliveryReceipt:
+        """
+        POST a body to one URL, retrying transient failures.
+
+        Retries on connection errors, timeouts, 429 and 5xx responses.
+        Other 4xx responses mean the receiver rejected the payload, so
+        retrying would not help.
+
+        Args:
+            url: Endpoint to POST to
+            body: Serialized JSON body
+            event: Event name sent in the X-OpenFlight-Event header
+            max_attempts: Attempts to make (default: the sink's max_attempts)
+
+        Returns:
+            Receipt with the outcome and any JSON acknowledgment
+        """
+        delay = self.backoff_sec
+        receipt = DeliveryReceipt(url=url, delivered=False)
+        max_attempts = max_attempts or self.max_attempts
+
+        for attempt in range(1, max_attempts + 1):
+            receipt.attempts = attempt
+            retryable = True
+            try:
+                request = self._build_request(url, body, event)
+                with urllib.request.urlopen(request, timeout=self.timeout_sec) as response:
+                    logger.debug("Webhook %s accepted (HTTP %d)", url, response.status)
+                    receipt.delivered = True
+                    receipt.status_code = response.status
+                    receipt.response = _parse_response(response.read())
+                    receipt.error = None
+                    return receipt
+            except urllib.error.HTTPError as e:
+                retryable = e.code == 429 or e.code >= 500
+                receipt.status_code = e.code
+                receipt.error = f"HTTP {e.code}"
+                receipt.response = _parse_response(e.read())
+                logger.warning(
+                    "Webhook %s returned HTTP %d (attempt %d/%d)",
+                    url,
+                    e.code,
+                    attempt,
+                    max_attempts,
+                )
+            except (urllib.error.URLError, OSError) as e:
+                receipt.error = str(getattr(e, "reason", e))
+                logger.warning(
+                    "Webhook %s failed: %s (attempt %d/%d)", url, e, attempt, max_attempts
+                )
+
+            if not retryable or attempt == max_attempts:
+                break
+
+            if self._stop_event.wait(delay):
+                break
+            delay = min(delay * 2, self.MAX_BACKOFF_SEC)
+
+        logger.error("Webhook %s: giving up after %d attempt(s)", url, receipt.attempts)
+        return receipt
+
+
+# Global webhook sink instance
+_webhook_sink: Optional[WebhookSink] = None
+
+
+def get_webhook_sink() -> Optional[WebhookSink]:
+    """Get the global webhook sink instance (None if no URLs configured)."""
+    return _webhook_sink
+
+
+def init_webhook_sink(
+    urls: List[str],
+    secret: Optional[str] = None,
+    min_interval_sec: float = 0.0,
+    throttle_policy: str = "queue",
+    failover: Optional[FailoverGroup] = None,
+) -> Optional[WebhookSink]:
+    """
+    Initialize and start the global webhook sink.
+
+    Args:
+        urls: Endpoints to POST shots to. An empty list disables the sink.
+        secret: Optional shared secret for HMAC signing
+        min_interval_sec: Least time between deliveries (0 = no throttling)
+        throttle_policy: "queue" or "merge" shots sent within the interval
+        failover: Sim endpoints with failover (see failover)
+
+    Returns:
+        WebhookSink instance, or None if no URLs or failover group were given
+    """
+    global _webhook_sink  # pylint: disable=global-statement
+
+    if _webhook_sink:
+        _webhook_sink.stop()
+        _webhook_sink = None
+
+    if not urls and not failover:
+        return None
+
+    _webhook_sink = WebhookSink(
+        urls,
+        secret=secret,
+        min_interval_sec=min_interval_sec,
+        throttle_policy=throttle_policy,
+        failover=failover,
+    )
+    _webhook_sink.start()
+    return _webhook_sink
@@ -0,0 +1,297 @@
+"""
+Windows service for sim PCs.
+
+Many GSPro setups run everything on one Windows PC, where the monitor
+should start with the machine, before anyone logs in, with no console
+window to close by accident. openflight-service registers the server as a
+Windows service (needs pywin32: uv pip install -e ".[windows]"), from an
+administrator prompt:
+
+    openflight-service --startup auto install -- --port COM3 --webhook-url http://localhost:8000
+    openflight-service start
+    openflight-service pause       # Releases the radar; continue picks it up again
+    openflight-service continue
+    openflight-service stop
+    openflight-service remove
+
+Everything after "--" is passed to openflight-server; run install again
+(or update) to change it. scripts/install_windows.ps1 sets up Python, the
+package and the service in one go.
+
+The service runs `openflight-server --service` as a child process. With
+--service the server reads commands from stdin instead of a console
+("stop", "pause", "continue"; end of input also stops it, so it never
+outlives the service), and the service writes the server's output to the
+Windows Event Log (Event Viewer > Windows Logs > Application, source
+OpenFlight). Stopping is graceful: the session log is closed and the
+radar released as with Ctrl+C.
+"""
+
+import json
+import logging
+import subprocess
+import sys
+import threading
+from typing import Callable, Dict, List, Optional, TextIO
+
+try:
+    import servicemanager
+    import win32event
+    import win32service
+    import win32serviceutil
+except ImportError:
+    win32serviceutil = None
+
+logger = logging.getLogger(__name__)
+
+SERVICE_NAME = "OpenFlight"
+SERVICE_DISPLAY_NAME = "OpenFlight Launch Monitor"
+SERVICE_COMMANDS = ("stop", "pause", "continue")
+
+_ARGS_OPTION = "ServerArgs"  # Registry value holding the server arguments
+_PYTHON_OPTION = "Python"  # Registry value holding the interpreter it was installed with
+
+
+def server_command(server_args: List[str], python: str = sys.executable) -> List[str]:
+    """
+    The command the service runs: openflight-server --service with the configured args.
+
+    Args:
+        server_args: openflight-server arguments
+        python: Interpreter to run it with (the service itself runs in
+            pythonservice.exe, so this is the one recorded at install)
+    """
+    return [python, "-m", "openflight.server", "--service", *server_args]
+
+
+def watch_commands(
+    handlers: Dict[str, Callable[[], None]], stream: TextIO = sys.stdin
+) -> threading.Thread:
+    """
+    Call handlers[command] for each command line read from stream.
+
+    End of input runs the "stop" handler: the service that started the
+    server is gone.
+
+    Returns:
+        The watching thread (a daemon)
+    """
+
+    def dispatch(command: str):
+        handler = handlers.get(command)
+        if handler is None:
+            logger.warning("Unknown service command %r", command)
+            return
+        try:
+            handler()
+        except Exception as e:  # pylint: disable=broad-except
+            logger.warning("Service command %s failed: %s", command, e)
+
+    def loop():
+        for line in stream:
+            command = line.strip().lower()
+            if command:
+                dispatch(command)
+        dispatch("stop")
+
+    thread = threading.Thread(target=loop, daemon=True)
+    thread.start()
+    return thread
+
+
+class ServerProcess:
+    """
+    The server as a child process driven over its stdin.
+
+    Example:
+        server = ServerProcess(server_command(["--mock"]), log=print)
+        server.start()
+        server.send("pause")
+        server.stop()
+    """
+
+    STOP_TIMEOUT_SEC = 15.0  # Time to close the session log and radar before killing it
+
+    def __init__(
+        self,
+        command: List[str],
+        log: Callable[[str], None],
+        popen: Callable[..., subprocess.Popen] = subprocess.Popen,
+    ):
+        """
+        Initialize the server process.
+
+        Args:
+            command: Command line (see server_command)
+            log: Called with each line the server prints
+            popen: Replaces subprocess.Popen (for tests)
+        """
+        self.command = command
+        self._log = log
+        self._popen = popen
+        self._process: Optional[subprocess.Popen] = None
+        self._reader: Optional[threading.Thread] = None
+
+    @property
+    def running(self) -> bool:
+        """Whether the server is running."""
+        return self._process is not None and self._process.poll() is None
+
+    def start(self):
+        """
+        Start the server, forwarding its output to log.
+
+        Raises:
+            OSError: If it can't be started
+        """
+        self._process = self._popen(
+            self.command,
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+            stderr=subprocess.STDOUT,
+            text=True,
+            encoding="utf-8",
+            errors="replace",
+            bufsize=1,
+        )
+        self._reader = threading.Thread(target=self._forward_output, daemon=True)
+        self._reader.start()
+
+    def _forward_output(self):
+        for line in self._process.stdout:
+            line = line.rstrip()
+            if line:
+                self._log(line)
+
+    def send(self, command: str) -> bool:
+        """
+        Send a command (see SERVICE_COMMANDS).
+
+        Returns:
+            False if the server isn't running
+        """
+        if not self.running:
+            return False
+        try:
+            self._process.stdin.write(command + "\n")
+            self._process.stdin.flush()
+        except OSError:
+            return False
+        return True
+
+    def wait(self, timeout: Optional[float] = None) -> Optional[int]:
+        """Wait for the server to exit; its exit code, or None if it's still running."""
+        if self._process is None:
+            return None
+        try:
+            return self._process.wait(timeout)
+        except subprocess.TimeoutExpired:
+            return None
+
+    def stop(self) -> Optional[int]:
+        """Stop the server gracefully, killing it if it doesn't exit in STOP_TIMEOUT_SEC."""
+        if self._process is None:
+            return None
+        if self.running:
+            self.send("stop")
+            try:
+                self._process.stdin.close()
+            except OSError:
+                pass
+            if self.wait(self.STOP_TIMEOUT_SEC) is None:
+                self._log("Server did not stop in time - killing it")
+                self._process.kill()
+        code = self._process.wait()
+        if self._reader:
+            self._reader.join(timeout=2.0)
+        return code
+
+
+if win32serviceutil:
+
+    class OpenFlightService(win32serviceutil.ServiceFramework):
+        """The Windows service: runs the server and passes on stop, pause and continue."""
+
+        _svc_name_ = SERVICE_NAME
+        _svc_display_name_ = SERVICE_DISPLAY_NAME
+        _svc_description_ = "Golf launch monitor server (openflight-server)"
+
+        def __init__(self, args):
+            super().__init__(args)
+            self._stop_event = win32event.CreateEvent(None, 0, 0, None)
+            self._server: Optional[ServerProcess] = None
+
+        def SvcDoRun(self):  # pylint: disable=invalid-name
+            """Run the server until the service is stopped (or the server exits)."""
+            server_args = json.loads(
+                win32serviceutil.GetServiceCustomOption(SERVICE_NAME, _ARGS_OPTION, "[]")
+            )
+            python = win32serviceutil.GetServiceCustomOption(
+                SERVICE_NAME, _PYTHON_OPTION, sys.executable
+            )
+            self._server = ServerProcess(
+                server_command(server_args, python), log=servicemanager.LogInfoMsg
+            )
+            try:
+                self._server.start()
+            except OSError as e:
+                servicemanager.LogErrorMsg(f"Could not start openflight-server: {e}")
+                return
+            servicemanager.LogInfoMsg(f"Started: {' '.join(self._server.command)}")
+            while self._server.running:
+                signaled = win32event.WaitForSingleObject(self._stop_event, 1000)
+                if signaled == win32event.WAIT_OBJECT_0:
+                    break
+            code = self._server.stop()
+            if code:
+                servicemanager.LogErrorMsg(f"openflight-server exited with code {code}")
+
+        def SvcStop(self):  # pylint: disable=invalid-name
+            """Stop: the server closes its session and radar first."""
+            self.ReportServiceStatus(win32service.SERVICE_STOP_PENDING)
+            win32event.SetEvent(self._stop_event)
+
+        def SvcPause(self):  # pylint: disable=invalid-name
+            """Pause: the server releases the radar."""
+            self.ReportServiceStatus(win32service.SERVICE_PAUSE_PENDING)
+            if self._server:
+                self._server.send("pause")
+            self.ReportServiceStatus(win32service.SERVICE_PAUSED)
+
+        def SvcContinue(self):  # pylint: disable=invalid-name
+            """Continue: the server reconnects the radar."""
+            self.ReportServiceStatus(win32service.SERVICE_CONTINUE_PENDING)
+            if self._server:
+                self._server.send("continue")
+            self.ReportServiceStatus(win32service.SERVICE_RUNNING)
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """CLI entry point: install and control the Windows service."""
+    argv = sys.argv[1:] if argv is None else argv
+    if win32serviceutil is None:
+        print(
+            "openflight-service needs Windows and pywin32. "
+            'Install with: uv pip install -e ".[windows]"'
+        )
+        return 1
+    if "--" in argv:
+        split = argv.index("--")
+        argv, server_args = argv[:split], argv[split + 1 :]
+    else:
+        server_args = None
+    result = win32serviceutil.HandleCommandLine(
+        OpenFlightService, argv=["openflight-service", *argv]
+    )
+    if {"install", "update"} & set(argv):
+        win32serviceutil.SetServiceCustomOption(SERVICE_NAME, _PYTHON_OPTION, sys.executable)
+        if server_args is not None:
+            win32serviceutil.SetServiceCustomOption(
+                SERVICE_NAME, _ARGS_OPTION, json.dumps(server_args)
+            )
+            print(f"Server arguments: {' '.join(server_args) or '(none)'}")
+    return result or 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,92 @@
+"""Tests for access module."""
+
+import pytest
+
+from openflight.access import AccessPolicy, parse_networks
+
+
+class TestParseNetworks:
+    """Tests for allowlist parsing."""
+
+    def test_single_address(self):
+        """A bare address should become a single-host network."""
+        networks = parse_networks(["192.168.1.20"])
+
+        assert str(networks[0]) == "192.168.1.20/32"
+
+    def test_cidr(self):
+        """CIDR ranges should be accepted, ignoring host bits."""
+        networks = parse_networks(["10.0.0.5/24"])
+
+        assert str(networks[0]) == "10.0.0.0/24"
+
+    def test_invalid_entry(self):
+        """Garbage entries should raise ValueError."""
+        with pytest.raises(ValueError):
+            parse_networks(["garage-wifi"])
+
+
+class TestAddressAllowlist:
+    """Tests for client address filtering."""
+
+    def test_no_allowlist_allows_all(self):
+        """Without an allowlist every address is allowed."""
+        policy = AccessPolicy()
+
+        assert policy.is_address_allowed("203.0.113.9")
+        assert not policy.enabled
+
+    def test_address_in_network(self):
+        """Addresses inside an allowed network should pass."""
+        policy = AccessPolicy(allowed=["192.168.1.0/24"])
+
+        assert policy.is_address_allowed("192.168.1.42")
+        assert not policy.is_address_allowed("192.168.2.42")
+
+    def test_loopback_always_allowed(self):
+        """Localhost should be allowed even when not listed."""
+        policy = AccessPolicy(allowed=["192.168.1.0/24"])
+
+        assert policy.is_address_allowed("127.0.0.1")
+        assert policy.is_address_allowed("::1")
+
+    def test_ipv4_mapped_address(self):
+        """IPv4-mapped IPv6 addresses should match IPv4 networks."""
+        policy = AccessPolicy(allowed=["192.168.1.0/24"])
+
+        assert policy.is_address_allowed("::ffff:192.168.1.42")
+
+    def test_missing_address_rejected(self):
+        """Unknown client addresses should be rejected when an allowlist is set."""
+        policy = AccessPolicy(allowed=["192.168.1.0/24"])
+
+        assert not policy.is_address_allowed(None)
+        assert not policy.is_address_allowed("not-an-ip")
+
+
+class TestToken:
+    """Tests for API token checks."""
+
+    def test_no_token_allows_all(self):
+        """Without a configured token every request passes."""
+        assert AccessPolicy().is_token_valid()
+
+    def test_bearer_token(self):
+        """Authorization: Bearer <token> should be accepted."""
+        policy = AccessPolicy(token="s3cret")
+
+        assert policy.is_token_valid(authorization="Bearer s3cret")
+        assert not policy.is_token_valid(authorization="Bearer wrong")
+
+    def test_token_header(self):
+        """X-OpenFlight-Token header should be accepted."""
+        policy = AccessPolicy(token="s3cret")
+
+        assert policy.is_token_valid(token_header="s3cret")
+
+    def test_missing_token_rejected(self):
+        """Requests without a token should be rejected when one is configured."""
+        policy = AccessPolicy(token="s3cret")
+
+        assert not policy.is_token_valid()
+        assert not policy.is_token_valid(authorization="Basic czNjcmV0")
@@ -0,0 +1,158 @@
+"""Tests for alignment module."""
+
+import math
+
+import pytest
+
+from openflight.alignment import (
+    AlignmentSample,
+    AlignmentTracker,
+    RollResult,
+    advice,
+    fit_path,
+    render_indicator,
+    summarize,
+)
+from openflight.frames import Direction, Point, PointCloud, SpeedReading
+
+
+def _path(angle_deg, offset_m=0.3, n=10):
+    """Positions along a straight path turned angle_deg right of boresight."""
+    slope = math.tan(math.radians(angle_deg))
+    return [(offset_m + slope * (1 + 0.2 * i), 1 + 0.2 * i) for i in range(n)]
+
+
+class TestFitPath:
+    """Tests for fitting the ball's path."""
+
+    def test_skewed_path(self):
+        """A path turned to the right should fit to its angle, whatever its offset."""
+        assert fit_path(_path(4.0)) == pytest.approx(4.0)
+        assert fit_path(_path(-2.5, offset_m=-0.5)) == pytest.approx(-2.5)
+
+    def test_straight_path_beside_boresight(self):
+        """A path parallel to boresight but off to the side should fit to zero."""
+        assert fit_path(_path(0.0, offset_m=0.8)) == pytest.approx(0.0)
+
+    def test_too_few_points(self):
+        """A handful of points shouldn't give a direction."""
+        assert fit_path(_path(4.0, n=3)) is None
+
+    def test_too_short(self):
+        """Points bunched together shouldn't give a direction."""
+        assert fit_path([(0.1, 2.0 + 0.01 * i) for i in range(10)]) is None
+
+
+class TestAdvice:
+    """Tests for the per-roll advice."""
+
+    def test_turn_right(self):
+        """A path right of boresight should say to turn the radar right."""
+        assert advice(RollResult(samples=10, peak_magnitude=20, path_deg=3.2)) == (
+            "turn the radar 3.2° right"
+        )
+
+    def test_turn_left(self):
+        """Without a fitted path, the mean angle should be used."""
+        result = RollResult(samples=10, peak_magnitude=20, mean_azimuth_deg=-2.0)
+        assert advice(result) == "turn the radar 2.0° left"
+
+    def test_aligned(self):
+        """A small angle should count as aligned."""
+        assert advice(RollResult(samples=10, peak_magnitude=20, path_deg=0.6)).startswith(
+            "aligned"
+        )
+
+    def test_no_angle(self):
+        """Without angles, the advice should be about signal strength."""
+        assert "strongest signal" in advice(RollResult(samples=10, peak_magnitude=900))
+
+
+class TestAlignmentTracker:
+    """Tests for collecting sightings into rolls."""
+
+    def test_roll_ends_after_gap(self):
+        """A roll should be finished only once the ball has been gone a while."""
+        tracker = AlignmentTracker()
+        for i in range(5):
+            tracker.add_reading(
+                SpeedReading(
+                    speed=3.0, direction=Direction.OUTBOUND, magnitude=500 + i, timestamp=i * 0.1
+                )
+            )
+
+        assert tracker.poll(now=0.6) is None
+        result = tracker.poll(now=2.0)
+        assert result.samples == 5
+        assert result.peak_magnitude == 504
+        assert result.misalignment_deg is None
+        assert tracker.poll(now=5.0) is None
+
+    def test_ignores_inbound_and_fast(self):
+        """Readings toward the radar or at swing speeds shouldn't count as a roll."""
+        tracker = AlignmentTracker()
+        tracker.add_reading(SpeedReading(speed=3.0, direction=Direction.INBOUND, timestamp=0.0))
+        tracker.add_reading(SpeedReading(speed=90.0, direction=Direction.OUTBOUND, timestamp=0.1))
+        tracker.add_reading(SpeedReading(speed=0.2, direction=Direction.OUTBOUND, timestamp=0.2))
+
+        assert not tracker.samples
+        assert tracker.poll(now=5.0) is None
+
+    def test_point_cloud_path(self):
+        """IWR6843 frames should fit the path of the strongest moving point."""
+        tracker = AlignmentTracker()
+        for i, (x, y) in enumerate(_path(3.0)):
+            tracker.add_point_cloud(
+                PointCloud(
+                    timestamp=i * 0.1,
+                    points=[
+                        Point(x=x, y=y, z=0.0, velocity=1.0, snr_db=20.0),
+                        Point(x=-1.0, y=3.0, z=0.0, velocity=1.0, snr_db=10.0),
+                        Point(x=0.5, y=2.0, z=0.0, velocity=0.0, snr_db=30.0),
+                    ],
+                )
+            )
+
+        result = tracker.poll(now=5.0)
+        assert result.samples == 10
+        assert result.peak_magnitude == 20.0
+        assert result.path_deg == pytest.approx(3.0)
+        assert tracker.last.x == pytest.approx(_path(3.0)[-1][0])
+
+    def test_summarize_mean_angle(self):
+        """Without enough positions, the mean angle should stand in for the path."""
+        samples = [
+            AlignmentSample(timestamp=0.0, magnitude=10.0, azimuth_deg=2.0),
+            AlignmentSample(timestamp=0.1, magnitude=12.0, azimuth_deg=4.0),
+        ]
+        result = summarize(samples)
+        assert result.path_deg is None
+        assert result.misalignment_deg == pytest.approx(3.0)
+
+
+class TestRenderIndicator:
+    """Tests for the live indicator line."""
+
+    def test_marker_position(self):
+        """The marker should sit right of boresight for a positive angle."""
+        line = render_indicator(7.5, None, full_scale=30.0, span_deg=15.0, width=29)
+        bar = line[line.index("[") + 1 : line.index("]")]
+        assert bar.index("|") == 14
+        assert bar.index("*") == 21
+        assert "+7.5°" in line
+
+    def test_marker_clamped(self):
+        """Angles past the span should pin the marker to the end of the bar."""
+        line = render_indicator(-40.0, None, full_scale=30.0)
+        bar = line[line.index("[") + 1 : line.index("]")]
+        assert bar.index("*") == 0
+
+    def test_signal_bar(self):
+        """The signal bar should fill in proportion to the magnitude."""
+        line = render_indicator(None, 15.0, full_scale=30.0)
+        assert "signal [#######.......]" in line
+        assert "L [" not in line
+
+    def test_nothing_seen(self):
+        """With nothing to show, the line should say it's waiting."""
+        assert render_indicator(None, None, full_scale=30.0) == "waiting for a rolling ball"
@@ -0,0 +1,59 @@
+"""Tests for arming module."""
+
+from openflight.arming import ArmingStateMachine, ArmState
+
+
+class TestArmingStateMachine:
+    """Tests for ball-present arming transitions."""
+
+    def test_starts_idle(self):
+        """New state machine should not be armed."""
+        assert not ArmingStateMachine().armed
+
+    def test_arms_after_settle_time(self):
+        """Ball present for the settle time should emit ready once."""
+        arming = ArmingStateMachine(settle_sec=0.5)
+
+        assert arming.update(True, now=0.0) is None
+        assert arming.update(True, now=0.3) is None
+        assert arming.update(True, now=0.5) == "ready"
+        assert arming.update(True, now=0.9) is None
+        assert arming.state == ArmState.ARMED
+
+    def test_flicker_resets_settle_timer(self):
+        """A dropped detection should restart the settle timer."""
+        arming = ArmingStateMachine(settle_sec=0.5)
+
+        arming.update(True, now=0.0)
+        arming.update(False, now=0.3)
+        assert arming.update(True, now=0.4) is None
+        assert arming.update(True, now=0.8) is None
+        assert arming.update(True, now=0.9) == "ready"
+
+    def test_ball_removed_emits_not_ready(self):
+        """Removing an armed ball should emit not_ready."""
+        arming = ArmingStateMachine(settle_sec=0.0)
+        arming.update(True, now=0.0)
+
+        assert arming.update(False, now=1.0) == "not_ready"
+        assert not arming.armed
+
+    def test_shot_disarms(self):
+        """After a shot the next ball must settle again."""
+        arming = ArmingStateMachine(settle_sec=0.5)
+        arming.update(True, now=0.0)
+        arming.update(True, now=0.5)
+
+        arming.on_shot()
+
+        assert not arming.armed
+        assert arming.update(False, now=1.0) is None
+        assert arming.update(True, now=2.0) is None
+        assert arming.update(True, now=2.5) == "ready"
+
+    def test_manual_arm(self):
+        """Arming by hand should not wait for a ball to settle."""
+        arming = ArmingStateMachine(settle_sec=0.5)
+
+        assert arming.arm() == "ready"
+        assert arming.armed
@@ -0,0 +1,113 @@
+"""Tests for ball types."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.ball_types import (
+    BALL_TYPE_NAMES,
+    apply_ball_type,
+    ball_type_for,
+    carry_scale,
+    describe,
+)
+from openflight.launch_monitor import ClubType, MonitorConfig, Shot
+from openflight.reprocess import shot_from_entry
+from openflight.sensitivity import apply_sensitivity
+
+
+class TestApplyBallType:
+    """Tests for adjusting detection thresholds to the ball."""
+
+    def test_ball_types_are_valid(self):
+        """Every ball type should give a config that validates."""
+        for name in BALL_TYPE_NAMES:
+            apply_ball_type(MonitorConfig(), name).validate()
+
+    def test_premium_is_defaults(self):
+        """premium should leave the config as it was."""
+        assert apply_ball_type(MonitorConfig(), "premium") == MonitorConfig()
+        assert describe("premium") == "defaults"
+
+    def test_foam_lowers_thresholds(self):
+        """Foam should accept slower, weaker shots with a lower smash factor."""
+        normal = MonitorConfig()
+        foam = apply_ball_type(normal, "foam")
+
+        for name in ("min_ball_speed_mph", "min_magnitude", "min_shot_magnitude"):
+            assert getattr(foam, name) < getattr(normal, name)
+        assert foam.smash_factor_min == 1.0
+        assert foam.smash_factor_max < normal.smash_factor_max
+
+    def test_range_smash_window_applies_with_club(self):
+        """A range ball's smash window should apply with a driver, not the driver's loft window."""
+        from openflight.launch_monitor import LaunchMonitor
+        from openflight.ops243 import Direction, SpeedReading
+
+        def club_speed(config, ball_speed):
+            monitor = LaunchMonitor(use_iq_streaming=False, config=config)
+            monitor._current_club = ClubType.DRIVER
+            monitor._current_readings = [
+                SpeedReading(speed=100.0, direction=Direction.OUTBOUND, magnitude=2000, timestamp=10.0),
+                SpeedReading(
+                    speed=ball_speed, direction=Direction.OUTBOUND, magnitude=1000, timestamp=10.05
+                ),
+            ]
+            monitor._process_shot()
+            return monitor.get_shots()[-1].club_speed_mph
+
+        range_ball = apply_ball_type(MonitorConfig(), "range")
+
+        # Smash 1.55: over the driver's loft window (about 1.52), inside the range ball's 1.6
+        assert club_speed(MonitorConfig(), 155.0) is None
+        assert club_speed(range_ball, 155.0) == 100.0
+        assert club_speed(range_ball, 165.0) is None
+
+    def test_magnitudes_combine_with_sensitivity(self):
+        """Magnitude thresholds should be scaled from the preset's, not replace them."""
+        config = apply_ball_type(apply_sensitivity(MonitorConfig(), "low"), "foam")
+
+        assert config.min_shot_magnitude == pytest.approx(200 * 0.25)
+        assert config.min_magnitude == pytest.approx(40 * 0.25)
+
+    def test_unknown_ball_type(self):
+        """An unknown name should raise a ValueError listing the ball types."""
+        with pytest.raises(ValueError, match="foam"):
+            apply_ball_type(MonitorConfig(), "pro-v1")
+
+
+class TestBallTypeFor:
+    """Tests for reading a ball type from the --ball-type tag."""
+
+    def test_known_names(self):
+        """Ball type names should be recognized in any case."""
+        assert ball_type_for("Foam") == "foam"
+        assert ball_type_for(" range ") == "range"
+
+    def test_other_balls(self):
+        """Other ball tags (and none) shouldn't name a ball type."""
+        assert ball_type_for("pro-v1") is None
+        assert ball_type_for(None) is None
+
+
+class TestCarryScale:
+    """Tests for scaling carry to the ball."""
+
+    def test_scales(self):
+        """Range balls should carry less and foam be scaled up; others unchanged."""
+        assert carry_scale("range") < 1.0 < carry_scale("foam")
+        assert carry_scale("premium") == 1.0
+        assert carry_scale(None) == 1.0
+
+    def test_shot_carry(self):
+        """A shot's carry should be scaled for its ball type."""
+        shot = Shot(ball_speed_mph=100.0, timestamp=datetime.now(), club=ClubType.IRON_7)
+        premium = shot.estimated_carry_yards
+
+        shot.ball_type = "foam"
+        assert shot.estimated_carry_yards == pytest.approx(premium * carry_scale("foam"))
+
+    def test_reprocessed_shot_keeps_ball_type(self):
+        """A logged shot's ball type should carry over into reprocessing."""
+        shot = shot_from_entry({"ball_speed_mph": 90.0, "club": "7-iron", "ball_type": "foam"})
+        assert shot.ball_type == "foam"
@@ -0,0 +1,117 @@
+"""Tests for bays module."""
+
+import json
+from datetime import datetime
+
+import pytest
+
+from openflight.bays import Bay, BayConfig, load_bays
+from openflight.launch_monitor import Shot
+from openflight.ops243 import MountPosition
+
+
+def _write(tmp_path, data):
+    path = tmp_path / "bays.json"
+    path.write_text(json.dumps(data))
+    return path
+
+
+class _FakeMonitor:
+    """Stands in for LaunchMonitor without a radar."""
+
+    def __init__(self, config):
+        self.config = config
+        self.shot_callback = None
+
+    def connect(self):
+        return True
+
+    def disconnect(self):
+        pass
+
+    def start(self, shot_callback=None, live_callback=None):  # pylint: disable=unused-argument
+        self.shot_callback = shot_callback
+
+
+class TestLoadBays:
+    """Tests for bay configuration loading."""
+
+    def test_load_two_bays(self, tmp_path):
+        """Bays should load in file order with their settings."""
+        path = _write(
+            tmp_path,
+            {
+                "bays": [
+                    {"name": "bay1", "port": "/dev/ttyACM0", "webhook_urls": ["http://a/shot"]},
+                    {"name": "bay2", "port": "/dev/ttyACM1", "mount_position": "front"},
+                ]
+            },
+        )
+
+        bays = load_bays(path)
+
+        assert [b.name for b in bays] == ["bay1", "bay2"]
+        assert bays[0].webhook_urls == ["http://a/shot"]
+        assert bays[1].mount_position == MountPosition.FRONT
+
+    def test_duplicate_port_rejected(self, tmp_path):
+        """Two bays cannot share a radar."""
+        path = _write(
+            tmp_path,
+            {
+                "bays": [
+                    {"name": "bay1", "port": "/dev/ttyACM0"},
+                    {"name": "bay2", "port": "/dev/ttyACM0"},
+                ]
+            },
+        )
+
+        with pytest.raises(ValueError, match="port"):
+            load_bays(path)
+
+    def test_missing_port_rejected(self, tmp_path):
+        """Every bay needs a port."""
+        path = _write(tmp_path, {"bays": [{"name": "bay1"}]})
+
+        with pytest.raises(ValueError):
+            load_bays(path)
+
+    def test_empty_config_rejected(self, tmp_path):
+        """A config without bays should be rejected."""
+        with pytest.raises(ValueError):
+            load_bays(_write(tmp_path, {"bays": []}))
+
+
+class TestBay:
+    """Tests for per-bay shot handling."""
+
+    def test_bays_have_independent_monitors(self):
+        """Each bay should get its own monitor instance."""
+        bay1 = Bay(BayConfig(name="bay1", port="/dev/a"), monitor_factory=_FakeMonitor)
+        bay2 = Bay(BayConfig(name="bay2", port="/dev/b"), monitor_factory=_FakeMonitor)
+
+        assert bay1.monitor is not bay2.monitor
+        assert bay1.monitor.config.port == "/dev/a"
+
+    def test_shot_output_prefixed(self, capsys):
+        """Shot output should carry the bay name."""
+        bay = Bay(BayConfig(name="bay2", port="/dev/b"), monitor_factory=_FakeMonitor)
+        bay.start()
+
+        bay.monitor.shot_callback(Shot(ball_speed_mph=150.0, timestamp=datetime.now()))
+
+        assert "[bay2] Shot: ball 150.0 mph" in capsys.readouterr().out
+
+    def test_shot_forwarded_to_bay_sink(self):
+        """Shots should go to this bay's webhook sink, tagged with the bay name."""
+        bay = Bay(
+            BayConfig(name="bay1", port="/dev/a", webhook_urls=["http://sim/shot"]),
+            monitor_factory=_FakeMonitor,
+        )
+        sent = []
+        bay.sink.send = sent.append
+
+        bay.on_shot(Shot(ball_speed_mph=150.0, timestamp=datetime.now()))
+
+        assert sent[0]["bay"] == "bay1"
+        assert sent[0]["shot"]["ball_speed_mph"] == 150.0
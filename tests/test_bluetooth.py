@@ -0,0 +1,153 @@
+"""Tests for bluetooth module."""
+
+import socket
+import time
+from datetime import datetime
+
+import pytest
+
+from openflight import bluetooth
+from openflight.bluetooth import BluetoothSerialServer, LineFormat
+from openflight.launch_monitor import Shot
+
+
+def _shot():
+    return Shot(ball_speed_mph=150.0, club_speed_mph=103.4, timestamp=datetime.now())
+
+
+SHOT_DICT = {
+    "ball_speed_mph": 150.0,
+    "club_speed_mph": 103.4,
+    "smash_factor": 1.45,
+    "estimated_carry_yards": 228,
+    "launch_angle_vertical": 11.5,
+    "launch_angle_horizontal": None,
+    "spin_rpm": None,
+    "club": "driver",
+}
+
+
+class _LoopbackServer(BluetoothSerialServer):
+    """Serves over TCP on localhost instead of RFCOMM."""
+
+    ACCEPT_TIMEOUT_SEC = 0.05
+
+    def _listen(self):
+        sock = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+        sock.bind(("127.0.0.1", 0))
+        sock.listen(2)
+        return sock
+
+    @property
+    def address(self):
+        return self._sock.getsockname()
+
+
+def _wait_for(condition, timeout=2.0):
+    deadline = time.monotonic() + timeout
+    while not condition() and time.monotonic() < deadline:
+        time.sleep(0.01)
+    return condition()
+
+
+def _read_line(conn):
+    data = b""
+    while not data.endswith(b"\r\n"):
+        chunk = conn.recv(4096)
+        if not chunk:
+            break
+        data += chunk
+    return data
+
+
+class TestLineFormat:
+    """Tests for shot line formats."""
+
+    def test_json(self):
+        """JSON lines should be the shot dict, compact."""
+        line = LineFormat("json").render(_shot(), SHOT_DICT)
+
+        assert line.startswith('{"ball_speed_mph":150.0,')
+
+    def test_csv(self):
+        """CSV lines should have the fixed columns, empty where unknown."""
+        line = LineFormat("csv").render(_shot(), SHOT_DICT)
+
+        assert line == "150.0,103.4,1.45,228,11.5,,,driver"
+
+    def test_shot_card_template(self):
+        """Anything else should be a shot card."""
+        line = LineFormat("BS={ball_speed:.1f}").render(_shot(), SHOT_DICT)
+
+        assert line == "BS=150.0"
+
+    def test_invalid_format(self):
+        """Text that is neither a format nor a template should be rejected."""
+        with pytest.raises(ValueError, match="csv"):
+            LineFormat("xml")
+
+
+class TestBluetoothSerialServer:
+    """Tests for writing shot lines to connected devices."""
+
+    def test_invalid_channel(self):
+        """Channels outside 1-30 should be rejected."""
+        with pytest.raises(ValueError):
+            BluetoothSerialServer(channel=31)
+
+    def test_unavailable_without_bluez(self, monkeypatch):
+        """Starting without Bluetooth sockets should raise."""
+        monkeypatch.setattr(bluetooth, "bluetooth_available", lambda: False)
+
+        with pytest.raises(RuntimeError):
+            BluetoothSerialServer().start()
+
+    def test_shot_sent_to_clients(self):
+        """Each connected device should get the shot line with CRLF."""
+        server = _LoopbackServer(line_format="csv")
+        server.start()
+        try:
+            with socket.create_connection(server.address, timeout=2.0) as conn:
+                assert _wait_for(lambda: len(server.clients) == 1)
+
+                server.send_shot(_shot(), SHOT_DICT)
+
+                assert _read_line(conn) == b"150.0,103.4,1.45,228,11.5,,,driver\r\n"
+        finally:
+            server.stop()
+
+    def test_multiline_card_uses_crlf(self):
+        """A multi-line card should be sent as CRLF-terminated lines."""
+        server = _LoopbackServer(line_format="{ball_speed:.0f}\\n{club_speed:.0f}")
+        server.start()
+        try:
+            with socket.create_connection(server.address, timeout=2.0) as conn:
+                assert _wait_for(lambda: len(server.clients) == 1)
+
+                server.send_shot(_shot(), SHOT_DICT)
+                data = _read_line(conn)
+                while data.count(b"\r\n") < 2:
+                    data += _read_line(conn)
+
+                assert data == b"150\r\n103\r\n"
+        finally:
+            server.stop()
+
+    def test_disconnected_client_dropped(self):
+        """A device that went away should be dropped on a later write."""
+        server = _LoopbackServer()
+        server.start()
+        try:
+            conn = socket.create_connection(server.address, timeout=2.0)
+            assert _wait_for(lambda: len(server.clients) == 1)
+            conn.close()
+
+            for _ in range(20):
+                server.send_line("x" * 1024)
+                if not server.clients:
+                    break
+                time.sleep(0.01)
+
+            assert server.clients == []
+        finally:
+            server.stop()
@@ -0,0 +1,149 @@
+"""Tests for multi-sensor capture."""
+
+import pytest
+
+from openflight import capture
+from openflight.capture import (
+    AUDIO,
+    CAMERA,
+    RADAR,
+    SHOT,
+    CaptureWriter,
+    align,
+    frame_from_dict,
+    frame_to_dict,
+    read_capture,
+    readings,
+)
+from openflight.clock import SessionClock
+from openflight.frames import Direction, Point, PointCloud, SpeedReading, SpeedTarget, Target
+
+
+def _write_swing(path):
+    """A capture of one swing: club and ball readings, an impact gate, the camera and the shot."""
+    writer = CaptureWriter(str(path), clock=SessionClock(monotonic_epoch=100.0, wall_epoch=1e9))
+    writer.record_reading(SpeedReading(95.0, Direction.OUTBOUND, 800.0, timestamp=10.00))
+    ball = SpeedReading(140.0, Direction.OUTBOUND, 2000.0, timestamp=10.02)
+    ball.frame_objects = [SpeedReading(94.0, Direction.OUTBOUND, 700.0, timestamp=10.02)]
+    writer.record_reading(ball)
+    writer.record_audio_gate(10.01, trigger="sound-gpio", accepted=True)
+    writer.record_camera(12.5, -1.0, 0.9, timestamp=10.1)
+    writer.record_shot({"ball_speed_mph": 140.0, "club": "driver"}, timestamp=10.3)
+    writer.record_reading(SpeedReading(30.0, Direction.INBOUND, 300.0, timestamp=15.0))
+    writer.close()
+    return writer
+
+
+class TestFrames:
+    """Tests for storing radar frames."""
+
+    def test_speed_target_round_trip(self):
+        """A speed report should come back with its targets and directions."""
+        frame = SpeedTarget(
+            timestamp=5.0,
+            targets=[Target(140.0, Direction.OUTBOUND, 2000.0), Target(3.0, Direction.INBOUND)],
+        )
+        assert frame_from_dict(frame_to_dict(frame), 5.0) == frame
+
+    def test_point_cloud_round_trip(self):
+        """A point cloud should come back with its points."""
+        frame = PointCloud(timestamp=5.0, points=[Point(0.1, 2.0, 0.3, 60.0, 12.0)])
+        assert frame_from_dict(frame_to_dict(frame), 5.0) == frame
+
+    def test_unknown_frame(self):
+        """Unknown frame types should be rejected."""
+        with pytest.raises(ValueError):
+            frame_from_dict({"type": "Hologram"}, 0.0)
+
+
+class TestCaptureFile:
+    """Tests for writing and reading a capture."""
+
+    def test_round_trip(self, tmp_path):
+        """Every stream's records should come back in order with their times."""
+        path = tmp_path / "session.ofcap"
+        writer = _write_swing(path)
+
+        header, records = read_capture(str(path))
+        records = list(records)
+
+        assert header["format"] == "openflight-capture"
+        assert header["clock"] == {"monotonic_epoch": 100.0, "wall_epoch": 1e9}
+        assert [r.stream for r in records] == [RADAR, RADAR, AUDIO, CAMERA, SHOT, RADAR]
+        assert records[2].timestamp == 10.01
+        assert records[3].data["vertical"] == 12.5
+        assert writer.counts[RADAR] == 3
+
+    def test_readings_for_replay(self, tmp_path):
+        """Radar records should replay as the readings that were recorded."""
+        path = tmp_path / "session.ofcap"
+        _write_swing(path)
+
+        replayed = list(readings(read_capture(str(path))[1]))
+
+        assert [r.speed for r in replayed] == [95.0, 140.0, 30.0]
+        assert replayed[1].timestamp == 10.02
+        assert [r.speed for r in replayed[1].frame_objects] == [94.0]
+
+    def test_truncated_record(self, tmp_path):
+        """A record cut off mid-write (power loss) should end the capture, not fail it."""
+        path = tmp_path / "session.ofcap"
+        _write_swing(path)
+        path.write_bytes(path.read_bytes()[:-5])
+
+        records = list(read_capture(str(path))[1])
+        assert [r.stream for r in records][-1] == SHOT
+
+    def test_not_a_capture(self, tmp_path):
+        """Other files should be rejected."""
+        path = tmp_path / "session.jsonl"
+        path.write_text('{"type": "session_start"}\n')
+        with pytest.raises(ValueError):
+            read_capture(str(path))
+
+    def test_closed_writer_ignores_records(self, tmp_path):
+        """Records after close (e.g. a late reading at shutdown) should be dropped."""
+        writer = _write_swing(tmp_path / "session.ofcap")
+        writer.record_shot({"ball_speed_mph": 1.0})
+        assert writer.counts[SHOT] == 1
+
+
+class TestAlign:
+    """Tests for lining up the streams."""
+
+    def test_align_on_shot(self, tmp_path):
+        """Each shot should get the strongest radar frame and nearest gate and camera event."""
+        path = tmp_path / "session.ofcap"
+        _write_swing(path)
+
+        events = align(list(read_capture(str(path))[1]), SHOT)
+
+        assert len(events) == 1
+        assert events[0].nearest[RADAR].data["targets"][0]["speed"] == 140.0
+        assert events[0].offset_sec(AUDIO) == pytest.approx(-0.29)
+        assert events[0].offset_sec(CAMERA) == pytest.approx(-0.2)
+
+    def test_nothing_in_window(self, tmp_path):
+        """Streams with nothing near the anchor should have no offset."""
+        path = tmp_path / "session.ofcap"
+        _write_swing(path)
+
+        events = align(list(read_capture(str(path))[1]), AUDIO, window_sec=0.05)
+
+        assert events[0].offset_sec(RADAR) == pytest.approx(0.01)
+        assert events[0].offset_sec(CAMERA) is None
+
+
+class TestMain:
+    """Tests for the openflight-capture command."""
+
+    def test_summary_and_align(self, tmp_path, capsys):
+        """The summary should count each stream and --align show the offsets in ms."""
+        path = tmp_path / "session.ofcap"
+        _write_swing(path)
+
+        assert capture.main([str(path), "--align"]) == 0
+
+        out = capsys.readouterr().out
+        assert "radar    3" in out
+        assert "-290" in out
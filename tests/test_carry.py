@@ -0,0 +1,144 @@
+"""Tests for carry module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.carry import (
+    CorrectedCarryModel,
+    PhysicsCarryModel,
+    PolynomialCarryModel,
+    TableCarryModel,
+    carry_model_from_dict,
+    fit_correction,
+    get_carry_model,
+    get_carry_model_specs,
+    parse_carry_models,
+    set_carry_models,
+)
+from openflight.launch_monitor import ClubType, Shot, estimate_carry_distance
+
+
+@pytest.fixture(autouse=True)
+def reset_carry_models():
+    """Restore the default (table for every club) after each test."""
+    yield
+    set_carry_models({})
+
+
+def _shot(ball_speed=150.0, club=ClubType.DRIVER, **kwargs):
+    return Shot(ball_speed_mph=ball_speed, timestamp=datetime.now(), club=club, **kwargs)
+
+
+class TestModels:
+    """Tests for the individual carry models."""
+
+    def test_table_matches_lookup(self):
+        """The table model should reproduce estimate_carry_distance."""
+        shot = _shot(120.0, ClubType.IRON_7)
+
+        assert TableCarryModel().estimate(shot) == estimate_carry_distance(120.0, ClubType.IRON_7)
+
+    def test_physics_matches_tour_driver(self):
+        """Tour-average driver launch conditions should carry about 275 yards."""
+        carry = PhysicsCarryModel().simulate(167, 10.9, 2686)
+
+        assert 265 < carry < 290
+
+    def test_physics_uses_measured_launch(self):
+        """A badly low launch should carry shorter than an optimal one."""
+        model = PhysicsCarryModel()
+        optimal = model.estimate(_shot(150.0, launch_angle_vertical=11.0))
+        low = model.estimate(_shot(150.0, launch_angle_vertical=4.0))
+
+        assert low < optimal
+
+    def test_polynomial(self):
+        """Coefficients are lowest order first, in ball speed mph."""
+        model = PolynomialCarryModel([10.0, 1.5, 0.001])
+
+        assert model.estimate(_shot(100.0)) == pytest.approx(10 + 150 + 10)
+
+    def test_polynomial_never_negative(self):
+        """Very slow shots should not produce negative carry."""
+        assert PolynomialCarryModel([-50.0, 1.0]).estimate(_shot(20.0)) == 0.0
+
+
+class TestCorrection:
+    """Tests for measured-carry correction curves."""
+
+    def test_correction_applied_to_base(self):
+        """A correction entry should wrap the base model's estimate."""
+        model = carry_model_from_dict(
+            {"model": "polynomial", "coefficients": [200.0], "correction": [5.0, 0.9]}
+        )
+
+        assert isinstance(model, CorrectedCarryModel)
+        assert model.estimate(_shot()) == pytest.approx(185.0)
+        assert model.to_dict()["correction"] == [5.0, 0.9]
+
+    def test_fit_linear(self):
+        """Enough spread in the estimates should recover offset and scale."""
+        estimated = [150.0, 180.0, 210.0, 240.0]
+        actual = [10.0 + 0.95 * e for e in estimated]
+
+        assert fit_correction(estimated, actual) == pytest.approx([10.0, 0.95])
+
+    def test_fit_scale_only_for_few_shots(self):
+        """Two shots, or shots all about the same distance, only fit a scale."""
+        assert fit_correction([200.0, 202.0], [190.0, 191.9]) == pytest.approx(
+            [0.0, 0.95], rel=1e-3
+        )
+        assert fit_correction([200.0, 201.0, 199.0], [190.0, 190.95, 189.05])[0] == 0.0
+
+    def test_fit_rejects_mismatched(self):
+        """Estimates and measurements must pair up."""
+        with pytest.raises(ValueError):
+            fit_correction([200.0], [])
+
+
+class TestSelection:
+    """Tests for per-club model selection."""
+
+    def test_default_is_table(self):
+        """With nothing configured every club uses the table model."""
+        assert isinstance(get_carry_model(ClubType.IRON_7), TableCarryModel)
+
+    def test_per_club_with_default(self):
+        """Listed clubs get their model; others fall back to the default entry."""
+        set_carry_models({"default": {"model": "physics"}, "7-iron": {"model": "table"}})
+
+        assert isinstance(get_carry_model(ClubType.IRON_7), TableCarryModel)
+        assert isinstance(get_carry_model(ClubType.DRIVER), PhysicsCarryModel)
+
+    def test_shot_carry_uses_selected_model(self):
+        """Shot.estimated_carry_yards should go through the selected model."""
+        set_carry_models({"driver": {"model": "polynomial", "coefficients": [200.0]}})
+
+        assert _shot(150.0).estimated_carry_yards == 200.0
+        assert _shot(150.0, ClubType.IRON_7).estimated_carry_yards != 200.0
+
+    def test_specs_round_trip(self):
+        """The active selection should serialize back to profile entries."""
+        specs = {"driver": {"model": "physics"}, "default": {"model": "table"}}
+        set_carry_models(specs)
+
+        assert get_carry_model_specs() == specs
+
+    def test_invalid_entries_rejected(self):
+        """Unknown models, clubs and missing coefficients should fail validation."""
+        with pytest.raises(ValueError):
+            carry_model_from_dict({"model": "magic"})
+        with pytest.raises(ValueError):
+            carry_model_from_dict({"model": "polynomial"})
+        with pytest.raises(ValueError):
+            parse_carry_models({"11-iron": {"model": "table"}})
+
+    def test_invalid_selection_keeps_current(self):
+        """A failed update should leave the previous selection active."""
+        set_carry_models({"driver": {"model": "physics"}})
+
+        with pytest.raises(ValueError):
+            set_carry_models({"driver": {"model": "magic"}})
+
+        assert isinstance(get_carry_model(ClubType.DRIVER), PhysicsCarryModel)
@@ -0,0 +1,114 @@
+"""Tests for carry_calibration module."""
+
+import json
+
+import pytest
+
+from openflight.carry import set_carry_models
+from openflight.carry_calibration import (
+    apply_fits,
+    base_spec,
+    build_samples,
+    fit_clubs,
+    load_shots,
+    read_carries_csv,
+)
+from openflight.launch_monitor import ClubType
+from openflight.profiles import CalibrationProfile, load_profile, save_profile
+
+
+@pytest.fixture(autouse=True)
+def reset_carry_models():
+    """Restore the default carry models after each test."""
+    yield
+    set_carry_models({})
+
+
+def _shot_entry(number, ball_speed, club="driver", mode="streaming"):
+    return {
+        "type": "shot_detected",
+        "shot_number": number,
+        "ball_speed_mph": ball_speed,
+        "club": club,
+        "mode": mode,
+    }
+
+
+def _write_session(path, entries):
+    path.write_text("\n".join(json.dumps(e) for e in entries) + "\n")
+    return str(path)
+
+
+class TestLoading:
+    """Tests for reading shots and measured carries."""
+
+    def test_load_shots_skips_non_radar(self, tmp_path):
+        """Only radar shot entries should be offered for calibration."""
+        path = _write_session(
+            tmp_path / "session_a.jsonl",
+            [
+                {"type": "session_start"},
+                _shot_entry(1, 150.0),
+                _shot_entry(2, 150.0, mode="mock"),
+                _shot_entry(3, 150.0, mode="injected"),
+            ],
+        )
+
+        shots = load_shots([path])
+
+        assert [(s, e["shot_number"]) for s, e in shots] == [("session_a", 1)]
+
+    def test_read_carries_csv(self, tmp_path):
+        """The session column is optional."""
+        path = tmp_path / "carries.csv"
+        path.write_text("shot_number,carry_yards,session\n1,231,session_a\n2,224,\n")
+
+        assert read_carries_csv(str(path)) == {("session_a", 1): 231.0, (None, 2): 224.0}
+
+    def test_read_carries_csv_invalid(self, tmp_path):
+        """A malformed row should name the file and line."""
+        path = tmp_path / "carries.csv"
+        path.write_text("shot_number,carry_yards\n1,far\n")
+
+        with pytest.raises(ValueError, match="carries.csv:2"):
+            read_carries_csv(str(path))
+
+
+class TestFitting:
+    """Tests for fitting and storing per-club corrections."""
+
+    def test_fit_per_club_and_store(self, tmp_path):
+        """Each club gets its own correction, saved on top of its base model."""
+        profile = CalibrationProfile(
+            name="range", carry_models={"driver": {"model": "physics"}}
+        )
+        shots = [
+            ("s", _shot_entry(1, 140.0)),
+            ("s", _shot_entry(2, 150.0)),
+            ("s", _shot_entry(3, 160.0)),
+            ("s", _shot_entry(4, 115.0, club="7-iron")),
+            ("s", _shot_entry(5, 999.0)),
+        ]
+        samples = build_samples(profile, shots, {(None, n): 200.0 for n in range(1, 5)})
+        fits = fit_clubs(samples)
+
+        assert len(samples) == 4
+        assert fits[ClubType.DRIVER].samples == 3
+        assert fits[ClubType.DRIVER].rms_after < fits[ClubType.DRIVER].rms_before
+
+        apply_fits(profile, fits)
+        save_profile(profile, tmp_path)
+        saved = load_profile("range", tmp_path)
+
+        assert saved.carry_models["driver"]["model"] == "physics"
+        assert saved.carry_models["driver"]["correction"] == fits[ClubType.DRIVER].correction
+        assert saved.carry_models["7-iron"]["model"] == "table"
+
+    def test_recalibration_does_not_compound(self):
+        """Estimates should come from the model without its old correction."""
+        profile = CalibrationProfile(
+            name="range",
+            carry_models={"default": {"model": "physics", "correction": [0.0, 2.0]}},
+        )
+
+        assert base_spec(profile, ClubType.IRON_7) == {"model": "physics"}
@@ -0,0 +1,123 @@
+"""Tests for classifier module."""
+
+import json
+
+import pytest
+
+from openflight.classifier import (
+    FEATURES,
+    ShotClassifier,
+    extract_features,
+    load_classifier,
+    save_classifier,
+)
+from openflight.launch_monitor import LaunchMonitor
+from openflight.ops243 import Direction, SpeedReading
+
+
+def _reading(speed, timestamp, magnitude=None, frame_objects=None):
+    return SpeedReading(
+        speed=speed,
+        direction=Direction.OUTBOUND,
+        magnitude=magnitude,
+        timestamp=timestamp,
+        frame_objects=frame_objects,
+    )
+
+
+def _classifier(weights, bias=0.0, threshold=0.5):
+    """A model over raw feature values (mean 0, scale 1)."""
+    n = len(FEATURES)
+    return ShotClassifier(
+        weights=weights, bias=bias, mean=[0.0] * n, scale=[1.0] * n, threshold=threshold
+    )
+
+
+class TestExtractFeatures:
+    """Tests for cluster features."""
+
+    def test_features(self):
+        """Features should describe count, timing, speeds, magnitude and frames."""
+        readings = [
+            _reading(100.0, 10.0, magnitude=999.0),
+            _reading(150.0, 10.05, frame_objects=[_reading(101.0, None)]),
+            _reading(148.0, 10.1),
+        ]
+
+        features = extract_features(readings)
+
+        assert list(features) == FEATURES
+        assert features["reading_count"] == 3
+        assert features["duration_sec"] == pytest.approx(0.1)
+        assert features["peak_speed_mph"] == 150.0
+        assert features["speed_spread_mph"] == 50.0
+        assert features["log_peak_magnitude"] == pytest.approx(6.9078, abs=1e-3)
+        assert features["max_frame_objects"] == 1
+        assert features["peak_position"] == 0.5
+
+    def test_empty_cluster(self):
+        """No readings should give all-zero features rather than raising."""
+        assert set(extract_features([]).values()) == {0.0}
+
+
+class TestShotClassifier:
+    """Tests for scoring and model files."""
+
+    def test_probability(self):
+        """The score should be the sigmoid of the standardized linear model."""
+        weights = [0.0] * len(FEATURES)
+        weights[FEATURES.index("peak_speed_mph")] = 0.1
+        model = _classifier(weights, bias=-10.0)
+
+        assert model.classify([_reading(100.0, 0.0)]) == pytest.approx(0.5)
+        assert model.classify([_reading(150.0, 0.0)]) > 0.99
+        assert model.classify([_reading(40.0, 0.0)]) < 0.01
+
+    def test_save_and_load(self, tmp_path):
+        """A saved model should load back unchanged."""
+        model = _classifier([0.5] * len(FEATURES), bias=1.0, threshold=0.7)
+        path = tmp_path / "model.json"
+
+        save_classifier(model, str(path))
+
+        assert json.loads(path.read_text())["version"] == 1
+        assert load_classifier(str(path)) == model
+
+    def test_malformed_models_rejected(self, tmp_path):
+        """Wrong lengths, unknown features, newer versions and non-JSON should raise ValueError."""
+        good = _classifier([0.0] * len(FEATURES)).to_dict()
+        path = tmp_path / "model.json"
+
+        with pytest.raises(ValueError):
+            ShotClassifier.from_dict({**good, "weights": [1.0]})
+        with pytest.raises(ValueError):
+            ShotClassifier.from_dict({**good, "features": ["x"] + FEATURES[1:]})
+        with pytest.raises(ValueError):
+            ShotClassifier.from_dict({**good, "version": 99})
+        path.write_text("not json")
+        with pytest.raises(ValueError):
+            load_classifier(str(path))
+
+
+class TestMonitorWithClassifier:
+    """Tests for the classifier replacing the rule-based filters."""
+
+    def _monitor(self, classifier):
+        return LaunchMonitor(
+            detect_club_speed=False, use_iq_streaming=False, classifier=classifier
+        )
+
+    def test_classifier_decides(self):
+        """A long, weak cluster the rules reject should be kept if the model says shot."""
+        readings = [_reading(150.0, 0.0, magnitude=50.0), _reading(151.0, 0.5, magnitude=50.0)]
+        keep = self._monitor(_classifier([0.0] * len(FEATURES), bias=5.0))
+        drop = self._monitor(_classifier([0.0] * len(FEATURES), bias=-5.0))
+
+        for monitor in (keep, drop):
+            monitor._current_readings = list(readings)
+            monitor._process_shot()
+
+        assert [s.ball_speed_mph for s in keep._shots] == [151.0]
+        assert drop._shots == []
+        assert keep._current_readings == [] and drop._current_readings == []
+
@@ -0,0 +1,131 @@
+"""Tests for classifier_training module."""
+
+import json
+
+import pytest
+
+from openflight.classifier import load_classifier, save_classifier
+from openflight.classifier_training import (
+    Example,
+    evaluate,
+    load_examples,
+    load_labels,
+    split_examples,
+    train,
+)
+
+
+def _shot_entry(number, speeds, magnitude, mode="streaming"):
+    return {
+        "type": "shot_detected",
+        "shot_number": number,
+        "ball_speed_mph": max(speeds),
+        "mode": mode,
+        "readings": [
+            {"speed": s, "direction": "outbound", "magnitude": magnitude, "timestamp": 0.03 * i}
+            for i, s in enumerate(speeds)
+        ],
+    }
+
+
+def _examples():
+    """Real shots are fast and strong; non-shots slow and weak."""
+    real = [Example("s", i, {}, True) for i in range(10)]
+    walk = [Example("s", 10 + i, {}, False) for i in range(10)]
+    for i, e in enumerate(real):
+        e.features = _features(140.0 + i, 7.0 + 0.1 * i)
+    for i, e in enumerate(walk):
+        e.features = _features(40.0 + i, 4.0 + 0.1 * i)
+    return real + walk
+
+
+def _features(speed, log_magnitude):
+    return {
+        "reading_count": 5.0,
+        "duration_sec": 0.1,
+        "peak_speed_mph": speed,
+        "speed_spread_mph": 10.0,
+        "log_peak_magnitude": log_magnitude,
+        "max_frame_objects": 0.0,
+        "peak_position": 1.0,
+    }
+
+
+class TestLoading:
+    """Tests for reading labels and extracting examples."""
+
+    def test_load_labels(self, tmp_path):
+        """Labels should be keyed by session and integer shot number."""
+        path = tmp_path / "labels.json"
+        path.write_text(json.dumps({"session_a": {"1": True, "2": False}}))
+
+        assert load_labels(str(path)) == {("session_a", 1): True, ("session_a", 2): False}
+
+    def test_load_labels_invalid(self, tmp_path):
+        """Non-boolean labels and non-numeric shot numbers should raise ValueError."""
+        path = tmp_path / "labels.json"
+        for data in ({"s": {"1": "yes"}}, {"s": {"one": True}}, ["s"], {"s": [1]}):
+            path.write_text(json.dumps(data))
+            with pytest.raises(ValueError):
+                load_labels(str(path))
+
+    def test_load_examples(self, tmp_path):
+        """Only labeled radar shots with readings should become examples."""
+        entries = [
+            {"type": "session_start"},
+            _shot_entry(1, [100.0, 150.0], 900.0),
+            _shot_entry(2, [30.0, 31.0], 50.0),
+            _shot_entry(3, [120.0], 800.0),  # Unlabeled
+            _shot_entry(4, [120.0], 800.0, mode="mock"),
+            {**_shot_entry(5, [120.0], 800.0), "readings": None},
+        ]
+        (tmp_path / "session_a.jsonl").write_text("\n".join(json.dumps(e) for e in entries))
+        (tmp_path / "session_a.reprocessed.jsonl").write_text(json.dumps(entries[1]))
+        labels = {("session_a", n): n != 2 for n in (1, 2, 4, 5)}
+
+        examples = load_examples(str(tmp_path), labels)
+
+        assert [(e.shot_number, e.is_shot) for e in examples] == [(1, True), (2, False)]
+        assert examples[0].features["peak_speed_mph"] == 150.0
+        assert examples[0].features["reading_count"] == 2
+
+
+class TestTraining:
+    """Tests for fitting and evaluating a model."""
+
+    def test_separable_data(self):
+        """A model trained on separable data should classify it correctly."""
+        examples = _examples()
+
+        classifier = train(examples, epochs=300)
+        metrics = evaluate(classifier, examples)
+
+        assert metrics["accuracy"] == 1.0
+        assert metrics["precision"] == 1.0 and metrics["recall"] == 1.0
+        assert metrics["log_loss"] < 0.2
+
+    def test_needs_both_classes(self):
+        """Training on only real shots should raise ValueError."""
+        with pytest.raises(ValueError):
+            train([e for e in _examples() if e.is_shot])
+
+    def test_split_is_repeatable(self):
+        """The same seed should give the same split, with the requested share held out."""
+        examples = _examples()
+
+        training, test = split_examples(examples, 0.25, seed=3)
+
+        assert len(test) == 5 and len(training) == 15
+        assert split_examples(examples, 0.25, seed=3) == (training, test)
+
+    def test_model_file_round_trip(self, tmp_path):
+        """A trained model written to disk should load with the runtime loader."""
+        classifier = train(_examples(), threshold=0.7, epochs=50)
+        classifier.metrics = {"test": evaluate(classifier, _examples())}
+        path = tmp_path / "model.json"
+        save_classifier(classifier, str(path))
+
+        loaded = load_classifier(str(path))
+
+        assert loaded.threshold == 0.7
+        assert loaded.metrics["test"]["examples"] == 20
@@ -0,0 +1,43 @@
+"""Tests for clock module."""
+
+from datetime import datetime
+
+from openflight import clock
+from openflight.clock import SessionClock
+
+
+class TestSessionClock:
+    """Tests for the monotonic to wall-clock mapping."""
+
+    def test_to_wall(self):
+        """Monotonic times should map by their offset from the epoch."""
+        mapping = SessionClock(monotonic_epoch=100.0, wall_epoch=1_700_000_000.0)
+
+        assert mapping.to_wall(100.0) == 1_700_000_000.0
+        assert mapping.to_wall(102.5) == 1_700_000_002.5
+        assert mapping.to_wall(None) is None
+
+    def test_to_datetime(self):
+        """to_datetime should give the local time of the mapped timestamp."""
+        mapping = SessionClock(monotonic_epoch=100.0, wall_epoch=1_700_000_000.0)
+
+        assert mapping.to_datetime(101.0) == datetime.fromtimestamp(1_700_000_001.0)
+
+    def test_unaffected_by_wall_clock_steps(self, monkeypatch):
+        """A wall clock step after the mapping is taken should not move mapped times."""
+        monkeypatch.setattr(clock.time, "monotonic", lambda: 50.0)
+        monkeypatch.setattr(clock.time, "time", lambda: 1_000.0)
+        mapping = SessionClock.start()
+
+        monkeypatch.setattr(clock.time, "time", lambda: 5_000.0)  # NTP step
+        assert mapping.to_wall(60.0) == 1_010.0
+
+    def test_reset_clock(self, monkeypatch):
+        """reset_clock should replace the current mapping."""
+        monkeypatch.setattr(clock.time, "monotonic", lambda: 7.0)
+        monkeypatch.setattr(clock.time, "time", lambda: 2_000.0)
+
+        mapping = clock.reset_clock()
+
+        assert clock.get_clock() is mapping
+        assert mapping.to_dict() == {"monotonic_epoch": 7.0, "wall_epoch": 2_000.0}
@@ -0,0 +1,215 @@
+"""Tests for cloud_sync module."""
+
+import sys
+import threading
+from datetime import datetime, timezone
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import pytest
+
+from openflight import cloud_sync
+from openflight.cloud_sync import (
+    CloudSync,
+    S3Uploader,
+    UploadError,
+    WebDavUploader,
+    make_uploader,
+    session_files,
+)
+from openflight.voice_memo import memo_path
+
+
+class _DavServer:
+    """Local WebDAV-ish endpoint that stores PUTs and replies with scripted codes."""
+
+    def __init__(self, status_codes=None):
+        self.files = {}
+        self.methods = []
+        self.auth = []
+        self.status_codes = list(status_codes or [])
+        server = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def _reply(self, code):
+                self.send_response(code)
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            def do_MKCOL(self):  # pylint: disable=invalid-name
+                server.methods.append("MKCOL")
+                self._reply(405)
+
+            def do_PUT(self):  # pylint: disable=invalid-name
+                body = self.rfile.read(int(self.headers.get("Content-Length", 0)))
+                server.methods.append("PUT")
+                server.auth.append(self.headers.get("Authorization"))
+                code = server.status_codes.pop(0) if server.status_codes else 201
+                if code < 300:
+                    server.files[self.path] = body
+                self._reply(code)
+
+            def log_message(self, *args):
+                pass
+
+        self.server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self.server.server_address[1]}/dav/golf"
+        threading.Thread(target=self.server.serve_forever, daemon=True).start()
+
+    def close(self):
+        self.server.shutdown()
+        self.server.server_close()
+
+
+@pytest.fixture
+def dav():
+    """Start a local WebDAV server."""
+    server = _DavServer()
+    yield server
+    server.close()
+
+
+def _session(tmp_path, name="session_20250601_100000_range.jsonl", text='{"type": "x"}\n'):
+    path = tmp_path / name
+    path.write_text(text)
+    return str(path)
+
+
+class TestMakeUploader:
+    """Tests for choosing an uploader from a target URL."""
+
+    def test_s3_from_env(self):
+        """S3 targets should take credentials and region from the environment."""
+        env = {"AWS_ACCESS_KEY_ID": "AK", "AWS_SECRET_ACCESS_KEY": "SK", "AWS_REGION": "eu-west-1"}
+
+        uploader = make_uploader("s3://bucket/golf/", env=env)
+
+        assert isinstance(uploader, S3Uploader)
+        assert uploader.object_url("a b.jsonl") == (
+            "https://s3.eu-west-1.amazonaws.com/bucket/golf/a%20b.jsonl"
+        )
+
+    def test_s3_needs_credentials(self):
+        """S3 without credentials should be a configuration error."""
+        with pytest.raises(ValueError):
+            make_uploader("s3://bucket/golf", env={})
+
+    def test_webdav_and_unknown(self):
+        """http(s) targets are WebDAV; anything else is rejected."""
+        assert isinstance(make_uploader("https://nas/dav/", env={}), WebDavUploader)
+        with pytest.raises(ValueError):
+            make_uploader("ftp://nas/golf", env={})
+
+
+class TestS3Signing:
+    """Tests for AWS Signature Version 4 headers."""
+
+    def test_signature_fields(self):
+        """Signed headers should cover the payload hash, date and session token."""
+        uploader = S3Uploader("bucket", "", "AKID", "secret", region="us-west-2",
+                              session_token="tok")
+        now = datetime(2025, 6, 1, 12, 0, tzinfo=timezone.utc)
+
+        headers = uploader.signed_headers(uploader.object_url("s.jsonl"), b"{}", now)
+
+        assert headers["x-amz-date"] == "20250601T120000Z"
+        assert headers["x-amz-security-token"] == "tok"
+        assert headers["Authorization"].startswith(
+            "AWS4-HMAC-SHA256 Credential=AKID/20250601/us-west-2/s3/aws4_request, "
+            "SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token, "
+            "Signature="
+        )
+        # Same request, same signature; different body, different signature
+        again = uploader.signed_headers(uploader.object_url("s.jsonl"), b"{}", now)
+        other = uploader.signed_headers(uploader.object_url("s.jsonl"), b"[]", now)
+        assert again["Authorization"] == headers["Authorization"]
+        assert other["Authorization"] != headers["Authorization"]
+
+
+class TestCloudSync:
+    """Tests for the persistent upload queue."""
+
+    def test_uploads_to_webdav(self, tmp_path, dav):
+        """Queued sessions should be PUT into the collection with basic auth."""
+        path = _session(tmp_path)
+        sync = CloudSync(WebDavUploader(dav.url, "me", "pw"), tmp_path, backoff_sec=0.01)
+
+        sync.enqueue(path)
+        result = sync.sync_pending()
+
+        assert result.uploaded == 1
+        assert dav.files["/dav/golf/session_20250601_100000_range.jsonl"] == b'{"type": "x"}\n'
+        assert dav.methods[0] == "MKCOL"
+        assert dav.auth[0].startswith("Basic ")
+
+    def test_retries_transient_failures(self, tmp_path, dav):
+        """5xx responses should be retried until the upload succeeds."""
+        dav.status_codes = [503, 500]
+        sync = CloudSync(WebDavUploader(dav.url), tmp_path, backoff_sec=0.01)
+
+        sync.enqueue(_session(tmp_path))
+        result = sync.sync_pending()
+
+        assert result.uploaded == 1
+        assert dav.methods.count("PUT") == 3
+
+    def test_failed_upload_resumes_after_restart(self, tmp_path, dav):
+        """Files that never got through should still be queued in a new process."""
+        dav.status_codes = [503, 503]
+        path = _session(tmp_path)
+        first = CloudSync(WebDavUploader(dav.url), tmp_path, max_attempts=2, backoff_sec=0.01)
+        first.enqueue(path)
+
+        assert first.sync_pending().failed == 1
+
+        second = CloudSync(WebDavUploader(dav.url), tmp_path, backoff_sec=0.01)
+        assert second.pending == [str((tmp_path / "session_20250601_100000_range.jsonl"))]
+        assert second.sync_pending().uploaded == 1
+
+    def test_skips_unchanged_and_resends_changed(self, tmp_path, dav):
+        """An uploaded file is only sent again once it changes."""
+        path = _session(tmp_path)
+        sync = CloudSync(WebDavUploader(dav.url), tmp_path, backoff_sec=0.01)
+        sync.enqueue(path)
+        sync.sync_pending()
+
+        assert not sync.needs_upload(path)
+        with open(path, "a", encoding="utf-8") as f:
+            f.write('{"type": "y"}\n')
+        assert sync.needs_upload(path)
+
+    def test_rejected_upload_not_retried(self, tmp_path):
+        """A non-retryable error should give up after one attempt."""
+
+        class _Rejecting:
+            calls = 0
+
+            def describe(self):
+                return "test://"
+
+            def upload(self, name, body):
+                self.calls += 1
+                raise UploadError("HTTP 403", retryable=False)
+
+        uploader = _Rejecting()
+        sync = CloudSync(uploader, tmp_path, backoff_sec=0.01)
+        sync.enqueue(_session(tmp_path))
+
+        assert sync.sync_pending().failed == 1
+        assert uploader.calls == 1
+
+
+class TestSyncCli:
+    """Tests for the openflight-sync command."""
+
+    def test_uploads_sessions_with_voice_memos(self, tmp_path, dav, monkeypatch):
+        """Each session log should go up with its shots' voice memos."""
+        path = _session(tmp_path)
+        memo = memo_path(tmp_path / "session_20250601_100000_range.jsonl", 3)
+        memo.write_bytes(b"RIFF")
+        monkeypatch.setattr(sys, "argv", ["openflight-sync", dav.url, "--log-dir", str(tmp_path)])
+
+        cloud_sync.main()
+
+        assert session_files(path) == [path, str(memo)]
+        assert dav.files["/dav/golf/session_20250601_100000_range_shot003.wav"] == b"RIFF"
+        assert "/dav/golf/session_20250601_100000_range.jsonl" in dav.files
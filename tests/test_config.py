@@ -0,0 +1,203 @@
+"""Tests for layered configuration."""
+
+import argparse
+
+import pytest
+
+from openflight.config import (
+    COMMAND_LINE_SOURCE,
+    DEFAULT_SOURCE,
+    ConfigLayers,
+    apply_env_defaults,
+    build_monitor_layers,
+    option_sources,
+    parse_assignments,
+)
+from openflight.launch_monitor import MonitorConfig
+from openflight.profiles import CalibrationProfile
+from openflight.shot_timing import ShotTiming
+
+
+def _parser():
+    """A parser with one option of each kind openflight-server uses."""
+    parser = argparse.ArgumentParser()
+    parser.add_argument("--port")
+    parser.add_argument("--web-port", type=int, default=8080)
+    parser.add_argument("--mock", action="store_true")
+    parser.add_argument("--units", choices=["imperial", "metric"], default="imperial")
+    parser.add_argument("--webhook-url", action="append", default=[])
+    parser.add_argument("--status-socket", nargs="?", const="/tmp/status.sock")
+    parser.add_argument("--supervise", action="store_true")
+    return parser
+
+
+class TestEnvDefaults:
+    """Tests for OPENFLIGHT_<OPTION> variables."""
+
+    def test_variables_become_defaults(self):
+        """Each kind of option should parse from its variable as from the command line."""
+        parser = _parser()
+        used = apply_env_defaults(
+            parser,
+            {
+                "OPENFLIGHT_WEB_PORT": "8081",
+                "OPENFLIGHT_MOCK": "yes",
+                "OPENFLIGHT_WEBHOOK_URL": "http://a, http://b",
+                "OPENFLIGHT_STATUS_SOCKET": "true",
+            },
+        )
+        args = parser.parse_args([])
+
+        assert args.web_port == 8081
+        assert args.mock is True
+        assert args.webhook_url == ["http://a", "http://b"]
+        assert args.status_socket == "/tmp/status.sock"
+        assert used["web_port"] == "OPENFLIGHT_WEB_PORT"
+
+    def test_command_line_wins(self):
+        """An option on the command line should override its variable."""
+        parser = _parser()
+        apply_env_defaults(parser, {"OPENFLIGHT_WEB_PORT": "8081", "OPENFLIGHT_PORT": "/dev/a"})
+
+        args = parser.parse_args(["--web-port", "9000"])
+
+        assert args.web_port == 9000
+        assert args.port == "/dev/a"
+
+    def test_process_modes_not_read(self):
+        """--supervise can't be turned on from the environment."""
+        parser = _parser()
+
+        assert apply_env_defaults(parser, {"OPENFLIGHT_SUPERVISE": "1"}) == {}
+        assert parser.parse_args([]).supervise is False
+
+    @pytest.mark.parametrize(
+        "name, value, message",
+        [
+            ("OPENFLIGHT_WEB_PORT", "eighty", "OPENFLIGHT_WEB_PORT: invalid value"),
+            ("OPENFLIGHT_UNITS", "furlongs", "OPENFLIGHT_UNITS: 'furlongs' is not one of"),
+            ("OPENFLIGHT_MOCK", "maybe", "OPENFLIGHT_MOCK: expected 1/true"),
+        ],
+    )
+    def test_invalid_values_name_variable(self, name, value, message):
+        """A bad value should be reported against its variable."""
+        with pytest.raises(ValueError, match=message):
+            apply_env_defaults(_parser(), {name: value})
+
+    def test_sources(self):
+        """Each option should be credited to the command line, a variable or the default."""
+        parser = _parser()
+        used = apply_env_defaults(parser, {"OPENFLIGHT_WEB_PORT": "8081", "OPENFLIGHT_MOCK": "1"})
+        argv = ["--mock"]
+
+        sources = option_sources(parser, argv, used)
+
+        assert sources["mock"] == COMMAND_LINE_SOURCE
+        assert sources["web_port"] == "env OPENFLIGHT_WEB_PORT"
+        assert sources["units"] == DEFAULT_SOURCE
+        assert parser.parse_args([]).web_port == 8081  # Defaults restored afterwards
+
+
+class TestMonitorLayers:
+    """Tests for layering the detection thresholds."""
+
+    def test_layer_order(self):
+        """Profile < environment < preset < --set, each value credited to its layer."""
+        profile = CalibrationProfile(
+            name="garage",
+            monitor=MonitorConfig(min_shot_magnitude=120, min_magnitude=30, shot_timeout_sec=0.4),
+        )
+        environ = {
+            "OPENFLIGHT_MONITOR_MIN_SHOT_MAGNITUDE": "90",
+            "OPENFLIGHT_MONITOR_MIN_MAGNITUDE": "25",
+        }
+
+        layers = build_monitor_layers(
+            profile,
+            environ,
+            sensitivity="high",
+            assignments={"min_magnitude": 12},
+        )
+
+        assert layers.config.shot_timeout_sec == 0.4
+        assert layers.sources["shot_timeout_sec"] == "profile garage"
+        assert layers.config.min_shot_magnitude == 50
+        assert layers.sources["min_shot_magnitude"] == "--sensitivity high"
+        assert layers.config.min_magnitude == 12
+        assert layers.sources["min_magnitude"] == "--set min_magnitude"
+        assert layers.sources["max_ball_speed_mph"] == DEFAULT_SOURCE
+
+    def test_profile_shot_timing_for_mode(self):
+        """The profile's shot timing for the mode layers over its thresholds, under --set."""
+        profile = CalibrationProfile(
+            name="garage",
+            monitor=MonitorConfig(shot_timeout_sec=0.4),
+            shot_timing=ShotTiming.from_dict(
+                {"modes": {"swing-speed": {"shot_timeout_sec": 0.3, "max_shot_duration_sec": 0.6}}}
+            ),
+        )
+
+        layers = build_monitor_layers(
+            profile, {}, assignments={"max_shot_duration_sec": 0.5}, mode="swing-speed"
+        )
+
+        assert layers.config.shot_timeout_sec == 0.3
+        assert layers.sources["shot_timeout_sec"] == "profile garage (swing-speed)"
+        assert layers.config.max_shot_duration_sec == 0.5
+        assert build_monitor_layers(profile, {}, mode="streaming").config.shot_timeout_sec == 0.4
+
+    def test_environment_over_profile(self):
+        """A variable should override the profile, and parse filters comma-separated."""
+        profile = CalibrationProfile(name="garage", monitor=MonitorConfig(min_shot_magnitude=120))
+        environ = {
+            "OPENFLIGHT_MONITOR_MIN_SHOT_MAGNITUDE": "90",
+            "OPENFLIGHT_MONITOR_FILTERS": "magnitude, direction",
+        }
+
+        layers = build_monitor_layers(profile, environ)
+
+        assert layers.config.min_shot_magnitude == 90
+        assert layers.config.filters == ("magnitude", "direction")
+        assert layers.sources["filters"] == "env OPENFLIGHT_MONITOR_FILTERS"
+
+    def test_defaults_unchanged(self):
+        """With no layers, nothing is changed."""
+        layers = build_monitor_layers(None, {"OPENFLIGHT_WEB_PORT": "8081"})
+
+        assert not layers.changed
+        assert layers.config == MonitorConfig()
+        assert layers.rows(effective=False) == []
+        assert len(layers.rows()) == len(MonitorConfig().to_dict())
+
+    def test_errors_name_layer(self):
+        """A bad value should be reported against the variable or flag that set it."""
+        with pytest.raises(ValueError, match="^env OPENFLIGHT_MONITOR_MIN_SHOT_MAGNTUDE: "):
+            build_monitor_layers(None, {"OPENFLIGHT_MONITOR_MIN_SHOT_MAGNTUDE": "90"})
+        with pytest.raises(ValueError, match="^--set max_ball_speed_mph: max_ball_speed_mph"):
+            build_monitor_layers(None, {}, assignments={"max_ball_speed_mph": -5})
+
+    def test_layer_validated_together(self):
+        """Settings that are only consistent together can be given in one layer."""
+        layers = ConfigLayers()
+
+        layers.set("--set", {"min_ball_speed_mph": 230, "max_ball_speed_mph": 240})
+
+        assert layers.config.min_ball_speed_mph == 230
+
+    def test_apply_credits_only_changes(self):
+        """Applying a whole config should only credit the values it changed."""
+        layers = ConfigLayers()
+
+        layers.apply("profile garage", MonitorConfig(min_magnitude=30))
+
+        assert layers.rows(effective=False) == [("min_magnitude", 30, "profile garage")]
+
+
+def test_parse_assignments():
+    """--set values are JSON if they parse, and must be FIELD=VALUE."""
+    assert parse_assignments(["min_magnitude=12", "filters=magnitude,direction"]) == {
+        "min_magnitude": 12,
+        "filters": ["magnitude", "direction"],
+    }
+    with pytest.raises(ValueError, match="--set: expected FIELD=VALUE"):
+        parse_assignments(["min_magnitude"])
@@ -0,0 +1,80 @@
+"""Tests for the tablet app connector feed."""
+
+from openflight.connector import ConnectorFeed, connector_shot
+
+SHOT = {
+    "ball_speed_mph": 148.2,
+    "club_speed_mph": 101.0,
+    "smash_factor": 1.47,
+    "estimated_carry_yards": 231,
+    "club": "driver",
+    "timestamp": "2024-01-15T10:30:00",
+    "launch_angle_vertical": None,
+    "launch_angle_horizontal": None,
+    "spin_rpm": 2650,
+}
+
+
+class TestConnectorShot:
+    """Tests for the shot layout."""
+
+    def test_layout(self):
+        """Measured values should go into BallData and ClubData under the connector's names."""
+        shot = connector_shot(3, SHOT)
+
+        assert shot["ShotNumber"] == 3
+        assert shot["BallData"] == {"Speed": 148.2, "TotalSpin": 2650, "CarryDistance": 231}
+        assert shot["ClubData"] == {"Speed": 101.0, "SmashFactor": 1.47}
+        assert shot["Club"] == "driver"
+
+    def test_unmeasured_left_out(self):
+        """Values the monitor didn't measure should be missing, not 0."""
+        shot = connector_shot(1, {"ball_speed_mph": 90.0, "club_speed_mph": None})
+
+        assert "VLA" not in shot["BallData"]
+        assert shot["ClubData"] == {}
+
+
+class TestConnectorFeed:
+    """Tests for following the event bus."""
+
+    def test_shots_numbered(self):
+        """Each shot event should get the next number."""
+        feed = ConnectorFeed()
+        feed.on_event("shot", {"shot": SHOT, "stats": {}})
+        feed.on_event("reading", {"speed": 30.0})
+        feed.on_event("shot", {"shot": {**SHOT, "ball_speed_mph": 150.0}, "stats": {}})
+
+        assert feed.last_shot_number == 2
+        assert feed.latest()["BallData"]["Speed"] == 150.0
+        assert [s["ShotNumber"] for s in feed.since(1)] == [2]
+
+    def test_mulligan(self):
+        """A mulligan should take the shot back without reusing its number."""
+        feed = ConnectorFeed()
+        feed.on_event("shot", {"shot": SHOT})
+        feed.on_event("mulligan", {"shot": SHOT})
+
+        assert feed.latest() is None
+        feed.on_event("shot", {"shot": SHOT})
+        assert feed.latest()["ShotNumber"] == 2
+
+    def test_ready_state(self):
+        """Arm events and schedule disarming should set Ready; a stopped radar is never ready."""
+        feed = ConnectorFeed(ready=False)
+        assert feed.status(connected=True)["Ready"] is False
+
+        feed.on_event("ready", {"ready": True})
+        assert feed.status(connected=True)["Ready"] is True
+        assert feed.status(connected=False)["Ready"] is False
+
+        feed.on_event("disarmed", {"disarmed": True})
+        assert feed.status(connected=True)["Ready"] is False
+
+    def test_history_bounded(self):
+        """Only the last history_size shots should be kept for catching up."""
+        feed = ConnectorFeed(history_size=2)
+        for _ in range(3):
+            feed.on_event("shot", {"shot": SHOT})
+
+        assert [s["ShotNumber"] for s in feed.since(0)] == [2, 3]
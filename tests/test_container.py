@@ -0,0 +1,47 @@
+"""Tests for container detection."""
+
+from openflight.container import PASS_THROUGH_HELP, device_hint, in_container, serial_devices
+
+
+def _root(tmp_path, cgroup="0::/init.scope\n", marker=None):
+    """A filesystem root with PID 1's cgroup and optionally a runtime's marker file."""
+    (tmp_path / "proc" / "1").mkdir(parents=True)
+    (tmp_path / "proc" / "1" / "cgroup").write_text(cgroup)
+    if marker:
+        path = tmp_path / marker
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.touch()
+    return str(tmp_path)
+
+
+def test_in_container(tmp_path):
+    """Docker and Podman marker files, and container cgroups, are recognized."""
+    assert not in_container(_root(tmp_path / "host"))
+    assert in_container(_root(tmp_path / "docker", marker=".dockerenv"))
+    assert in_container(_root(tmp_path / "podman", marker="run/.containerenv"))
+    assert in_container(_root(tmp_path / "k8s", cgroup="0::/kubepods/besteffort/pod1\n"))
+
+
+def test_serial_devices(tmp_path):
+    """USB serial devices and their by-id links are listed; other devices aren't."""
+    (tmp_path / "serial" / "by-id").mkdir(parents=True)
+    for name in ("ttyACM0", "ttyUSB1", "tty0", "null", "serial/by-id/usb-OmniPreSense-if00"):
+        (tmp_path / name).touch()
+
+    assert serial_devices(str(tmp_path)) == [
+        str(tmp_path / "serial" / "by-id" / "usb-OmniPreSense-if00"),
+        str(tmp_path / "ttyACM0"),
+        str(tmp_path / "ttyUSB1"),
+    ]
+
+
+def test_device_hint_only_in_container_without_devices(tmp_path):
+    """Pass-through guidance is only given in a container that has no serial devices."""
+    dev = tmp_path / "dev"
+    dev.mkdir()
+    container = _root(tmp_path / "docker", marker=".dockerenv")
+
+    assert device_hint(str(dev), container) == PASS_THROUGH_HELP
+    assert device_hint(str(dev), _root(tmp_path / "host")) is None
+    (dev / "ttyACM0").touch()
+    assert device_hint(str(dev), container) is None
@@ -0,0 +1,149 @@
+"""Tests for the local control socket."""
+
+import json
+import os
+import socket
+import stat
+import sys
+
+import pytest
+
+from openflight import control
+from openflight.control import (
+    INTERNAL_ERROR,
+    INVALID_PARAMS,
+    METHOD_NOT_FOUND,
+    PARSE_ERROR,
+    ControlError,
+    ControlServer,
+    call,
+)
+
+
+def _status(params):
+    return {"mode": "streaming", "shots": 3}
+
+
+def _set_mode(params):
+    if params.get("mode") not in ("streaming", "rolling-buffer"):
+        raise ValueError("bad mode")
+    return {"mode": params["mode"]}
+
+
+def _broken(params):
+    raise RuntimeError("radar unplugged")
+
+
+HANDLERS = {"status": _status, "set_mode": _set_mode, "broken": _broken}
+
+
+@pytest.fixture
+def control_server(tmp_path):
+    """A control server listening on a socket in a temporary directory."""
+    server = ControlServer(str(tmp_path / "of.sock"), HANDLERS)
+    server.start()
+    yield server
+    server.stop()
+
+
+class TestHandle:
+    """Tests for answering request lines."""
+
+    def _handle(self, request):
+        return ControlServer("unused", HANDLERS).handle(json.dumps(request).encode())
+
+    def test_result(self):
+        """A known method should be answered with its result and the request's id."""
+        response = self._handle({"jsonrpc": "2.0", "id": 7, "method": "status"})
+
+        assert response == {"jsonrpc": "2.0", "id": 7, "result": {"mode": "streaming", "shots": 3}}
+
+    def test_errors(self):
+        """Bad JSON, unknown methods and bad params should get JSON-RPC error codes."""
+        server = ControlServer("unused", HANDLERS)
+
+        assert server.handle(b"{nope")["error"]["code"] == PARSE_ERROR
+        unknown = self._handle({"jsonrpc": "2.0", "id": 1, "method": "reboot"})
+        assert unknown["error"]["code"] == METHOD_NOT_FOUND
+        bad_mode = self._handle(
+            {"jsonrpc": "2.0", "id": 1, "method": "set_mode", "params": {"mode": "turbo"}}
+        )
+        assert bad_mode["error"] == {"code": INVALID_PARAMS, "message": "bad mode"}
+
+    def test_handler_exception_is_internal_error(self):
+        """A handler crashing should be reported, not take the server down."""
+        response = self._handle({"jsonrpc": "2.0", "id": 1, "method": "broken"})
+
+        assert response["error"]["code"] == INTERNAL_ERROR
+
+    def test_notification_not_answered(self):
+        """Requests without an id are notifications and get no response."""
+        assert self._handle({"jsonrpc": "2.0", "method": "status"}) is None
+
+
+class TestSocket:
+    """Tests for the socket and the client."""
+
+    def test_call(self, control_server):
+        """call should return the method's result over the socket."""
+        assert call(control_server.path, "set_mode", {"mode": "rolling-buffer"}) == {
+            "mode": "rolling-buffer"
+        }
+
+    def test_call_error(self, control_server):
+        """Error responses should be raised as ControlError with their code."""
+        with pytest.raises(ControlError) as info:
+            call(control_server.path, "reboot")
+
+        assert info.value.code == METHOD_NOT_FOUND
+
+    def test_socket_private(self, control_server):
+        """Only the owner should be able to open the socket."""
+        assert stat.S_IMODE(os.stat(control_server.path).st_mode) == 0o600
+
+    def test_stale_socket_replaced(self, tmp_path):
+        """A socket file left by a crashed server shouldn't stop a new one starting."""
+        path = str(tmp_path / "of.sock")
+        stale = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        stale.bind(path)
+        stale.close()
+
+        server = ControlServer(path, HANDLERS)
+        server.start()
+        try:
+            assert call(path, "status")["shots"] == 3
+        finally:
+            server.stop()
+        assert not os.path.exists(path)
+
+    def test_running_server_not_replaced(self, control_server):
+        """A second server on a live socket should refuse to start."""
+        with pytest.raises(OSError):
+            ControlServer(control_server.path, HANDLERS).start()
+
+
+class TestCtlCli:
+    """Tests for the openflight-ctl command."""
+
+    def test_params_parsed(self, control_server, monkeypatch, capsys):
+        """key=value params should be sent and the result printed as JSON."""
+        monkeypatch.setattr(
+            sys,
+            "argv",
+            ["openflight-ctl", "--socket", control_server.path, "set_mode", "mode=streaming"],
+        )
+
+        control.main()
+
+        assert json.loads(capsys.readouterr().out) == {"mode": "streaming"}
+
+    def test_no_server(self, tmp_path, monkeypatch):
+        """An unreachable socket should exit with an error."""
+        monkeypatch.setattr(
+            sys, "argv", ["openflight-ctl", "--socket", str(tmp_path / "none.sock"), "status"]
+        )
+
+        with pytest.raises(SystemExit) as info:
+            control.main()
+
+        assert info.value.code == 2
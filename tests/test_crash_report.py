@@ -0,0 +1,94 @@
+"""Tests for crash reports."""
+
+import json
+import sys
+import threading
+
+from openflight.crash_report import CrashReporter
+from openflight.frames import Direction, SpeedReading
+
+
+def _raise(exc):
+    try:
+        raise exc
+    except type(exc) as e:
+        return e
+
+
+class TestCrashReport:
+    """Tests for what goes into a report."""
+
+    def test_report_written_with_context(self, tmp_path):
+        """A report should hold the exception, recent readings and the monitor state."""
+        reporter = CrashReporter(
+            tmp_path / "crashes",
+            snapshot=lambda: {"mode": "streaming", "monitor": {"buffered_readings": 2}},
+            max_readings=2,
+        )
+        for speed in (10.0, 20.0, 30.0):
+            reporter.record_reading(
+                SpeedReading(speed=speed, direction=Direction.OUTBOUND, magnitude=900)
+            )
+
+        path = reporter.report(_raise(IndexError("list index out of range")))
+
+        report = json.loads(path.read_text())
+        assert path.parent == tmp_path / "crashes"
+        assert report["exception"]["type"] == "IndexError"
+        assert "raise exc" in "".join(report["exception"]["traceback"])
+        assert [r["speed"] for r in report["readings"]] == [20.0, 30.0]
+        assert report["state"]["monitor"] == {"buffered_readings": 2}
+
+    def test_broken_snapshot_still_reports(self, tmp_path):
+        """A snapshot that fails (state broken by the crash) shouldn't lose the report."""
+
+        def snapshot():
+            raise AttributeError("'NoneType' object has no attribute 'config'")
+
+        reporter = CrashReporter(tmp_path, snapshot=snapshot)
+
+        report = reporter.build_report(_raise(RuntimeError("boom")))
+
+        assert "AttributeError" in report["state"]["snapshot_error"]
+        assert report["exception"]["message"] == "boom"
+
+    def test_unreachable_endpoint(self, tmp_path):
+        """A failed POST should be reported, not raised, and the file still written."""
+        reporter = CrashReporter(tmp_path, endpoint="http://127.0.0.1:1/crash")
+
+        path = reporter.report(_raise(RuntimeError("boom")))
+
+        assert path.exists()
+
+
+class TestHooks:
+    """Tests for catching escaped exceptions."""
+
+    def test_thread_exception_reported(self, tmp_path):
+        """An exception escaping a background thread should write a report."""
+        reporter = CrashReporter(tmp_path)
+        reporter.install()
+        try:
+
+            def radar_loop():
+                raise ValueError("bad frame")
+
+            thread = threading.Thread(target=radar_loop, name="radar")
+            thread.start()
+            thread.join()
+        finally:
+            reporter.uninstall()
+
+        reports = [json.loads(p.read_text()) for p in tmp_path.glob("crash_*.json")]
+        assert [r["thread"] for r in reports] == ["radar"]
+        assert reports[0]["exception"]["message"] == "bad frame"
+
+    def test_uninstall_restores_hooks(self, tmp_path):
+        """uninstall should put back the hooks from before."""
+        before = (sys.excepthook, threading.excepthook)
+        reporter = CrashReporter(tmp_path)
+
+        reporter.install()
+        reporter.uninstall()
+
+        assert (sys.excepthook, threading.excepthook) == before
@@ -0,0 +1,96 @@
+"""Tests for landing dispersion."""
+
+import math
+
+import pytest
+
+from openflight.dispersion import club_dispersion, fit_ellipse, landing_point
+
+
+def _shot(carry, angle, club="7-iron"):
+    return {"estimated_carry_yards": carry, "launch_angle_horizontal": angle, "club": club}
+
+
+class TestLandingPoint:
+    """Tests for where a shot lands."""
+
+    def test_right_of_target(self):
+        """A positive horizontal angle lands right of the target line."""
+        downrange, offline = landing_point(_shot(150.0, 3.0))
+
+        assert downrange == pytest.approx(150.0 * math.cos(math.radians(3.0)))
+        assert offline == pytest.approx(150.0 * math.sin(math.radians(3.0)))
+        assert offline > 0
+
+    def test_spin_adjusted_carry_preferred(self):
+        """The spin-adjusted carry should be used when there is one."""
+        downrange, _ = landing_point({**_shot(150.0, 0.0), "carry_spin_adjusted": 140.0})
+
+        assert downrange == 140.0
+
+    def test_no_horizontal_angle(self):
+        """Radar-only shots have no landing point."""
+        assert landing_point(_shot(150.0, None)) is None
+
+
+class TestFitEllipse:
+    """Tests for the dispersion ellipse."""
+
+    def test_distance_scatter_is_long(self):
+        """Scatter along the target line gives a long axis on the target line."""
+        points = [(140.0, 0.0), (150.0, 1.0), (160.0, 0.0), (150.0, -1.0)]
+
+        ellipse = fit_ellipse(points)
+
+        assert ellipse.downrange == pytest.approx(150.0)
+        assert ellipse.length > 4 * ellipse.width
+        assert ellipse.angle_deg == pytest.approx(0.0, abs=1.0)
+
+    def test_long_when_right_tilts_right(self):
+        """Shots that go longer as they go right tilt the ellipse toward the right."""
+        points = [(140.0, -5.0), (150.0, 0.0), (160.0, 5.0), (150.0, 1.0)]
+
+        assert fit_ellipse(points).angle_deg > 0
+
+    def test_95_percent_size(self):
+        """For a round scatter the ellipse is about 2.45 standard deviations across each way."""
+        points = [(150.0 + dx, dy) for dx, dy in ((10, 0), (-10, 0), (0, 10), (0, -10))]
+        sd = math.sqrt(200 / 3)
+
+        ellipse = fit_ellipse(points)
+
+        assert ellipse.length == pytest.approx(2 * 2.4477 * sd, rel=1e-3)
+        assert ellipse.width == pytest.approx(ellipse.length)
+
+    def test_too_few_points(self):
+        """Two points don't make a scatter."""
+        assert fit_ellipse([(150.0, 0.0), (155.0, 1.0)]) is None
+
+
+class TestClubDispersion:
+    """Tests for per-club dispersion."""
+
+    def test_grouped_by_club(self):
+        """Clubs should be separate, in the order first hit, without radar-only shots."""
+        shots = [
+            _shot(250.0, 1.0, "driver"),
+            _shot(150.0, 0.0),
+            _shot(255.0, None, "driver"),
+            _shot(245.0, -2.0, "driver"),
+        ]
+
+        dispersion = club_dispersion(shots)
+
+        assert [(d.club, d.shots) for d in dispersion] == [("driver", 2), ("7-iron", 1)]
+        assert dispersion[1].ellipse is None
+
+    def test_target_basket(self):
+        """Shots within basket_pct of the average carry, around the target line, are in it."""
+        # Average carry 150: the basket is 15 yd across the target at 150
+        shots = [_shot(150.0, 0.0), _shot(150.0, 4.0), _shot(150.0, -8.0), _shot(150.0, 0.5)]
+
+        (dispersion,) = club_dispersion(shots, basket_pct=10.0)
+
+        assert dispersion.basket_radius == pytest.approx(15.0, rel=0.01)
+        assert dispersion.in_basket_pct == 75.0
+        assert dispersion.to_dict()["ellipse"] is not None
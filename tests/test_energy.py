@@ -0,0 +1,76 @@
+"""Tests for the energy metrics."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.energy import (
+    BALL_MASS_KG,
+    CLUB_HEAD_MASS_KG,
+    energy_from_dict,
+    energy_stats,
+    kinetic_energy_j,
+    shot_energy,
+)
+from openflight.launch_monitor import ClubType, Shot
+
+
+def test_kinetic_energy():
+    """0.5 m v², with the speed converted from mph."""
+    assert kinetic_energy_j(2.0, 10 / 0.44704) == pytest.approx(100.0)
+
+
+def test_every_club_has_a_head_mass():
+    """All real clubs should have a head mass; "unknown" shouldn't guess one."""
+    clubs = {c.value for c in ClubType} - {"unknown"}
+
+    assert set(CLUB_HEAD_MASS_KG) == clubs
+
+
+def test_transfer_follows_smash_and_head_mass():
+    """Transfer is (ball mass / head mass) x smash squared."""
+    energy = shot_energy(150.0, 100.0, "driver")
+
+    assert energy["ball_energy_j"] == pytest.approx(103.3, abs=0.1)
+    assert energy["club_energy_j"] == pytest.approx(199.8, abs=0.1)
+    assert energy["energy_transfer_pct"] == pytest.approx(
+        100 * BALL_MASS_KG / CLUB_HEAD_MASS_KG["driver"] * 1.5**2
+    )
+
+
+def test_heavier_head_transfers_less():
+    """At the same smash factor, an iron keeps more of its energy than a driver."""
+    driver = shot_energy(150.0, 100.0, "driver")["energy_transfer_pct"]
+    wedge = shot_energy(150.0, 100.0, "pw")["energy_transfer_pct"]
+
+    assert wedge < driver
+
+
+def test_needs_club_speed_and_known_club():
+    """Without a club speed or a known club, only the ball energy is given."""
+    for energy in (shot_energy(120.0, None, "7-iron"), shot_energy(120.0, 90.0, "unknown")):
+        assert energy["ball_energy_j"] == pytest.approx(66.1, abs=0.1)
+        assert energy["club_energy_j"] is None
+        assert energy["energy_transfer_pct"] is None
+
+
+def test_from_log_entry():
+    """Log entries without a club are driver shots."""
+    assert energy_from_dict({"ball_speed_mph": 150.0, "club_speed_mph": 100.0}) == shot_energy(
+        150.0, 100.0, "driver"
+    )
+
+
+def test_stats():
+    """Averages leave out shots without the value."""
+    shots = [
+        Shot(ball_speed_mph=150.0, club_speed_mph=100.0, timestamp=datetime.now()),
+        Shot(ball_speed_mph=120.0, timestamp=datetime.now(), club=ClubType.IRON_7),
+    ]
+
+    stats = energy_stats(shots)
+
+    assert stats["max_ball_energy_j"] == pytest.approx(103.3, abs=0.1)
+    assert stats["avg_club_energy_j"] == pytest.approx(199.8, abs=0.1)
+    assert stats["avg_energy_transfer_pct"] == pytest.approx(51.7, abs=0.1)
+    assert energy_stats([])["avg_energy_transfer_pct"] is None
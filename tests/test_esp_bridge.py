@@ -0,0 +1,242 @@
+"""Tests for the ESP32 bridge protocol and backend."""
+
+import socket
+
+import pytest
+
+from openflight import cbor
+from openflight.esp_bridge import (
+    PROTOCOL_VERSION,
+    BridgeClock,
+    EspBridgePort,
+    EspBridgeRadar,
+    ReorderBuffer,
+    decode_datagram,
+    encode_frame,
+    parse_address,
+)
+from openflight.ops243 import OPS243Radar
+
+
+class TestCbor:
+    """Tests for the CBOR subset."""
+
+    def test_round_trip(self):
+        value = {
+            "v": 1,
+            "s": 2**32 - 1,
+            "n": -100,
+            "f": 1.5,
+            "d": b"\x00\xff",
+            "text": "café",
+            "list": [True, False, None],
+        }
+        assert cbor.loads(cbor.dumps(value)) == value
+
+    def test_rfc_examples(self):
+        """Decodes the RFC 8949 appendix A encodings embedded encoders write."""
+        assert cbor.loads(bytes.fromhex("1903e8")) == 1000
+        assert cbor.loads(bytes.fromhex("3863")) == -100
+        assert cbor.loads(bytes.fromhex("f93e00")) == 1.5  # Half precision
+        assert cbor.loads(bytes.fromhex("fa47c35000")) == 100000.0
+        assert cbor.loads(bytes.fromhex("a26161016162820203")) == {"a": 1, "b": [2, 3]}
+        assert cbor.loads(bytes.fromhex("c11a514b67b0")) == 1363896240  # Tag skipped
+
+    def test_malformed(self):
+        with pytest.raises(cbor.CBORDecodeError, match="truncated"):
+            cbor.loads(bytes.fromhex("1903"))
+        with pytest.raises(cbor.CBORDecodeError, match="after the CBOR item"):
+            cbor.loads(bytes.fromhex("0101"))
+        with pytest.raises(cbor.CBORDecodeError, match="indefinite"):
+            cbor.loads(bytes.fromhex("9f01ff"))
+
+
+class TestFraming:
+    """Tests for length-prefixed frames."""
+
+    def test_several_frames_in_a_datagram(self):
+        datagram = encode_frame({"s": 1}) + encode_frame({"s": 2, "d": b"x\n"})
+        assert decode_datagram(datagram) == [{"s": 1}, {"s": 2, "d": b"x\n"}]
+
+    def test_truncated(self):
+        with pytest.raises(ValueError, match="truncated"):
+            decode_datagram(encode_frame({"s": 1})[:-1])
+
+    def test_not_a_map(self):
+        with pytest.raises(ValueError, match="not a CBOR map"):
+            decode_datagram(encode_frame([1, 2]))
+
+    def test_parse_address(self):
+        assert parse_address("4210") == ("0.0.0.0", 4210)
+        assert parse_address("192.168.1.5:5000") == ("192.168.1.5", 5000)
+        with pytest.raises(ValueError):
+            parse_address("bridge")
+
+
+class TestReorderBuffer:
+    """Tests for putting frames back in order."""
+
+    def test_in_order(self):
+        buffer = ReorderBuffer()
+        assert buffer.push(7, "a", now=0) == ["a"]
+        assert buffer.push(8, "b", now=0) == ["b"]
+
+    def test_reordered(self):
+        """An early frame waits for the one before it."""
+        buffer = ReorderBuffer()
+        buffer.push(1, "a", now=0)
+        assert buffer.push(3, "c", now=0.01) == []
+        assert buffer.push(2, "b", now=0.02) == ["b", "c"]
+        assert buffer.reordered == 1
+        assert buffer.lost == 0
+
+    def test_gap_skipped_after_window(self):
+        """A frame that doesn't arrive within the window is lost, and late if it turns up."""
+        buffer = ReorderBuffer(window_sec=0.05)
+        buffer.push(1, "a", now=0)
+        buffer.push(4, "d", now=0.01)
+        assert buffer.flush(now=0.04) == []
+        assert buffer.flush(now=0.07) == ["d"]
+        assert buffer.lost == 2
+        assert buffer.push(2, "b", now=0.08) == []
+        assert buffer.late == 1
+
+    def test_duplicates(self):
+        buffer = ReorderBuffer()
+        buffer.push(1, "a", now=0)
+        buffer.push(3, "c", now=0)
+        assert buffer.push(1, "a", now=0) == []
+        assert buffer.push(3, "c", now=0) == []
+        assert buffer.late == 2
+
+    def test_sequence_wraps(self):
+        buffer = ReorderBuffer()
+        buffer.push(2**32 - 1, "a", now=0)
+        assert buffer.push(1, "c", now=0) == []
+        assert buffer.push(0, "b", now=0) == ["b", "c"]
+
+    def test_too_many_held(self):
+        """A long gap is skipped once max_held frames are waiting."""
+        buffer = ReorderBuffer(max_held=2)
+        buffer.push(0, "a", now=0)
+        buffer.push(5, "f", now=0)
+        buffer.push(6, "g", now=0)
+        assert buffer.push(8, "i", now=0) == ["f", "g"]
+        assert buffer.lost == 4
+
+
+def test_bridge_clock_uses_quickest_frame():
+    """Device times map onto the local clock by the least-delayed frame."""
+    clock = BridgeClock()
+    assert clock.to_local(1_000_000, arrival=100.030) == pytest.approx(100.030)
+    # Arrived with 5 ms less delay: the offset follows it
+    assert clock.to_local(2_000_000, arrival=101.025) == pytest.approx(101.025)
+    # A delayed frame is still placed by the device clock
+    assert clock.to_local(3_000_000, arrival=102.200) == pytest.approx(102.025)
+
+
+@pytest.fixture
+def bridge():
+    """A bridge port on localhost and a socket standing in for the ESP32."""
+    port = EspBridgePort("127.0.0.1:0", timeout=0.5)
+    esp = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    esp.bind(("127.0.0.1", 0))
+    server = port._sock.getsockname()  # pylint: disable=protected-access
+
+    def send(seq, data=None, device_us=None, boot=1):
+        message = {"v": PROTOCOL_VERSION, "b": boot, "s": seq, "t": device_us or seq * 1000}
+        if data is not None:
+            message["d"] = data
+        esp.sendto(encode_frame(message), server)
+
+    yield port, esp, send
+    port.close()
+    esp.close()
+
+
+class TestEspBridgePort:
+    """Tests for the serial-port-like bridge link."""
+
+    def test_lines_in_sequence_order(self, bridge):
+        port, _, send = bridge
+        send(1, b'{"speed":[-10.0]}\n')
+        send(3, b"ond\n")
+        send(2, b"sec")
+        send(4, b"third\n")
+
+        assert port.readline() == b'{"speed":[-10.0]}\n'
+        assert port.readline() == b"second\n"
+        assert port.readline() == b"third\n"
+        assert port.stats["reordered"] == 1
+
+    def test_heartbeat_finds_device(self, bridge):
+        port, esp, send = bridge
+        send(1)
+        port.wait_for_device()
+
+        assert port.device == esp.getsockname()
+        assert port.in_waiting == 0
+
+    def test_no_device(self):
+        port = EspBridgePort("127.0.0.1:0")
+        try:
+            with pytest.raises(ConnectionError, match="No ESP32 bridge heard"):
+                port.wait_for_device(timeout=0.1)
+        finally:
+            port.close()
+
+    def test_write_sends_command(self, bridge):
+        port, esp, send = bridge
+        send(1)
+        port.wait_for_device()
+
+        port.write(b"??")
+
+        esp.settimeout(1.0)
+        data, _ = esp.recvfrom(1500)
+        assert decode_datagram(data) == [{"v": PROTOCOL_VERSION, "c": b"??"}]
+
+    def test_restart_resets_sequence(self, bridge):
+        """A new boot id starts the sequence over instead of dropping it as late."""
+        port, _, send = bridge
+        send(500, b"before\n")
+        assert port.readline() == b"before\n"
+
+        send(0, b"after\n", boot=2)
+
+        assert port.readline() == b"after\n"
+        assert port.stats["late"] == 0
+
+    def test_malformed_ignored(self, bridge):
+        port, esp, send = bridge
+        esp.sendto(b"\x00\x05junk", port._sock.getsockname())  # pylint: disable=protected-access
+        send(1, b"ok\n")
+
+        assert port.readline() == b"ok\n"
+        assert port.stats["malformed"] == 1
+
+
+class TestEspBridgeRadar:
+    """Tests for the OPS243 driver over the bridge."""
+
+    def test_no_iq_over_bridge(self, monkeypatch):
+        """I/Q and rolling buffer aren't available, so LaunchMonitor falls back."""
+        monkeypatch.setattr(OPS243Radar, "get_info", lambda self: {"Product": "OPS243-A"})
+        radar = EspBridgeRadar()
+
+        capabilities = radar.detect_model()
+
+        assert not capabilities.iq_output
+        assert not capabilities.rolling_buffer
+        assert capabilities.magnitude
+
+    def test_readings_timed_by_device(self, bridge):
+        port, _, send = bridge
+        radar = EspBridgeRadar()
+        radar.bridge = radar.serial = port
+        send(1, b'{"speed":[-152.3],"magnitude":[900]}\n', device_us=5_000_000)
+
+        frame = radar.read_frame()
+
+        assert frame.timestamp == port.line_time
+        assert frame.to_reading().speed == pytest.approx(152.3)
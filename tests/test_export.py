@@ -0,0 +1,152 @@
+"""Tests for export module."""
+
+import csv
+import io
+import json
+
+import pytest
+
+from openflight.export import (
+    ExportTemplate,
+    list_templates,
+    load_session_shots,
+    load_template,
+    to_csv,
+)
+
+
+def _shot(**overrides):
+    """A shot_detected entry as loaded for export."""
+    shot = {
+        "type": "shot_detected",
+        "ts": "2025-06-01T10:15:30.123456",
+        "shot_number": 3,
+        "ball_speed_mph": 150.04,
+        "club_speed_mph": 104.2,
+        "smash_factor": 1.44,
+        "estimated_carry_yards": 250.4,
+        "club": "7-iron",
+        "spin_rpm": None,
+        "mode": "streaming",
+        "session": "20250601_100000",
+        "tags": {"player": "Sam", "ball_type": "pro-v1"},
+    }
+    shot.update(overrides)
+    return shot
+
+
+def _rows(text, delimiter=","):
+    return list(csv.reader(io.StringIO(text), delimiter=delimiter))
+
+
+class TestBuiltinTemplates:
+    """Tests for the bundled layouts."""
+
+    def test_trackman_layout(self):
+        """TrackMan layout should have a units row, app club names and blanks."""
+        rows = _rows(to_csv([_shot()], load_template("trackman")))
+        header, units, row = rows
+        values = dict(zip(header, row))
+
+        assert units[header.index("Ball Speed")] == "[mph]"
+        assert values["Date"] == "2025-06-01 10:15:30"
+        assert values["Player"] == "Sam"
+        assert values["Club"] == "7 Iron"
+        assert values["Ball Speed"] == "150.0"
+        assert values["Carry"] == "250.4"
+        assert values["Club Path"] == ""
+        assert values["Spin Rate"] == ""
+
+    def test_energy_worked_out_from_speeds(self):
+        """Energy columns should be filled from the logged speeds and club."""
+        rows = _rows(to_csv([_shot(), _shot(club_speed_mph=None)], load_template("openflight")))
+        header, with_club, without_club = rows
+
+        assert with_club[header.index("ball_energy_j")] == "103.3"
+        assert with_club[header.index("energy_transfer_pct")] == "35.7"
+        assert without_club[header.index("club_energy_j")] == ""
+
+    def test_all_builtins_load(self):
+        """Every built-in template should be valid."""
+        for name in ("openflight", "trackman", "garmin"):
+            assert load_template(name).columns
+
+
+class TestCustomTemplates:
+    """Tests for user-defined templates."""
+
+    def test_units_values_and_delimiter(self, tmp_path):
+        """Custom templates should convert units, remap values and use their delimiter."""
+        template = {
+            "name": "fitting",
+            "delimiter": ";",
+            "columns": [
+                {"header": "Club", "field": "club", "values": {"7-iron": "7i"}},
+                {"header": "Speed km/h", "field": "ball_speed_mph", "unit": "km/h",
+                 "decimals": 0},
+                {"header": "Carry m", "field": "estimated_carry_yards", "unit": "m",
+                 "decimals": 1},
+                {"header": "Ball", "field": "tags.ball_type"},
+            ],
+        }
+        (tmp_path / "fitting.json").write_text(json.dumps(template))
+
+        loaded = load_template("fitting", tmp_path)
+        rows = _rows(to_csv([_shot()], loaded), delimiter=";")
+
+        assert rows[1] == ["7i", "241", "229.0", "pro-v1"]
+        assert "fitting" in list_templates(tmp_path)
+
+    def test_invalid_templates(self):
+        """Missing headers, unknown keys and impossible units should be rejected."""
+        bad = [
+            {"columns": [{"field": "club"}]},
+            {"columns": [{"header": "X", "colour": "red"}]},
+            {"columns": [{"header": "X", "field": "spin_rpm", "unit": "km/h"}]},
+            {"columns": [], "delimiter": "::"},
+        ]
+        for data in bad:
+            with pytest.raises(ValueError):
+                ExportTemplate.from_dict(data)
+
+    def test_unknown_or_unsafe_name(self, tmp_path):
+        """Unknown names, and paths when not allowed, should not be found."""
+        with pytest.raises(FileNotFoundError):
+            load_template("nope", tmp_path)
+        with pytest.raises(FileNotFoundError):
+            load_template("../../etc/template.json", tmp_path, allow_paths=False)
+
+
+class TestLoadSessionShots:
+    """Tests for reading shots from session logs."""
+
+    def test_adds_session_and_tags(self, tmp_path):
+        """Shots should carry their session id and tags; mock shots are skipped."""
+        path = tmp_path / "session_20250601_100000_range.jsonl"
+        entries = [
+            {"type": "session_start", "session_id": "20250601_100000",
+             "tags": {"location": "range"}},
+            _shot(session=None, tags=None),
+            _shot(session=None, tags=None, mode="mock"),
+        ]
+        path.write_text("".join(json.dumps(e) + "\n" for e in entries))
+
+        shots = load_session_shots([str(path)])
+
+        assert len(shots) == 1
+        assert shots[0]["session"] == "20250601_100000"
+        assert shots[0]["tags"] == {"location": "range"}
+
+    def test_skips_mulligans(self, tmp_path):
+        """Shots taken back with a mulligan should not be exported."""
+        path = tmp_path / "session_20250601_100000.jsonl"
+        entries = [
+            _shot(session=None, tags=None, shot_number=1),
+            _shot(session=None, tags=None, shot_number=2, ball_speed_mph=90.0),
+            {"type": "shot_mulligan", "shot_number": 2},
+        ]
+        path.write_text("".join(json.dumps(e) + "\n" for e in entries))
+
+        shots = load_session_shots([str(path)])
+
+        assert [s["shot_number"] for s in shots] == [1]
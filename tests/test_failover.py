@@ -0,0 +1,70 @@
+"""Tests for failover module."""
+
+import socket
+
+import pytest
+
+from openflight.failover import FailoverGroup, tcp_probe
+
+PRIMARY = "http://gaming-pc:8000/shot"
+STANDBY = "http://nas:9000/log"
+
+
+class TestFailoverGroup:
+    """Tests for choosing the active sim endpoint."""
+
+    def test_primary_active(self):
+        group = FailoverGroup([PRIMARY, STANDBY])
+
+        assert group.active == PRIMARY
+        assert group.order() == [PRIMARY, STANDBY]
+
+    def test_failed_delivery_fails_over(self):
+        changes = []
+        group = FailoverGroup([PRIMARY, STANDBY], on_change=lambda *c: changes.append(c))
+
+        group.record(PRIMARY, delivered=False)
+
+        assert group.active == STANDBY
+        assert group.order() == [STANDBY]
+        assert group.failovers == 1
+        assert changes == [(PRIMARY, STANDBY)]
+
+    def test_health_check_fails_back(self):
+        """Once the primary answers a probe again, shots go back to it."""
+        up = {PRIMARY: False, STANDBY: True}
+        group = FailoverGroup([PRIMARY, STANDBY], probe=lambda url: up[url])
+
+        group.check()
+        assert group.active == STANDBY
+
+        up[PRIMARY] = True
+        group.check()
+
+        assert group.active == PRIMARY
+        assert group.failovers == 1
+
+    def test_all_down_tries_every_endpoint(self):
+        group = FailoverGroup([PRIMARY, STANDBY], probe=lambda url: False)
+
+        group.check()
+
+        assert group.active is None
+        assert group.order() == [PRIMARY, STANDBY]
+        assert group.to_dict()["endpoints"] == [
+            {"url": PRIMARY, "up": False},
+            {"url": STANDBY, "up": False},
+        ]
+
+    def test_invalid_settings(self):
+        with pytest.raises(ValueError):
+            FailoverGroup([])
+        with pytest.raises(ValueError):
+            FailoverGroup([PRIMARY], health_interval_sec=-1)
+
+    def test_tcp_probe(self):
+        with socket.socket() as listener:
+            listener.bind(("127.0.0.1", 0))
+            listener.listen()
+            assert tcp_probe(f"http://127.0.0.1:{listener.getsockname()[1]}/shot") is True
+        assert tcp_probe("http://127.0.0.1:1/hook", timeout_sec=0.5) is False
@@ -0,0 +1,102 @@
+"""Tests for fault_sink module: webhook retries against a misbehaving receiver."""
+
+import json
+import random
+import threading
+import time
+
+import pytest
+
+from openflight.fault_sink import FaultInjectingReceiver, FaultProfile
+from openflight.webhooks import WebhookSink
+
+
+@pytest.fixture
+def make_receiver():
+    """Start fault-injecting receivers and close them after the test."""
+    receivers = []
+
+    def make(profile):
+        receiver = FaultInjectingReceiver(profile)
+        receivers.append(receiver)
+        return receiver
+
+    yield make
+    for receiver in receivers:
+        receiver.close()
+
+
+class TestFaultProfile:
+    """Tests for fault profile validation and selection."""
+
+    def test_rates_validated(self):
+        """Negative rates or rates summing past 1 should be rejected."""
+        with pytest.raises(ValueError):
+            FaultProfile(drop=-0.1)
+        with pytest.raises(ValueError):
+            FaultProfile(drop=0.6, error=0.6)
+
+    def test_choose_follows_rates(self):
+        """Outcomes should follow the configured rates."""
+        profile = FaultProfile(drop=0.5)
+        rng = random.Random(1)
+
+        outcomes = [profile.choose(rng) for _ in range(1000)]
+
+        assert set(outcomes) == {"drop", "accept"}
+        assert 400 < outcomes.count("drop") < 600
+
+
+class TestRetriesUnderFaults:
+    """WebhookSink should get every payload through transient faults."""
+
+    @pytest.mark.parametrize("fault", ["reset", "drop", "error"])
+    def test_each_fault_retried(self, make_receiver, fault):
+        """A persistent fault exhausts the attempts; one fault is retried past."""
+        receiver = make_receiver(FaultProfile(**{fault: 1.0}))
+        sink = WebhookSink([], max_attempts=3, backoff_sec=0.01, timeout_sec=1.0)
+
+        assert sink.deliver(receiver.url, b"{}") is False
+        assert receiver.outcomes == [fault] * 3
+
+        receiver.profile = FaultProfile()
+        assert sink.deliver(receiver.url, b"{}") is True
+
+    def test_slow_response_within_timeout(self, make_receiver):
+        """A delayed response inside the sender's timeout needs no retry."""
+        receiver = make_receiver(FaultProfile(delay=1.0, max_delay_sec=0.05))
+        sink = WebhookSink([], max_attempts=1, timeout_sec=1.0)
+
+        assert sink.deliver(receiver.url, b'{"n": 1}') is True
+        assert receiver.accepted == [{"n": 1}]
+        assert receiver.outcomes == ["delay"]
+
+    def test_mixed_faults_deliver_everything(self, make_receiver):
+        """With retries, every queued shot should eventually be accepted."""
+        receiver = make_receiver(
+            FaultProfile(reset=0.15, drop=0.15, error=0.15, delay=0.1, max_delay_sec=0.05, seed=3)
+        )
+        sink = WebhookSink([receiver.url], max_attempts=10, backoff_sec=0.005, timeout_sec=1.0)
+
+        sink.start()
+        for n in range(20):
+            sink.send({"event": "shot", "n": n})
+        sink.stop(timeout=30)
+
+        assert sorted(p["n"] for p in receiver.accepted) == list(range(20))
+        assert set(receiver.outcomes) - {"accept"}
+
+    def test_outage_then_recovery(self, make_receiver):
+        """Refused connections during an outage should be retried until it ends."""
+        receiver = make_receiver(FaultProfile())
+        receiver.go_down()
+        sink = WebhookSink([], max_attempts=10, backoff_sec=0.05, timeout_sec=1.0)
+        restart = threading.Timer(0.2, receiver.come_up)
+
+        restart.start()
+        started = time.monotonic()
+        delivered = sink.deliver(receiver.url, json.dumps({"event": "shot"}).encode())
+
+        assert delivered is True
+        assert time.monotonic() - started >= 0.2
+        assert len(receiver.accepted) == 1
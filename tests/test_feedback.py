@@ -0,0 +1,129 @@
+"""Tests for feedback module."""
+
+import pytest
+
+from openflight.feedback import (
+    HIGH_HZ,
+    LOW_HZ,
+    FeedbackCurve,
+    LedOutput,
+    ShotFeedback,
+    ToneOutput,
+    gradient_color,
+    parse_pins,
+    tone_hz,
+)
+
+
+class _FakeLed:
+    """Stands in for gpiozero's RGBLED."""
+
+    def __init__(self):
+        self.color = (0.0, 0.0, 0.0)
+        self.offs = 0
+
+    def off(self):
+        self.color = (0.0, 0.0, 0.0)
+        self.offs += 1
+
+
+class TestFeedbackCurve:
+    """Tests for mapping shots to intensity."""
+
+    def test_ball_speed(self):
+        """Ball speed should map linearly onto the range, clamped at the ends."""
+        curve = FeedbackCurve("ball_speed", 100, 150)
+
+        assert curve.intensity({"ball_speed_mph": 125.0}) == pytest.approx(0.5)
+        assert curve.intensity({"ball_speed_mph": 90.0}) == 0.0
+        assert curve.intensity({"ball_speed_mph": 180.0}) == 1.0
+
+    def test_target(self):
+        """Closer to the target should be more intense; no target, no intensity."""
+        curve = FeedbackCurve("target")
+
+        assert curve.intensity({"estimated_carry_yards": 150, "target_yards": 150}) == 1.0
+        assert curve.intensity({"estimated_carry_yards": 135, "target_yards": 150}) == 0.5
+        assert curve.intensity({"estimated_carry_yards": 100, "target_yards": 150}) == 0.0
+        assert curve.intensity({"estimated_carry_yards": 150, "target_yards": None}) is None
+
+    def test_from_spec(self):
+        """A LOW-HIGH range should override the metric's default."""
+        curve = FeedbackCurve.from_spec("carry", "100-200")
+        assert (curve.low, curve.high) == (100, 200)
+        assert FeedbackCurve.from_spec("carry").describe() == "carry 50-300"
+
+    def test_invalid(self):
+        """Unknown metrics and backwards or malformed ranges should be rejected."""
+        for metric, text in (("spin", None), ("carry", "200-100"), ("carry", "fast")):
+            with pytest.raises(ValueError):
+                FeedbackCurve.from_spec(metric, text)
+
+
+class TestMappings:
+    """Tests for intensity to color and pitch."""
+
+    def test_gradient(self):
+        """0 should be red, 0.5 yellow and 1 green."""
+        assert gradient_color(0.0) == (1.0, 0.0, 0.0)
+        assert gradient_color(0.5) == (1.0, 1.0, 0.0)
+        assert gradient_color(1.0) == (0.0, 1.0, 0.0)
+
+    def test_tone_pitch(self):
+        """Pitch should run from low to high, an octave up at the midpoint."""
+        assert tone_hz(0.0) == LOW_HZ
+        assert tone_hz(0.5) == LOW_HZ * 2
+        assert tone_hz(1.0) == HIGH_HZ
+
+    def test_parse_pins(self):
+        """Three pins should be accepted, anything else rejected."""
+        assert parse_pins("5,6,13") == (5, 6, 13)
+        for text in ("5,6", "a,b,c", "5,6,-1"):
+            with pytest.raises(ValueError):
+                parse_pins(text)
+
+
+class TestShotFeedback:
+    """Tests for feedback off the event bus."""
+
+    def test_shot_event(self):
+        """A shot event should light the LED and play a tone for its intensity."""
+        tones = []
+        led = _FakeLed()
+        feedback = ShotFeedback(
+            FeedbackCurve("ball_speed", 100, 150),
+            [
+                ToneOutput(play=lambda hz, sec: tones.append(hz) or True),
+                LedOutput(led=led, hold_sec=60),
+            ],
+        )
+
+        feedback.on_event("reading", {"speed": 150.0})
+        feedback.on_event("shot", {"shot": {"ball_speed_mph": 150.0}, "stats": {}})
+
+        assert tones[-1] == HIGH_HZ
+        assert led.color == (0.0, 1.0, 0.0)
+        feedback.close()
+        assert led.color == (0.0, 0.0, 0.0)
+
+    def test_led_turns_off(self):
+        """The LED should go off after its hold time."""
+        led = _FakeLed()
+        output = LedOutput(led=led, hold_sec=0.01)
+
+        output.show(0.0)
+        assert led.color == (1.0, 0.0, 0.0)
+        output._timer.join(1.0)  # pylint: disable=protected-access
+        assert led.offs == 1
+
+    def test_failing_output(self):
+        """One output failing should not stop the others."""
+
+        class Broken:
+            def show(self, intensity):
+                raise OSError("gone")
+
+        led = _FakeLed()
+        feedback = ShotFeedback(FeedbackCurve(), [Broken(), LedOutput(led=led, hold_sec=60)])
+        feedback.on_event("shot", {"shot": {"ball_speed_mph": 60.0}})
+        assert led.color == (1.0, 0.0, 0.0)
@@ -0,0 +1,146 @@
+"""Tests for filters module."""
+
+import pytest
+
+from openflight import filters
+from openflight.filters import (
+    AngleFilter,
+    ClutterFilter,
+    DirectionFilter,
+    Filter,
+    FilterContext,
+    FilterPipeline,
+    MagnitudeFilter,
+    RangeFilter,
+    RefractoryFilter,
+    SpeedBandFilter,
+    filter_names,
+    register_filter,
+)
+from openflight.launch_monitor import MonitorConfig
+from openflight.ops243 import Direction, MountPosition, SpeedReading
+
+
+def _context(now=0.0, last_shot_time=None, **config):
+    return FilterContext(
+        config=MonitorConfig(**config),
+        ball_directions=MountPosition.BEHIND.ball_directions,
+        min_speed_mph=30.0,
+        now=now,
+        last_shot_time=last_shot_time,
+    )
+
+
+def _reading(speed=100.0, direction=Direction.OUTBOUND, magnitude=500.0, **position):
+    return SpeedReading(speed=speed, direction=direction, magnitude=magnitude, **position)
+
+
+class TestBuiltInFilters:
+    """Tests for the built-in filters."""
+
+    def test_speed_band(self):
+        """Readings outside the club-to-ball band are dropped."""
+        speed = SpeedBandFilter()
+
+        assert speed.check(_reading(100.0), _context()) is None
+        assert "outside range" in speed.check(_reading(20.0), _context())
+        assert "outside range" in speed.check(_reading(250.0), _context())
+
+    def test_direction(self):
+        """Readings away from the target are dropped."""
+        direction = DirectionFilter()
+
+        assert direction.check(_reading(), _context()) is None
+        assert "inbound" in direction.check(_reading(direction=Direction.INBOUND), _context())
+
+    def test_magnitude(self):
+        """Weak readings are dropped; readings without a magnitude pass."""
+        magnitude = MagnitudeFilter()
+
+        assert "below minimum" in magnitude.check(_reading(magnitude=5.0), _context())
+        assert magnitude.check(_reading(magnitude=None), _context()) is None
+
+    def test_clutter(self):
+        """A speed repeated often enough within the window is clutter."""
+        clutter = ClutterFilter()
+        context = dict(clutter_min_repeats=3, clutter_window_sec=1.0, clutter_tolerance_mph=0.5)
+
+        results = [
+            clutter.check(_reading(42.0 + i * 0.1), _context(i * 0.1, **context)) for i in range(4)
+        ]
+
+        assert results[:3] == [None, None, None]
+        assert "clutter" in results[3]
+        assert clutter.check(_reading(120.0), _context(0.4, **context)) is None
+        assert clutter.check(_reading(42.0), _context(5.0, **context)) is None
+
+    def test_refractory(self):
+        """Readings shortly after a shot are dropped."""
+        refractory = RefractoryFilter()
+
+        assert refractory.check(_reading(), _context(10.5, 10.0, refractory_sec=1.0))
+        assert refractory.check(_reading(), _context(11.5, 10.0, refractory_sec=1.0)) is None
+        assert refractory.check(_reading(), _context(0.0)) is None
+
+    def test_range_gate(self):
+        """Targets outside the gate are dropped; readings without a range pass."""
+        gate = RangeFilter()
+        context = _context(min_range_m=0.5, max_range_m=3.0)
+
+        assert gate.check(_reading(range_m=1.5), context) is None
+        assert "nearer than" in gate.check(_reading(range_m=0.2), context)
+        assert "beyond" in gate.check(_reading(range_m=12.0), context)
+        assert gate.check(_reading(range_m=None), context) is None
+        assert gate.check(_reading(range_m=12.0), _context()) is None
+
+    def test_angle_corridor(self):
+        """Targets outside the azimuth or elevation window are dropped; no angles pass."""
+        corridor = AngleFilter()
+        context = _context(
+            min_azimuth_deg=-15, max_azimuth_deg=15, min_elevation_deg=-5, max_elevation_deg=40
+        )
+
+        assert corridor.check(_reading(azimuth_deg=-10.0, elevation_deg=12.0), context) is None
+        assert "Azimuth 60.0°" in corridor.check(_reading(azimuth_deg=60.0), context)
+        assert "Elevation -20.0°" in corridor.check(_reading(elevation_deg=-20.0), context)
+        assert corridor.check(_reading(), context) is None
+        assert corridor.check(_reading(azimuth_deg=60.0), _context()) is None
+
+
+class TestFilterPipeline:
+    """Tests for building and running pipelines."""
+
+    def test_first_rejection_wins(self):
+        """Filters run in order and the first reason is reported."""
+        pipeline = FilterPipeline.from_names(["direction", "speed"])
+
+        reason = pipeline.check(_reading(10.0, direction=Direction.INBOUND), _context())
+
+        assert reason.startswith("Direction")
+
+    def test_empty_pipeline_keeps_everything(self):
+        """With no filters every reading passes."""
+        assert FilterPipeline.from_names([]).check(_reading(1.0), _context()) is None
+
+    def test_unknown_name(self):
+        """Unknown names are rejected with the known ones listed."""
+        with pytest.raises(ValueError, match="known: angle, clutter"):
+            FilterPipeline.from_names(["speed", "sped"])
+
+    def test_register_custom_filter(self, monkeypatch):
+        """Registered filters can be named in a pipeline and config."""
+        monkeypatch.setattr(filters, "_REGISTRY", dict(filters._REGISTRY))
+
+        class EvenSpeedFilter(Filter):
+            name = "test-even-speed"
+
+            def check(self, reading, context):
+                return "even" if int(reading.speed) % 2 == 0 else None
+
+        register_filter("test-even-speed", EvenSpeedFilter)
+
+        assert "test-even-speed" in filter_names()
+        assert FilterPipeline.from_names(["test-even-speed"]).check(_reading(100.0), _context())
+        MonitorConfig(filters=("speed", "test-even-speed")).validate()
+        with pytest.raises(ValueError):
+            register_filter("speed", EvenSpeedFilter)
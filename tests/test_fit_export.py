@@ -0,0 +1,96 @@
+"""Tests for fit_export module."""
+
+import struct
+from datetime import datetime
+
+import pytest
+
+from openflight.fit_export import (
+    RECORD,
+    SESSION,
+    SPORT_GOLF,
+    fit_crc,
+    fit_time,
+    read_fit_records,
+    to_fit,
+)
+
+
+def _shot(**overrides):
+    """A shot_detected entry as loaded for export."""
+    shot = {
+        "type": "shot_detected",
+        "ts": "2025-06-01T10:15:30.123456",
+        "shot_number": 3,
+        "ball_speed_mph": 150.0,
+        "club_speed_mph": 104.0,
+        "smash_factor": 1.44,
+        "estimated_carry_yards": 250.5,
+        "club": "7-iron",
+        "spin_rpm": None,
+    }
+    shot.update(overrides)
+    return shot
+
+
+def _messages(data, global_number):
+    return [m for m in read_fit_records(data) if m["global"] == global_number]
+
+
+class TestToFit:
+    """Tests for writing a session as a FIT golf activity."""
+
+    def test_valid_file(self):
+        """The header and file CRCs check out and the header names the data size."""
+        data = to_fit([_shot()])
+
+        assert data[8:12] == b".FIT"
+        assert fit_crc(data[:12]) == struct.unpack_from("<H", data, 12)[0]
+        assert struct.unpack_from("<I", data, 4)[0] == len(data) - 16
+        assert fit_crc(data[:-2]) == struct.unpack_from("<H", data, len(data) - 2)[0]
+
+    def test_record_per_shot(self):
+        """Each shot is a record at its time, with its metrics as developer fields."""
+        shots = [
+            _shot(),
+            _shot(shot_number=4, ts="2025-06-01T10:16:00", club="driver", ball_speed_mph=165.5),
+        ]
+
+        records = _messages(to_fit(shots), RECORD)
+
+        assert len(records) == 2
+        second = records[1]
+        expected_time = fit_time(datetime.fromisoformat("2025-06-01T10:16:00"))
+        assert struct.unpack("<I", second["fields"][253])[0] == expected_time
+        assert struct.unpack("<H", second["developer"][0])[0] == 4
+        assert second["developer"][1].rstrip(b"\0") == b"driver"
+        assert struct.unpack("<f", second["developer"][2])[0] == pytest.approx(165.5)
+
+    def test_golf_session(self):
+        """The activity's session is golf, spanning the first to the last shot."""
+        shots = [_shot(), _shot(shot_number=4, ts="2025-06-01T10:17:30.123456")]
+
+        (session,) = _messages(to_fit(shots), SESSION)
+
+        assert session["fields"][5][0] == SPORT_GOLF
+        assert struct.unpack("<I", session["fields"][7])[0] == 120_000
+
+    def test_missing_value_invalid(self):
+        """A metric the shot doesn't have is written as FIT's invalid value."""
+        (record,) = _messages(to_fit([_shot()]), RECORD)
+
+        assert record["developer"][7] == b"\xff\xff\xff\xff"
+
+    def test_no_shots(self):
+        """An empty session is still a readable activity."""
+        data = to_fit([], created=datetime(2025, 6, 1, 10, 0))
+
+        assert not _messages(data, RECORD)
+        assert len(_messages(data, SESSION)) == 1
+
+    def test_corrupt_file_rejected(self):
+        data = bytearray(to_fit([_shot()]))
+        data[20] ^= 0xFF
+
+        with pytest.raises(ValueError):
+            read_fit_records(bytes(data))
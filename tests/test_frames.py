@@ -0,0 +1,225 @@
+"""Tests for frames module."""
+
+import math
+
+import pytest
+
+from openflight.frames import (
+    Direction,
+    FftFrame,
+    OPS243Decoder,
+    Point,
+    PointCloud,
+    RawAdc,
+    SpeedTarget,
+    Status,
+    Target,
+)
+from openflight.units import MetersPerSecond, to_mph
+
+
+class TestOPS243Decoder:
+    """Tests for decoding OPS243 output into frames."""
+
+    def test_multi_object_speed_report(self):
+        """O4 arrays become targets, strongest first, with direction from the sign."""
+        frame = OPS243Decoder().decode(
+            '{"speed":[-152.3,12.0],"magnitude":[1200,800]}', timestamp=5.0
+        )
+
+        assert frame == SpeedTarget(
+            timestamp=5.0,
+            targets=[
+                Target(152.3, Direction.OUTBOUND, 1200.0),
+                Target(12.0, Direction.INBOUND, 800.0),
+            ],
+        )
+
+    def test_plain_number(self):
+        """Plain signed numbers are single-target reports in the decoder's unit."""
+        frame = OPS243Decoder(unit="kph").decode("-88.3", timestamp=1.0)
+
+        assert frame.targets == [Target(88.3, Direction.OUTBOUND)]
+        assert frame.unit == "kph"
+
+    def test_iq_lines_paired(self):
+        """An I line and the Q line after it make one RawAdc frame."""
+        decoder = OPS243Decoder()
+
+        assert decoder.decode('{"I":[1,2,3]}') is None
+        frame = decoder.decode('{"Q":[4,5,6]}', timestamp=2.0)
+
+        assert frame == RawAdc(timestamp=2.0, i_samples=[1, 2, 3], q_samples=[4, 5, 6])
+        assert frame.to_block().q_samples == [4, 5, 6]
+
+    def test_unpaired_or_mismatched_iq(self):
+        """Q without I, or blocks of different lengths, are dropped."""
+        decoder = OPS243Decoder()
+
+        assert decoder.decode('{"Q":[4,5,6]}') is None
+        decoder.decode('{"I":[1,2]}')
+        assert decoder.decode('{"Q":[4,5,6]}') is None
+
+    def test_fft_and_status(self):
+        """FFT output and module answers get their own frame types."""
+        decoder = OPS243Decoder()
+
+        assert decoder.decode('{"FFT":[1,2.5,3]}').magnitudes == [1.0, 2.5, 3.0]
+        assert isinstance(decoder.decode('{"FFT":[]}'), FftFrame)
+        status = decoder.decode('{"Product":"OPS243-C","Version":"1.2.3"}')
+        assert isinstance(status, Status)
+        assert status.values["Product"] == "OPS243-C"
+
+    def test_garbage(self):
+        """Lines that aren't radar output decode to nothing."""
+        decoder = OPS243Decoder()
+
+        for line in ("", "hello", '{"speed": invalid}', '{"speed": []}', "[1, 2]"):
+            assert decoder.decode(line) is None
+
+    def test_non_finite_samples_discarded(self):
+        """NaN and infinite speeds or magnitudes should be dropped and counted."""
+        decoder = OPS243Decoder()
+
+        assert decoder.decode("nan") is None
+        frame = decoder.decode('{"speed":[NaN,-150.0,Infinity],"magnitude":[900,800,700]}')
+        assert decoder.decode('{"speed":-150.0,"magnitude":NaN}') is None
+
+        assert [t.speed for t in frame.targets] == [150.0]
+        assert decoder.discarded == 4
+
+    def test_damaged_lines_resynced(self):
+        """A frame after noise or a lost newline should still decode, and be counted."""
+        decoder = OPS243Decoder()
+
+        after_noise = decoder.decode('\x00\x7f{"speed":-150.0,"magnitude":900}')
+        after_partial = decoder.decode('{"speed":-14{"speed":-99.0}')
+        truncated = decoder.decode('{"speed":-150.')
+
+        assert after_noise.targets[0].speed == 150.0
+        assert after_partial.targets[0].speed == 99.0
+        assert truncated is None
+        assert (decoder.corrupt, decoder.recovered) == (3, 2)
+
+    def test_intact_lines_not_corrupt(self):
+        """Well-formed output shouldn't count as damaged."""
+        decoder = OPS243Decoder()
+
+        decoder.decode('{"speed":-150.0}')
+        decoder.decode("-12.5")
+        decoder.decode("")
+
+        assert decoder.corrupt == 0
+
+    def test_compact_pairs(self):
+        """Compact lines should be read as magnitude,speed pairs."""
+        decoder = OPS243Decoder()
+
+        frame = decoder.decode("1200,-150.0,800,-98.5")
+        with_unit = decoder.decode('"mph",900,-40.0')
+
+        assert [(t.magnitude, t.speed) for t in frame.targets] == [(1200.0, 150.0), (800.0, 98.5)]
+        assert with_unit.targets[0].speed == 40.0
+
+    def test_compact_cut_off(self):
+        """A compact line whose pairs don't line up is damaged, not a reading."""
+        decoder = OPS243Decoder()
+
+        assert decoder.decode("1200,-150.0,800") is None
+        assert decoder.decode("12x0,-150.0") is None
+        assert decoder.corrupt == 2
+
+    def test_feed_splits_lines(self):
+        """Raw chunks that end mid-line should be decoded once the line completes."""
+        decoder = OPS243Decoder()
+
+        first = decoder.feed(b'{"speed":-150.0}\r\n{"spe', timestamp=1.0)
+        second = decoder.feed(b'ed":-99.0}\r\n', timestamp=2.0)
+
+        assert [f.targets[0].speed for f in first + second] == [150.0, 99.0]
+        assert second[0].timestamp == 2.0
+
+
+class TestPointCloud:
+    """Tests for turning point clouds into speed targets."""
+
+    def test_to_speed_target(self):
+        """Moving points become mph targets by SNR, with direction from the velocity sign."""
+        cloud = PointCloud(
+            timestamp=2.0,
+            points=[
+                Point(0.0, 2.0, 0.0, -5.0, snr_db=10.0),
+                Point(0.0, 2.0, 0.0, 44.704, snr_db=20.0),
+                Point(0.0, 3.0, 0.0, 0.0, snr_db=30.0),  # Static
+            ],
+        )
+
+        target = cloud.to_speed_target()
+
+        assert [t.direction for t in target.targets] == [Direction.OUTBOUND, Direction.INBOUND]
+        assert target.targets[0].speed == pytest.approx(100.0)
+        assert target.targets[0].magnitude == pytest.approx(100.0)
+        assert target.unit == "mph"
+        reading = target.to_reading()
+        assert reading.range_m == pytest.approx(2.0)
+        assert reading.azimuth_deg == pytest.approx(0.0)
+
+    def test_point_geometry(self):
+        """Range and angles should follow from the point's position."""
+        point = Point(1.0, 1.0, 0.0, 0.0)
+
+        assert point.range == pytest.approx(math.sqrt(2))
+        assert point.azimuth_deg == pytest.approx(45.0)
+        assert point.elevation_deg == pytest.approx(0.0)
+
+
+class TestSpeedTarget:
+    """Tests for turning speed reports into SpeedReadings."""
+
+    def test_to_reading_keeps_other_targets(self):
+        """The strongest target is the reading; the others are its frame_objects."""
+        frame = SpeedTarget(
+            timestamp=3.0,
+            targets=[Target(101.2, Direction.OUTBOUND, 600.0), Target(148.7, Direction.OUTBOUND)],
+        )
+
+        reading = frame.to_reading()
+
+        assert reading.speed == 101.2
+        assert reading.frame_objects[0].speed == 148.7
+        assert reading.frame_objects[0].timestamp == 3.0
+        assert SpeedTarget(timestamp=3.0).to_reading() is None
+
+    def test_reading_speed_typed_by_unit(self):
+        """Readings in m/s should carry MetersPerSecond, not pass as mph."""
+        frame = SpeedTarget(
+            timestamp=3.0, targets=[Target(44.7, Direction.OUTBOUND)], unit="m/s"
+        )
+
+        reading = frame.to_reading()
+
+        assert isinstance(reading.speed, MetersPerSecond)
+        assert to_mph(reading.speed) == pytest.approx(99.99, abs=0.01)
+
+    def test_in_mph(self):
+        """Reports in other units should convert to mph, keeping magnitude and direction."""
+        frame = SpeedTarget(
+            timestamp=3.0, targets=[Target(160.9344, Direction.INBOUND, 700.0)], unit="kph"
+        )
+
+        converted = frame.in_mph()
+
+        assert converted.unit == "mph"
+        assert converted.targets[0].speed == pytest.approx(100.0)
+        assert converted.targets[0].direction == Direction.INBOUND
+        assert converted.targets[0].magnitude == 700.0
+
+    def test_report_names_its_unit(self):
+        """A unit in the report should override the decoder's configured unit."""
+        decoder = OPS243Decoder(unit="mph")
+
+        json_frame = decoder.decode('{"unit":"km-per-hr","speed":-160.9,"magnitude":900}')
+        compact_frame = decoder.decode('"m-per-sec",900,-44.7')
+
+        assert json_frame.unit == "kph"
+        assert compact_frame.unit == "m/s"
@@ -0,0 +1,101 @@
+"""Tests for geometry module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.geometry import MountingGeometry, apply_cosine_correction
+from openflight.launch_monitor import Shot
+
+
+class TestMountingGeometry:
+    """Tests for mounting angle and correction factor."""
+
+    def test_on_line_needs_no_correction(self):
+        """A radar on the target line should have a factor of 1."""
+        geometry = MountingGeometry()
+
+        assert geometry.is_aligned
+        assert geometry.angle_deg == 0
+        assert geometry.correction_factor == 1.0
+
+    def test_lateral_offset_angle(self):
+        """30 cm to the side at 1.2 m should be about 14 degrees."""
+        geometry = MountingGeometry(lateral_offset_m=0.3, distance_m=1.2)
+
+        assert geometry.angle_deg == pytest.approx(14.04, abs=0.01)
+
+    def test_correction_raises_speed(self):
+        """Corrected speed should be measured speed / cos(angle)."""
+        geometry = MountingGeometry(lateral_offset_m=0.3, distance_m=1.2)
+
+        assert geometry.correct(150.0) == pytest.approx(154.6, abs=0.1)
+
+    def test_lateral_and_height_combine(self):
+        """Lateral and height offsets should combine into one off-axis angle."""
+        combined = MountingGeometry(lateral_offset_m=0.3, height_offset_m=0.4, distance_m=1.5)
+        equivalent = MountingGeometry(lateral_offset_m=0.5, distance_m=1.5)
+
+        assert combined.correction_factor == pytest.approx(equivalent.correction_factor)
+
+    def test_invalid_distance(self):
+        """Zero or negative distance should be rejected."""
+        with pytest.raises(ValueError):
+            MountingGeometry(lateral_offset_m=0.3, distance_m=0)
+
+    def test_extreme_angle_rejected(self):
+        """Geometry far off the target line should be rejected."""
+        with pytest.raises(ValueError):
+            MountingGeometry(lateral_offset_m=1.0, distance_m=1.0)
+
+
+class TestApplyCosineCorrection:
+    """Tests for correcting shot speeds."""
+
+    def test_corrects_ball_and_club_speed(self):
+        """Both speeds should be corrected and raw values kept."""
+        shot = Shot(ball_speed_mph=150.0, club_speed_mph=103.0, timestamp=datetime.now())
+        geometry = MountingGeometry(lateral_offset_m=0.3, distance_m=1.2)
+
+        apply_cosine_correction(shot, geometry)
+
+        assert shot.ball_speed_raw_mph == 150.0
+        assert shot.club_speed_raw_mph == 103.0
+        assert shot.ball_speed_mph == pytest.approx(150.0 * geometry.correction_factor)
+        assert shot.club_speed_mph == pytest.approx(103.0 * geometry.correction_factor)
+
+    def test_smash_factor_unchanged(self):
+        """Correcting both speeds by the same factor should keep smash factor."""
+        shot = Shot(ball_speed_mph=150.0, club_speed_mph=103.0, timestamp=datetime.now())
+
+        apply_cosine_correction(shot, MountingGeometry(lateral_offset_m=0.3, distance_m=1.2))
+
+        assert shot.smash_factor == pytest.approx(150.0 / 103.0)
+
+    def test_missing_club_speed(self):
+        """Shots without club speed should only correct ball speed."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime.now())
+
+        apply_cosine_correction(shot, MountingGeometry(lateral_offset_m=0.3, distance_m=1.2))
+
+        assert shot.club_speed_mph is None
+        assert shot.club_speed_raw_mph is None
+
+    def test_aligned_geometry_leaves_shot_alone(self):
+        """No raw values should be recorded when no correction applies."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime.now())
+
+        apply_cosine_correction(shot, MountingGeometry())
+
+        assert shot.ball_speed_mph == 150.0
+        assert shot.ball_speed_raw_mph is None
+
+    def test_idempotent(self):
+        """Applying the correction twice should not compound it."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime.now())
+        geometry = MountingGeometry(lateral_offset_m=0.3, distance_m=1.2)
+
+        apply_cosine_correction(shot, geometry)
+        apply_cosine_correction(shot, geometry)
+
+        assert shot.ball_speed_mph == pytest.approx(150.0 * geometry.correction_factor)
@@ -0,0 +1,95 @@
+"""Tests for gestures module."""
+
+import pytest
+
+from openflight.gestures import ClubGestureDetector, club_announcement, next_club, parse_bag
+from openflight.launch_monitor import ClubType
+from openflight.ops243 import Direction, SpeedReading
+
+
+def _wave(gesture, start, readings=4, speed=15.0, step=0.05):
+    """Feed one pass toward the radar; returns True if any reading completed the gesture."""
+    done = False
+    for i in range(readings):
+        done = gesture.update(SpeedReading(speed, Direction.INBOUND), now=start + i * step) or done
+    return done
+
+
+class TestClubGestureDetector:
+    """Tests for recognizing the club-switch gesture."""
+
+    def test_three_passes_complete_gesture(self):
+        """The third separate pass within the window completes the gesture once."""
+        gesture = ClubGestureDetector()
+
+        assert not _wave(gesture, 0.0)
+        assert not _wave(gesture, 1.0)
+        assert _wave(gesture, 2.0)
+        assert not _wave(gesture, 3.0)
+
+    def test_passes_must_be_separate(self):
+        """Readings without a gap are one pass, however many there are."""
+        gesture = ClubGestureDetector()
+
+        assert not _wave(gesture, 0.0, readings=20)
+
+    def test_passes_outside_window(self):
+        """Passes spread out over more than the window don't add up."""
+        gesture = ClubGestureDetector(window_sec=4.0)
+
+        _wave(gesture, 0.0)
+        _wave(gesture, 3.0)
+        assert not _wave(gesture, 6.0)
+
+    def test_fast_reading_cancels(self):
+        """A swing between passes cancels the gesture."""
+        gesture = ClubGestureDetector()
+        _wave(gesture, 0.0)
+        _wave(gesture, 1.0)
+
+        gesture.update(SpeedReading(95.0, Direction.OUTBOUND), now=1.5)
+
+        assert not _wave(gesture, 2.0)
+
+    def test_long_pass_cancels(self):
+        """Walking up to the radar is not a pass and doesn't count toward one."""
+        gesture = ClubGestureDetector(max_pass_sec=1.5)
+
+        assert not _wave(gesture, 0.0, readings=40, step=0.1)
+        assert not _wave(gesture, 5.0)
+        assert not _wave(gesture, 6.0)
+        assert _wave(gesture, 7.0)
+
+    def test_ignores_other_direction_and_noise(self):
+        """Readings moving away or below the speed band are ignored."""
+        gesture = ClubGestureDetector()
+
+        for start in (0.0, 1.0, 2.0):
+            assert not gesture.update(SpeedReading(15.0, Direction.OUTBOUND), now=start)
+            assert not gesture.update(SpeedReading(5.0, Direction.INBOUND), now=start + 0.5)
+
+
+class TestClubCycling:
+    """Tests for choosing and announcing the next club."""
+
+    def test_next_club_wraps(self):
+        """The bag is cycled in order and wraps around."""
+        bag = [ClubType.DRIVER, ClubType.IRON_7, ClubType.PW]
+
+        assert next_club(ClubType.DRIVER, bag) == ClubType.IRON_7
+        assert next_club(ClubType.PW, bag) == ClubType.DRIVER
+        assert next_club(ClubType.SW, bag) == ClubType.DRIVER
+        assert next_club(ClubType.LW) == ClubType.DRIVER
+
+    def test_parse_bag(self):
+        """Club lists are parsed in order; unknown clubs are rejected."""
+        assert parse_bag("driver, 7-iron,PW") == [ClubType.DRIVER, ClubType.IRON_7, ClubType.PW]
+        with pytest.raises(ValueError):
+            parse_bag("driver,putter")
+        with pytest.raises(ValueError):
+            parse_bag(" , ")
+
+    def test_announcement(self):
+        """Clubs are spoken as words."""
+        assert club_announcement(ClubType.IRON_7) == "7 iron"
+        assert club_announcement(ClubType.GW) == "gap wedge"
@@ -0,0 +1,119 @@
+"""Tests for the gRPC service."""
+
+import json
+
+import pytest
+
+from openflight.grpc_service import GrpcServer, ServiceError, event_fields, peer_address
+
+
+def _stats(params):
+    return {"shot_count": 3, "avg_ball_speed": 150.0}
+
+
+def _set_club(params):
+    if params.get("club") != "7-iron":
+        raise ValueError("bad club")
+    return {"club": params["club"]}
+
+
+def _stream(params, is_active):
+    yield "club_changed", {"club": "7-iron"}
+    yield "shot", {"ball_speed_mph": 150.0}
+
+
+def _authorize(metadata, address):
+    if metadata.get("authorization") != "Bearer s3cret":
+        raise ServiceError("Missing or invalid API token", "UNAUTHENTICATED")
+
+
+HANDLERS = {"GetStats": _stats, "SetClub": _set_club}
+
+
+class TestPeerAddress:
+    """Tests for reading the client address from a gRPC peer."""
+
+    def test_addresses(self):
+        """IPv4 and IPv6 peers should give the address without the port."""
+        assert peer_address("ipv4:192.168.1.7:50432") == "192.168.1.7"
+        assert peer_address("ipv6:[::1]:50432") == "::1"
+        assert peer_address("ipv6:%5B::1%5D:50432") == "::1"
+
+    def test_other_transports(self):
+        """Peers without an IP address should give None."""
+        assert peer_address("unix:/tmp/openflight.sock") is None
+        assert peer_address(None) is None
+
+
+class TestGrpcServer:
+    """Tests for running handlers behind the service."""
+
+    def test_call(self):
+        """A unary method should run its handler with the request's fields."""
+        server = GrpcServer(0, HANDLERS, _stream)
+
+        assert server.call("GetStats", {}, {}, "ipv4:127.0.0.1:1")["shot_count"] == 3
+        assert server.call("SetClub", {"club": "7-iron"}, {}, None) == {"club": "7-iron"}
+        with pytest.raises(ValueError):
+            server.call("SetClub", {"club": "putter"}, {}, None)
+
+    def test_unknown_method(self):
+        """A method without a handler should be unimplemented."""
+        server = GrpcServer(0, HANDLERS, _stream)
+
+        with pytest.raises(ServiceError) as error:
+            server.call("SetPlayer", {"player": "sam"}, {}, None)
+        assert error.value.code == "UNIMPLEMENTED"
+
+    def test_authorize(self):
+        """Calls and streams should be refused before their handler runs."""
+        server = GrpcServer(0, HANDLERS, _stream, authorize=_authorize)
+
+        with pytest.raises(ServiceError):
+            server.call("GetStats", {}, {}, None)
+        with pytest.raises(ServiceError):
+            server.events({}, {}, None, lambda: True)
+        assert server.call("GetStats", {}, {"authorization": "Bearer s3cret"}, None)
+
+    def test_events(self):
+        """Stream events should carry their data as JSON, and shots also as a message."""
+        server = GrpcServer(0, HANDLERS, _stream)
+
+        club, shot = server.events({}, {}, None, lambda: True)
+
+        assert club == {"event": "club_changed", "data_json": json.dumps({"club": "7-iron"})}
+        assert shot["shot"] == {"ball_speed_mph": 150.0}
+        assert event_fields("reading", {"speed": 1.0})["reading"] == {"speed": 1.0}
+
+
+class TestGrpcEndToEnd:
+    """Tests over a real gRPC channel (needs the grpc extra)."""
+
+    def test_calls_and_stream(self):
+        """A client generated from the proto should reach the handlers."""
+        grpc = pytest.importorskip("grpc")
+        pytest.importorskip("grpc_tools")
+        from openflight.grpc_service import load_proto
+
+        protos, services = load_proto()
+        server = GrpcServer(0, HANDLERS, _stream, authorize=_authorize, host="127.0.0.1")
+        server.start()
+        try:
+            with grpc.insecure_channel(f"127.0.0.1:{server.port}") as channel:
+                stub = services.LaunchMonitorStub(channel)
+                token = [("authorization", "Bearer s3cret")]
+
+                stats = stub.GetStats(protos.GetStatsRequest(), metadata=token)
+                assert stats.shot_count == 3
+                with pytest.raises(grpc.RpcError) as error:
+                    stub.SetClub(protos.SetClubRequest(club="putter"), metadata=token)
+                assert error.value.code() == grpc.StatusCode.INVALID_ARGUMENT
+                with pytest.raises(grpc.RpcError) as error:
+                    stub.GetStats(protos.GetStatsRequest())
+                assert error.value.code() == grpc.StatusCode.UNAUTHENTICATED
+
+                events = list(stub.StreamEvents(protos.StreamEventsRequest(), metadata=token))
+                assert [e.event for e in events] == ["club_changed", "shot"]
+                assert events[1].shot.ball_speed_mph == 150.0
+        finally:
+            server.stop()
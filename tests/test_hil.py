@@ -0,0 +1,167 @@
+"""Tests for hardware-in-the-loop runs over a virtual serial port."""
+
+import os
+import subprocess
+import sys
+import threading
+
+import pytest
+
+from openflight.hil import CLI, VirtualSerialPair, replay_to, run
+from openflight.serial_dump import READ, WRITE, SerialDump, read_dump
+
+pytestmark = pytest.mark.skipif(sys.platform == "win32", reason="needs pseudo-terminals")
+
+# A radar asked for its info and units, then two shots (club, then ball, moving away)
+INFO = b'{"Product":"OPS243-A","Version":"1.2.3"}\n'
+SHOTS = [
+    b'{"speed": -95.0, "magnitude": 2400}\n',
+    b'{"speed": -142.0, "magnitude": 1200}\n',
+    b'{"speed": -88.0, "magnitude": 2200}\n',
+    b'{"speed": -131.0, "magnitude": 1100}\n',
+]
+
+
+def _capture(path, records, radar="ops243"):
+    """Write a capture of (direction, data) records, 0.05 s apart."""
+    dump = SerialDump(str(path), radar=radar)
+    for i, (direction, data) in enumerate(records):
+        dump.record(direction, data, timestamp=1000.0 + i * 0.05)
+    dump.close()
+    return str(path)
+
+
+def _radar_session(path):
+    """Capture of the openflight CLI with --no-iq-streaming --skip-configure."""
+    records = [
+        (WRITE, b"??"),
+        (READ, INFO),
+        (WRITE, b"U?"),
+        (READ, b'{"Units":"mph"}\n'),
+        (WRITE, b"??"),
+        (READ, INFO),
+    ]
+    dump = SerialDump(str(path))
+    for i, (direction, data) in enumerate(records):
+        dump.record(direction, data, timestamp=1000.0 + i * 0.05)
+    # Club and ball 0.1 s apart, shots 2 s apart (longer than the shot timeout)
+    for i, data in enumerate(SHOTS):
+        dump.record(READ, data, timestamp=1001.0 + (i // 2) * 2.0 + (i % 2) * 0.1)
+    dump.close()
+    return str(path)
+
+
+def _program(pair, command=b"??"):
+    """Open the port like a serial client, ask something and read the answer."""
+    fd = os.open(pair.port, os.O_RDWR | os.O_NOCTTY)
+    os.write(fd, command)
+    answer = b""
+    while not answer.endswith(b"\n"):
+        answer += os.read(fd, 64)
+    os.close(fd)
+    return answer
+
+
+class TestVirtualSerialPair:
+    """Tests for the pseudo-terminal pair."""
+
+    def test_bytes_cross_both_ways(self):
+        """Sent bytes should reach the port unchanged, and the program's writes come back."""
+        with VirtualSerialPair() as pair:
+            answers = []
+            thread = threading.Thread(target=lambda: answers.append(_program(pair)))
+            thread.start()
+
+            assert pair.expect_written(2, timeout=2.0) == b"??"
+            pair.send(b"\x00raw\r\n")
+            thread.join(timeout=2.0)
+
+        assert answers == [b"\x00raw\r\n"]
+
+    def test_expect_written_times_out(self):
+        """Waiting for bytes that never come should return what arrived."""
+        with VirtualSerialPair() as pair:
+            assert pair.expect_written(2, timeout=0.05) == b""
+
+
+class TestReplay:
+    """Tests for playing a capture to a program."""
+
+    def test_answers_follow_commands(self, tmp_path):
+        """Reads after a captured write should wait for the program to send it."""
+        path = _capture(tmp_path / "c.ofdump", [(WRITE, b"??"), (READ, INFO)])
+        _, records = read_dump(path)
+
+        with VirtualSerialPair() as pair:
+            answers = []
+            thread = threading.Thread(target=lambda: answers.append(_program(pair)))
+            thread.start()
+            mismatched = replay_to(pair, records, speed=10.0)
+            thread.join(timeout=2.0)
+
+        assert mismatched == []
+        assert answers == [INFO]
+
+    def test_different_command_reported(self, tmp_path):
+        """A program sending another command than the capture's should be reported."""
+        path = _capture(tmp_path / "c.ofdump", [(WRITE, b"??"), (READ, INFO)])
+        _, records = read_dump(path)
+
+        with VirtualSerialPair() as pair:
+            thread = threading.Thread(target=lambda: _program(pair, command=b"U?"))
+            thread.start()
+            mismatched = replay_to(pair, records, speed=10.0)
+            thread.join(timeout=2.0)
+
+        assert mismatched == [(b"??", b"U?")]
+
+
+class TestRun:
+    """Tests for running a program against a capture."""
+
+    def test_program_output_collected(self, tmp_path):
+        """The program should get the port and its output should come back."""
+        path = _capture(tmp_path / "c.ofdump", [(WRITE, b"??"), (READ, INFO)])
+        script = (
+            "import os, sys\n"
+            "fd = os.open(sys.argv[1], os.O_RDWR | os.O_NOCTTY)\n"
+            "os.write(fd, b'??')\n"
+            "print('Ball Speed:', os.read(fd, 64))\n"
+        )
+
+        result = run(path, [sys.executable, "-c", script, "{port}"], settle_sec=5.0)
+
+        assert result.returncode == 0
+        assert result.shots == 1
+        assert "OPS243-A" in result.output
+        assert result.written == b"??"
+
+    def test_iwr6843_capture_rejected(self, tmp_path):
+        """IWR6843 captures lack the CLI port, so they can't be replayed to a program."""
+        path = _capture(tmp_path / "c.ofdump", [(READ, b"\x02\x01")], radar="iwr6843")
+
+        with pytest.raises(ValueError, match="OPS243"):
+            run(path, ["true"])
+
+
+def _has_pyserial() -> bool:
+    """Whether the program under test can open a serial port (pyserial installed)."""
+    check = subprocess.run([sys.executable, "-c", "import serial; serial.Serial"], check=False)
+    return check.returncode == 0
+
+
+class TestEndToEnd:
+    """Tests running the openflight CLI through pyserial on the virtual port."""
+
+    @pytest.mark.skipif(not _has_pyserial(), reason="needs pyserial")
+    def test_shots_detected(self, tmp_path):
+        """Both shots in the capture should come out of the openflight CLI."""
+        path = _radar_session(tmp_path / "session.ofdump")
+        command = CLI + ["--port", "{port}", "--no-iq-streaming", "--skip-configure"]
+
+        result = run(path, command, settle_sec=1.5)
+
+        assert result.returncode == 0, result.output
+        assert result.mismatched_writes == []
+        assert result.shots == 2
+        assert "Ball Speed:   142.0 mph" in result.output
@@ -0,0 +1,100 @@
+"""Tests for html_export module."""
+
+from openflight.html_export import to_html
+
+
+def _shot(**overrides):
+    """A shot_detected entry as loaded for export."""
+    shot = {
+        "type": "shot_detected",
+        "ts": "2025-06-01T10:15:30.123456",
+        "shot_number": 3,
+        "ball_speed_mph": 150.04,
+        "club_speed_mph": 104.2,
+        "smash_factor": 1.44,
+        "estimated_carry_yards": 250.4,
+        "club": "7-iron",
+        "spin_rpm": None,
+        "session": "20250601_100000",
+        "tags": {},
+    }
+    shot.update(overrides)
+    return shot
+
+
+class TestToHtml:
+    """Tests for rendering the session viewer page."""
+
+    def test_self_contained_page(self):
+        """The page has the table, charts and script inline, with nothing loaded from elsewhere."""
+        page = to_html([_shot(), _shot(shot_number=4, club="driver", ball_speed_mph=160.0)])
+
+        assert page.startswith("<!DOCTYPE html>")
+        assert '<table id="shots">' in page
+        assert page.count("<svg") == 2
+        assert "<script>" in page
+        assert "src=" not in page and "<link" not in page
+
+    def test_shot_rows(self):
+        """Each shot is a row with sortable cell values and formatted text."""
+        page = to_html([_shot()])
+
+        assert '<td data-value="150.04">150.0</td>' in page
+        assert '<td data-value="7 Iron">7 Iron</td>' in page
+        assert '<td data-value="10:15:30">10:15:30</td>' in page
+        # Missing values are blank and sort last
+        assert '<td data-value=""></td>' in page
+
+    def test_summary_by_club(self):
+        """The summary averages each club's shots."""
+        page = to_html([_shot(ball_speed_mph=140.0), _shot(ball_speed_mph=150.0)])
+
+        assert "<td>7 Iron</td><td>2</td><td>145.0</td>" in page
+        assert "2 shot(s) · 2025-06-01 · 20250601_100000" in page
+
+    def test_escapes_text(self):
+        """Titles and values are HTML-escaped."""
+        page = to_html([_shot(club="<b>")], title="Lesson <1>")
+
+        assert "Lesson &lt;1&gt;" in page
+        assert "<b>" not in page
+
+    def test_no_shots(self):
+        """An empty session still renders, without charts."""
+        page = to_html([])
+
+        assert "0 shot(s)" in page
+        assert "<svg" not in page
+
+    def test_dispersion_with_horizontal_angles(self):
+        """Shots with a horizontal launch angle should get a dispersion table and scatter."""
+        shots = [
+            _shot(estimated_carry_yards=carry, launch_angle_horizontal=angle)
+            for carry, angle in ((150.0, 1.0), (145.0, -2.0), (155.0, 3.0))
+        ]
+
+        page = to_html(shots)
+
+        assert "<h2>Dispersion</h2>" in page
+        assert page.count("<ellipse") == 1
+        assert "Landing dispersion" in page
+
+    def test_no_dispersion_without_horizontal_angles(self):
+        """Radar-only shots have no landing points, so no dispersion section."""
+        page = to_html([_shot()])
+
+        assert "Dispersion" not in page
+
+    def test_best_shots_per_player(self):
+        """Each player's best ball speed and carry per club should be listed with its date."""
+        shots = [
+            _shot(tags={"player": "sam"}),
+            _shot(tags={"player": "sam"}, ball_speed_mph=155.0, ts="2025-06-02T10:00:00"),
+            _shot(tags={"player": "alex"}, ball_speed_mph=140.0),
+        ]
+
+        page = to_html(shots)
+
+        assert "<h2>Best shots</h2>" in page
+        assert "<th>Player</th>" in page
+        assert "<td>sam</td><td>7 Iron</td><td>155.0</td><td>2025-06-02</td>" in page
@@ -0,0 +1,76 @@
+"""Tests for impact module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.impact import estimate_impact, impact_stats
+from openflight.launch_monitor import Shot
+from openflight.ops243 import Direction, SpeedReading
+
+
+def _reading(speed, t, magnitude=None, objects=None):
+    reading = SpeedReading(speed, Direction.OUTBOUND, magnitude=magnitude, timestamp=t)
+    reading.frame_objects = objects or []
+    return reading
+
+
+class TestEstimateImpact:
+    """Tests for estimating impact time."""
+
+    def test_interpolated_by_first_ball_magnitude(self):
+        """A ball at a quarter of its peak in its first frame was hit late in the gap."""
+        readings = [
+            _reading(98.0, 10.00, 400),
+            _reading(101.0, 10.02, 420),  # Club's peak
+            _reading(99.5, 10.04, 380),  # Last club frame
+            _reading(145.0, 10.08, 50),  # First ball frame
+            _reading(146.0, 10.10, 200),
+        ]
+
+        impact = estimate_impact(readings, ball_speed_mph=146.0, club_speed_mph=101.0)
+
+        assert impact.window_ms == pytest.approx(40.0)
+        assert impact.impact_time == pytest.approx(10.07)
+        assert impact.offset_ms == pytest.approx(50.0)
+
+    def test_midpoint_without_magnitudes(self):
+        """With no magnitudes, impact should be halfway through the gap."""
+        readings = [_reading(100.0, 1.0), _reading(148.0, 1.02)]
+
+        impact = estimate_impact(readings, 148.0, 100.0)
+
+        assert impact.impact_time == pytest.approx(1.01)
+        assert impact.offset_ms == pytest.approx(10.0)
+
+    def test_same_frame_ball(self):
+        """A frame holding the ball as a secondary object is the first ball frame."""
+        ball = SpeedReading(147.0, Direction.OUTBOUND, magnitude=90)
+        readings = [
+            _reading(100.0, 2.00, 500),
+            _reading(102.0, 2.02, 520, objects=[ball]),
+        ]
+
+        impact = estimate_impact(readings, 147.0, 100.0)
+
+        assert impact.impact_time == pytest.approx(2.01)  # No ball magnitude to go by
+
+    def test_needs_club_before_ball(self):
+        """Without a club frame before the ball, impact can't be placed."""
+        assert estimate_impact([_reading(148.0, 1.0), _reading(100.0, 1.02)], 148, 100) is None
+        assert estimate_impact([_reading(100.0, None)], 148, 100) is None
+
+
+def test_impact_stats():
+    """The spread of the offsets should need at least two timed shots."""
+
+    def shot(offset):
+        return Shot(ball_speed_mph=150, timestamp=datetime.now(), impact_offset_ms=offset)
+
+    assert impact_stats([shot(4.0), shot(None)]) == {
+        "avg_impact_offset_ms": 4.0,
+        "impact_timing_sd_ms": None,
+    }
+    stats = impact_stats([shot(4.0), shot(8.0), shot(6.0)])
+    assert stats["avg_impact_offset_ms"] == pytest.approx(6.0)
+    assert stats["impact_timing_sd_ms"] == pytest.approx(2.0)
@@ -0,0 +1,72 @@
+"""Tests for inject module."""
+
+import json
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import pytest
+
+from openflight.inject import inject_shot
+
+
+class _Server:
+    """Local HTTP server that records one POST and replies with a scripted response."""
+
+    def __init__(self, status=200, body=None):
+        self.requests = []
+        server = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_POST(self):  # pylint: disable=invalid-name
+                length = int(self.headers.get("Content-Length", 0))
+                body_in = json.loads(self.rfile.read(length))
+                server.requests.append((self.path, self.headers, body_in))
+                payload = json.dumps(body or {}).encode()
+                self.send_response(status)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(payload)))
+                self.end_headers()
+                self.wfile.write(payload)
+
+            def log_message(self, *args):
+                pass
+
+        self.httpd = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self.httpd.server_address[1]}"
+        threading.Thread(target=self.httpd.serve_forever, daemon=True).start()
+
+    def close(self):
+        self.httpd.shutdown()
+        self.httpd.server_close()
+
+
+class TestInjectShot:
+    """Tests for the inject client."""
+
+    def test_posts_payload_with_token(self):
+        """Shot payload and token should be sent to /api/inject."""
+        server = _Server(body={"shot": {"ball_speed_mph": 150.0}})
+        try:
+            result = inject_shot(server.url, {"ball_speed_mph": 150.0}, token="s3cret")
+        finally:
+            server.close()
+
+        path, headers, body = server.requests[0]
+        assert path == "/api/inject"
+        assert headers["X-OpenFlight-Token"] == "s3cret"
+        assert body == {"ball_speed_mph": 150.0}
+        assert result["shot"]["ball_speed_mph"] == 150.0
+
+    def test_rejection_raises_with_message(self):
+        """Server errors should surface the server's message."""
+        server = _Server(status=401, body={"error": "Missing or invalid API token"})
+        try:
+            with pytest.raises(RuntimeError, match="invalid API token"):
+                inject_shot(server.url, {"ball_speed_mph": 150.0})
+        finally:
+            server.close()
+
+    def test_unreachable_server(self):
+        """Connection failures should raise RuntimeError."""
+        with pytest.raises(RuntimeError):
+            inject_shot("http://127.0.0.1:1", {"ball_speed_mph": 150.0}, timeout=0.5)
@@ -0,0 +1,189 @@
+"""Tests for IWR6843 radar driver."""
+
+import math
+import struct
+
+import pytest
+
+from openflight.frames import IWR6843Decoder
+from openflight.iwr6843 import IWR6843Radar
+from openflight.launch_monitor import LaunchMonitor
+from openflight.ops243 import Direction
+
+
+def _iwr_packet(points, snrs=None, frame_number=7):
+    """An mmWave demo data packet with these (x, y, z, velocity) points."""
+    tlvs = b""
+    payload = b"".join(struct.pack("<4f", *p) for p in points)
+    tlvs += struct.pack("<2I", IWR6843Decoder.TLV_DETECTED_POINTS, len(payload)) + payload
+    if snrs is not None:
+        payload = b"".join(struct.pack("<2h", snr, 50) for snr in snrs)
+        tlvs += struct.pack("<2I", IWR6843Decoder.TLV_SIDE_INFO, len(payload)) + payload
+    num_tlvs = 1 if snrs is None else 2
+    length = IWR6843Decoder.HEADER.size + len(tlvs)
+    header = IWR6843Decoder.HEADER.pack(
+        IWR6843Decoder.MAGIC, 0x03050004, length, 0xA6843, frame_number, 0, len(points),
+        num_tlvs, 0,
+    )
+    return header + tlvs
+
+
+class TestIWR6843Decoder:
+    """Tests for decoding the TI mmWave demo's binary packets."""
+
+    def test_points_with_snr(self):
+        """Points and their side info should become one PointCloud."""
+        packet = _iwr_packet([(0.1, 2.0, 0.3, 44.7), (0.0, 1.0, 0.0, -3.0)], snrs=[250, 120])
+
+        frames = IWR6843Decoder().feed(packet, timestamp=4.0)
+
+        assert len(frames) == 1
+        cloud = frames[0]
+        assert cloud.frame_number == 7 and cloud.timestamp == 4.0
+        assert cloud.points[0].velocity == pytest.approx(44.7, abs=1e-4)
+        assert [p.snr_db for p in cloud.points] == [25.0, 12.0]
+
+    def test_split_and_garbage(self):
+        """Packets split across chunks, after garbage, should still decode."""
+        packet = _iwr_packet([(0.0, 2.0, 0.0, 40.0)])
+        data = b"\x00\xffjunk" + packet + packet
+        decoder = IWR6843Decoder()
+
+        frames = []
+        for i in range(0, len(data), 13):
+            frames += decoder.feed(data[i:i + 13])
+
+        assert len(frames) == 2
+        assert frames[1].points[0].snr_db is None
+
+    def test_corrupt_length_resyncs(self):
+        """A header with an impossible length should be skipped, not waited on."""
+        bad = bytearray(_iwr_packet([(0.0, 1.0, 0.0, 1.0)]))
+        struct.pack_into("<I", bad, 12, 10**9)
+
+        frames = IWR6843Decoder().feed(bytes(bad) + _iwr_packet([(0.0, 1.0, 0.0, 2.0)]))
+
+        assert [f.points[0].velocity for f in frames] == [2.0]
+
+    def test_non_finite_points_discarded(self):
+        """NaN or infinite coordinates should be dropped and counted."""
+        decoder = IWR6843Decoder()
+
+        frames = decoder.feed(_iwr_packet([(math.nan, 1.0, 0.0, 40.0), (0.0, 1.0, 0.0, math.inf)]))
+
+        assert frames[0].points == []
+        assert decoder.discarded == 2
+
+
+class _FakeCli:
+    """CLI port that answers Done, or Error for commands it was told to reject."""
+
+    def __init__(self, reject=()):
+        self.is_open = True
+        self.commands = []
+        self.reject = reject
+        self._response = b""
+
+    def reset_input_buffer(self):
+        self._response = b""
+
+    def write(self, data):
+        command = data.decode().strip()
+        self.commands.append(command)
+        if command == "version":
+            answer = "Platform : xWR68xx\nmmWave SDK Version : 03.05.00.04\nDone"
+        else:
+            answer = "Error -1" if command.split()[0] in self.reject else "Done"
+        self._response = f"{command}\n{answer}\nmmwDemo:/>".encode()
+
+    def read(self, _size):
+        data, self._response = self._response, b""
+        return data
+
+    def close(self):
+        self.is_open = False
+
+
+def _radar(**kwargs):
+    radar = IWR6843Radar(cli_port="/dev/ttyUSB0", data_port="/dev/ttyUSB1", **kwargs)
+    radar.cli = _FakeCli()
+    return radar
+
+
+class TestConfiguration:
+    """Tests for the CLI port."""
+
+    def test_configure_sends_golf_config(self):
+        """Every built-in configuration line should be sent, ending with sensorStart."""
+        radar = _radar()
+
+        radar.configure_for_golf()
+
+        assert radar.cli.commands == IWR6843Radar.GOLF_CONFIG
+        assert radar.cli.commands[-1] == "sensorStart"
+
+    def test_config_file_skips_comments(self, tmp_path):
+        """A .cfg file's commands should replace the built-in ones, without % comments."""
+        path = tmp_path / "golf.cfg"
+        path.write_text("% Created by mmWave Demo Visualizer\nsensorStop\n\nsensorStart\n")
+        radar = _radar(config_file=str(path))
+
+        radar.configure_for_golf()
+
+        assert radar.cli.commands == ["sensorStop", "sensorStart"]
+
+    def test_rejected_command_raises(self):
+        """A command the demo answers with Error should raise."""
+        radar = _radar()
+        radar.cli.reject = ("profileCfg",)
+
+        with pytest.raises(RuntimeError, match="profileCfg"):
+            radar.configure_for_golf()
+
+    def test_get_info(self):
+        """The SDK version should be reported as the firmware version."""
+        info = _radar().get_info()
+
+        assert info["Product"] == "IWR6843"
+        assert info["Version"] == "03.05.00.04"
+
+    def test_both_ports_required(self):
+        """Connecting without a data port should explain what's missing."""
+        with pytest.raises(ConnectionError, match="data port"):
+            IWR6843Radar(cli_port="/dev/ttyUSB0").connect()
+
+
+class TestStreaming:
+    """Tests for turning data port packets into readings."""
+
+    def test_readings_with_filters(self):
+        """Moving points should reach the callback as readings, after the speed filter."""
+        radar = _radar()
+        readings, frames = [], []
+        radar._callback = readings.append
+        radar._frame_callback = frames.append
+        radar.set_min_speed_filter(20)
+
+        radar.process_data(
+            _iwr_packet([(0.0, 2.0, 0.0, 60.0), (0.0, 2.0, 0.0, 2.0)], snrs=[300, 400]),
+            timestamp=1.0,
+        )
+        radar.process_data(_iwr_packet([(0.0, 2.0, 0.0, -3.0)], snrs=[200]), timestamp=1.1)
+
+        assert len(frames) == 2
+        assert len(readings) == 1
+        assert readings[0].speed == pytest.approx(134.2, abs=0.1)
+        assert readings[0].direction == Direction.OUTBOUND
+        assert readings[0].frame_objects is None
+
+    def test_launch_monitor_uses_internal_processing(self):
+        """A LaunchMonitor on an IWR6843 should configure it, not try I/Q streaming."""
+        radar = _radar()
+        radar.connect = lambda: True
+        monitor = LaunchMonitor(radar=radar)
+
+        monitor.connect()
+
+        assert monitor.radar is radar
+        assert not monitor._use_iq_streaming
+        assert radar.cli.commands == IWR6843Radar.GOLF_CONFIG
@@ -1,5 +1,7 @@
 """Tests for launch_monitor module."""
 
+import math
+import time
 import pytest
 from datetime import datetime
 
@@ -9,7 +11,11 @@ from openflight.launch_monitor import (
     estimate_carry_distance,
     adjust_carry_for_launch_angle,
     LaunchMonitor,
+    ConfigError,
+    MonitorConfig,
 )
+from openflight.ops243 import capabilities_for
+from openflight.units import Degrees, MetersPerSecond, Mph, Rpm
 
 
 class TestEstimateCarryDistance:
@@ -69,6 +75,20 @@ class TestShot:
         assert shot.ball_speed_mph == 150.0
         assert shot.club == ClubType.DRIVER  # default
 
+    def test_unit_typed_fields(self):
+        """Speeds, angles and spin should be typed, and m/s rejected for mph fields."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime.now(), spin_rpm=2500)
+        shot.launch_angle_vertical = 12.0
+
+        assert isinstance(shot.ball_speed_mph, Mph)
+        assert isinstance(shot.spin_rpm, Rpm)
+        assert isinstance(shot.launch_angle_vertical, Degrees)
+        assert shot.ball_speed_ms == pytest.approx(67.056)
+        with pytest.raises(TypeError):
+            Shot(ball_speed_mph=MetersPerSecond(67.0), timestamp=datetime.now())
+        with pytest.raises(TypeError):
+            shot.club_speed_mph = MetersPerSecond(45.0)
+
     def test_shot_with_club_speed(self):
         """Shot with both ball and club speed."""
         shot = Shot(
@@ -161,8 +181,7 @@ class TestLaunchMonitorSessionStats:
 
     def test_empty_session_stats(self):
         """Empty session should return zeros."""
-        monitor = LaunchMonitor.__new__(LaunchMonitor)
-        monitor._shots = []
+        monitor = LaunchMonitor(use_iq_streaming=False)
 
         stats = monitor.get_session_stats()
 
@@ -173,10 +192,12 @@ class TestLaunchMonitorSessionStats:
         assert stats["avg_club_speed"] is None
         assert stats["avg_smash_factor"] is None
         assert stats["avg_carry_est"] == 0
+        assert stats["avg_tempo_ratio"] is None
+        assert stats["discarded_readings"] == 0
 
     def test_single_shot_stats(self):
         """Stats with a single shot."""
-        monitor = LaunchMonitor.__new__(LaunchMonitor)
+        monitor = LaunchMonitor(use_iq_streaming=False)
         monitor._shots = [
             Shot(ball_speed_mph=150.0, club_speed_mph=100.0, timestamp=datetime.now())
         ]
@@ -192,7 +213,7 @@ class TestLaunchMonitorSessionStats:
 
     def test_multiple_shots_stats(self):
         """Stats with multiple shots."""
-        monitor = LaunchMonitor.__new__(LaunchMonitor)
+        monitor = LaunchMonitor(use_iq_streaming=False)
         monitor._shots = [
             Shot(ball_speed_mph=140.0, club_speed_mph=95.0, timestamp=datetime.now()),
             Shot(ball_speed_mph=150.0, club_speed_mph=100.0, timestamp=datetime.now()),
@@ -206,9 +227,29 @@ class TestLaunchMonitorSessionStats:
         assert stats["max_ball_speed"] == 160.0
         assert stats["min_ball_speed"] == 140.0
 
+    def test_tempo_stats(self):
+        """Tempo averages over the shots it was measured for."""
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        monitor._shots = [
+            Shot(ball_speed_mph=150.0, timestamp=datetime.now()),
+            Shot(
+                ball_speed_mph=150.0, timestamp=datetime.now(),
+                tempo_backswing_sec=0.75, tempo_downswing_sec=0.25, tempo_target_ratio=3.0,
+            ),
+            Shot(
+                ball_speed_mph=150.0, timestamp=datetime.now(),
+                tempo_backswing_sec=0.5, tempo_downswing_sec=0.25, tempo_target_ratio=3.0,
+            ),
+        ]
+
+        stats = monitor.get_session_stats()
+
+        assert stats["avg_tempo_ratio"] == pytest.approx(2.5)
+        assert stats["tempo_on_target_pct"] == 50
+
     def test_clear_session(self):
         """Clear session should reset shots."""
-        monitor = LaunchMonitor.__new__(LaunchMonitor)
+        monitor = LaunchMonitor(use_iq_streaming=False)
         monitor._shots = [
             Shot(ball_speed_mph=150.0, timestamp=datetime.now())
         ]
@@ -218,10 +259,22 @@ class TestLaunchMonitorSessionStats:
         assert monitor._shots == []
         assert monitor.get_session_stats()["shot_count"] == 0
 
+    def test_discard_last_shot(self):
+        """A mulligan should remove only the most recent shot."""
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        monitor._shots = [
+            Shot(ball_speed_mph=140.0, timestamp=datetime.now()),
+            Shot(ball_speed_mph=150.0, timestamp=datetime.now()),
+        ]
+
+        assert monitor.discard_last_shot().ball_speed_mph == 150.0
+        assert [s.ball_speed_mph for s in monitor._shots] == [140.0]
+        monitor.discard_last_shot()
+        assert monitor.discard_last_shot() is None
+
     def test_set_club(self):
         """Set club should update current club."""
-        monitor = LaunchMonitor.__new__(LaunchMonitor)
-        monitor._current_club = ClubType.DRIVER
+        monitor = LaunchMonitor(use_iq_streaming=False)
 
         monitor.set_club(ClubType.IRON_7)
 
@@ -233,13 +286,7 @@ class TestShotDetection:
 
     def setup_method(self):
         """Set up test monitor instance."""
-        self.monitor = LaunchMonitor.__new__(LaunchMonitor)
-        self.monitor._shots = []
-        self.monitor._current_readings = []
-        self.monitor._shot_callback = None
-        self.monitor._detect_club_speed = True
-        self.monitor._current_club = ClubType.DRIVER
-        self.monitor._use_iq_streaming = False
+        self.monitor = LaunchMonitor(use_iq_streaming=False)
 
     def test_process_shot_extracts_ball_speed(self):
         """Ball speed should be the peak reading."""
@@ -279,6 +326,51 @@ class TestShotDetection:
         assert shot.ball_speed_mph == 145.0
         assert shot.club_speed_mph is not None
         assert 95 <= shot.club_speed_mph <= 105
+        # The ball is at full strength in its first frame: hit right after the club's peak
+        assert shot.impact_time == pytest.approx(base_time + 0.02)
+        assert shot.impact_offset_ms == pytest.approx(0.0, abs=1e-3)
+
+    def test_process_shot_lost_ball_frames(self):
+        """Ball frames lost after the club are taken back along the ball's slowdown."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        base_time = 1000.0
+        frames = [(0.00, 98.0), (0.01, 99.0), (0.02, 100.0)]  # Club
+        frames += [(0.05, 149.0), (0.06, 148.5), (0.07, 148.0), (0.08, 147.5)]  # Ball
+        self.monitor._current_readings = [
+            SpeedReading(speed=mph, direction=Direction.OUTBOUND, magnitude=300,
+                         timestamp=base_time + t)
+            for t, mph in frames
+        ]
+
+        self.monitor._process_shot()
+
+        shot = self.monitor._shots[0]
+        assert shot.lost_frames == 2
+        assert shot.max_reading_gap_ms == pytest.approx(10.0)
+        assert shot.ball_speed_reading_mph == 149.0
+        assert shot.ball_speed_mph == pytest.approx(150.0)
+
+    def test_recovered_speed_kept_within_smash_window(self):
+        """The club is paired against the recovered ball speed, never outside the smash window."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        base_time = 1000.0
+        frames = [(0.00, 98.0), (0.01, 99.0), (0.02, 100.0)]  # Club
+        frames += [(0.05, 152.0), (0.06, 151.0), (0.07, 150.0), (0.08, 149.0)]  # Ball
+        self.monitor._current_readings = [
+            SpeedReading(speed=mph, direction=Direction.OUTBOUND, magnitude=300,
+                         timestamp=base_time + t)
+            for t, mph in frames
+        ]
+
+        self.monitor._process_shot()
+
+        shot = self.monitor._shots[0]
+        assert shot.ball_speed_reading_mph == 152.0
+        assert shot.ball_speed_mph == pytest.approx(154.0)
+        # 154 / 100 is above a driver's loft limit (~1.52), so the club isn't paired
+        assert shot.club_speed_mph is None
 
     def test_process_shot_minimum_readings(self):
         """Zero readings should not create a shot."""
@@ -360,13 +452,7 @@ class TestClubBallSeparation:
 
     def setup_method(self):
         """Set up test monitor instance."""
-        self.monitor = LaunchMonitor.__new__(LaunchMonitor)
-        self.monitor._shots = []
-        self.monitor._current_readings = []
-        self.monitor._shot_callback = None
-        self.monitor._detect_club_speed = True
-        self.monitor._current_club = ClubType.DRIVER
-        self.monitor._use_iq_streaming = False
+        self.monitor = LaunchMonitor(use_iq_streaming=False)
 
     def test_club_detected_before_ball_by_timing(self):
         """Club reading before ball (by timestamp) should be detected."""
@@ -488,6 +574,104 @@ class TestClubBallSeparation:
         assert shot.club_speed_mph == 100.0
         assert shot.smash_factor == 1.4
 
+    def _shot_with_club(self, club_speed, ball_speed):
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        base_time = time.time()
+        self.monitor._current_readings = [
+            SpeedReading(speed=club_speed, direction=Direction.OUTBOUND, magnitude=2000, timestamp=base_time),
+            SpeedReading(speed=ball_speed, direction=Direction.OUTBOUND, magnitude=1000, timestamp=base_time + 0.1),
+        ]
+        self.monitor._process_shot()
+        return self.monitor._shots[-1]
+
+    def test_driver_smash_capped_by_loft(self):
+        """Smash 1.6 is inside the global window but more than a driver can deliver."""
+        shot = self._shot_with_club(100.0, 160.0)
+
+        assert shot.ball_speed_mph == 160.0
+        assert shot.club_speed_mph is None
+
+    def test_wedge_low_smash_accepted(self):
+        """A sand wedge's smash of 1.05 is below the global window but normal for its loft."""
+        self.monitor._current_club = ClubType.SW
+
+        shot = self._shot_with_club(80.0, 84.0)
+
+        assert shot.club_speed_mph == 80.0
+
+    def test_unknown_club_uses_global_window(self):
+        """Without a club, the global smash window applies."""
+        self.monitor._current_club = ClubType.UNKNOWN
+
+        assert self._shot_with_club(100.0, 160.0).club_speed_mph == 100.0
+        assert self._shot_with_club(80.0, 84.0).club_speed_mph is None
+
+    def test_club_timing_for_selected_club(self):
+        """A club's shot timing replaces the config's only while that club is selected."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        self.monitor.set_club_timing(
+            {
+                "sw": {
+                    "club_ball_window_sec": 0.5,
+                    "max_shot_duration_sec": 0.5,
+                    "shot_timeout_sec": 0.8,
+                }
+            }
+        )
+
+        def chip():
+            base_time = time.time()
+            self.monitor._current_readings = [
+                SpeedReading(speed=60.0, direction=Direction.OUTBOUND, magnitude=2000, timestamp=base_time),
+                SpeedReading(speed=66.0, direction=Direction.OUTBOUND, magnitude=1000, timestamp=base_time + 0.4),
+            ]
+            self.monitor._process_shot()
+
+        chip()
+        assert self.monitor._shots == []  # 400 ms is too long for the default 300 ms
+
+        self.monitor._current_club = ClubType.SW
+        chip()
+        assert self.monitor._shots[-1].club_speed_mph == 60.0
+        assert self.monitor._timing(self.monitor._config, "shot_timeout_sec") == 0.8
+
+        self.monitor._current_club = ClubType.DRIVER
+        assert self.monitor._timing(self.monitor._config, "shot_timeout_sec") == (
+            self.monitor._config.shot_timeout_sec
+        )
+
+    def test_call_when_idle_waits_for_shot(self):
+        """A callback registered mid-shot should run only after that shot is processed."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        calls = []
+        self.monitor.call_when_idle(lambda: calls.append("idle"))
+        assert calls == ["idle"]
+
+        base_time = time.time()
+        self.monitor._current_readings = [
+            SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=1000, timestamp=base_time),
+        ]
+        self.monitor.call_when_idle(lambda: calls.append("after shot"))
+        assert calls == ["idle"]
+
+        self.monitor._process_shot()
+        self.monitor._run_idle_callbacks()
+        assert calls == ["idle", "after shot"]
+
+    def test_club_timing_not_shared_between_monitors(self):
+        """One monitor's club timing must not leak into another's."""
+        self.monitor.set_club_timing({"sw": {"shot_timeout_sec": 0.8}})
+        other = LaunchMonitor(use_iq_streaming=False)
+        other._current_club = ClubType.SW
+
+        assert other._timing(other._config, "shot_timeout_sec") == other._config.shot_timeout_sec
+
 
 class TestAdjustCarryForLaunchAngle:
     """Tests for launch-angle-based carry distance adjustment."""
@@ -564,3 +748,589 @@ class TestMultiObjectReporting:
         assert Direction.INBOUND.value == "inbound"
         assert Direction.OUTBOUND.value == "outbound"
         assert Direction.UNKNOWN.value == "unknown"
+
+
+class TestSameFrameClubDetection:
+    """Tests for pairing club and ball seen in one multi-object frame."""
+
+    def setup_method(self):
+        """Set up test monitor instance."""
+        self.monitor = LaunchMonitor(use_iq_streaming=False)
+
+    def test_same_frame_pair_used(self):
+        """Club in the same frame as the ball should set club speed."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        now = time.time()
+        ball = SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=200, timestamp=now)
+        club = SpeedReading(
+            speed=103.0,
+            direction=Direction.OUTBOUND,
+            magnitude=600,
+            timestamp=now,
+            frame_objects=[ball],
+        )
+        self.monitor._current_readings = [club]
+
+        self.monitor._process_shot()
+
+        shot = self.monitor._shots[0]
+        assert shot.ball_speed_mph == 150.0
+        assert shot.club_speed_mph == 103.0
+
+    def test_same_frame_preferred_over_temporal(self):
+        """A same-frame club should win over an earlier temporal candidate."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        now = time.time()
+        self.monitor._current_readings = [
+            # Earlier, stronger reading that the temporal heuristic would pick
+            SpeedReading(speed=95.0, direction=Direction.OUTBOUND, magnitude=900, timestamp=now),
+            SpeedReading(
+                speed=150.0,
+                direction=Direction.OUTBOUND,
+                magnitude=300,
+                timestamp=now + 0.05,
+                frame_objects=[
+                    SpeedReading(
+                        speed=104.0,
+                        direction=Direction.OUTBOUND,
+                        magnitude=250,
+                        timestamp=now + 0.05,
+                    )
+                ],
+            ),
+        ]
+
+        self.monitor._process_shot()
+
+        assert self.monitor._shots[0].club_speed_mph == 104.0
+
+    def test_implausible_pair_falls_back(self):
+        """Frame pairs outside the club/ball ratio should be ignored."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        now = time.time()
+        slow = SpeedReading(speed=40.0, direction=Direction.OUTBOUND, magnitude=100, timestamp=now)
+        self.monitor._current_readings = [
+            SpeedReading(
+                speed=150.0,
+                direction=Direction.OUTBOUND,
+                magnitude=300,
+                timestamp=now,
+                frame_objects=[slow],
+            ),
+        ]
+
+        self.monitor._process_shot()
+
+        assert self.monitor._shots[0].club_speed_mph is None
+
+    def test_frame_objects_logged(self):
+        """Frame objects should be kept in the serialized readings."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        now = time.time()
+        ball = SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=200, timestamp=now)
+        self.monitor._current_readings = [
+            SpeedReading(
+                speed=103.0,
+                direction=Direction.OUTBOUND,
+                magnitude=600,
+                timestamp=now,
+                frame_objects=[ball],
+            ),
+        ]
+
+        self.monitor._process_shot()
+
+        logged = self.monitor._shots[0].readings_data[0]
+        assert logged["frame_objects"][0]["speed"] == 150.0
+
+
+class TestMountPositionFiltering:
+    """Tests for direction filtering by mounting position."""
+
+    def _monitor(self, position):
+        from openflight.ops243 import MountPosition
+
+        return LaunchMonitor(
+            detect_club_speed=False,
+            use_iq_streaming=True,
+            mount_position=MountPosition(position),
+        )
+
+    def test_behind_ignores_inbound(self):
+        """Default mounting should drop inbound readings."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor("behind")
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.INBOUND, magnitude=1))
+
+        assert monitor._current_readings == []
+
+    def test_front_accepts_inbound(self):
+        """Front mounting should treat inbound readings as ball flight."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor("front")
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.INBOUND, magnitude=1))
+        monitor._on_reading(SpeedReading(speed=40.0, direction=Direction.OUTBOUND, magnitude=1))
+
+        assert [r.speed for r in monitor._current_readings] == [150.0]
+
+
+class TestConfiguredFilters:
+    """Tests for running the reading filter pipeline from the config."""
+
+    def _monitor(self, **config):
+        return LaunchMonitor(use_iq_streaming=False, config=MonitorConfig(**config))
+
+    def test_default_pipeline_filters_magnitude(self):
+        """With the radar's internal processing, weak readings are dropped by default."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor()
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=5))
+
+        assert monitor._current_readings == []
+
+    def test_disabled_filter_keeps_reading(self):
+        """Leaving a filter out of the pipeline disables it."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor(filters=("speed", "direction"))
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=5))
+
+        assert len(monitor._current_readings) == 1
+
+    def test_injected_pipeline(self):
+        """A pipeline of filter instances replaces the config's until the config changes."""
+        from openflight.filters import Filter, FilterPipeline
+        from openflight.ops243 import SpeedReading, Direction
+
+        class RejectAll(Filter):
+            def check(self, reading, context):
+                return "rejected"
+
+        monitor = self._monitor()
+        monitor.set_filter_pipeline(FilterPipeline([RejectAll()]))
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=500))
+        assert monitor._current_readings == []
+
+        monitor.set_config(MonitorConfig())
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=500))
+        assert len(monitor._current_readings) == 1
+
+    def test_refractory_after_shot(self):
+        """The refractory filter drops readings right after a shot."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor(filters=("refractory", "direction"), refractory_sec=60.0)
+        monitor._last_shot_time = time.monotonic()
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=500))
+
+        assert monitor._current_readings == []
+
+
+class TestDegenerateReadings:
+    """Tests for dropping NaN and infinite readings before shot detection."""
+
+    def _monitor(self):
+        return LaunchMonitor(use_iq_streaming=True)
+
+    def test_nan_readings_discarded_and_counted(self):
+        """NaN speeds or magnitudes should never be buffered, and should be counted."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor()
+        monitor._on_reading(SpeedReading(speed=math.nan, direction=Direction.OUTBOUND))
+        monitor._on_reading(
+            SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=math.inf)
+        )
+        monitor._on_reading(SpeedReading(speed=150.0, direction=Direction.OUTBOUND, magnitude=1))
+
+        assert [r.speed for r in monitor._current_readings] == [150.0]
+        assert monitor.get_session_stats()["discarded_readings"] == 2
+
+    def test_bad_frame_objects_dropped(self):
+        """A NaN object in a multi-object frame should be dropped, keeping the reading."""
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = self._monitor()
+        monitor._on_reading(
+            SpeedReading(
+                speed=150.0,
+                direction=Direction.OUTBOUND,
+                magnitude=1,
+                frame_objects=[SpeedReading(speed=math.nan, direction=Direction.OUTBOUND)],
+            )
+        )
+
+        assert monitor._current_readings[0].frame_objects is None
+        assert monitor.discarded_readings == 1
+
+
+class TestMonitorConfig:
+    """Tests for MonitorConfig thresholds and live updates."""
+
+    def test_defaults_are_valid(self):
+        """Default config should pass validation."""
+        MonitorConfig().validate()
+
+    def test_with_updates_returns_copy(self):
+        """with_updates should not modify the original config."""
+        config = MonitorConfig()
+
+        updated = config.with_updates({"min_shot_magnitude": 80})
+
+        assert updated.min_shot_magnitude == 80
+        assert config.min_shot_magnitude == 100
+
+    def test_fingerprint(self):
+        """Equal configs should share a fingerprint and any change should alter it."""
+        config = MonitorConfig()
+
+        assert config.fingerprint() == MonitorConfig().fingerprint()
+        assert len(config.fingerprint()) == 12
+        assert config.with_updates({"shot_timeout_sec": 0.6}).fingerprint() != config.fingerprint()
+
+    def test_unknown_field_rejected(self):
+        """Unknown field names should raise ValueError suggesting the closest name."""
+        with pytest.raises(ValueError, match="did you mean min_shot_magnitude"):
+            MonitorConfig().with_updates({"min_shot_magnitud": 80})
+
+    def test_inverted_speed_range_rejected(self):
+        """min ball speed above max should be rejected, naming both settings."""
+        message = r"^min_ball_speed_mph: 250 mph is above max_ball_speed_mph \(220 mph\)"
+        with pytest.raises(ValueError, match=message):
+            MonitorConfig().with_updates({"min_ball_speed_mph": 250})
+
+    def test_non_numeric_rejected(self):
+        """Non-numeric values should be rejected."""
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"shot_timeout_sec": "fast"})
+
+    def test_fractional_min_readings_rejected(self):
+        """min_readings_for_shot must be a whole number."""
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"min_readings_for_shot": 1.5})
+
+    def test_smash_bounds_rejected(self):
+        """Smash factor minimum below 1 should be rejected."""
+        with pytest.raises(ValueError, match="^smash_factor_min: must be >= 1"):
+            MonitorConfig().with_updates({"smash_factor_min": 0.5})
+
+    def test_implausible_values_rejected(self):
+        """Values no golf shot could need should be rejected with the limit and the value."""
+        with pytest.raises(ConfigError, match=r"^smash_factor_max: must be <= 2 .*got 3$"):
+            MonitorConfig().with_updates({"smash_factor_max": 3})
+        with pytest.raises(ConfigError, match="^max_ball_speed_mph: must be <= 250 mph"):
+            MonitorConfig().with_updates({"max_ball_speed_mph": 400})
+        with pytest.raises(ConfigError, match=r"^club_speed_max_ratio: must be in \(0, 1\]"):
+            MonitorConfig().with_updates({"club_speed_max_ratio": 1.2})
+        with pytest.raises(ConfigError, match="^shot_timeout_sec: must be a finite number"):
+            MonitorConfig().with_updates({"shot_timeout_sec": float("inf")})
+
+    def test_error_names_key(self):
+        """ConfigError should carry the offending key."""
+        with pytest.raises(ConfigError) as excinfo:
+            MonitorConfig().with_updates({"club_speed_min_ratio": 0.9})
+
+        assert excinfo.value.key == "club_speed_min_ratio"
+        assert "above club_speed_max_ratio (0.85)" in str(excinfo.value)
+
+    def test_inverted_range_gate_rejected(self):
+        """A range gate ending before it starts should be rejected; max 0 means no far limit."""
+        config = MonitorConfig().with_updates({"min_range_m": 0.5})
+
+        assert config.max_range_m == 0.0
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"min_range_m": 3.0, "max_range_m": 0.5})
+
+    def test_angle_corridor_validated(self):
+        """Angle limits may be negative but must stay within +-90 and in order."""
+        config = MonitorConfig().with_updates({"min_azimuth_deg": -20, "max_azimuth_deg": 10})
+
+        assert config.min_azimuth_deg == -20
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"min_elevation_deg": 30, "max_elevation_deg": 10})
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"max_azimuth_deg": 120})
+
+    def test_segmentation_mode_validated(self):
+        """segmentation should only accept known modes."""
+        config = MonitorConfig().with_updates({"segmentation": "change-point"})
+
+        assert config.segmentation == "change-point"
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"segmentation": "magic"})
+
+    def test_filters_validated(self):
+        """filters should take a list of known filter names."""
+        config = MonitorConfig().with_updates({"filters": ["refractory", "direction"]})
+
+        assert config.filters == ("refractory", "direction")
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"filters": ["direction", "magic"]})
+        with pytest.raises(ValueError):
+            MonitorConfig().with_updates({"filters": "direction"})
+
+    def test_change_point_segmentation_splits_shots(self, monkeypatch):
+        """In change-point mode a new swing should close the previous shot."""
+        from openflight import launch_monitor
+        from openflight.ops243 import SpeedReading, Direction
+
+        monitor = LaunchMonitor(detect_club_speed=False, use_iq_streaming=True)
+        monitor.set_config(MonitorConfig().with_updates({"segmentation": "change-point"}))
+
+        events = [(10.0, 60, 900), (10.12, 61, 950), (10.24, 60, 900)]
+        events += [(10.5, 150, 5000), (10.62, 152, 5200), (10.74, 151, 5100)]
+        for t, speed, magnitude in events:
+            monkeypatch.setattr(launch_monitor.time, "monotonic", lambda t=t: t)
+            monitor._on_reading(
+                SpeedReading(
+                    speed=speed, direction=Direction.OUTBOUND, magnitude=magnitude, timestamp=t
+                )
+            )
+
+        assert [shot.ball_speed_mph for shot in monitor._shots] == [61]
+        assert [r.speed for r in monitor._current_readings] == [150, 152, 151]
+
+    def test_set_config_applies_to_next_shot(self):
+        """New thresholds should take effect without restarting."""
+        from openflight.ops243 import SpeedReading, Direction
+        import time
+
+        monitor = LaunchMonitor(detect_club_speed=False, use_iq_streaming=False)
+        monitor.set_config(MonitorConfig().with_updates({"min_shot_magnitude": 500}))
+
+        monitor._current_readings = [
+            SpeedReading(
+                speed=150.0, direction=Direction.OUTBOUND, magnitude=300, timestamp=time.time()
+            ),
+        ]
+        monitor._process_shot()
+        assert len(monitor._shots) == 0
+
+        monitor.set_config(monitor.config.with_updates({"min_shot_magnitude": 100}))
+        monitor._current_readings = [
+            SpeedReading(
+                speed=150.0, direction=Direction.OUTBOUND, magnitude=300, timestamp=time.time()
+            ),
+        ]
+        monitor._process_shot()
+        assert len(monitor._shots) == 1
+
+    def test_set_config_rejects_invalid(self):
+        """set_config should refuse an invalid config and keep the old one."""
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        bad = MonitorConfig(min_club_speed_mph=200)
+
+        with pytest.raises(ValueError):
+            monitor.set_config(bad)
+
+        assert monitor.config == MonitorConfig()
+
+    def test_takeaway_settings_validated(self):
+        """The takeaway scale must be a fraction and the reading count an integer."""
+        with pytest.raises(ValueError):
+            MonitorConfig(takeaway_threshold_scale=0).validate()
+        with pytest.raises(ValueError):
+            MonitorConfig(takeaway_threshold_scale=1.5).validate()
+        with pytest.raises(ValueError):
+            MonitorConfig(takeaway_min_readings=1.5).validate()
+
+
+class TestTakeawayWindow:
+    """Tests for relaxed thresholds after a backswing."""
+
+    def _monitor(self, window_sec):
+        monitor = LaunchMonitor(detect_club_speed=False, use_iq_streaming=False)
+        monitor.set_config(
+            MonitorConfig(takeaway_window_sec=window_sec, takeaway_threshold_scale=0.5)
+        )
+        return monitor
+
+    def _feed(self, monitor, monkeypatch, events):
+        from openflight import launch_monitor
+        from openflight.ops243 import SpeedReading, Direction
+
+        for t, speed, direction, magnitude in events:
+            monkeypatch.setattr(launch_monitor.time, "monotonic", lambda t=t: t)
+            monitor._on_reading(
+                SpeedReading(
+                    speed=speed,
+                    direction=Direction(direction),
+                    magnitude=magnitude,
+                    timestamp=t,
+                )
+            )
+        monkeypatch.setattr(launch_monitor.time, "monotonic", lambda: events[-1][0] + 1.0)
+        monitor._process_shot()
+
+    # A backswing (inbound from behind), then a 150 mph ball with a weak return
+    SWING = [
+        (10.0, 15, "inbound", 300),
+        (10.1, 20, "inbound", 300),
+        (11.0, 150, "outbound", 70),
+    ]
+
+    def test_weak_shot_after_backswing_accepted(self, monkeypatch):
+        """Inside the window, a shot below the strict magnitude thresholds should count."""
+        monitor = self._monitor(window_sec=2.0)
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        assert [shot.ball_speed_mph for shot in monitor._shots] == [150]
+        assert not monitor._takeaway.is_open(11.0)
+
+    def test_weak_shot_without_window_rejected(self, monkeypatch):
+        """With the window off, the same readings should fail the strict thresholds."""
+        monitor = self._monitor(window_sec=0.0)
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        assert monitor._shots == []
+
+    def test_window_expires(self, monkeypatch):
+        """A weak shot long after the backswing should be judged strictly."""
+        monitor = self._monitor(window_sec=0.5)
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        assert monitor._shots == []
+
+
+class TestPreTriggerBuffer:
+    """Tests for attaching filtered-out readings from before a shot."""
+
+    def _monitor(self, **config):
+        monitor = LaunchMonitor(detect_club_speed=False, use_iq_streaming=False)
+        monitor.set_config(MonitorConfig(**config))
+        return monitor
+
+    def _feed(self, monitor, monkeypatch, events):
+        from openflight import launch_monitor
+        from openflight.ops243 import SpeedReading, Direction
+
+        for t, speed, magnitude in events:
+            monkeypatch.setattr(launch_monitor.time, "monotonic", lambda t=t: t)
+            monitor._on_reading(
+                SpeedReading(speed, Direction.OUTBOUND, magnitude=magnitude, timestamp=t)
+            )
+        monitor._process_shot()
+
+    # Weak club readings (below min_magnitude), then the ball
+    SWING = [
+        (9.5, 60, 10),
+        (9.85, 80, 10),
+        (9.9, 85, 10),
+        (9.95, 90, 10),
+        (10.0, 150, 500),
+    ]
+
+    def test_approach_attached(self, monkeypatch):
+        """Rejected readings within pre_trigger_sec of the shot should be attached to it."""
+        monitor = self._monitor()
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        shot = monitor._shots[0]
+        assert [r.speed for r in shot.pre_trigger_readings] == [80, 85, 90]
+        assert [entry["speed"] for entry in shot.pre_trigger_data] == [80, 85, 90]
+
+    def test_decimation_and_size(self, monkeypatch):
+        """Only every Nth rejected reading should be kept, up to the buffer size."""
+        monitor = self._monitor(
+            pre_trigger_sec=1.0, pre_trigger_decimation=2, pre_trigger_buffer_size=1
+        )
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        assert [r.speed for r in monitor._shots[0].pre_trigger_readings] == [90]
+
+    def test_disabled(self, monkeypatch):
+        """pre_trigger_sec 0 should attach nothing."""
+        monitor = self._monitor(pre_trigger_sec=0)
+
+        self._feed(monitor, monkeypatch, self.SWING)
+
+        assert monitor._shots[0].pre_trigger_readings == []
+        assert monitor._shots[0].pre_trigger_data is None
+
+    def test_invalid_settings(self):
+        """Buffer size and decimation should be positive integers."""
+        for updates in ({"pre_trigger_buffer_size": 0}, {"pre_trigger_decimation": 1.5}):
+            with pytest.raises(ValueError):
+                MonitorConfig().with_updates(updates)
+
+
+class _FakeRadar:
+    """Radar stand-in that records how it was configured."""
+
+    def __init__(self, product):
+        self.product = product
+        self.capabilities = capabilities_for(product)
+        self.configured = None
+
+    def connect(self):
+        pass
+
+    def configure_for_iq_streaming(self):
+        self.configured = "iq"
+
+    def configure_for_golf(self):
+        self.configured = "golf"
+
+
+class TestRadarModelFallback:
+    """Tests for adapting to the connected radar model."""
+
+    def _monitor(self, radar):
+        return LaunchMonitor(radar=radar)
+
+    def test_no_iq_output_uses_internal_processing(self):
+        """A model without raw I/Q output should fall back to the radar's own processing."""
+        monitor = self._monitor(_FakeRadar("OPS241-A"))
+
+        monitor.connect()
+
+        assert monitor.radar.configured == "golf"
+        assert not monitor._use_iq_streaming
+
+    def test_iq_output_kept(self):
+        """A model with raw I/Q output should stream I/Q as requested."""
+        monitor = self._monitor(_FakeRadar("OPS243-C"))
+
+        monitor.connect()
+
+        assert monitor.radar.configured == "iq"
+        assert monitor._use_iq_streaming
+
+
+class TestRadarDataAge:
+    """Tests for noticing a radar that stopped sending I/Q data."""
+
+    def test_age_since_last_block(self, monkeypatch):
+        """The age should grow from the last I/Q block while streaming."""
+        from openflight import launch_monitor
+
+        monitor = LaunchMonitor(use_iq_streaming=True)
+        monitor._running = True
+        monkeypatch.setattr(launch_monitor.time, "monotonic", lambda: 100.0)
+        monitor._on_iq_block(object())
+        monkeypatch.setattr(launch_monitor.time, "monotonic", lambda: 112.5)
+
+        assert monitor.radar_data_age_sec == 12.5
+
+    def test_no_age_with_internal_processing(self):
+        """The radar's own processing is quiet when nothing moves, so no age is given."""
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        monitor._running = True
+
+        assert monitor.radar_data_age_sec is None
@@ -0,0 +1,162 @@
+"""Property-based tests for shot detection invariants in launch_monitor."""
+
+import math
+
+from hypothesis import given, settings
+from hypothesis import strategies as st
+
+from openflight.launch_monitor import ClubType, LaunchMonitor, MonitorConfig
+from openflight.ops243 import Direction, SpeedReading
+
+DIRECTIONS = st.sampled_from([Direction.OUTBOUND, Direction.INBOUND])
+SPEEDS = st.floats(min_value=0, max_value=250, allow_nan=False)
+MAGNITUDES = st.one_of(st.none(), st.floats(min_value=0, max_value=5000, allow_nan=False))
+# A few distinct times, so duplicates are common
+TIMES = st.sampled_from([0.0, 0.01, 0.02, 0.05, 0.1, 0.2, 0.31])
+
+DEGENERATE_FLOATS = st.one_of(
+    st.floats(min_value=0, max_value=250),
+    st.sampled_from([math.nan, math.inf, -math.inf]),
+)
+
+
+def _objects(speeds, times, magnitudes):
+    return st.builds(
+        SpeedReading,
+        speed=speeds,
+        direction=DIRECTIONS,
+        magnitude=magnitudes,
+        timestamp=times,
+    )
+
+
+def _readings(speeds=SPEEDS, times=TIMES, magnitudes=MAGNITUDES):
+    """Reading sequences, some with other objects in the same frame."""
+    reading = st.builds(
+        SpeedReading,
+        speed=speeds,
+        direction=DIRECTIONS,
+        magnitude=magnitudes,
+        timestamp=times,
+        frame_objects=st.one_of(
+            st.none(), st.lists(_objects(speeds, st.none(), magnitudes), min_size=1, max_size=3)
+        ),
+    )
+    return st.lists(reading, max_size=12)
+
+
+@st.composite
+def _lossy_readings(draw):
+    """A club then a slowing ball at a steady frame rate, with the first ball frames lost."""
+    spacing = draw(st.sampled_from([0.01, 0.0125, 0.02]))
+    club_frames = draw(st.integers(min_value=1, max_value=3))
+    lost = draw(st.integers(min_value=1, max_value=4))
+    club_speed = draw(st.floats(min_value=40, max_value=130))
+    speed = draw(st.floats(min_value=60, max_value=200))
+    readings = [
+        SpeedReading(
+            speed=club_speed - i,
+            direction=Direction.OUTBOUND,
+            magnitude=3000,
+            timestamp=i * spacing,
+        )
+        for i in range(club_frames)
+    ]
+    for i in range(draw(st.integers(min_value=1, max_value=6))):
+        readings.append(
+            SpeedReading(
+                speed=speed,
+                direction=Direction.OUTBOUND,
+                magnitude=1000,
+                timestamp=(club_frames + lost + i) * spacing,
+            )
+        )
+        speed -= draw(st.floats(min_value=0, max_value=3))
+    return readings
+
+
+@st.composite
+def _configs(draw):
+    smash_min = draw(st.floats(min_value=1.0, max_value=1.5))
+    smash_max = draw(st.floats(min_value=smash_min, max_value=2.0))
+    margin = draw(st.sampled_from([0, 0.05]))
+    return MonitorConfig(
+        smash_factor_min=smash_min, smash_factor_max=smash_max, club_smash_margin=margin
+    )
+
+
+CONFIGS = st.one_of(st.just(MonitorConfig()), _configs())
+CLUBS = st.sampled_from(list(ClubType))
+
+
+def _monitor(config: MonitorConfig, iq_streaming: bool = False) -> LaunchMonitor:
+    return LaunchMonitor(use_iq_streaming=iq_streaming, config=config)
+
+
+def _with_frame_times(readings):
+    # Objects seen in a frame were seen at that frame's time
+    for r in readings:
+        for o in r.frame_objects or []:
+            o.timestamp = r.timestamp
+    return readings
+
+
+class TestShotInvariants:
+    """Invariants of _process_shot over random reading sequences."""
+
+    @settings(max_examples=300, deadline=None)
+    @given(
+        readings=st.one_of(_readings(), _lossy_readings()),
+        config=CONFIGS,
+        club=CLUBS,
+        iq_streaming=st.booleans(),
+    )
+    def test_smash_within_configured_bounds(self, readings, config, club, iq_streaming):
+        """A shot with a club speed should never have a smash outside the club's window."""
+        monitor = _monitor(config, iq_streaming)
+        monitor._current_club = club
+        monitor._current_readings = _with_frame_times(readings)
+        low, high = monitor._smash_window(config)
+
+        monitor._process_shot()
+
+        for shot in monitor._shots:
+            if shot.club_speed_mph:
+                assert low <= shot.smash_factor <= high
+
+    @settings(max_examples=300, deadline=None)
+    @given(
+        readings=_readings(
+            speeds=DEGENERATE_FLOATS,
+            times=st.one_of(TIMES, st.none(), st.sampled_from([math.nan, math.inf])),
+            magnitudes=st.one_of(st.none(), DEGENERATE_FLOATS),
+        ),
+        iq_streaming=st.booleans(),
+    )
+    def test_degenerate_readings_never_raise(self, readings, iq_streaming):
+        """NaN, infinite or duplicate values should never raise or yield a non-finite shot."""
+        monitor = _monitor(MonitorConfig(), iq_streaming)
+        monitor._current_readings = readings
+
+        monitor._process_shot()
+
+        assert len(monitor._shots) <= 1
+        for shot in monitor._shots:
+            assert math.isfinite(shot.ball_speed_mph)
+            assert shot.club_speed_mph is None or math.isfinite(shot.club_speed_mph)
+
+    @settings(max_examples=300, deadline=None)
+    @given(readings=st.one_of(_readings(), _lossy_readings()), config=CONFIGS)
+    def test_club_never_after_ball(self, readings, config):
+        """The club reading should never come from after the ball reading."""
+        monitor = _monitor(config)
+        readings = sorted(_with_frame_times(readings), key=lambda r: r.timestamp)
+        if not readings:
+            return
+        ball = max(readings, key=lambda r: r.speed)
+
+        same_frame = monitor._find_same_frame_club(readings, ball.speed, ball.timestamp)
+        earlier = monitor._find_club_speed(readings, ball.speed, ball.timestamp)
+
+        for club in (same_frame, earlier):
+            assert club is None or club.timestamp <= ball.timestamp
@@ -0,0 +1,187 @@
+"""Tests for mock_radar module: detection should invert the simulated flight."""
+
+import time
+
+import pytest
+
+from openflight.carry import PhysicsCarryModel
+from openflight.geometry import MountingGeometry, apply_cosine_correction
+from openflight.launch_monitor import ClubType, LaunchMonitor
+from openflight.mock_radar import LaunchConditions, MockRadar, ReadingTiming
+from openflight.ops243 import Direction, MountPosition
+
+
+def _detect(radar, launch, mount_position=MountPosition.BEHIND):
+    """Run the mock's readings through the real detector and corrections."""
+    monitor = LaunchMonitor(use_iq_streaming=False, mount_position=mount_position)
+    monitor.set_club(launch.club)
+    shot = monitor.analyze_readings(radar.readings_for(launch))
+    assert shot is not None
+    apply_cosine_correction(shot, radar.geometry)
+    # The camera would supply these
+    shot.launch_angle_vertical = launch.launch_angle_vertical
+    shot.spin_rpm = launch.effective_spin_rpm
+    return shot
+
+
+class TestReadings:
+    """Tests for the synthesized reading sequence."""
+
+    def test_club_before_ball(self):
+        """Club readings come before impact and are stronger than the ball's."""
+        readings = MockRadar(dropout=0.0, seed=1).readings_for(LaunchConditions(150.0))
+        club = [r for r in readings if r.timestamp < 0]
+        ball = [r for r in readings if r.timestamp > 0]
+
+        assert club and ball
+        assert max(r.speed for r in club) < min(r.speed for r in ball)
+        assert min(r.magnitude for r in club) > max(r.magnitude for r in ball)
+
+    def test_radial_projection_reads_low(self):
+        """Launch angle and off-line mounting lower the speed measured at impact."""
+
+        def first_ball_speed(geometry, launch_deg):
+            radar = MockRadar(geometry, dropout=0.0, noise_mph=0.0)
+            readings = radar.readings_for(LaunchConditions(150.0, launch_deg))
+            return next(r.speed for r in readings if r.timestamp > 0)
+
+        aligned = MountingGeometry()
+        offset = MountingGeometry(lateral_offset_m=0.5, distance_m=1.0)
+
+        assert first_ball_speed(aligned, 30.0) < first_ball_speed(aligned, 0.0)
+        assert first_ball_speed(offset, 0.0) < first_ball_speed(aligned, 0.0) * 0.97
+
+    def test_ball_lost_with_distance(self):
+        """Readings stop once the ball is too far away to return a signal."""
+        readings = MockRadar(dropout=0.0, seed=1).readings_for(LaunchConditions(150.0))
+
+        assert max(r.timestamp for r in readings) < MockRadar.OBSERVE_SEC
+
+    def test_dropout(self):
+        """Full dropout reports nothing; a seed makes dropout repeatable."""
+        launch = LaunchConditions(150.0)
+
+        assert MockRadar(dropout=1.0).readings_for(launch) == []
+        assert MockRadar(dropout=0.3, seed=7).readings_for(launch) == MockRadar(
+            dropout=0.3, seed=7
+        ).readings_for(launch)
+
+    def test_front_mount_is_inbound(self):
+        """A radar down-range sees the club and ball approaching until the ball passes."""
+        radar = MockRadar(
+            MountingGeometry(distance_m=5.0), mount_position=MountPosition.FRONT, seed=1
+        )
+
+        readings = radar.readings_for(LaunchConditions(150.0))
+        approaching = [r for r in readings if r.timestamp < 0.05]
+
+        assert approaching
+        assert {r.direction for r in approaching} == {Direction.INBOUND}
+
+
+class TestEndToEnd:
+    """Detection and carry estimation should recover the simulated shot."""
+
+    @pytest.mark.parametrize(
+        "club,ball_speed,launch",
+        [
+            (ClubType.DRIVER, 150.0, 11.0),
+            (ClubType.IRON_7, 115.0, 17.0),
+            (ClubType.PW, 95.0, 24.0),
+        ],
+    )
+    def test_inverts_simulation(self, club, ball_speed, launch):
+        """Ball speed within 3% and carry within 5% of the simulated flight."""
+        radar = MockRadar(MountingGeometry(lateral_offset_m=0.3), seed=3)
+        conditions = LaunchConditions(ball_speed, launch, club=club)
+
+        shot = _detect(radar, conditions)
+
+        assert shot.ball_speed_mph == pytest.approx(ball_speed, rel=0.03)
+        assert PhysicsCarryModel().estimate(shot) == pytest.approx(
+            radar.true_carry_yards(conditions), rel=0.05
+        )
+
+    def test_club_speed_recovered(self):
+        """Club speed should be picked out of the pre-impact readings."""
+        radar = MockRadar(dropout=0.0, seed=1)
+
+        shot = _detect(radar, LaunchConditions(150.0, 11.0, club_speed_mph=103.0))
+
+        assert shot.club_speed_mph == pytest.approx(103.0, rel=0.05)
+
+    def test_front_mount_detected(self):
+        """A front-mounted monitor should detect the inbound ball."""
+        radar = MockRadar(
+            MountingGeometry(distance_m=5.0), mount_position=MountPosition.FRONT, seed=2
+        )
+
+        shot = _detect(radar, LaunchConditions(150.0, 11.0), MountPosition.FRONT)
+
+        assert shot.ball_speed_mph == pytest.approx(150.0, rel=0.03)
+
+    def test_streams_through_monitor(self):
+        """hit() should drive a LaunchMonitor in place of the serial radar."""
+        radar = MockRadar(seed=4)
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        monitor.radar = radar
+        shots = []
+
+        monitor.start(shot_callback=shots.append)
+        radar.hit(LaunchConditions(140.0, 12.0))
+        monitor.stop()
+
+        assert len(shots) == 1
+        assert shots[0].ball_speed_mph == pytest.approx(140.0, rel=0.03)
+
+
+class TestReadingTiming:
+    """Tests for latency, jitter and bursts on the link to the host."""
+
+    def test_bursts_stamped_on_arrival(self):
+        """A burst's readings arrive together, after the last frame in it is ready."""
+        timing = ReadingTiming(latency_ms=10.0, burst_size=4)
+        radar = MockRadar(dropout=0.0, seed=1, timing=timing)
+
+        deliveries = radar.deliveries_for(LaunchConditions(150.0))
+
+        assert all(len(burst) == 4 for _, burst in deliveries[:-1])
+        frames = MockRadar(dropout=0.0, seed=1).readings_for(LaunchConditions(150.0))
+        arrival, first = deliveries[0]
+        assert arrival == pytest.approx(frames[3].timestamp + 0.010)
+        assert {r.timestamp for r in first} == {arrival}
+
+    def test_timestamps_never_go_back(self):
+        timing = ReadingTiming(latency_ms=5.0, latency_jitter_ms=20.0, timestamp_jitter_ms=3.0)
+        readings = MockRadar(seed=2, timing=timing).readings_for(LaunchConditions(150.0))
+
+        stamps = [r.timestamp for r in readings]
+        assert stamps == sorted(stamps)
+
+    def test_club_found_in_burst_with_ball(self):
+        """Club and ball handed over in one burst, with one timestamp, still pair up."""
+        timing = ReadingTiming(burst_size=8)
+        radar = MockRadar(dropout=0.0, seed=3, timing=timing)
+
+        shot = _detect(radar, LaunchConditions(150.0, 11.0))
+
+        assert shot.club_speed_mph == pytest.approx(150.0 / 1.45, rel=0.05)
+
+    def test_realtime_paced(self):
+        """Realtime delivery takes as long as the shot and stamps on time.monotonic()."""
+        radar = MockRadar(dropout=0.0, seed=4, timing=ReadingTiming(latency_ms=5.0))
+        arrivals = []
+        radar.start_streaming(lambda r: arrivals.append((time.monotonic(), r.timestamp)))
+
+        start = time.monotonic()
+        readings = radar.hit(LaunchConditions(140.0, 12.0), realtime=True)
+
+        assert time.monotonic() - start >= readings[-1].timestamp - readings[0].timestamp
+        assert all(arrived >= stamp - 0.001 for arrived, stamp in arrivals)
+        assert readings[0].timestamp >= start
+
+    def test_invalid_timing(self):
+        with pytest.raises(ValueError):
+            ReadingTiming(latency_ms=-1)
+        with pytest.raises(ValueError):
+            ReadingTiming(burst_size=0)
@@ -0,0 +1,86 @@
+"""Tests for the OpenAPI document."""
+
+import json
+from dataclasses import fields
+from datetime import datetime
+
+from openflight import openapi, server
+from openflight.launch_monitor import MonitorConfig, Shot
+from openflight.openapi import OPERATIONS, build_spec
+from openflight.server import shot_to_dict
+
+
+def _api_rules():
+    return [rule for rule in server.app.url_map.iter_rules() if rule.rule.startswith("/api/")]
+
+
+class TestBuildSpec:
+    """Tests for building the document from the app."""
+
+    def test_every_endpoint_documented(self):
+        """Every /api route and method should be in the document with a summary."""
+        spec = build_spec(server.app)
+
+        for rule in _api_rules():
+            for method in rule.methods - {"HEAD", "OPTIONS"}:
+                operation = spec["paths"][rule.rule][method.lower()]
+                assert operation["operationId"] == rule.endpoint
+                assert operation["summary"]
+
+    def test_operations_declared(self):
+        """Every /api endpoint should have an OPERATIONS entry, and none should be stale."""
+        assert set(OPERATIONS) == {rule.endpoint for rule in _api_rules()}
+
+    def test_shot_schema_matches(self):
+        """The Shot schema should list exactly what shot_to_dict returns."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime(2024, 1, 15))
+        spec = build_spec(server.app)
+
+        properties = spec["components"]["schemas"]["Shot"]["properties"]
+        assert set(properties) == set(shot_to_dict(shot))
+
+    def test_config_schema_matches(self):
+        """The MonitorConfig schema should have every field with its default."""
+        properties = build_spec(server.app)["components"]["schemas"]["MonitorConfig"]["properties"]
+
+        assert set(properties) == {f.name for f in fields(MonitorConfig)}
+        assert properties["min_magnitude"] == {"type": "number", "default": 20}
+
+    def test_request_bodies(self):
+        """Write endpoints should document their JSON bodies, reads shouldn't have one."""
+        paths = build_spec(server.app)["paths"]
+
+        body = paths["/api/club"]["put"]["requestBody"]["content"]["application/json"]["schema"]
+        assert body["required"] == ["club"]
+        assert "requestBody" not in paths["/api/round"]["get"]
+        assert "text/event-stream" in paths["/api/stream"]["get"]["responses"]["200"]["content"]
+
+
+class TestServer:
+    """Tests for serving the document."""
+
+    def test_openapi_json(self):
+        """/openapi.json should serve the document."""
+        response = server.app.test_client().get("/openapi.json")
+
+        assert response.status_code == 200
+        assert response.get_json()["openapi"] == openapi.OPENAPI_VERSION
+
+    def test_docs_page(self):
+        """/docs should serve Swagger UI pointed at the document."""
+        response = server.app.test_client().get("/docs")
+
+        assert response.status_code == 200
+        assert b"/openapi.json" in response.data
+
+
+class TestMain:
+    """Tests for the openflight-openapi command."""
+
+    def test_write_file(self, tmp_path):
+        """The command should write the document as JSON."""
+        path = tmp_path / "openapi.json"
+
+        assert openapi.main(["-o", str(path)]) == 0
+
+        assert "/api/shots" in json.loads(path.read_text())["paths"]
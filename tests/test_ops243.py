@@ -2,7 +2,16 @@
 
 import pytest
 
-from openflight.ops243 import OPS243Radar, SpeedReading, Direction
+from openflight import ops243
+from openflight.ops243 import (
+    MODEL_CAPABILITIES,
+    OPS243Radar,
+    RadarStateCache,
+    capabilities_for,
+    SpeedReading,
+    Direction,
+    MountPosition,
+)
 
 
 class TestParseReading:
@@ -136,6 +145,47 @@ class TestParseReading:
         # Positive speed = INBOUND
         assert reading.direction == Direction.INBOUND
 
+    def test_parse_json_array_keeps_frame_objects(self):
+        """Other O4 objects should be attached to the strongest reading."""
+        line = '{"magnitude":[606.71, 352.58], "speed":[-101.2, -148.7]}'
+        reading = self.radar._parse_reading(line)
+
+        assert len(reading.frame_objects) == 1
+        other = reading.frame_objects[0]
+        assert other.speed == 148.7
+        assert other.magnitude == 352.58
+        assert other.direction == Direction.OUTBOUND
+        assert other.timestamp == reading.timestamp
+
+    def test_parse_status_line_is_not_a_reading(self):
+        """Module answers are status frames, not zero-speed readings."""
+        line = '{"Product":"OPS243-C","Version":"1.2.3"}'
+
+        assert self.radar._parse_reading(line) is None
+        assert self.radar.decode_line(line).values["Product"] == "OPS243-C"
+
+    def test_parse_single_object_has_no_frame_objects(self):
+        """Single-object frames should not carry frame_objects."""
+        reading = self.radar._parse_reading('{"magnitude":[500.0], "speed":[-150.0]}')
+
+        assert reading.frame_objects is None
+
+
+class TestMountPosition:
+    """Tests for mounting position direction semantics."""
+
+    def test_behind_expects_outbound(self):
+        """Radar behind the ball sees the ball moving away."""
+        assert MountPosition.BEHIND.ball_directions == {Direction.OUTBOUND}
+
+    def test_front_expects_inbound(self):
+        """Radar facing the player sees the ball approaching."""
+        assert MountPosition.FRONT.ball_directions == {Direction.INBOUND}
+
+    def test_side_accepts_both(self):
+        """Side mounting should accept either sign."""
+        assert MountPosition.SIDE.ball_directions == {Direction.INBOUND, Direction.OUTBOUND}
+
 
 class TestConfigureForGolf:
     """Tests for golf configuration."""
@@ -152,6 +202,164 @@ class TestConfigureForGolf:
         assert radar.DEFAULT_TIMEOUT == 1.0
 
 
+class _FakeSerial:
+    """Serial port stand-in that records commands and answers queries."""
+
+    def __init__(self, serial_number="1234", product="OPS243-A"):
+        self.is_open = True
+        self.commands = []
+        self.serial_number = serial_number
+        self.product = product
+        self.units = "m-per-sec"
+        self._response = b""
+
+    def reset_input_buffer(self):
+        self._response = b""
+
+    def write(self, data):
+        cmd = data.decode().strip()
+        if not cmd:
+            return
+        self.commands.append(cmd)
+        if cmd == "US":
+            self.units = "mph"
+        elif cmd == "?N":
+            self._response = f'{{"SerialNumber":"{self.serial_number}"}}'.encode()
+        elif cmd == "U?":
+            self._response = f'{{"Units":"{self.units}"}}'.encode()
+        elif cmd == "??":
+            self._response = f'{{"Product":"{self.product}"}}\r\n'.encode()
+
+    @property
+    def in_waiting(self):
+        return len(self._response)
+
+    def read(self, _size):
+        data, self._response = self._response, b""
+        return data
+
+
+@pytest.fixture
+def fake_radar(tmp_path, monkeypatch):
+    """Radar driver on a fake serial port with a temporary state cache."""
+    monkeypatch.setattr(ops243.time, "sleep", lambda _s: None)
+    radar = OPS243Radar(state_cache=RadarStateCache(tmp_path / "radar_state.json"))
+    radar.serial = _FakeSerial()
+    return radar
+
+
+def _settings_commands(commands):
+    """Commands that change settings (queries removed)."""
+    return [c for c in commands if not (c.startswith("?") or c.endswith("?"))]
+
+
+class TestRadarStateReuse:
+    """Tests for skipping configuration commands the radar already has."""
+
+    def test_first_configure_sends_everything(self, fake_radar):
+        """An unknown radar should get every golf setting."""
+        fake_radar.configure_for_golf()
+
+        sent = _settings_commands(fake_radar.serial.commands)
+        assert sent == list(OPS243Radar.GOLF_SETTINGS.values())
+        assert fake_radar.state_cache.get("1234") == OPS243Radar.GOLF_SETTINGS
+
+    def test_second_configure_sends_nothing(self, fake_radar):
+        """A radar still holding the cached settings needs no commands."""
+        fake_radar.configure_for_golf()
+        fake_radar.serial.commands.clear()
+
+        fake_radar.configure_for_golf()
+
+        assert _settings_commands(fake_radar.serial.commands) == []
+
+    def test_only_changed_settings_sent(self, fake_radar):
+        """Settings that differ from the cache should be the only ones sent."""
+        cached = dict(OPS243Radar.GOLF_SETTINGS, min_magnitude="M>80")
+        fake_radar.state_cache.put("1234", cached)
+        fake_radar.serial.units = "mph"
+
+        fake_radar.configure_for_golf()
+
+        assert _settings_commands(fake_radar.serial.commands) == ["M>50"]
+
+    def test_power_cycled_radar_fully_configured(self, fake_radar):
+        """Units back at the default mean the radar lost its settings."""
+        fake_radar.state_cache.put("1234", OPS243Radar.GOLF_SETTINGS)
+        fake_radar.serial.units = "m-per-sec"
+
+        fake_radar.configure_for_golf()
+
+        sent = _settings_commands(fake_radar.serial.commands)
+        assert sent == list(OPS243Radar.GOLF_SETTINGS.values())
+
+    def test_compact_output_configured(self, fake_radar):
+        """Compact output should turn JSON off instead of on."""
+        fake_radar.output_format = "compact"
+
+        fake_radar.configure_for_golf()
+
+        sent = _settings_commands(fake_radar.serial.commands)
+        assert "Oj" in sent and "OJ" not in sent
+        assert not fake_radar._json_mode
+
+    def test_other_commands_invalidate_cache(self, fake_radar):
+        """Changing settings outside configure_for_golf should drop the cache entry."""
+        fake_radar.configure_for_golf()
+
+        fake_radar.set_transmit_power(3)
+
+        assert fake_radar.state_cache.get("1234") == {}
+
+
+class TestModelCapabilities:
+    """Tests for OmniPreSense model detection and capability limits."""
+
+    def test_capabilities_by_product(self):
+        """Product names should map to their family; unknown names to the OPS243."""
+        assert capabilities_for("OPS241-A") == MODEL_CAPABILITIES["OPS241"]
+        assert capabilities_for("OPS7243-C") == MODEL_CAPABILITIES["OPS7243"]
+        assert capabilities_for("OPS243-C") == MODEL_CAPABILITIES["OPS243"]
+        assert capabilities_for("OPS999") == MODEL_CAPABILITIES["OPS243"]
+        assert capabilities_for(None) == MODEL_CAPABILITIES["OPS243"]
+
+    def test_detect_model(self, fake_radar):
+        """The model should be read from the "??" info."""
+        fake_radar.serial.product = "OPS241-A"
+
+        caps = fake_radar.detect_model()
+
+        assert fake_radar.product == "OPS241-A"
+        assert caps.max_sample_rate == 20000
+        assert not caps.iq_output
+
+    def test_golf_settings_capped_to_model(self, fake_radar):
+        """A model that can't sample at 30 ksps should be configured at its fastest rate."""
+        fake_radar.serial.product = "OPS241-A"
+        fake_radar.detect_model()
+
+        fake_radar.configure_for_golf()
+
+        sent = _settings_commands(fake_radar.serial.commands)
+        assert "S2" in sent and "S=30" not in sent
+        assert fake_radar.state_cache.get("1234")["sample_rate"] == "S2"
+
+    def test_unsupported_features_rejected(self, fake_radar):
+        """Rates above the model's maximum and missing output modes should raise."""
+        fake_radar.serial.product = "OPS241-A"
+        fake_radar.detect_model()
+
+        with pytest.raises(ValueError):
+            fake_radar.set_sample_rate(50000)
+        with pytest.raises(RuntimeError, match="OPS241-A"):
+            fake_radar.configure_for_iq_streaming()
+        with pytest.raises(RuntimeError):
+            fake_radar.enter_rolling_buffer_mode()
+        with pytest.raises(RuntimeError):
+            fake_radar.enable_fft_output()
+        assert _settings_commands(fake_radar.serial.commands) == []
+
+
 class TestFFTSize:
     """Tests for FFT size configuration."""
 
@@ -205,3 +413,158 @@ class TestSpeedReading:
         assert reading.magnitude is None
         assert reading.timestamp is None
         assert reading.unit == "mph"
+
+
+class _LogRecorder:
+    """Stand-in logger that keeps warnings and info messages."""
+
+    def __init__(self):
+        self.warnings = []
+        self.infos = []
+
+    def warning(self, msg, *args):
+        self.warnings.append(msg % args)
+
+    def info(self, msg, *args):
+        self.infos.append(msg % args)
+
+    def debug(self, msg, *args):
+        pass
+
+
+@pytest.fixture
+def json_radar(monkeypatch):
+    """Radar in JSON mode whose warnings are recorded in radar.log."""
+    log = _LogRecorder()
+    monkeypatch.setattr(ops243, "logger", log)
+    radar = OPS243Radar()
+    radar._json_mode = True
+    radar.log = log
+    return radar
+
+
+class TestCorruption:
+    """Tests for tracking damaged radar output."""
+
+    def test_corruption_rate(self, json_radar):
+        """The rate should cover recent lines, and one warning should cover many."""
+        for _ in range(45):
+            json_radar.decode_line('{"speed":-40.0,"magnitude":900}')
+        for _ in range(5):
+            json_radar.decode_line('\x00{"speed":-41.0,"magnitude":900}')
+
+        assert json_radar.corrupt_lines == 5
+        assert json_radar.corruption_rate == pytest.approx(0.1)
+        assert len(json_radar.log.warnings) == 1
+        assert "10%" in json_radar.log.warnings[0]
+
+    def test_rare_corruption_not_logged(self, json_radar):
+        """A damaged line now and then shouldn't warn."""
+        for _ in range(60):
+            json_radar.decode_line('{"speed":-40.0,"magnitude":900}')
+        json_radar.decode_line('{"speed":-4')
+
+        assert json_radar.corrupt_lines == 1
+        assert json_radar.log.warnings == []
+
+    def test_json_mode_lost_and_restored(self, json_radar):
+        """Plain speeds in JSON mode should still become readings, with one warning."""
+        reading = json_radar._parse_reading("-45.0")
+        json_radar._parse_reading("-46.0")
+
+        assert reading.speed == 45.0 and reading.magnitude is None
+        assert json_radar.json_output_lost
+        assert len(json_radar.log.warnings) == 1
+
+        json_radar._parse_reading('{"speed":-47.0,"magnitude":900}')
+
+        assert not json_radar.json_output_lost
+        assert json_radar.log.infos == ["Radar is back in JSON output mode"]
+
+    def test_json_restore_rate_limited(self, json_radar):
+        """OJ should be resent at most once per interval while JSON is lost."""
+        json_radar.serial = _FakeSerial()
+        json_radar._parse_reading("-45.0")
+
+        json_radar._restore_json_output()
+        json_radar._restore_json_output()
+
+        assert json_radar.serial.commands == ["OJ"]
+
+
+class TestOutputFormat:
+    """Tests for compact speed output and its fallback to JSON."""
+
+    def test_unknown_format_rejected(self):
+        """Only the known output formats should be accepted."""
+        with pytest.raises(ValueError):
+            OPS243Radar(output_format="binary")
+
+    def test_compact_readings(self, fake_radar):
+        """Compact reports should become readings with magnitudes."""
+        fake_radar.output_format = "compact"
+        fake_radar.configure_for_golf()
+
+        reading = fake_radar._parse_reading("1200,-150.0,800,-98.5")
+
+        assert reading.speed == 150.0 and reading.magnitude == 1200.0
+        assert [r.speed for r in reading.frame_objects] == [98.5]
+
+    def test_damaged_compact_falls_back_to_json(self, fake_radar):
+        """Too many damaged compact lines should switch the radar to JSON."""
+        fake_radar.output_format = "compact"
+        fake_radar.configure_for_golf()
+        fake_radar.serial.commands.clear()
+
+        for _ in range(40):
+            fake_radar.decode_line("1200,-150.0")
+        for _ in range(10):
+            fake_radar.decode_line("1200,-150.0,8")
+
+        assert fake_radar.output_format == "json"
+        assert fake_radar._json_mode
+        assert fake_radar.serial.commands == ["OJ"]
+        assert fake_radar.state_cache.get("1234") == {}
+
+    def test_compact_survives_rare_damage(self, fake_radar):
+        """A few damaged lines shouldn't give up on compact output."""
+        fake_radar.output_format = "compact"
+        fake_radar.configure_for_golf()
+
+        for _ in range(60):
+            fake_radar.decode_line("1200,-150.0")
+        fake_radar.decode_line("1200,-150.0,8")
+
+        assert fake_radar.output_format == "compact"
+
+
+class TestUnitDetection:
+    """Tests for noticing a radar left in other speed units."""
+
+    def test_detect_units(self, fake_radar):
+        """A radar left in m/s should have its speeds converted to mph."""
+        fake_radar.serial.units = "m-per-sec"
+
+        assert fake_radar.detect_units() == "m/s"
+        reading = fake_radar._parse_reading('{"speed":-44.704,"magnitude":900}')
+
+        assert reading.unit == "mph"
+        assert reading.speed == pytest.approx(100.0)
+
+    def test_report_unit_conflict_warns(self, json_radar):
+        """Reports in another unit than assumed should warn once and be converted."""
+        first = json_radar._parse_reading('{"unit":"kph","speed":-160.9344,"magnitude":900}')
+        json_radar._parse_reading('{"unit":"kph","speed":-80.4672,"magnitude":900}')
+
+        assert first.speed == pytest.approx(100.0)
+        assert len(json_radar.log.warnings) == 1
+        assert "kph" in json_radar.log.warnings[0]
+
+    def test_configured_mph_not_converted(self, fake_radar):
+        """Readings after configure_for_golf should be mph as reported."""
+        fake_radar.configure_for_golf()
+
+        reading = fake_radar._parse_reading('{"speed":-150.0,"magnitude":900}')
+
+        assert reading.speed == 150.0
+        assert fake_radar.detect_units() == "mph"
@@ -0,0 +1,113 @@
+"""Tests for historical percentile context."""
+
+import json
+from datetime import datetime, timedelta
+
+from openflight.percentiles import MIN_HISTORY, ShotHistory, ordinal
+
+
+def _write_session(path, tags, shots):
+    """Write a session log with (timestamp, club, ball speed, mode) shots."""
+    lines = [{"type": "session_start", "session_id": path.stem[8:23], "tags": tags}]
+    for ts, club, speed, mode in shots:
+        lines.append({
+            "type": "shot_detected",
+            "ts": ts.isoformat(),
+            "club": club,
+            "ball_speed_mph": speed,
+            "mode": mode,
+        })
+    path.write_text("".join(json.dumps(line) + "\n" for line in lines))
+    return str(path)
+
+
+def _history(player="sam", club="driver", speeds=range(140, 150)):
+    history = ShotHistory()
+    last_week = datetime.now() - timedelta(days=7)
+    for speed in speeds:
+        history.add(player, club, float(speed), last_week)
+    return history
+
+
+class TestOrdinal:
+    """Tests for ordinal suffixes."""
+
+    def test_suffixes(self):
+        """1st, 2nd, 3rd, then th, with 11-13 as th."""
+        assert [ordinal(n) for n in (1, 2, 3, 4, 11, 12, 13, 21, 78)] == [
+            "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "78th",
+        ]
+
+
+class TestPercentile:
+    """Tests for ranking a value."""
+
+    def test_rank(self):
+        """The rank should count the shots below, half the ties, and stay within 1-99."""
+        history = _history()
+
+        assert history.percentile("sam", "driver", 145.0) == 55
+        assert history.percentile("sam", "driver", 200.0) == 99
+        assert history.percentile("sam", "driver", 100.0) == 1
+
+    def test_not_enough_history(self):
+        """Clubs and players with too few shots should have no percentile."""
+        history = _history(speeds=range(140, 140 + MIN_HISTORY - 1))
+
+        assert history.percentile("sam", "driver", 145.0) is None
+        assert history.percentile("alex", "driver", 145.0) is None
+
+
+class TestDescribe:
+    """Tests for the printed context."""
+
+    def test_percentile_and_best_today(self):
+        """A shot should get its percentile, and "best today" when it beats today's shots."""
+        history = _history()
+        history.add("sam", "driver", 146.0)
+
+        text = history.describe({"ball_speed_mph": 152.2, "club": "driver"}, player="sam")
+
+        assert text == "Ball 152 mph — 99th percentile (driver, 11 shots), best today"
+        assert history.shot_count("sam", "driver") == 12
+
+    def test_first_shot_of_the_day(self):
+        """With no shot yet today there's nothing to beat."""
+        history = _history()
+
+        text = history.describe({"ball_speed_mph": 141.0, "club": "driver"}, player="sam")
+
+        assert text == "Ball 141 mph — 15th percentile (driver, 10 shots)"
+
+    def test_nothing_to_compare(self):
+        """A new club's first shot, and mock shots, should get no context."""
+        history = _history()
+
+        assert history.describe({"ball_speed_mph": 120.0, "club": "7-iron"}, "sam") is None
+        assert history.describe({"ball_speed_mph": 150.0, "mode": "mock"}, "sam") is None
+        assert history.shot_count("sam", "7-iron") == 1
+
+
+class TestLoad:
+    """Tests for reading the history from session logs."""
+
+    def test_players_and_modes(self, tmp_path):
+        """Shots should be kept per player tag, leaving out mock shots."""
+        when = datetime(2025, 6, 1, 10, 0)
+        paths = [
+            _write_session(
+                tmp_path / "session_20250601_100000_range.jsonl",
+                {"player": "sam"},
+                [(when, "driver", 150.0, "streaming"), (when, "driver", 120.0, "mock")],
+            ),
+            _write_session(
+                tmp_path / "session_20250602_100000_range.jsonl",
+                {},
+                [(when, "7-iron", 110.0, "rolling-buffer")],
+            ),
+        ]
+        history = ShotHistory()
+
+        assert history.load(paths) == 2
+        assert history.shot_count("sam", "driver") == 1
+        assert history.shot_count(None, "7-iron") == 1
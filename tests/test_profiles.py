@@ -0,0 +1,185 @@
+"""Tests for profiles module."""
+
+import json
+import os
+
+import pytest
+
+from openflight.geometry import MountingGeometry
+from openflight.launch_monitor import MonitorConfig
+from openflight.profiles import (
+    CalibrationProfile,
+    ProfileWatcher,
+    diff_profiles,
+    list_profiles,
+    load_profile,
+    save_profile,
+    validate_name,
+)
+from openflight.schedule import ArmSchedule
+from openflight.shot_timing import ShotTiming
+
+
+class TestProfileNames:
+    """Tests for profile name validation."""
+
+    def test_valid_names(self):
+        """Simple names should be accepted."""
+        for name in ("garage-net", "backyard", "range_cart.v2"):
+            assert validate_name(name) == name
+
+    def test_path_traversal_rejected(self):
+        """Names that could escape the profile directory should be rejected."""
+        for name in ("../etc/passwd", "a/b", "", ".hidden"):
+            with pytest.raises(ValueError):
+                validate_name(name)
+
+
+class TestSaveLoad:
+    """Tests for saving and loading profiles."""
+
+    def test_round_trip(self, tmp_path):
+        """A saved profile should load back identically."""
+        profile = CalibrationProfile(
+            name="garage-net",
+            mounting=MountingGeometry(lateral_offset_m=0.3, distance_m=1.2),
+            launch_angle_offset_deg=-1.5,
+            monitor=MonitorConfig().with_updates({"min_shot_magnitude": 80}),
+            schedule=ArmSchedule.from_dict({"quiet_hours": ["22:00-07:00"]}),
+            shot_timing=ShotTiming.from_dict({"clubs": {"sw": {"shot_timeout_sec": 0.8}}}),
+        )
+
+        save_profile(profile, tmp_path)
+        loaded = load_profile("garage-net", tmp_path)
+
+        assert loaded == profile
+
+    def test_partial_monitor_section(self, tmp_path):
+        """Unlisted threshold fields should keep their defaults."""
+        (tmp_path / "backyard.json").write_text(
+            json.dumps({"monitor": {"shot_timeout_sec": 0.8}})
+        )
+
+        profile = load_profile("backyard", tmp_path)
+
+        assert profile.name == "backyard"
+        assert profile.monitor.shot_timeout_sec == 0.8
+        assert profile.monitor.min_shot_magnitude == MonitorConfig().min_shot_magnitude
+        assert profile.mounting.is_aligned
+
+    def test_missing_profile(self, tmp_path):
+        """Loading an unknown profile should list what is available."""
+        save_profile(CalibrationProfile(name="backyard"), tmp_path)
+
+        with pytest.raises(FileNotFoundError, match="backyard"):
+            load_profile("garage-net", tmp_path)
+
+    def test_invalid_threshold_rejected(self, tmp_path):
+        """Profiles with invalid thresholds should fail to load."""
+        (tmp_path / "bad.json").write_text(json.dumps({"monitor": {"min_magnitude": -5}}))
+
+        message = r"^Profile 'bad' .*: monitor\.min_magnitude: must be >= 0"
+        with pytest.raises(ValueError, match=message):
+            load_profile("bad", tmp_path)
+
+    def test_unknown_mounting_field_rejected(self, tmp_path):
+        """Typos in the mounting section should fail to load."""
+        (tmp_path / "bad.json").write_text(json.dumps({"mounting": {"offset": 0.3}}))
+
+        with pytest.raises(ValueError):
+            load_profile("bad", tmp_path)
+
+    def test_list_profiles(self, tmp_path):
+        """Saved profiles should be listed by name."""
+        save_profile(CalibrationProfile(name="range-cart"), tmp_path)
+        save_profile(CalibrationProfile(name="backyard"), tmp_path)
+
+        assert list_profiles(tmp_path) == ["backyard", "range-cart"]
+        assert list_profiles(tmp_path / "missing") == []
+
+
+def _touch_later(path):
+    """Bump a file's mtime so the change is seen even on coarse-mtime filesystems."""
+    stat = path.stat()
+    os.utime(path, (stat.st_atime, stat.st_mtime + 1))
+
+
+class TestProfileReload:
+    """Tests for detecting edits to a profile file."""
+
+    def test_diff_lists_changed_settings(self):
+        """Only settings that differ should be reported, with old and new values."""
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(
+            name="garage-net",
+            launch_angle_offset_deg=1.5,
+            monitor=MonitorConfig(min_shot_magnitude=80),
+        )
+
+        changes = diff_profiles(old, new)
+
+        assert changes == {
+            "launch_angle_offset_deg": (0.0, 1.5),
+            "monitor.min_shot_magnitude": (MonitorConfig().min_shot_magnitude, 80),
+        }
+
+    def test_edit_triggers_callback(self, tmp_path):
+        """Saving a changed profile should call on_change with old and new."""
+        save_profile(CalibrationProfile(name="garage-net"), tmp_path)
+        calls = []
+        watcher = ProfileWatcher("garage-net", lambda o, n: calls.append((o, n)), tmp_path)
+
+        save_profile(
+            CalibrationProfile(name="garage-net", launch_angle_offset_deg=2.0), tmp_path
+        )
+        _touch_later(watcher.path)
+
+        assert watcher.check() is True
+        assert len(calls) == 1
+        assert calls[0][0].launch_angle_offset_deg == 0.0
+        assert calls[0][1].launch_angle_offset_deg == 2.0
+        assert watcher.current.launch_angle_offset_deg == 2.0
+
+    def test_unchanged_file_ignored(self, tmp_path):
+        """No callback when the file has not been modified."""
+        save_profile(CalibrationProfile(name="garage-net"), tmp_path)
+        calls = []
+        watcher = ProfileWatcher("garage-net", lambda o, n: calls.append(n), tmp_path)
+
+        assert watcher.check() is False
+        _touch_later(watcher.path)
+        assert watcher.check() is False  # Touched but same content
+        assert not calls
+
+    def test_invalid_edit_keeps_current_profile(self, tmp_path):
+        """A broken edit should be ignored rather than replacing the working profile."""
+        save_profile(
+            CalibrationProfile(name="garage-net", launch_angle_offset_deg=1.0), tmp_path
+        )
+        calls = []
+        watcher = ProfileWatcher("garage-net", lambda o, n: calls.append(n), tmp_path)
+
+        watcher.path.write_text("{not json")
+        _touch_later(watcher.path)
+
+        assert watcher.check() is False
+        assert not calls
+        assert watcher.current.launch_angle_offset_deg == 1.0
+
+    def test_carry_models_round_trip(self, tmp_path):
+        """Per-club carry model selection should be saved with the profile."""
+        profile = CalibrationProfile(
+            name="range-cart", carry_models={"driver": {"model": "physics"}}
+        )
+        save_profile(profile, tmp_path)
+
+        assert load_profile("range-cart", tmp_path).carry_models == profile.carry_models
+
+    def test_invalid_carry_model_rejected(self, tmp_path):
+        """Unknown carry models should fail to load."""
+        (tmp_path / "bad.json").write_text(
+            json.dumps({"carry_models": {"driver": {"model": "magic"}}})
+        )
+
+        with pytest.raises(ValueError):
+            load_profile("bad", tmp_path)
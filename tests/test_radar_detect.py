@@ -0,0 +1,144 @@
+"""Tests for radar_detect module."""
+
+import pytest
+
+from openflight import radar_detect
+from openflight.container import PASS_THROUGH_HELP
+from openflight.radar_detect import (
+    DetectedRadar,
+    RadarModel,
+    choose_radar,
+    detect_radars,
+    probe_port,
+    resolve_port,
+)
+
+
+class _FakeSerial:
+    """Serial port that answers like whatever device the test attaches."""
+
+    def __init__(self, device, **settings):
+        self.device = device
+        self.settings = settings
+        self.written = []
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        return False
+
+    def reset_input_buffer(self):
+        pass
+
+    def write(self, data):
+        self.written.append(data)
+
+    def read(self, size):
+        data = self.written[-1] if self.written else b""
+        if self.device == "ops243" and data == b"??":
+            return b'{"Product":"OPS243-C","Version":"1.2.3"}\r\n'
+        if self.device == "kld7" and data.startswith(b"INIT"):
+            return b"RESP\x01\x00\x00\x00\x00"
+        return b""
+
+
+def _opener(devices):
+    """Serial factory for ports named in devices (port -> fake device kind)."""
+    opened = []
+
+    def open_port(port, **settings):
+        fake = _FakeSerial(devices.get(port), **settings)
+        opened.append((port, fake))
+        return fake
+
+    open_port.opened = opened
+    return open_port
+
+
+class TestProbePort:
+    """Tests for identifying the radar on a port."""
+
+    def test_ops243(self):
+        """An OPS243 is recognized from its module info."""
+        radar = probe_port("/dev/ttyACM0", _opener({"/dev/ttyACM0": "ops243"}))
+
+        assert radar == DetectedRadar("/dev/ttyACM0", RadarModel.OPS243, "OPS243-C")
+
+    def test_kld7_released_after_probe(self):
+        """A K-LD7 answers INIT at 115200 8E1 and is released with GBYE."""
+        open_port = _opener({"/dev/ttyUSB0": "kld7"})
+
+        radar = probe_port("/dev/ttyUSB0", open_port)
+
+        assert radar.model == RadarModel.KLD7
+        _, kld7 = open_port.opened[-1]
+        assert kld7.settings["baudrate"] == 115200
+        assert kld7.written[-1].startswith(b"GBYE")
+
+    def test_unknown_device(self):
+        """Ports that answer neither probe have no radar."""
+        assert probe_port("/dev/ttyUSB1", _opener({})) is None
+
+
+class TestChooseRadar:
+    """Tests for picking the radar to use."""
+
+    def test_prefers_ops243_when_both_attached(self):
+        """With an OPS243 and a K-LD7 attached, the OPS243 is used."""
+        open_port = _opener({"/dev/ttyUSB0": "kld7", "/dev/ttyACM0": "ops243"})
+
+        radars = detect_radars(["/dev/ttyUSB0", "/dev/ttyACM0"], open_port)
+
+        assert [r.model for r in radars] == [RadarModel.KLD7, RadarModel.OPS243]
+        assert choose_radar(radars).port == "/dev/ttyACM0"
+
+    def test_unsupported_only(self):
+        """A lone K-LD7 is reported by name rather than driven as an OPS243."""
+        radars = [DetectedRadar("/dev/ttyUSB0", RadarModel.KLD7, "K-LD7")]
+
+        with pytest.raises(ConnectionError, match="K-LD7 on /dev/ttyUSB0"):
+            choose_radar(radars)
+
+    def test_nothing_found(self, monkeypatch):
+        """No radar at all is a connection error."""
+        monkeypatch.setattr(radar_detect, "device_hint", lambda: None)
+
+        with pytest.raises(ConnectionError, match="No OPS243 radar found"):
+            choose_radar([])
+
+    def test_nothing_found_in_container(self, monkeypatch):
+        """In a container without serial devices, say how to pass the radar through."""
+        monkeypatch.setattr(radar_detect, "device_hint", lambda: PASS_THROUGH_HELP)
+
+        with pytest.raises(ConnectionError, match="pass the radar through"):
+            choose_radar([])
+
+
+class TestResolvePort:
+    """Tests for --port glob patterns."""
+
+    def test_plain_port_unchanged(self):
+        """A port without glob characters is used as given, even if it doesn't exist yet."""
+        assert resolve_port("/dev/ttyACM0") == "/dev/ttyACM0"
+        assert resolve_port("COM3") == "COM3"
+
+    def test_bridge_url_unchanged(self):
+        """A serial bridge URL isn't treated as a pattern."""
+        assert resolve_port("rfc2217://[fe80::1]:2217") == "rfc2217://[fe80::1]:2217"
+
+    def test_first_match_used(self, tmp_path):
+        """A pattern picks the first matching device by name."""
+        for name in ("usb-OmniPreSense_B-if00", "usb-OmniPreSense_A-if00", "usb-Arduino-if00"):
+            (tmp_path / name).touch()
+
+        port = resolve_port(str(tmp_path / "usb-OmniPreSense*"))
+
+        assert port == str(tmp_path / "usb-OmniPreSense_A-if00")
+
+    def test_no_match(self, tmp_path, monkeypatch):
+        """A pattern matching nothing is a connection error naming the pattern."""
+        monkeypatch.setattr(radar_detect, "device_hint", lambda: None)
+
+        with pytest.raises(ConnectionError, match="No serial port matches .*usb-OmniPreSense"):
+            resolve_port(str(tmp_path / "usb-OmniPreSense*"))
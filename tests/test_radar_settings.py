@@ -0,0 +1,150 @@
+"""Tests for radar_settings module."""
+
+import json
+
+import pytest
+
+from openflight import ops243
+from openflight.ops243 import OPS243Radar, RadarStateCache
+from openflight.radar_settings import load_snapshot, restore_settings, save_settings
+
+
+class _FakeSerial:
+    """Serial port stand-in that records commands and answers queries."""
+
+    def __init__(self, serial_number="1234"):
+        self.is_open = True
+        self.commands = []
+        self.serial_number = serial_number
+        self.units = "mph"
+        self._response = b""
+
+    def reset_input_buffer(self):
+        self._response = b""
+
+    def write(self, data):
+        cmd = data.decode().strip()
+        if not cmd:
+            return
+        self.commands.append(cmd)
+        answers = {
+            "??": '{"Product":"OPS243-A"}\r\n{"Version":"1.2.3"}',
+            "?V": '{"Version":"1.2.3"}',
+            "?N": f'{{"SerialNumber":"{self.serial_number}"}}',
+            "U?": f'{{"Units":"{self.units}"}}',
+            "S?": '{"SampleRate":20000}',
+            "R?": '{"MinSpeed":10}',
+            "X?": '{"FFTSize":4096}',
+        }
+        if cmd == "UK":
+            self.units = "km-per-hr"
+        if cmd in answers:
+            self._response = answers[cmd].encode()
+
+    @property
+    def in_waiting(self):
+        return len(self._response)
+
+    def read(self, _size):
+        data, self._response = self._response, b""
+        return data
+
+
+@pytest.fixture
+def fake_radar(tmp_path, monkeypatch):
+    """Radar driver on a fake serial port with a temporary state cache."""
+    monkeypatch.setattr(ops243.time, "sleep", lambda _s: None)
+    radar = OPS243Radar(port="/dev/fake", state_cache=RadarStateCache(tmp_path / "state.json"))
+    radar.serial = _FakeSerial()
+    return radar
+
+
+def _settings_commands(commands):
+    """Commands that change settings (queries removed)."""
+    return [c for c in commands if not (c.startswith("?") or c.endswith("?"))]
+
+
+class TestSaveSettings:
+    """Tests for snapshotting a radar."""
+
+    def test_readback_kept_verbatim(self, fake_radar):
+        """The radar's answers should be saved as it gave them."""
+        snapshot = save_settings(fake_radar)
+
+        assert snapshot["serial_number"] == "1234"
+        assert snapshot["readback"]["S?"] == '{"SampleRate":20000}'
+        assert set(snapshot["readback"]) == set(OPS243Radar.READBACK_QUERIES)
+
+    def test_cached_settings_captured(self, fake_radar):
+        """Settings openflight sent should be restorable, read-back ones from the radar."""
+        fake_radar.state_cache.put("1234", OPS243Radar.GOLF_SETTINGS)
+
+        snapshot = save_settings(fake_radar)
+
+        assert snapshot["settings"]["fft_size"] == "X=32"
+        assert snapshot["settings"]["sample_rate"] == "S2"  # As read, not as cached
+        assert snapshot["unrestorable"] == []
+
+    def test_unknown_settings_listed(self, fake_radar):
+        """Without a cache entry only read-back settings can be captured."""
+        snapshot = save_settings(fake_radar)
+
+        assert snapshot["settings"] == {"units": "US", "sample_rate": "S2"}
+        assert "fft_size" in snapshot["unrestorable"]
+
+    def test_save_sends_no_settings(self, fake_radar):
+        """Taking a snapshot should only query the radar."""
+        save_settings(fake_radar)
+
+        assert _settings_commands(fake_radar.serial.commands) == []
+
+
+class TestRestoreSettings:
+    """Tests for restoring a snapshot."""
+
+    def test_restore_sends_settings_in_order(self, fake_radar):
+        """Settings should go in configure order and refresh the state cache."""
+        snapshot = {
+            "serial_number": "1234",
+            "settings": {"json_output": "OJ", "num_reports": "O4", "units": "UK"},
+        }
+
+        restore_settings(fake_radar, snapshot)
+
+        assert _settings_commands(fake_radar.serial.commands) == ["UK", "O4", "OJ"]
+        assert fake_radar.state_cache.get("1234") == snapshot["settings"]
+        assert fake_radar.detect_units() == "kph"
+
+    def test_persist_saves_to_flash(self, fake_radar):
+        """--persist should finish with A!."""
+        restore_settings(fake_radar, {"settings": {"units": "US"}}, persist=True)
+
+        assert _settings_commands(fake_radar.serial.commands)[-1] == "A!"
+
+    def test_other_radar_refused(self, fake_radar):
+        """A snapshot from another radar needs force."""
+        snapshot = {"serial_number": "9999", "settings": {"units": "US"}}
+
+        with pytest.raises(ValueError, match="9999"):
+            restore_settings(fake_radar, snapshot)
+        restore_settings(fake_radar, snapshot, force=True)
+
+        assert "US" in fake_radar.serial.commands
+
+    def test_malformed_snapshot(self, fake_radar):
+        """Something that isn't a snapshot should be rejected before sending anything."""
+        with pytest.raises(ValueError):
+            restore_settings(fake_radar, {"settings": ["US"]})
+
+        assert _settings_commands(fake_radar.serial.commands) == []
+
+    def test_round_trip_through_file(self, fake_radar, tmp_path):
+        """A saved snapshot file should restore what was captured."""
+        fake_radar.state_cache.put("1234", OPS243Radar.GOLF_SETTINGS)
+        path = tmp_path / "radar.json"
+        path.write_text(json.dumps(save_settings(fake_radar)))
+
+        settings = restore_settings(fake_radar, load_snapshot(str(path)))
+
+        assert _settings_commands(fake_radar.serial.commands) == list(settings.values())
+        assert len(settings) == len(OPS243Radar.GOLF_SETTINGS)
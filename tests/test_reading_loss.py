@@ -0,0 +1,114 @@
+"""Tests for lost radar frames within a shot."""
+
+import pytest
+
+from openflight.ops243 import Direction, SpeedReading
+from openflight.reading_loss import (
+    analyze_loss,
+    fill_gaps,
+    fit_line,
+    frame_spacing,
+    lost_between,
+)
+
+
+def _readings(frames):
+    """SpeedReadings from (ms, mph) pairs."""
+    return [
+        SpeedReading(speed=mph, direction=Direction.OUTBOUND, timestamp=ms / 1000)
+        for ms, mph in frames
+    ]
+
+
+# Club at 10 ms frames, then the ball slowing 0.5 mph per frame (50 mph/s)
+CLUB = [(0, 98.0), (10, 99.0), (20, 100.0)]
+BALL = [(30 + 10 * i, 150.0 - 0.5 * i) for i in range(6)]
+
+
+class TestGaps:
+    """Tests for finding lost frames."""
+
+    def test_frame_spacing_is_median(self):
+        assert frame_spacing([0.0, 0.01, 0.02, 0.05, 0.06]) == pytest.approx(0.01)
+        assert frame_spacing([0.0]) is None
+
+    def test_lost_between(self):
+        assert lost_between(0.0, 0.01, 0.01) == 0
+        assert lost_between(0.0, 0.015, 0.01) == 0  # Jitter, not a lost frame
+        assert lost_between(0.0, 0.02, 0.01) == 1
+        assert lost_between(0.0, 0.04, 0.01) == 3
+
+    def test_fill_gaps(self):
+        """Gaps up to max_frames are interpolated, longer ones left open."""
+        points = [(0.0, 10.0), (0.03, 7.0), (0.04, 6.0), (0.1, 0.0)]
+        filled = fill_gaps(points, 0.01, max_frames=2)
+
+        assert filled == pytest.approx(
+            [(0.0, 10.0), (0.01, 9.0), (0.02, 8.0), (0.03, 7.0), (0.04, 6.0), (0.1, 0.0)]
+        )
+
+    def test_fit_line(self):
+        assert fit_line([(0.0, 3.0), (1.0, 5.0), (2.0, 7.0)]) == pytest.approx((3.0, 2.0))
+        assert fit_line([(0.0, 3.0), (1.0, 5.0)]) is None
+
+
+class TestAnalyzeLoss:
+    """Tests for per-shot loss and the ball speed it hid."""
+
+    def test_no_loss(self):
+        loss, speed = analyze_loss(_readings(CLUB + BALL), 150.0)
+
+        assert loss.lost_frames == 0
+        assert loss.gaps == 0
+        assert loss.spacing_ms == pytest.approx(10.0)
+        assert loss.max_gap_ms == pytest.approx(10.0)
+        assert speed is None
+
+    def test_lost_first_frames(self):
+        """Lost first ball frames: the decay fit is taken back to where they were."""
+        frames = CLUB + BALL[2:]
+        loss, speed = analyze_loss(_readings(frames), BALL[2][1])
+
+        assert loss.lost_leading == 2
+        assert loss.lost_frames == 2
+        assert speed == pytest.approx(150.0)
+
+    def test_interior_gap_filled_for_fit(self):
+        """A gap inside the ball's readings is reported, and filled before fitting."""
+        frames = CLUB + BALL[1:2] + BALL[3:]
+        loss, speed = analyze_loss(_readings(frames), BALL[1][1])
+
+        assert loss.lost_frames == 2  # One before the ball, one within
+        assert loss.gaps == 2
+        assert loss.max_gap_ms == pytest.approx(20.0)
+        assert speed == pytest.approx(150.0)
+
+    def test_long_gap_not_taken_back(self):
+        """More lost frames than max_filled_frames are reported but not filled."""
+        loss, speed = analyze_loss(_readings(CLUB + BALL[4:] + [(90, 147.5)]), BALL[4][1], 3)
+
+        assert loss.lost_leading == 4
+        assert speed is None
+
+    def test_filling_off(self):
+        _, speed = analyze_loss(_readings(CLUB + BALL[2:]), BALL[2][1], max_filled_frames=0)
+        assert speed is None
+
+    def test_noisy_fit_capped(self):
+        """A fit steeper than drag can't add more than drag would have taken off."""
+        frames = CLUB + [(50, 150.0), (60, 145.0), (70, 140.0)]
+        _, speed = analyze_loss(_readings(frames), 150.0)
+
+        assert speed == pytest.approx(152.0)  # 100 mph/s over the two lost frames
+
+    def test_speeding_up_not_taken_back(self):
+        frames = CLUB + [(50, 148.0), (60, 149.0), (70, 150.0)]
+        _, speed = analyze_loss(_readings(frames), 150.0)
+        assert speed is None
+
+    def test_untimed_readings(self):
+        readings = [SpeedReading(speed=150.0, direction=Direction.OUTBOUND)]
+        loss, speed = analyze_loss(readings, 150.0)
+
+        assert loss.spacing_ms is None
+        assert speed is None
@@ -0,0 +1,89 @@
+"""Tests for personal records."""
+
+import json
+from datetime import datetime
+
+from openflight.records import PersonalRecords, best_shots
+
+
+def _shot(speed, carry=200, club="driver", mode="streaming", player=None):
+    """A shot_detected entry."""
+    shot = {
+        "ts": datetime(2025, 6, 1, 10, 0).isoformat(),
+        "club": club,
+        "ball_speed_mph": speed,
+        "estimated_carry_yards": carry,
+        "mode": mode,
+    }
+    if player:
+        shot["tags"] = {"player": player}
+    return shot
+
+
+class TestBestShots:
+    """Tests for finding the best shots."""
+
+    def test_best_per_player_club_and_metric(self):
+        """Each player and club should get their best ball speed and carry, driver first."""
+        records = best_shots([
+            _shot(150.0, 240, player="sam"),
+            _shot(155.0, 235, player="sam"),
+            _shot(120.0, 160, club="7-iron", player="sam"),
+            _shot(140.0, 220, player="alex"),
+            _shot(190.0, 300, mode="mock", player="sam"),
+        ])
+
+        sam = [(r.club, r.metric, r.value) for r in records if r.player == "sam"]
+        assert sam == [
+            ("driver", "ball_speed", 155.0),
+            ("driver", "carry", 240.0),
+            ("7-iron", "ball_speed", 120.0),
+            ("7-iron", "carry", 160.0),
+        ]
+        assert records[0].player == "alex"
+        assert records[0].date == "2025-06-01"
+
+
+class TestPersonalRecords:
+    """Tests for checking new shots against the records."""
+
+    def test_new_record(self, tmp_path):
+        """A shot beating the logged best should be a new record, and become the record."""
+        path = tmp_path / "session_20250601_100000_range.jsonl"
+        lines = [{"type": "session_start", "tags": {"player": "sam"}}]
+        lines += [{"type": "shot_detected", **_shot(150.0, 240)}]
+        path.write_text("".join(json.dumps(line) + "\n" for line in lines))
+        records = PersonalRecords()
+        assert records.load([str(path)]) == 2
+
+        new = records.check(_shot(153.5, 230), player="sam")
+
+        assert [(n.record.metric, n.previous) for n in new] == [("ball_speed", 150.0)]
+        assert new[0].describe() == "Driver ball speed: 153.5 mph (old record 150.0 mph, +3.5)"
+        assert new[0].announcement() == (
+            "New personal record! Driver ball speed, 154 miles per hour"
+        )
+        assert records.check(_shot(152.0, 230), player="sam") == []
+
+    def test_first_shot_not_announced(self):
+        """A club's first shot should set the record without announcing it."""
+        records = PersonalRecords()
+
+        assert records.check(_shot(150.0)) == []
+        assert [r.value for r in records.table()] == [150.0, 200.0]
+
+    def test_players_separate(self):
+        """One player's shots shouldn't count against another's records."""
+        records = PersonalRecords()
+        records.check(_shot(150.0), player="sam")
+
+        assert records.check(_shot(140.0), player="alex") == []
+        assert records.table("alex")[0].value == 140.0
+
+    def test_synthetic_shots_ignored(self):
+        """Mock and injected shots should never set records."""
+        records = PersonalRecords()
+        records.check(_shot(150.0))
+
+        assert records.check(_shot(190.0, mode="injected")) == []
+        assert records.table()[0].value == 150.0
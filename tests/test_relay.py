@@ -0,0 +1,150 @@
+"""Tests for relay module."""
+
+import json
+import socket
+import threading
+
+import pytest
+
+from openflight.relay import (
+    OP_PING,
+    OP_PONG,
+    OP_TEXT,
+    RelayClient,
+    accept_key,
+    encode_frame,
+    read_frame,
+)
+
+
+class _Relay:
+    """Local WebSocket endpoint that checks the token and records messages."""
+
+    def __init__(self, token="s3cret", ping=False):
+        self.token = token
+        self.ping = ping
+        self.headers = {}
+        self.messages = []
+        self.pong = threading.Event()
+        self.received = threading.Event()
+        self.server = socket.socket()
+        self.server.bind(("127.0.0.1", 0))
+        self.server.listen(1)
+        self.url = f"ws://127.0.0.1:{self.server.getsockname()[1]}/monitor"
+        self.thread = threading.Thread(target=self._serve, daemon=True)
+        self.thread.start()
+
+    def _serve(self):
+        conn, _ = self.server.accept()
+        request = b""
+        while not request.endswith(b"\r\n\r\n"):
+            request += conn.recv(1)
+        lines = request.decode().split("\r\n")
+        self.headers = {
+            name.lower(): value.strip()
+            for name, _, value in (line.partition(":") for line in lines[1:] if line)
+        }
+        if self.headers.get("authorization") != f"Bearer {self.token}":
+            conn.sendall(b"HTTP/1.1 401 Unauthorized\r\n\r\n")
+            conn.close()
+            return
+        conn.sendall(
+            b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n"
+            + f"Sec-WebSocket-Accept: {accept_key(self.headers['sec-websocket-key'])}".encode()
+            + b"\r\n\r\n"
+        )
+        if self.ping:
+            conn.sendall(encode_frame(OP_PING, b"hi"))
+        try:
+            while True:
+                opcode, payload = read_frame(conn)
+                if opcode == OP_TEXT:
+                    self.messages.append(json.loads(payload))
+                    self.received.set()
+                elif opcode == OP_PONG and payload == b"hi":
+                    self.pong.set()
+        except ConnectionError:
+            pass
+        finally:
+            conn.close()
+
+    def close(self):
+        self.server.close()
+
+
+class TestFrames:
+    """Tests for WebSocket framing."""
+
+    @pytest.mark.parametrize("size", [0, 125, 126, 70000])
+    def test_masked_round_trip(self, size):
+        """Masked frames of every length encoding decode to the same payload."""
+        payload = bytes(i % 251 for i in range(size))
+        left, right = socket.socketpair()
+        try:
+            frame = encode_frame(OP_TEXT, payload, mask=b"\x01\x02\x03\x04")
+            threading.Thread(target=left.sendall, args=(frame,), daemon=True).start()
+            assert read_frame(right) == (OP_TEXT, payload)
+        finally:
+            left.close()
+            right.close()
+
+    def test_accept_key(self):
+        """The accept key matches the example in RFC 6455."""
+        assert accept_key("dGhlIHNhbXBsZSBub25jZQ==") == "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+
+
+class TestRelayClient:
+    """Tests for pushing events to a relay."""
+
+    def test_sends_events_with_token(self):
+        """Events queued before connecting are sent after an authenticated handshake."""
+        relay = _Relay()
+        client = RelayClient(relay.url, token="s3cret")
+        client.send("shot", {"shot": {"ball_speed_mph": 150.0}})
+        client.start()
+        try:
+            assert relay.received.wait(5)
+        finally:
+            client.stop()
+            relay.close()
+
+        message = relay.messages[0]
+        assert message["event"] == "shot"
+        assert message["data"] == {"shot": {"ball_speed_mph": 150.0}}
+        assert "ts" in message
+
+    def test_answers_pings(self):
+        """Pings from the relay are answered with the same payload."""
+        relay = _Relay(ping=True)
+        client = RelayClient(relay.url, token="s3cret")
+        client.start()
+        try:
+            assert relay.pong.wait(5)
+        finally:
+            client.stop()
+            relay.close()
+
+    def test_rejected_token(self):
+        """A 401 from the relay is a PermissionError."""
+        relay = _Relay(token="other")
+        client = RelayClient(relay.url, token="s3cret")
+        try:
+            with pytest.raises(PermissionError):
+                client.connect()
+        finally:
+            relay.close()
+
+    def test_queue_keeps_latest_events(self):
+        """While disconnected, the oldest events are dropped first."""
+        client = RelayClient("ws://127.0.0.1:9/relay")
+        for i in range(RelayClient.MAX_QUEUED_EVENTS + 5):
+            client.send("shot", {"n": i})
+
+        assert client._queue.qsize() == RelayClient.MAX_QUEUED_EVENTS
+        assert client._queue.get_nowait()["data"] == {"n": 5}
+
+    @pytest.mark.parametrize("url", ["https://relay.example.com", "relay.example.com", "ws://"])
+    def test_invalid_url(self, url):
+        """Only ws:// and wss:// URLs are accepted."""
+        with pytest.raises(ValueError):
+            RelayClient(url)
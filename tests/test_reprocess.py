@@ -0,0 +1,155 @@
+"""Tests for reprocess module."""
+
+import json
+
+from openflight.geometry import MountingGeometry
+from openflight.launch_monitor import LaunchMonitor, MonitorConfig
+from openflight.reprocess import (
+    find_sessions,
+    output_path_for,
+    readings_from_entry,
+    reprocess_entry,
+    reprocess_file,
+)
+
+
+def _shot_entry(**overrides):
+    """A shot_detected entry as written by SessionLogger.log_shot."""
+    entry = {
+        "type": "shot_detected",
+        "ts": "2025-06-01T10:00:00",
+        "shot_number": 1,
+        "ball_speed_mph": 150.0,
+        "club_speed_mph": 104.0,
+        "smash_factor": 1.44,
+        "estimated_carry_yards": 250.0,
+        "club": "driver",
+        "mode": "streaming",
+        "readings": [
+            {"speed": 104.0, "direction": "outbound", "magnitude": 2000, "timestamp": 0.00},
+            {"speed": 150.0, "direction": "outbound", "magnitude": 900, "timestamp": 0.02},
+            {"speed": 149.5, "direction": "outbound", "magnitude": 850, "timestamp": 0.04},
+        ],
+    }
+    entry.update(overrides)
+    return entry
+
+
+class TestReadingsFromEntry:
+    """Tests for rebuilding readings from a log entry."""
+
+    def test_round_trip_fields(self):
+        """Logged speed, direction, magnitude and timestamp should be restored."""
+        readings = readings_from_entry(_shot_entry())
+
+        assert [r.speed for r in readings] == [104.0, 150.0, 149.5]
+        assert readings[1].magnitude == 900
+        assert readings[2].timestamp == 0.04
+
+    def test_frame_objects_restored(self):
+        """O4 frame objects should be rebuilt with the frame's timestamp."""
+        entry = _shot_entry(
+            readings=[
+                {
+                    "speed": 100.0,
+                    "direction": "outbound",
+                    "magnitude": 2000,
+                    "timestamp": 1.5,
+                    "frame_objects": [
+                        {"speed": 145.0, "direction": "outbound", "magnitude": 700}
+                    ],
+                }
+            ]
+        )
+
+        reading = readings_from_entry(entry)[0]
+
+        assert reading.frame_objects[0].speed == 145.0
+        assert reading.frame_objects[0].timestamp == 1.5
+
+
+class TestReprocessEntry:
+    """Tests for re-running detection on one shot."""
+
+    def test_redetects_from_readings(self):
+        """Shots with readings should be re-detected by the current detector."""
+        result = reprocess_entry(_shot_entry(), LaunchMonitor())
+
+        assert result["status"] == "redetected"
+        assert result["ball_speed_mph"] == 150.0
+        assert result["club_speed_mph"] == 104.0
+        assert result["original"]["estimated_carry_yards"] == 250.0
+
+    def test_rejected_by_stricter_config(self):
+        """A shot the current thresholds reject should be reported as rejected."""
+        monitor = LaunchMonitor(config=MonitorConfig(min_readings_for_shot=5))
+
+        result = reprocess_entry(_shot_entry(), monitor)
+
+        assert result["status"] == "rejected"
+        assert "ball_speed_mph" not in result
+
+    def test_profile_geometry_applied(self):
+        """A profile's mounting geometry should cosine-correct the re-detected speeds."""
+        geometry = MountingGeometry(lateral_offset_m=0.5, distance_m=1.0)
+
+        result = reprocess_entry(_shot_entry(), LaunchMonitor(), geometry)
+
+        assert result["ball_speed_raw_mph"] == 150.0
+        assert result["ball_speed_mph"] > 150.0
+
+    def test_logged_correction_reused(self):
+        """Without a profile, the correction the shot was logged with is kept."""
+        entry = _shot_entry(ball_speed_mph=165.0, ball_speed_raw_mph=150.0)
+
+        result = reprocess_entry(entry, LaunchMonitor())
+
+        assert abs(result["ball_speed_mph"] - 165.0) < 1e-9
+        assert result["ball_speed_raw_mph"] == 150.0
+
+    def test_physics_only_without_readings(self):
+        """Shots logged without readings should keep their speeds."""
+        entry = _shot_entry(readings=None, mode="rolling-buffer", club="7-iron")
+
+        result = reprocess_entry(entry, LaunchMonitor())
+
+        assert result["status"] == "physics_only"
+        assert result["ball_speed_mph"] == 150.0
+        assert result["club"] == "7-iron"
+
+    def test_mock_shots_skipped(self):
+        """Mock and injected shots have nothing to reprocess."""
+        assert reprocess_entry(_shot_entry(mode="mock"), LaunchMonitor()) is None
+        assert reprocess_entry(_shot_entry(mode="injected"), LaunchMonitor()) is None
+
+
+class TestReprocessFile:
+    """Tests for whole-file reprocessing."""
+
+    def test_writes_sibling_output(self, tmp_path):
+        """Each shot should produce one line in the .reprocessed.jsonl output."""
+        session = tmp_path / "session_20250601_100000_range.jsonl"
+        lines = [
+            {"type": "session_start", "session_id": "x"},
+            _shot_entry(),
+            {"type": "reading_accepted", "speed": 10.0},
+            _shot_entry(shot_number=2, mode="mock"),
+        ]
+        session.write_text("\n".join(json.dumps(line) for line in lines) + "\n")
+
+        result = reprocess_file(str(session))
+
+        assert result.error is None
+        assert (result.shots, result.redetected, result.skipped) == (2, 1, 1)
+        output = tmp_path / "session_20250601_100000_range.reprocessed.jsonl"
+        assert result.output_path == str(output)
+        written = [json.loads(line) for line in output.read_text().splitlines()]
+        assert [e["shot_number"] for e in written] == [1]
+
+    def test_find_sessions_skips_outputs(self, tmp_path):
+        """Earlier reprocessed output should not be picked up again."""
+        session = tmp_path / "session_a.jsonl"
+        session.write_text("")
+        output_path_for(session).write_text("")
+
+        assert find_sessions([str(tmp_path / "*.jsonl")]) == [str(session)]
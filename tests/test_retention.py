@@ -0,0 +1,132 @@
+"""Tests for retention module."""
+
+import json
+import os
+import time
+
+import pytest
+
+from openflight.ops243 import Direction, SpeedReading
+from openflight.retention import COMPACTED_TYPE, compact_session, vacuum
+from openflight.session_logger import SessionLogger
+from openflight.voice_memo import memo_path
+
+DAY = 86400
+
+
+def _record_session(log_dir):
+    """Record a session with per-reading telemetry, as the server would."""
+    logger = SessionLogger(log_dir=log_dir, tags={"weather": "calm"})
+    logger.start_session(mode="streaming")
+    for _ in range(50):
+        logger.log_accepted_reading(SpeedReading(150.0, Direction.OUTBOUND, 900.0, 0.0))
+    logger.log_shot(
+        150.0, 104.0, 1.44, 250.0, "driver", 900.0, 2,
+        readings=[{"speed": 104.0, "direction": "outbound", "magnitude": 2000}] * 20,
+        pre_trigger_readings=[{"speed": 40.0, "direction": "outbound", "magnitude": 300}] * 20,
+    )
+    logger.end_session()
+    return logger
+
+
+def _age(path, days, now):
+    os.utime(path, (now - days * DAY, now - days * DAY))
+
+
+def _entries(path):
+    return [json.loads(line) for line in path.read_text().splitlines()]
+
+
+class TestCompactSession:
+    """Tests for compacting one session log."""
+
+    def test_keeps_shot_level_entries(self, tmp_path):
+        """Readings should be dropped while shots, tags and summary survive."""
+        logger = _record_session(tmp_path)
+
+        result = compact_session(str(logger.session_path))
+
+        entries = _entries(logger.session_path)
+        types = [e["type"] for e in entries]
+        assert result.dropped_entries == 50
+        assert result.bytes_after < result.bytes_before
+        assert types == ["session_start", "shot_detected", "session_end", COMPACTED_TYPE]
+        assert entries[0]["tags"]["weather"] == "calm"
+        assert entries[1]["ball_speed_mph"] == 150.0
+        assert "readings" not in entries[1]
+        assert "pre_trigger_readings" not in entries[1]
+
+    def test_keeps_mtime_and_runs_once(self, tmp_path):
+        """A compacted file keeps its age and is not compacted again."""
+        logger = _record_session(tmp_path)
+        _age(logger.session_path, 40, time.time())
+        mtime = logger.session_path.stat().st_mtime
+
+        compact_session(str(logger.session_path))
+        again = compact_session(str(logger.session_path))
+
+        assert logger.session_path.stat().st_mtime == pytest.approx(mtime)
+        assert again.already_compacted
+
+    def test_dry_run_changes_nothing(self, tmp_path):
+        """A dry run should report savings without rewriting the file."""
+        logger = _record_session(tmp_path)
+        before = logger.session_path.read_text()
+
+        result = compact_session(str(logger.session_path), dry_run=True)
+
+        assert result.bytes_after < result.bytes_before
+        assert logger.session_path.read_text() == before
+
+
+class TestVacuum:
+    """Tests for the retention policy."""
+
+    def test_compacts_old_sessions_and_deletes_raw_logs(self, tmp_path):
+        """Old sessions are compacted and raw logs deleted; recent ones are untouched."""
+        now = time.time()
+        old = _record_session(tmp_path)
+        _age(old.session_path, 40, now)
+        _age(old.raw_path, 40, now)
+        recent_raw = tmp_path / "radar_raw_20990101_000000.log"
+        recent_raw.write_text("raw\n")
+
+        result = vacuum(tmp_path, keep_days=30, now=now)
+
+        assert [c.path for c in result.compacted] == [str(old.session_path)]
+        assert result.deleted == [str(old.raw_path)]
+        assert result.bytes_freed > 0
+        assert recent_raw.exists()
+        assert _entries(old.session_path)[-1]["type"] == COMPACTED_TYPE
+
+    def test_delete_after(self, tmp_path):
+        """Sessions past delete_after_days are removed entirely."""
+        now = time.time()
+        old = _record_session(tmp_path)
+        _age(old.session_path, 400, now)
+
+        result = vacuum(tmp_path, keep_days=30, delete_after_days=365, now=now)
+
+        assert str(old.session_path) in result.deleted
+        assert not old.session_path.exists()
+
+    def test_delete_after_removes_voice_memos(self, tmp_path):
+        """A deleted session's memos go with it; a compacted session keeps them."""
+        now = time.time()
+        old = _record_session(tmp_path)
+        _age(old.session_path, 400, now)
+        memo = memo_path(old.session_path, 1)
+        memo.write_bytes(b"RIFF")
+        kept = memo_path(tmp_path / "session_20990101_000000.jsonl", 1)
+        kept.write_bytes(b"RIFF")
+
+        result = vacuum(tmp_path, keep_days=30, delete_after_days=365, now=now)
+
+        assert str(memo) in result.deleted
+        assert not memo.exists()
+        assert kept.exists()
+
+    def test_delete_before_keep_rejected(self, tmp_path):
+        """Deleting sessions before their telemetry expires makes no sense."""
+        with pytest.raises(ValueError):
+            vacuum(tmp_path, keep_days=30, delete_after_days=7)
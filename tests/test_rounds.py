@@ -0,0 +1,86 @@
+"""Tests for rounds module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.launch_monitor import Shot
+from openflight.rounds import RoundTracker
+
+
+def _shot():
+    return Shot(ball_speed_mph=150.0, timestamp=datetime.now())
+
+
+class TestRoundTracker:
+    """Tests for tagging shots with hole context."""
+
+    def test_untagged_without_hole(self):
+        """Shots before the sim reports a hole should stay untagged."""
+        shot = _shot()
+
+        RoundTracker().tag(shot)
+
+        assert shot.hole_number is None
+        assert shot.hole_shot is None
+
+    def test_shots_numbered_per_hole(self):
+        """Stroke index counts up on a hole and restarts on the next one."""
+        tracker = RoundTracker()
+        tracker.set_hole(1)
+        shots = [_shot() for _ in range(3)]
+        tracker.tag(shots[0])
+        tracker.tag(shots[1])
+        tracker.set_hole(2)
+        tracker.tag(shots[2])
+
+        assert [(s.hole_number, s.hole_shot) for s in shots] == [(1, 1), (1, 2), (2, 1)]
+
+    def test_same_hole_keeps_index(self):
+        """Re-reporting the current hole should not restart its count."""
+        tracker = RoundTracker()
+        tracker.set_hole(5)
+        tracker.tag(_shot())
+        tracker.set_hole(5)
+
+        assert tracker.to_dict() == {"hole": 5, "next_shot": 2}
+
+    def test_explicit_shot_index(self):
+        """The sim can set the stroke index, e.g. after a penalty."""
+        tracker = RoundTracker()
+        tracker.set_hole(3, shot=3)
+        shot = _shot()
+        tracker.tag(shot)
+
+        assert shot.hole_shot == 3
+
+    def test_untag_last_shot(self):
+        """A mulligan on the last stroke of the hole gives the stroke back."""
+        tracker = RoundTracker()
+        tracker.set_hole(2)
+        first, second = _shot(), _shot()
+        tracker.tag(first)
+        tracker.tag(second)
+
+        tracker.untag(first)
+        assert tracker.to_dict()["next_shot"] == 3
+        tracker.untag(second)
+        assert tracker.to_dict()["next_shot"] == 2
+
+    def test_invalid_values_rejected(self):
+        """Holes and stroke indexes must be whole numbers from 1."""
+        tracker = RoundTracker()
+        for hole, shot in ((0, None), ("4", None), (True, None), (4, 0), (4, 1.5)):
+            with pytest.raises(ValueError):
+                tracker.set_hole(hole, shot)
+
+    def test_clear(self):
+        """Clearing the round stops tagging."""
+        tracker = RoundTracker()
+        tracker.set_hole(9)
+        tracker.clear()
+        shot = _shot()
+        tracker.tag(shot)
+
+        assert shot.hole_number is None
+        assert tracker.to_dict() == {"hole": None, "next_shot": None}
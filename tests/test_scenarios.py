@@ -0,0 +1,101 @@
+"""Tests for scripted MockRadar scenarios: every file in tests/scenarios/ is a test case."""
+
+import json
+from pathlib import Path
+
+import pytest
+
+from openflight.scenarios import check_scenario, find_scenarios, load_scenario, run_scenario
+
+SCENARIO_DIR = Path(__file__).parent / "scenarios"
+
+
+@pytest.mark.parametrize("path", find_scenarios(SCENARIO_DIR), ids=lambda path: path.stem)
+def test_scenario(path):
+    """Detection should produce the outcomes the scenario expects for each shot."""
+    failures = check_scenario(load_scenario(path))
+
+    assert not failures, "\n".join(failures)
+
+
+def _write(tmp_path, data) -> Path:
+    path = tmp_path / "scenario.json"
+    path.write_text(json.dumps(data))
+    return path
+
+
+class TestScenarioFiles:
+    """Tests for reading and checking scenario files."""
+
+    def test_scenarios_found(self):
+        """The bundled scenarios should be picked up."""
+        assert len(find_scenarios(SCENARIO_DIR)) >= 4
+
+    def test_unmet_expectation_reported(self, tmp_path):
+        """A range the detected shot falls outside should be reported with its value."""
+        path = _write(
+            tmp_path,
+            {
+                "radar": {"dropout": 0.0, "seed": 1},
+                "shots": [
+                    {
+                        "launch": {"ball_speed_mph": 150, "club": "driver"},
+                        "expect": {"ball_speed_mph": [100, 110]},
+                    },
+                    {"launch": {"ball_speed_mph": 140}, "expect": {"detected": False}},
+                ],
+            },
+        )
+
+        failures = check_scenario(load_scenario(path))
+
+        assert len(failures) == 2
+        assert failures[0].startswith("shot 1 (150 mph): ball_speed_mph")
+        assert "outside 100-110" in failures[0]
+        assert failures[1] == "shot 2 (140 mph): expected rejected, was not"
+
+    def test_shots_share_one_monitor(self, tmp_path):
+        """Shots should be played in order through the same monitor and radar."""
+        path = _write(
+            tmp_path,
+            {
+                "radar": {"seed": 2},
+                "shots": [
+                    {"launch": {"ball_speed_mph": 150, "club": "driver"}},
+                    {"launch": {"ball_speed_mph": 115, "club": "7-iron"}},
+                ],
+            },
+        )
+
+        outcomes = run_scenario(load_scenario(path))
+
+        assert [o.index for o in outcomes] == [1, 2]
+        assert outcomes[1].shot.club.value == "7-iron"
+        assert outcomes[1].shot.ball_speed_mph == pytest.approx(115, rel=0.05)
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            {"shots": []},
+            {"radar": {}},
+            {"shots": [{"launch": {"ball_speed_mph": 150, "club": "putter"}}]},
+            {"shots": [{"launch": {"ball_speed_mph": 150}, "expect": {"spin_rpm": [1, 2]}}]},
+            {"shots": [{"launch": {"ball_speed_mph": 150}, "expect": {"carry_yards": [300, 200]}}]},
+            {
+                "shots": [
+                    {
+                        "launch": {"ball_speed_mph": 150},
+                        "expect": {"detected": False, "ball_speed_mph": [140, 160]},
+                    }
+                ]
+            },
+            {"monitor": {"min_ball_speed": 20}, "shots": [{"launch": {"ball_speed_mph": 150}}]},
+            {"radar": {"mount_position": "above"}, "shots": [{"launch": {"ball_speed_mph": 150}}]},
+        ],
+    )
+    def test_invalid_scenario_rejected(self, tmp_path, data):
+        """Malformed scenarios should fail to load with the file named, not run."""
+        path = _write(tmp_path, data)
+
+        with pytest.raises(ValueError, match="scenario.json"):
+            load_scenario(path)
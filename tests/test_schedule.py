@@ -0,0 +1,126 @@
+"""Tests for schedule module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.schedule import (
+    IDLE,
+    QUIET_HOURS,
+    ArmSchedule,
+    ScheduledDisarm,
+    parse_quiet_hours,
+    parse_window,
+)
+
+
+def _at(hour, minute=0):
+    return datetime(2025, 6, 1, hour, minute)
+
+
+class TestArmSchedule:
+    """Tests for parsing and evaluating schedules."""
+
+    def test_window_across_midnight(self):
+        """A window ending earlier than it starts should cover midnight."""
+        schedule = ArmSchedule.from_dict({"quiet_hours": ["22:00-07:00"]})
+
+        assert schedule.in_quiet_hours(_at(23, 30))
+        assert schedule.in_quiet_hours(_at(3))
+        assert not schedule.in_quiet_hours(_at(7))
+        assert not schedule.in_quiet_hours(_at(12))
+
+    def test_daytime_window(self):
+        """A same-day window should include its start and exclude its end."""
+        schedule = ArmSchedule.from_dict({"quiet_hours": ["12:00-13:00"]})
+
+        assert schedule.in_quiet_hours(_at(12))
+        assert not schedule.in_quiet_hours(_at(13))
+
+    def test_round_trip(self):
+        """to_dict should give back the profile section."""
+        data = {"quiet_hours": ["22:00-07:00"], "idle_disarm_min": 30.0}
+
+        assert ArmSchedule.from_dict(data).to_dict() == data
+        assert not ArmSchedule.from_dict({}).enabled
+
+    def test_invalid(self):
+        """Bad windows, idle times and unknown fields should be rejected."""
+        for data in (
+            {"quiet_hours": ["22-7"]},
+            {"quiet_hours": "22:00-07:00"},
+            {"idle_disarm_min": 0},
+            {"idle_disarm_min": True},
+            {"quiet": []},
+        ):
+            with pytest.raises(ValueError):
+                ArmSchedule.from_dict(data)
+        with pytest.raises(ValueError):
+            parse_window("07:00-07:00")
+
+    def test_parse_quiet_hours_flag(self):
+        """Comma-separated windows should be split and validated."""
+        assert parse_quiet_hours("22:00-07:00, 12:00-13:00") == ["22:00-07:00", "12:00-13:00"]
+        with pytest.raises(ValueError):
+            parse_quiet_hours("22:00-25:00")
+
+
+class TestScheduledDisarm:
+    """Tests for disarming on schedule."""
+
+    def _disarm(self, events, **schedule):
+        return ScheduledDisarm(
+            ArmSchedule.from_dict(schedule), on_change=lambda e, r: events.append((e, r))
+        )
+
+    def test_quiet_hours_disarm_and_rearm(self):
+        """The monitor should disarm for quiet hours and re-arm after them."""
+        events = []
+        disarm = self._disarm(events, quiet_hours=["22:00-07:00"])
+
+        disarm.check(now=_at(21))
+        disarm.check(now=_at(22, 30))
+        assert disarm.disarmed
+        disarm.check(now=_at(7, 5))
+
+        assert events == [("disarmed", QUIET_HOURS), ("armed", None)]
+
+    def test_idle_disarm_needs_rearm(self):
+        """An idle disarm should outlast later shots until re-armed by hand."""
+        events = []
+        disarm = self._disarm(events, idle_disarm_min=30)
+        disarm.on_shot(mono=0.0)
+
+        disarm.check(now=_at(12), mono=29 * 60)
+        assert not disarm.disarmed
+        disarm.check(now=_at(12), mono=31 * 60)
+        disarm.on_shot(mono=32 * 60)
+        disarm.check(now=_at(12), mono=32 * 60)
+        assert disarm.reason == IDLE
+
+        disarm.rearm(now=_at(12), mono=33 * 60)
+        disarm.check(now=_at(12), mono=34 * 60)
+        assert not disarm.disarmed
+
+    def test_rearm_overrides_quiet_window(self):
+        """Re-arming during quiet hours should hold until the window ends."""
+        disarm = self._disarm([], quiet_hours=["22:00-07:00"])
+        disarm.check(now=_at(23))
+
+        disarm.rearm(now=_at(23))
+        disarm.check(now=_at(23, 30))
+        assert not disarm.disarmed
+        disarm.check(now=_at(8))
+        disarm.check(now=_at(22, 5))
+
+        assert disarm.reason == QUIET_HOURS
+
+    def test_schedule_edit_lifts_idle(self):
+        """Removing the idle disarm from the schedule should re-arm."""
+        disarm = self._disarm([], idle_disarm_min=1)
+        disarm.on_shot(mono=0.0)
+        disarm.check(now=_at(12), mono=120.0)
+
+        disarm.set_schedule(ArmSchedule())
+
+        assert not disarm.disarmed
@@ -0,0 +1,129 @@
+"""Tests for scoring module."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.launch_monitor import ClubType, Shot
+from openflight.scoring import (
+    PracticeTarget,
+    expected_strokes,
+    session_score,
+    strokes_gained,
+)
+
+
+class TestExpectedStrokes:
+    """Tests for the baseline table."""
+
+    def test_interpolates_fairway(self):
+        """Distances between table rows should interpolate linearly."""
+        assert expected_strokes(100) == pytest.approx(2.80)
+        assert expected_strokes(110) == pytest.approx(2.825)
+
+    def test_green_uses_putting_baseline(self):
+        """Within the green radius the baseline is in putts (by feet)."""
+        assert expected_strokes(1) == pytest.approx(1.04)
+        assert expected_strokes(0) == pytest.approx(1.0)
+
+    def test_clamped_beyond_table(self):
+        """Distances past the last row should use the last value."""
+        assert expected_strokes(500) == expected_strokes(350)
+
+
+class TestStrokesGained:
+    """Tests for scoring single shots."""
+
+    def test_close_shot_gains(self):
+        """A shot finishing near the target should gain strokes."""
+        gained, remaining = strokes_gained(150, 147)
+
+        assert remaining == pytest.approx(3.0)
+        assert gained > 0
+
+    def test_short_shot_loses(self):
+        """A shot left well short should lose strokes."""
+        gained, remaining = strokes_gained(150, 110)
+
+        assert remaining == pytest.approx(40.0)
+        assert gained < 0
+
+    def test_offline_counts(self):
+        """The same carry scores worse the further it lands offline."""
+        straight, _ = strokes_gained(150, 150, 0.0)
+        pushed, remaining = strokes_gained(150, 150, 10.0)
+
+        assert remaining > 20
+        assert pushed < straight
+
+
+class TestPracticeTarget:
+    """Tests for scoring shots against the selected target."""
+
+    def test_no_target_leaves_shot_unscored(self):
+        """Without a target shots should not be scored."""
+        shot = Shot(ball_speed_mph=150.0, timestamp=datetime.now())
+
+        PracticeTarget().score(shot)
+
+        assert shot.target_yards is None
+        assert shot.strokes_gained is None
+
+    def test_score_stamps_shot(self):
+        """Scoring should record the target and strokes gained on the shot."""
+        target = PracticeTarget()
+        target.set_target(150)
+        shot = Shot(
+            ball_speed_mph=110.0,
+            timestamp=datetime.now(),
+            club=ClubType.IRON_7,
+            launch_angle_horizontal=2.0,
+        )
+
+        target.score(shot)
+
+        expected, _ = strokes_gained(150, shot.estimated_carry_yards, 2.0)
+        assert shot.target_yards == 150.0
+        assert shot.strokes_gained == pytest.approx(expected)
+
+    def test_invalid_target(self):
+        """Targets that aren't a distance in range should be rejected."""
+        target = PracticeTarget()
+
+        for yards in (None, "150", True, 0, 400):
+            with pytest.raises(ValueError):
+                target.set_target(yards)
+
+    def test_clear(self):
+        """Clearing should stop scoring."""
+        target = PracticeTarget()
+        target.set_target(100)
+
+        target.clear()
+
+        assert target.to_dict() == {"target_yards": None, "baseline_strokes": None}
+
+
+class TestSessionScore:
+    """Tests for session aggregation."""
+
+    def test_totals_per_club(self):
+        """Scored shots should be summed overall and per club."""
+        shots = [
+            {"club": "7-iron", "strokes_gained": 0.25},
+            {"club": "7-iron", "strokes_gained": -0.5},
+            {"club": "pw", "strokes_gained": 0.1},
+            {"club": "pw", "strokes_gained": None},
+        ]
+
+        score = session_score(shots)
+
+        assert score["shots"] == 3
+        assert score["total"] == pytest.approx(-0.15)
+        assert score["best"] == pytest.approx(0.25)
+        assert score["by_club"]["7-iron"]["total"] == pytest.approx(-0.25)
+        assert score["by_club"]["pw"]["shots"] == 1
+
+    def test_no_scored_shots(self):
+        """A session without a target should have no score."""
+        assert session_score([{"club": "driver", "strokes_gained": None}]) is None
@@ -0,0 +1,64 @@
+"""Tests for segmentation module."""
+
+from openflight.ops243 import Direction, SpeedReading
+from openflight.segmentation import ChangePointSegmenter
+
+
+def _reading(speed, magnitude=1000.0):
+    return SpeedReading(speed=speed, direction=Direction.OUTBOUND, magnitude=magnitude)
+
+
+def _feed(segmenter, events):
+    """Feed (time, speed, magnitude) events; returns (time, completed) for each split."""
+    return [
+        (t, completed)
+        for t, speed, magnitude in events
+        if (completed := segmenter.update(_reading(speed, magnitude), t))
+    ]
+
+
+class TestChangePointSegmenter:
+    """Tests for splitting the reading stream into shots."""
+
+    def test_one_swing_is_one_shot(self):
+        """Club and ball readings in one burst should never be split."""
+        events = [(0.00, 95, 3000), (0.03, 98, 3200), (0.06, 150, 800), (0.09, 149, 750)]
+
+        assert _feed(ChangePointSegmenter(), events) == []
+
+    def test_slow_wedge_not_split_by_long_gap(self):
+        """Similar readings 0.6 s apart (longer than the old timeout) stay one shot."""
+        events = [(0.0, 60, 900), (0.2, 61, 950), (0.4, 60, 900), (1.0, 61, 920), (1.2, 60, 900)]
+
+        assert _feed(ChangePointSegmenter(), events) == []
+
+    def test_new_shot_split_at_change_point(self):
+        """A run of different readings after a pause should start a new shot where it began."""
+        wedge = [(0.0, 60, 900), (0.15, 61, 950), (0.3, 60, 900)]
+        drive = [(0.5, 150, 5000), (0.62, 152, 5200), (0.74, 151, 5100), (0.86, 150, 5000)]
+
+        splits = _feed(ChangePointSegmenter(), wedge + drive)
+
+        assert len(splits) == 1
+        assert splits[0][1] == 3  # The wedge's three readings
+
+    def test_single_outlier_does_not_split(self):
+        """One odd reading after a pause should not end the shot on its own."""
+        events = [(0.0, 150, 800), (0.03, 151, 820), (0.15, 90, 3000), (0.18, 150, 800)]
+
+        assert _feed(ChangePointSegmenter(), events) == []
+
+    def test_max_gap_always_splits(self):
+        """A pause of max_gap_sec should end the shot whatever the readings look like."""
+        segmenter = ChangePointSegmenter(max_gap_sec=1.0)
+        events = [(0.0, 150, 800), (0.03, 151, 800), (1.5, 150, 800)]
+
+        assert _feed(segmenter, events) == [(1.5, 2)]
+
+    def test_reset(self):
+        """After reset the next reading should start a fresh shot."""
+        segmenter = ChangePointSegmenter()
+        segmenter.update(_reading(150), 0.0)
+        segmenter.reset()
+
+        assert segmenter.update(_reading(150), 5.0) == 0
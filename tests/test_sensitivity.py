@@ -0,0 +1,43 @@
+"""Tests for shot detection sensitivity presets."""
+
+import pytest
+
+from openflight.launch_monitor import MonitorConfig
+from openflight.sensitivity import SENSITIVITY_LEVELS, apply_sensitivity, describe
+
+
+class TestSensitivity:
+    """Tests for applying presets."""
+
+    def test_presets_are_valid(self):
+        """Every preset should give a config that validates."""
+        for name in SENSITIVITY_LEVELS:
+            apply_sensitivity(MonitorConfig(), name).validate()
+
+    def test_normal_is_defaults(self):
+        """normal should leave the config as it was."""
+        assert apply_sensitivity(MonitorConfig(), "normal") == MonitorConfig()
+        assert describe("normal") == "defaults"
+
+    def test_high_accepts_what_low_rejects(self):
+        """high should have lower thresholds than normal, and low higher."""
+        low = apply_sensitivity(MonitorConfig(), "low")
+        high = apply_sensitivity(MonitorConfig(), "high")
+        normal = MonitorConfig()
+
+        for field in ("min_magnitude", "min_shot_magnitude", "min_ball_speed_mph"):
+            assert getattr(high, field) < getattr(normal, field) < getattr(low, field)
+
+    def test_unlisted_fields_kept(self):
+        """A preset should only change its own fields, keeping e.g. a profile's others."""
+        profile_config = MonitorConfig(club_ball_window_sec=0.2, min_shot_magnitude=80)
+
+        config = apply_sensitivity(profile_config, "high")
+
+        assert config.club_ball_window_sec == 0.2
+        assert config.min_shot_magnitude == 50
+
+    def test_unknown_preset(self):
+        """An unknown name should raise a ValueError listing the presets."""
+        with pytest.raises(ValueError, match="indoor-net"):
+            apply_sensitivity(MonitorConfig(), "max")
@@ -0,0 +1,160 @@
+"""Tests for raw serial capture and replay."""
+
+import sys
+
+import pytest
+
+from openflight import serial_dump
+from openflight.frames import RawAdc, SpeedTarget
+from openflight.serial_dump import READ, WRITE, SerialDump, read_dump, replay
+
+
+class _FakePort:
+    """Serial port that returns queued chunks."""
+
+    def __init__(self, chunks):
+        self.chunks = list(chunks)
+        self.written = []
+        self.timeout = 1.0
+
+    def read(self, size=1):
+        return self.chunks.pop(0) if self.chunks else b""
+
+    def readline(self):
+        return self.chunks.pop(0) if self.chunks else b""
+
+    def write(self, data):
+        self.written.append(data)
+        return len(data)
+
+
+def _capture(path, chunks, radar="ops243"):
+    """Write a capture of these chunks, read one per second."""
+    dump = SerialDump(str(path), radar=radar)
+    for i, chunk in enumerate(chunks):
+        dump.record(READ, chunk, timestamp=1000.0 + i)
+    dump.close()
+    return str(path)
+
+
+class TestTeeSerial:
+    """Tests for recording a port's traffic."""
+
+    def test_reads_and_writes_recorded(self, tmp_path):
+        """Reads and writes should reach the caller and the capture, in order."""
+        dump = SerialDump(str(tmp_path / "c.ofdump"))
+        port = dump.tee(_FakePort([b"12.5\n", b"", b"-3.0\n"]))
+
+        port.write(b"OJ")
+        assert port.readline() == b"12.5\n"
+        assert port.read(64) == b""
+        assert port.readline() == b"-3.0\n"
+        dump.close()
+
+        header, records = read_dump(str(tmp_path / "c.ofdump"))
+        records = list(records)
+        assert header["radar"] == "ops243"
+        assert [(r.direction, r.data) for r in records] == [
+            (WRITE, b"OJ"),
+            (READ, b"12.5\n"),
+            (READ, b"-3.0\n"),
+        ]
+
+    def test_attributes_reach_the_port(self, tmp_path):
+        """Setting timeout on the wrapper should set it on the port."""
+        fake = _FakePort([])
+        port = SerialDump(str(tmp_path / "c.ofdump")).tee(fake)
+
+        port.timeout = 0.25
+
+        assert fake.timeout == 0.25
+        assert port.timeout == 0.25
+
+    def test_records_after_close_ignored(self, tmp_path):
+        """Reads after the capture is closed shouldn't raise."""
+        dump = SerialDump(str(tmp_path / "c.ofdump"))
+        port = dump.tee(_FakePort([b"1.0\n"]))
+        dump.close()
+
+        assert port.readline() == b"1.0\n"
+
+
+class TestReadDump:
+    """Tests for reading capture files."""
+
+    def test_truncated_record_dropped(self, tmp_path):
+        """A record cut short by a crash should end the capture, not fail it."""
+        path = _capture(tmp_path / "c.ofdump", [b"10.0\n", b"20.0\n"])
+        with open(path, "ab") as f:
+            f.write(b"\x00\x01\x02")
+
+        _, records = read_dump(path)
+
+        assert [r.data for r in records] == [b"10.0\n", b"20.0\n"]
+
+    def test_not_a_capture(self, tmp_path):
+        """Other files should be rejected."""
+        path = tmp_path / "session.jsonl"
+        path.write_text('{"type": "session_start"}\n')
+
+        with pytest.raises(ValueError):
+            read_dump(str(path))
+
+
+class TestReplay:
+    """Tests for re-running the parser over a capture."""
+
+    def test_lines_split_across_reads(self, tmp_path):
+        """Lines split between reads should decode as if read whole."""
+        path = _capture(tmp_path / "c.ofdump", [b'{"speed": 45.', b'2, "magnitude": 900}\n'])
+
+        result = replay(*read_dump(path))
+
+        assert len(result.frames) == 1
+        timestamp, frame = result.frames[0]
+        assert isinstance(frame, SpeedTarget)
+        assert frame.targets[0].speed == pytest.approx(45.2)
+        assert timestamp == 1001.0
+        assert result.duration_sec == 1.0
+
+    def test_garbage_reported(self, tmp_path):
+        """Lines that aren't frames should be listed, I/Q halves and NaNs not."""
+        path = _capture(
+            tmp_path / "c.ofdump",
+            [b'{"I": [1, 2]}\n{"Q": [3, 4]}\n', b"12\x00\xff.4\n", b"nan\n", b"\r\n"],
+        )
+
+        result = replay(*read_dump(path))
+
+        assert [type(f) for _, f in result.frames] == [RawAdc]
+        assert result.unparsed == [(1001.0, b"12\x00\xff.4")]
+        assert result.discarded == 1
+        assert result.bytes_read == 41
+
+    def test_writes_not_parsed(self, tmp_path):
+        """Commands sent to the radar should be counted, not decoded."""
+        dump = SerialDump(str(tmp_path / "c.ofdump"))
+        dump.record(WRITE, b"50.0\n", timestamp=1.0)
+        dump.close()
+
+        result = replay(*read_dump(str(tmp_path / "c.ofdump")))
+
+        assert result.frames == []
+        assert result.bytes_written == 5
+
+
+class TestParseDumpCli:
+    """Tests for the openflight-parse-dump command."""
+
+    def test_summary(self, tmp_path, monkeypatch, capsys):
+        """The summary should count frames and show unparsed lines."""
+        path = _capture(tmp_path / "c.ofdump", [b"45.0\n-12.0\n", b"??garbage\n"])
+        monkeypatch.setattr(sys, "argv", ["openflight-parse-dump", path, "--frames"])
+
+        serial_dump.main()
+
+        out = capsys.readouterr().out
+        assert "SpeedTarget  2" in out
+        assert "45.0 mph" in out
+        assert "Unparsed lines: 1" in out
+        assert "b'??garbage'" in out
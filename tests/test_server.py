@@ -1,10 +1,32 @@
 """Tests for server module."""
 
+import json
+import sys
+import threading
+import time
+
 import pytest
 from datetime import datetime
 
-from openflight.launch_monitor import Shot, ClubType
-from openflight.server import MockLaunchMonitor, shot_to_dict, estimate_launch_angle
+from openflight.fit_export import RECORD, read_fit_records
+from openflight.geometry import MountingGeometry
+from openflight.launch_monitor import LaunchMonitor, MonitorConfig, Shot, ClubType
+from openflight import server
+from openflight.profiles import CalibrationProfile
+from openflight.rounds import RoundTracker
+from openflight.schedule import ArmSchedule, ScheduledDisarm
+from openflight.scoring import PracticeTarget
+from openflight.session_logger import SessionLogger
+from openflight.session_split import SessionSplitter
+from openflight.shot_timing import ShotTiming
+from openflight.speed_training import SpeedTrainingSession, parse_protocol
+from openflight.throttle import RateLimiter
+from openflight.server import (
+    MockLaunchMonitor,
+    estimate_launch_angle,
+    shot_from_payload,
+    shot_to_dict,
+)
 
 
 class TestShotToDict:
@@ -55,6 +77,17 @@ class TestShotToDict:
         assert result["club_speed_mph"] == 103.8  # 1 decimal
         assert result["smash_factor"] == 1.45  # 2 decimals
 
+    def test_ball_type(self):
+        """The ball type should be included, with the carry scaled for it."""
+        shot = Shot(ball_speed_mph=90.0, timestamp=datetime.now(), club=ClubType.IRON_7)
+        premium_carry = shot_to_dict(shot)["estimated_carry_yards"]
+
+        shot.ball_type = "foam"
+        result = shot_to_dict(shot)
+
+        assert result["ball_type"] == "foam"
+        assert result["estimated_carry_yards"] > premium_carry
+
 
 class TestEstimateLaunchAngle:
     """Tests for launch angle estimation from club type and ball speed."""
@@ -279,3 +312,1619 @@ class TestMockLaunchMonitor:
 
         assert monitor._shots == []
         assert monitor.get_session_stats()["shot_count"] == 0
+
+
+@pytest.fixture
+def api_client(monkeypatch):
+    """Flask test client backed by a mock monitor."""
+    mock = MockLaunchMonitor()
+    mock.connect()
+    mock.start()
+    monkeypatch.setattr(server, "monitor", mock)
+    monkeypatch.setattr(server, "mock_mode", True)
+    return server.app.test_client(), mock
+
+
+class TestRestApi:
+    """Tests for the /api REST endpoints."""
+
+    def test_session_without_monitor(self, monkeypatch):
+        """Endpoints should return 503 when no monitor is running."""
+        monkeypatch.setattr(server, "monitor", None)
+
+        response = server.app.test_client().get("/api/session")
+
+        assert response.status_code == 503
+        assert "error" in response.get_json()
+
+    def test_get_session(self, api_client):
+        """Session endpoint should return stats and shots."""
+        client, mock = api_client
+        mock.simulate_shot(ball_speed=150.0)
+
+        data = client.get("/api/session").get_json()
+
+        assert data["stats"]["shot_count"] == 1
+        assert data["shots"][0]["ball_speed_mph"] == 150.0
+        assert data["mock_mode"] is True
+
+    def test_stats_dispersion(self, api_client):
+        """Stats should include each club's landing dispersion."""
+        client, mock = api_client
+        for _ in range(3):
+            mock.simulate_shot(ball_speed=150.0)
+
+        data = client.get("/api/stats?basket_pct=5").get_json()
+
+        (dispersion,) = data["dispersion"]
+        assert dispersion["club"] == "driver"
+        assert dispersion["shots"] == 3
+        assert dispersion["ellipse"] is not None
+        expected_radius = dispersion["avg_downrange"] * 0.05
+        assert dispersion["basket_radius"] == pytest.approx(expected_radius, abs=0.1)
+
+    def test_stats_bad_basket(self, api_client):
+        """A basket that isn't a percentage should be rejected."""
+        client, _ = api_client
+
+        assert client.get("/api/stats?basket_pct=0").status_code == 400
+        assert client.get("/api/stats?basket_pct=wide").status_code == 400
+
+    def test_get_shots_limit(self, api_client):
+        """limit query parameter should return only the most recent shots."""
+        client, mock = api_client
+        for speed in (140.0, 150.0, 160.0):
+            mock.simulate_shot(ball_speed=speed)
+
+        shots = client.get("/api/shots?limit=2").get_json()["shots"]
+
+        assert [s["ball_speed_mph"] for s in shots] == [150.0, 160.0]
+
+    def test_set_club(self, api_client):
+        """PUT /api/club should change the monitor's club."""
+        client, mock = api_client
+
+        response = client.put("/api/club", json={"club": "7-iron"})
+
+        assert response.status_code == 200
+        assert mock.simulate_shot().club == ClubType.IRON_7
+
+    def test_set_unknown_club(self, api_client):
+        """Unknown club names should be rejected with 400."""
+        client, _ = api_client
+
+        response = client.put("/api/club", json={"club": "putter"})
+
+        assert response.status_code == 400
+
+    def test_simulate_shot(self, api_client):
+        """POST /api/simulate should record a shot at the requested speed."""
+        client, mock = api_client
+
+        data = client.post("/api/simulate", json={"ball_speed": 155}).get_json()
+
+        assert data["shot"]["ball_speed_mph"] == 155.0
+        assert len(mock.get_shots()) == 1
+
+    def test_simulate_requires_mock(self, monkeypatch):
+        """Simulation should be refused for a real monitor."""
+        monkeypatch.setattr(server, "monitor", object())
+
+        response = server.app.test_client().post("/api/simulate")
+
+        assert response.status_code == 409
+
+    def test_simulate_rejects_non_finite_speed(self, api_client):
+        """NaN and infinite ball speeds should be rejected with 400."""
+        client, mock = api_client
+
+        for body in ('{"ball_speed": NaN}', '{"ball_speed": Infinity}', '{"ball_speed": "-inf"}'):
+            response = client.post("/api/simulate", data=body, content_type="application/json")
+            assert response.status_code == 400
+
+        assert len(mock.get_shots()) == 0
+
+    def test_clear_session(self, api_client):
+        """DELETE /api/session should clear shots."""
+        client, mock = api_client
+        mock.simulate_shot()
+
+        client.delete("/api/session")
+
+        assert mock.get_shots() == []
+
+    def test_get_config(self, api_client):
+        """GET /api/config should return the active thresholds."""
+        client, _ = api_client
+
+        data = client.get("/api/config").get_json()
+
+        assert data["config"]["min_shot_magnitude"] == 100
+
+    def test_put_config_applies_live(self, api_client):
+        """PUT /api/config should swap in the updated thresholds."""
+        client, mock = api_client
+
+        response = client.put("/api/config", json={"shot_timeout_sec": 0.8})
+
+        assert response.status_code == 200
+        assert mock.config.shot_timeout_sec == 0.8
+        assert response.get_json()["config"]["shot_timeout_sec"] == 0.8
+
+    def test_put_config_invalid_is_atomic(self, api_client):
+        """An invalid update should be rejected without applying any field."""
+        client, mock = api_client
+
+        response = client.put(
+            "/api/config", json={"shot_timeout_sec": 0.8, "min_ball_speed_mph": 500}
+        )
+
+        assert response.status_code == 400
+        assert mock.config.shot_timeout_sec == 0.5
+
+    def test_put_config_logs_change(self, api_client, tmp_path, monkeypatch):
+        """Config changes should be recorded in the session log."""
+        from openflight.session_logger import SessionLogger
+
+        client, _ = api_client
+        session_logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        session_logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: session_logger)
+
+        client.put("/api/config", json={"min_magnitude": 30})
+
+        lines = session_logger.session_path.read_text().splitlines()
+        entries = [json.loads(line) for line in lines]
+        assert entries[-1]["type"] == "monitor_config"
+        assert entries[-1]["source"] == "api"
+        assert entries[-1]["config"]["min_magnitude"] == 30
+
+
+class TestApiAccessControl:
+    """Tests for token and allowlist enforcement on the REST API."""
+
+    def test_token_required(self, api_client, monkeypatch):
+        """Requests without the token should get 401."""
+        from openflight.access import AccessPolicy
+
+        client, _ = api_client
+        monkeypatch.setattr(server, "access_policy", AccessPolicy(token="s3cret"))
+
+        assert client.get("/api/stats").status_code == 401
+        response = client.get("/api/stats", headers={"Authorization": "Bearer s3cret"})
+        assert response.status_code == 200
+
+    def test_config_change_blocked_without_token(self, api_client, monkeypatch):
+        """An unauthenticated PUT must not change thresholds."""
+        from openflight.access import AccessPolicy
+
+        client, mock = api_client
+        monkeypatch.setattr(server, "access_policy", AccessPolicy(token="s3cret"))
+
+        client.put("/api/config", json={"min_magnitude": 5})
+
+        assert mock.config.min_magnitude == 20
+
+    def test_address_not_in_allowlist(self, api_client, monkeypatch):
+        """Clients outside the allowlist should get 403."""
+        from openflight.access import AccessPolicy
+
+        client, _ = api_client
+        monkeypatch.setattr(server, "access_policy", AccessPolicy(allowed=["192.168.1.0/24"]))
+
+        response = client.get("/api/stats", environ_base={"REMOTE_ADDR": "10.0.0.7"})
+        assert response.status_code == 403
+        response = client.get("/api/stats", environ_base={"REMOTE_ADDR": "192.168.1.7"})
+        assert response.status_code == 200
+
+    def _socket_connect(self, auth=None, headers=None):
+        """Run the Socket.IO connect handler for a client at 192.168.1.7."""
+        from flask import request
+
+        with server.app.test_request_context(
+            "/socket.io/", headers=headers, environ_base={"REMOTE_ADDR": "192.168.1.7"}
+        ):
+            request.sid = "test-sid"
+            return server.handle_connect(auth)
+
+    def test_socket_rejected_without_token(self, monkeypatch):
+        """A Socket.IO client without the token must not connect."""
+        from openflight.access import AccessPolicy
+
+        monkeypatch.setattr(server, "access_policy", AccessPolicy(token="s3cret"))
+        monkeypatch.setattr(server, "monitor", None)
+
+        assert self._socket_connect() is False
+        assert self._socket_connect(auth={"token": "wrong"}) is False
+
+    def test_socket_accepted_with_token(self, monkeypatch):
+        """The token is accepted from the connect auth payload or the headers."""
+        from openflight.access import AccessPolicy
+
+        monkeypatch.setattr(server, "access_policy", AccessPolicy(token="s3cret"))
+        monkeypatch.setattr(server, "monitor", None)
+
+        assert self._socket_connect(auth={"token": "s3cret"}) is not False
+        assert self._socket_connect(headers={"Authorization": "Bearer s3cret"}) is not False
+        server._socket_reading_limits.pop("test-sid", None)
+
+
+class TestShotInjection:
+    """Tests for synthetic shot injection."""
+
+    def test_shot_from_payload(self):
+        """Payload fields should map onto the shot."""
+        shot = shot_from_payload(
+            {"ball_speed_mph": 150, "launch_angle_vertical": 14, "club": "7-iron"}
+        )
+
+        assert shot.ball_speed_mph == 150.0
+        assert shot.launch_angle_vertical == 14.0
+        assert shot.launch_angle_horizontal == 0.0
+        assert shot.club == ClubType.IRON_7
+        assert shot.mode == "injected"
+
+    def test_shot_from_payload_requires_ball_speed(self):
+        """Missing ball speed should be rejected."""
+        with pytest.raises(ValueError):
+            shot_from_payload({"launch_angle_vertical": 14})
+
+    def test_shot_from_payload_rejects_bad_values(self):
+        """Non-numeric and non-positive speeds should be rejected."""
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": "fast"})
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": -5})
+
+    def test_shot_from_payload_rejects_non_finite_values(self):
+        """NaN and infinite values should be rejected for every numeric field."""
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": float("nan")})
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": float("inf")})
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": 150, "spin_rpm": "nan"})
+        with pytest.raises(ValueError):
+            shot_from_payload({"ball_speed_mph": 150, "launch_angle_vertical": float("-inf")})
+
+    def test_inject_rejects_non_finite_payload(self, api_client, monkeypatch):
+        """A NaN or Infinity literal in the JSON body should get 400, not a shot."""
+        client, _ = api_client
+        delivered = []
+        monkeypatch.setattr(server, "on_shot_detected", delivered.append)
+
+        for body in ('{"ball_speed_mph": NaN}', '{"ball_speed_mph": 150, "spin_rpm": Infinity}'):
+            response = client.post("/api/inject", data=body, content_type="application/json")
+            assert response.status_code == 400
+
+        assert delivered == []
+
+    def test_inject_uses_delivery_path(self, api_client, monkeypatch):
+        """Injected shots should go through on_shot_detected, not the session."""
+        client, mock = api_client
+        delivered = []
+        monkeypatch.setattr(server, "on_shot_detected", delivered.append)
+
+        response = client.post("/api/inject", json={"ball_speed_mph": 150, "spin_rpm": 2500})
+
+        assert response.status_code == 200
+        assert response.get_json()["shot"]["mode"] == "injected"
+        assert len(delivered) == 1
+        assert delivered[0].spin_rpm == 2500.0
+        assert mock.get_shots() == []
+
+    def test_inject_refused_from_network_without_token(self, api_client, monkeypatch):
+        """Remote injection without a configured token should be refused."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "on_shot_detected", lambda shot: None)
+
+        response = client.post(
+            "/api/inject",
+            json={"ball_speed_mph": 150},
+            environ_base={"REMOTE_ADDR": "192.168.1.50"},
+        )
+
+        assert response.status_code == 403
+
+    def test_inject_invalid_payload(self, api_client):
+        """Invalid payloads should return 400."""
+        client, _ = api_client
+
+        response = client.post("/api/inject", json={"ball_speed_mph": 0})
+
+        assert response.status_code == 400
+
+
+class TestCompanionLaunchAngle:
+    """Tests for launch angles from a companion camera."""
+
+    def test_angle_matched_to_shot(self, api_client, monkeypatch):
+        """An angle stamped in companion time should go to the shot at that monitor time."""
+        from openflight.ops243 import Direction, SpeedReading
+        from openflight.timesync import CompanionEvents
+
+        client, _ = api_client
+        monkeypatch.setattr(server, "companion_angles", CompanionEvents())
+        monkeypatch.setattr(server, "launch_angle_offset_deg", 0.0)
+
+        response = client.post(
+            "/api/launch_angle",
+            json={"vertical": 14.0, "horizontal": 2.0, "time": 40.05, "clock_offset_sec": 60.0},
+        )
+        shot = Shot(
+            ball_speed_mph=150.0,
+            timestamp=datetime.now(),
+            readings=[
+                SpeedReading(100.0, Direction.OUTBOUND, timestamp=99.98),
+                SpeedReading(150.0, Direction.OUTBOUND, timestamp=100.0),
+            ],
+        )
+        server.on_shot_detected(shot)
+
+        assert response.get_json() == {"monitor_time": pytest.approx(100.05)}
+        assert shot.launch_angle_vertical == 14.0
+        assert shot.launch_angle_horizontal == 2.0
+        assert shot.launch_angle_confidence == 1.0
+
+    def test_late_angle_waited_for_only_within_window(self, api_client, monkeypatch):
+        """A fresh shot should wait for its angle; an old one shouldn't hold up the radar."""
+        from openflight.ops243 import Direction, SpeedReading
+        from openflight.timesync import CompanionEvents
+
+        monkeypatch.setattr(server, "companion_angles", CompanionEvents())
+        server.companion_angles.add(5.0, {"vertical": 9.0, "horizontal": 0.0, "confidence": 1.0})
+
+        def shot_at(impact):
+            reading = SpeedReading(150.0, Direction.OUTBOUND, timestamp=impact)
+            return Shot(ball_speed_mph=150.0, timestamp=datetime.now(), readings=[reading])
+
+        old = shot_at(time.monotonic() - 5.0)
+        start = time.monotonic()
+        assert server.apply_companion_angle(old) is None
+        assert time.monotonic() - start < 0.1
+
+        impact = time.monotonic()
+        angle = {"vertical": 14.0, "horizontal": 0.0, "confidence": 1.0}
+        threading.Timer(0.05, server.companion_angles.add, args=(impact, angle)).start()
+        fresh = shot_at(impact)
+        assert server.apply_companion_angle(fresh)["source"] == "companion"
+
+    def test_invalid_angle(self, api_client):
+        """A missing angle, or a time without its offset, should be rejected."""
+        client, _ = api_client
+
+        assert client.post("/api/launch_angle", json={"horizontal": 1}).status_code == 400
+        response = client.post("/api/launch_angle", json={"vertical": 12, "time": 5.0})
+        assert response.status_code == 400
+
+    def test_non_finite_angle(self, api_client, monkeypatch):
+        """NaN or infinite angles and times should be rejected, not buffered."""
+        from openflight.timesync import CompanionEvents
+
+        client, _ = api_client
+        monkeypatch.setattr(server, "companion_angles", CompanionEvents())
+
+        for body in (
+            '{"vertical": NaN}',
+            '{"vertical": 12, "horizontal": Infinity}',
+            '{"vertical": 12, "time": NaN, "clock_offset_sec": 0.0}',
+            '{"vertical": 12, "time": 5.0, "clock_offset_sec": -Infinity}',
+        ):
+            response = client.post("/api/launch_angle", data=body, content_type="application/json")
+            assert response.status_code == 400
+        assert not server.companion_angles.active
+
+
+class TestRoundApi:
+    """Tests for sim hole context."""
+
+    def test_put_round_tags_shots(self, api_client, monkeypatch):
+        """Shots after PUT /api/round should carry the hole and stroke index."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+
+        response = client.put("/api/round", json={"hole": 7})
+        shot = shot_from_payload({"ball_speed_mph": 150})
+        server.on_shot_detected(shot)
+
+        assert response.get_json() == {"hole": 7, "next_shot": 1}
+        assert shot_to_dict(shot)["hole_number"] == 7
+        assert shot_to_dict(shot)["hole_shot"] == 1
+        assert client.get("/api/round").get_json()["next_shot"] == 2
+
+    def test_put_round_invalid(self, api_client, monkeypatch):
+        """A missing or non-integer hole should be rejected."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+
+        assert client.put("/api/round", json={}).status_code == 400
+        assert client.put("/api/round", json={"hole": "3"}).status_code == 400
+
+    def test_delete_round(self, api_client, monkeypatch):
+        """DELETE /api/round should stop tagging shots."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        client.put("/api/round", json={"hole": 2})
+
+        client.delete("/api/round")
+        shot = shot_from_payload({"ball_speed_mph": 150})
+        server.on_shot_detected(shot)
+
+        assert shot.hole_number is None
+
+
+class TestTargetApi:
+    """Tests for strokes-gained practice targets."""
+
+    def test_put_target_scores_shots(self, api_client, monkeypatch):
+        """Shots after PUT /api/target should be scored and totalled in the stats."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "practice_target", PracticeTarget())
+
+        response = client.put("/api/target", json={"yards": 150})
+        shot = shot_from_payload({"ball_speed_mph": 150})
+        server.on_shot_detected(shot)
+
+        assert response.get_json()["target_yards"] == 150.0
+        assert shot_to_dict(shot)["target_yards"] == 150.0
+        assert shot_to_dict(shot)["strokes_gained"] is not None
+
+    def test_stats_strokes_gained(self, api_client, monkeypatch):
+        """Stats should total the session's scored shots."""
+        client, mock = api_client
+        monkeypatch.setattr(server, "practice_target", PracticeTarget())
+        mock.start(shot_callback=server.on_shot_detected)
+        client.put("/api/target", json={"yards": 200})
+        mock.simulate_shot(ball_speed=150.0)
+
+        score = client.get("/api/stats").get_json()["strokes_gained"]
+
+        assert score["shots"] == 1
+        assert "driver" in score["by_club"]
+
+    def test_put_target_invalid(self, api_client, monkeypatch):
+        """A missing or out-of-range distance should be rejected."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "practice_target", PracticeTarget())
+
+        assert client.put("/api/target", json={}).status_code == 400
+        assert client.put("/api/target", json={"yards": 1000}).status_code == 400
+
+    def test_delete_target(self, api_client, monkeypatch):
+        """DELETE /api/target should stop scoring shots."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "practice_target", PracticeTarget())
+        client.put("/api/target", json={"yards": 100})
+
+        client.delete("/api/target")
+        shot = shot_from_payload({"ball_speed_mph": 150})
+        server.on_shot_detected(shot)
+
+        assert shot.strokes_gained is None
+
+    def test_speed_training_summary(self, api_client, monkeypatch):
+        """Club-only swings should count through the protocol, summarized by the API."""
+        client, _ = api_client
+        session = SpeedTrainingSession(parse_protocol("light:2,heavy:1"), rest_sec=0)
+        monkeypatch.setattr(server, "speed_training", session)
+
+        for speed in (110.0, 108.0, 101.0):
+            server.record_speed_swing(speed)
+        server.record_speed_swing(120.0)  # After the protocol: ignored
+
+        summary = client.get("/api/speed-training").get_json()
+        assert summary["complete"] is True
+        assert [s["speeds_mph"] for s in summary["sets"]] == [[110.0, 108.0], [101.0]]
+
+        summary = client.delete("/api/speed-training").get_json()
+        assert summary["reps_done"] == 0
+
+    def test_swing_speed_mode_counts_swings(self, api_client, monkeypatch):
+        """Swings from --mode swing-speed should count towards speed training."""
+        from openflight.swing_speed import Swing
+
+        session = SpeedTrainingSession(parse_protocol("1x2"), rest_sec=0)
+        monkeypatch.setattr(server, "speed_training", session)
+        monkeypatch.setattr(server, "speed_swings", None)
+        events = []
+        monkeypatch.setattr(server, "publish_stream_event", lambda *args: events.append(args))
+
+        server.on_swing_detected(Swing(club_speed_mph=104.2, timestamp=datetime.now()))
+
+        assert events[0] == ("swing", events[0][1])
+        assert events[0][1]["club_speed_mph"] == 104.2
+        assert session.summary()["sets"][0]["speeds_mph"] == [104.2]
+
+    def test_speed_training_not_enabled(self, api_client, monkeypatch):
+        """GET /api/speed-training should be 404 without --speed-training."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "speed_training", None)
+
+        assert client.get("/api/speed-training").status_code == 404
+
+
+class TestExportApi:
+    """Tests for CSV export of the current session."""
+
+    def test_export_template(self, api_client):
+        """Shots should be exported in the requested template's layout."""
+        client, mock = api_client
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.get("/api/export?template=trackman")
+
+        lines = response.get_data(as_text=True).splitlines()
+        assert response.mimetype == "text/csv"
+        assert "attachment" in response.headers["Content-Disposition"]
+        assert lines[0].startswith("Date,Player,Club,Club Speed")
+        assert len(lines) == 3
+
+    def test_export_html(self, api_client):
+        """format=html should download a self-contained page of the session's shots."""
+        client, mock = api_client
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.get("/api/export?format=html")
+
+        assert response.mimetype == "text/html"
+        assert ".html" in response.headers["Content-Disposition"]
+        assert '<table id="shots">' in response.get_data(as_text=True)
+        assert client.get("/api/export?format=pdf").status_code == 400
+
+    def test_export_fit(self, api_client):
+        """format=fit should download the session's shots as a FIT activity."""
+        client, mock = api_client
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.get("/api/export?format=fit")
+
+        assert response.mimetype == "application/vnd.ant.fit"
+        assert ".fit" in response.headers["Content-Disposition"]
+        records = [m for m in read_fit_records(response.get_data()) if m["global"] == RECORD]
+        assert len(records) == 1
+
+    def test_export_unknown_template(self, api_client):
+        """Unknown templates and file paths should be 404."""
+        client, _ = api_client
+
+        assert client.get("/api/export?template=nope").status_code == 404
+        assert client.get("/api/export?template=/etc/x.json").status_code == 404
+
+
+class TestSessionTagsApi:
+    """Tests for tagging the session mid-session."""
+
+    def test_patch_tags(self, api_client, tmp_path, monkeypatch):
+        """PATCH should merge tags, log them and return the full set."""
+        client, _ = api_client
+        logger = SessionLogger(log_dir=tmp_path, tags={"ball_type": "range"})
+        logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+
+        response = client.patch("/api/session/tags", json={"weather": "windy", "ball_type": None})
+
+        assert response.get_json()["tags"] == {"location": "range", "weather": "windy"}
+        assert client.get("/api/session/tags").get_json()["tags"]["weather"] == "windy"
+        last = json.loads(logger.session_path.read_text().strip().split("\n")[-1])
+        assert last["type"] == "session_tags"
+
+    def test_new_player_starts_session(self, api_client, tmp_path, monkeypatch):
+        """With --session-per-player, a new player gets a session of their own."""
+        client, mock = api_client
+        logger = SessionLogger(log_dir=tmp_path, tags={"player": "sam"})
+        logger.start_session(mode="mock")
+        first = logger.session_path
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        monkeypatch.setattr(
+            server,
+            "session_splitter",
+            SessionSplitter(on_player=True, on_split=server.split_session),
+        )
+        mock.start(shot_callback=server.on_shot_detected)
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.patch("/api/session/tags", json={"player": "alex"})
+
+        assert response.get_json()["tags"]["player"] == "alex"
+        assert logger.session_path != first
+        assert mock.get_shots() == []
+        header = json.loads(logger.session_path.read_text().split("\n")[0])
+        assert header["tags"]["player"] == "alex"
+
+    def test_patch_invalid_tags(self, api_client, tmp_path, monkeypatch):
+        """A body that isn't an object of text values should be rejected."""
+        client, _ = api_client
+        logger = SessionLogger(log_dir=tmp_path, enabled=False)
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+
+        assert client.patch("/api/session/tags", json=["windy"]).status_code == 400
+        assert client.patch("/api/session/tags", json={"wind": [10]}).status_code == 400
+
+
+class TestGrpcHandlers:
+    """Tests for the gRPC service's handlers."""
+
+    def test_set_player(self, api_client, tmp_path, monkeypatch):
+        """SetPlayer should set the session's player tag, and "" should clear it."""
+        logger = SessionLogger(log_dir=tmp_path, tags={"location": "range"})
+        logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+        monkeypatch.setattr(server, "session_splitter", None)
+
+        result = server.grpc_set_player({"player": "sam"})
+
+        assert result == {"player": "sam", "tags": {"location": "range", "player": "sam"}}
+        assert server.current_player() == "sam"
+        assert server.grpc_set_player({})["player"] is None
+
+    def test_set_club(self, api_client):
+        """SetClub should select the club, and reject unknown ones as invalid arguments."""
+        _, mock = api_client
+
+        assert server.grpc_set_club({"club": "7-iron"}) == {"club": "7-iron"}
+        assert mock.get_club() == ClubType.IRON_7
+        with pytest.raises(ValueError, match="putter"):
+            server.grpc_set_club({"club": "putter"})
+
+    def test_simulate_shot(self, api_client, monkeypatch):
+        """SimulateShot should need mock mode and a finite ball speed."""
+        _, mock = api_client
+
+        assert server.grpc_simulate_shot({"ball_speed": 150.0})["shot"]["ball_speed_mph"] == 150.0
+        with pytest.raises(ValueError):
+            server.grpc_simulate_shot({"ball_speed": float("nan")})
+        assert len(mock.get_shots()) == 1
+
+        monkeypatch.setattr(server, "monitor", object())
+        with pytest.raises(server.ServiceError):
+            server.grpc_simulate_shot({})
+
+    def test_stream_events(self, api_client):
+        """StreamEvents should yield published events and unsubscribe when the client goes."""
+        active = [True]
+        events = server.grpc_stream_events({}, lambda: active[0])
+
+        def publish():
+            while not server._stream_subscribers:  # Subscribed on the first next()
+                time.sleep(0.01)
+            server.publish_stream_event("reading", {"speed": 150.0})  # Not asked for
+            server.publish_stream_event("club_changed", {"club": "driver"})
+            server.publish_stream_event("shot", {"ball_speed_mph": 150.0})
+
+        threading.Thread(target=publish, daemon=True).start()
+
+        assert next(events) == ("club_changed", {"club": "driver"})
+        assert next(events) == ("shot", {"ball_speed_mph": 150.0})
+        active[0] = False
+        assert list(events) == []
+        assert server._stream_subscribers == []
+
+    def test_authorize(self, monkeypatch):
+        """--api-token and --allow should apply to gRPC calls as to /api."""
+        from openflight.access import AccessPolicy
+
+        monkeypatch.setattr(
+            server, "access_policy", AccessPolicy(token="s3cret", allowed=["192.168.1.0/24"])
+        )
+
+        server.grpc_authorize({"authorization": "Bearer s3cret"}, "192.168.1.7")
+        server.grpc_authorize({"x-openflight-token": "s3cret"}, "127.0.0.1")
+        with pytest.raises(server.ServiceError) as error:
+            server.grpc_authorize({}, "192.168.1.7")
+        assert error.value.code == "UNAUTHENTICATED"
+        with pytest.raises(server.ServiceError) as error:
+            server.grpc_authorize({"authorization": "Bearer s3cret"}, "10.0.0.7")
+        assert error.value.code == "PERMISSION_DENIED"
+
+
+class TestRemoteApi:
+    """Tests for the phone remote's mulligan and re-arm endpoints."""
+
+    def test_mulligan_removes_last_shot(self, api_client, tmp_path, monkeypatch):
+        """A mulligan should drop the last shot and mark it in the session log."""
+        client, mock = api_client
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        mock.start(shot_callback=server.on_shot_detected)
+        mock.simulate_shot(ball_speed=140.0)
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.post("/api/mulligan")
+
+        assert response.get_json()["shot"]["ball_speed_mph"] == 150.0
+        assert [s.ball_speed_mph for s in mock.get_shots()] == [140.0]
+        last = json.loads(logger.session_path.read_text().strip().split("\n")[-1])
+        assert last == {**last, "type": "shot_mulligan", "shot_number": 2}
+
+    def test_delete_shot(self, api_client, tmp_path, monkeypatch):
+        """Any shot in the session can be deleted, with an audit entry in the log."""
+        client, mock = api_client
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        mock.start(shot_callback=server.on_shot_detected)
+        mock.simulate_shot(ball_speed=140.0)
+        mock.simulate_shot(ball_speed=90.0)
+        mock.simulate_shot(ball_speed=150.0)
+
+        response = client.delete("/api/shot?shot_number=2&reason=shank")
+
+        assert response.get_json()["shot"]["shot_number"] == 2
+        assert [s.ball_speed_mph for s in mock.get_shots()] == [140.0, 150.0]
+        last = json.loads(logger.session_path.read_text().strip().split("\n")[-1])
+        assert last == {**last, "type": "shot_deleted", "shot_number": 2, "reason": "shank"}
+        assert client.delete("/api/shot?shot_number=2").status_code == 404
+
+    def test_edit_shot_club(self, api_client, tmp_path, monkeypatch):
+        """Correcting a shot's club re-estimates its carry and logs the old club."""
+        client, mock = api_client
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: logger)
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        mock.start(shot_callback=server.on_shot_detected)
+        mock.simulate_shot(ball_speed=120.0)
+        shot = mock.get_shots()[0]
+        driver_carry = shot.estimated_carry_yards
+
+        response = client.patch("/api/shot?shot_number=1", json={"club": "7-iron"})
+
+        assert response.get_json()["shot"]["club"] == "7-iron"
+        assert shot.club == ClubType.IRON_7
+        last = json.loads(logger.session_path.read_text().strip().split("\n")[-1])
+        assert last["type"] == "shot_edited"
+        assert last["previous"] == {"club": "driver", "estimated_carry_yards": driver_carry}
+        assert (
+            client.patch("/api/shot?shot_number=1", json={"club": "spoon"}).status_code == 400
+        )
+
+    def test_mulligan_gives_back_stroke(self, api_client, monkeypatch):
+        """Taking back the last shot on a hole should reuse its stroke index."""
+        client, mock = api_client
+        monkeypatch.setattr(server, "round_tracker", RoundTracker())
+        mock.start(shot_callback=server.on_shot_detected)
+        client.put("/api/round", json={"hole": 4})
+        mock.simulate_shot(ball_speed=150.0)
+
+        client.post("/api/mulligan")
+
+        assert client.get("/api/round").get_json()["next_shot"] == 1
+
+    def test_mulligan_without_shots(self, api_client):
+        """There is nothing to take back in an empty session."""
+        client, _ = api_client
+
+        assert client.post("/api/mulligan").status_code == 409
+
+    def test_arm_publishes_ready(self, api_client, monkeypatch):
+        """Re-arming should tell subscribers the monitor is ready."""
+        client, _ = api_client
+        events = []
+        monkeypatch.setattr(server, "publish_arm_event", events.append)
+
+        response = client.post("/api/arm")
+
+        assert response.get_json() == {"ready": True}
+        assert events == ["ready"]
+
+    def test_remote_page_served(self, api_client):
+        """The remote page should be served at /remote."""
+        client, _ = api_client
+
+        response = client.get("/remote")
+
+        assert response.status_code == 200
+        assert b"/api/mulligan" in response.data
+        response.close()
+
+
+class TestClubGesture:
+    """Tests for switching clubs with the radar gesture."""
+
+    def test_gesture_selects_next_club(self, api_client, monkeypatch):
+        """A completed gesture should select and announce the next club in the bag."""
+        from openflight.gestures import ClubGestureDetector
+        from openflight.ops243 import Direction, SpeedReading
+
+        _, mock = api_client
+        spoken = []
+        emitted = []
+        monkeypatch.setattr(server, "club_gesture", ClubGestureDetector(passes=1))
+        monkeypatch.setattr(server, "club_gesture_bag", [ClubType.DRIVER, ClubType.IRON_7])
+        monkeypatch.setattr(server, "speak", spoken.append)
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(a))
+
+        server.on_live_reading(SpeedReading(15.0, Direction.INBOUND))
+
+        assert mock.get_club() == ClubType.IRON_7
+        assert spoken == ["7 iron"]
+        assert ("club_changed", {"club": "7-iron"}) in emitted
+
+
+class TestTempo:
+    """Tests for measuring swing tempo on detected shots."""
+
+    def test_shot_gets_tempo(self, monkeypatch):
+        """The backswing before the ball reading gives the shot its tempo."""
+        from openflight.ops243 import Direction, SpeedReading
+        from openflight.tempo import TempoMeter
+
+        monkeypatch.setattr(server, "tempo_meter", TempoMeter())
+        monkeypatch.setattr(server, "tempo_target", 3.0)
+        for t in (10.0, 10.3, 10.6):
+            server.on_live_reading(SpeedReading(20.0, Direction.INBOUND, timestamp=t))
+        shot = Shot(
+            ball_speed_mph=150.0,
+            timestamp=datetime.now(),
+            readings=[
+                SpeedReading(100.0, Direction.OUTBOUND, timestamp=10.78),
+                SpeedReading(150.0, Direction.OUTBOUND, timestamp=10.8),
+            ],
+        )
+
+        server.measure_tempo(shot)
+
+        assert shot.tempo_ratio == pytest.approx(3.0)
+        assert shot.tempo_on_target
+        assert shot_to_dict(shot)["tempo_ratio"] == 3.0
+
+
+class TestShotDelivery:
+    """Tests for webhook delivery status on shots."""
+
+    class _FakeSink:
+        def __init__(self):
+            self.sent = []
+
+        def send(self, payload, on_result=None):
+            self.sent.append((payload, on_result))
+
+    def test_pending_then_delivered(self, api_client, monkeypatch):
+        """Shots start pending and are marked from the delivery receipts."""
+        from openflight.webhooks import DeliveryReceipt
+
+        client, _ = api_client
+        sink = self._FakeSink()
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: sink)
+        emitted = []
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(a))
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_detected(shot)
+        payload, on_result = sink.sent[0]
+
+        assert payload["shot"]["delivery_status"] == "pending"
+        on_result([DeliveryReceipt("http://sim/hook", delivered=True, status_code=200)])
+        assert shot.delivery_status == "delivered"
+        assert emitted[-1][0] == "shot_delivery"
+        assert emitted[-1][1]["status"] == "delivered"
+
+    def test_merged_by_throttle(self, monkeypatch):
+        """A shot a later one replaced in the throttled queue should be marked merged."""
+        from openflight.webhooks import MERGED_ERROR, DeliveryReceipt
+
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_delivered(
+            shot, 3, [DeliveryReceipt("http://sim/hook", delivered=False, error=MERGED_ERROR)]
+        )
+
+        assert shot.delivery_status == "merged"
+
+    def test_any_failure_marks_failed(self, tmp_path, monkeypatch):
+        """One endpoint failing should mark the shot failed and log the receipts."""
+        from openflight.session_logger import SessionLogger
+        from openflight.webhooks import DeliveryReceipt
+
+        session_logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        session_logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: session_logger)
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_delivered(
+            shot,
+            3,
+            [
+                DeliveryReceipt("http://a/hook", delivered=True, status_code=200),
+                DeliveryReceipt("http://b/hook", delivered=False, error="timed out"),
+            ],
+        )
+
+        assert shot.delivery_status == "failed"
+        lines = session_logger.session_path.read_text().splitlines()
+        entries = [json.loads(line) for line in lines]
+        assert entries[-1]["type"] == "shot_delivery"
+        assert entries[-1]["shot_number"] == 3
+        assert entries[-1]["receipts"][1]["error"] == "timed out"
+
+    def test_no_webhooks_no_status(self):
+        """Without webhooks configured the shot has no delivery status."""
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_detected(shot)
+
+        assert shot_to_dict(shot)["delivery_status"] is None
+
+    def test_sim_failover_announced(self, monkeypatch):
+        """Switching sim endpoints should tell the UI which one is active."""
+        from openflight.failover import FailoverGroup
+        from openflight.webhooks import WebhookSink
+
+        group = FailoverGroup(["http://pc/shot", "http://nas/log"], on_change=server.on_sim_failover)
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: WebhookSink([], failover=group))
+        emitted = []
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(a))
+
+        group.record("http://pc/shot", delivered=False)
+
+        event, payload = emitted[-1]
+        assert event == "sim_endpoint"
+        assert (payload["previous"], payload["active"]) == ("http://pc/shot", "http://nas/log")
+        assert payload["failovers"] == 1
+        assert server.crash_snapshot()["sim_endpoints"]["active"] == "http://nas/log"
+
+
+class TestOutputChannels:
+    """Tests for the practice log and sim channels failing independently."""
+
+    class _BrokenLogger:
+        session_id = "s1"
+
+        def log_shot(self, **kwargs):
+            raise OSError("disk full")
+
+    class _BrokenSink:
+        def send(self, payload, on_result=None):
+            raise RuntimeError("queue gone")
+
+    def test_log_failure_still_reaches_sim(self, monkeypatch):
+        """A shot the session log can't store should still go to the sim."""
+        sink = TestShotDelivery._FakeSink()
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: sink)
+        monkeypatch.setattr(server, "get_session_logger", self._BrokenLogger)
+
+        server.on_shot_detected(shot_from_payload({"ball_speed_mph": 150}))
+
+        assert sink.sent[0][0]["shot"]["ball_speed_mph"] == 150
+
+    def test_sim_failure_still_logged_and_shown(self, tmp_path, monkeypatch):
+        """A sim channel failure shouldn't lose the practice log entry or the UI update."""
+        from openflight.session_logger import SessionLogger
+
+        session_logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        session_logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: session_logger)
+        monkeypatch.setattr(server, "get_webhook_sink", self._BrokenSink)
+        emitted = []
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(a))
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_detected(shot)
+
+        lines = session_logger.session_path.read_text().splitlines()
+        entries = [json.loads(line) for line in lines]
+        assert [e for e in entries if e["type"] == "shot_detected"]
+        assert emitted[0][0] == "shot"
+        assert shot.delivery_status == "failed"
+
+    def test_emit_failure_still_reaches_sim(self, monkeypatch):
+        """A UI emit failure shouldn't keep the shot from the sim."""
+        sink = TestShotDelivery._FakeSink()
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: sink)
+
+        def broken_emit(*args, **kwargs):
+            raise RuntimeError("socket closed")
+
+        monkeypatch.setattr(server.socketio, "emit", broken_emit)
+
+        server.on_shot_detected(shot_from_payload({"ball_speed_mph": 150}))
+
+        assert len(sink.sent) == 1
+
+    def test_sim_payload(self, monkeypatch):
+        """--webhook-payload sim should send only the sim fields."""
+        sink = TestShotDelivery._FakeSink()
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: sink)
+        monkeypatch.setattr(server, "webhook_payload", "sim")
+
+        server.on_shot_detected(shot_from_payload({"ball_speed_mph": 150}))
+
+        shot = sink.sent[0][0]["shot"]
+        assert shot["ball_speed_mph"] == 150
+        assert "peak_magnitude" not in shot
+
+
+class TestVoiceMemo:
+    """Tests for recording a voice memo for the last shot."""
+
+    def test_memo_logged_for_last_shot(self, api_client, tmp_path, monkeypatch):
+        """A memo should be recorded next to the log and linked to the last shot."""
+        import subprocess
+
+        from openflight.sessions import read_shots
+        from openflight.voice_memo import VoiceMemoRecorder
+
+        _, mock = api_client
+        session_logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        session_logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: session_logger)
+        done = threading.Event()
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: done.set())
+        recorder = VoiceMemoRecorder(run=lambda args, **k: subprocess.CompletedProcess(args, 0))
+        monkeypatch.setattr(server, "voice_memo", recorder)
+
+        shot = mock.simulate_shot(ball_speed=150.0)
+        shot.log_number = 1
+        session_logger.log_shot(150.0, None, None, 250.0, "driver", None, 1)
+        server.take_voice_memo()
+
+        assert done.wait(2.0)
+        memo = read_shots(str(session_logger.session_path))[0]["memo"]
+        assert memo.endswith("_shot001.wav")
+
+
+class TestConnectorFeed:
+    """Tests for the tablet app connector endpoints."""
+
+    def test_disabled(self, api_client):
+        """Without --connector the endpoints should 404."""
+        client, _ = api_client
+
+        assert client.get("/connector/status").status_code == 404
+
+    def test_shot_polling(self, api_client, monkeypatch):
+        """Published shots should show up on /connector/shot and /connector/shots."""
+        from openflight.connector import ConnectorFeed
+
+        client, mock = api_client
+        feed = ConnectorFeed()
+        monkeypatch.setattr(server, "connector_feed", feed)
+        monkeypatch.setattr(server, "_event_sinks", [feed.on_event])
+        assert client.get("/connector/shot").status_code == 204
+
+        shot = mock.simulate_shot(ball_speed=150.0)
+        server.publish_stream_event("shot", {"shot": shot_to_dict(shot), "stats": {}})
+
+        status = client.get("/connector/status").get_json()
+        assert status["Ready"] is True
+        assert status["LastShotNumber"] == 1
+        assert client.get("/connector/shot").get_json()["BallData"]["Speed"] == 150.0
+        assert client.get("/connector/shots?since=1").get_json()["Shots"] == []
+        assert client.get("/connector/shots?since=x").status_code == 400
+
+
+class TestPersonalRecords:
+    """Tests for announcing personal records."""
+
+    def test_new_record_published(self, api_client, monkeypatch):
+        """A record-breaking shot should go out as a personal_record event and show in stats."""
+        from openflight.records import PersonalRecords
+
+        client, _ = api_client
+        records = PersonalRecords()
+        records.check({"ball_speed_mph": 150.0, "club": "driver"})
+        events = []
+        monkeypatch.setattr(server, "personal_records", records)
+        monkeypatch.setattr(server, "_event_sinks", [lambda event, data: events.append(event)])
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: None)
+
+        server.announce_records({"ball_speed_mph": 155.0, "club": "driver"}, None)
+
+        assert events == ["personal_record"]
+        stats = client.get("/api/stats").get_json()
+        assert stats["personal_records"][0]["value"] == 155.0
+
+
+class TestControlMethods:
+    """Tests for the control socket methods."""
+
+    def test_status(self, monkeypatch):
+        """status should report the mode and the session's shot count."""
+        mock = MockLaunchMonitor()
+        mock.simulate_shot()
+        monkeypatch.setattr(server, "monitor", mock)
+        monkeypatch.setattr(server, "mock_mode", True)
+
+        status = server.control_status({})
+
+        assert status["mock"] is True
+        assert status["running"] is True
+        assert status["shots"] == 1
+        assert status["club"] == "driver"
+
+    def test_state(self, monkeypatch):
+        """state should dump readiness, the config hash and the connections."""
+        mock = MockLaunchMonitor()
+        monkeypatch.setattr(server, "monitor", mock)
+        monkeypatch.setattr(server, "mock_mode", True)
+        monkeypatch.setattr(server, "arming", None)
+        monkeypatch.setattr(server, "scheduled_disarm", None)
+
+        state = server.control_state({})
+
+        assert state["ready"] == "ready"
+        assert state["monitor"] == {"running": True}
+        assert state["config_hash"] == mock.config.fingerprint()
+        assert state["connections"] == {"webhooks": None, "relay": None, "bluetooth_clients": None}
+
+    def test_set_mode_restarts_monitor(self, monkeypatch):
+        """set_mode should restart the monitor with the startup arguments and the new mode."""
+        started = []
+        monkeypatch.setattr(server, "monitor", MockLaunchMonitor())
+        monkeypatch.setattr(server, "mock_mode", False)
+        monkeypatch.setattr(server, "monitor_mode", "streaming")
+        monkeypatch.setattr(server, "monitor_args", {"port": "/dev/ttyACM0", "mode": "streaming"})
+
+        def start_monitor(**kwargs):
+            started.append(kwargs)
+            server.monitor_mode = kwargs["mode"]
+
+        monkeypatch.setattr(server, "start_monitor", start_monitor)
+
+        server.control_set_mode({"mode": "rolling-buffer"})
+        server.control_set_mode({"mode": "streaming"})
+
+        assert started[0] == {"port": "/dev/ttyACM0", "mode": "rolling-buffer"}
+        assert len(started) == 2
+
+    def test_set_mode_same_mode_is_noop(self, monkeypatch):
+        """Asking for the current mode shouldn't restart the radar."""
+        started = []
+        monkeypatch.setattr(server, "monitor", MockLaunchMonitor())
+        monkeypatch.setattr(server, "mock_mode", False)
+        monkeypatch.setattr(server, "monitor_mode", "streaming")
+        monkeypatch.setattr(server, "start_monitor", lambda **kwargs: started.append(kwargs))
+
+        server.control_set_mode({"mode": "streaming"})
+
+        assert started == []
+
+    def test_set_mode_rejected(self, monkeypatch):
+        """Unknown modes, mock mode and streaming-only radars outside streaming should be refused."""
+        from openflight.control import ControlError
+
+        monkeypatch.setattr(server, "mock_mode", False)
+        monkeypatch.setattr(server, "monitor_args", {"radar_model": "iwr6843"})
+        with pytest.raises(ValueError):
+            server.control_set_mode({"mode": "turbo"})
+        with pytest.raises(ControlError):
+            server.control_set_mode({"mode": "rolling-buffer"})
+        monkeypatch.setattr(server, "monitor_args", {"radar_model": "esp-bridge"})
+        with pytest.raises(ControlError, match="esp-bridge"):
+            server.control_set_mode({"mode": "rolling-buffer"})
+        monkeypatch.setattr(server, "mock_mode", True)
+        with pytest.raises(ControlError):
+            server.control_set_mode({"mode": "streaming"})
+
+
+class TestServiceCommands:
+    """Tests for the Windows service's pause and continue commands."""
+
+    def test_pause_releases_radar_and_continue_reconnects(self, monkeypatch):
+        """Pause should stop the monitor; continue should start it with the startup arguments."""
+        started, stopped = [], []
+        monkeypatch.setattr(server, "monitor", MockLaunchMonitor())
+        monkeypatch.setattr(server, "monitor_args", {"port": "COM3", "mode": "streaming"})
+
+        def stop_monitor():
+            stopped.append(True)
+            server.monitor = None
+
+        monkeypatch.setattr(server, "stop_monitor", stop_monitor)
+        monkeypatch.setattr(server, "start_monitor", lambda **kwargs: started.append(kwargs))
+
+        server.service_pause()
+        server.service_pause()
+        server.service_continue()
+
+        assert stopped == [True]
+        assert started == [{"port": "COM3", "mode": "streaming"}]
+
+    def test_continue_while_running_is_noop(self, monkeypatch):
+        """Continue without a pause shouldn't restart the radar."""
+        started = []
+        monkeypatch.setattr(server, "monitor", MockLaunchMonitor())
+        monkeypatch.setattr(server, "monitor_args", {"mode": "streaming"})
+        monkeypatch.setattr(server, "start_monitor", lambda **kwargs: started.append(kwargs))
+
+        server.service_continue()
+
+        assert started == []
+
+
+class TestMenuBarStatus:
+    """Tests for the status pushed to menu bar widgets."""
+
+    def test_running_mock(self, monkeypatch):
+        """A running mock monitor should be ready, with its shots and club."""
+        mock = MockLaunchMonitor()
+        mock.simulate_shot()
+        monkeypatch.setattr(server, "monitor", mock)
+        monkeypatch.setattr(server, "mock_mode", True)
+        monkeypatch.setattr(server, "arming", None)
+        monkeypatch.setattr(server, "scheduled_disarm", None)
+
+        status = server.menu_bar_status()
+
+        assert status["state"] == "ready"
+        assert status["shots"] == 1
+        assert status["club"] == "driver"
+        assert status["connection"] == {"radar": "mock", "radar_data_age_sec": None}
+
+    def test_stopped(self, monkeypatch):
+        """With the radar released the state should be stopped and the radar down."""
+        monkeypatch.setattr(server, "monitor", None)
+
+        status = server.menu_bar_status()
+
+        assert status["state"] == "stopped"
+        assert status["club"] is None
+        assert status["connection"]["radar"] == "down"
+
+    def test_disarmed(self, monkeypatch):
+        """A schedule disarm should show as disarmed."""
+        monkeypatch.setattr(server, "monitor", MockLaunchMonitor())
+        monkeypatch.setattr(server, "scheduled_disarm", TestScheduledDisarm()._disarmed())
+
+        assert server.menu_bar_status()["state"] == "disarmed"
+
+
+class TestResumeSession:
+    """Tests for continuing a session after a supervised restart."""
+
+    def test_shots_restored(self, tmp_path, monkeypatch):
+        """A resumed session's shots should be back on the monitor with their numbers."""
+        session_logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        session_logger.start_session(mode="mock")
+        monkeypatch.setattr(server, "get_session_logger", lambda: session_logger)
+        server.record_practice_shot(shot_from_payload({"ball_speed_mph": 150, "club": "7-iron"}))
+        path = session_logger.session_path
+        session_logger.end_session()
+
+        mock = MockLaunchMonitor()
+        monkeypatch.setattr(server, "monitor", mock)
+        server.restore_session_shots(path)
+
+        shots = mock.get_shots()
+        assert [s.ball_speed_mph for s in shots] == [150]
+        assert shots[0].log_number == 1
+        assert mock.get_club() == ClubType.IRON_7
+
+
+class TestServiceFlag:
+    """Tests for running under openflight-service."""
+
+    def test_service_with_supervise_rejected(self, monkeypatch):
+        """--service --supervise should be an error, not start a supervisor."""
+        monkeypatch.setattr(sys, "argv", ["openflight-server", "--service", "--supervise"])
+        monkeypatch.setattr(
+            server, "supervise", lambda *args, **kwargs: pytest.fail("supervisor started")
+        )
+
+        with pytest.raises(SystemExit) as info:
+            server.main()
+
+        assert info.value.code == 2
+
+
+class TestStreamSubscriber:
+    """Tests for per-client filtering of /api/stream events."""
+
+    def test_readings_only_when_requested(self):
+        """Clients that didn't ask for readings should only get other events."""
+        subscriber = server.StreamSubscriber(include_readings=False)
+
+        subscriber.offer("reading", {"speed": 10.0})
+        subscriber.offer("shot", {"shot": {}})
+
+        assert subscriber.queue.get_nowait()["event"] == "shot"
+        assert subscriber.queue.empty()
+
+    def test_readings_rate_limited(self, monkeypatch):
+        """Readings beyond the client's rate are dropped; shots never are."""
+        subscriber = server.StreamSubscriber(include_readings=True, reading_hz=20)
+        monkeypatch.setattr(server.RateLimiter, "allow", lambda self: False)
+
+        subscriber.offer("reading", {"speed": 10.0})
+        subscriber.offer("shot", {"shot": {}})
+
+        assert [subscriber.queue.get_nowait()["event"]] == ["shot"]
+
+    def test_backed_up_client_keeps_room_for_shots(self):
+        """A slow client's queue should stop taking readings before it is full."""
+        subscriber = server.StreamSubscriber(include_readings=True, reading_hz=0)
+
+        for _ in range(server.StreamSubscriber.QUEUE_SIZE):
+            subscriber.offer("reading", {"speed": 10.0})
+        subscriber.offer("shot", {"shot": {}})
+
+        assert subscriber.queue.qsize() == server.StreamSubscriber.QUEUE_SIZE * 3 // 4 + 1
+
+    def test_events_forwarded_to_relay(self, monkeypatch):
+        """Stream events should also go to the remote viewing relay."""
+        sent = []
+
+        class FakeRelay:
+            def send(self, event, data):
+                sent.append((event, data))
+
+        monkeypatch.setattr(server, "get_relay", FakeRelay)
+
+        server.publish_stream_event("shot", {"shot": {}})
+
+        assert sent == [("shot", {"shot": {}})]
+
+    def test_events_forwarded_to_sinks(self, monkeypatch):
+        """In-process event sinks (e.g. --feedback) should get every event."""
+        sent = []
+        monkeypatch.setattr(server, "_event_sinks", [])
+        monkeypatch.setattr(server, "get_relay", lambda: None)
+
+        server.add_event_sink(lambda event, data: sent.append((event, data)))
+        server.publish_stream_event("shot", {"shot": {}})
+
+        assert sent == [("shot", {"shot": {}})]
+
+    def test_invalid_rate_rejected(self, api_client):
+        """A bad reading_hz should be a 400, not a stream."""
+        client, _ = api_client
+
+        response = client.get("/api/stream?readings=1&reading_hz=fast")
+
+        assert response.status_code == 400
+
+    def test_debug_readings_per_socket_rate(self, monkeypatch):
+        """Each Socket.IO client gets debug readings at its own rate."""
+        from openflight.ops243 import Direction, SpeedReading
+
+        emitted = []
+        monkeypatch.setattr(server, "debug_mode", True)
+        monkeypatch.setattr(server, "debug_log_file", None)
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(k["to"]))
+        monkeypatch.setattr(
+            server, "_socket_reading_limits", {"fast": RateLimiter(0), "slow": RateLimiter(1)}
+        )
+
+        for _ in range(3):
+            server.on_live_reading(SpeedReading(speed=50.0, direction=Direction.OUTBOUND))
+
+        assert emitted.count("fast") == 3
+        assert emitted.count("slow") == 1
+
+    def test_live_readings_captured(self, monkeypatch, tmp_path):
+        """With --capture, every reading should go into the capture's radar stream."""
+        from openflight.capture import CaptureWriter, read_capture
+        from openflight.ops243 import Direction, SpeedReading
+
+        writer = CaptureWriter(str(tmp_path / "session.ofcap"))
+        monkeypatch.setattr(server, "sensor_capture", writer)
+        monkeypatch.setattr(server, "debug_mode", False)
+
+        server.on_live_reading(SpeedReading(50.0, Direction.OUTBOUND, 900.0, timestamp=3.0))
+        writer.close()
+
+        records = list(read_capture(writer.path)[1])
+        assert [(r.stream, r.timestamp) for r in records] == [("radar", 3.0)]
+
+
+class TestProfileApi:
+    """Tests for saving calibration profiles via the API."""
+
+    def test_save_current_thresholds(self, api_client, tmp_path, monkeypatch):
+        """Saving should capture thresholds tuned via /api/config."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "profile_dir", tmp_path)
+        monkeypatch.setattr(server, "active_profile", None)
+        client.put("/api/config", json={"min_shot_magnitude": 80})
+
+        response = client.post("/api/profiles", json={"name": "garage-net"})
+
+        assert response.status_code == 200
+        saved = json.loads((tmp_path / "garage-net.json").read_text())
+        assert saved["monitor"]["min_shot_magnitude"] == 80
+        listing = client.get("/api/profiles").get_json()
+        assert listing["profiles"] == ["garage-net"]
+        assert listing["active"] == "garage-net"
+
+    def test_invalid_name(self, api_client, tmp_path, monkeypatch):
+        """Unsafe profile names should be rejected."""
+        client, _ = api_client
+        monkeypatch.setattr(server, "profile_dir", tmp_path)
+
+        response = client.post("/api/profiles", json={"name": "../oops"})
+
+        assert response.status_code == 400
+
+
+class TestScheduledDisarm:
+    """Tests for keeping shots from the sim outside the arm schedule."""
+
+    def _disarmed(self):
+        disarm = ScheduledDisarm(ArmSchedule(idle_disarm_min=1.0))
+        disarm.on_shot(mono=0.0)
+        disarm.check(mono=120.0)
+        return disarm
+
+    def test_disarmed_shot_not_sent(self, api_client, monkeypatch):
+        """A shot while disarmed should reach the UI but not the sim."""
+        _, mock = api_client
+        sink = TestShotDelivery._FakeSink()
+        monkeypatch.setattr(server, "get_webhook_sink", lambda: sink)
+        monkeypatch.setattr(server, "scheduled_disarm", self._disarmed())
+        emitted = []
+        monkeypatch.setattr(server.socketio, "emit", lambda *a, **k: emitted.append(a))
+        shot = shot_from_payload({"ball_speed_mph": 150})
+
+        server.on_shot_detected(shot)
+
+        assert sink.sent == []
+        assert shot.delivery_status is None
+        assert emitted[-1][0] == "shot"
+
+    def test_manual_arm_rearms(self, api_client, monkeypatch):
+        """POST /api/arm should lift an idle disarm and send ready."""
+        client, _ = api_client
+        disarm = self._disarmed()
+        monkeypatch.setattr(server, "scheduled_disarm", disarm)
+        monkeypatch.setattr(server, "arming", None)
+        events = []
+        monkeypatch.setattr(server, "publish_stream_event", lambda e, d: events.append(e))
+
+        client.post("/api/arm")
+
+        assert not disarm.disarmed
+        assert events == ["ready"]
+
+    def test_ready_suppressed_while_disarmed(self, monkeypatch):
+        """Ball-present "ready" events should not go out while disarmed."""
+        monkeypatch.setattr(server, "scheduled_disarm", self._disarmed())
+        events = []
+        monkeypatch.setattr(server, "publish_stream_event", lambda e, d: events.append(e))
+
+        server.publish_arm_event("ready")
+
+        assert events == []
+
+    def test_profile_reload_applies_schedule(self, api_client, monkeypatch):
+        """An edited profile schedule should start scheduled disarming."""
+        monkeypatch.setattr(server, "scheduled_disarm", None)
+        monkeypatch.setattr(server, "schedule_flags", {})
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: None)
+
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(name="garage-net", schedule=ArmSchedule(idle_disarm_min=30.0))
+        server.apply_profile_reload(old, new)
+
+        try:
+            assert server.scheduled_disarm.schedule.idle_disarm_min == 30.0
+        finally:
+            server.scheduled_disarm.stop()
+
+
+class TestProfileReload:
+    """Tests for applying an edited profile while running."""
+
+    def test_reload_applies_changed_sections(self, api_client, monkeypatch):
+        """Changed thresholds and offset should apply; untouched mounting should not."""
+        _, mock = api_client
+        flag_geometry = MountingGeometry(lateral_offset_m=0.4)
+        monkeypatch.setattr(server, "mounting_geometry", flag_geometry)
+        monkeypatch.setattr(server, "launch_angle_offset_deg", 0.0)
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        events = []
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: events.append((e, d)))
+
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(
+            name="garage-net",
+            launch_angle_offset_deg=1.5,
+            monitor=MonitorConfig(min_shot_magnitude=80),
+        )
+        server.apply_profile_reload(old, new)
+
+        assert mock.config.min_shot_magnitude == 80
+        assert server.launch_angle_offset_deg == 1.5
+        assert server.mounting_geometry is flag_geometry
+        name, event = events[-1]
+        assert name == "config_reloaded"
+        assert event["changes"]["launch_angle_offset_deg"] == {"old": 0.0, "new": 1.5}
+
+    def test_reload_keeps_sensitivity(self, api_client, monkeypatch):
+        """A --sensitivity preset should still override an edited profile's thresholds."""
+        _, mock = api_client
+        monkeypatch.setattr(server, "sensitivity", "high")
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: None)
+
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(
+            name="garage-net", monitor=MonitorConfig(min_shot_magnitude=80, shot_timeout_sec=0.4)
+        )
+        server.apply_profile_reload(old, new)
+
+        assert mock.config.min_shot_magnitude == 50
+        assert mock.config.shot_timeout_sec == 0.4
+
+    def test_reload_keeps_ball_type(self, api_client, monkeypatch):
+        """A --ball-type should still adjust an edited profile's thresholds."""
+        _, mock = api_client
+        monkeypatch.setattr(server, "ball_type", "foam")
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: None)
+
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(name="garage-net", monitor=MonitorConfig(min_shot_magnitude=80))
+        server.apply_profile_reload(old, new)
+
+        assert mock.config.min_shot_magnitude == 20
+        assert mock.config.smash_factor_min == 1.0
+
+    def test_state_config_hash_follows_config(self, api_client):
+        """GET /api/state's config hash should change with the live thresholds."""
+        client, _ = api_client
+        before = client.get("/api/state").get_json()
+
+        client.put("/api/config", json={"shot_timeout_sec": 0.8})
+        after = client.get("/api/state").get_json()
+
+        assert before["config_hash"] != after["config_hash"]
+        assert after["config"]["shot_timeout_sec"] == 0.8
+
+    def test_reload_applies_mode_shot_timing(self, api_client, monkeypatch):
+        """An edited profile's shot timing for the running mode should replace its thresholds."""
+        _, mock = api_client
+        monkeypatch.setattr(server, "loaded_profile", None)
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: None)
+
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(
+            name="garage-net",
+            shot_timing=ShotTiming.from_dict(
+                {"modes": {"mock": {"shot_timeout_sec": 0.7}, "swing-speed": {"shot_timeout_sec": 0.2}}}
+            ),
+        )
+        server.apply_profile_reload(old, new)
+
+        assert mock.config.shot_timeout_sec == 0.7
+        assert server.loaded_profile is new
+
+    def test_reload_waits_for_shot_in_progress(self, monkeypatch):
+        """A profile edited mid-shot should stay pending until the shot ends, however long."""
+        from openflight import launch_monitor
+        from openflight.ops243 import Direction, SpeedReading
+
+        monitor = LaunchMonitor(use_iq_streaming=False)
+        monkeypatch.setattr(server, "monitor", monitor)
+        monkeypatch.setattr(server, "loaded_profile", None)
+        monkeypatch.setattr(server, "get_session_logger", lambda: None)
+        monkeypatch.setattr(server.socketio, "emit", lambda e, d=None: None)
+
+        def reading(t, speed):
+            monkeypatch.setattr(launch_monitor.time, "monotonic", lambda: t)
+            monitor._on_reading(
+                SpeedReading(speed=speed, direction=Direction.OUTBOUND, magnitude=2000, timestamp=t)
+            )
+
+        reading(10.0, 150)
+        old = CalibrationProfile(name="garage-net")
+        new = CalibrationProfile(name="garage-net", monitor=MonitorConfig(min_shot_magnitude=80))
+        server.apply_profile_reload(old, new)
+
+        # Still busy well past the 5 s a reload used to wait before forcing itself
+        for t in (10.2, 10.4, 16.0):
+            monkeypatch.setattr(launch_monitor.time, "monotonic", lambda t=t: t)
+            assert not monitor.is_idle
+            assert monitor.config.min_shot_magnitude != 80
+            assert server.loaded_profile is None
+
+        # The next reading after the gap ends the shot, then the profile applies
+        reading(17.0, 150)
+        assert monitor.config.min_shot_magnitude == 80
+        assert server.loaded_profile is new
@@ -0,0 +1,144 @@
+"""Tests for session_diff module."""
+
+import json
+
+import pytest
+
+from openflight.launch_monitor import ClubType
+from openflight.session_diff import (
+    MetricDiff,
+    MetricStats,
+    diff_sessions,
+    format_diff,
+    main,
+    profile_session,
+)
+
+
+def _write_session(path, shots, triggers=(), entry_type="trigger_diagnostic"):
+    """Write a session log with shot_detected entries and accepted/rejected triggers."""
+    lines = [{"type": "session_start", "session_id": path.stem, "tags": {}}]
+    for number, shot in enumerate(shots, start=1):
+        lines.append({"type": "shot_detected", "shot_number": number, **shot})
+    for accepted in triggers:
+        lines.append({"type": entry_type, "accepted": accepted})
+    path.write_text("".join(json.dumps(line) + "\n" for line in lines))
+    return str(path)
+
+
+class TestProfileSession:
+    """Tests for reading a session into comparable numbers."""
+
+    def test_metrics_and_rates(self, tmp_path):
+        """Metric distributions and detection rates should come from the shots."""
+        path = _write_session(
+            tmp_path / "a.jsonl",
+            [
+                {"club": "driver", "ball_speed_mph": 150.0, "club_speed_mph": 103.0},
+                {"club": "driver", "ball_speed_mph": 154.0, "club_speed_mph": None},
+                {"club": "7-iron", "ball_speed_mph": 120.0, "spin_rpm": 6500},
+            ],
+            triggers=[True, True, True, False],
+        )
+        profile = profile_session(path)
+
+        assert profile.shots == 3
+        assert profile.clubs == {"driver": 2, "7-iron": 1}
+        assert profile.metrics["ball_speed"].count == 3
+        assert profile.metrics["ball_speed"].median == 150.0
+        assert profile.metrics["spin"].count == 1
+        assert profile.metrics["launch"] is None
+        assert profile.rates["triggers accepted"].value == 0.75
+        assert profile.rates["club speed detected"].hits == 1
+        assert profile.rates["spin detected"].value == pytest.approx(1 / 3)
+
+    def test_club_filter_and_mulligans(self, tmp_path):
+        """Only the chosen club's shots should count, without mulligans."""
+        path = tmp_path / "a.jsonl"
+        _write_session(
+            path,
+            [
+                {"club": "driver", "ball_speed_mph": 150.0},
+                {"club": "driver", "ball_speed_mph": 120.0},
+                {"club": "7-iron", "ball_speed_mph": 118.0},
+            ],
+        )
+        with open(path, "a", encoding="utf-8") as f:
+            f.write(json.dumps({"type": "shot_mulligan", "shot_number": 2}) + "\n")
+
+        profile = profile_session(str(path), ClubType.DRIVER)
+        assert profile.shots == 1
+        assert profile.metrics["ball_speed"].mean == 150.0
+
+    def test_trigger_events_without_diagnostics(self, tmp_path):
+        """Older logs with only trigger_event entries should still give a trigger rate."""
+        path = _write_session(
+            tmp_path / "a.jsonl", [], triggers=[True, False], entry_type="trigger_event"
+        )
+        assert profile_session(path).rates["triggers accepted"].value == 0.5
+
+
+class TestMetricDiff:
+    """Tests for comparing one metric."""
+
+    def test_welch_t(self):
+        """t should be the change in mean over its standard error."""
+        diff = MetricDiff(
+            metric="ball_speed",
+            unit="mph",
+            a=MetricStats.of([148.0, 150.0, 152.0]),
+            b=MetricStats.of([153.0, 155.0, 157.0]),
+        )
+        assert diff.delta == pytest.approx(5.0)
+        assert diff.t == pytest.approx(5.0 / ((4 / 3 + 4 / 3) ** 0.5))
+        assert diff.significant
+
+    def test_missing_side(self):
+        """A metric only one session has should have no delta or t."""
+        diff = MetricDiff(metric="spin", unit="rpm", a=None, b=MetricStats.of([3000.0]))
+        assert diff.delta is None
+        assert diff.t is None
+        assert not diff.significant
+
+
+class TestDiffSessions:
+    """Tests for comparing two sessions."""
+
+    def test_diff(self, tmp_path):
+        """Deltas should be B - A, leaving out metrics neither session has."""
+        a = _write_session(
+            tmp_path / "a.jsonl",
+            [{"ball_speed_mph": v} for v in (148.0, 149.0, 150.0)],
+            triggers=[True, True, True, False],
+        )
+        b = _write_session(
+            tmp_path / "b.jsonl",
+            [{"ball_speed_mph": v} for v in (152.0, 153.0, 154.0)],
+            triggers=[True, True, True],
+        )
+        diff = diff_sessions(a, b)
+
+        assert [m.metric for m in diff.metrics] == ["ball_speed"]
+        assert diff.metrics[0].delta == pytest.approx(4.0)
+        triggers = next(r for r in diff.rates if r.name == "triggers accepted")
+        assert triggers.delta == pytest.approx(0.25)
+
+        text = format_diff(diff)
+        assert "ball_speed" in text
+        assert "+25.0 pt" in text
+        assert json.loads(json.dumps(diff.to_dict()))["metrics"][0]["significant"]
+
+    def test_cli_json(self, tmp_path, capsys):
+        """--json should print the comparison as JSON."""
+        a = _write_session(tmp_path / "a.jsonl", [{"ball_speed_mph": 150.0}])
+        b = _write_session(tmp_path / "b.jsonl", [{"ball_speed_mph": 155.0}])
+
+        assert main([a, b, "--json"]) == 0
+        data = json.loads(capsys.readouterr().out)
+        assert data["metrics"][0]["delta"] == pytest.approx(5.0)
+
+    def test_cli_missing_file(self, tmp_path, capsys):
+        """A missing session log should be reported, not raise."""
+        a = _write_session(tmp_path / "a.jsonl", [])
+        assert main([a, str(tmp_path / "missing.jsonl")]) == 1
+        assert "Error" in capsys.readouterr().out
@@ -4,7 +4,7 @@ import json
 import pytest
 from pathlib import Path
 
-from openflight.session_logger import SessionLogger
+from openflight.session_logger import SessionLogger, parse_tag
 
 
 class TestLogTriggerDiagnostic:
@@ -153,3 +153,152 @@ class TestLogTriggerDiagnostic:
 
         assert entry["all_outbound_speeds"] == []
         assert entry["all_inbound_speeds"] == []
+
+
+class TestLogMonitorConfig:
+    """Tests for monitor threshold logging."""
+
+    def test_monitor_config_entry(self, tmp_path):
+        """Monitor config should be written with its source."""
+        logger = SessionLogger(log_dir=tmp_path, enabled=True)
+        logger.start_session(mode="streaming")
+
+        logger.log_monitor_config({"min_shot_magnitude": 80}, source="api")
+
+        entry = json.loads(logger.session_path.read_text().strip().split('\n')[-1])
+        assert entry["type"] == "monitor_config"
+        assert entry["source"] == "api"
+        assert entry["config"]["min_shot_magnitude"] == 80
+
+    def test_disabled_logger_writes_nothing(self, tmp_path):
+        """Disabled logger should not create a log file."""
+        logger = SessionLogger(log_dir=tmp_path, enabled=False)
+
+        logger.log_monitor_config({"min_shot_magnitude": 80})
+
+        assert list(tmp_path.iterdir()) == []
+
+
+class TestSessionTags:
+    """Tests for session tags in the log."""
+
+    def test_tags_in_header(self, tmp_path):
+        """Startup tags and the location should be written to session_start."""
+        logger = SessionLogger(log_dir=tmp_path, location="garage", tags={"ball_type": "pro-v1"})
+        logger.start_session(mode="streaming")
+
+        entry = json.loads(logger.session_path.read_text().split('\n')[0])
+        assert entry["type"] == "session_start"
+        assert entry["tags"] == {"location": "garage", "ball_type": "pro-v1"}
+
+    def test_update_tags_mid_session(self, tmp_path):
+        """Tag changes should be logged with the full set of tags."""
+        logger = SessionLogger(log_dir=tmp_path, tags={"weather": "calm"})
+        logger.start_session(mode="streaming")
+
+        tags = logger.update_tags({"weather": "windy", "notes": "new driver"})
+        logger.update_tags({"notes": None})
+
+        entries = [json.loads(line) for line in logger.session_path.read_text().split('\n')[:-1]]
+        assert tags["weather"] == "windy"
+        assert entries[1]["type"] == "session_tags"
+        assert entries[1]["changed"] == ["notes", "weather"]
+        assert entries[2]["tags"] == {"location": "range", "weather": "windy"}
+
+    def test_invalid_tags_rejected(self, tmp_path):
+        """Non-object bodies and non-text values should raise ValueError."""
+        logger = SessionLogger(log_dir=tmp_path)
+
+        with pytest.raises(ValueError):
+            logger.update_tags(["windy"])
+        with pytest.raises(ValueError):
+            logger.update_tags({"weather": {"wind": 10}})
+        assert logger.tags == {"location": "range"}
+
+    def test_parse_tag(self):
+        """KEY=VALUE should split on the first '='."""
+        assert parse_tag("notes=a=b") == ("notes", "a=b")
+        with pytest.raises(ValueError):
+            parse_tag("windy")
+
+
+class TestSessionClock:
+    """Tests for the clock mapping in the log."""
+
+    def test_clock_in_header(self, tmp_path):
+        """session_start should record the session's monotonic-to-wall mapping."""
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="streaming")
+
+        entry = json.loads(logger.session_path.read_text().split('\n')[0])
+        assert set(entry["clock"]) == {"monotonic_epoch", "wall_epoch"}
+
+
+def _log_shot(logger, ball_speed):
+    logger.log_shot(
+        ball_speed_mph=ball_speed,
+        club_speed_mph=None,
+        smash_factor=None,
+        estimated_carry_yards=200.0,
+        club="driver",
+        peak_magnitude=None,
+        readings_count=1,
+    )
+
+
+class TestResumeSession:
+    """Tests for continuing a session log after a restart."""
+
+    def test_resume_appends_and_keeps_numbering(self, tmp_path):
+        """A resumed session should append to the same file and continue shot numbers."""
+        first = SessionLogger(log_dir=tmp_path, tags={"ball_type": "range"})
+        session_id = first.start_session(mode="streaming")
+        _log_shot(first, 150.0)
+        first.update_tags({"weather": "windy"})
+        path = first.session_path
+        first._session_file.close()  # Crash: no session_end
+
+        second = SessionLogger(log_dir=tmp_path)
+        resumed_id = second.start_session(mode="streaming", resume=path)
+        _log_shot(second, 140.0)
+
+        entries = [json.loads(line) for line in path.read_text().splitlines()]
+        assert resumed_id == session_id
+        assert second.session_path == path
+        assert second.tags["weather"] == "windy"
+        assert [e["type"] for e in entries].count("session_resume") == 1
+        assert [e["shot_number"] for e in entries if e["type"] == "shot_detected"] == [1, 2]
+
+    def test_unreadable_resume_starts_new_session(self, tmp_path):
+        """A missing or foreign file should start a fresh session instead."""
+        logger = SessionLogger(log_dir=tmp_path)
+
+        logger.start_session(mode="streaming", resume=tmp_path / "missing.jsonl")
+
+        assert logger.session_path != tmp_path / "missing.jsonl"
+        entry = json.loads(logger.session_path.read_text().split('\n')[0])
+        assert entry["type"] == "session_start"
+
+
+class TestRollover:
+    """Tests for ending a session and starting the next in its place."""
+
+    def test_rollover_starts_new_session(self, tmp_path):
+        """The next session gets its own file, numbering and the changed tags."""
+        logger = SessionLogger(log_dir=tmp_path, tags={"player": "sam"})
+        logger.start_session(mode="streaming", radar_port="/dev/ttyACM0")
+        _log_shot(logger, 150.0)
+        first = logger.session_path
+
+        logger.rollover("player", {"player": "alex"})
+        _log_shot(logger, 140.0)
+
+        ended = [json.loads(line) for line in first.read_text().splitlines()]
+        assert ended[-1]["type"] == "session_end"
+        assert ended[-1]["reason"] == "player"
+        assert ended[-1]["tags"]["player"] == "sam"
+        entries = [json.loads(line) for line in logger.session_path.read_text().splitlines()]
+        assert logger.session_path != first
+        assert entries[0]["tags"]["player"] == "alex"
+        assert entries[0]["radar_port"] == "/dev/ttyACM0"
+        assert [e["shot_number"] for e in entries if e["type"] == "shot_detected"] == [1]
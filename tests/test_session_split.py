@@ -0,0 +1,77 @@
+"""Tests for splitting a long-running server's log into sessions."""
+
+import pytest
+
+from openflight.session_split import IDLE, PLAYER, SessionSplitter
+
+
+@pytest.fixture
+def splits():
+    return []
+
+
+def _splitter(splits, **kwargs):
+    return SessionSplitter(on_split=lambda reason, tags: splits.append((reason, tags)), **kwargs)
+
+
+class TestIdle:
+    """Tests for new sessions after idle time."""
+
+    def test_split_after_idle(self, splits):
+        splitter = _splitter(splits, idle_min=30)
+        splitter.on_shot(mono=0.0)
+
+        assert not splitter.check(mono=29 * 60)
+        assert splitter.check(mono=30 * 60)
+        assert splits == [(IDLE, {"player": None})]
+
+    def test_no_split_without_shots(self, splits):
+        """The new session waits for a shot: it isn't split again while idle."""
+        splitter = _splitter(splits, idle_min=30)
+        splitter.on_shot(mono=0.0)
+        splitter.check(mono=30 * 60)
+
+        assert not splitter.check(mono=120 * 60)
+        assert len(splits) == 1
+
+    def test_idle_off(self, splits):
+        splitter = _splitter(splits, on_player=True)
+        splitter.on_shot(mono=0.0)
+
+        assert not splitter.check(mono=24 * 3600)
+        assert splits == []
+
+    def test_invalid_idle(self):
+        with pytest.raises(ValueError, match="positive"):
+            SessionSplitter(idle_min=0)
+
+
+class TestPlayer:
+    """Tests for new sessions on a player switch."""
+
+    def test_new_player(self, splits):
+        splitter = _splitter(splits, on_player=True)
+        splitter.on_shot()
+
+        assert splitter.player_changed("sam", "alex")
+        assert splitter.player_changed(None, "alex")
+        assert not splitter.player_changed("sam", "sam")
+        assert not splitter.player_changed("sam", None)  # Clearing the player
+
+    def test_no_shots_yet(self):
+        """Picking a player before hitting names the session rather than splitting it."""
+        assert not SessionSplitter(on_player=True).player_changed("sam", "alex")
+
+    def test_player_off(self):
+        splitter = SessionSplitter(idle_min=30)
+        splitter.on_shot()
+        assert not splitter.player_changed("sam", "alex")
+
+    def test_split_resets_shots(self, splits):
+        splitter = _splitter(splits, on_player=True)
+        splitter.on_shot()
+        splitter.split(PLAYER, {"player": "alex"})
+
+        assert splits == [(PLAYER, {"player": "alex"})]
+        assert not splitter.player_changed("alex", "kim")
+        assert splitter.to_dict()["splits"] == 1
@@ -0,0 +1,108 @@
+"""Tests for sessions module."""
+
+import json
+
+from openflight.session_logger import SessionLogger
+from openflight.sessions import matches_tags, read_shots, summarize_session
+
+
+def _write_log(path, entries):
+    path.write_text("".join(json.dumps(e) + "\n" for e in entries))
+    return str(path)
+
+
+class TestSummarizeSession:
+    """Tests for reading a session's tags and shot count."""
+
+    def test_final_tags_and_shots(self, tmp_path):
+        """Mid-session tag changes should win over the header."""
+        logger = SessionLogger(log_dir=tmp_path, location="range", tags={"weather": "calm"})
+        logger.start_session(mode="streaming")
+        logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+        logger.update_tags({"weather": "windy"})
+        logger.log_shot(148.0, 103.0, 1.44, 246.0, "driver", 880.0, 5)
+        logger.end_session()
+
+        summary = summarize_session(str(logger.session_path))
+
+        assert summary.session_id == logger.session_id
+        assert summary.shots == 2
+        assert summary.tags == {"location": "range", "weather": "windy"}
+
+    def test_untagged_log_uses_file_name(self, tmp_path):
+        """Logs without tags should take the location from the file name."""
+        path = _write_log(
+            tmp_path / "session_20250601_100000_home_net.jsonl",
+            [{"type": "session_start", "session_id": "20250601_100000"}],
+        )
+
+        assert summarize_session(path).tags == {"location": "home_net"}
+
+
+class TestReadShots:
+    """Tests for reading a session's shots."""
+
+    def test_tags_per_shot_and_mulligans(self, tmp_path):
+        """Each shot keeps the tags it was hit with; mulligans are left out."""
+        logger = SessionLogger(log_dir=tmp_path, tags={"player": "Sam"})
+        logger.start_session(mode="streaming")
+        logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+        logger.update_tags({"player": "Alex"})
+        logger.log_shot(120.0, 90.0, 1.33, 180.0, "driver", 800.0, 5)
+        logger.log_mulligan(2)
+        logger.log_shot(148.0, 103.0, 1.44, 246.0, "driver", 880.0, 5)
+
+        shots = read_shots(str(logger.session_path))
+
+        assert [s["ball_speed_mph"] for s in shots] == [150.0, 148.0]
+        assert [s["tags"]["player"] for s in shots] == ["Sam", "Alex"]
+        assert summarize_session(str(logger.session_path)).shots == 2
+
+    def test_voice_memo(self, tmp_path):
+        """A shot's voice memo should be read with it, as a path next to the log."""
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="streaming")
+        logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+        logger.log_shot(148.0, 103.0, 1.44, 246.0, "driver", 880.0, 5)
+        logger.log_voice_memo(2, "memo.wav", 5.0)
+
+        shots = read_shots(str(logger.session_path))
+
+        assert "memo" not in shots[0]
+        assert shots[1]["memo"] == str(tmp_path / "memo.wav")
+
+    def test_deleted_and_edited_shots(self, tmp_path):
+        """Deleted shots are left out and corrections applied, with the edits listed."""
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="streaming")
+        logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+        logger.log_shot(80.0, 90.0, 0.89, 90.0, "driver", 800.0, 5)
+        logger.log_shot(120.0, 87.0, 1.38, 165.0, "driver", 880.0, 5)
+        logger.log_shot_deleted(2, reason="shank")
+        logger.log_shot_edited(
+            3,
+            {"club": "7-iron", "estimated_carry_yards": 160.0},
+            {"club": "driver", "estimated_carry_yards": 165.0},
+            reason="forgot to change clubs",
+        )
+
+        shots = read_shots(str(logger.session_path))
+
+        assert [s["shot_number"] for s in shots] == [1, 3]
+        assert shots[1]["club"] == "7-iron"
+        assert shots[1]["estimated_carry_yards"] == 160.0
+        assert shots[1]["edits"][0]["previous"]["club"] == "driver"
+        assert "edits" not in shots[0]
+        assert summarize_session(str(logger.session_path)).shots == 2
+
+
+class TestMatchesTags:
+    """Tests for tag filters."""
+
+    def test_substring_case_insensitive(self):
+        """Filters should match any part of the tag, ignoring case."""
+        tags = {"ball_type": "Pro V1", "notes": "testing new shaft"}
+
+        assert matches_tags(tags, {"ball_type": "pro"})
+        assert matches_tags(tags, {"notes": "shaft", "ball_type": "v1"})
+        assert not matches_tags(tags, {"weather": "windy"})
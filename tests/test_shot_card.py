@@ -0,0 +1,65 @@
+"""Tests for console shot card templates."""
+
+from datetime import datetime
+
+import pytest
+
+from openflight.launch_monitor import ClubType, Shot
+from openflight.shot_card import ShotCard
+
+
+def _shot(**kwargs):
+    return Shot(ball_speed_mph=150.0, timestamp=datetime.now(), **kwargs)
+
+
+class TestShotCard:
+    """Tests for rendering shots through a template."""
+
+    def test_full_card(self):
+        """The default card should show every measured number."""
+        shot = _shot(club_speed_mph=105.0, peak_magnitude=1200)
+
+        card = ShotCard.load("full").render(shot)
+
+        assert "  Club Speed:   105.0 mph" in card.splitlines()
+        assert "  Ball Speed:   150.0 mph" in card.splitlines()
+        assert "  Smash Factor: 1.43" in card.splitlines()
+        assert "  Signal:       1200" in card.splitlines()
+
+    def test_missing_fields_drop_their_lines(self):
+        """Without a club speed, the club speed and smash lines should be left out."""
+        card = ShotCard.load("full").render(_shot())
+
+        assert "Club Speed" not in card
+        assert "Smash" not in card
+        assert "Ball Speed:   150.0 mph" in card
+
+    def test_custom_template_order_and_units(self):
+        """A template should pick fields, order and units."""
+        shot = _shot(club=ClubType.IRON_7)
+        card = ShotCard.load("{club}\\nBall {ball_speed@km/h:.0f} km/h | {ball_speed}")
+
+        assert card.render(shot) == "7-iron\nBall 241 km/h | 150.0"
+
+    def test_carry_in_metres(self):
+        """Distances should convert to metres."""
+        shot = _shot()
+        card = ShotCard.load("{carry@m:.1f}")
+
+        assert card.render(shot) == f"{shot.estimated_carry_yards * 0.9144:.1f}"
+
+    @pytest.mark.parametrize(
+        "template",
+        [
+            "{launch_speed}",  # unknown field
+            "{ball_speed@yd}",  # not a distance
+            "{smash@km/h}",  # no unit
+            "{ball_speed:d}",  # not an integer
+            "{ball_speed",  # unclosed
+            "ball speed only",  # neither a card nor a template
+        ],
+    )
+    def test_invalid_templates(self, template):
+        """Mistakes should be caught when the template is loaded, not at the first shot."""
+        with pytest.raises(ValueError):
+            ShotCard.load(template)
@@ -0,0 +1,77 @@
+"""Tests for deleting and correcting shots in a session log."""
+
+import pytest
+
+from openflight.launch_monitor import ClubType
+from openflight.session_logger import SessionLogger
+from openflight.sessions import read_shots
+from openflight.shot_edit import delete_shots, edit_history, main, session_ended, set_club
+
+
+@pytest.fixture
+def session(tmp_path):
+    """An ended session log with three driver shots."""
+    logger = SessionLogger(log_dir=tmp_path)
+    logger.start_session(mode="streaming")
+    logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+    logger.log_shot(80.0, 90.0, 0.89, 90.0, "driver", 800.0, 5)
+    logger.log_shot(120.0, 87.0, 1.38, 165.0, "driver", 880.0, 5)
+    logger.end_session()
+    return str(logger.session_path)
+
+
+class TestEdits:
+    """Tests for writing edits."""
+
+    def test_delete(self, session):
+        written = delete_shots(session, [2], reason="shank")
+
+        assert written[0]["type"] == "shot_deleted"
+        assert [s["shot_number"] for s in read_shots(session)] == [1, 3]
+
+    def test_delete_missing(self, session):
+        delete_shots(session, [2])
+        with pytest.raises(ValueError, match="no shot 2"):
+            delete_shots(session, [2])
+
+    def test_set_club_re_estimates_carry(self, session):
+        written = set_club(session, [3], ClubType.IRON_7, reason="forgot to change clubs")
+
+        shot = read_shots(session)[2]
+        assert shot["club"] == "7-iron"
+        assert shot["estimated_carry_yards"] == written[0]["changes"]["estimated_carry_yards"]
+        assert shot["estimated_carry_yards"] != 165.0
+        assert written[0]["previous"] == {"club": "driver", "estimated_carry_yards": 165.0}
+
+    def test_set_same_club_skipped(self, session):
+        assert set_club(session, [1], ClubType.DRIVER) == []
+
+    def test_history(self, session):
+        delete_shots(session, [2], reason="shank")
+        set_club(session, [3], ClubType.IRON_7)
+
+        assert [e["type"] for e in edit_history(session)] == ["shot_deleted", "shot_edited"]
+
+
+class TestMain:
+    """Tests for the openflight-edit-shot command."""
+
+    def test_delete(self, session, capsys):
+        assert main([session, "2", "--delete", "--reason", "shank"]) == 0
+
+        assert "shot 2 deleted: shank [cli]" in capsys.readouterr().out
+
+    def test_refuses_open_session(self, tmp_path):
+        """A log the server may still be writing is only edited with --force."""
+        logger = SessionLogger(log_dir=tmp_path)
+        logger.start_session(mode="streaming")
+        logger.log_shot(150.0, 104.0, 1.44, 250.0, "driver", 900.0, 5)
+        path = str(logger.session_path)
+
+        assert not session_ended(path)
+        assert main([path, "1", "--delete"]) == 1
+        assert main([path, "1", "--delete", "--force"]) == 0
+
+    def test_unknown_club(self, session):
+        with pytest.raises(SystemExit):
+            main([session, "1", "--club", "spoon"])
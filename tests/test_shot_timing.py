@@ -0,0 +1,50 @@
+"""Tests for shot timing per radar mode and club."""
+
+import pytest
+
+from openflight.shot_timing import ShotTiming
+
+
+class TestShotTiming:
+    """Tests for parsing a profile's shot_timing section."""
+
+    def test_from_dict(self):
+        """Modes and clubs should each map to their timing fields."""
+        timing = ShotTiming.from_dict(
+            {
+                "modes": {"swing-speed": {"shot_timeout_sec": 0.3}},
+                "clubs": {"driver": {"max_shot_duration_sec": 0.4}, "sw": {"shot_timeout_sec": 1}},
+            }
+        )
+
+        assert timing.for_mode("swing-speed") == {"shot_timeout_sec": 0.3}
+        assert timing.for_mode("streaming") == {}
+        assert timing.for_club("driver") == {"max_shot_duration_sec": 0.4}
+        assert timing.for_club("sw") == {"shot_timeout_sec": 1.0}
+        assert timing.for_club("7-iron") == {}
+
+    def test_round_trip(self):
+        """to_dict should give back the section it was built from."""
+        data = {"modes": {"mock": {"club_ball_window_sec": 0.5}}, "clubs": {}}
+
+        assert ShotTiming.from_dict(data).to_dict() == data
+        assert ShotTiming.from_dict({}) == ShotTiming()
+
+    def test_unknown_names_rejected(self):
+        """Unknown modes, clubs and fields should be named in the error."""
+        with pytest.raises(ValueError, match=r"^shot_timing\.modes\.rolling-buffer: unknown mode"):
+            ShotTiming.from_dict({"modes": {"rolling-buffer": {"shot_timeout_sec": 0.3}}})
+        with pytest.raises(ValueError, match=r"^shot_timing\.clubs\.putter: unknown club"):
+            ShotTiming.from_dict({"clubs": {"putter": {"shot_timeout_sec": 0.3}}})
+        with pytest.raises(ValueError, match=r"^shot_timing\.clubs\.sw: unknown field 'min_mag"):
+            ShotTiming.from_dict({"clubs": {"sw": {"min_magnitude": 10}}})
+        with pytest.raises(ValueError, match="^Unknown shot_timing field"):
+            ShotTiming.from_dict({"club": {}})
+
+    def test_invalid_value_rejected(self):
+        """Values are checked like the monitor's, with the full key."""
+        message = r"^shot_timing\.clubs\.driver\.shot_timeout_sec: must be > 0"
+        with pytest.raises(ValueError, match=message):
+            ShotTiming.from_dict({"clubs": {"driver": {"shot_timeout_sec": 0}}})
+        with pytest.raises(ValueError, match="must be an object"):
+            ShotTiming.from_dict({"clubs": {"driver": 0.5}})
@@ -0,0 +1,37 @@
+"""Tests for smash module."""
+
+import pytest
+
+from openflight.smash import CLUB_LOFT_DEG, max_smash, smash_window
+
+
+class TestSmashWindow:
+    """Tests for smash factor windows from the club's loft."""
+
+    def test_driver_near_physical_limit(self):
+        assert max_smash("driver") == pytest.approx(1.47, abs=0.02)
+
+    def test_more_loft_lower_bound(self):
+        """Down the set, each club's bound is no higher than the one before."""
+        irons = ["3-iron", "5-iron", "7-iron", "9-iron", "pw", "sw", "lw"]
+        bounds = [max_smash(club) for club in irons]
+
+        assert bounds == sorted(bounds, reverse=True)
+
+    def test_window(self):
+        low, high = smash_window("7-iron", 0.05)
+
+        assert high == pytest.approx(max_smash("7-iron") + 0.05)
+        assert 1.0 < low < high
+
+    def test_wedge_floor(self):
+        """A wedge's window starts at 1, where the ball leaves as fast as the club."""
+        assert smash_window("lw", 0.05)[0] == 1.0
+
+    def test_every_club_has_a_window(self):
+        for club in CLUB_LOFT_DEG:
+            assert smash_window(club, 0.05) is not None
+
+    def test_global_fallback(self):
+        assert smash_window("unknown", 0.05) is None
+        assert smash_window("driver", 0) is None
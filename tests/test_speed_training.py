@@ -0,0 +1,154 @@
+"""Tests for speed_training module."""
+
+import pytest
+
+from openflight.ops243 import Direction, SpeedReading
+from openflight.speed_training import (
+    Protocol,
+    SpeedTrainingSession,
+    SwingDetector,
+    TrainingSet,
+    format_summary,
+    parse_protocol,
+)
+
+
+def _swing(detector, start, speeds, step=0.02):
+    """Feed one swing's readings through impact; returns any swing they ended."""
+    ended = None
+    for i, speed in enumerate(speeds):
+        ended = detector.update(SpeedReading(speed, Direction.OUTBOUND), now=start + i * step)
+        ended = ended or None
+    return ended
+
+
+class TestParseProtocol:
+    """Tests for protocol specs."""
+
+    def test_labeled_sets(self):
+        """LABEL:REPS items should become sets in order."""
+        protocol = parse_protocol("Light:5, normal:5,heavy:3")
+
+        assert protocol.sets == (
+            TrainingSet("light", 5),
+            TrainingSet("normal", 5),
+            TrainingSet("heavy", 3),
+        )
+        assert protocol.total_reps == 13
+        assert str(protocol) == "light:5,normal:5,heavy:3"
+
+    def test_sets_by_reps(self):
+        """SETSxREPS should give numbered sets."""
+        assert parse_protocol("3x5") == Protocol(
+            (TrainingSet("set 1", 5), TrainingSet("set 2", 5), TrainingSet("set 3", 5))
+        )
+
+    @pytest.mark.parametrize(
+        "text, message",
+        [
+            ("", "no sets given"),
+            ("light", "expected LABEL:REPS"),
+            ("light:five", "whole number"),
+            ("light:0", "at least 1"),
+            ("0x5", "at least 1"),
+        ],
+    )
+    def test_invalid(self, text, message):
+        """Bad protocols should say what is wrong."""
+        with pytest.raises(ValueError, match=message):
+            parse_protocol(text)
+
+
+class TestSwingDetector:
+    """Tests for finding club-only swings."""
+
+    def test_swing_peak_after_gap(self):
+        """A run of readings should be one swing, reported at its peak once it ends."""
+        detector = SwingDetector()
+
+        assert _swing(detector, 0.0, [60, 95, 112, 104]) is None
+        assert detector.poll(now=0.2) is None
+        assert detector.poll(now=1.0) == 112
+        assert detector.poll(now=2.0) is None
+
+    def test_next_reading_ends_swing(self):
+        """A reading after the gap should end the swing before it."""
+        detector = SwingDetector()
+        _swing(detector, 0.0, [90, 105])
+
+        assert _swing(detector, 5.0, [98]) == 105
+
+    def test_ignores_other_readings(self):
+        """The backswing, slow practice swings and noise should not make swings."""
+        detector = SwingDetector(min_speed_mph=50)
+        detector.update(SpeedReading(80, Direction.INBOUND), now=0.0)
+        detector.update(SpeedReading(40, Direction.OUTBOUND), now=0.1)
+        detector.update(SpeedReading(250, Direction.OUTBOUND), now=0.2)
+
+        assert detector.poll(now=5.0) is None
+
+
+class TestSpeedTrainingSession:
+    """Tests for working through a protocol."""
+
+    def test_sets_advance(self):
+        """Reps should fill each set in turn, then stop counting."""
+        session = SpeedTrainingSession(parse_protocol("light:2,heavy:1"), rest_sec=30)
+
+        first = session.record_swing(110, now=0)
+        second = session.record_swing(112, now=10)
+        third = session.record_swing(100, now=60)
+
+        assert (first["label"], first["rep"], first["set_complete"]) == ("light", 1, False)
+        assert second["set_complete"] and not second["complete"]
+        assert (third["set"], third["label"], third["complete"]) == (2, "heavy", True)
+        assert session.complete
+        assert session.record_swing(120, now=70) is None
+
+    def test_summary_compliance(self):
+        """Eased-off reps, short rests and missing reps should lower compliance."""
+        session = SpeedTrainingSession(parse_protocol("2x3"), rest_sec=60)
+        for now, speed in [(0, 110), (10, 112), (20, 100), (40, 111)]:
+            session.record_swing(speed, now=now)
+
+        summary = session.summary()
+
+        first, second = summary["sets"]
+        assert first["speeds_mph"] == [110, 112, 100]
+        assert first["best_mph"] == 112
+        assert first["avg_mph"] == pytest.approx(107.33, abs=0.01)
+        assert first["change_mph"] == -10
+        assert first["intent_reps"] == 2  # 100 is under 95% of 112
+        assert second["rest_sec"] == 20
+        assert second["rested"] is False
+        assert (summary["sets_rested"], summary["set_breaks"]) == (0, 1)
+        assert (summary["reps_done"], summary["reps_planned"]) == (4, 6)
+        assert summary["intent_pct"] == 75
+        assert summary["compliance_pct"] == 50
+        assert (summary["best_mph"], summary["best_set"]) == (112, "set 1")
+        assert not summary["complete"]
+
+    def test_reset(self):
+        """reset() should start the protocol over."""
+        session = SpeedTrainingSession(parse_protocol("1x1"))
+        session.record_swing(100, now=0)
+
+        session.reset()
+
+        summary = session.summary()
+        assert summary["reps_done"] == 0
+        assert summary["best_set"] is None
+        assert summary["intent_pct"] is None
+
+
+def test_format_summary():
+    """The console summary should have a line per set."""
+    session = SpeedTrainingSession(parse_protocol("light:1,heavy:1"), rest_sec=60)
+    session.record_swing(115.0, now=0)
+
+    lines = format_summary(session.summary())
+
+    assert lines[0] == "Speed training: 1/2 reps"
+    assert lines[1] == "  light: 1/1 reps, best 115.0 mph, avg 115.0 mph, 1 at max intent"
+    assert lines[2] == "  heavy: not started"
+    assert lines[3] == "  Compliance: 50%"
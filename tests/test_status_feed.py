@@ -0,0 +1,174 @@
+"""Tests for the menu bar status feed."""
+
+import json
+import os
+import socket
+import stat
+
+import pytest
+
+from openflight.status_feed import (
+    RADAR_STALE_SEC,
+    SCHEMA_VERSION,
+    StatusFeed,
+    last_shot_summary,
+    radar_health,
+)
+
+_SHOT = {
+    "ball_speed_mph": 150.2,
+    "estimated_carry_yards": 231.0,
+    "club": "driver",
+    "timestamp": "2026-01-01T10:00:00",
+    "peak_magnitude": 812,
+}
+
+
+class _Status:
+    """Status callback whose answer the test changes."""
+
+    def __init__(self):
+        self.value = {
+            "state": "ready",
+            "mode": "streaming",
+            "shots": 0,
+            "club": "driver",
+            "connection": {"radar": "ok", "radar_data_age_sec": 0.1},
+        }
+
+    def __call__(self):
+        return dict(self.value)
+
+
+def _read_line(stream) -> dict:
+    line = stream.readline()
+    assert line, "feed closed the connection"
+    return json.loads(line)
+
+
+@pytest.fixture(name="status")
+def _status():
+    return _Status()
+
+
+@pytest.fixture(name="feed")
+def _feed(status):
+    feed = StatusFeed(status, keepalive_sec=0.3, poll_sec=0.05)
+    yield feed
+    feed.stop()
+
+
+def _connect_tcp(feed):
+    port = feed.listen_tcp(0)
+    feed.start()
+    sock = socket.create_connection(("127.0.0.1", port), timeout=2)
+    return sock, sock.makefile("rb")
+
+
+class TestRadarHealth:
+    """Tests for the connection.radar value."""
+
+    def test_values(self):
+        """Down when stopped, mock for the mock monitor, stale when the radar goes quiet."""
+        assert radar_health(False, False, None) == "down"
+        assert radar_health(True, True, None) == "mock"
+        assert radar_health(True, False, None) == "ok"
+        assert radar_health(True, False, 0.2) == "ok"
+        assert radar_health(True, False, RADAR_STALE_SEC + 1) == "stale"
+
+
+class TestStatusFeed:
+    """Tests for the pushed status lines."""
+
+    def test_status_sent_on_connect(self, feed):
+        """A client should get the full status line straight away."""
+        sock, stream = _connect_tcp(feed)
+        with sock:
+            status = _read_line(stream)
+
+        assert status["schema"] == SCHEMA_VERSION
+        assert status["state"] == "ready"
+        assert status["connection"]["radar"] == "ok"
+        assert status["last_shot"] is None
+        assert "ts" in status
+
+    def test_shot_pushed(self, feed, status):
+        """A shot on the event bus should be pushed as last_shot."""
+        sock, stream = _connect_tcp(feed)
+        with sock:
+            _read_line(stream)
+            status.value["shots"] = 1
+            feed.on_event("shot", {"shot": _SHOT, "stats": {}})
+            pushed = _read_line(stream)
+
+        assert pushed["shots"] == 1
+        assert pushed["last_shot"] == last_shot_summary(_SHOT)
+        assert "peak_magnitude" not in pushed["last_shot"]
+
+    def test_mulligan_clears_last_shot(self, feed):
+        """A mulligan takes the last shot back."""
+        feed.on_event("shot", {"shot": _SHOT})
+        feed.on_event("mulligan", {"shot": _SHOT})
+
+        assert feed.current()["last_shot"] is None
+
+    def test_state_change_pushed_without_event(self, feed, status):
+        """Changes in the status callback (e.g. the radar going quiet) should be polled."""
+        sock, stream = _connect_tcp(feed)
+        with sock:
+            _read_line(stream)
+            status.value["connection"] = {"radar": "stale", "radar_data_age_sec": 9}
+            pushed = _read_line(stream)
+
+        assert pushed["connection"]["radar"] == "stale"
+
+    def test_keepalive_repeats_unchanged_status(self, feed):
+        """With nothing changing, the status should still be resent every keepalive_sec."""
+        sock, stream = _connect_tcp(feed)
+        with sock:
+            first = _read_line(stream)
+            second = _read_line(stream)
+
+        assert {k: v for k, v in first.items() if k != "ts"} == {
+            k: v for k, v in second.items() if k != "ts"
+        }
+
+    def test_disconnected_client_dropped(self, feed):
+        """A client that goes away should be dropped on the next push."""
+        sock, stream = _connect_tcp(feed)
+        _read_line(stream)
+        stream.close()
+        sock.close()
+        for _ in range(5):
+            feed.broadcast(feed.current())
+
+        assert feed.client_count == 0
+
+    def test_unix_socket_owner_only(self, tmp_path):
+        """The Unix socket should only be usable by its owner and be removed on stop."""
+        path = str(tmp_path / "status.sock")
+        feed = StatusFeed(_Status(), keepalive_sec=0.3, poll_sec=0.05)
+        feed.listen_unix(path)
+        feed.start()
+        try:
+            assert stat.S_IMODE(os.stat(path).st_mode) == 0o600
+            with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+                sock.settimeout(2)
+                sock.connect(path)
+                assert _read_line(sock.makefile("rb"))["state"] == "ready"
+        finally:
+            feed.stop()
+
+        assert not os.path.exists(path)
+
+    def test_unix_socket_in_use_refused(self, tmp_path):
+        """A second feed on a socket that's being served should fail."""
+        path = str(tmp_path / "status.sock")
+        feed = StatusFeed(_Status())
+        feed.listen_unix(path)
+        feed.start()
+        try:
+            with pytest.raises(OSError):
+                StatusFeed(_Status()).listen_unix(path)
+        finally:
+            feed.stop()
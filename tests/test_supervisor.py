@@ -0,0 +1,155 @@
+"""Tests for the kiosk watchdog."""
+
+import subprocess
+
+from openflight.supervisor import Supervisor
+
+
+class _FakeChild:
+    """Child process that exits with a code after some polls, or hangs."""
+
+    def __init__(self, command, exit_code=None, polls=0):
+        self.command = command
+        self.exit_code = exit_code
+        self.polls = polls
+        self.returncode = None
+        self.signals = []
+        self.killed = False
+
+    def poll(self):
+        if self.returncode is None and self.exit_code is not None:
+            self.polls -= 1
+            if self.polls < 0:
+                self.returncode = self.exit_code
+        return self.returncode
+
+    def wait(self, timeout=None):
+        if self.returncode is None:
+            if self.exit_code is None and not self.killed:
+                raise subprocess.TimeoutExpired("server", timeout)
+            self.returncode = self.exit_code if self.exit_code is not None else -9
+        return self.returncode
+
+    def send_signal(self, signum):
+        self.signals.append(signum)
+        self.returncode = 0
+
+    def kill(self):
+        self.killed = True
+
+
+def _supervisor(children, status=None, wedge_timeout_sec=30.0):
+    """Supervisor over scripted children, with no real waiting."""
+    started = []
+
+    def popen(command):
+        child = children.pop(0)
+        child.command = command
+        started.append(child)
+        return child
+
+    def no_status(path):
+        raise ConnectionRefusedError(path)
+
+    supervisor = Supervisor(
+        ["openflight-server"],
+        "/tmp/of.sock",
+        wedge_timeout_sec=wedge_timeout_sec,
+        popen=popen,
+        status=status or no_status,
+    )
+    supervisor.CHECK_INTERVAL_SEC = 0.001
+    supervisor.STARTUP_GRACE_SEC = 0.0
+    supervisor.BACKOFF_SEC = 0.001
+    supervisor.MAX_BACKOFF_SEC = 0.004
+    return supervisor, started
+
+
+class TestSupervisor:
+    """Tests for restarting the server."""
+
+    def test_crash_restarts_then_clean_exit_ends(self):
+        """A crashed server should be restarted; a clean exit should end supervision."""
+        supervisor, started = _supervisor(
+            [_FakeChild(None, exit_code=1), _FakeChild(None, exit_code=0)],
+            status=lambda path: {"session_path": None},
+        )
+
+        assert supervisor.run() == 0
+        assert len(started) == 2
+        assert supervisor.restarts == 1
+
+    def test_restart_resumes_session(self):
+        """The restarted server should continue the session the old one was logging."""
+        supervisor, started = _supervisor(
+            [_FakeChild(None, exit_code=1, polls=2), _FakeChild(None, exit_code=0)],
+            status=lambda path: {"session_path": "/logs/session_1.jsonl"},
+        )
+
+        supervisor.run()
+
+        assert "--resume-session" not in started[0].command
+        assert started[1].command[-2:] == ["--resume-session", "/logs/session_1.jsonl"]
+
+    def test_wedged_radar_restarts(self):
+        """No radar data for longer than the wedge timeout should restart the server."""
+        statuses = [{"radar_data_age_sec": 45.0, "session_path": "/logs/s.jsonl"}]
+        supervisor, started = _supervisor(
+            [_FakeChild(None), _FakeChild(None, exit_code=0)],
+            status=lambda path: statuses[0],
+        )
+
+        supervisor.run()
+
+        assert started[0].signals  # Asked to stop cleanly first
+        assert len(started) == 2
+
+    def test_hung_server_killed(self):
+        """A server that stops answering and ignores SIGINT should be killed and restarted."""
+
+        class _Stubborn(_FakeChild):
+            def send_signal(self, signum):
+                self.signals.append(signum)
+
+        hung = _Stubborn(None)
+        supervisor, started = _supervisor([hung, _FakeChild(None, exit_code=0)])
+
+        supervisor.run()
+
+        assert hung.killed
+        assert len(started) == 2
+
+    def test_no_wedge_check_without_iq(self):
+        """A radar that doesn't stream I/Q (age None) shouldn't count as wedged."""
+        checks = []
+
+        def status(path):
+            checks.append(path)
+            return {"radar_data_age_sec": None}
+
+        supervisor, started = _supervisor([_FakeChild(None, exit_code=0, polls=5)], status=status)
+
+        assert supervisor.run() == 0
+        assert len(started) == 1
+        assert checks
+
+    def test_backoff_capped(self):
+        """Repeated crashes should back off up to MAX_BACKOFF_SEC."""
+        waits = []
+        supervisor, _ = _supervisor(
+            [_FakeChild(None, exit_code=1) for _ in range(5)] + [_FakeChild(None, exit_code=0)],
+            status=lambda path: {},
+        )
+        supervisor.CHECK_INTERVAL_SEC = 0.0001
+        real_wait = supervisor._stop_event.wait
+
+        def wait(timeout):
+            if timeout != supervisor.CHECK_INTERVAL_SEC:
+                waits.append(timeout)
+            return real_wait(0)
+
+        supervisor._stop_event.wait = wait
+
+        supervisor.run()
+
+        assert waits == [0.001, 0.002, 0.004, 0.004, 0.004]
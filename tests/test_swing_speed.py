@@ -0,0 +1,76 @@
+"""Tests for swing_speed module."""
+
+from openflight.launch_monitor import ClubType, MonitorConfig
+from openflight.ops243 import Direction, SpeedReading
+from openflight.swing_speed import SwingSpeedMonitor, swing_stats
+
+
+def _swing(speeds, start=100.0, step=0.02, magnitude=300):
+    """Readings of one swing through the hitting area."""
+    return [
+        SpeedReading(speed, Direction.OUTBOUND, magnitude=magnitude, timestamp=start + i * step)
+        for i, speed in enumerate(speeds)
+    ]
+
+
+def _monitor(**config):
+    return SwingSpeedMonitor(use_iq_streaming=False, config=MonitorConfig(**config))
+
+
+class TestSwingSpeedMonitor:
+    """Tests for club-only swing detection."""
+
+    def test_peak_is_club_speed(self):
+        """A swing with no ball should report its peak as the club speed."""
+        monitor = _monitor()
+        monitor.set_club(ClubType.IRON_7)
+        swings = []
+        monitor._swing_callback = swings.append
+
+        swing = monitor.analyze_readings(_swing([72.0, 88.5, 93.1, 90.2]))
+
+        assert swing.club_speed_mph == 93.1
+        assert swing.club == ClubType.IRON_7
+        assert swings == [swing]
+        assert monitor.get_shots() == []
+        assert swing.to_dict()["readings_count"] == 4
+
+    def test_shot_pipeline_would_take_it_for_a_ball(self):
+        """Without the mode, the same readings would be a 93 mph ball with no club."""
+        from openflight.launch_monitor import LaunchMonitor
+
+        shot = LaunchMonitor(use_iq_streaming=False).analyze_readings(_swing([93.1]))
+
+        assert shot.ball_speed_mph == 93.1
+        assert shot.club_speed_mph is None
+
+    def test_rejects_out_of_range_and_weak(self):
+        """Speeds outside the club range, long bursts and weak signals aren't swings."""
+        monitor = _monitor(max_club_speed_mph=140)
+
+        assert monitor.analyze_readings(_swing([150.0])) is None  # A ball
+        assert monitor.analyze_readings(_swing([20.0])) is None
+        assert monitor.analyze_readings(_swing([90.0] * 30)) is None  # 0.6 s: walking
+        assert monitor.analyze_readings(_swing([90.0], magnitude=10)) is None
+        assert monitor.get_swings() == []
+
+    def test_session_stats(self):
+        """Stats should count swings, with no shots."""
+        monitor = _monitor()
+        monitor.analyze_readings(_swing([100.0]))
+        monitor.analyze_readings(_swing([110.0]))
+
+        stats = monitor.get_session_stats()
+
+        assert stats["shot_count"] == 0
+        assert stats["swing_count"] == 2
+        assert stats["avg_swing_speed"] == 105.0
+        assert stats["max_swing_speed"] == 110.0
+
+        monitor.clear_session()
+        assert monitor.get_session_stats()["swing_count"] == 0
+
+
+def test_swing_stats_empty():
+    """No swings should give no speeds."""
+    assert swing_stats([]) == {"swing_count": 0, "avg_swing_speed": None, "max_swing_speed": None}
@@ -0,0 +1,69 @@
+"""Tests for the takeaway arming window."""
+
+from openflight.frames import Direction, SpeedReading
+from openflight.launch_monitor import MonitorConfig
+from openflight.takeaway import TakeawayWindow, backswing_direction, relaxed_config
+
+
+def _reading(direction, speed=20.0):
+    return SpeedReading(speed=speed, direction=direction, magnitude=50)
+
+
+class TestTakeawayWindow:
+    """Tests for opening and closing the window."""
+
+    def test_opens_after_backswing_run(self):
+        """Enough backswing readings close together should open the window."""
+        window = TakeawayWindow(Direction.INBOUND, window_sec=2.0, min_readings=2)
+
+        assert not window.update(_reading(Direction.INBOUND), 10.0)
+        assert not window.is_open(10.0)
+        assert window.update(_reading(Direction.INBOUND), 10.1)
+        assert window.is_open(11.0)
+        assert not window.is_open(12.2)
+
+    def test_spread_out_readings_dont_open(self):
+        """Backswing readings further apart than MAX_GAP_SEC aren't a takeaway."""
+        window = TakeawayWindow(Direction.INBOUND, window_sec=2.0, min_readings=2)
+
+        window.update(_reading(Direction.INBOUND), 10.0)
+        window.update(_reading(Direction.INBOUND), 11.0)
+
+        assert not window.is_open(11.0)
+
+    def test_ball_direction_ignored(self):
+        """Readings in the ball's direction shouldn't count toward a takeaway."""
+        window = TakeawayWindow(Direction.INBOUND, window_sec=2.0, min_readings=1)
+
+        window.update(_reading(Direction.OUTBOUND), 10.0)
+
+        assert not window.is_open(10.0)
+
+    def test_close(self):
+        """close should return to strict thresholds straight away."""
+        window = TakeawayWindow(Direction.INBOUND, window_sec=2.0, min_readings=1)
+        window.update(_reading(Direction.INBOUND), 10.0)
+
+        window.close()
+
+        assert not window.is_open(10.5)
+
+
+class TestHelpers:
+    """Tests for the config and direction helpers."""
+
+    def test_relaxed_config_scales_magnitudes(self):
+        """Only the magnitude thresholds should be scaled."""
+        config = MonitorConfig(takeaway_threshold_scale=0.25)
+
+        relaxed = relaxed_config(config)
+
+        assert relaxed.min_magnitude == config.min_magnitude * 0.25
+        assert relaxed.min_shot_magnitude == config.min_shot_magnitude * 0.25
+        assert relaxed.min_ball_speed_mph == config.min_ball_speed_mph
+
+    def test_backswing_direction(self):
+        """The backswing is opposite the ball, and ambiguous with two ball directions."""
+        assert backswing_direction({Direction.OUTBOUND}) == Direction.INBOUND
+        assert backswing_direction({Direction.INBOUND}) == Direction.OUTBOUND
+        assert backswing_direction({Direction.INBOUND, Direction.OUTBOUND}) is None
@@ -0,0 +1,134 @@
+"""Tests for tempo module."""
+
+import pytest
+
+from openflight import tempo
+from openflight.ops243 import Direction, SpeedReading
+from openflight.tempo import SwingTempo, TempoCues, TempoMeter, compare_tempo, parse_ratio
+
+
+def _feed(meter, times, direction=Direction.INBOUND, speed=20.0):
+    for t in times:
+        meter.update(SpeedReading(speed, direction, timestamp=t))
+
+
+class TestParseRatio:
+    """Tests for parsing tempo ratios."""
+
+    @pytest.mark.parametrize(
+        "text,expected", [("3:1", 3.0), ("2:1", 2.0), ("3", 3.0), ("5:2", 2.5)]
+    )
+    def test_valid(self, text, expected):
+        """Ratios parse as backswing over downswing."""
+        assert parse_ratio(text) == expected
+
+    @pytest.mark.parametrize("text", ["", "fast", "3:0", "0:1", "-3:1", "inf"])
+    def test_invalid(self, text):
+        """Anything but a positive ratio is rejected."""
+        with pytest.raises(ValueError):
+            parse_ratio(text)
+
+
+class TestTempoMeter:
+    """Tests for measuring tempo from the backswing readings."""
+
+    def test_measures_backswing_and_downswing(self):
+        """Takeaway to top is the backswing run, top to impact the downswing."""
+        meter = TempoMeter()
+        _feed(meter, [10.0, 10.2, 10.4, 10.6])
+
+        tempo = meter.measure(10.85)
+
+        assert tempo.backswing_sec == pytest.approx(0.6)
+        assert tempo.downswing_sec == pytest.approx(0.25)
+        assert tempo.ratio == pytest.approx(2.4)
+
+    def test_ignores_ball_direction_and_later_readings(self):
+        """Club and ball readings toward the target, and readings after impact, aren't backswing."""
+        meter = TempoMeter()
+        _feed(meter, [10.0, 10.3])
+        _feed(meter, [10.55, 10.6], direction=Direction.OUTBOUND, speed=150.0)
+        _feed(meter, [10.7])
+
+        tempo = meter.measure(10.6)
+
+        assert tempo.backswing_sec == pytest.approx(0.3)
+        assert tempo.downswing_sec == pytest.approx(0.3)
+
+    def test_gap_ends_backswing_run(self):
+        """Earlier movement (a waggle) separated by a pause isn't part of the backswing."""
+        meter = TempoMeter()
+        _feed(meter, [8.0, 8.1, 10.0, 10.3, 10.6])
+
+        assert meter.measure(10.8).backswing_sec == pytest.approx(0.6)
+
+    def test_front_mount_backswing_is_outbound(self):
+        """With the radar in front of the ball the backswing moves away from it."""
+        meter = TempoMeter(backswing_direction=Direction.OUTBOUND)
+        _feed(meter, [10.0, 10.3, 10.6], direction=Direction.OUTBOUND)
+
+        assert meter.measure(10.8).ratio == pytest.approx(3.0)
+
+    def test_no_backswing(self):
+        """No tempo without at least two backswing readings shortly before impact."""
+        meter = TempoMeter()
+        assert meter.measure(10.0) is None
+
+        _feed(meter, [9.9])
+        assert meter.measure(10.0) is None
+
+        _feed(meter, [12.0, 12.3])
+        assert meter.measure(14.0) is None
+
+    def test_old_readings_are_dropped(self):
+        """Only the last few seconds of readings are kept."""
+        meter = TempoMeter()
+        _feed(meter, [0.0, 0.1, 10.0])
+
+        assert len(meter._readings) == 1
+
+
+class TestCompareTempo:
+    """Tests for comparing a tempo to its target."""
+
+    def test_on_target(self):
+        """Within tolerance of the target is on target."""
+        result = compare_tempo(SwingTempo(backswing_sec=0.72, downswing_sec=0.25), 3.0)
+
+        assert result["ratio"] == 2.88
+        assert result["error_pct"] == -4.0
+        assert result["on_target"]
+
+    def test_off_target(self):
+        """A quick backswing is well under the target ratio."""
+        assert not compare_tempo(SwingTempo(backswing_sec=0.5, downswing_sec=0.25), 3.0)["on_target"]
+
+
+class TestTempoCues:
+    """Tests for the tempo metronome."""
+
+    def test_tones_spaced_at_ratio(self, monkeypatch):
+        """Takeaway, top and impact tones follow the backswing time and ratio."""
+        clock = [0.0]
+        played = []
+        cues = TempoCues(ratio=3.0, backswing_sec=0.6, play=lambda hz, sec: played.append(clock[0]))
+
+        def fake_wait(timeout):
+            clock[0] += timeout
+            return False
+
+        monkeypatch.setattr(tempo.time, "monotonic", lambda: clock[0])
+        monkeypatch.setattr(cues._stop, "wait", fake_wait)
+        cues.play_swing()
+
+        assert played == pytest.approx([0.0, 0.6, 0.8])
+
+    def test_stop_interrupts_swing(self):
+        """A stopped metronome plays nothing more."""
+        played = []
+        cues = TempoCues(play=lambda hz, sec: played.append(hz))
+        cues._stop.set()
+
+        cues.play_swing()
+
+        assert played == []
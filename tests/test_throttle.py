@@ -0,0 +1,50 @@
+"""Tests for throttle module."""
+
+import pytest
+
+from openflight.throttle import DEFAULT_READING_HZ, RateLimiter, parse_reading_hz
+
+
+class TestRateLimiter:
+    """Tests for per-subscriber rate limiting."""
+
+    def test_limits_rate(self):
+        """At 20 Hz, 100 events over one second should let about 20 through."""
+        limiter = RateLimiter(20)
+
+        allowed = sum(limiter.allow(now=i * 0.01) for i in range(100))
+
+        assert allowed == 20
+        assert limiter.dropped == 80
+
+    def test_zero_is_unlimited(self):
+        """0 Hz means full fidelity."""
+        limiter = RateLimiter(0)
+
+        assert all(limiter.allow(now=0.0) for _ in range(50))
+
+    def test_negative_rejected(self):
+        """Negative rates should be rejected."""
+        with pytest.raises(ValueError):
+            RateLimiter(-1)
+
+
+class TestParseReadingHz:
+    """Tests for the reading_hz query parameter."""
+
+    def test_default_when_missing(self):
+        """Missing or empty values use the default."""
+        assert parse_reading_hz(None) == DEFAULT_READING_HZ
+        assert parse_reading_hz("") == DEFAULT_READING_HZ
+
+    def test_parses_numbers(self):
+        """Numbers, including 0 for unlimited, should parse."""
+        assert parse_reading_hz("5") == 5.0
+        assert parse_reading_hz("0") == 0.0
+
+    def test_invalid_rejected(self):
+        """Non-numeric and negative values should be rejected."""
+        with pytest.raises(ValueError):
+            parse_reading_hz("fast")
+        with pytest.raises(ValueError):
+            parse_reading_hz("-2")
@@ -0,0 +1,100 @@
+"""Tests for companion clock synchronization."""
+
+import json
+import socket
+import threading
+import time
+
+import pytest
+
+from openflight import timesync
+from openflight.timesync import (
+    CompanionEvents,
+    TimeSyncResponder,
+    measure_offset,
+    offset_from_times,
+)
+
+
+@pytest.fixture
+def responder():
+    """Responder on a free localhost port whose clock runs 100 s ahead."""
+    server = TimeSyncResponder(host="127.0.0.1", port=0, clock=lambda: time.monotonic() + 100.0)
+    server.RECV_TIMEOUT_SEC = 0.05
+    server.start()
+    yield server
+    server.stop()
+
+
+class TestOffset:
+    """Tests for the offset calculation."""
+
+    def test_symmetric_delay(self):
+        """With equal delays each way, the offset should be exact."""
+        # Monitor 5 s ahead; 10 ms each way, 2 ms to reply
+        result = offset_from_times(t0=1.000, t1=6.010, t2=6.012, t3=1.022)
+
+        assert result.offset_sec == pytest.approx(5.0)
+        assert result.delay_sec == pytest.approx(0.020)
+
+    def test_to_monitor(self):
+        """Companion times should be shifted by the offset."""
+        assert offset_from_times(1.0, 6.0, 6.0, 1.0).to_monitor(10.0) == pytest.approx(15.0)
+
+
+class TestExchange:
+    """Tests for measuring against a responder."""
+
+    def test_measure_offset(self, responder):
+        """The measured offset should be the responder's clock lead."""
+        host, port = responder.address
+
+        result = measure_offset(host, port, samples=4)
+
+        assert result.offset_sec == pytest.approx(100.0, abs=0.01)
+        assert result.delay_sec >= 0
+
+    def test_no_responder(self):
+        """No reply at all should raise rather than give a made-up offset."""
+        with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as unused:
+            unused.bind(("127.0.0.1", 0))
+            port = unused.getsockname()[1]
+            with pytest.raises(OSError):
+                measure_offset("127.0.0.1", port, samples=2, timeout=0.05)
+
+    def test_cli(self, responder, capsys):
+        """openflight-timesync --json should print the offset."""
+        host, port = responder.address
+
+        assert timesync.main([host, "--port", str(port), "--samples", "2", "--json"]) == 0
+        result = json.loads(capsys.readouterr().out)
+        assert result["offset_sec"] == pytest.approx(100.0, abs=0.01)
+
+
+class TestCompanionEvents:
+    """Tests for matching companion events to shots."""
+
+    def test_nearest_in_window(self):
+        """The closest event within the window should be taken, once."""
+        events = CompanionEvents()
+        events.add(10.0, "early")
+        events.add(10.3, "close")
+
+        assert events.nearest(10.25) == (10.3, "close")
+        assert events.nearest(10.25, window_sec=0.1) is None
+        assert events.nearest(10.25) == (10.0, "early")
+
+    def test_wait_for_late_event(self):
+        """A shot should get an event that arrives while it waits."""
+        events = CompanionEvents()
+        threading.Timer(0.05, events.add, args=(20.1, "angle")).start()
+
+        assert events.wait_for(20.0, timeout=2.0) == (20.1, "angle")
+        assert events.active
+
+    def test_wait_times_out(self):
+        """Without a matching event, waiting should give up."""
+        events = CompanionEvents()
+        events.add(5.0, "other shot")
+
+        assert events.wait_for(20.0, timeout=0.05) is None
@@ -0,0 +1,124 @@
+"""Tests for the pipeline timing trace."""
+
+import json
+import threading
+
+import pytest
+
+from openflight import tracing
+from openflight.tracing import Tracer, init_tracer, trace_span, traced
+
+
+@pytest.fixture(autouse=True)
+def no_global_tracer():
+    yield
+    init_tracer(None)
+
+
+def _load(path):
+    with open(path, encoding="utf-8") as f:
+        return json.load(f)
+
+
+def _spans(events):
+    return [e for e in events if e["ph"] == "X"]
+
+
+class TestTracer:
+    """Tests for writing trace events."""
+
+    def test_complete_span(self, tmp_path):
+        path = tmp_path / "trace.json"
+        tracer = Tracer(str(path))
+        tracer.complete("decode", "parse", 1.0, 1.0025, bytes=40)
+        tracer.close()
+
+        (span,) = _spans(_load(path))
+        assert span["name"] == "decode"
+        assert span["cat"] == "parse"
+        assert span["ts"] == pytest.approx(1_000_000)
+        assert span["dur"] == pytest.approx(2500)
+        assert span["args"] == {"bytes": 40}
+        assert span["tid"] == threading.get_ident()
+
+    def test_threads_named_once(self, tmp_path):
+        path = tmp_path / "trace.json"
+        tracer = Tracer(str(path))
+
+        def work():
+            with tracer.span("webhook", "deliver"):
+                pass
+
+        worker = threading.Thread(target=work, name="webhook-worker")
+        worker.start()
+        worker.join()
+        with tracer.span("on_shot", "deliver"):
+            pass
+        with tracer.span("emit", "deliver"):
+            pass
+        tracer.close()
+
+        events = _load(path)
+        names = [e["args"]["name"] for e in events if e["name"] == "thread_name"]
+        assert sorted(names) == sorted(["webhook-worker", threading.current_thread().name])
+        assert len(_spans(events)) == 3
+
+    def test_span_args_added_inside(self, tmp_path):
+        path = tmp_path / "trace.json"
+        tracer = Tracer(str(path))
+        with tracer.span("shot", "detect") as args:
+            args["readings"] = 12
+        tracer.close()
+
+        assert _spans(_load(path))[0]["args"] == {"readings": 12}
+
+    def test_unclosed_trace_still_parses(self, tmp_path):
+        """A trace cut short is valid once the missing "]" is added, as viewers do."""
+        path = tmp_path / "trace.json"
+        tracer = Tracer(str(path))
+        tracer.complete("read", "read", 0.0, 0.001)
+        tracer._file.flush()  # pylint: disable=protected-access
+
+        events = json.loads(path.read_text(encoding="utf-8") + "]")
+        assert len(_spans(events)) == 1
+        tracer.close()
+
+    def test_max_events(self, tmp_path):
+        path = tmp_path / "trace.json"
+        tracer = Tracer(str(path), max_events=3)
+        for _ in range(5):
+            tracer.complete("read", "read", 0.0, 0.001)
+        tracer.close()
+
+        assert len(_load(path)) == 3
+        assert tracer.dropped == 4  # Process name, thread name, then one span fits
+
+
+class TestGlobalTracer:
+    """Tests for the module-level helpers."""
+
+    def test_off_by_default(self):
+        assert tracing.get_tracer() is None
+        with trace_span("read", "read", bytes=5) as args:
+            assert args == {"bytes": 5}
+
+    def test_traced_decorator(self, tmp_path):
+        path = tmp_path / "trace.json"
+        init_tracer(str(path))
+
+        @traced("shot", "detect")
+        def process(value):
+            return value * 2
+
+        assert process(21) == 42
+        tracing.get_tracer().close()
+
+        (span,) = _spans(_load(path))
+        assert (span["name"], span["cat"]) == ("shot", "detect")
+
+    def test_init_replaces_and_closes(self, tmp_path):
+        first = init_tracer(str(tmp_path / "first.json"))
+        init_tracer(str(tmp_path / "second.json"))
+
+        assert tracing.get_tracer() is not first
+        assert _load(tmp_path / "first.json")[0]["name"] == "process_name"
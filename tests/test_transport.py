@@ -0,0 +1,93 @@
+"""Tests for radar serial transports."""
+
+import pytest
+import serial
+
+from openflight import transport
+from openflight.ops243 import OPS243Radar
+from openflight.transport import is_url, open_port
+
+
+@pytest.fixture
+def opened(monkeypatch):
+    """Record what open_port asks pyserial to open."""
+    calls = []
+
+    def fake_serial(**settings):
+        calls.append(("serial", settings.pop("port"), settings))
+        return "device"
+
+    def fake_for_url(url, **settings):
+        calls.append(("url", url, settings))
+        return "bridge"
+
+    monkeypatch.setattr(transport.serial, "Serial", fake_serial)
+    monkeypatch.setattr(transport.serial, "serial_for_url", fake_for_url, raising=False)
+    return calls
+
+
+class TestIsUrl:
+    """Tests for telling bridge URLs from device paths."""
+
+    def test_urls(self):
+        assert is_url("tcp://radar.local:2000")
+        assert is_url("rfc2217://192.168.1.20:2217")
+
+    def test_devices(self):
+        assert not is_url("/dev/ttyACM0")
+        assert not is_url("COM3")
+        assert not is_url("/dev/serial/by-id/usb-OmniPreSense*")
+
+
+class TestOpenPort:
+    """Tests for opening devices and bridges."""
+
+    def test_device(self, opened):
+        """A device path opens a local serial port."""
+        assert open_port("/dev/ttyACM0", 57600, 1.0, bytesize=8) == "device"
+        assert opened == [
+            ("serial", "/dev/ttyACM0", {"baudrate": 57600, "timeout": 1.0, "bytesize": 8})
+        ]
+
+    def test_tcp_is_pyserial_socket(self, opened):
+        """tcp:// is pyserial's socket:// (raw TCP)."""
+        assert open_port("tcp://radar.local:2000", 57600, 1.0) == "bridge"
+        assert opened == [
+            ("url", "socket://radar.local:2000", {"baudrate": 57600, "timeout": 1.0})
+        ]
+
+    def test_rfc2217_passed_through(self, opened):
+        open_port("RFC2217://radar.local:2217", 57600, 0.5)
+        assert opened[0][1] == "rfc2217://radar.local:2217"
+
+    def test_unknown_scheme(self, opened):
+        with pytest.raises(ValueError, match="unsupported port URL"):
+            open_port("http://radar.local", 57600, 1.0)
+        assert opened == []
+
+    def test_missing_address(self, opened):
+        with pytest.raises(ValueError, match="no host:port"):
+            open_port("tcp://", 57600, 1.0)
+
+
+class TestRadarOverBridge:
+    """Tests for OPS243Radar on a bridge URL."""
+
+    def test_unreachable_bridge(self, monkeypatch):
+        """A bridge that can't be reached is a connection error naming it."""
+
+        def refuse(url, **settings):
+            raise serial.SerialException(f"Could not open port {url}: connection refused")
+
+        monkeypatch.setattr(transport.serial, "serial_for_url", refuse, raising=False)
+        radar = OPS243Radar(port="tcp://radar.local:2000")
+
+        with pytest.raises(ConnectionError, match="tcp://radar.local:2000.*serial bridge"):
+            radar.connect()
+
+    def test_bad_url(self):
+        """A URL with an unknown scheme is a connection error, not a crash."""
+        radar = OPS243Radar(port="udp://radar.local:2000")
+
+        with pytest.raises(ConnectionError, match="unsupported port URL"):
+            radar.connect()
@@ -0,0 +1,113 @@
+"""Tests for trends module."""
+
+import json
+from datetime import date, datetime, timedelta
+
+import pytest
+
+from openflight.launch_monitor import ClubType
+from openflight.trends import fit_trend, load_values, parse_window, sparkline, weekly_stats
+
+
+def _write_session(path, tags, shots):
+    """Write a session log with (timestamp, club, ball speed, mode) shots."""
+    lines = [{"type": "session_start", "session_id": path.stem[8:23], "tags": tags}]
+    for ts, club, speed, mode in shots:
+        lines.append({
+            "type": "shot_detected",
+            "ts": ts.isoformat(),
+            "club": club,
+            "ball_speed_mph": speed,
+            "mode": mode,
+        })
+    path.write_text("".join(json.dumps(line) + "\n" for line in lines))
+    return str(path)
+
+
+class TestParseWindow:
+    """Tests for look-back windows."""
+
+    def test_units(self):
+        """Days, weeks and months should be accepted."""
+        assert parse_window("90d") == timedelta(days=90)
+        assert parse_window("12w") == timedelta(days=84)
+        assert parse_window("6M") == timedelta(days=180)
+
+    def test_invalid(self):
+        """Missing units, unknown units and zero should be rejected."""
+        for text in ("90", "3y", "0d", "d"):
+            with pytest.raises(ValueError):
+                parse_window(text)
+
+
+class TestLoadValues:
+    """Tests for collecting shots from session logs."""
+
+    def test_filters_club_mode_window_and_tags(self, tmp_path):
+        """Only radar shots with the club, in the window, from matching sessions count."""
+        now = datetime(2025, 6, 1, 10, 0)
+        outdoor = _write_session(
+            tmp_path / "session_20250601_100000_range.jsonl",
+            {"location": "range", "ball_type": "pro-v1"},
+            [
+                (now, "driver", 150.0, "streaming"),
+                (now, "7-iron", 120.0, "streaming"),
+                (now, "driver", 170.0, "mock"),
+                (now - timedelta(days=100), "driver", 140.0, "streaming"),
+            ],
+        )
+        indoor = _write_session(
+            tmp_path / "session_20250601_120000_home.jsonl",
+            {"location": "home", "ball_type": "range"},
+            [(now, "driver", 145.0, "streaming")],
+        )
+
+        values = load_values(
+            [outdoor, indoor],
+            ClubType.DRIVER,
+            "ball_speed",
+            since=now - timedelta(days=90),
+            tag_filters={"ball_type": "pro"},
+        )
+
+        assert values == [(now, 150.0)]
+
+
+class TestWeeklyStats:
+    """Tests for weekly grouping and the trend line."""
+
+    def test_groups_by_monday(self):
+        """Shots should be grouped into Monday-start weeks."""
+        values = [
+            (datetime(2025, 6, 2, 9), 140.0),  # Monday
+            (datetime(2025, 6, 8, 20), 150.0),  # Sunday, same week
+            (datetime(2025, 6, 9, 9), 152.0),
+        ]
+
+        weeks = weekly_stats(values)
+
+        assert [w.week_start for w in weeks] == [date(2025, 6, 2), date(2025, 6, 9)]
+        assert weeks[0].count == 2
+        assert weeks[0].mean == pytest.approx(145.0)
+        assert weeks[0].best == 150.0
+
+    def test_trend_per_week(self):
+        """A steady gain should be reported per week with r near 1."""
+        start = datetime(2025, 6, 2)
+        values = [(start + timedelta(weeks=i), 140.0 + 0.5 * i) for i in range(10)]
+
+        trend = fit_trend(values)
+
+        assert trend.per_week == pytest.approx(0.5)
+        assert trend.r == pytest.approx(1.0)
+
+    def test_no_trend_from_one_moment(self):
+        """Shots all at one time have no trend."""
+        now = datetime(2025, 6, 2)
+        assert fit_trend([(now, 140.0), (now, 150.0)]) is None
+        assert fit_trend([(now, 140.0)]) is None
+
+    def test_sparkline(self):
+        """Lowest value should map to the lowest bar and highest to the highest."""
+        assert sparkline([1.0, 5.0, 9.0]) == "▁▅█"
+        assert sparkline([3.0, 3.0]) == "▅▅"
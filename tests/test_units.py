@@ -0,0 +1,97 @@
+"""Tests for units module."""
+
+import math
+
+import pytest
+
+from openflight.units import Degrees, MetersPerSecond, Mph, Rpm, radar_unit, speed_in, to_mph
+
+
+class TestQuantities:
+    """Tests for unit-typed quantities."""
+
+    def test_speed_conversions(self):
+        """mph and m/s should convert both ways."""
+        assert Mph(100).to_mps() == pytest.approx(44.704)
+        assert isinstance(Mph(100).to_mps(), MetersPerSecond)
+        assert MetersPerSecond(44.704).to_mph() == pytest.approx(100.0)
+
+    def test_angle_and_spin_conversions(self):
+        """Degrees and rpm should convert to radians and rad/s."""
+        assert Degrees(180).to_radians() == pytest.approx(math.pi)
+        assert Rpm(60).to_rad_per_sec() == pytest.approx(2 * math.pi)
+
+    def test_mixing_units_raises(self):
+        """Adding, comparing or relabelling different units should raise TypeError."""
+        with pytest.raises(TypeError):
+            Mph(100) + MetersPerSecond(10)  # pylint: disable=expression-not-assigned
+        with pytest.raises(TypeError):
+            Mph(100) < MetersPerSecond(50)  # pylint: disable=expression-not-assigned
+        with pytest.raises(TypeError):
+            Mph(MetersPerSecond(44.7))
+
+    def test_equality_across_units_raises(self):
+        """== and != between different units should raise, not compare the numbers."""
+        with pytest.raises(TypeError):
+            Mph(100) == MetersPerSecond(100)  # pylint: disable=expression-not-assigned
+        with pytest.raises(TypeError):
+            Mph(100) != MetersPerSecond(100)  # pylint: disable=expression-not-assigned
+        with pytest.raises(TypeError):
+            Degrees(10) == Rpm(10)  # pylint: disable=expression-not-assigned
+
+    def test_equality_same_unit_and_plain_numbers(self):
+        """Same-unit and plain-number equality work like floats, and hashing is unchanged."""
+        assert Mph(100) == Mph(100)
+        assert Mph(100) != Mph(101)
+        assert Mph(100) == 100 and 100 == Mph(100)
+        assert Mph(100) != "100"
+        assert hash(Mph(100)) == hash(100.0)
+        assert {Mph(100): "driver"}[Mph(100)] == "driver"
+
+    def test_arithmetic(self):
+        """Sums keep the unit; products and ratios are plain floats."""
+        total = Mph(100) + 5
+        assert isinstance(total, Mph) and total == 105
+        assert isinstance(sum([Mph(1), Mph(2)]), Mph)
+        assert type(Mph(150) / Mph(100)) is float  # pylint: disable=unidiomatic-typecheck
+        assert type(Mph(100) * 2.6) is float  # pylint: disable=unidiomatic-typecheck
+        assert max(Mph(1), Mph(2)) == 2
+
+    def test_floats_everywhere_else(self):
+        """Quantities should format and compare like floats."""
+        assert f"{Mph(150.25):.1f}" == "150.2"
+        assert Mph(150) == 150.0
+        assert str(Mph(104.0)) == "104.0"
+        assert f"{Mph(104.0) or 'N/A'}" == "104.0"
+        assert repr(Rpm(2500)) == "Rpm(2500.0)"
+
+
+class TestRadarSpeeds:
+    """Tests for typing radar speeds by unit name."""
+
+    def test_speed_in(self):
+        """Known unit names should give a quantity, others a plain float."""
+        assert isinstance(speed_in("mph", 10), Mph)
+        assert isinstance(speed_in("m/s", 10), MetersPerSecond)
+        assert type(speed_in("kph", 10)) is float  # pylint: disable=unidiomatic-typecheck
+
+    def test_to_mph(self):
+        """to_mph should convert by type or unit name, and refuse unknown units."""
+        assert to_mph(MetersPerSecond(44.704)) == pytest.approx(100.0)
+        assert to_mph(44.704, "m/s") == pytest.approx(100.0)
+        assert isinstance(to_mph(100.0), Mph)
+        with pytest.raises(ValueError):
+            to_mph(10.0, "knots")
+
+    def test_to_mph_untyped_units(self):
+        """Units without a quantity type should still convert to mph."""
+        assert to_mph(160.9344, "kph") == pytest.approx(100.0)
+        assert to_mph(146.667, "fps") == pytest.approx(100.0, abs=1e-3)
+        assert to_mph(4470.4, "cm/s") == pytest.approx(100.0)
+
+    def test_radar_unit(self):
+        """Radar spellings of units should map to unit names."""
+        assert radar_unit("km-per-hr") == "kph"
+        assert radar_unit("m-per-sec") == "m/s"
+        assert radar_unit(' "MPH" ') == "mph"
+        assert radar_unit("furlongs") is None
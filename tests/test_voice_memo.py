@@ -0,0 +1,79 @@
+"""Tests for voice memos."""
+
+import io
+import subprocess
+import threading
+from pathlib import Path
+
+import pytest
+
+from openflight.voice_memo import VoiceMemoRecorder, memo_path, watch_key
+
+
+class _FakeArecord:
+    """Stands in for subprocess.run, recording the arecord arguments."""
+
+    def __init__(self, returncode=0, release=None):
+        self.calls = []
+        self.returncode = returncode
+        self.release = release
+
+    def __call__(self, args, **kwargs):
+        self.calls.append(args)
+        if self.release:
+            self.release.wait(2.0)
+        return subprocess.CompletedProcess(args, self.returncode, stderr=b"no device")
+
+
+class TestVoiceMemoRecorder:
+    """Tests for recording memos."""
+
+    def test_memo_path(self):
+        """Memos should sit next to the session log, named for the shot."""
+        path = memo_path(Path("/logs/session_20250601_100000_range.jsonl"), 7)
+        assert path == Path("/logs/session_20250601_100000_range_shot007.wav")
+
+    def test_arecord_arguments(self, tmp_path):
+        """Recording should run arecord for the memo length on the chosen device."""
+        arecord = _FakeArecord()
+        recorder = VoiceMemoRecorder(duration_sec=4, device="plughw:1,0", run=arecord)
+
+        assert recorder.record(tmp_path / "memo.wav")
+
+        args = arecord.calls[0]
+        assert args[args.index("-D") + 1] == "plughw:1,0"
+        assert args[args.index("-d") + 1] == "4"
+        assert args[-1] == str(tmp_path / "memo.wav")
+
+    def test_failure_reported(self, tmp_path):
+        """A failing arecord (no microphone) should be reported, not raised."""
+        recorder = VoiceMemoRecorder(run=_FakeArecord(returncode=1))
+        assert not recorder.record(tmp_path / "memo.wav")
+
+    def test_one_at_a_time(self, tmp_path):
+        """A second memo while one is recording should be refused."""
+        release = threading.Event()
+        done = threading.Event()
+        recorder = VoiceMemoRecorder(run=_FakeArecord(release=release))
+
+        assert recorder.start(tmp_path / "a.wav", on_done=lambda ok: done.set())
+        assert not recorder.start(tmp_path / "b.wav")
+        release.set()
+        assert done.wait(2.0)
+
+    def test_invalid_length(self):
+        """A memo length of zero should be rejected."""
+        with pytest.raises(ValueError):
+            VoiceMemoRecorder(duration_sec=0, run=_FakeArecord())
+
+
+class TestWatchKey:
+    """Tests for the memo key."""
+
+    def test_each_line_triggers(self):
+        """Each Enter (line) should call the callback once."""
+        presses = []
+
+        watch_key(lambda: presses.append(1), io.StringIO("\n\n")).join(2.0)
+
+        assert len(presses) == 2
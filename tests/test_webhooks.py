@@ -0,0 +1,315 @@
+"""Tests for webhooks module."""
+
+import json
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import pytest
+
+from openflight.failover import FailoverGroup
+from openflight.webhooks import (
+    MERGED_ERROR,
+    SIGNATURE_HEADER,
+    DeliveryReceipt,
+    WebhookSink,
+    sign_payload,
+    verify_signature,
+)
+
+
+class _Receiver:
+    """Local HTTP endpoint that records requests and replies with scripted codes."""
+
+    def __init__(self, status_codes=None, response_body=b""):
+        self.requests = []
+        self.status_codes = list(status_codes or [])
+        self.response_body = response_body
+        receiver = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_POST(self):  # pylint: disable=invalid-name
+                length = int(self.headers.get("Content-Length", 0))
+                body = self.rfile.read(length)
+                receiver.requests.append((self.headers, body))
+                code = receiver.status_codes.pop(0) if receiver.status_codes else 200
+                self.send_response(code)
+                self.send_header("Content-Length", str(len(receiver.response_body)))
+                self.end_headers()
+                self.wfile.write(receiver.response_body)
+
+            def log_message(self, *args):
+                pass
+
+        self.server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self.server.server_address[1]}/hook"
+        self.thread = threading.Thread(target=self.server.serve_forever, daemon=True)
+        self.thread.start()
+
+    def close(self):
+        self.server.shutdown()
+        self.server.server_close()
+
+
+@pytest.fixture
+def receiver():
+    """Start a local webhook receiver."""
+    r = _Receiver()
+    yield r
+    r.close()
+
+
+class TestSignature:
+    """Tests for HMAC signing helpers."""
+
+    def test_sign_payload_format(self):
+        """Signature should be prefixed with the algorithm name."""
+        signature = sign_payload(b'{"a": 1}', "secret")
+        assert signature.startswith("sha256=")
+        assert len(signature) == len("sha256=") + 64
+
+    def test_verify_round_trip(self):
+        """A signature should verify against the same body and secret."""
+        body = b'{"ball_speed_mph": 150.0}'
+        assert verify_signature(body, "secret", sign_payload(body, "secret"))
+
+    def test_verify_rejects_tampered_body(self):
+        """A modified body should not verify."""
+        signature = sign_payload(b'{"ball_speed_mph": 150.0}', "secret")
+        assert not verify_signature(b'{"ball_speed_mph": 190.0}', "secret", signature)
+
+    def test_verify_rejects_wrong_secret(self):
+        """A different secret should not verify."""
+        body = b"{}"
+        assert not verify_signature(body, "other", sign_payload(body, "secret"))
+
+
+class TestWebhookSink:
+    """Tests for WebhookSink delivery."""
+
+    def test_deliver_posts_json(self, receiver):
+        """Delivered body should be the JSON payload."""
+        sink = WebhookSink([receiver.url])
+        body = json.dumps({"event": "shot", "shot": {"ball_speed_mph": 150.0}}).encode()
+
+        assert sink.deliver(receiver.url, body) is True
+
+        assert len(receiver.requests) == 1
+        headers, received = receiver.requests[0]
+        assert json.loads(received)["shot"]["ball_speed_mph"] == 150.0
+        assert headers["Content-Type"] == "application/json"
+        assert SIGNATURE_HEADER not in headers
+
+    def test_deliver_signs_when_secret_set(self, receiver):
+        """Signature header should verify against the received body."""
+        sink = WebhookSink([receiver.url], secret="s3cret")
+
+        assert sink.deliver(receiver.url, b'{"event": "shot"}') is True
+
+        headers, received = receiver.requests[0]
+        assert verify_signature(received, "s3cret", headers[SIGNATURE_HEADER])
+
+    def test_retries_server_errors(self, receiver):
+        """5xx responses should be retried until success."""
+        receiver.status_codes = [500, 503]
+        sink = WebhookSink([receiver.url], max_attempts=5, backoff_sec=0.01)
+
+        assert sink.deliver(receiver.url, b"{}") is True
+        assert len(receiver.requests) == 3
+
+    def test_gives_up_after_max_attempts(self, receiver):
+        """Persistent failures should stop after max_attempts."""
+        receiver.status_codes = [500] * 10
+        sink = WebhookSink([receiver.url], max_attempts=3, backoff_sec=0.01)
+
+        assert sink.deliver(receiver.url, b"{}") is False
+        assert len(receiver.requests) == 3
+
+    def test_client_errors_not_retried(self, receiver):
+        """4xx responses (other than 429) should not be retried."""
+        receiver.status_codes = [400]
+        sink = WebhookSink([receiver.url], max_attempts=5, backoff_sec=0.01)
+
+        assert sink.deliver(receiver.url, b"{}") is False
+        assert len(receiver.requests) == 1
+
+    def test_rate_limit_retried(self, receiver):
+        """429 responses should be retried."""
+        receiver.status_codes = [429]
+        sink = WebhookSink([receiver.url], max_attempts=3, backoff_sec=0.01)
+
+        assert sink.deliver(receiver.url, b"{}") is True
+        assert len(receiver.requests) == 2
+
+    def test_connection_refused_returns_false(self):
+        """Unreachable endpoints should fail without raising."""
+        sink = WebhookSink([], max_attempts=2, backoff_sec=0.01, timeout_sec=0.5)
+
+        assert sink.deliver("http://127.0.0.1:1/hook", b"{}") is False
+
+    def test_send_delivers_to_all_urls(self):
+        """Queued payloads should reach every configured URL."""
+        first, second = _Receiver(), _Receiver()
+        try:
+            sink = WebhookSink([first.url, second.url])
+            sink.start()
+            sink.send({"event": "shot", "shot": {"ball_speed_mph": 140.0}})
+            sink.stop()
+
+            assert len(first.requests) == 1
+            assert len(second.requests) == 1
+            headers, _ = first.requests[0]
+            assert headers["X-OpenFlight-Event"] == "shot"
+        finally:
+            first.close()
+            second.close()
+
+
+class TestDeliveryReceipts:
+    """Tests for delivery receipts and receiver acknowledgments."""
+
+    def test_receipt_parses_json_ack(self, receiver):
+        """A JSON response body should be kept as the acknowledgment."""
+        receiver.response_body = b'{"id": "shot-17", "ok": true}'
+        sink = WebhookSink([receiver.url])
+
+        receipt = sink.deliver_with_receipt(receiver.url, b"{}")
+
+        assert receipt.delivered is True
+        assert receipt.status_code == 200
+        assert receipt.attempts == 1
+        assert receipt.response == {"id": "shot-17", "ok": True}
+
+    def test_receipt_records_failure(self, receiver):
+        """A rejected payload should report the status and error."""
+        receiver.status_codes = [422]
+        receiver.response_body = b'{"error": "missing ball speed"}'
+        sink = WebhookSink([receiver.url])
+
+        receipt = sink.deliver_with_receipt(receiver.url, b"{}")
+
+        assert receipt.delivered is False
+        assert receipt.status_code == 422
+        assert receipt.error == "HTTP 422"
+        assert receipt.response == {"error": "missing ball speed"}
+
+    def test_send_reports_receipts(self, receiver):
+        """on_result should get one receipt per URL after delivery."""
+        sink = WebhookSink([receiver.url, "http://127.0.0.1:1/hook"], max_attempts=1)
+        results = []
+
+        sink.start()
+        sink.send({"event": "shot"}, on_result=results.append)
+        sink.stop()
+
+        assert len(results) == 1
+        assert [r.delivered for r in results[0]] == [True, False]
+        assert results[0][1].status_code is None
+
+    def test_state_keeps_last_delivery(self, receiver):
+        """to_dict should show the queue and the last delivery's receipts."""
+        sink = WebhookSink([receiver.url, "http://127.0.0.1:1/hook"], max_attempts=1)
+        assert sink.to_dict()["since_last_delivery_sec"] is None
+
+        sink.start()
+        sink.send({"event": "shot"})
+        sink.stop()
+
+        state = sink.to_dict()
+        assert state["running"] is False
+        assert state["queued"] == 0
+        assert state["since_last_delivery_sec"] >= 0
+        assert [r["delivered"] for r in state["last_delivery"]] == [True, False]
+
+
+class TestThrottling:
+    """Tests for spacing out deliveries of rapid-fire shots."""
+
+    @staticmethod
+    def _send(sink, results, *numbers):
+        for n in numbers:
+            sink.send({"event": "shot", "n": n}, on_result=lambda r, n=n: results.update({n: r}))
+
+    def test_queue_spaces_deliveries(self):
+        """With the queue policy every shot should arrive, min_interval_sec apart."""
+        times = []
+
+        def deliver(url, body, event="shot"):
+            times.append(time.monotonic())
+            return DeliveryReceipt(url=url, delivered=True)
+
+        sink = WebhookSink(["http://sim/hook"], min_interval_sec=0.1)
+        sink.deliver_with_receipt = deliver
+        results = {}
+
+        sink.start()
+        self._send(sink, results, 1, 2, 3)
+        sink.stop()
+
+        assert sorted(results) == [1, 2, 3]
+        assert times[1] - times[0] >= 0.09
+        assert times[2] - times[1] >= 0.09
+
+    def test_merge_sends_latest(self, receiver):
+        """With the merge policy, shots waiting out the interval give way to the latest."""
+        sink = WebhookSink([receiver.url], min_interval_sec=0.2, throttle_policy="merge")
+        results = {}
+
+        sink.start()
+        self._send(sink, results, 1)
+        time.sleep(0.1)  # Shot 1 delivered; the next ones wait out the interval
+        self._send(sink, results, 2, 3, 4)
+        sink.stop()
+
+        assert [json.loads(body)["n"] for _, body in receiver.requests] == [1, 4]
+        assert results[2][0].error == MERGED_ERROR
+        assert results[3][0].error == MERGED_ERROR
+        assert results[4][0].delivered
+
+    def test_invalid_settings(self):
+        """A negative interval or unknown policy should be refused."""
+        with pytest.raises(ValueError):
+            WebhookSink([], min_interval_sec=-1)
+        with pytest.raises(ValueError, match="throttle policy"):
+            WebhookSink([], throttle_policy="drop")
+
+
+class TestSinkFailover:
+    """Tests for delivering through a failover group."""
+
+    def test_dead_primary_delivered_to_standby(self):
+        """A shot the primary doesn't take goes to the standby, reported as delivered."""
+        standby = _Receiver()
+        try:
+            group = FailoverGroup(["http://127.0.0.1:1/hook", standby.url], health_interval_sec=0)
+            sink = WebhookSink([], backoff_sec=0.01, timeout_sec=0.5, failover=group)
+            results = []
+
+            sink.start()
+            sink.send({"event": "shot"}, on_result=results.append)
+            sink.send({"event": "shot"}, on_result=results.append)
+            sink.stop()
+
+            assert len(standby.requests) == 2
+            (first,), (second,) = results
+            assert first.delivered and first.url == standby.url
+            assert second.attempts == 1
+            assert group.active == standby.url
+        finally:
+            standby.close()
+
+    def test_rejected_by_all(self):
+        primary, standby = _Receiver(status_codes=[500]), _Receiver(status_codes=[500])
+        try:
+            group = FailoverGroup([primary.url, standby.url], health_interval_sec=0)
+            sink = WebhookSink([], failover=group)
+
+            receipt = sink.deliver_failover(b"{}")
+
+            assert not receipt.delivered
+            assert receipt.url == standby.url
+            assert len(primary.requests) == len(standby.requests) == 1
+            assert group.active is None
+        finally:
+            primary.close()
+            standby.close()
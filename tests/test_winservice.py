@@ -0,0 +1,152 @@
+"""Tests for the Windows service wrapper."""
+
+import io
+import subprocess
+import sys
+
+from openflight.winservice import ServerProcess, server_command, watch_commands
+
+
+class _Pipe(io.StringIO):
+    """stdin pipe that keeps what was written after it's closed."""
+
+    def close(self):
+        pass
+
+
+class _FakeServer:
+    """Server child process that records its stdin and exits when told to stop."""
+
+    def __init__(self, command, output="", ignore_stop=False, **kwargs):
+        self.command = command
+        self.kwargs = kwargs
+        self.stdin = _Pipe()
+        self.stdout = io.StringIO(output)
+        self.ignore_stop = ignore_stop
+        self.returncode = None
+        self.killed = False
+
+    def poll(self):
+        if self.returncode is None and not self.ignore_stop and "stop\n" in self.stdin.getvalue():
+            self.returncode = 0
+        return self.returncode
+
+    def wait(self, timeout=None):
+        if self.poll() is None:
+            if not self.killed:
+                raise subprocess.TimeoutExpired("server", timeout)
+            self.returncode = 1
+        return self.returncode
+
+    def kill(self):
+        self.killed = True
+
+
+def _server_process(**fake_kwargs):
+    """ServerProcess over a fake child, collecting its log lines."""
+    lines = []
+    children = []
+
+    def popen(command, **kwargs):
+        child = _FakeServer(command, **fake_kwargs, **kwargs)
+        children.append(child)
+        return child
+
+    process = ServerProcess(["openflight-server"], log=lines.append, popen=popen)
+    return process, children, lines
+
+
+def test_server_command_runs_server_in_service_mode():
+    """The service should run openflight-server --service with the installed args."""
+    command = server_command(["--port", "COM3"], python="C:/of/python.exe")
+
+    assert command == ["C:/of/python.exe", "-m", "openflight.server", "--service", "--port", "COM3"]
+    assert server_command([])[0] == sys.executable
+
+
+def test_watch_commands_dispatches_lines():
+    """Each command line should run its handler, ignoring case, blanks and unknown commands."""
+    calls = []
+    handlers = {name: (lambda name=name: calls.append(name)) for name in ("stop", "pause")}
+
+    watch_commands(handlers, io.StringIO("PAUSE\n\nbogus\nstop\n")).join(timeout=2)
+
+    assert calls == ["pause", "stop", "stop"]
+
+
+def test_watch_commands_stops_at_end_of_input():
+    """Losing stdin (the service is gone) should stop the server."""
+    calls = []
+
+    watch_commands({"stop": lambda: calls.append("stop")}, io.StringIO("")).join(timeout=2)
+
+    assert calls == ["stop"]
+
+
+def test_watch_commands_survives_failing_handler():
+    """A handler raising shouldn't end the command loop."""
+    calls = []
+
+    def fail():
+        raise RuntimeError("radar unplugged")
+
+    handlers = {"continue": fail, "stop": lambda: calls.append("stop")}
+    watch_commands(handlers, io.StringIO("continue\nstop\n")).join(timeout=2)
+
+    assert calls == ["stop", "stop"]
+
+
+def test_output_forwarded_to_log():
+    """The server's output lines should reach the log, without blank lines."""
+    process, children, lines = _server_process(output="Server starting\n\n[SHOT] 150 mph\n")
+
+    process.start()
+    process.stop()
+
+    assert children[0].kwargs["stdin"] == subprocess.PIPE
+    assert children[0].kwargs["stderr"] == subprocess.STDOUT
+    assert lines == ["Server starting", "[SHOT] 150 mph"]
+
+
+def test_send_writes_command_line():
+    """Commands should go to the server's stdin one per line."""
+    process, children, _ = _server_process()
+
+    process.start()
+    assert process.send("pause")
+    assert process.send("continue")
+
+    assert children[0].stdin.getvalue() == "pause\ncontinue\n"
+
+
+def test_send_before_start_fails():
+    """There's nothing to send to before the server starts."""
+    process, _, _ = _server_process()
+
+    assert not process.send("pause")
+    assert process.stop() is None
+
+
+def test_stop_is_graceful():
+    """Stop should ask the server to stop and return its exit code without killing it."""
+    process, children, _ = _server_process()
+
+    process.start()
+    code = process.stop()
+
+    assert code == 0
+    assert not children[0].killed
+    assert not process.running
+
+
+def test_stop_kills_hung_server():
+    """A server that doesn't stop in time should be killed."""
+    process, children, lines = _server_process(ignore_stop=True)
+    process.STOP_TIMEOUT_SEC = 0
+
+    process.start()
+    code = process.stop()
+
+    assert children[0].killed
+    assert code == 1
+    assert "Server did not stop in time - killing it" in lines
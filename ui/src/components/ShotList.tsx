@@ -16,7 +16,15 @@ interface ShotRowProps {
 const ShotRow = memo(function ShotRow({ shot, shotNumber }: ShotRowProps) {
   return (
     <div className="shot-row">
-      <span className="shot-row__number">#{shotNumber}</span>
+      <span className="shot-row__number">
+        #{shotNumber}
+        {shot.delivery_status && shot.delivery_status !== 'delivered' && (
+          <span
+            className={`shot-row__delivery shot-row__delivery--${shot.delivery_status}`}
+            title={shot.delivery_status === 'failed' ? 'Not delivered to sim' : 'Sending to sim'}
+          />
+        )}
+      </span>
       <span className="shot-row__club">{shot.club}</span>
       <span className="shot-row__stat">
         <span className="shot-row__value">{shot.ball_speed_mph.toFixed(1)}</span>
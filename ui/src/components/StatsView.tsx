@@ -87,6 +87,16 @@ export function StatsView({ shots, onClearSession }: StatsViewProps) {
             <span className="stat-card__label">Avg Smash</span>
           </div>
         )}
+        {stats.avg_tempo_ratio && (
+          <div className="stat-card">
+            <span className="stat-card__value">{stats.avg_tempo_ratio.toFixed(1)}:1</span>
+            <span className="stat-card__label">
+              Avg Tempo
+              {stats.tempo_on_target_pct != null &&
+                ` (${stats.tempo_on_target_pct.toFixed(0)}% on target)`}
+            </span>
+          </div>
+        )}
       </div>
 
       {/* Clear Button */}
@@ -48,19 +48,21 @@ export interface DebugShotLog {
 
 export function useSocket() {
   const socketRef = useRef<Socket | null>(null);
-  const { addShot, setShots, clearShots } = useShotContext();
+  const { addShot, setShots, clearShots, setDeliveryStatus } = useShotContext();
 
   // Keep stable refs so socket event handlers always see the latest callbacks
   // without needing to re-register listeners when they change.
   const addShotRef = useRef(addShot);
   const setShotsRef = useRef(setShots);
   const clearShotsRef = useRef(clearShots);
+  const setDeliveryStatusRef = useRef(setDeliveryStatus);
 
   useEffect(() => {
     addShotRef.current = addShot;
     setShotsRef.current = setShots;
     clearShotsRef.current = clearShots;
-  }, [addShot, setShots, clearShots]);
+    setDeliveryStatusRef.current = setDeliveryStatus;
+  }, [addShot, setShots, clearShots, setDeliveryStatus]);
 
   const [connected, setConnected] = useState(false);
   const [mockMode, setMockMode] = useState(false);
@@ -94,8 +96,18 @@ export function useSocket() {
   });
 
   useEffect(() => {
+    // Live reading rate for this client (?reading_hz=N on the page URL, 0 = every reading)
+    const params = new URLSearchParams(window.location.search);
+    const readingHz = params.get('reading_hz');
+    // API token for servers started with --api-token (?token=... once, then remembered)
+    if (params.get('token')) {
+      localStorage.setItem('openflight.token', params.get('token') as string);
+    }
+    const token = localStorage.getItem('openflight.token');
     const newSocket = io(SOCKET_URL, {
       transports: ['websocket', 'polling'],
+      query: readingHz !== null ? { reading_hz: readingHz } : undefined,
+      auth: token ? { token } : undefined,
     });
 
     newSocket.on('connect', () => {
@@ -114,6 +126,13 @@ export function useSocket() {
       addShotRef.current(data.shot);
     });
 
+    newSocket.on(
+      'shot_delivery',
+      (data: { timestamp: string; status: 'delivered' | 'failed' }) => {
+        setDeliveryStatusRef.current(data.timestamp, data.status);
+      }
+    );
+
     newSocket.on(
       'session_state',
       (
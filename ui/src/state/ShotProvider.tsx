@@ -34,6 +34,16 @@ export function ShotProvider({ children }: { children: ReactNode }) {
     // Session restore — don't trigger animations
   }, []);
 
+  const setDeliveryStatus = useCallback(
+    (timestamp: string, status: NonNullable<Shot['delivery_status']>) => {
+      const update = (shot: Shot) =>
+        shot.timestamp === timestamp ? { ...shot, delivery_status: status } : shot;
+      setShotsState((prev) => prev.map(update));
+      setLatestShot((prev) => (prev ? update(prev) : prev));
+    },
+    []
+  );
+
   const clearShots = useCallback(() => {
     setLatestShot(null);
     setShotsState([]);
@@ -51,6 +61,7 @@ export function ShotProvider({ children }: { children: ReactNode }) {
         addShot,
         setShots,
         clearShots,
+        setDeliveryStatus,
       }}
     >
       {children}
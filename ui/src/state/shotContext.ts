@@ -10,6 +10,8 @@ export interface ShotContextValue {
   addShot: (shot: Shot) => void;
   setShots: (shots: Shot[]) => void;
   clearShots: () => void;
+  /** Apply a webhook delivery result to the shot with this timestamp */
+  setDeliveryStatus: (timestamp: string, status: NonNullable<Shot['delivery_status']>) => void;
 }
 
 export const ShotContext = createContext<ShotContextValue | null>(null);
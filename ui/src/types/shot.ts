@@ -16,6 +16,19 @@ export interface Shot {
   spin_confidence: number | null;
   spin_quality: 'high' | 'medium' | 'low' | null;
   carry_spin_adjusted: number | null;
+  // Measured speeds before cosine correction (null when radar is on the target line)
+  ball_speed_raw_mph?: number | null;
+  club_speed_raw_mph?: number | null;
+  // Sim round context (null unless the sim reported a hole)
+  hole_number?: number | null;
+  hole_shot?: number | null;
+  // Webhook delivery (null when no webhooks are configured)
+  delivery_status?: 'pending' | 'delivered' | 'failed' | null;
+  // Swing tempo (null unless the server runs with --tempo)
+  tempo_ratio?: number | null;
+  tempo_backswing_sec?: number | null;
+  tempo_downswing_sec?: number | null;
+  tempo_on_target?: boolean | null;
 }
 
 export interface SessionStats {
@@ -30,6 +43,9 @@ export interface SessionStats {
   // Rolling buffer mode spin stats
   avg_spin_rpm?: number | null;
   spin_detection_rate?: number;
+  // Swing tempo stats (with --tempo)
+  avg_tempo_ratio?: number | null;
+  tempo_on_target_pct?: number | null;
   mode?: 'streaming' | 'rolling-buffer';
 }
 
@@ -91,6 +107,12 @@ export function computeStats(shots: Shot[]): SessionStats {
   const clubSpeeds = shots.map((s) => s.club_speed_mph).filter((v): v is number => v !== null);
   const smashFactors = shots.map((s) => s.smash_factor).filter((v): v is number => v !== null);
   const carries = shots.map((s) => s.estimated_carry_yards);
+  const tempoRatios = shots
+    .map((s) => s.tempo_ratio)
+    .filter((v): v is number => v !== null && v !== undefined);
+  const onTarget = shots
+    .map((s) => s.tempo_on_target)
+    .filter((v): v is boolean => v !== null && v !== undefined);
 
   const mean = (arr: number[]) => arr.reduce((a, b) => a + b, 0) / arr.length;
   const stdDev = (arr: number[]) => {
@@ -108,6 +130,9 @@ export function computeStats(shots: Shot[]): SessionStats {
     avg_club_speed: clubSpeeds.length > 0 ? mean(clubSpeeds) : null,
     avg_smash_factor: smashFactors.length > 0 ? mean(smashFactors) : null,
     avg_carry_est: mean(carries),
+    avg_tempo_ratio: tempoRatios.length > 0 ? mean(tempoRatios) : null,
+    tempo_on_target_pct:
+      onTarget.length > 0 ? (100 * onTarget.filter(Boolean).length) / onTarget.length : null,
   };
 }
 